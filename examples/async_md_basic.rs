@@ -58,9 +58,13 @@ async fn main() -> CtpResult<()> {
 
     // 创建登录请求
     info!("🔐 创建登录请求...");
-    let login_req =
-        ReqUserLoginField::new(&config.broker_id, &config.investor_id, &config.password)?
-            .with_product_info("AsyncRustCTP")?;
+    let credentials = config.credentials()?;
+    let login_req = ReqUserLoginField::new(
+        &config.broker_id,
+        &config.investor_id,
+        credentials.password(),
+    )?
+    .with_product_info("AsyncRustCTP")?;
 
     // 异步登录
     info!("📤 发送登录请求...");
@@ -61,10 +61,11 @@ async fn main() -> CtpResult<()> {
     }
 
     // 创建登录请求
+    let credentials = config.credentials()?;
     let login_req = ReqUserLoginField::new(
         &config.broker_id,
         &config.investor_id,
-        &config.password,
+        credentials.password(),
     )?
     .with_product_info("AsyncRustCTP")?;
 
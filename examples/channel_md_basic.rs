@@ -0,0 +1,72 @@
+//! 通道行情API示例
+//!
+//! 展示如何在不使用tokio的情况下，通过crossbeam通道接收行情和事件
+//!
+//! 运行：`cargo run --example channel_md_basic --features channel`
+
+use crossbeam_channel::{select, tick};
+use ctp_rust::api::async_md_api::AsyncMdEvent;
+use ctp_rust::api::channel::{BackpressurePolicy, ChannelMdApi, ChannelOptions};
+use ctp_rust::error::{CtpError, CtpResult};
+use ctp_rust::types::ReqUserLoginField;
+use ctp_rust::CtpConfig;
+use std::time::Duration;
+use tracing::{info, warn};
+
+fn main() -> CtpResult<()> {
+    tracing_subscriber::fmt()
+        .with_max_level(tracing::Level::INFO)
+        .init();
+
+    let config = CtpConfig::from_env()
+        .map_err(|e| CtpError::InitializationError(format!("配置加载失败: {}", e)))?;
+
+    // 行情洪峰时丢弃最旧的行情，保证消费到的总是最新数据
+    let options = ChannelOptions::default()
+        .with_market_data_capacity(8192)
+        .with_market_data_policy(BackpressurePolicy::DropOldest);
+    let api = ChannelMdApi::new(Some(&config.flow_path), false, false, None, options)?;
+    api.register_front(&config.md_front_address)?;
+    api.init()?;
+    api.wait_connected(30)?;
+    info!("连接成功");
+
+    let credentials = config.credentials()?;
+    let login_req = ReqUserLoginField::new(
+        &config.broker_id,
+        &config.investor_id,
+        credentials.password(),
+    )?;
+    let login_info = api.login(&login_req, 10)?;
+    info!("登录成功, 会话编号: {}", login_info.session_id);
+
+    let instruments: Vec<&str> = config.instruments.iter().map(|s| s.as_str()).collect();
+    api.subscribe_market_data(&instruments)?;
+
+    let report = tick(Duration::from_secs(10));
+    let mut received = 0u64;
+    loop {
+        select! {
+            recv(api.market_data()) -> tick => {
+                let Ok(market_data) = tick else { break };
+                received += 1;
+                if let Ok(instrument_id) = market_data.get_instrument_id() {
+                    info!("{} 最新价: {:.2}", instrument_id, market_data.last_price);
+                }
+            }
+            recv(api.events()) -> event => match event {
+                Ok(AsyncMdEvent::Disconnected(reason)) => warn!("连接断开: {}", reason),
+                Ok(event) => info!("事件: {:?}", event),
+                Err(_) => break,
+            },
+            recv(report) -> _ => {
+                info!(
+                    "已接收行情 {} 条, 丢弃 {} 条",
+                    received,
+                    api.dropped_market_data()
+                );
+            }
+        }
+    }
+    Ok(())
+}
@@ -91,7 +91,7 @@ fn demo_fixed_length_types() -> CtpResult<()> {
     let converted_broker = broker_id.to_utf8_string()?;
     println!("经纪公司代码:");
     println!("  原始: '{}'", broker_id_str);
-    println!("  字节数组: {:?}", &broker_id[..8]); // 只显示前8个字节
+    println!("  字节数组: {:?}", &broker_id.as_raw()[..8]); // 只显示前8个字节
     println!("  转换回: '{}'", converted_broker.trim_end_matches('\0'));
     println!("  ✓ 转换成功");
     println!();
@@ -102,7 +102,7 @@ fn demo_fixed_length_types() -> CtpResult<()> {
     let converted_user = user_id.to_utf8_string()?;
     println!("用户代码:");
     println!("  原始: '{}'", user_id_str);
-    println!("  字节数组: {:?}", &user_id[..12]); // 只显示前12个字节
+    println!("  字节数组: {:?}", &user_id.as_raw()[..12]); // 只显示前12个字节
     println!("  转换回: '{}'", converted_user.trim_end_matches('\0'));
     println!("  ✓ 转换成功");
     println!();
@@ -113,7 +113,7 @@ fn demo_fixed_length_types() -> CtpResult<()> {
     let converted_instrument = instrument_id.to_utf8_string()?;
     println!("合约代码:");
     println!("  原始: '{}'", instrument_id_str);
-    println!("  字节数组: {:?}", &instrument_id[..8]);
+    println!("  字节数组: {:?}", &instrument_id.as_raw()[..8]);
     println!(
         "  转换回: '{}'",
         converted_instrument.trim_end_matches('\0')
@@ -126,9 +126,12 @@ fn demo_fixed_length_types() -> CtpResult<()> {
     let password = PasswordType::from_utf8_string(password_str)?;
     let converted_password = password.to_utf8_string()?;
     println!("密码:");
-    println!("  原始: '{}'", password_str);
-    println!("  转换回: '{}'", converted_password.trim_end_matches('\0'));
-    println!("  ✓ 转换成功");
+    // 密码类型的Display/Debug输出会隐去内容
+    println!("  显示为: '{}'", password);
+    println!("  字节数: {}", password.len());
+    if converted_password == password_str {
+        println!("  ✓ 转换成功");
+    }
     println!();
 
     // 产品信息 (11字符)
@@ -165,14 +168,12 @@ fn demo_struct_conversion() -> CtpResult<()> {
     let user_id = login_req.user_id.to_utf8_string()?;
     println!("  用户代码: '{}'", user_id.trim_end_matches('\0'));
 
-    let password = login_req.password.to_utf8_string()?;
-    println!("  密码: '{}'", password.trim_end_matches('\0'));
+    println!("  密码: '{}'", login_req.password);
 
     let product_info = login_req.user_product_info.to_utf8_string()?;
     println!("  产品信息: '{}'", product_info.trim_end_matches('\0'));
 
-    let auth_code = login_req.one_time_password.to_utf8_string()?;
-    println!("  认证码: '{}'", auth_code.trim_end_matches('\0'));
+    println!("  认证码: '{}'", login_req.one_time_password);
 
     let mac_address = login_req.mac_address.to_utf8_string()?;
     println!("  MAC地址: '{}'", mac_address.trim_end_matches('\0'));
@@ -3,7 +3,7 @@
 //! 展示CTP Rust SDK中的错误处理机制和最佳实践
 
 use ctp_rust::encoding::GbkConverter;
-use ctp_rust::error::{CtpError, CtpResult};
+use ctp_rust::error::{CtpError, CtpErrorCode, CtpResult};
 use ctp_rust::types::{BrokerIdType, ReqUserLoginField, RspInfoField, StringConvert};
 use tracing::info;
 use tracing_subscriber;
@@ -312,36 +312,20 @@ fn demo_response_errors() -> CtpResult<()> {
 
     // 常见CTP错误代码处理
     println!("✓ 常见CTP错误代码:");
-    let common_errors = vec![
-        (-1, "用户名不存在"),
-        (-2, "用户密码错误"),
-        (-3, "用户已经登录"),
-        (-4, "用户不在线"),
-        (-5, "重复登录"),
-        (-6, "登录失败"),
-        (-7, "未授权的IP地址"),
-        (-8, "MAC地址不匹配"),
-        (-9, "认证失败"),
-        (-10, "版本不匹配"),
-    ];
-
-    for (error_code, error_desc) in common_errors {
-        let mut rsp = RspInfoField {
-            error_id: error_code,
-            error_msg: [0; 81],
-        };
-
-        if let Ok(error_bytes) = GbkConverter::utf8_to_fixed_bytes::<81>(error_desc) {
-            rsp.error_msg = error_bytes;
-        }
-
-        if !rsp.is_success() {
-            match rsp.get_error_msg() {
-                Ok(msg) => println!("  错误 [{}]: {}", error_code, msg.trim_end_matches('\0')),
-                Err(e) => println!("  错误 [{}]: 获取消息失败 - {}", error_code, e),
-            }
-        }
+    for error_id in [3, 22, 31, 50, 90] {
+        let code = CtpErrorCode::from_code(error_id);
+        println!(
+            "  错误 {} ({}) 类别: {:?} 可重试: {}",
+            code,
+            code.message_en(),
+            code.category(),
+            code.is_retryable()
+        );
     }
+
+    // 请求函数的负数返回值
+    let error = CtpError::request_failed("报单录入请求失败", -3);
+    println!("  {} 可重试: {}", error, error.is_retryable());
     println!();
 
     Ok(())
@@ -0,0 +1,46 @@
+//! 审计日志查询示例
+//!
+//! 按报单引用、合约或接口名称输出审计日志中校验通过的记录，并列出校验失败的文件
+//!
+//! 运行：`cargo run --example journal_query -- <日志目录> [--order-ref 引用] [--instrument 合约] [--name 接口]`
+
+use ctp_rust::error::{CtpError, CtpResult};
+use ctp_rust::journal::{JournalQuery, JournalReader};
+use std::env;
+
+fn main() -> CtpResult<()> {
+    let mut args = env::args().skip(1);
+    let dir = args.next().ok_or_else(|| {
+        CtpError::InvalidParameterError(
+            "用法: journal_query <日志目录> [--order-ref 引用] [--instrument 合约] [--name 接口]"
+                .to_string(),
+        )
+    })?;
+
+    let mut query = JournalQuery::default();
+    while let Some(flag) = args.next() {
+        let value = args
+            .next()
+            .ok_or_else(|| CtpError::InvalidParameterError(format!("{} 缺少参数值", flag)))?;
+        query = match flag.as_str() {
+            "--order-ref" => query.with_order_ref(&value),
+            "--instrument" => query.with_instrument(&value),
+            "--name" => query.with_name(&value),
+            _ => {
+                return Err(CtpError::InvalidParameterError(format!(
+                    "未知参数: {}",
+                    flag
+                )))
+            }
+        };
+    }
+
+    let result = JournalReader::new(&dir).query(&query)?;
+    for entry in &result.entries {
+        println!("{}", entry);
+    }
+    for (path, error) in &result.corrupt {
+        eprintln!("校验失败 {}: {}", path.display(), error);
+    }
+    Ok(())
+}
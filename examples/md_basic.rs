@@ -102,8 +102,8 @@ impl MdSpiHandler for MarketDataHandler {
                             .send(CtpEvent::LoginSuccess(RspUserLoginField {
                                 trading_day: [0; 9],
                                 login_time: [0; 9],
-                                broker_id: [0; 11],
-                                user_id: [0; 16],
+                                broker_id: Default::default(),
+                                user_id: Default::default(),
                                 system_name: [0; 41],
                                 front_id: 0,
                                 session_id: 0,
@@ -393,10 +393,11 @@ fn main() -> CtpResult<()> {
                         thread::sleep(Duration::from_millis(1000)); // 稍微等待
 
                         let login_result = (|| -> Result<(), Box<dyn std::error::Error>> {
+                            let credentials = config.credentials()?;
                             let login_req = ReqUserLoginField::new(
                                 config.broker_id.as_str(),
                                 config.investor_id.as_str(),
-                                config.password.as_str(),
+                                credentials.password(),
                             )?
                             .with_product_info("RustCTP")?;
 
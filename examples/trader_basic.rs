@@ -103,13 +103,13 @@ impl TraderSpiHandler for TraderHandler {
                 info!("📋 登录参数:");
                 info!("   • 经纪公司: {}", config.broker_id);
                 info!("   • 投资者账号: {}", config.investor_id);
-                info!("   • 密码长度: {} 字符", config.password);
 
                 let login_result = (|| -> Result<(), Box<dyn std::error::Error>> {
+                    let credentials = config.credentials()?;
                     let login_req = ReqUserLoginField::new(
                         config.broker_id.as_str(),
                         config.investor_id.as_str(),
-                        config.password.as_str(),
+                        credentials.password(),
                     )?
                     .with_product_info("RustCTP")?;
 
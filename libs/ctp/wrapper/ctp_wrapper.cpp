@@ -730,6 +730,81 @@ int CThostFtdcTraderApi_ReqQrySettlementInfo(void *api,
   return -1;
 }
 
+int CThostFtdcTraderApi_ReqQryTransferBank(void *api, void *pQryTransferBank,
+                                            int nRequestID) {
+  if (api) {
+    return static_cast<CThostFtdcTraderApi *>(api)->ReqQryTransferBank(
+        static_cast<CThostFtdcQryTransferBankField *>(pQryTransferBank),
+        nRequestID);
+  }
+  return -1;
+}
+
+// 银期转账接口
+
+int CThostFtdcTraderApi_ReqFromBankToFutureByFuture(void *api,
+                                                    void *pReqTransfer,
+                                                    int nRequestID) {
+  if (api) {
+    return static_cast<CThostFtdcTraderApi *>(api)->ReqFromBankToFutureByFuture(
+        static_cast<CThostFtdcReqTransferField *>(pReqTransfer), nRequestID);
+  }
+  return -1;
+}
+
+int CThostFtdcTraderApi_ReqFromFutureToBankByFuture(void *api,
+                                                    void *pReqTransfer,
+                                                    int nRequestID) {
+  if (api) {
+    return static_cast<CThostFtdcTraderApi *>(api)->ReqFromFutureToBankByFuture(
+        static_cast<CThostFtdcReqTransferField *>(pReqTransfer), nRequestID);
+  }
+  return -1;
+}
+
+int CThostFtdcTraderApi_ReqQueryBankAccountMoneyByFuture(
+    void *api, void *pReqQueryAccount, int nRequestID) {
+  if (api) {
+    return static_cast<CThostFtdcTraderApi *>(api)
+        ->ReqQueryBankAccountMoneyByFuture(
+            static_cast<CThostFtdcReqQueryAccountField *>(pReqQueryAccount),
+            nRequestID);
+  }
+  return -1;
+}
+
+int CThostFtdcTraderApi_ReqQryTransferSerial(void *api,
+                                             void *pQryTransferSerial,
+                                             int nRequestID) {
+  if (api) {
+    return static_cast<CThostFtdcTraderApi *>(api)->ReqQryTransferSerial(
+        static_cast<CThostFtdcQryTransferSerialField *>(pQryTransferSerial),
+        nRequestID);
+  }
+  return -1;
+}
+
+int CThostFtdcTraderApi_ReqQryContractBank(void *api, void *pQryContractBank,
+                                           int nRequestID) {
+  if (api) {
+    return static_cast<CThostFtdcTraderApi *>(api)->ReqQryContractBank(
+        static_cast<CThostFtdcQryContractBankField *>(pQryContractBank),
+        nRequestID);
+  }
+  return -1;
+}
+
+int CThostFtdcTraderApi_ReqQryAccountregister(void *api,
+                                              void *pQryAccountregister,
+                                              int nRequestID) {
+  if (api) {
+    return static_cast<CThostFtdcTraderApi *>(api)->ReqQryAccountregister(
+        static_cast<CThostFtdcQryAccountregisterField *>(pQryAccountregister),
+        nRequestID);
+  }
+  return -1;
+}
+
 const char *CThostFtdcTraderApi_GetApiVersion() {
   return CThostFtdcTraderApi::GetApiVersion();
 }
@@ -162,6 +162,26 @@ int CThostFtdcTraderApi_ReqQryDepthMarketData(void *api,
 int CThostFtdcTraderApi_ReqQrySettlementInfo(void *api,
                                              void *pQrySettlementInfo,
                                              int nRequestID);
+int CThostFtdcTraderApi_ReqQryTransferBank(void *api, void *pQryTransferBank,
+                                            int nRequestID);
+
+// 银期转账接口
+int CThostFtdcTraderApi_ReqFromBankToFutureByFuture(void *api,
+                                                    void *pReqTransfer,
+                                                    int nRequestID);
+int CThostFtdcTraderApi_ReqFromFutureToBankByFuture(void *api,
+                                                    void *pReqTransfer,
+                                                    int nRequestID);
+int CThostFtdcTraderApi_ReqQueryBankAccountMoneyByFuture(
+    void *api, void *pReqQueryAccount, int nRequestID);
+int CThostFtdcTraderApi_ReqQryTransferSerial(void *api,
+                                             void *pQryTransferSerial,
+                                             int nRequestID);
+int CThostFtdcTraderApi_ReqQryContractBank(void *api, void *pQryContractBank,
+                                           int nRequestID);
+int CThostFtdcTraderApi_ReqQryAccountregister(void *api,
+                                              void *pQryAccountregister,
+                                              int nRequestID);
 const char *CThostFtdcTraderApi_GetApiVersion();
 
 // Debug logging functions
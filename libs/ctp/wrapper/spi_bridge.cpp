@@ -569,6 +569,208 @@ public:
                                   nRequestID, bIsLast ? 1 : 0);
     }
   }
+
+  // 银期转账回调方法
+
+  // 期货发起银行资金转期货应答
+  virtual void
+  OnRspFromBankToFutureByFuture(CThostFtdcReqTransferField *pReqTransfer,
+                                CThostFtdcRspInfoField *pRspInfo,
+                                int nRequestID, bool bIsLast) override {
+    if (callbacks.on_rsp_from_bank_to_future_by_future) {
+      callbacks.on_rsp_from_bank_to_future_by_future(
+          callbacks.user_data, pReqTransfer, pRspInfo, nRequestID,
+          bIsLast ? 1 : 0);
+    }
+  }
+
+  // 期货发起期货资金转银行应答
+  virtual void
+  OnRspFromFutureToBankByFuture(CThostFtdcReqTransferField *pReqTransfer,
+                                CThostFtdcRspInfoField *pRspInfo,
+                                int nRequestID, bool bIsLast) override {
+    if (callbacks.on_rsp_from_future_to_bank_by_future) {
+      callbacks.on_rsp_from_future_to_bank_by_future(
+          callbacks.user_data, pReqTransfer, pRspInfo, nRequestID,
+          bIsLast ? 1 : 0);
+    }
+  }
+
+  // 期货发起查询银行余额应答
+  virtual void OnRspQueryBankAccountMoneyByFuture(
+      CThostFtdcReqQueryAccountField *pReqQueryAccount,
+      CThostFtdcRspInfoField *pRspInfo, int nRequestID, bool bIsLast) override {
+    if (callbacks.on_rsp_query_bank_account_money_by_future) {
+      callbacks.on_rsp_query_bank_account_money_by_future(
+          callbacks.user_data, pReqQueryAccount, pRspInfo, nRequestID,
+          bIsLast ? 1 : 0);
+    }
+  }
+
+  // 期货发起银行资金转期货通知
+  virtual void
+  OnRtnFromBankToFutureByFuture(CThostFtdcRspTransferField *pRspTransfer) override {
+    if (callbacks.on_rtn_from_bank_to_future_by_future) {
+      callbacks.on_rtn_from_bank_to_future_by_future(callbacks.user_data,
+                                                     pRspTransfer);
+    }
+  }
+
+  // 期货发起期货资金转银行通知
+  virtual void
+  OnRtnFromFutureToBankByFuture(CThostFtdcRspTransferField *pRspTransfer) override {
+    if (callbacks.on_rtn_from_future_to_bank_by_future) {
+      callbacks.on_rtn_from_future_to_bank_by_future(callbacks.user_data,
+                                                     pRspTransfer);
+    }
+  }
+
+  // 期货发起冲正银行转期货请求，银行处理完毕后报盘发回的通知
+  virtual void OnRtnRepealFromBankToFutureByFuture(
+      CThostFtdcRspRepealField *pRspRepeal) override {
+    if (callbacks.on_rtn_repeal_from_bank_to_future_by_future) {
+      callbacks.on_rtn_repeal_from_bank_to_future_by_future(callbacks.user_data,
+                                                            pRspRepeal);
+    }
+  }
+
+  // 期货发起冲正期货转银行请求，银行处理完毕后报盘发回的通知
+  virtual void OnRtnRepealFromFutureToBankByFuture(
+      CThostFtdcRspRepealField *pRspRepeal) override {
+    if (callbacks.on_rtn_repeal_from_future_to_bank_by_future) {
+      callbacks.on_rtn_repeal_from_future_to_bank_by_future(callbacks.user_data,
+                                                            pRspRepeal);
+    }
+  }
+
+  // 期货发起银行资金转期货错误回报
+  virtual void
+  OnErrRtnBankToFutureByFuture(CThostFtdcReqTransferField *pReqTransfer,
+                               CThostFtdcRspInfoField *pRspInfo) override {
+    if (callbacks.on_err_rtn_bank_to_future_by_future) {
+      callbacks.on_err_rtn_bank_to_future_by_future(callbacks.user_data,
+                                                    pReqTransfer, pRspInfo);
+    }
+  }
+
+  // 期货发起期货资金转银行错误回报
+  virtual void
+  OnErrRtnFutureToBankByFuture(CThostFtdcReqTransferField *pReqTransfer,
+                               CThostFtdcRspInfoField *pRspInfo) override {
+    if (callbacks.on_err_rtn_future_to_bank_by_future) {
+      callbacks.on_err_rtn_future_to_bank_by_future(callbacks.user_data,
+                                                    pReqTransfer, pRspInfo);
+    }
+  }
+
+  // 期货发起查询银行余额通知
+  virtual void OnRtnQueryBankBalanceByFuture(
+      CThostFtdcNotifyQueryAccountField *pNotifyQueryAccount) override {
+    if (callbacks.on_rtn_query_bank_balance_by_future) {
+      callbacks.on_rtn_query_bank_balance_by_future(callbacks.user_data,
+                                                    pNotifyQueryAccount);
+    }
+  }
+
+  // 期货发起查询银行余额错误回报
+  virtual void OnErrRtnQueryBankBalanceByFuture(
+      CThostFtdcReqQueryAccountField *pReqQueryAccount,
+      CThostFtdcRspInfoField *pRspInfo) override {
+    if (callbacks.on_err_rtn_query_bank_balance_by_future) {
+      callbacks.on_err_rtn_query_bank_balance_by_future(
+          callbacks.user_data, pReqQueryAccount, pRspInfo);
+    }
+  }
+
+  // 请求查询转帐流水响应
+  virtual void
+  OnRspQryTransferSerial(CThostFtdcTransferSerialField *pTransferSerial,
+                         CThostFtdcRspInfoField *pRspInfo, int nRequestID,
+                         bool bIsLast) override {
+    if (callbacks.on_rsp_qry_transfer_serial) {
+      callbacks.on_rsp_qry_transfer_serial(callbacks.user_data, pTransferSerial,
+                                           pRspInfo, nRequestID,
+                                           bIsLast ? 1 : 0);
+    }
+  }
+
+  // 请求查询签约银行响应
+  virtual void OnRspQryContractBank(CThostFtdcContractBankField *pContractBank,
+                                    CThostFtdcRspInfoField *pRspInfo,
+                                    int nRequestID, bool bIsLast) override {
+    if (callbacks.on_rsp_qry_contract_bank) {
+      callbacks.on_rsp_qry_contract_bank(callbacks.user_data, pContractBank,
+                                         pRspInfo, nRequestID, bIsLast ? 1 : 0);
+    }
+  }
+
+  // 请求查询银期签约关系响应
+  virtual void
+  OnRspQryAccountregister(CThostFtdcAccountregisterField *pAccountregister,
+                          CThostFtdcRspInfoField *pRspInfo, int nRequestID,
+                          bool bIsLast) override {
+    if (callbacks.on_rsp_qry_accountregister) {
+      callbacks.on_rsp_qry_accountregister(callbacks.user_data,
+                                           pAccountregister, pRspInfo,
+                                           nRequestID, bIsLast ? 1 : 0);
+    }
+  }
+
+  // 密码管理回调方法
+
+  // 用户口令更新请求响应
+  virtual void OnRspUserPasswordUpdate(
+      CThostFtdcUserPasswordUpdateField *pUserPasswordUpdate,
+      CThostFtdcRspInfoField *pRspInfo, int nRequestID, bool bIsLast) override {
+    if (callbacks.on_rsp_user_password_update) {
+      callbacks.on_rsp_user_password_update(callbacks.user_data,
+                                            pUserPasswordUpdate, pRspInfo,
+                                            nRequestID, bIsLast ? 1 : 0);
+    }
+  }
+
+  // 资金账户口令更新请求响应
+  virtual void OnRspTradingAccountPasswordUpdate(
+      CThostFtdcTradingAccountPasswordUpdateField *pTradingAccountPasswordUpdate,
+      CThostFtdcRspInfoField *pRspInfo, int nRequestID, bool bIsLast) override {
+    if (callbacks.on_rsp_trading_account_password_update) {
+      callbacks.on_rsp_trading_account_password_update(
+          callbacks.user_data, pTradingAccountPasswordUpdate, pRspInfo,
+          nRequestID, bIsLast ? 1 : 0);
+    }
+  }
+
+  // 认证模式回调方法
+
+  // 查询用户当前支持的认证模式的回复
+  virtual void OnRspUserAuthMethod(
+      CThostFtdcRspUserAuthMethodField *pRspUserAuthMethod,
+      CThostFtdcRspInfoField *pRspInfo, int nRequestID, bool bIsLast) override {
+    if (callbacks.on_rsp_user_auth_method) {
+      callbacks.on_rsp_user_auth_method(callbacks.user_data, pRspUserAuthMethod,
+                                        pRspInfo, nRequestID, bIsLast ? 1 : 0);
+    }
+  }
+
+  // 获取图形验证码请求的回复
+  virtual void OnRspGenUserCaptcha(
+      CThostFtdcRspGenUserCaptchaField *pRspGenUserCaptcha,
+      CThostFtdcRspInfoField *pRspInfo, int nRequestID, bool bIsLast) override {
+    if (callbacks.on_rsp_gen_user_captcha) {
+      callbacks.on_rsp_gen_user_captcha(callbacks.user_data, pRspGenUserCaptcha,
+                                        pRspInfo, nRequestID, bIsLast ? 1 : 0);
+    }
+  }
+
+  // 获取短信验证码请求的回复
+  virtual void OnRspGenUserText(
+      CThostFtdcRspGenUserTextField *pRspGenUserText,
+      CThostFtdcRspInfoField *pRspInfo, int nRequestID, bool bIsLast) override {
+    if (callbacks.on_rsp_gen_user_text) {
+      callbacks.on_rsp_gen_user_text(callbacks.user_data, pRspGenUserText,
+                                     pRspInfo, nRequestID, bIsLast ? 1 : 0);
+    }
+  }
 };
 
 extern "C" {
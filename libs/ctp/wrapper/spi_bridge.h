@@ -158,6 +158,74 @@ typedef void (*OnRspQryNoticeCallback)(void *user_data, void *notice,
                                        void *rsp_info, int request_id,
                                        int is_last);
 
+// 银期转账回调函数类型定义
+typedef void (*OnRspFromBankToFutureByFutureCallback)(void *user_data,
+                                                      void *req_transfer,
+                                                      void *rsp_info,
+                                                      int request_id,
+                                                      int is_last);
+typedef void (*OnRspFromFutureToBankByFutureCallback)(void *user_data,
+                                                      void *req_transfer,
+                                                      void *rsp_info,
+                                                      int request_id,
+                                                      int is_last);
+typedef void (*OnRspQueryBankAccountMoneyByFutureCallback)(
+    void *user_data, void *req_query_account, void *rsp_info, int request_id,
+    int is_last);
+typedef void (*OnRtnFromBankToFutureByFutureCallback)(void *user_data,
+                                                      void *rsp_transfer);
+typedef void (*OnRtnFromFutureToBankByFutureCallback)(void *user_data,
+                                                      void *rsp_transfer);
+typedef void (*OnRtnRepealFromBankToFutureByFutureCallback)(void *user_data,
+                                                            void *rsp_repeal);
+typedef void (*OnRtnRepealFromFutureToBankByFutureCallback)(void *user_data,
+                                                            void *rsp_repeal);
+typedef void (*OnErrRtnBankToFutureByFutureCallback)(void *user_data,
+                                                     void *req_transfer,
+                                                     void *rsp_info);
+typedef void (*OnErrRtnFutureToBankByFutureCallback)(void *user_data,
+                                                     void *req_transfer,
+                                                     void *rsp_info);
+typedef void (*OnRtnQueryBankBalanceByFutureCallback)(
+    void *user_data, void *notify_query_account);
+typedef void (*OnErrRtnQueryBankBalanceByFutureCallback)(
+    void *user_data, void *req_query_account, void *rsp_info);
+typedef void (*OnRspQryTransferSerialCallback)(void *user_data,
+                                               void *transfer_serial,
+                                               void *rsp_info, int request_id,
+                                               int is_last);
+typedef void (*OnRspQryContractBankCallback)(void *user_data,
+                                             void *contract_bank,
+                                             void *rsp_info, int request_id,
+                                             int is_last);
+typedef void (*OnRspQryAccountregisterCallback)(void *user_data,
+                                                void *accountregister,
+                                                void *rsp_info, int request_id,
+                                                int is_last);
+
+// 密码管理回调函数类型定义
+typedef void (*OnRspUserPasswordUpdateCallback)(void *user_data,
+                                                void *user_password_update,
+                                                void *rsp_info, int request_id,
+                                                int is_last);
+typedef void (*OnRspTradingAccountPasswordUpdateCallback)(
+    void *user_data, void *trading_account_password_update, void *rsp_info,
+    int request_id, int is_last);
+
+// 认证模式回调函数类型定义
+typedef void (*OnRspUserAuthMethodCallback)(void *user_data,
+                                            void *rsp_user_auth_method,
+                                            void *rsp_info, int request_id,
+                                            int is_last);
+typedef void (*OnRspGenUserCaptchaCallback)(void *user_data,
+                                            void *rsp_gen_user_captcha,
+                                            void *rsp_info, int request_id,
+                                            int is_last);
+typedef void (*OnRspGenUserTextCallback)(void *user_data,
+                                         void *rsp_gen_user_text,
+                                         void *rsp_info, int request_id,
+                                         int is_last);
+
 // 行情SPI回调结构体
 typedef struct {
   void *user_data;
@@ -222,6 +290,36 @@ typedef struct {
   OnRspQryTransferBankCallback on_rsp_qry_transfer_bank;
   OnRspQryInvestorPositionDetailCallback on_rsp_qry_investor_position_detail;
   OnRspQryNoticeCallback on_rsp_qry_notice;
+
+  // 银期转账回调
+  OnRspFromBankToFutureByFutureCallback on_rsp_from_bank_to_future_by_future;
+  OnRspFromFutureToBankByFutureCallback on_rsp_from_future_to_bank_by_future;
+  OnRspQueryBankAccountMoneyByFutureCallback
+      on_rsp_query_bank_account_money_by_future;
+  OnRtnFromBankToFutureByFutureCallback on_rtn_from_bank_to_future_by_future;
+  OnRtnFromFutureToBankByFutureCallback on_rtn_from_future_to_bank_by_future;
+  OnRtnRepealFromBankToFutureByFutureCallback
+      on_rtn_repeal_from_bank_to_future_by_future;
+  OnRtnRepealFromFutureToBankByFutureCallback
+      on_rtn_repeal_from_future_to_bank_by_future;
+  OnErrRtnBankToFutureByFutureCallback on_err_rtn_bank_to_future_by_future;
+  OnErrRtnFutureToBankByFutureCallback on_err_rtn_future_to_bank_by_future;
+  OnRtnQueryBankBalanceByFutureCallback on_rtn_query_bank_balance_by_future;
+  OnErrRtnQueryBankBalanceByFutureCallback
+      on_err_rtn_query_bank_balance_by_future;
+  OnRspQryTransferSerialCallback on_rsp_qry_transfer_serial;
+  OnRspQryContractBankCallback on_rsp_qry_contract_bank;
+  OnRspQryAccountregisterCallback on_rsp_qry_accountregister;
+
+  // 密码管理回调
+  OnRspUserPasswordUpdateCallback on_rsp_user_password_update;
+  OnRspTradingAccountPasswordUpdateCallback
+      on_rsp_trading_account_password_update;
+
+  // 认证模式回调
+  OnRspUserAuthMethodCallback on_rsp_user_auth_method;
+  OnRspGenUserCaptchaCallback on_rsp_gen_user_captcha;
+  OnRspGenUserTextCallback on_rsp_gen_user_text;
 } TraderSpiCallbacks;
 
 // 创建行情SPI桥接器
@@ -0,0 +1,623 @@
+//! 多账户会话管理
+//!
+//! 一个进程同时运行多个投资者账户时，每个账户都需要独立的`TraderApi`实例、流文件目录
+//! 和认证登录流程。[`AccountManager`]根据账户配置列表创建这些会话，并且：
+//!
+//! - 按账户名称隔离流文件目录，避免多个实例写同一份流文件
+//! - 所有账户共用一份合约目录[`InstrumentCatalog`]和一个行情会话
+//! - 汇总各账户的资金和持仓
+//! - 按账户名称路由报单和撤单，请求中的经纪公司和投资者代码以账户配置为准
+
+use crate::api::async_trader_api::{AsyncTraderApi, AsyncTraderEvent};
+use crate::api::order_handle::OrderHandle;
+use crate::api::trader_api::{InputOrderField, ReqAuthenticateField};
+use crate::api::AsyncMdApi;
+use crate::config::CtpConfig;
+use crate::error::{CtpError, CtpResult};
+use crate::failover::FailoverPolicy;
+use crate::models::{Account, Instrument, PosiDirection, Position};
+use crate::types::{
+    BrokerIdType, InputOrderActionField, InvestorIdType, QryInstrumentField,
+    QryInvestorPositionField, QryTradingAccountField, ReqUserLoginField, RspUserLoginField,
+    StringConvert, UserIdType,
+};
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, RwLock};
+use tracing::info;
+
+/// 账户配置
+#[derive(Debug, Clone)]
+pub struct AccountConfig {
+    /// 账户名称，用于路由报单和区分流文件目录
+    pub name: String,
+    /// 连接和登录参数
+    pub config: CtpConfig,
+}
+
+impl AccountConfig {
+    /// 创建账户配置
+    pub fn new(name: &str, config: CtpConfig) -> Self {
+        Self {
+            name: name.to_string(),
+            config,
+        }
+    }
+
+    /// 该账户使用的流文件目录：`<flow_path>/<name>/`
+    pub fn flow_path(&self) -> PathBuf {
+        // 名称中的路径分隔符会把目录嵌套到其他账户下，统一替换掉；
+        // 开头的`.`也替换掉，`.`开头的目录留给共享会话（如行情的`.md`）
+        let dir: String = self
+            .name
+            .chars()
+            .enumerate()
+            .map(|(i, c)| match c {
+                '/' | '\\' | ':' => '_',
+                '.' if i == 0 => '_',
+                c => c,
+            })
+            .collect();
+        Path::new(&self.config.flow_path).join(dir)
+    }
+}
+
+/// 合约目录
+#[derive(Debug, Clone, Default)]
+pub struct InstrumentCatalog {
+    instruments: HashMap<String, Instrument>,
+}
+
+impl InstrumentCatalog {
+    /// 创建空的合约目录
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 添加或替换合约
+    pub fn insert(&mut self, instrument: Instrument) {
+        self.instruments
+            .insert(instrument.instrument_id.clone(), instrument);
+    }
+
+    /// 按合约代码查找
+    pub fn get(&self, instrument_id: &str) -> Option<&Instrument> {
+        self.instruments.get(instrument_id)
+    }
+
+    /// 是否包含合约
+    pub fn contains(&self, instrument_id: &str) -> bool {
+        self.instruments.contains_key(instrument_id)
+    }
+
+    /// 合约数量
+    pub fn len(&self) -> usize {
+        self.instruments.len()
+    }
+
+    /// 是否为空
+    pub fn is_empty(&self) -> bool {
+        self.instruments.is_empty()
+    }
+
+    /// 全部合约
+    pub fn iter(&self) -> impl Iterator<Item = &Instrument> {
+        self.instruments.values()
+    }
+}
+
+impl FromIterator<Instrument> for InstrumentCatalog {
+    fn from_iter<I: IntoIterator<Item = Instrument>>(iter: I) -> Self {
+        let mut catalog = Self::new();
+        catalog.extend(iter);
+        catalog
+    }
+}
+
+impl Extend<Instrument> for InstrumentCatalog {
+    fn extend<I: IntoIterator<Item = Instrument>>(&mut self, iter: I) {
+        for instrument in iter {
+            self.insert(instrument);
+        }
+    }
+}
+
+/// 单个账户的交易会话
+pub struct AccountSession {
+    name: String,
+    config: CtpConfig,
+    api: Arc<AsyncTraderApi>,
+}
+
+impl AccountSession {
+    /// 账户名称
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// 账户配置，`flow_path`为隔离后的目录
+    pub fn config(&self) -> &CtpConfig {
+        &self.config
+    }
+
+    /// 交易API
+    pub fn api(&self) -> &Arc<AsyncTraderApi> {
+        &self.api
+    }
+
+    /// 注册前置、连接、认证（配置了AppID时）并登录
+    pub async fn connect(&self, timeout_secs: u64) -> CtpResult<RspUserLoginField> {
+        let config = &self.config;
+        let fronts = if config.trader_front_addresses.is_empty() {
+            vec![config.trader_front_address.clone()]
+        } else {
+            config.trader_front_addresses.clone()
+        };
+        self.api
+            .register_fronts(&fronts, FailoverPolicy::default())
+            .await?;
+        self.api.init().await?;
+        self.api.wait_connected(timeout_secs).await?;
+
+        // 凭证在登录时才获取，用完即清零
+        let credentials = config.credentials()?;
+        if !config.app_id.is_empty() {
+            let req = ReqAuthenticateField::new(
+                &config.broker_id,
+                &config.investor_id,
+                &config.app_id,
+                credentials.auth_code(),
+            )?
+            .with_product_info(&config.product_info)?;
+            self.api.authenticate(&req, timeout_secs).await?;
+        }
+
+        let mut req = ReqUserLoginField::new(
+            &config.broker_id,
+            &config.investor_id,
+            credentials.password(),
+        )?;
+        if !config.product_info.is_empty() {
+            req = req.with_product_info(&config.product_info)?;
+        }
+        let login = self.api.login(&req, timeout_secs).await?;
+        info!("账户 {} 登录成功", self.name);
+        Ok(login)
+    }
+
+    /// 查询资金账户
+    pub async fn trading_accounts(&self, timeout_secs: u64) -> CtpResult<Vec<Account>> {
+        let req = QryTradingAccountField::new(&self.config.broker_id, &self.config.investor_id)?;
+        let accounts = self.api.qry_trading_account(&req, timeout_secs).await?;
+        Ok(accounts.iter().map(Account::from).collect())
+    }
+
+    /// 查询持仓
+    pub async fn positions(&self, timeout_secs: u64) -> CtpResult<Vec<Position>> {
+        let req = QryInvestorPositionField::new(&self.config.broker_id, &self.config.investor_id)?;
+        let positions = self.api.qry_investor_position(&req, timeout_secs).await?;
+        Ok(positions.iter().map(Position::from).collect())
+    }
+}
+
+/// 跨账户资金汇总
+#[derive(Debug, Clone, Default)]
+pub struct FundsSummary {
+    /// 各账户的资金明细
+    pub accounts: Vec<(String, Account)>,
+    /// 动态权益合计
+    pub balance: f64,
+    /// 可用资金合计
+    pub available: f64,
+    /// 占用保证金合计
+    pub curr_margin: f64,
+    /// 平仓盈亏合计
+    pub close_profit: f64,
+    /// 持仓盈亏合计
+    pub position_profit: f64,
+    /// 手续费合计
+    pub commission: f64,
+}
+
+impl FundsSummary {
+    /// 汇总各账户资金
+    pub fn from_accounts(accounts: Vec<(String, Account)>) -> Self {
+        let mut summary = Self::default();
+        for (_, account) in &accounts {
+            summary.balance += account.balance;
+            summary.available += account.available;
+            summary.curr_margin += account.curr_margin;
+            summary.close_profit += account.close_profit;
+            summary.position_profit += account.position_profit;
+            summary.commission += account.commission;
+        }
+        summary.accounts = accounts;
+        summary
+    }
+}
+
+/// 跨账户合并后的单个合约单个方向的持仓
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct AggregatedPosition {
+    /// 总持仓
+    pub position: i32,
+    /// 今仓
+    pub today_position: i32,
+    /// 占用保证金
+    pub use_margin: f64,
+    /// 持仓盈亏
+    pub position_profit: f64,
+    /// 各账户的持仓数量
+    pub by_account: BTreeMap<String, i32>,
+}
+
+/// 跨账户持仓汇总
+#[derive(Debug, Clone, Default)]
+pub struct PositionSummary {
+    /// 各账户的持仓明细
+    pub positions: Vec<(String, Position)>,
+    /// 按(合约代码, 持仓方向)合并的持仓，CTP按昨仓/今仓分开返回的记录会合并
+    pub totals: BTreeMap<(String, u8), AggregatedPosition>,
+}
+
+impl PositionSummary {
+    /// 汇总各账户持仓
+    pub fn from_positions(positions: Vec<(String, Position)>) -> Self {
+        let mut totals: BTreeMap<(String, u8), AggregatedPosition> = BTreeMap::new();
+        for (account, position) in &positions {
+            let key = (
+                position.instrument_id.clone(),
+                u8::from(position.posi_direction),
+            );
+            let total = totals.entry(key).or_default();
+            total.position += position.position;
+            total.today_position += position.today_position;
+            total.use_margin += position.use_margin;
+            total.position_profit += position.position_profit;
+            *total.by_account.entry(account.clone()).or_insert(0) += position.position;
+        }
+        Self { positions, totals }
+    }
+
+    /// 指定合约和方向的合并持仓
+    pub fn get(
+        &self,
+        instrument_id: &str,
+        direction: PosiDirection,
+    ) -> Option<&AggregatedPosition> {
+        self.totals
+            .get(&(instrument_id.to_string(), u8::from(direction)))
+    }
+}
+
+/// 多账户会话管理器
+pub struct AccountManager {
+    sessions: Vec<AccountSession>,
+    index: HashMap<String, usize>,
+    catalog: RwLock<Arc<InstrumentCatalog>>,
+    market_data: Option<Arc<AsyncMdApi>>,
+}
+
+impl AccountManager {
+    /// 根据账户配置创建会话，每个账户使用`<flow_path>/<name>/`作为流文件目录
+    ///
+    /// 账户名称为空、重复，或两个账户落到同一个流文件目录时返回错误
+    pub async fn new(accounts: Vec<AccountConfig>) -> CtpResult<Self> {
+        Self::validate(&accounts)?;
+
+        let mut sessions = Vec::with_capacity(accounts.len());
+        let mut index = HashMap::new();
+        for account in accounts {
+            let flow_path = account.flow_path();
+            fs::create_dir_all(&flow_path)
+                .map_err(|e| CtpError::InvalidPath(format!("{}: {}", flow_path.display(), e)))?;
+            let mut config = account.config;
+            config.flow_path = flow_path.to_string_lossy().to_string();
+
+            let api = AsyncTraderApi::new(Some(&config.flow_path), Some(true)).await?;
+            index.insert(account.name.clone(), sessions.len());
+            sessions.push(AccountSession {
+                name: account.name,
+                config,
+                api: Arc::new(api),
+            });
+        }
+
+        Ok(Self {
+            sessions,
+            index,
+            catalog: RwLock::new(Arc::new(InstrumentCatalog::new())),
+            market_data: None,
+        })
+    }
+
+    fn validate(accounts: &[AccountConfig]) -> CtpResult<()> {
+        if accounts.is_empty() {
+            return Err(CtpError::InvalidParameterError(
+                "至少需要一个账户".to_string(),
+            ));
+        }
+        let mut names = HashSet::new();
+        let mut flow_paths = HashSet::new();
+        for account in accounts {
+            if account.name.trim().is_empty() {
+                return Err(CtpError::InvalidParameterError(
+                    "账户名称不能为空".to_string(),
+                ));
+            }
+            if !names.insert(account.name.as_str()) {
+                return Err(CtpError::InvalidParameterError(format!(
+                    "账户名称重复: {}",
+                    account.name
+                )));
+            }
+            if !flow_paths.insert(account.flow_path()) {
+                return Err(CtpError::InvalidPath(format!(
+                    "账户 {} 的流文件目录与其他账户相同",
+                    account.name
+                )));
+            }
+        }
+        Ok(())
+    }
+
+    /// 全部会话，按配置顺序
+    pub fn sessions(&self) -> &[AccountSession] {
+        &self.sessions
+    }
+
+    /// 按名称查找会话
+    pub fn session(&self, name: &str) -> CtpResult<&AccountSession> {
+        self.index
+            .get(name)
+            .map(|&i| &self.sessions[i])
+            .ok_or_else(|| CtpError::InvalidParameterError(format!("未知账户: {}", name)))
+    }
+
+    /// 依次连接并登录全部账户，任一账户失败时返回原错误类型，错误信息带账户名称
+    pub async fn connect_all(&self, timeout_secs: u64) -> CtpResult<()> {
+        for session in &self.sessions {
+            session
+                .connect(timeout_secs)
+                .await
+                .map_err(|e| e.with_context(&format!("账户 {}", session.name)))?;
+        }
+        Ok(())
+    }
+
+    /// 通过第一个账户查询全部合约，刷新共享的合约目录
+    pub async fn load_instruments(&self, timeout_secs: u64) -> CtpResult<Arc<InstrumentCatalog>> {
+        let session = &self.sessions[0];
+        let instruments = session
+            .api
+            .qry_instrument(&QryInstrumentField::new(), timeout_secs)
+            .await?;
+        let catalog: Arc<InstrumentCatalog> =
+            Arc::new(instruments.iter().map(Instrument::from).collect());
+        info!("合约目录已加载 {} 个合约", catalog.len());
+        *self.catalog.write().unwrap_or_else(|e| e.into_inner()) = catalog.clone();
+        Ok(catalog)
+    }
+
+    /// 共享的合约目录
+    pub fn instruments(&self) -> Arc<InstrumentCatalog> {
+        self.catalog
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .clone()
+    }
+
+    /// 使用第一个账户的行情前置创建共享行情会话并登录
+    ///
+    /// 行情流文件目录为`<flow_path>/.md/`，不会与账户目录重名
+    pub async fn connect_market_data(&mut self, timeout_secs: u64) -> CtpResult<Arc<AsyncMdApi>> {
+        let config = &self.sessions[0].config;
+        let flow_path = Path::new(&config.flow_path)
+            .parent()
+            .unwrap_or_else(|| Path::new("."))
+            .join(".md");
+        fs::create_dir_all(&flow_path)
+            .map_err(|e| CtpError::InvalidPath(format!("{}: {}", flow_path.display(), e)))?;
+
+        let md =
+            AsyncMdApi::new(Some(&flow_path.to_string_lossy()), false, false, Some(true)).await?;
+        let fronts = if config.md_front_addresses.is_empty() {
+            vec![config.md_front_address.clone()]
+        } else {
+            config.md_front_addresses.clone()
+        };
+        md.register_fronts(&fronts).await?;
+        md.init().await?;
+        md.wait_connected(timeout_secs).await?;
+        let credentials = config.credentials()?;
+        let req = ReqUserLoginField::new(
+            &config.broker_id,
+            &config.investor_id,
+            credentials.password(),
+        )?;
+        md.login(&req, timeout_secs).await?;
+
+        let md = Arc::new(md);
+        self.market_data = Some(md.clone());
+        Ok(md)
+    }
+
+    /// 共享的行情会话
+    pub fn market_data(&self) -> Option<&Arc<AsyncMdApi>> {
+        self.market_data.as_ref()
+    }
+
+    /// 汇总全部账户的资金
+    pub async fn funds(&self, timeout_secs: u64) -> CtpResult<FundsSummary> {
+        let mut accounts = Vec::new();
+        for session in &self.sessions {
+            for account in session.trading_accounts(timeout_secs).await? {
+                accounts.push((session.name.clone(), account));
+            }
+        }
+        Ok(FundsSummary::from_accounts(accounts))
+    }
+
+    /// 汇总全部账户的持仓
+    pub async fn positions(&self, timeout_secs: u64) -> CtpResult<PositionSummary> {
+        let mut positions = Vec::new();
+        for session in &self.sessions {
+            for position in session.positions(timeout_secs).await? {
+                positions.push((session.name.clone(), position));
+            }
+        }
+        Ok(PositionSummary::from_positions(positions))
+    }
+
+    /// 向指定账户报单，返回跟踪报单进展的句柄
+    ///
+    /// 经纪公司、投资者和用户代码以账户配置为准；合约目录已加载时检查合约是否存在，
+    /// 并在未填写交易所时补上
+    pub async fn order_insert(
+        &self,
+        account: &str,
+        req: &InputOrderField,
+    ) -> CtpResult<OrderHandle> {
+        let session = self.session(account)?;
+        let req = self.route_order(session, req)?;
+        session.api.order_insert(&req).await
+    }
+
+    /// 向指定账户发送报单操作（撤单等）
+    pub async fn order_action(
+        &self,
+        account: &str,
+        req: &InputOrderActionField,
+        timeout_secs: u64,
+    ) -> CtpResult<AsyncTraderEvent> {
+        let session = self.session(account)?;
+        let mut req = req.clone();
+        req.broker_id = BrokerIdType::from_utf8_string(&session.config.broker_id)?;
+        req.investor_id = InvestorIdType::from_utf8_string(&session.config.investor_id)?;
+        req.user_id = UserIdType::from_utf8_string(&session.config.investor_id)?;
+        session.api.order_action(&req, timeout_secs).await
+    }
+
+    fn route_order(
+        &self,
+        session: &AccountSession,
+        req: &InputOrderField,
+    ) -> CtpResult<InputOrderField> {
+        let mut req = req.clone();
+        req.broker_id = <[u8; 11]>::from_utf8_string(&session.config.broker_id)?;
+        req.investor_id = <[u8; 13]>::from_utf8_string(&session.config.investor_id)?;
+        req.user_id = <[u8; 16]>::from_utf8_string(&session.config.investor_id)?;
+
+        let catalog = self.instruments();
+        if !catalog.is_empty() {
+            let instrument_id = req.instrument_id.to_utf8_string()?;
+            let instrument = catalog.get(&instrument_id).ok_or_else(|| {
+                CtpError::InvalidParameterError(format!("未知合约: {}", instrument_id))
+            })?;
+            if req.exchange_id[0] == 0 {
+                req.exchange_id = <[u8; 9]>::from_utf8_string(&instrument.exchange_id)?;
+            }
+        }
+        Ok(req)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::SecretSource;
+
+    fn config(investor_id: &str, flow_path: &str) -> CtpConfig {
+        CtpConfig {
+            md_front_address: String::new(),
+            trader_front_address: String::new(),
+            md_front_addresses: Vec::new(),
+            trader_front_addresses: Vec::new(),
+            broker_id: "9999".to_string(),
+            investor_id: investor_id.to_string(),
+            password: String::new(),
+            flow_path: flow_path.to_string(),
+            instruments: Vec::new(),
+            app_id: String::new(),
+            auth_code: String::new(),
+            product_info: String::new(),
+            secret_source: SecretSource::Memory,
+            credential_provider: None,
+        }
+    }
+
+    #[test]
+    fn test_flow_paths_are_isolated() {
+        let a = AccountConfig::new("a/1", config("00001", "./flow"));
+        assert_eq!(a.flow_path(), Path::new("./flow").join("a_1"));
+        let hidden = AccountConfig::new(".md", config("00001", "./flow"));
+        assert_eq!(hidden.flow_path(), Path::new("./flow").join("_md"));
+        let parent = AccountConfig::new("..", config("00001", "./flow"));
+        assert_eq!(parent.flow_path(), Path::new("./flow").join("_."));
+
+        let e = CtpError::TimeoutError("等待登录超时".to_string()).with_context("账户 a");
+        assert!(matches!(e, CtpError::TimeoutError(msg) if msg == "账户 a: 等待登录超时"));
+
+        let accounts = vec![
+            AccountConfig::new("a", config("00001", "./flow")),
+            AccountConfig::new("b", config("00002", "./flow")),
+        ];
+        assert!(AccountManager::validate(&accounts).is_ok());
+
+        let duplicate = vec![
+            AccountConfig::new("a", config("00001", "./flow")),
+            AccountConfig::new("a", config("00002", "./other")),
+        ];
+        assert!(AccountManager::validate(&duplicate).is_err());
+
+        // 名称不同但目录相同
+        let same_dir = vec![
+            AccountConfig::new("x/y", config("00001", "./flow")),
+            AccountConfig::new("x_y", config("00002", "./flow")),
+        ];
+        assert!(matches!(
+            AccountManager::validate(&same_dir),
+            Err(CtpError::InvalidPath(_))
+        ));
+    }
+
+    #[test]
+    fn test_aggregation() {
+        let account = |balance: f64, available: f64| Account {
+            balance,
+            available,
+            ..Default::default()
+        };
+        let funds = FundsSummary::from_accounts(vec![
+            ("a".to_string(), account(100.0, 60.0)),
+            ("b".to_string(), account(50.0, 40.0)),
+        ]);
+        assert_eq!(funds.balance, 150.0);
+        assert_eq!(funds.available, 100.0);
+
+        let position = |instrument: &str, direction: PosiDirection, volume: i32| Position {
+            instrument_id: instrument.to_string(),
+            posi_direction: direction,
+            position: volume,
+            ..Default::default()
+        };
+        let summary = PositionSummary::from_positions(vec![
+            ("a".to_string(), position("rb2601", PosiDirection::Long, 2)),
+            ("a".to_string(), position("rb2601", PosiDirection::Long, 1)),
+            ("b".to_string(), position("rb2601", PosiDirection::Long, 4)),
+            ("b".to_string(), position("rb2601", PosiDirection::Short, 5)),
+        ]);
+        let long = summary.get("rb2601", PosiDirection::Long).unwrap();
+        assert_eq!(long.position, 7);
+        assert_eq!(long.by_account["a"], 3);
+        assert_eq!(long.by_account["b"], 4);
+        assert_eq!(
+            summary
+                .get("rb2601", PosiDirection::Short)
+                .unwrap()
+                .position,
+            5
+        );
+    }
+}
@@ -4,16 +4,28 @@
 
 pub mod async_md_api;
 pub mod async_trader_api;
+#[cfg(feature = "channel")]
+pub mod channel;
 pub mod md_api;
+pub mod md_ring;
+pub mod order_handle;
 pub mod trader_api;
 pub mod utils;
+pub mod working_orders;
 
 pub use async_md_api::AsyncMdApi;
 pub use async_trader_api::AsyncTraderApi;
 pub use md_api::{MdApi, MdSpiHandler};
+pub use md_ring::{market_data_ring, MarketDataConsumer, MarketDataProducer, RingStats};
+pub use order_handle::{OrderHandle, OrderOutcome, OrderSnapshot, OrderUpdate};
 pub use trader_api::{TraderApi, TraderSpiHandler};
+pub use working_orders::{CancelFilter, CancelOutcome, CancelResult, MassCancelReport};
+
+#[cfg(feature = "channel")]
+pub use channel::{ChannelMdApi, ChannelTraderApi};
 
 use crate::error::{CtpError, CtpResult};
+use crate::models::FensUserInfo;
 use std::ffi::CString;
 
 // CTP API基础功能特质
@@ -33,10 +45,29 @@ pub trait CtpApi {
     // 注册前置机地址
     fn register_front(&mut self, front_address: &str) -> CtpResult<()>;
 
+    // 注册名字服务器网络地址，优先于register_front
+    fn register_name_server(&mut self, ns_address: &str) -> CtpResult<()>;
+
+    // 注册名字服务器用户信息，需要在init之前调用
+    fn register_fens_user_info(&mut self, user_info: &FensUserInfo) -> CtpResult<()>;
+
     // 等待API线程结束
     fn join(&self) -> CtpResult<i32>;
 }
 
+// 回调线程向上层投递事件的出口，返回事件是否被接收
+//
+// 异步API使用tokio无界通道，`channel` feature使用有界crossbeam通道
+pub(crate) trait EventSink<E>: Send + Sync {
+    fn send(&self, event: E) -> bool;
+}
+
+impl<E: Send> EventSink<E> for tokio::sync::mpsc::UnboundedSender<E> {
+    fn send(&self, event: E) -> bool {
+        tokio::sync::mpsc::UnboundedSender::send(self, event).is_ok()
+    }
+}
+
 // 将Rust字符串转换为C字符串
 pub(crate) fn to_cstring(s: &str) -> CtpResult<CString> {
     CString::new(s).map_err(|e| CtpError::InvalidParameterError(format!("字符串转换失败: {}", e)))
@@ -5,8 +5,12 @@
 use crate::api::md_api::{
     DepthMarketDataField, ForQuoteRspField, MdApi, MdSpiHandler, SpecificInstrumentField,
 };
-use crate::api::CtpApi;
+use crate::api::md_ring::MarketDataProducer;
+use crate::api::{CtpApi, EventSink};
 use crate::error::{CtpError, CtpResult};
+use crate::latency::LatencyRecorder;
+use crate::metrics::{ObservedSink, SessionMetrics};
+use crate::models::FensUserInfo;
 use crate::types::{ReqUserLoginField, RspInfoField, RspUserLoginField};
 use std::sync::Arc;
 use tokio::sync::{mpsc, Mutex, Notify};
@@ -15,6 +19,7 @@ use tracing::{debug, error, warn};
 
 /// 异步事件类型
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum AsyncMdEvent {
     /// 连接成功
     Connected,
@@ -63,6 +68,7 @@ pub enum AsyncMdEvent {
 
 /// 异步行情API状态
 #[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct AsyncMdState {
     pub connected: bool,
     pub logged_in: bool,
@@ -83,6 +89,8 @@ pub struct AsyncMdApi {
     connected_notify: Arc<Notify>,
     /// 登录通知
     login_notify: Arc<Notify>,
+    /// 运行指标
+    metrics: Option<Arc<SessionMetrics>>,
 }
 
 impl AsyncMdApi {
@@ -108,22 +116,76 @@ impl AsyncMdApi {
             state: Arc::new(Mutex::new(AsyncMdState::default())),
             connected_notify: Arc::new(Notify::new()),
             login_notify: Arc::new(Notify::new()),
+            metrics: None,
         })
     }
 
+    /// 挂接运行指标，应在`init`之前调用
+    pub fn with_metrics(mut self, metrics: Arc<SessionMetrics>) -> Self {
+        self.metrics = Some(metrics);
+        self
+    }
+
     /// 注册前置机地址
     pub async fn register_front(&self, front_address: &str) -> CtpResult<()> {
         let mut api = self.inner.lock().await;
         api.register_front(front_address)
     }
 
+    /// 按优先顺序注册多个前置地址，由CTP在其中选择可用的前置连接
+    pub async fn register_fronts(&self, addresses: &[String]) -> CtpResult<()> {
+        if addresses.is_empty() {
+            return Err(CtpError::InvalidParameterError(
+                "至少需要一个前置地址".to_string(),
+            ));
+        }
+        let mut api = self.inner.lock().await;
+        for address in addresses {
+            api.register_front(address)?;
+        }
+        Ok(())
+    }
+
+    /// 挂接行情环形缓冲区，只能在`init`之前调用
+    ///
+    /// 挂接后深度行情直接写入缓冲区，不再产生[`AsyncMdEvent::DepthMarketData`]事件
+    pub async fn attach_market_data_ring(&self, producer: MarketDataProducer) -> CtpResult<()> {
+        let mut api = self.inner.lock().await;
+        api.attach_market_data_ring(producer)
+    }
+
+    /// 挂接延迟记录器，统计行情从交易所到本地的延迟
+    pub async fn set_latency_recorder(&self, recorder: Arc<std::sync::Mutex<LatencyRecorder>>) {
+        let mut api = self.inner.lock().await;
+        api.set_latency_recorder(recorder);
+    }
+
+    /// 注册名字服务器网络地址
+    pub async fn register_name_server(&self, ns_address: &str) -> CtpResult<()> {
+        let mut api = self.inner.lock().await;
+        api.register_name_server(ns_address)
+    }
+
+    /// 注册名字服务器用户信息
+    pub async fn register_fens_user_info(&self, user_info: &FensUserInfo) -> CtpResult<()> {
+        let mut api = self.inner.lock().await;
+        api.register_fens_user_info(user_info)
+    }
+
     /// 初始化API
     pub async fn init(&self) -> CtpResult<()> {
         let mut api = self.inner.lock().await;
 
         // 创建异步事件处理器
+        let event_sender: Arc<dyn EventSink<AsyncMdEvent>> = match &self.metrics {
+            Some(metrics) => Arc::new(ObservedSink::new(
+                self.event_sender.clone(),
+                metrics.clone(),
+            )),
+            None => Arc::new(self.event_sender.clone()),
+        };
         let handler = AsyncMdHandler::new(
-            self.event_sender.clone(),
+            event_sender,
             self.state.clone(),
             self.connected_notify.clone(),
             self.login_notify.clone(),
@@ -205,13 +267,21 @@ impl AsyncMdApi {
     /// 接收下一个事件
     pub async fn recv_event(&self) -> Option<AsyncMdEvent> {
         let mut receiver = self.event_receiver.lock().await;
-        receiver.recv().await
+        let event = receiver.recv().await;
+        if let (Some(_), Some(metrics)) = (&event, &self.metrics) {
+            metrics.on_event_dequeued();
+        }
+        event
     }
 
     /// 尝试接收事件（非阻塞）
     pub async fn try_recv_event(&self) -> Result<AsyncMdEvent, mpsc::error::TryRecvError> {
         let mut receiver = self.event_receiver.lock().await;
-        receiver.try_recv()
+        let event = receiver.try_recv();
+        if let (Ok(_), Some(metrics)) = (&event, &self.metrics) {
+            metrics.on_event_dequeued();
+        }
+        event
     }
 
     /// 获取当前状态
@@ -223,7 +293,7 @@ impl AsyncMdApi {
 /// 异步事件处理器
 #[derive(Clone)]
 struct AsyncMdHandler {
-    event_sender: mpsc::UnboundedSender<AsyncMdEvent>,
+    event_sender: Arc<dyn EventSink<AsyncMdEvent>>,
     state: Arc<Mutex<AsyncMdState>>,
     connected_notify: Arc<Notify>,
     login_notify: Arc<Notify>,
@@ -231,7 +301,7 @@ struct AsyncMdHandler {
 
 impl AsyncMdHandler {
     fn new(
-        event_sender: mpsc::UnboundedSender<AsyncMdEvent>,
+        event_sender: Arc<dyn EventSink<AsyncMdEvent>>,
         state: Arc<Mutex<AsyncMdState>>,
         connected_notify: Arc<Notify>,
         login_notify: Arc<Notify>,
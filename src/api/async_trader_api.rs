@@ -2,24 +2,52 @@
 //!
 //! 基于同步TraderApi提供异步封装，使用tokio实现
 
+use crate::api::order_handle::{field_string, OrderHandle, OrderTracker};
 use crate::api::trader_api::{
-    InputOrderField, InvestorPositionField, OrderField, ReqAuthenticateField, RspAuthenticateField,
-    TradeField, TraderApi, TraderSpiHandler, TradingAccountField,
+    FrontInfoProbe, InputOrderField, InstrumentField, InvestorPositionField, OrderField,
+    ReqAuthenticateField, RspAuthenticateField, TradeField, TraderApi, TraderSpiHandler,
+    TradingAccountField,
 };
-use crate::api::CtpApi;
+use crate::api::working_orders::{
+    cancel_request, pacing_interval, CancelFilter, CancelOutcome, CancelResult, MassCancelReport,
+    WorkingOrders,
+};
+use crate::api::{CtpApi, EventSink};
+use crate::config::CtpConfig;
 use crate::error::{CtpError, CtpResult};
+use crate::execution::{self, ExecutionAlgo, ExecutionConfig, ExecutionHandle};
+use crate::failover::{FailoverEvent, FailoverPolicy, FrontPool};
+use crate::journal::AuditJournal;
+use crate::latency::LatencyRecorder;
+use crate::metrics::{ObservedSink, SessionMetrics};
+use crate::models::{FensUserInfo, FrontInfo};
+use crate::order_ref::OrderRefAllocator;
+use crate::relay::UserSystemInfo;
+use crate::settlement::{SettlementAssembler, SettlementStatement};
+use crate::transfer::{TransferDirection, TransferHistory, TransferOutcome};
 use crate::types::{
-    InputOrderActionField, QryInvestorPositionField, QryTradingAccountField, ReqUserLoginField,
-    RspInfoField, RspUserLoginField,
+    AccountregisterField, ContractBankField, InputOrderActionField, NotifyQueryAccountField,
+    OrderActionField, QryAccountregisterField, QryContractBankField, QryInstrumentField,
+    QryInvestorPositionField, QryOrderField, QrySettlementInfoField, QryTradingAccountField,
+    QryTransferSerialField, ReqGenUserCaptchaField, ReqGenUserTextField, ReqQueryAccountField,
+    ReqTransferField, ReqUserAuthMethodField, ReqUserLoginField, ReqUserLoginWithCaptchaField,
+    ReqUserLoginWithOtpField, ReqUserLoginWithTextField, RspGenUserCaptchaField,
+    RspGenUserTextField, RspInfoField, RspRepealField, RspTransferField, RspUserAuthMethodField,
+    RspUserLoginField, SettlementInfoConfirmField, SettlementInfoField, StringConvert,
+    TradingAccountPasswordUpdateField, TransferSerialField, UserPasswordUpdateField,
 };
 use std::collections::HashMap;
-use std::sync::Arc;
+use std::future::Future;
+use std::path::Path;
+use std::sync::{Arc, Mutex as StdMutex};
+use std::time::Instant;
 use tokio::sync::{mpsc, Mutex, Notify};
 use tokio::time::{timeout, Duration};
 use tracing::{debug, error, warn};
 
 /// 异步交易事件类型
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum AsyncTraderEvent {
     /// 连接成功
     Connected,
@@ -75,6 +103,13 @@ pub enum AsyncTraderEvent {
         request_id: i32,
         is_last: bool,
     },
+    /// 查询合约响应
+    QryInstrumentResponse {
+        instrument: Option<InstrumentField>,
+        rsp_info: Option<RspInfoField>,
+        request_id: i32,
+        is_last: bool,
+    },
     /// 查询报单响应
     QryOrderResponse {
         order: Option<OrderField>,
@@ -89,10 +124,112 @@ pub enum AsyncTraderEvent {
         request_id: i32,
         is_last: bool,
     },
+    /// 查询结算单响应
+    QrySettlementInfoResponse {
+        settlement_info: Option<SettlementInfoField>,
+        rsp_info: Option<RspInfoField>,
+        request_id: i32,
+        is_last: bool,
+    },
+    /// 结算单确认响应
+    SettlementInfoConfirmResponse {
+        settlement_info_confirm: Option<SettlementInfoConfirmField>,
+        rsp_info: Option<RspInfoField>,
+        request_id: i32,
+        is_last: bool,
+    },
+    /// 期货发起转帐应答（银行转期货、期货转银行）
+    TransferResponse {
+        req_transfer: Option<ReqTransferField>,
+        rsp_info: Option<RspInfoField>,
+        request_id: i32,
+        is_last: bool,
+    },
+    /// 期货发起查询银行余额应答
+    QueryBankAccountResponse {
+        req_query_account: Option<ReqQueryAccountField>,
+        rsp_info: Option<RspInfoField>,
+        request_id: i32,
+        is_last: bool,
+    },
+    /// 查询转帐流水响应
+    QryTransferSerialResponse {
+        transfer_serial: Option<TransferSerialField>,
+        rsp_info: Option<RspInfoField>,
+        request_id: i32,
+        is_last: bool,
+    },
+    /// 查询签约银行响应
+    QryContractBankResponse {
+        contract_bank: Option<ContractBankField>,
+        rsp_info: Option<RspInfoField>,
+        request_id: i32,
+        is_last: bool,
+    },
+    /// 查询银期签约关系响应
+    QryAccountregisterResponse {
+        accountregister: Option<AccountregisterField>,
+        rsp_info: Option<RspInfoField>,
+        request_id: i32,
+        is_last: bool,
+    },
+    /// 用户口令更新响应
+    UserPasswordUpdateResponse {
+        user_password_update: Option<UserPasswordUpdateField>,
+        rsp_info: Option<RspInfoField>,
+        request_id: i32,
+        is_last: bool,
+    },
+    /// 资金账户口令更新响应
+    TradingAccountPasswordUpdateResponse {
+        trading_account_password_update: Option<TradingAccountPasswordUpdateField>,
+        rsp_info: Option<RspInfoField>,
+        request_id: i32,
+        is_last: bool,
+    },
+    /// 查询认证模式响应
+    UserAuthMethodResponse {
+        rsp_user_auth_method: Option<RspUserAuthMethodField>,
+        rsp_info: Option<RspInfoField>,
+        request_id: i32,
+        is_last: bool,
+    },
+    /// 获取图形验证码响应（图片数据较大，装箱保存）
+    GenUserCaptchaResponse {
+        rsp_gen_user_captcha: Option<Box<RspGenUserCaptchaField>>,
+        rsp_info: Option<RspInfoField>,
+        request_id: i32,
+        is_last: bool,
+    },
+    /// 获取短信验证码响应
+    GenUserTextResponse {
+        rsp_gen_user_text: Option<RspGenUserTextField>,
+        rsp_info: Option<RspInfoField>,
+        request_id: i32,
+        is_last: bool,
+    },
+    /// 转帐完成通知
+    TransferReturn(RspTransferField),
+    /// 转帐冲正通知
+    TransferRepealReturn(RspRepealField),
+    /// 转帐错误回报
+    TransferErrorReturn {
+        req_transfer: Option<ReqTransferField>,
+        rsp_info: Option<RspInfoField>,
+    },
+    /// 查询银行余额通知
+    QueryBankBalanceReturn(NotifyQueryAccountField),
+    /// 查询银行余额错误回报
+    QueryBankBalanceErrorReturn {
+        req_query_account: Option<ReqQueryAccountField>,
+        rsp_info: Option<RspInfoField>,
+    },
     /// 报单回报
     OrderReturn(OrderField),
     /// 成交回报
     TradeReturn(TradeField),
+    /// 前置连接跟踪与故障切换
+    Failover(FailoverEvent),
     /// 错误响应
     ErrorResponse {
         rsp_info: Option<RspInfoField>,
@@ -101,14 +238,174 @@ pub enum AsyncTraderEvent {
     },
 }
 
+impl AsyncTraderEvent {
+    /// 请求响应的请求编号、是否为最后一条以及响应信息，连接事件和回报返回`None`
+    pub fn response_meta(&self) -> Option<(i32, bool, Option<&RspInfoField>)> {
+        match self {
+            AsyncTraderEvent::AuthenticateResponse {
+                request_id,
+                is_last,
+                rsp_info,
+                ..
+            }
+            | AsyncTraderEvent::LoginResponse {
+                request_id,
+                is_last,
+                rsp_info,
+                ..
+            }
+            | AsyncTraderEvent::LogoutResponse {
+                request_id,
+                is_last,
+                rsp_info,
+                ..
+            }
+            | AsyncTraderEvent::OrderInsertResponse {
+                request_id,
+                is_last,
+                rsp_info,
+                ..
+            }
+            | AsyncTraderEvent::OrderActionResponse {
+                request_id,
+                is_last,
+                rsp_info,
+                ..
+            }
+            | AsyncTraderEvent::QryTradingAccountResponse {
+                request_id,
+                is_last,
+                rsp_info,
+                ..
+            }
+            | AsyncTraderEvent::QryInvestorPositionResponse {
+                request_id,
+                is_last,
+                rsp_info,
+                ..
+            }
+            | AsyncTraderEvent::QryInstrumentResponse {
+                request_id,
+                is_last,
+                rsp_info,
+                ..
+            }
+            | AsyncTraderEvent::QryOrderResponse {
+                request_id,
+                is_last,
+                rsp_info,
+                ..
+            }
+            | AsyncTraderEvent::QryTradeResponse {
+                request_id,
+                is_last,
+                rsp_info,
+                ..
+            }
+            | AsyncTraderEvent::QrySettlementInfoResponse {
+                request_id,
+                is_last,
+                rsp_info,
+                ..
+            }
+            | AsyncTraderEvent::SettlementInfoConfirmResponse {
+                request_id,
+                is_last,
+                rsp_info,
+                ..
+            }
+            | AsyncTraderEvent::TransferResponse {
+                request_id,
+                is_last,
+                rsp_info,
+                ..
+            }
+            | AsyncTraderEvent::QueryBankAccountResponse {
+                request_id,
+                is_last,
+                rsp_info,
+                ..
+            }
+            | AsyncTraderEvent::QryTransferSerialResponse {
+                request_id,
+                is_last,
+                rsp_info,
+                ..
+            }
+            | AsyncTraderEvent::QryContractBankResponse {
+                request_id,
+                is_last,
+                rsp_info,
+                ..
+            }
+            | AsyncTraderEvent::QryAccountregisterResponse {
+                request_id,
+                is_last,
+                rsp_info,
+                ..
+            }
+            | AsyncTraderEvent::UserPasswordUpdateResponse {
+                request_id,
+                is_last,
+                rsp_info,
+                ..
+            }
+            | AsyncTraderEvent::TradingAccountPasswordUpdateResponse {
+                request_id,
+                is_last,
+                rsp_info,
+                ..
+            }
+            | AsyncTraderEvent::UserAuthMethodResponse {
+                request_id,
+                is_last,
+                rsp_info,
+                ..
+            }
+            | AsyncTraderEvent::GenUserCaptchaResponse {
+                request_id,
+                is_last,
+                rsp_info,
+                ..
+            }
+            | AsyncTraderEvent::GenUserTextResponse {
+                request_id,
+                is_last,
+                rsp_info,
+                ..
+            }
+            | AsyncTraderEvent::ErrorResponse {
+                request_id,
+                is_last,
+                rsp_info,
+                ..
+            } => Some((*request_id, *is_last, rsp_info.as_ref())),
+            _ => None,
+        }
+    }
+}
+
+/// 登录时需要用户提供的验证信息
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum VerificationChallenge {
+    /// 图形验证码，内容为柜台返回的图片数据
+    Captcha(Vec<u8>),
+    /// 动态口令
+    Otp,
+    /// 短信验证码，序号用于核对收到的短信
+    Text { user_text_seq: i32 },
+}
+
 /// 异步交易API状态
 #[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct AsyncTraderState {
     pub connected: bool,
     pub authenticated: bool,
     pub logged_in: bool,
     pub auth_info: Option<RspAuthenticateField>,
     pub login_info: Option<RspUserLoginField>,
+    pub front_info: Option<FrontInfo>,
 }
 
 /// 待处理的异步请求
@@ -136,6 +433,22 @@ pub struct AsyncTraderApi {
     login_notify: Arc<Notify>,
     /// 待处理的请求映射 (request_id -> PendingRequest)
     pending_requests: Arc<Mutex<HashMap<i32, PendingRequest>>>,
+    /// 流文件目录，重建API时使用
+    flow_path: Option<String>,
+    /// 是否生产环境，重建API时使用
+    is_production_mode: Option<bool>,
+    /// 前置地址池，回调线程中也会更新
+    fronts: Arc<StdMutex<FrontPool>>,
+    /// 故障切换触发器
+    failover_trigger: mpsc::UnboundedSender<()>,
+    /// 故障切换请求接收器
+    failover_receiver: Arc<Mutex<mpsc::UnboundedReceiver<()>>>,
+    /// 运行指标
+    metrics: Option<Arc<SessionMetrics>>,
+    /// 已发出报单的跟踪表
+    orders: Arc<OrderTracker>,
+    /// 账户挂单簿
+    working_orders: Arc<WorkingOrders>,
 }
 
 impl AsyncTraderApi {
@@ -143,6 +456,7 @@ impl AsyncTraderApi {
     pub async fn new(flow_path: Option<&str>, is_production_mode: Option<bool>) -> CtpResult<Self> {
         let trader_api = TraderApi::new(flow_path, is_production_mode)?;
         let (event_sender, event_receiver) = mpsc::unbounded_channel();
+        let (failover_trigger, failover_receiver) = mpsc::unbounded_channel();
 
         Ok(Self {
             inner: Arc::new(Mutex::new(trader_api)),
@@ -153,28 +467,228 @@ impl AsyncTraderApi {
             auth_notify: Arc::new(Notify::new()),
             login_notify: Arc::new(Notify::new()),
             pending_requests: Arc::new(Mutex::new(HashMap::new())),
+            flow_path: flow_path.map(str::to_string),
+            is_production_mode,
+            fronts: Arc::new(StdMutex::new(FrontPool::default())),
+            failover_trigger,
+            failover_receiver: Arc::new(Mutex::new(failover_receiver)),
+            metrics: None,
+            orders: Arc::new(OrderTracker::default()),
+            working_orders: Arc::new(WorkingOrders::default()),
         })
     }
 
+    /// 挂接运行指标，应在`init`之前调用
+    pub fn with_metrics(mut self, metrics: Arc<SessionMetrics>) -> Self {
+        self.metrics = Some(metrics);
+        self
+    }
+
     /// 注册前置机地址
     pub async fn register_front(&self, front_address: &str) -> CtpResult<()> {
         let mut api = self.inner.lock().await;
         api.register_front(front_address)
     }
 
-    /// 初始化API
-    pub async fn init(&self) -> CtpResult<()> {
+    /// 按优先顺序注册多个前置地址，并跟踪各前置的连接耗时和断线频率
+    ///
+    /// 连接、断线通过[`AsyncTraderEvent::Failover`]上报；需要自动切换时再调用
+    /// [`AsyncTraderApi::spawn_failover`]
+    pub async fn register_fronts(
+        &self,
+        addresses: &[String],
+        policy: FailoverPolicy,
+    ) -> CtpResult<()> {
+        let pool = FrontPool::new(addresses.iter().cloned(), policy)?;
+        let mut api = self.inner.lock().await;
+        for address in pool.addresses() {
+            api.register_front(address)?;
+        }
+        drop(api);
+
+        *self.lock_fronts() = pool;
+        Ok(())
+    }
+
+    /// 前置地址池的快照，包括各前置的健康状况
+    pub fn front_pool(&self) -> FrontPool {
+        self.lock_fronts().clone()
+    }
+
+    /// 切换到最健康的前置，返回目标前置地址
+    ///
+    /// 释放当前API实例，针对目标前置重新创建并初始化；切换后需要重新认证和登录。
+    /// 旧实例上未完成的请求不会再收到响应
+    pub async fn failover(&self) -> CtpResult<String> {
+        let (from, to) = {
+            let pool = self.lock_fronts();
+            let to = pool.next_front(Instant::now()).ok_or_else(|| {
+                CtpError::InvalidParameterError("未注册前置地址，无法切换".to_string())
+            })?;
+            (pool.current().map(str::to_string), to.to_string())
+        };
+
+        warn!("异步交易API: 切换前置 {:?} -> {}", from, to);
+        let _ = self
+            .event_sink()
+            .send(AsyncTraderEvent::Failover(FailoverEvent::FailingOver {
+                from,
+                to: to.clone(),
+            }));
+
+        match self.recreate(&to).await {
+            Ok(()) => Ok(to),
+            Err(e) => {
+                error!("异步交易API: 切换前置失败: {}", e);
+                let _ = self.event_sink().send(AsyncTraderEvent::Failover(
+                    FailoverEvent::FailoverFailed {
+                        to,
+                        error: e.to_string(),
+                    },
+                ));
+                Err(e)
+            }
+        }
+    }
+
+    /// 启动自动故障切换任务
+    ///
+    /// 回调判断当前前置达到切换条件时，任务调用[`AsyncTraderApi::failover`]。任务只持有
+    /// 弱引用，API释放后自动结束
+    pub fn spawn_failover(self: &Arc<Self>) -> tokio::task::JoinHandle<()> {
+        let api = Arc::downgrade(self);
+        let receiver = self.failover_receiver.clone();
+        tokio::spawn(async move {
+            let mut receiver = receiver.lock().await;
+            while receiver.recv().await.is_some() {
+                let Some(api) = api.upgrade() else {
+                    break;
+                };
+                // 失败已通过事件上报，等待下一次触发
+                let _ = api.failover().await;
+            }
+        })
+    }
+
+    // 用新的API实例替换当前实例，只注册目标前置
+    async fn recreate(&self, front: &str) -> CtpResult<()> {
         let mut api = self.inner.lock().await;
+        let compliance = api.compliance_monitor();
+        let latency = api.latency_recorder();
+        let journal = api.journal();
+        let order_refs = api.order_ref_allocator();
 
-        // 创建异步事件处理器
-        let handler = AsyncTraderHandler::new(
-            self.event_sender.clone(),
+        // 先释放旧实例，解除流文件目录锁
+        api.release();
+        // 回调以TraderApi自身的地址作为上下文，必须先放到最终位置再注册处理器
+        *api = TraderApi::new(self.flow_path.as_deref(), self.is_production_mode)?;
+        if let Some(monitor) = compliance {
+            api.set_compliance_monitor(monitor);
+        }
+        if let Some(recorder) = latency {
+            api.set_latency_recorder(recorder);
+        }
+        if let Some(journal) = journal {
+            api.set_journal(journal);
+        }
+        // 沿用同一个分配器，重新登录后引用不会回退
+        api.set_order_ref_allocator(order_refs);
+        api.register_front(front)?;
+
+        self.pending_requests.lock().await.clear();
+        self.orders.reset_requests();
+        *self.state.lock().await = AsyncTraderState::default();
+        self.lock_fronts()
+            .begin_connect(Some(front), Instant::now());
+
+        let handler = self.handler(&api);
+        api.register_spi(handler)?;
+        api.init()
+    }
+
+    fn lock_fronts(&self) -> std::sync::MutexGuard<'_, FrontPool> {
+        // 池中只有统计数据，持锁线程panic后继续使用不会破坏一致性
+        self.fronts.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    // 挂接指标时事件在进入队列前先更新指标
+    fn event_sink(&self) -> Arc<dyn EventSink<AsyncTraderEvent>> {
+        match &self.metrics {
+            Some(metrics) => Arc::new(ObservedSink::new(
+                self.event_sender.clone(),
+                metrics.clone(),
+            )),
+            None => Arc::new(self.event_sender.clone()),
+        }
+    }
+
+    fn handler(&self, api: &TraderApi) -> AsyncTraderHandler {
+        AsyncTraderHandler::new(
+            self.event_sink(),
             self.state.clone(),
             self.connected_notify.clone(),
             self.auth_notify.clone(),
             self.login_notify.clone(),
             self.pending_requests.clone(),
-        );
+            FailoverHook {
+                fronts: self.fronts.clone(),
+                probe: api.front_info_probe(),
+                trigger: self.failover_trigger.clone(),
+            },
+        )
+        .with_orders(self.orders.clone())
+        .with_working_orders(self.working_orders.clone())
+    }
+
+    /// 替换报单引用分配器，故障切换重建API时会保留
+    pub async fn set_order_ref_allocator(&self, allocator: Arc<OrderRefAllocator>) {
+        let mut api = self.inner.lock().await;
+        api.set_order_ref_allocator(allocator);
+    }
+
+    /// 获取报单引用分配器，可用于手动分配引用或为策略预留引用范围
+    pub async fn order_ref_allocator(&self) -> Arc<OrderRefAllocator> {
+        self.inner.lock().await.order_ref_allocator()
+    }
+
+    /// 挂接审计日志，故障切换重建API时会保留
+    pub async fn set_journal(&self, journal: Arc<StdMutex<AuditJournal>>) {
+        let mut api = self.inner.lock().await;
+        api.set_journal(journal);
+    }
+
+    /// 挂接延迟记录器，故障切换重建API时会保留
+    pub async fn set_latency_recorder(&self, recorder: Arc<StdMutex<LatencyRecorder>>) {
+        let mut api = self.inner.lock().await;
+        api.set_latency_recorder(recorder);
+    }
+
+    /// 注册名字服务器网络地址
+    pub async fn register_name_server(&self, ns_address: &str) -> CtpResult<()> {
+        let mut api = self.inner.lock().await;
+        api.register_name_server(ns_address)
+    }
+
+    /// 注册名字服务器用户信息
+    pub async fn register_fens_user_info(&self, user_info: &FensUserInfo) -> CtpResult<()> {
+        let mut api = self.inner.lock().await;
+        api.register_fens_user_info(user_info)
+    }
+
+    /// 获取当前连接的前置信息，并更新到状态中
+    pub async fn front_info(&self) -> CtpResult<FrontInfo> {
+        let front_info = self.inner.lock().await.front_info()?;
+        self.state.lock().await.front_info = Some(front_info.clone());
+        Ok(front_info)
+    }
+
+    /// 初始化API
+    pub async fn init(&self) -> CtpResult<()> {
+        let mut api = self.inner.lock().await;
+
+        // 创建异步事件处理器
+        let handler = self.handler(&api);
+        self.lock_fronts().begin_connect(None, Instant::now());
 
         // 注册处理器
         api.register_spi(handler)?;
@@ -199,11 +713,13 @@ impl AsyncTraderApi {
         {
             Ok(_) => {
                 let state = self.state.lock().await;
-                if state.connected {
-                    Ok(())
-                } else {
-                    Err(CtpError::InitializationError("连接失败".to_string()))
+                if !state.connected {
+                    return Err(CtpError::InitializationError("连接失败".to_string()));
                 }
+                drop(state);
+                // 注册多个前置或通过名字服务器连接时，实际前置地址只有连接后才能知道
+                self.front_info().await?;
+                Ok(())
             }
             Err(_) => Err(CtpError::InitializationError("连接超时".to_string())),
         }
@@ -263,77 +779,924 @@ impl AsyncTraderApi {
         let request_id = api.req_user_login(req)?;
         drop(api);
 
-        let pending_request = PendingRequest {
-            notify: Arc::new(Notify::new()),
-            response_data: Arc::new(Mutex::new(None)),
-        };
-
-        // 注册待处理请求
-        {
-            let mut pending = self.pending_requests.lock().await;
-            pending.insert(request_id, pending_request.clone());
-        }
-
-        match timeout(
-            Duration::from_secs(timeout_secs),
-            pending_request.notify.notified(),
-        )
-        .await
-        {
-            Ok(_) => {
-                let state = self.state.lock().await;
-                if let Some(login_info) = &state.login_info {
-                    Ok(login_info.clone())
-                } else {
-                    Err(CtpError::InitializationError("登录失败".to_string()))
-                }
-            }
-            Err(_) => {
-                // 清理待处理请求
-                let mut pending = self.pending_requests.lock().await;
-                pending.remove(&request_id);
-                Err(CtpError::InitializationError("登录超时".to_string()))
-            }
-        }
+        self.wait_for_login(request_id, timeout_secs).await
     }
 
-    /// 异步报单录入
-    pub async fn order_insert(
+    /// 中继多连接模式登录
+    ///
+    /// 在终端认证成功后先注册终端客户的系统信息，再发送登录请求
+    pub async fn relay_login(
         &self,
-        req: &InputOrderField,
+        system_info: &UserSystemInfo,
+        req: &ReqUserLoginField,
+        timeout_secs: u64,
+    ) -> CtpResult<RspUserLoginField> {
+        if !self.state.lock().await.authenticated {
+            return Err(CtpError::InitializationError(
+                "注册终端信息前需要先完成客户端认证".to_string(),
+            ));
+        }
+
+        let field = system_info.to_field()?;
+        let mut api = self.inner.lock().await;
+        api.register_user_system_info(&field)?;
+        let request_id = api.req_user_login(req)?;
+        drop(api);
+
+        self.wait_for_login(request_id, timeout_secs).await
+    }
+
+    /// 中继操作员登录模式下上报终端客户的系统信息，操作员登录后可以多次调用
+    pub async fn submit_user_system_info(&self, system_info: &UserSystemInfo) -> CtpResult<()> {
+        if !self.state.lock().await.logged_in {
+            return Err(CtpError::InitializationError(
+                "上报终端信息前需要先登录".to_string(),
+            ));
+        }
+
+        let field = system_info.to_field()?;
+        let mut api = self.inner.lock().await;
+        api.submit_user_system_info(&field)
+    }
+
+    /// 查询用户当前支持的认证模式
+    pub async fn user_auth_method(
+        &self,
+        broker_id: &str,
+        user_id: &str,
+        timeout_secs: u64,
+    ) -> CtpResult<RspUserAuthMethodField> {
+        let req = ReqUserAuthMethodField::new(broker_id, user_id)?;
+        let mut api = self.inner.lock().await;
+        let request_id = api.req_user_auth_method(&req)?;
+        drop(api);
+
+        match self.wait_for_response(request_id, timeout_secs).await? {
+            AsyncTraderEvent::UserAuthMethodResponse {
+                rsp_user_auth_method,
+                rsp_info,
+                ..
+            } => Self::response_payload(rsp_user_auth_method, rsp_info),
+            event => Err(Self::unexpected_response(event)),
+        }
+    }
+
+    /// 获取图形验证码
+    pub async fn gen_user_captcha(
+        &self,
+        broker_id: &str,
+        user_id: &str,
+        timeout_secs: u64,
+    ) -> CtpResult<RspGenUserCaptchaField> {
+        let req = ReqGenUserCaptchaField::new(broker_id, user_id)?;
+        let mut api = self.inner.lock().await;
+        let request_id = api.req_gen_user_captcha(&req)?;
+        drop(api);
+
+        match self.wait_for_response(request_id, timeout_secs).await? {
+            AsyncTraderEvent::GenUserCaptchaResponse {
+                rsp_gen_user_captcha,
+                rsp_info,
+                ..
+            } => Self::response_payload(rsp_gen_user_captcha, rsp_info).map(|captcha| *captcha),
+            event => Err(Self::unexpected_response(event)),
+        }
+    }
+
+    /// 获取短信验证码，返回验证码序号
+    pub async fn gen_user_text(
+        &self,
+        broker_id: &str,
+        user_id: &str,
+        timeout_secs: u64,
+    ) -> CtpResult<RspGenUserTextField> {
+        let req = ReqGenUserTextField::new(broker_id, user_id)?;
+        let mut api = self.inner.lock().await;
+        let request_id = api.req_gen_user_text(&req)?;
+        drop(api);
+
+        match self.wait_for_response(request_id, timeout_secs).await? {
+            AsyncTraderEvent::GenUserTextResponse {
+                rsp_gen_user_text,
+                rsp_info,
+                ..
+            } => Self::response_payload(rsp_gen_user_text, rsp_info),
+            event => Err(Self::unexpected_response(event)),
+        }
+    }
+
+    /// 带图形验证码登录
+    pub async fn login_with_captcha(
+        &self,
+        req: &ReqUserLoginWithCaptchaField,
+        timeout_secs: u64,
+    ) -> CtpResult<RspUserLoginField> {
+        let mut api = self.inner.lock().await;
+        let request_id = api.req_user_login_with_captcha(req)?;
+        drop(api);
+
+        self.wait_for_login(request_id, timeout_secs).await
+    }
+
+    /// 带短信验证码登录
+    pub async fn login_with_text(
+        &self,
+        req: &ReqUserLoginWithTextField,
+        timeout_secs: u64,
+    ) -> CtpResult<RspUserLoginField> {
+        let mut api = self.inner.lock().await;
+        let request_id = api.req_user_login_with_text(req)?;
+        drop(api);
+
+        self.wait_for_login(request_id, timeout_secs).await
+    }
+
+    /// 带动态口令登录
+    pub async fn login_with_otp(
+        &self,
+        req: &ReqUserLoginWithOtpField,
+        timeout_secs: u64,
+    ) -> CtpResult<RspUserLoginField> {
+        let mut api = self.inner.lock().await;
+        let request_id = api.req_user_login_with_otp(req)?;
+        drop(api);
+
+        self.wait_for_login(request_id, timeout_secs).await
+    }
+
+    /// 按柜台要求的认证模式登录
+    ///
+    /// 先查询用户当前支持的认证模式：无需额外验证时按普通方式登录；否则按图形验证码、
+    /// 动态口令、短信验证码的顺序选择第一个可用的模式，获取验证码后交给`provider`，
+    /// 由它返回用户输入的验证码或动态口令
+    pub async fn login_with_verification<F, Fut>(
+        &self,
+        req: &ReqUserLoginField,
+        provider: F,
+        timeout_secs: u64,
+    ) -> CtpResult<RspUserLoginField>
+    where
+        F: FnOnce(VerificationChallenge) -> Fut,
+        Fut: Future<Output = CtpResult<String>>,
+    {
+        let broker_id = req.broker_id.to_utf8_string()?;
+        let user_id = req.user_id.to_utf8_string()?;
+        let methods = self
+            .user_auth_method(&broker_id, &user_id, timeout_secs)
+            .await?;
+        debug!("用户可用认证模式: {}", methods.usable_auth_method);
+
+        if !methods.requires_verification() {
+            self.login(req, timeout_secs).await
+        } else if methods.supports_captcha() {
+            let captcha = self
+                .gen_user_captcha(&broker_id, &user_id, timeout_secs)
+                .await?;
+            let image = captcha.captcha_image().to_vec();
+            let code = provider(VerificationChallenge::Captcha(image)).await?;
+            let req = ReqUserLoginWithCaptchaField::from_login(req, &code)?;
+            self.login_with_captcha(&req, timeout_secs).await
+        } else if methods.supports_otp() {
+            let code = provider(VerificationChallenge::Otp).await?;
+            let req = ReqUserLoginWithOtpField::from_login(req, &code)?;
+            self.login_with_otp(&req, timeout_secs).await
+        } else if methods.supports_text() {
+            let text = self
+                .gen_user_text(&broker_id, &user_id, timeout_secs)
+                .await?;
+            let code = provider(VerificationChallenge::Text {
+                user_text_seq: text.user_text_seq,
+            })
+            .await?;
+            let req = ReqUserLoginWithTextField::from_login(req, &code)?;
+            self.login_with_text(&req, timeout_secs).await
+        } else {
+            Err(CtpError::InitializationError(format!(
+                "不支持的认证模式: {}",
+                methods.usable_auth_method
+            )))
+        }
+    }
+
+    fn check_rsp_info(rsp_info: Option<RspInfoField>) -> CtpResult<()> {
+        match rsp_info {
+            Some(rsp) if !rsp.is_success() => Err(CtpError::BusinessError(
+                rsp.error_id,
+                rsp.get_error_msg().unwrap_or_default(),
+            )),
+            _ => Ok(()),
+        }
+    }
+
+    // 先检查业务错误，柜台拒绝时响应数据通常为空
+    fn response_payload<T>(payload: Option<T>, rsp_info: Option<RspInfoField>) -> CtpResult<T> {
+        Self::check_rsp_info(rsp_info)?;
+        payload.ok_or_else(|| CtpError::InitializationError("响应数据为空".to_string()))
+    }
+
+    fn unexpected_response(event: AsyncTraderEvent) -> CtpError {
+        match event {
+            AsyncTraderEvent::ErrorResponse {
+                rsp_info: Some(rsp),
+                ..
+            } => CtpError::BusinessError(rsp.error_id, rsp.get_error_msg().unwrap_or_default()),
+            _ => CtpError::InitializationError("响应数据为空".to_string()),
+        }
+    }
+
+    // 各种登录请求都通过OnRspUserLogin应答
+    async fn wait_for_login(
+        &self,
+        request_id: i32,
+        timeout_secs: u64,
+    ) -> CtpResult<RspUserLoginField> {
+        let pending_request = PendingRequest {
+            notify: Arc::new(Notify::new()),
+            response_data: Arc::new(Mutex::new(None)),
+        };
+
+        // 注册待处理请求
+        {
+            let mut pending = self.pending_requests.lock().await;
+            pending.insert(request_id, pending_request.clone());
+        }
+
+        match timeout(
+            Duration::from_secs(timeout_secs),
+            pending_request.notify.notified(),
+        )
+        .await
+        {
+            Ok(_) => {
+                let state = self.state.lock().await;
+                if let Some(login_info) = &state.login_info {
+                    Ok(login_info.clone())
+                } else {
+                    Err(CtpError::InitializationError("登录失败".to_string()))
+                }
+            }
+            Err(_) => {
+                // 清理待处理请求
+                let mut pending = self.pending_requests.lock().await;
+                pending.remove(&request_id);
+                Err(CtpError::InitializationError("登录超时".to_string()))
+            }
+        }
+    }
+
+    /// 异步报单录入，返回跟踪报单进展的句柄
+    ///
+    /// 报单引用为空时先由报单引用分配器填写，句柄据此匹配报单回报和成交回报；未登录时返回错误
+    pub async fn order_insert(&self, req: &InputOrderField) -> CtpResult<OrderHandle> {
+        let (front_id, session_id) = match &self.state.lock().await.login_info {
+            Some(login) => (login.front_id, login.session_id),
+            None => {
+                return Err(CtpError::InitializationError(
+                    "报单前需要先登录".to_string(),
+                ))
+            }
+        };
+
+        let mut req = req.clone();
+        let mut api = self.inner.lock().await;
+        api.order_ref_allocator().fill_order(&mut req)?;
+        let order_ref = req.order_ref.to_utf8_string()?;
+        let (request_id, snapshot, updates) =
+            self.orders.track(front_id, session_id, &order_ref, || {
+                api.req_order_insert(&req)
+            })?;
+        drop(api);
+        if let Some(metrics) = &self.metrics {
+            metrics.on_order_insert();
+        }
+
+        Ok(OrderHandle::new(
+            request_id,
+            req,
+            front_id,
+            session_id,
+            Arc::downgrade(&self.inner),
+            snapshot,
+            updates,
+        ))
+    }
+
+    /// 异步报单操作
+    pub async fn order_action(
+        &self,
+        req: &InputOrderActionField,
         timeout_secs: u64,
     ) -> CtpResult<AsyncTraderEvent> {
         let mut api = self.inner.lock().await;
-        let request_id = api.req_order_insert(req)?;
+        let request_id = api.req_order_action(req)?;
+        drop(api);
+        if let Some(metrics) = &self.metrics {
+            metrics.on_order_action();
+        }
+
+        self.wait_for_response(request_id, timeout_secs).await
+    }
+
+    /// 异步查询报单
+    pub async fn qry_order(
+        &self,
+        req: &QryOrderField,
+        timeout_secs: u64,
+    ) -> CtpResult<Vec<OrderField>> {
+        let mut api = self.inner.lock().await;
+        let request_id = api.req_qry_order(req)?;
+        drop(api);
+
+        // 收集所有响应数据
+        let mut results = Vec::new();
+        let mut is_finished = false;
+
+        let start_time = std::time::Instant::now();
+        let timeout_duration = Duration::from_secs(timeout_secs);
+
+        while !is_finished && start_time.elapsed() < timeout_duration {
+            if let Some(event) = self.recv_event().await {
+                match event {
+                    AsyncTraderEvent::QryOrderResponse {
+                        order,
+                        rsp_info,
+                        request_id: resp_id,
+                        is_last,
+                    } if resp_id == request_id => {
+                        if let Some(rsp) = rsp_info {
+                            if !rsp.is_success() {
+                                return Err(CtpError::BusinessError(
+                                    rsp.error_id,
+                                    rsp.get_error_msg().unwrap_or_default(),
+                                ));
+                            }
+                        }
+                        if let Some(order) = order {
+                            results.push(order);
+                        }
+                        is_finished = is_last;
+                    }
+                    _ => continue,
+                }
+            }
+        }
+
+        if is_finished {
+            Ok(results)
+        } else {
+            Err(CtpError::InitializationError("查询超时".to_string()))
+        }
+    }
+
+    /// 查询报单并重建挂单簿，返回挂单数量
+    ///
+    /// 登录后调用一次，之后挂单簿随报单回报更新。查询期间收到的报单回报会与查询结果合并，
+    /// 查询失败时挂单簿保持不变
+    pub async fn refresh_working_orders(
+        &self,
+        req: &QryOrderField,
+        timeout_secs: u64,
+    ) -> CtpResult<usize> {
+        let sync = self.working_orders.begin_sync();
+        let orders = self.qry_order(req, timeout_secs).await?;
+        sync.apply(orders);
+        Ok(self.working_orders.len())
+    }
+
+    /// 当前挂单
+    pub fn working_orders(&self) -> Vec<OrderField> {
+        self.working_orders.orders()
+    }
+
+    /// 撤销挂单簿中满足条件的全部挂单
+    ///
+    /// 按前置的FTD流控逐笔发出撤单，全部发出后最多等待`timeout_secs`秒收集结果。
+    /// 挂单簿需先通过[`AsyncTraderApi::refresh_working_orders`]初始化
+    pub async fn cancel_orders(
+        &self,
+        filter: &CancelFilter,
+        timeout_secs: u64,
+    ) -> CtpResult<MassCancelReport> {
+        let state = self.state.lock().await.clone();
+        let login = state
+            .login_info
+            .ok_or_else(|| CtpError::InitializationError("撤单前需要先登录".to_string()))?;
+        let user_id = login.user_id.to_utf8_string()?;
+        let interval = pacing_interval(state.front_info.map_or(0, |front| front.ftd_pkg_freq));
+        let allocator = self.inner.lock().await.order_ref_allocator();
+
+        let targets: Vec<OrderField> = self
+            .working_orders
+            .orders()
+            .into_iter()
+            .filter(|order| {
+                // 策略只能从本会话分配的报单引用识别
+                let own = order.front_id == login.front_id && order.session_id == login.session_id;
+                let strategy = match filter.strategy() {
+                    Some(_) if own => allocator.strategy_of(&field_string(&order.order_ref)),
+                    _ => None,
+                };
+                filter.matches(order, strategy.as_deref())
+            })
+            .collect();
+
+        let mut results = Vec::with_capacity(targets.len());
+        for (i, order) in targets.into_iter().enumerate() {
+            if i > 0 {
+                tokio::time::sleep(interval).await;
+            }
+            // 先登记再发送，撤单结果可能先于请求返回到达
+            self.working_orders.begin_cancel(&order);
+            let sent = match cancel_request(&order, &user_id) {
+                Ok(req) => self.inner.lock().await.req_order_action(&req),
+                Err(e) => Err(e),
+            };
+            let (request_id, outcome) = match sent {
+                Ok(request_id) => {
+                    if let Some(metrics) = &self.metrics {
+                        metrics.on_order_action();
+                    }
+                    (Some(request_id), CancelOutcome::Pending)
+                }
+                Err(e) => {
+                    warn!("异步交易API: 撤单请求发送失败: {}", e);
+                    (None, CancelOutcome::SendFailed(e))
+                }
+            };
+            results.push(CancelResult {
+                order,
+                request_id,
+                outcome,
+            });
+        }
+
+        let deadline = tokio::time::Instant::now() + Duration::from_secs(timeout_secs);
+        self.working_orders
+            .wait_cancels(&mut results, deadline)
+            .await;
+        Ok(MassCancelReport { results })
+    }
+
+    /// 以执行算法拆分母单
+    ///
+    /// `template`给出合约、买卖方向、开平和投机套保标志，`volume_total_original`为母单数量，
+    /// `limit_price`为可接受的最差价格。子单通过[`AsyncTraderApi::order_insert`]发出
+    pub async fn execute(
+        self: &Arc<Self>,
+        template: &InputOrderField,
+        algo: ExecutionAlgo,
+        config: ExecutionConfig,
+    ) -> CtpResult<ExecutionHandle> {
+        execution::spawn(self, template, algo, config).await
+    }
+
+    /// 异步查询交易账户
+    pub async fn qry_trading_account(
+        &self,
+        req: &QryTradingAccountField,
+        timeout_secs: u64,
+    ) -> CtpResult<Vec<TradingAccountField>> {
+        let mut api = self.inner.lock().await;
+        let request_id = api.req_qry_trading_account(req)?;
+        drop(api);
+
+        // 收集所有响应数据
+        let mut results = Vec::new();
+        let mut is_finished = false;
+
+        let start_time = std::time::Instant::now();
+        let timeout_duration = Duration::from_secs(timeout_secs);
+
+        while !is_finished && start_time.elapsed() < timeout_duration {
+            if let Some(event) = self.recv_event().await {
+                match event {
+                    AsyncTraderEvent::QryTradingAccountResponse {
+                        trading_account,
+                        rsp_info,
+                        request_id: resp_id,
+                        is_last,
+                    } if resp_id == request_id => {
+                        if let Some(rsp) = rsp_info {
+                            if !rsp.is_success() {
+                                return Err(CtpError::BusinessError(
+                                    rsp.error_id,
+                                    rsp.get_error_msg().unwrap_or_default(),
+                                ));
+                            }
+                        }
+                        if let Some(account) = trading_account {
+                            results.push(account);
+                        }
+                        is_finished = is_last;
+                    }
+                    _ => continue,
+                }
+            }
+        }
+
+        if is_finished {
+            Ok(results)
+        } else {
+            Err(CtpError::InitializationError("查询超时".to_string()))
+        }
+    }
+
+    /// 异步查询投资者持仓
+    pub async fn qry_investor_position(
+        &self,
+        req: &QryInvestorPositionField,
+        timeout_secs: u64,
+    ) -> CtpResult<Vec<InvestorPositionField>> {
+        let mut api = self.inner.lock().await;
+        let request_id = api.req_qry_investor_position(req)?;
+        drop(api);
+
+        // 收集所有响应数据
+        let mut results = Vec::new();
+        let mut is_finished = false;
+
+        let start_time = std::time::Instant::now();
+        let timeout_duration = Duration::from_secs(timeout_secs);
+
+        while !is_finished && start_time.elapsed() < timeout_duration {
+            if let Some(event) = self.recv_event().await {
+                match event {
+                    AsyncTraderEvent::QryInvestorPositionResponse {
+                        investor_position,
+                        rsp_info,
+                        request_id: resp_id,
+                        is_last,
+                    } if resp_id == request_id => {
+                        if let Some(rsp) = rsp_info {
+                            if !rsp.is_success() {
+                                return Err(CtpError::BusinessError(
+                                    rsp.error_id,
+                                    rsp.get_error_msg().unwrap_or_default(),
+                                ));
+                            }
+                        }
+                        if let Some(position) = investor_position {
+                            results.push(position);
+                        }
+                        is_finished = is_last;
+                    }
+                    _ => continue,
+                }
+            }
+        }
+
+        if is_finished {
+            Ok(results)
+        } else {
+            Err(CtpError::InitializationError("查询超时".to_string()))
+        }
+    }
+
+    /// 异步查询合约
+    pub async fn qry_instrument(
+        &self,
+        req: &QryInstrumentField,
+        timeout_secs: u64,
+    ) -> CtpResult<Vec<InstrumentField>> {
+        let mut api = self.inner.lock().await;
+        let request_id = api.req_qry_instrument(req)?;
+        drop(api);
+
+        // 收集所有响应数据
+        let mut results = Vec::new();
+        let mut is_finished = false;
+
+        let start_time = std::time::Instant::now();
+        let timeout_duration = Duration::from_secs(timeout_secs);
+
+        while !is_finished && start_time.elapsed() < timeout_duration {
+            if let Some(event) = self.recv_event().await {
+                match event {
+                    AsyncTraderEvent::QryInstrumentResponse {
+                        instrument,
+                        rsp_info,
+                        request_id: resp_id,
+                        is_last,
+                    } if resp_id == request_id => {
+                        if let Some(rsp) = rsp_info {
+                            if !rsp.is_success() {
+                                return Err(CtpError::BusinessError(
+                                    rsp.error_id,
+                                    rsp.get_error_msg().unwrap_or_default(),
+                                ));
+                            }
+                        }
+                        if let Some(instrument) = instrument {
+                            results.push(instrument);
+                        }
+                        is_finished = is_last;
+                    }
+                    _ => continue,
+                }
+            }
+        }
+
+        if is_finished {
+            Ok(results)
+        } else {
+            Err(CtpError::InitializationError("查询超时".to_string()))
+        }
+    }
+
+    /// 异步查询结算单
+    ///
+    /// 收齐所有分片后拼接字节再解码，避免多字节字符被分片截断
+    pub async fn qry_settlement_statement(
+        &self,
+        req: &QrySettlementInfoField,
+        timeout_secs: u64,
+    ) -> CtpResult<SettlementStatement> {
+        let mut api = self.inner.lock().await;
+        let request_id = api.req_qry_settlement_info(req)?;
+        drop(api);
+
+        let mut assembler = SettlementAssembler::new();
+        let mut is_finished = false;
+
+        let start_time = std::time::Instant::now();
+        let timeout_duration = Duration::from_secs(timeout_secs);
+
+        while !is_finished && start_time.elapsed() < timeout_duration {
+            if let Some(event) = self.recv_event().await {
+                match event {
+                    AsyncTraderEvent::QrySettlementInfoResponse {
+                        settlement_info,
+                        rsp_info,
+                        request_id: resp_id,
+                        is_last,
+                    } if resp_id == request_id => {
+                        if let Some(rsp) = rsp_info {
+                            if !rsp.is_success() {
+                                return Err(CtpError::BusinessError(
+                                    rsp.error_id,
+                                    rsp.get_error_msg().unwrap_or_default(),
+                                ));
+                            }
+                        }
+                        if let Some(info) = settlement_info {
+                            assembler.push(&info);
+                        }
+                        is_finished = is_last;
+                    }
+                    _ => continue,
+                }
+            }
+        }
+
+        if !is_finished {
+            return Err(CtpError::InitializationError("查询超时".to_string()));
+        }
+
+        let mut statement = assembler.finish()?;
+        if statement.trading_day.is_empty() {
+            statement.trading_day = req.trading_day.to_utf8_string().unwrap_or_default();
+        }
+        if statement.investor_id.is_empty() {
+            statement.investor_id = req.investor_id.to_string();
+        }
+        Ok(statement)
+    }
+
+    /// 获取结算单、可选归档原文，然后确认结算单
+    pub async fn confirm_settlement(
+        &self,
+        broker_id: &str,
+        investor_id: &str,
+        archive_dir: Option<&Path>,
+        timeout_secs: u64,
+    ) -> CtpResult<SettlementStatement> {
+        let qry = QrySettlementInfoField::new(broker_id, investor_id)?;
+        let statement = self.qry_settlement_statement(&qry, timeout_secs).await?;
+
+        if let Some(dir) = archive_dir {
+            let path = statement.archive(dir)?;
+            debug!("结算单已归档: {}", path.display());
+        }
+
+        let confirm = SettlementInfoConfirmField::new(broker_id, investor_id)?;
+        let mut api = self.inner.lock().await;
+        let request_id = api.req_settlement_info_confirm(&confirm)?;
         drop(api);
 
-        self.wait_for_response(request_id, timeout_secs).await
+        match self.wait_for_response(request_id, timeout_secs).await? {
+            AsyncTraderEvent::SettlementInfoConfirmResponse {
+                rsp_info: Some(rsp),
+                ..
+            }
+            | AsyncTraderEvent::ErrorResponse {
+                rsp_info: Some(rsp),
+                ..
+            } if !rsp.is_success() => Err(CtpError::BusinessError(
+                rsp.error_id,
+                rsp.get_error_msg().unwrap_or_default(),
+            )),
+            _ => Ok(statement),
+        }
     }
 
-    /// 异步报单操作
-    pub async fn order_action(
+    /// 银行资金转期货
+    ///
+    /// 银行处理完成后返回[`TransferOutcome::Completed`]，被冲正时返回
+    /// [`TransferOutcome::Repealed`]，前置拒绝或银行返回错误时返回业务错误
+    pub async fn bank_to_future(
         &self,
-        req: &InputOrderActionField,
+        req: &ReqTransferField,
         timeout_secs: u64,
-    ) -> CtpResult<AsyncTraderEvent> {
+    ) -> CtpResult<TransferOutcome> {
+        self.transfer(TransferDirection::BankToFuture, req, timeout_secs)
+            .await
+    }
+
+    /// 期货资金转银行
+    ///
+    /// 结果含义同[`AsyncTraderApi::bank_to_future`]
+    pub async fn future_to_bank(
+        &self,
+        req: &ReqTransferField,
+        timeout_secs: u64,
+    ) -> CtpResult<TransferOutcome> {
+        self.transfer(TransferDirection::FutureToBank, req, timeout_secs)
+            .await
+    }
+
+    async fn transfer(
+        &self,
+        direction: TransferDirection,
+        req: &ReqTransferField,
+        timeout_secs: u64,
+    ) -> CtpResult<TransferOutcome> {
+        if req.direction() != Some(direction) {
+            return Err(CtpError::InvalidParameterError(format!(
+                "业务功能码与转帐方向不一致，应为 {}",
+                direction.trade_code()
+            )));
+        }
+
         let mut api = self.inner.lock().await;
-        let request_id = api.req_order_action(req)?;
+        let request_id = match direction {
+            TransferDirection::BankToFuture => api.req_from_bank_to_future_by_future(req)?,
+            TransferDirection::FutureToBank => api.req_from_future_to_bank_by_future(req)?,
+        };
         drop(api);
 
-        self.wait_for_response(request_id, timeout_secs).await
+        let deadline = tokio::time::Instant::now() + Duration::from_secs(timeout_secs);
+        while let Ok(Some(event)) = tokio::time::timeout_at(deadline, self.recv_event()).await {
+            match event {
+                AsyncTraderEvent::TransferResponse {
+                    rsp_info: Some(rsp),
+                    request_id: resp_id,
+                    ..
+                } if resp_id == request_id && !rsp.is_success() => {
+                    return Err(CtpError::BusinessError(
+                        rsp.error_id,
+                        rsp.get_error_msg().unwrap_or_default(),
+                    ));
+                }
+                AsyncTraderEvent::TransferErrorReturn {
+                    req_transfer: Some(transfer),
+                    rsp_info,
+                } if transfer.request_id == request_id => {
+                    let rsp = rsp_info.unwrap_or_default();
+                    return Err(CtpError::BusinessError(
+                        rsp.error_id,
+                        rsp.get_error_msg().unwrap_or_default(),
+                    ));
+                }
+                AsyncTraderEvent::TransferReturn(rsp) if rsp.request_id == request_id => {
+                    if rsp.error_id != 0 {
+                        return Err(CtpError::BusinessError(
+                            rsp.error_id,
+                            rsp.error_msg.to_utf8_string().unwrap_or_default(),
+                        ));
+                    }
+                    return Ok(TransferOutcome::Completed(rsp));
+                }
+                AsyncTraderEvent::TransferRepealReturn(rsp) if rsp.request_id == request_id => {
+                    return Ok(TransferOutcome::Repealed(rsp));
+                }
+                _ => continue,
+            }
+        }
+
+        Err(CtpError::InitializationError("转帐超时".to_string()))
     }
 
-    /// 异步查询交易账户
-    pub async fn qry_trading_account(
+    /// 查询银行余额
+    pub async fn query_bank_balance(
         &self,
-        req: &QryTradingAccountField,
+        req: &ReqQueryAccountField,
         timeout_secs: u64,
-    ) -> CtpResult<Vec<TradingAccountField>> {
+    ) -> CtpResult<NotifyQueryAccountField> {
         let mut api = self.inner.lock().await;
-        let request_id = api.req_qry_trading_account(req)?;
+        let request_id = api.req_query_bank_account_money_by_future(req)?;
+        drop(api);
+
+        let deadline = tokio::time::Instant::now() + Duration::from_secs(timeout_secs);
+        while let Ok(Some(event)) = tokio::time::timeout_at(deadline, self.recv_event()).await {
+            match event {
+                AsyncTraderEvent::QueryBankAccountResponse {
+                    rsp_info: Some(rsp),
+                    request_id: resp_id,
+                    ..
+                } if resp_id == request_id && !rsp.is_success() => {
+                    return Err(CtpError::BusinessError(
+                        rsp.error_id,
+                        rsp.get_error_msg().unwrap_or_default(),
+                    ));
+                }
+                AsyncTraderEvent::QueryBankBalanceErrorReturn {
+                    req_query_account: Some(query),
+                    rsp_info,
+                } if query.request_id == request_id => {
+                    let rsp = rsp_info.unwrap_or_default();
+                    return Err(CtpError::BusinessError(
+                        rsp.error_id,
+                        rsp.get_error_msg().unwrap_or_default(),
+                    ));
+                }
+                AsyncTraderEvent::QueryBankBalanceReturn(notify)
+                    if notify.request_id == request_id =>
+                {
+                    if notify.error_id != 0 {
+                        return Err(CtpError::BusinessError(
+                            notify.error_id,
+                            notify.error_msg.to_utf8_string().unwrap_or_default(),
+                        ));
+                    }
+                    return Ok(notify);
+                }
+                _ => continue,
+            }
+        }
+
+        Err(CtpError::InitializationError("查询超时".to_string()))
+    }
+
+    /// 异步查询转帐流水，返回按时间排序的转帐历史
+    pub async fn qry_transfer_serial(
+        &self,
+        req: &QryTransferSerialField,
+        timeout_secs: u64,
+    ) -> CtpResult<TransferHistory> {
+        let mut api = self.inner.lock().await;
+        let request_id = api.req_qry_transfer_serial(req)?;
+        drop(api);
+
+        let mut history = TransferHistory::new();
+        let mut is_finished = false;
+
+        let start_time = std::time::Instant::now();
+        let timeout_duration = Duration::from_secs(timeout_secs);
+
+        while !is_finished && start_time.elapsed() < timeout_duration {
+            if let Some(event) = self.recv_event().await {
+                match event {
+                    AsyncTraderEvent::QryTransferSerialResponse {
+                        transfer_serial,
+                        rsp_info,
+                        request_id: resp_id,
+                        is_last,
+                    } if resp_id == request_id => {
+                        if let Some(rsp) = rsp_info {
+                            if !rsp.is_success() {
+                                return Err(CtpError::BusinessError(
+                                    rsp.error_id,
+                                    rsp.get_error_msg().unwrap_or_default(),
+                                ));
+                            }
+                        }
+                        if let Some(serial) = transfer_serial {
+                            history.push_field(&serial);
+                        }
+                        is_finished = is_last;
+                    }
+                    _ => continue,
+                }
+            }
+        }
+
+        if is_finished {
+            Ok(history)
+        } else {
+            Err(CtpError::InitializationError("查询超时".to_string()))
+        }
+    }
+
+    /// 异步查询签约银行
+    pub async fn qry_contract_bank(
+        &self,
+        req: &QryContractBankField,
+        timeout_secs: u64,
+    ) -> CtpResult<Vec<ContractBankField>> {
+        let mut api = self.inner.lock().await;
+        let request_id = api.req_qry_contract_bank(req)?;
         drop(api);
 
-        // 收集所有响应数据
         let mut results = Vec::new();
         let mut is_finished = false;
 
@@ -343,8 +1706,8 @@ impl AsyncTraderApi {
         while !is_finished && start_time.elapsed() < timeout_duration {
             if let Some(event) = self.recv_event().await {
                 match event {
-                    AsyncTraderEvent::QryTradingAccountResponse {
-                        trading_account,
+                    AsyncTraderEvent::QryContractBankResponse {
+                        contract_bank,
                         rsp_info,
                         request_id: resp_id,
                         is_last,
@@ -357,8 +1720,8 @@ impl AsyncTraderApi {
                                 ));
                             }
                         }
-                        if let Some(account) = trading_account {
-                            results.push(account);
+                        if let Some(bank) = contract_bank {
+                            results.push(bank);
                         }
                         is_finished = is_last;
                     }
@@ -374,17 +1737,16 @@ impl AsyncTraderApi {
         }
     }
 
-    /// 异步查询投资者持仓
-    pub async fn qry_investor_position(
+    /// 异步查询银期签约关系
+    pub async fn qry_accountregister(
         &self,
-        req: &QryInvestorPositionField,
+        req: &QryAccountregisterField,
         timeout_secs: u64,
-    ) -> CtpResult<Vec<InvestorPositionField>> {
+    ) -> CtpResult<Vec<AccountregisterField>> {
         let mut api = self.inner.lock().await;
-        let request_id = api.req_qry_investor_position(req)?;
+        let request_id = api.req_qry_accountregister(req)?;
         drop(api);
 
-        // 收集所有响应数据
         let mut results = Vec::new();
         let mut is_finished = false;
 
@@ -394,8 +1756,8 @@ impl AsyncTraderApi {
         while !is_finished && start_time.elapsed() < timeout_duration {
             if let Some(event) = self.recv_event().await {
                 match event {
-                    AsyncTraderEvent::QryInvestorPositionResponse {
-                        investor_position,
+                    AsyncTraderEvent::QryAccountregisterResponse {
+                        accountregister,
                         rsp_info,
                         request_id: resp_id,
                         is_last,
@@ -408,8 +1770,8 @@ impl AsyncTraderApi {
                                 ));
                             }
                         }
-                        if let Some(position) = investor_position {
-                            results.push(position);
+                        if let Some(register) = accountregister {
+                            results.push(register);
                         }
                         is_finished = is_last;
                     }
@@ -425,6 +1787,78 @@ impl AsyncTraderApi {
         }
     }
 
+    /// 异步更新用户口令
+    pub async fn update_user_password(
+        &self,
+        req: &UserPasswordUpdateField,
+        timeout_secs: u64,
+    ) -> CtpResult<()> {
+        let mut api = self.inner.lock().await;
+        let request_id = api.req_user_password_update(req)?;
+        drop(api);
+
+        let event = self.wait_for_response(request_id, timeout_secs).await?;
+        Self::check_password_update(event)
+    }
+
+    /// 异步更新资金账户口令
+    pub async fn update_trading_account_password(
+        &self,
+        req: &TradingAccountPasswordUpdateField,
+        timeout_secs: u64,
+    ) -> CtpResult<()> {
+        let mut api = self.inner.lock().await;
+        let request_id = api.req_trading_account_password_update(req)?;
+        drop(api);
+
+        let event = self.wait_for_response(request_id, timeout_secs).await?;
+        Self::check_password_update(event)
+    }
+
+    /// 使用配置中的当前密码更新用户口令，成功后写回配置的密码来源
+    ///
+    /// 适用于经纪公司要求的首次登录改密和定期改密。配置了凭证提供者时新口令无法写回，
+    /// 不发送请求直接返回错误，需要在提供者对应的位置改密
+    pub async fn rotate_password(
+        &self,
+        config: &mut CtpConfig,
+        new_password: &str,
+        timeout_secs: u64,
+    ) -> CtpResult<()> {
+        // 先确认新密码能保存，避免柜台已改密而本地仍用旧密码登录
+        config.check_password_writable()?;
+        let credentials = config.credentials()?;
+        let req = UserPasswordUpdateField::new(
+            &config.broker_id,
+            &config.investor_id,
+            credentials.password(),
+            new_password,
+        )?;
+        self.update_user_password(&req, timeout_secs).await?;
+        config.update_password(new_password)
+    }
+
+    fn check_password_update(event: AsyncTraderEvent) -> CtpResult<()> {
+        match event {
+            AsyncTraderEvent::UserPasswordUpdateResponse {
+                rsp_info: Some(rsp),
+                ..
+            }
+            | AsyncTraderEvent::TradingAccountPasswordUpdateResponse {
+                rsp_info: Some(rsp),
+                ..
+            }
+            | AsyncTraderEvent::ErrorResponse {
+                rsp_info: Some(rsp),
+                ..
+            } if !rsp.is_success() => Err(CtpError::BusinessError(
+                rsp.error_id,
+                rsp.get_error_msg().unwrap_or_default(),
+            )),
+            _ => Ok(()),
+        }
+    }
+
     /// 等待指定请求的响应
     async fn wait_for_response(
         &self,
@@ -468,13 +1902,21 @@ impl AsyncTraderApi {
     /// 接收下一个事件
     pub async fn recv_event(&self) -> Option<AsyncTraderEvent> {
         let mut receiver = self.event_receiver.lock().await;
-        receiver.recv().await
+        let event = receiver.recv().await;
+        if let (Some(_), Some(metrics)) = (&event, &self.metrics) {
+            metrics.on_event_dequeued();
+        }
+        event
     }
 
     /// 尝试接收事件（非阻塞）
     pub async fn try_recv_event(&self) -> Result<AsyncTraderEvent, mpsc::error::TryRecvError> {
         let mut receiver = self.event_receiver.lock().await;
-        receiver.try_recv()
+        let event = receiver.try_recv();
+        if let (Ok(_), Some(metrics)) = (&event, &self.metrics) {
+            metrics.on_event_dequeued();
+        }
+        event
     }
 
     /// 获取当前状态
@@ -492,23 +1934,35 @@ impl AsyncTraderApi {
 
 /// 异步事件处理器
 #[derive(Clone)]
-struct AsyncTraderHandler {
-    event_sender: mpsc::UnboundedSender<AsyncTraderEvent>,
+pub(crate) struct AsyncTraderHandler {
+    event_sender: Arc<dyn EventSink<AsyncTraderEvent>>,
     state: Arc<Mutex<AsyncTraderState>>,
     connected_notify: Arc<Notify>,
     auth_notify: Arc<Notify>,
     login_notify: Arc<Notify>,
     pending_requests: Arc<Mutex<HashMap<i32, PendingRequest>>>,
+    orders: Arc<OrderTracker>,
+    working_orders: Arc<WorkingOrders>,
+    failover: FailoverHook,
+}
+
+/// 回调线程中跟踪前置连接所需的句柄
+#[derive(Clone)]
+struct FailoverHook {
+    fronts: Arc<StdMutex<FrontPool>>,
+    probe: FrontInfoProbe,
+    trigger: mpsc::UnboundedSender<()>,
 }
 
 impl AsyncTraderHandler {
     fn new(
-        event_sender: mpsc::UnboundedSender<AsyncTraderEvent>,
+        event_sender: Arc<dyn EventSink<AsyncTraderEvent>>,
         state: Arc<Mutex<AsyncTraderState>>,
         connected_notify: Arc<Notify>,
         auth_notify: Arc<Notify>,
         login_notify: Arc<Notify>,
         pending_requests: Arc<Mutex<HashMap<i32, PendingRequest>>>,
+        failover: FailoverHook,
     ) -> Self {
         Self {
             event_sender,
@@ -517,6 +1971,78 @@ impl AsyncTraderHandler {
             auth_notify,
             login_notify,
             pending_requests,
+            orders: Arc::new(OrderTracker::default()),
+            working_orders: Arc::new(WorkingOrders::default()),
+            failover,
+        }
+    }
+
+    /// 与[`AsyncTraderApi`]共用报单跟踪表
+    fn with_orders(mut self, orders: Arc<OrderTracker>) -> Self {
+        self.orders = orders;
+        self
+    }
+
+    /// 与[`AsyncTraderApi`]共用挂单簿
+    fn with_working_orders(mut self, working_orders: Arc<WorkingOrders>) -> Self {
+        self.working_orders = working_orders;
+        self
+    }
+
+    /// 不依赖[`AsyncTraderApi`]的处理器，只把回调转换为事件交给`event_sender`，
+    /// 供同步通道API使用
+    #[cfg(feature = "channel")]
+    pub(crate) fn standalone(
+        api: &TraderApi,
+        event_sender: Arc<dyn EventSink<AsyncTraderEvent>>,
+    ) -> Self {
+        // 没有前置池时不会触发故障切换，接收端直接丢弃
+        let (trigger, _) = mpsc::unbounded_channel();
+        Self::new(
+            event_sender,
+            Arc::new(Mutex::new(AsyncTraderState::default())),
+            Arc::new(Notify::new()),
+            Arc::new(Notify::new()),
+            Arc::new(Notify::new()),
+            Arc::new(Mutex::new(HashMap::new())),
+            FailoverHook {
+                fronts: Arc::new(StdMutex::new(FrontPool::default())),
+                probe: api.front_info_probe(),
+                trigger,
+            },
+        )
+    }
+
+    /// 记录连接到的前置，未注册前置池时不做统计
+    fn record_front_connected(&self, front: String) {
+        let latency = match self.failover.fronts.lock() {
+            Ok(mut pool) if !pool.is_empty() => pool.record_connected(&front, Instant::now()),
+            _ => return,
+        };
+        debug!("异步交易API: 连接到前置 {}, 耗时 {:?}", front, latency);
+        let _ = self
+            .event_sender
+            .send(AsyncTraderEvent::Failover(FailoverEvent::FrontConnected {
+                front,
+                latency,
+            }));
+    }
+
+    /// 记录前置断开，达到切换条件时触发故障切换
+    fn record_front_disconnected(&self, reason: i32) {
+        let (front, should_failover) = match self.failover.fronts.lock() {
+            Ok(mut pool) if !pool.is_empty() => {
+                let front = pool.current().map(str::to_string);
+                (front, pool.record_disconnect(Instant::now()))
+            }
+            _ => return,
+        };
+        let _ = self.event_sender.send(AsyncTraderEvent::Failover(
+            FailoverEvent::FrontDisconnected { front, reason },
+        ));
+        if should_failover {
+            warn!("异步交易API: 当前前置达到切换条件");
+            let _ = self.failover.trigger.send(());
         }
     }
 
@@ -539,9 +2065,12 @@ impl TraderSpiHandler for AsyncTraderHandler {
     fn on_front_connected(&mut self) {
         debug!("异步交易API: 连接成功");
 
+        let front_info = self.failover.probe.front_info().ok();
+
         // 更新状态
         if let Ok(mut state) = self.state.try_lock() {
             state.connected = true;
+            state.front_info = front_info.clone();
         }
 
         // 通知等待者
@@ -549,6 +2078,10 @@ impl TraderSpiHandler for AsyncTraderHandler {
 
         // 发送事件
         let _ = self.event_sender.send(AsyncTraderEvent::Connected);
+
+        if let Some(front_info) = front_info {
+            self.record_front_connected(front_info.front_addr);
+        }
     }
 
     fn on_front_disconnected(&mut self, reason: i32) {
@@ -561,12 +2094,15 @@ impl TraderSpiHandler for AsyncTraderHandler {
             state.logged_in = false;
             state.auth_info = None;
             state.login_info = None;
+            state.front_info = None;
         }
 
         // 发送事件
         let _ = self
             .event_sender
             .send(AsyncTraderEvent::Disconnected(reason));
+
+        self.record_front_disconnected(reason);
     }
 
     fn on_heart_beat_warning(&mut self, time_lapse: i32) {
@@ -578,28 +2114,107 @@ impl TraderSpiHandler for AsyncTraderHandler {
 
     fn on_rsp_authenticate(
         &mut self,
-        rsp_authenticate: Option<RspAuthenticateField>,
+        rsp_authenticate: Option<RspAuthenticateField>,
+        rsp_info: Option<RspInfoField>,
+        request_id: i32,
+        is_last: bool,
+    ) {
+        debug!("异步交易API: 收到认证响应");
+
+        // 检查是否成功
+        let success = rsp_info.as_ref().map_or(true, |info| info.is_success());
+
+        if success && is_last {
+            if let Ok(mut state) = self.state.try_lock() {
+                state.authenticated = true;
+                state.auth_info = rsp_authenticate.clone();
+            }
+            // 通知认证完成
+            self.auth_notify.notify_waiters();
+        }
+
+        // 发送事件
+        let event = AsyncTraderEvent::AuthenticateResponse {
+            rsp_authenticate,
+            rsp_info,
+            request_id,
+            is_last,
+        };
+
+        let _ = self.event_sender.send(event.clone());
+
+        // 通知待处理的请求
+        self.notify_pending_request(request_id, event);
+    }
+
+    fn on_rsp_user_login(
+        &mut self,
+        user_login: Option<RspUserLoginField>,
+        rsp_info: Option<RspInfoField>,
+        request_id: i32,
+        is_last: bool,
+    ) {
+        debug!("异步交易API: 收到登录响应");
+
+        // 检查是否成功
+        let success = rsp_info.as_ref().map_or(true, |info| info.is_success());
+
+        if success && is_last {
+            if let Ok(mut state) = self.state.try_lock() {
+                state.logged_in = true;
+                state.login_info = user_login.clone();
+            }
+            // 通知登录完成
+            self.login_notify.notify_waiters();
+        }
+
+        // 发送事件
+        let event = AsyncTraderEvent::LoginResponse {
+            user_login,
+            rsp_info,
+            request_id,
+            is_last,
+        };
+
+        let _ = self.event_sender.send(event.clone());
+
+        // 通知待处理的请求
+        self.notify_pending_request(request_id, event);
+    }
+
+    fn on_rsp_user_auth_method(
+        &mut self,
+        rsp_user_auth_method: Option<RspUserAuthMethodField>,
+        rsp_info: Option<RspInfoField>,
+        request_id: i32,
+        is_last: bool,
+    ) {
+        debug!("异步交易API: 收到查询认证模式响应");
+
+        let event = AsyncTraderEvent::UserAuthMethodResponse {
+            rsp_user_auth_method,
+            rsp_info,
+            request_id,
+            is_last,
+        };
+
+        let _ = self.event_sender.send(event.clone());
+
+        // 通知待处理的请求
+        self.notify_pending_request(request_id, event);
+    }
+
+    fn on_rsp_gen_user_captcha(
+        &mut self,
+        rsp_gen_user_captcha: Option<RspGenUserCaptchaField>,
         rsp_info: Option<RspInfoField>,
         request_id: i32,
         is_last: bool,
     ) {
-        debug!("异步交易API: 收到认证响应");
-
-        // 检查是否成功
-        let success = rsp_info.as_ref().map_or(true, |info| info.is_success());
-
-        if success && is_last {
-            if let Ok(mut state) = self.state.try_lock() {
-                state.authenticated = true;
-                state.auth_info = rsp_authenticate.clone();
-            }
-            // 通知认证完成
-            self.auth_notify.notify_waiters();
-        }
+        debug!("异步交易API: 收到获取图形验证码响应");
 
-        // 发送事件
-        let event = AsyncTraderEvent::AuthenticateResponse {
-            rsp_authenticate,
+        let event = AsyncTraderEvent::GenUserCaptchaResponse {
+            rsp_gen_user_captcha: rsp_gen_user_captcha.map(Box::new),
             rsp_info,
             request_id,
             is_last,
@@ -611,30 +2226,17 @@ impl TraderSpiHandler for AsyncTraderHandler {
         self.notify_pending_request(request_id, event);
     }
 
-    fn on_rsp_user_login(
+    fn on_rsp_gen_user_text(
         &mut self,
-        user_login: Option<RspUserLoginField>,
+        rsp_gen_user_text: Option<RspGenUserTextField>,
         rsp_info: Option<RspInfoField>,
         request_id: i32,
         is_last: bool,
     ) {
-        debug!("异步交易API: 收到登录响应");
-
-        // 检查是否成功
-        let success = rsp_info.as_ref().map_or(true, |info| info.is_success());
-
-        if success && is_last {
-            if let Ok(mut state) = self.state.try_lock() {
-                state.logged_in = true;
-                state.login_info = user_login.clone();
-            }
-            // 通知登录完成
-            self.login_notify.notify_waiters();
-        }
+        debug!("异步交易API: 收到获取短信验证码响应");
 
-        // 发送事件
-        let event = AsyncTraderEvent::LoginResponse {
-            user_login,
+        let event = AsyncTraderEvent::GenUserTextResponse {
+            rsp_gen_user_text,
             rsp_info,
             request_id,
             is_last,
@@ -697,6 +2299,8 @@ impl TraderSpiHandler for AsyncTraderHandler {
         is_last: bool,
     ) {
         debug!("异步交易API: 收到报单录入响应");
+        self.orders
+            .on_insert_rejected(request_id, input_order.as_ref(), rsp_info.as_ref());
 
         let event = AsyncTraderEvent::OrderInsertResponse {
             input_order,
@@ -719,6 +2323,24 @@ impl TraderSpiHandler for AsyncTraderHandler {
         is_last: bool,
     ) {
         debug!("异步交易API: 收到报单操作响应");
+        if let Some(action) = &input_order_action {
+            self.orders.on_action_rejected(
+                action.front_id,
+                action.session_id,
+                &action.order_ref,
+                &action.exchange_id,
+                &action.order_sys_id,
+                rsp_info.as_ref(),
+            );
+            self.working_orders.on_action_rejected(
+                action.front_id,
+                action.session_id,
+                &action.order_ref,
+                &action.exchange_id,
+                &action.order_sys_id,
+                rsp_info.as_ref(),
+            );
+        }
 
         let event = AsyncTraderEvent::OrderActionResponse {
             input_order_action,
@@ -771,6 +2393,25 @@ impl TraderSpiHandler for AsyncTraderHandler {
             });
     }
 
+    fn on_rsp_qry_instrument(
+        &mut self,
+        instrument: Option<InstrumentField>,
+        rsp_info: Option<RspInfoField>,
+        request_id: i32,
+        is_last: bool,
+    ) {
+        debug!("异步交易API: 收到查询合约响应");
+
+        let _ = self
+            .event_sender
+            .send(AsyncTraderEvent::QryInstrumentResponse {
+                instrument,
+                rsp_info,
+                request_id,
+                is_last,
+            });
+    }
+
     fn on_rsp_qry_order(
         &mut self,
         order: Option<OrderField>,
@@ -805,13 +2446,330 @@ impl TraderSpiHandler for AsyncTraderHandler {
         });
     }
 
+    fn on_rsp_qry_settlement_info(
+        &mut self,
+        settlement_info: Option<SettlementInfoField>,
+        rsp_info: Option<RspInfoField>,
+        request_id: i32,
+        is_last: bool,
+    ) {
+        debug!("异步交易API: 收到查询结算单响应");
+
+        let _ = self
+            .event_sender
+            .send(AsyncTraderEvent::QrySettlementInfoResponse {
+                settlement_info,
+                rsp_info,
+                request_id,
+                is_last,
+            });
+    }
+
+    fn on_rsp_settlement_info_confirm(
+        &mut self,
+        settlement_info_confirm: Option<SettlementInfoConfirmField>,
+        rsp_info: Option<RspInfoField>,
+        request_id: i32,
+        is_last: bool,
+    ) {
+        debug!("异步交易API: 收到结算单确认响应");
+
+        let event = AsyncTraderEvent::SettlementInfoConfirmResponse {
+            settlement_info_confirm,
+            rsp_info,
+            request_id,
+            is_last,
+        };
+
+        let _ = self.event_sender.send(event.clone());
+
+        // 通知待处理的请求
+        self.notify_pending_request(request_id, event);
+    }
+
+    fn on_rsp_from_bank_to_future_by_future(
+        &mut self,
+        req_transfer: Option<ReqTransferField>,
+        rsp_info: Option<RspInfoField>,
+        request_id: i32,
+        is_last: bool,
+    ) {
+        debug!("异步交易API: 收到银行转期货应答");
+
+        let _ = self.event_sender.send(AsyncTraderEvent::TransferResponse {
+            req_transfer,
+            rsp_info,
+            request_id,
+            is_last,
+        });
+    }
+
+    fn on_rsp_from_future_to_bank_by_future(
+        &mut self,
+        req_transfer: Option<ReqTransferField>,
+        rsp_info: Option<RspInfoField>,
+        request_id: i32,
+        is_last: bool,
+    ) {
+        debug!("异步交易API: 收到期货转银行应答");
+
+        let _ = self.event_sender.send(AsyncTraderEvent::TransferResponse {
+            req_transfer,
+            rsp_info,
+            request_id,
+            is_last,
+        });
+    }
+
+    fn on_rsp_query_bank_account_money_by_future(
+        &mut self,
+        req_query_account: Option<ReqQueryAccountField>,
+        rsp_info: Option<RspInfoField>,
+        request_id: i32,
+        is_last: bool,
+    ) {
+        debug!("异步交易API: 收到查询银行余额应答");
+
+        let _ = self
+            .event_sender
+            .send(AsyncTraderEvent::QueryBankAccountResponse {
+                req_query_account,
+                rsp_info,
+                request_id,
+                is_last,
+            });
+    }
+
+    fn on_rtn_from_bank_to_future_by_future(&mut self, rsp_transfer: RspTransferField) {
+        debug!("异步交易API: 收到银行转期货通知");
+        let _ = self
+            .event_sender
+            .send(AsyncTraderEvent::TransferReturn(rsp_transfer));
+    }
+
+    fn on_rtn_from_future_to_bank_by_future(&mut self, rsp_transfer: RspTransferField) {
+        debug!("异步交易API: 收到期货转银行通知");
+        let _ = self
+            .event_sender
+            .send(AsyncTraderEvent::TransferReturn(rsp_transfer));
+    }
+
+    fn on_rtn_repeal_from_bank_to_future_by_future(&mut self, rsp_repeal: RspRepealField) {
+        warn!("异步交易API: 银行转期货被冲正");
+        let _ = self
+            .event_sender
+            .send(AsyncTraderEvent::TransferRepealReturn(rsp_repeal));
+    }
+
+    fn on_rtn_repeal_from_future_to_bank_by_future(&mut self, rsp_repeal: RspRepealField) {
+        warn!("异步交易API: 期货转银行被冲正");
+        let _ = self
+            .event_sender
+            .send(AsyncTraderEvent::TransferRepealReturn(rsp_repeal));
+    }
+
+    fn on_err_rtn_bank_to_future_by_future(
+        &mut self,
+        req_transfer: Option<ReqTransferField>,
+        rsp_info: Option<RspInfoField>,
+    ) {
+        error!("异步交易API: 银行转期货错误回报");
+        let _ = self
+            .event_sender
+            .send(AsyncTraderEvent::TransferErrorReturn {
+                req_transfer,
+                rsp_info,
+            });
+    }
+
+    fn on_err_rtn_future_to_bank_by_future(
+        &mut self,
+        req_transfer: Option<ReqTransferField>,
+        rsp_info: Option<RspInfoField>,
+    ) {
+        error!("异步交易API: 期货转银行错误回报");
+        let _ = self
+            .event_sender
+            .send(AsyncTraderEvent::TransferErrorReturn {
+                req_transfer,
+                rsp_info,
+            });
+    }
+
+    fn on_rtn_query_bank_balance_by_future(
+        &mut self,
+        notify_query_account: NotifyQueryAccountField,
+    ) {
+        debug!("异步交易API: 收到查询银行余额通知");
+        let _ = self
+            .event_sender
+            .send(AsyncTraderEvent::QueryBankBalanceReturn(
+                notify_query_account,
+            ));
+    }
+
+    fn on_err_rtn_query_bank_balance_by_future(
+        &mut self,
+        req_query_account: Option<ReqQueryAccountField>,
+        rsp_info: Option<RspInfoField>,
+    ) {
+        error!("异步交易API: 查询银行余额错误回报");
+        let _ = self
+            .event_sender
+            .send(AsyncTraderEvent::QueryBankBalanceErrorReturn {
+                req_query_account,
+                rsp_info,
+            });
+    }
+
+    fn on_rsp_qry_transfer_serial(
+        &mut self,
+        transfer_serial: Option<TransferSerialField>,
+        rsp_info: Option<RspInfoField>,
+        request_id: i32,
+        is_last: bool,
+    ) {
+        debug!("异步交易API: 收到查询转帐流水响应");
+
+        let _ = self
+            .event_sender
+            .send(AsyncTraderEvent::QryTransferSerialResponse {
+                transfer_serial,
+                rsp_info,
+                request_id,
+                is_last,
+            });
+    }
+
+    fn on_rsp_qry_contract_bank(
+        &mut self,
+        contract_bank: Option<ContractBankField>,
+        rsp_info: Option<RspInfoField>,
+        request_id: i32,
+        is_last: bool,
+    ) {
+        debug!("异步交易API: 收到查询签约银行响应");
+
+        let _ = self
+            .event_sender
+            .send(AsyncTraderEvent::QryContractBankResponse {
+                contract_bank,
+                rsp_info,
+                request_id,
+                is_last,
+            });
+    }
+
+    fn on_rsp_qry_accountregister(
+        &mut self,
+        accountregister: Option<AccountregisterField>,
+        rsp_info: Option<RspInfoField>,
+        request_id: i32,
+        is_last: bool,
+    ) {
+        debug!("异步交易API: 收到查询银期签约关系响应");
+
+        let _ = self
+            .event_sender
+            .send(AsyncTraderEvent::QryAccountregisterResponse {
+                accountregister,
+                rsp_info,
+                request_id,
+                is_last,
+            });
+    }
+
+    fn on_rsp_user_password_update(
+        &mut self,
+        user_password_update: Option<UserPasswordUpdateField>,
+        rsp_info: Option<RspInfoField>,
+        request_id: i32,
+        is_last: bool,
+    ) {
+        debug!("异步交易API: 收到用户口令更新响应");
+
+        let event = AsyncTraderEvent::UserPasswordUpdateResponse {
+            user_password_update,
+            rsp_info,
+            request_id,
+            is_last,
+        };
+
+        let _ = self.event_sender.send(event.clone());
+
+        // 通知待处理的请求
+        self.notify_pending_request(request_id, event);
+    }
+
+    fn on_rsp_trading_account_password_update(
+        &mut self,
+        trading_account_password_update: Option<TradingAccountPasswordUpdateField>,
+        rsp_info: Option<RspInfoField>,
+        request_id: i32,
+        is_last: bool,
+    ) {
+        debug!("异步交易API: 收到资金账户口令更新响应");
+
+        let event = AsyncTraderEvent::TradingAccountPasswordUpdateResponse {
+            trading_account_password_update,
+            rsp_info,
+            request_id,
+            is_last,
+        };
+
+        let _ = self.event_sender.send(event.clone());
+
+        // 通知待处理的请求
+        self.notify_pending_request(request_id, event);
+    }
+
     fn on_rtn_order(&mut self, order: OrderField) {
         debug!("异步交易API: 收到报单回报");
+        self.orders.on_order(&order);
+        self.working_orders.on_order(&order);
         let _ = self.event_sender.send(AsyncTraderEvent::OrderReturn(order));
     }
 
     fn on_rtn_trade(&mut self, trade: TradeField) {
         debug!("异步交易API: 收到成交回报");
+        self.orders.on_trade(&trade);
         let _ = self.event_sender.send(AsyncTraderEvent::TradeReturn(trade));
     }
+
+    fn on_err_rtn_order_insert(
+        &mut self,
+        input_order: Option<InputOrderField>,
+        rsp_info: Option<RspInfoField>,
+    ) {
+        warn!("异步交易API: 报单录入错误回报");
+        let request_id = input_order.as_ref().map_or(0, |req| req.request_id);
+        self.orders
+            .on_insert_rejected(request_id, input_order.as_ref(), rsp_info.as_ref());
+    }
+
+    fn on_err_rtn_order_action(
+        &mut self,
+        order_action: Option<OrderActionField>,
+        rsp_info: Option<RspInfoField>,
+    ) {
+        warn!("异步交易API: 报单操作错误回报");
+        if let Some(action) = &order_action {
+            self.orders.on_action_rejected(
+                action.front_id,
+                action.session_id,
+                &action.order_ref,
+                &action.exchange_id,
+                &action.order_sys_id,
+                rsp_info.as_ref(),
+            );
+            self.working_orders.on_action_rejected(
+                action.front_id,
+                action.session_id,
+                &action.order_ref,
+                &action.exchange_id,
+                &action.order_sys_id,
+                rsp_info.as_ref(),
+            );
+        }
+    }
 }
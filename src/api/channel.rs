@@ -0,0 +1,891 @@
+//! 基于crossbeam通道的同步事件API
+//!
+//! 不依赖tokio运行时：回调线程把事件投递到有界通道，调用方可以直接`recv`，也可以
+//! 把多个接收端放进`crossbeam_channel::select!`。带`request_id`的请求响应会优先交给
+//! 阻塞等待中的请求，其余事件进入事件通道。
+//!
+//! 通道写满时的处理方式由[`BackpressurePolicy`]决定，行情默认丢弃最旧的数据，
+//! 避免行情洪峰阻塞CTP回调线程。
+
+use crate::api::async_md_api::AsyncMdEvent;
+use crate::api::async_trader_api::{AsyncTraderEvent, AsyncTraderHandler};
+use crate::api::md_api::{DepthMarketDataField, ForQuoteRspField, SpecificInstrumentField};
+use crate::api::trader_api::{
+    InputOrderField, InstrumentField, InvestorPositionField, ReqAuthenticateField,
+    RspAuthenticateField, TradingAccountField,
+};
+use crate::api::{CtpApi, EventSink, MdApi, MdSpiHandler, TraderApi};
+use crate::error::{CtpError, CtpResult};
+use crate::types::{
+    InputOrderActionField, QryInstrumentField, QryInvestorPositionField, QryTradingAccountField,
+    ReqUserLoginField, RspInfoField, RspUserLoginField, SettlementInfoConfirmField,
+};
+use crossbeam_channel::{Receiver, Sender, TrySendError};
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Condvar, Mutex, MutexGuard, Weak};
+use std::time::{Duration, Instant};
+
+/// 通道写满时的处理策略
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum BackpressurePolicy {
+    /// 阻塞回调线程直到有空位，不丢失事件
+    Block,
+    /// 丢弃通道中最旧的事件
+    DropOldest,
+    /// 丢弃新到达的事件
+    DropNewest,
+}
+
+/// 通道API选项
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ChannelOptions {
+    /// 事件通道容量
+    pub capacity: usize,
+    /// 行情通道容量
+    pub market_data_capacity: usize,
+    /// 事件通道写满时的策略
+    pub event_policy: BackpressurePolicy,
+    /// 行情通道写满时的策略
+    pub market_data_policy: BackpressurePolicy,
+}
+
+impl Default for ChannelOptions {
+    fn default() -> Self {
+        Self {
+            capacity: 1024,
+            market_data_capacity: 4096,
+            event_policy: BackpressurePolicy::Block,
+            market_data_policy: BackpressurePolicy::DropOldest,
+        }
+    }
+}
+
+impl ChannelOptions {
+    /// 设置事件通道容量
+    pub fn with_capacity(mut self, capacity: usize) -> Self {
+        self.capacity = capacity;
+        self
+    }
+
+    /// 设置行情通道容量
+    pub fn with_market_data_capacity(mut self, capacity: usize) -> Self {
+        self.market_data_capacity = capacity;
+        self
+    }
+
+    /// 设置事件通道写满时的策略
+    pub fn with_event_policy(mut self, policy: BackpressurePolicy) -> Self {
+        self.event_policy = policy;
+        self
+    }
+
+    /// 设置行情通道写满时的策略
+    pub fn with_market_data_policy(mut self, policy: BackpressurePolicy) -> Self {
+        self.market_data_policy = policy;
+        self
+    }
+}
+
+fn lock<T>(mutex: &Mutex<T>) -> MutexGuard<'_, T> {
+    mutex.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+/// 按策略写入的有界发送端
+pub(crate) struct BoundedSender<T> {
+    sender: Sender<T>,
+    // 仅DropOldest持有，用于腾出空位；只持有弱引用，接收端全部释放后通道能够断开
+    receiver: Option<Weak<Receiver<T>>>,
+    policy: BackpressurePolicy,
+    dropped: AtomicU64,
+}
+
+impl<T> BoundedSender<T> {
+    pub(crate) fn new(capacity: usize, policy: BackpressurePolicy) -> (Self, Arc<Receiver<T>>) {
+        // 容量为0时DropOldest无法腾出空位
+        let (sender, receiver) = crossbeam_channel::bounded(capacity.max(1));
+        let receiver = Arc::new(receiver);
+        let own_receiver =
+            (policy == BackpressurePolicy::DropOldest).then(|| Arc::downgrade(&receiver));
+        let sender = Self {
+            sender,
+            receiver: own_receiver,
+            policy,
+            dropped: AtomicU64::new(0),
+        };
+        (sender, receiver)
+    }
+
+    /// 写入事件，返回事件是否进入通道
+    pub(crate) fn send(&self, value: T) -> bool {
+        match self.policy {
+            BackpressurePolicy::Block => self.sender.send(value).is_ok(),
+            BackpressurePolicy::DropNewest => match self.sender.try_send(value) {
+                Ok(()) => true,
+                Err(TrySendError::Full(_)) => {
+                    self.dropped.fetch_add(1, Ordering::Relaxed);
+                    false
+                }
+                Err(TrySendError::Disconnected(_)) => false,
+            },
+            BackpressurePolicy::DropOldest => {
+                let mut value = value;
+                loop {
+                    match self.sender.try_send(value) {
+                        Ok(()) => return true,
+                        Err(TrySendError::Full(v)) => {
+                            value = v;
+                            let Some(receiver) = self.receiver.as_ref().and_then(Weak::upgrade)
+                            else {
+                                // 通道所有者已释放，只剩克隆出的接收端，无法腾出空位时丢弃新事件
+                                self.dropped.fetch_add(1, Ordering::Relaxed);
+                                return false;
+                            };
+                            // 消费者可能同时取走了数据，此时直接重试
+                            if receiver.try_recv().is_ok() {
+                                self.dropped.fetch_add(1, Ordering::Relaxed);
+                            }
+                        }
+                        Err(TrySendError::Disconnected(_)) => return false,
+                    }
+                }
+            }
+        }
+    }
+
+    /// 因通道写满丢弃的事件数
+    pub(crate) fn dropped(&self) -> u64 {
+        self.dropped.load(Ordering::Relaxed)
+    }
+}
+
+/// 可按请求编号路由的响应事件
+pub(crate) trait ResponseEvent {
+    /// 请求编号和是否为最后一条响应
+    fn request_meta(&self) -> Option<(i32, bool)>;
+}
+
+impl ResponseEvent for AsyncTraderEvent {
+    fn request_meta(&self) -> Option<(i32, bool)> {
+        self.response_meta()
+            .map(|(request_id, is_last, _)| (request_id, is_last))
+    }
+}
+
+impl ResponseEvent for AsyncMdEvent {
+    fn request_meta(&self) -> Option<(i32, bool)> {
+        match self {
+            AsyncMdEvent::LoginResponse {
+                request_id,
+                is_last,
+                ..
+            }
+            | AsyncMdEvent::LogoutResponse {
+                request_id,
+                is_last,
+                ..
+            }
+            | AsyncMdEvent::ErrorResponse {
+                request_id,
+                is_last,
+                ..
+            } => Some((*request_id, *is_last)),
+            _ => None,
+        }
+    }
+}
+
+/// 把响应交给等待中的阻塞请求
+pub(crate) struct ResponseRouter<E> {
+    waiters: Mutex<HashMap<i32, Sender<E>>>,
+}
+
+impl<E: ResponseEvent> ResponseRouter<E> {
+    pub(crate) fn new() -> Self {
+        Self {
+            waiters: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// 发出请求并登记等待者
+    ///
+    /// 发送请求期间持有锁，响应不会先于登记到达
+    pub(crate) fn register<F>(&self, send: F) -> CtpResult<(i32, Receiver<E>)>
+    where
+        F: FnOnce() -> CtpResult<i32>,
+    {
+        let mut waiters = lock(&self.waiters);
+        let request_id = send()?;
+        let (sender, receiver) = crossbeam_channel::unbounded();
+        waiters.insert(request_id, sender);
+        Ok((request_id, receiver))
+    }
+
+    /// 路由事件，没有等待者时原样返回
+    pub(crate) fn dispatch(&self, event: E) -> Option<E> {
+        let Some((request_id, is_last)) = event.request_meta() else {
+            return Some(event);
+        };
+        let mut waiters = lock(&self.waiters);
+        let Some(sender) = waiters.get(&request_id) else {
+            return Some(event);
+        };
+        let delivered = sender.send(event);
+        if is_last || delivered.is_err() {
+            waiters.remove(&request_id);
+        }
+        delivered.err().map(|e| e.into_inner())
+    }
+
+    /// 收集请求的全部响应，直到`is_last`或超时
+    pub(crate) fn collect(
+        &self,
+        request_id: i32,
+        receiver: Receiver<E>,
+        timeout: Duration,
+    ) -> CtpResult<Vec<E>> {
+        let deadline = Instant::now() + timeout;
+        let mut responses = Vec::new();
+        loop {
+            match receiver.recv_deadline(deadline) {
+                Ok(event) => {
+                    let is_last = event.request_meta().map(|(_, last)| last).unwrap_or(true);
+                    responses.push(event);
+                    if is_last {
+                        return Ok(responses);
+                    }
+                }
+                Err(_) => {
+                    lock(&self.waiters).remove(&request_id);
+                    return Err(CtpError::TimeoutError(format!(
+                        "请求 {} 在 {} 毫秒内未完成响应",
+                        request_id,
+                        timeout.as_millis()
+                    )));
+                }
+            }
+        }
+    }
+}
+
+/// 连接状态，回调线程更新，调用方可阻塞等待
+#[derive(Default)]
+struct ConnectionState {
+    connected: Mutex<bool>,
+    changed: Condvar,
+}
+
+impl ConnectionState {
+    fn set(&self, connected: bool) {
+        *lock(&self.connected) = connected;
+        self.changed.notify_all();
+    }
+
+    fn is_connected(&self) -> bool {
+        *lock(&self.connected)
+    }
+
+    fn wait(&self, timeout_secs: u64) -> CtpResult<()> {
+        let guard = lock(&self.connected);
+        let (guard, _) = self
+            .changed
+            .wait_timeout_while(guard, Duration::from_secs(timeout_secs), |c| !*c)
+            .unwrap_or_else(|e| e.into_inner());
+        if *guard {
+            Ok(())
+        } else {
+            Err(CtpError::TimeoutError("等待连接超时".to_string()))
+        }
+    }
+}
+
+fn check_rsp_info(rsp_info: Option<&RspInfoField>) -> CtpResult<()> {
+    match rsp_info {
+        Some(rsp) if !rsp.is_success() => Err(CtpError::BusinessError(
+            rsp.error_id,
+            rsp.get_error_msg().unwrap_or_default(),
+        )),
+        _ => Ok(()),
+    }
+}
+
+struct TraderSink {
+    events: BoundedSender<AsyncTraderEvent>,
+    router: ResponseRouter<AsyncTraderEvent>,
+    connection: ConnectionState,
+}
+
+impl EventSink<AsyncTraderEvent> for TraderSink {
+    fn send(&self, event: AsyncTraderEvent) -> bool {
+        match event {
+            AsyncTraderEvent::Connected => self.connection.set(true),
+            AsyncTraderEvent::Disconnected(_) => self.connection.set(false),
+            _ => {}
+        }
+        match self.router.dispatch(event) {
+            Some(event) => self.events.send(event),
+            None => true,
+        }
+    }
+}
+
+/// 基于crossbeam通道的同步交易API
+///
+/// 回报、未被阻塞请求取走的响应以及连接事件都从[`events`](Self::events)接收。
+/// 事件策略为[`BackpressurePolicy::Block`]时需要及时消费事件，否则回调线程会被阻塞，
+/// 阻塞请求也将因收不到响应而超时
+pub struct ChannelTraderApi {
+    inner: Arc<Mutex<TraderApi>>,
+    events: Arc<Receiver<AsyncTraderEvent>>,
+    sink: Arc<TraderSink>,
+}
+
+impl ChannelTraderApi {
+    /// 创建通道交易API
+    pub fn new(
+        flow_path: Option<&str>,
+        is_production_mode: Option<bool>,
+        options: ChannelOptions,
+    ) -> CtpResult<Self> {
+        let api = TraderApi::new(flow_path, is_production_mode)?;
+        let (events_sender, events) = BoundedSender::new(options.capacity, options.event_policy);
+        Ok(Self {
+            inner: Arc::new(Mutex::new(api)),
+            events,
+            sink: Arc::new(TraderSink {
+                events: events_sender,
+                router: ResponseRouter::new(),
+                connection: ConnectionState::default(),
+            }),
+        })
+    }
+
+    /// 注册前置机地址
+    pub fn register_front(&self, front_address: &str) -> CtpResult<()> {
+        lock(&self.inner).register_front(front_address)
+    }
+
+    /// 按优先顺序注册多个前置地址
+    pub fn register_fronts(&self, addresses: &[String]) -> CtpResult<()> {
+        if addresses.is_empty() {
+            return Err(CtpError::InvalidParameterError(
+                "至少需要一个前置地址".to_string(),
+            ));
+        }
+        let mut api = lock(&self.inner);
+        for address in addresses {
+            api.register_front(address)?;
+        }
+        Ok(())
+    }
+
+    /// 注册回调并初始化API
+    pub fn init(&self) -> CtpResult<()> {
+        let mut api = lock(&self.inner);
+        let handler = AsyncTraderHandler::standalone(&api, self.sink.clone());
+        api.register_spi(handler)?;
+        api.init()
+    }
+
+    /// 事件接收端，可克隆后放入`select!`
+    pub fn events(&self) -> &Receiver<AsyncTraderEvent> {
+        &self.events
+    }
+
+    /// 因事件通道写满丢弃的事件数
+    pub fn dropped_events(&self) -> u64 {
+        self.sink.events.dropped()
+    }
+
+    /// 是否已连接到前置
+    pub fn is_connected(&self) -> bool {
+        self.sink.connection.is_connected()
+    }
+
+    /// 阻塞等待连接到前置
+    pub fn wait_connected(&self, timeout_secs: u64) -> CtpResult<()> {
+        self.sink.connection.wait(timeout_secs)
+    }
+
+    /// 发出请求并阻塞等待全部响应
+    ///
+    /// `send`返回请求编号，响应按`is_last`收齐后返回，不会再进入事件通道
+    pub fn request<F>(&self, timeout_secs: u64, send: F) -> CtpResult<Vec<AsyncTraderEvent>>
+    where
+        F: FnOnce(&mut TraderApi) -> CtpResult<i32>,
+    {
+        let (request_id, receiver) = {
+            let mut api = lock(&self.inner);
+            self.sink.router.register(|| send(&mut api))?
+        };
+        self.sink
+            .router
+            .collect(request_id, receiver, Duration::from_secs(timeout_secs))
+    }
+
+    /// 客户端认证
+    pub fn authenticate(
+        &self,
+        req: &ReqAuthenticateField,
+        timeout_secs: u64,
+    ) -> CtpResult<RspAuthenticateField> {
+        let responses = self.request(timeout_secs, |api| api.req_authenticate(req))?;
+        let mut result = None;
+        for event in responses {
+            check_rsp_info(event.response_meta().and_then(|(_, _, rsp)| rsp))?;
+            if let AsyncTraderEvent::AuthenticateResponse {
+                rsp_authenticate, ..
+            } = event
+            {
+                result = rsp_authenticate;
+            }
+        }
+        result.ok_or_else(|| CtpError::InitializationError("认证响应数据为空".to_string()))
+    }
+
+    /// 用户登录
+    pub fn login(
+        &self,
+        req: &ReqUserLoginField,
+        timeout_secs: u64,
+    ) -> CtpResult<RspUserLoginField> {
+        let responses = self.request(timeout_secs, |api| api.req_user_login(req))?;
+        let mut result = None;
+        for event in responses {
+            check_rsp_info(event.response_meta().and_then(|(_, _, rsp)| rsp))?;
+            if let AsyncTraderEvent::LoginResponse { user_login, .. } = event {
+                result = user_login;
+            }
+        }
+        result.ok_or_else(|| CtpError::InitializationError("登录响应数据为空".to_string()))
+    }
+
+    /// 确认结算单
+    pub fn settlement_info_confirm(
+        &self,
+        req: &SettlementInfoConfirmField,
+        timeout_secs: u64,
+    ) -> CtpResult<()> {
+        let responses = self.request(timeout_secs, |api| api.req_settlement_info_confirm(req))?;
+        for event in &responses {
+            check_rsp_info(event.response_meta().and_then(|(_, _, rsp)| rsp))?;
+        }
+        Ok(())
+    }
+
+    /// 查询资金账户
+    pub fn qry_trading_account(
+        &self,
+        req: &QryTradingAccountField,
+        timeout_secs: u64,
+    ) -> CtpResult<Vec<TradingAccountField>> {
+        let responses = self.request(timeout_secs, |api| api.req_qry_trading_account(req))?;
+        let mut accounts = Vec::new();
+        for event in responses {
+            check_rsp_info(event.response_meta().and_then(|(_, _, rsp)| rsp))?;
+            if let AsyncTraderEvent::QryTradingAccountResponse {
+                trading_account: Some(account),
+                ..
+            } = event
+            {
+                accounts.push(account);
+            }
+        }
+        Ok(accounts)
+    }
+
+    /// 查询投资者持仓
+    pub fn qry_investor_position(
+        &self,
+        req: &QryInvestorPositionField,
+        timeout_secs: u64,
+    ) -> CtpResult<Vec<InvestorPositionField>> {
+        let responses = self.request(timeout_secs, |api| api.req_qry_investor_position(req))?;
+        let mut positions = Vec::new();
+        for event in responses {
+            check_rsp_info(event.response_meta().and_then(|(_, _, rsp)| rsp))?;
+            if let AsyncTraderEvent::QryInvestorPositionResponse {
+                investor_position: Some(position),
+                ..
+            } = event
+            {
+                positions.push(position);
+            }
+        }
+        Ok(positions)
+    }
+
+    /// 查询合约
+    pub fn qry_instrument(
+        &self,
+        req: &QryInstrumentField,
+        timeout_secs: u64,
+    ) -> CtpResult<Vec<InstrumentField>> {
+        let responses = self.request(timeout_secs, |api| api.req_qry_instrument(req))?;
+        let mut instruments = Vec::new();
+        for event in responses {
+            check_rsp_info(event.response_meta().and_then(|(_, _, rsp)| rsp))?;
+            if let AsyncTraderEvent::QryInstrumentResponse {
+                instrument: Some(instrument),
+                ..
+            } = event
+            {
+                instruments.push(instrument);
+            }
+        }
+        Ok(instruments)
+    }
+
+    /// 报单录入，报单回报和成交回报从事件通道接收
+    pub fn order_insert(&self, req: &InputOrderField) -> CtpResult<i32> {
+        lock(&self.inner).req_order_insert(req)
+    }
+
+    /// 报单操作，结果从事件通道接收
+    pub fn order_action(&self, req: &InputOrderActionField) -> CtpResult<i32> {
+        lock(&self.inner).req_order_action(req)
+    }
+
+    /// 直接访问内部同步API，用于未封装的请求
+    pub fn with_api<R>(&self, f: impl FnOnce(&mut TraderApi) -> R) -> R {
+        f(&mut lock(&self.inner))
+    }
+}
+
+struct MdSink {
+    events: BoundedSender<AsyncMdEvent>,
+    market_data: BoundedSender<DepthMarketDataField>,
+    router: ResponseRouter<AsyncMdEvent>,
+    connection: ConnectionState,
+}
+
+impl MdSink {
+    fn send(&self, event: AsyncMdEvent) {
+        if let Some(event) = self.router.dispatch(event) {
+            self.events.send(event);
+        }
+    }
+}
+
+struct ChannelMdHandler {
+    sink: Arc<MdSink>,
+}
+
+impl MdSpiHandler for ChannelMdHandler {
+    fn on_front_connected(&mut self) {
+        self.sink.connection.set(true);
+        self.sink.send(AsyncMdEvent::Connected);
+    }
+
+    fn on_front_disconnected(&mut self, reason: i32) {
+        self.sink.connection.set(false);
+        self.sink.send(AsyncMdEvent::Disconnected(reason));
+    }
+
+    fn on_heart_beat_warning(&mut self, time_lapse: i32) {
+        self.sink.send(AsyncMdEvent::HeartBeatWarning(time_lapse));
+    }
+
+    fn on_rsp_user_login(
+        &mut self,
+        user_login: Option<RspUserLoginField>,
+        rsp_info: Option<RspInfoField>,
+        request_id: i32,
+        is_last: bool,
+    ) {
+        self.sink.send(AsyncMdEvent::LoginResponse {
+            user_login,
+            rsp_info,
+            request_id,
+            is_last,
+        });
+    }
+
+    fn on_rsp_user_logout(
+        &mut self,
+        _user_logout: Option<()>,
+        rsp_info: Option<RspInfoField>,
+        request_id: i32,
+        is_last: bool,
+    ) {
+        self.sink.send(AsyncMdEvent::LogoutResponse {
+            rsp_info,
+            request_id,
+            is_last,
+        });
+    }
+
+    fn on_rsp_error(&mut self, rsp_info: Option<RspInfoField>, request_id: i32, is_last: bool) {
+        self.sink.send(AsyncMdEvent::ErrorResponse {
+            rsp_info,
+            request_id,
+            is_last,
+        });
+    }
+
+    fn on_rsp_sub_market_data(
+        &mut self,
+        specific_instrument: Option<SpecificInstrumentField>,
+        rsp_info: Option<RspInfoField>,
+        request_id: i32,
+        is_last: bool,
+    ) {
+        self.sink.send(AsyncMdEvent::SubMarketDataResponse {
+            specific_instrument,
+            rsp_info,
+            request_id,
+            is_last,
+        });
+    }
+
+    fn on_rsp_unsub_market_data(
+        &mut self,
+        specific_instrument: Option<SpecificInstrumentField>,
+        rsp_info: Option<RspInfoField>,
+        request_id: i32,
+        is_last: bool,
+    ) {
+        self.sink.send(AsyncMdEvent::UnsubMarketDataResponse {
+            specific_instrument,
+            rsp_info,
+            request_id,
+            is_last,
+        });
+    }
+
+    fn on_rtn_depth_market_data(&mut self, market_data: DepthMarketDataField) {
+        self.sink.market_data.send(market_data);
+    }
+
+    fn on_rtn_for_quote_rsp(&mut self, for_quote_rsp: ForQuoteRspField) {
+        self.sink
+            .send(AsyncMdEvent::ForQuoteResponse(for_quote_rsp));
+    }
+}
+
+/// 基于crossbeam通道的同步行情API
+///
+/// 深度行情从独立的[`market_data`](Self::market_data)通道接收，按
+/// [`ChannelOptions::market_data_policy`]处理行情洪峰；其余事件从
+/// [`events`](Self::events)接收
+pub struct ChannelMdApi {
+    inner: Arc<Mutex<MdApi>>,
+    events: Arc<Receiver<AsyncMdEvent>>,
+    market_data: Arc<Receiver<DepthMarketDataField>>,
+    sink: Arc<MdSink>,
+}
+
+impl ChannelMdApi {
+    /// 创建通道行情API
+    pub fn new(
+        flow_path: Option<&str>,
+        is_using_udp: bool,
+        is_multicast: bool,
+        is_production_mode: Option<bool>,
+        options: ChannelOptions,
+    ) -> CtpResult<Self> {
+        let api = MdApi::new(
+            flow_path,
+            is_using_udp,
+            is_multicast,
+            is_production_mode.unwrap_or(false),
+        )?;
+        let (events_sender, events) = BoundedSender::new(options.capacity, options.event_policy);
+        let (market_data_sender, market_data) =
+            BoundedSender::new(options.market_data_capacity, options.market_data_policy);
+        Ok(Self {
+            inner: Arc::new(Mutex::new(api)),
+            events,
+            market_data,
+            sink: Arc::new(MdSink {
+                events: events_sender,
+                market_data: market_data_sender,
+                router: ResponseRouter::new(),
+                connection: ConnectionState::default(),
+            }),
+        })
+    }
+
+    /// 注册前置机地址
+    pub fn register_front(&self, front_address: &str) -> CtpResult<()> {
+        lock(&self.inner).register_front(front_address)
+    }
+
+    /// 按优先顺序注册多个前置地址
+    pub fn register_fronts(&self, addresses: &[String]) -> CtpResult<()> {
+        if addresses.is_empty() {
+            return Err(CtpError::InvalidParameterError(
+                "至少需要一个前置地址".to_string(),
+            ));
+        }
+        let mut api = lock(&self.inner);
+        for address in addresses {
+            api.register_front(address)?;
+        }
+        Ok(())
+    }
+
+    /// 注册回调并初始化API
+    pub fn init(&self) -> CtpResult<()> {
+        let mut api = lock(&self.inner);
+        api.register_spi(ChannelMdHandler {
+            sink: self.sink.clone(),
+        })?;
+        api.init()
+    }
+
+    /// 事件接收端，不含深度行情
+    pub fn events(&self) -> &Receiver<AsyncMdEvent> {
+        &self.events
+    }
+
+    /// 深度行情接收端
+    pub fn market_data(&self) -> &Receiver<DepthMarketDataField> {
+        &self.market_data
+    }
+
+    /// 因事件通道写满丢弃的事件数
+    pub fn dropped_events(&self) -> u64 {
+        self.sink.events.dropped()
+    }
+
+    /// 因行情通道写满丢弃的行情数
+    pub fn dropped_market_data(&self) -> u64 {
+        self.sink.market_data.dropped()
+    }
+
+    /// 是否已连接到前置
+    pub fn is_connected(&self) -> bool {
+        self.sink.connection.is_connected()
+    }
+
+    /// 阻塞等待连接到前置
+    pub fn wait_connected(&self, timeout_secs: u64) -> CtpResult<()> {
+        self.sink.connection.wait(timeout_secs)
+    }
+
+    /// 用户登录
+    pub fn login(
+        &self,
+        req: &ReqUserLoginField,
+        timeout_secs: u64,
+    ) -> CtpResult<RspUserLoginField> {
+        let (request_id, receiver) = {
+            let mut api = lock(&self.inner);
+            self.sink.router.register(|| api.req_user_login(req))?
+        };
+        let responses =
+            self.sink
+                .router
+                .collect(request_id, receiver, Duration::from_secs(timeout_secs))?;
+        let mut result = None;
+        for event in responses {
+            match event {
+                AsyncMdEvent::LoginResponse {
+                    user_login,
+                    rsp_info,
+                    ..
+                } => {
+                    check_rsp_info(rsp_info.as_ref())?;
+                    result = user_login;
+                }
+                AsyncMdEvent::ErrorResponse { rsp_info, .. } => {
+                    check_rsp_info(rsp_info.as_ref())?;
+                }
+                _ => {}
+            }
+        }
+        result.ok_or_else(|| CtpError::InitializationError("登录响应数据为空".to_string()))
+    }
+
+    /// 订阅行情
+    pub fn subscribe_market_data(&self, instrument_ids: &[&str]) -> CtpResult<()> {
+        lock(&self.inner).subscribe_market_data(instrument_ids)
+    }
+
+    /// 取消订阅行情
+    pub fn unsubscribe_market_data(&self, instrument_ids: &[&str]) -> CtpResult<()> {
+        lock(&self.inner).unsubscribe_market_data(instrument_ids)
+    }
+
+    /// 直接访问内部同步API
+    pub fn with_api<R>(&self, f: impl FnOnce(&mut MdApi) -> R) -> R {
+        f(&mut lock(&self.inner))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_backpressure_policies() {
+        let (sender, receiver) = BoundedSender::new(2, BackpressurePolicy::DropOldest);
+        for i in 0..5 {
+            assert!(sender.send(i));
+        }
+        assert_eq!(receiver.try_iter().collect::<Vec<_>>(), vec![3, 4]);
+        assert_eq!(sender.dropped(), 3);
+
+        // 发送端不持有接收端，接收端全部释放后视为断开
+        drop(receiver);
+        assert!(!sender.send(5));
+        let (sender, receiver) = BoundedSender::new(1, BackpressurePolicy::DropOldest);
+        let cloned = (*receiver).clone();
+        drop(receiver);
+        assert!(sender.send(1));
+        assert!(!sender.send(2));
+        assert_eq!(cloned.try_iter().collect::<Vec<_>>(), vec![1]);
+
+        let (sender, receiver) = BoundedSender::new(2, BackpressurePolicy::DropNewest);
+        let accepted: Vec<bool> = (0..4).map(|i| sender.send(i)).collect();
+        assert_eq!(accepted, vec![true, true, false, false]);
+        assert_eq!(receiver.try_iter().collect::<Vec<_>>(), vec![0, 1]);
+        assert_eq!(sender.dropped(), 2);
+
+        let (sender, receiver) = BoundedSender::new(1, BackpressurePolicy::Block);
+        assert!(sender.send(1));
+        let consumer = std::thread::spawn(move || receiver.iter().take(2).collect::<Vec<_>>());
+        assert!(sender.send(2));
+        assert_eq!(consumer.join().unwrap(), vec![1, 2]);
+        assert_eq!(sender.dropped(), 0);
+    }
+
+    fn login_response(request_id: i32, is_last: bool) -> AsyncMdEvent {
+        AsyncMdEvent::LoginResponse {
+            user_login: None,
+            rsp_info: None,
+            request_id,
+            is_last,
+        }
+    }
+
+    #[test]
+    fn test_response_router() {
+        let router = ResponseRouter::new();
+        let (request_id, receiver) = router.register(|| Ok(7)).unwrap();
+
+        // 其他请求的响应和非响应事件不被路由
+        assert!(router.dispatch(login_response(8, true)).is_some());
+        assert!(router.dispatch(AsyncMdEvent::Connected).is_some());
+
+        assert!(router.dispatch(login_response(7, false)).is_none());
+        assert!(router.dispatch(login_response(7, true)).is_none());
+        let responses = router
+            .collect(request_id, receiver, Duration::from_millis(10))
+            .unwrap();
+        assert_eq!(responses.len(), 2);
+
+        // 收齐后等待者被移除
+        assert!(router.dispatch(login_response(7, true)).is_some());
+
+        let (request_id, receiver) = router.register(|| Ok(9)).unwrap();
+        let err = router
+            .collect(request_id, receiver, Duration::from_millis(10))
+            .unwrap_err();
+        assert!(matches!(err, CtpError::TimeoutError(_)));
+        assert!(router.dispatch(login_response(9, true)).is_some());
+    }
+}
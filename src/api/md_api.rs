@@ -2,17 +2,21 @@
 //!
 //! 提供期货行情数据订阅和接收功能
 
-use crate::api::utils::normalize_flow_path;
+use crate::api::md_ring::MarketDataProducer;
 use crate::api::{safe_cstr_to_string, to_cstring, CtpApi};
 use crate::encoding::GbkConverter;
 use crate::error::{CtpError, CtpResult};
 use crate::ffi::md_api::*;
 use crate::ffi::{CreateMdSpiBridge, MdSpiCallbacks};
-use crate::types::{ReqUserLoginField, RspInfoField, RspUserLoginField};
+use crate::flow::FlowLock;
+use crate::latency::LatencyRecorder;
+use crate::models::FensUserInfo;
+use crate::types::{FensUserInfoField, ReqUserLoginField, RspInfoField, RspUserLoginField};
 use std::ffi::{c_void, CString};
 use std::os::raw::c_int;
 use std::ptr;
 use std::sync::{Arc, Mutex};
+use std::time::SystemTime;
 
 // 行情API封装
 #[allow(dead_code)]
@@ -27,6 +31,14 @@ pub struct MdApi {
     request_id: Arc<Mutex<i32>>,
     // 回调处理器
     handler: Option<Box<dyn MdSpiHandler + Send + Sync>>,
+    // 流文件目录锁，释放API后解锁
+    flow_lock: Option<FlowLock>,
+    // 行情低延迟通道，挂接后深度行情不再交给处理器
+    market_data_ring: Option<MarketDataProducer>,
+    // 延迟记录器
+    latency: Option<Arc<Mutex<LatencyRecorder>>>,
+    // 已注册的前置地址
+    fronts: Vec<String>,
 }
 
 // 行情SPI回调处理器特质
@@ -100,14 +112,23 @@ pub trait MdSpiHandler {
 // 深度行情数据 - 必须与C++结构体CThostFtdcDepthMarketDataField完全匹配
 #[repr(C)]
 #[derive(Debug, Clone)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(default)
+)]
 pub struct DepthMarketDataField {
     /// 交易日
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
     pub trading_day: [u8; 9],
     /// 保留的无效字段
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
     pub reserve1: [u8; 31],
     /// 交易所代码
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
     pub exchange_id: [u8; 9],
     /// 保留的无效字段
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
     pub reserve2: [u8; 31],
     /// 最新价
     pub last_price: f64,
@@ -142,6 +163,7 @@ pub struct DepthMarketDataField {
     /// 今虚实度
     pub curr_delta: f64,
     /// 最后修改时间
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
     pub update_time: [u8; 9],
     /// 最后修改毫秒
     pub update_millisec: i32,
@@ -188,10 +210,13 @@ pub struct DepthMarketDataField {
     /// 当日均价
     pub average_price: f64,
     /// 业务日期
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
     pub action_day: [u8; 9],
     /// 合约代码
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
     pub instrument_id: [u8; 81],
     /// 合约在交易所的代码
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
     pub exchange_inst_id: [u8; 81],
     /// 上带价
     pub banding_upper_price: f64,
@@ -220,10 +245,17 @@ impl DepthMarketDataField {
 // 指定的合约
 #[repr(C)]
 #[derive(Debug, Clone)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(default)
+)]
 pub struct SpecificInstrumentField {
     /// 保留的无效字段
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
     pub reserve1: [u8; 31],
     /// 合约代码
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
     pub instrument_id: [u8; 81],
 }
 
@@ -243,34 +275,52 @@ impl SpecificInstrumentField {
 // 询价响应
 #[repr(C)]
 #[derive(Debug, Clone)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(default)
+)]
 pub struct ForQuoteRspField {
     // 交易日
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
     pub trading_day: [u8; 9],
     // 合约代码
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
     pub instrument_id: [u8; 31],
     // 询价编号
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
     pub for_quote_ref: [u8; 13],
     // 用户代码
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
     pub user_id: [u8; 16],
     // 本地询价编号
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
     pub for_quote_local_id: [u8; 13],
     // 交易所代码
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
     pub exchange_id: [u8; 9],
     // 会员代码
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
     pub participant_id: [u8; 11],
     // 客户代码
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
     pub client_id: [u8; 11],
     // 合约在交易所的代码
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
     pub exchange_inst_id: [u8; 31],
     // 交易员代码
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
     pub trader_id: [u8; 21],
     // 安装编号
     pub install_id: i32,
     // 询价时间
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
     pub insert_time: [u8; 9],
     // 本地询价编号
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
     pub for_quote_local_id2: [u8; 13],
     // 业务日期
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
     pub action_day: [u8; 9],
 }
 
@@ -287,7 +337,8 @@ impl MdApi {
     // 创建行情API实例
     //
     // # 参数
-    // * `flow_path` - 存储流文件的目录，默认为当前目录
+    // * `flow_path` - 存储流文件的目录，默认为当前目录。指定时创建目录并加锁，
+    //   同一目录不能同时被多个API实例使用，见`flow::FlowDirectory`
     // * `is_using_udp` - 是否使用UDP协议接收多播数据
     // * `is_multicast` - 是否使用组播方式
     // * `is_production_mode` - 是否使用生产版本API，true:生产版本 false:测评版本
@@ -297,11 +348,10 @@ impl MdApi {
         is_multicast: bool,
         is_production_mode: bool,
     ) -> CtpResult<Self> {
-        let flow_path_cstr = match flow_path {
-            Some(path) => {
-                let npath = normalize_flow_path(path)?;
-                Some(to_cstring(npath.as_str())?)
-            }
+        // 指定目录时创建并加锁，防止其他会话共用同一目录
+        let flow_lock = flow_path.map(FlowLock::acquire).transpose()?;
+        let flow_path_cstr = match &flow_lock {
+            Some(lock) => Some(to_cstring(&lock.dir().to_string_lossy())?),
             None => None,
         };
 
@@ -328,6 +378,10 @@ impl MdApi {
             initialized: false,
             request_id: Arc::new(Mutex::new(1)),
             handler: None,
+            flow_lock,
+            market_data_ring: None,
+            latency: None,
+            fronts: Vec::new(),
         })
     }
 
@@ -381,7 +435,7 @@ impl MdApi {
             CThostFtdcMdApi_ReqUserLogin(self.api_ptr, req as *const _ as *const c_void, request_id)
         };
         if result != 0 {
-            return Err(CtpError::FfiError(format!("登录请求失败: {}", result)));
+            return Err(CtpError::request_failed("登录请求失败", result));
         }
 
         Ok(request_id)
@@ -399,7 +453,7 @@ impl MdApi {
             unsafe { CThostFtdcMdApi_ReqUserLogout(self.api_ptr, ptr::null(), request_id) };
 
         if result != 0 {
-            return Err(CtpError::FfiError(format!("登出请求失败: {}", result)));
+            return Err(CtpError::request_failed("登出请求失败", result));
         }
 
         Ok(request_id)
@@ -428,7 +482,7 @@ impl MdApi {
         };
 
         if result != 0 {
-            return Err(CtpError::FfiError(format!("订阅行情失败: {}", result)));
+            return Err(CtpError::request_failed("订阅行情失败", result));
         }
 
         Ok(())
@@ -461,7 +515,7 @@ impl MdApi {
         };
 
         if result != 0 {
-            return Err(CtpError::FfiError(format!("退订行情失败: {}", result)));
+            return Err(CtpError::request_failed("退订行情失败", result));
         }
 
         Ok(())
@@ -486,7 +540,7 @@ impl MdApi {
         };
 
         if result != 0 {
-            return Err(CtpError::FfiError(format!("订阅询价失败: {}", result)));
+            return Err(CtpError::request_failed("订阅询价失败", result));
         }
 
         Ok(())
@@ -515,12 +569,40 @@ impl MdApi {
         };
 
         if result != 0 {
-            return Err(CtpError::FfiError(format!("退订询价失败: {}", result)));
+            return Err(CtpError::request_failed("退订询价失败", result));
         }
 
         Ok(())
     }
 
+    // 挂接行情环形缓冲区，只能在init之前调用
+    //
+    // 挂接后深度行情直接复制到缓冲区，不再调用处理器的on_rtn_depth_market_data。
+    // 回调线程不加锁读取缓冲区，运行中替换会产生数据竞争，因此init之后返回错误，
+    // 缓冲区随API一起释放
+    pub fn attach_market_data_ring(&mut self, producer: MarketDataProducer) -> CtpResult<()> {
+        if self.initialized {
+            return Err(CtpError::InitializationError(
+                "行情环形缓冲区只能在init之前挂接".to_string(),
+            ));
+        }
+        self.market_data_ring = Some(producer);
+        Ok(())
+    }
+
+    // 挂接延迟记录器
+    //
+    // 挂接后每条行情在回调入口记录本地时间，与行情时间比较得到交易所到本地的延迟。
+    // 只注册了一个前置时延迟记入该前置，否则需通过LatencyRecorder::set_md_front指定
+    pub fn set_latency_recorder(&mut self, recorder: Arc<Mutex<LatencyRecorder>>) {
+        self.latency = Some(recorder);
+    }
+
+    // 获取已挂接的延迟记录器
+    pub fn latency_recorder(&self) -> Option<Arc<Mutex<LatencyRecorder>>> {
+        self.latency.clone()
+    }
+
     // 获取下一个请求ID
     fn next_request_id(&self) -> i32 {
         let mut id = self.request_id.lock().unwrap();
@@ -557,6 +639,7 @@ impl CtpApi for MdApi {
             self.api_ptr = ptr::null_mut();
         }
         self.initialized = false;
+        self.flow_lock = None;
     }
 
     fn get_trading_day(&self) -> CtpResult<String> {
@@ -579,6 +662,35 @@ impl CtpApi for MdApi {
         unsafe {
             CThostFtdcMdApi_RegisterFront(self.api_ptr, front_address_cstr.as_ptr());
         }
+        self.fronts.push(front_address.to_string());
+
+        Ok(())
+    }
+
+    fn register_name_server(&mut self, ns_address: &str) -> CtpResult<()> {
+        if self.api_ptr.is_null() {
+            return Err(CtpError::InitializationError("API未初始化".to_string()));
+        }
+
+        let ns_address_cstr = to_cstring(ns_address)?;
+
+        unsafe {
+            CThostFtdcMdApi_RegisterNameServer(self.api_ptr, ns_address_cstr.as_ptr());
+        }
+
+        Ok(())
+    }
+
+    fn register_fens_user_info(&mut self, user_info: &FensUserInfo) -> CtpResult<()> {
+        if self.api_ptr.is_null() {
+            return Err(CtpError::InitializationError("API未初始化".to_string()));
+        }
+
+        let field = FensUserInfoField::try_from(user_info)?;
+
+        unsafe {
+            CThostFtdcMdApi_RegisterFensUserInfo(self.api_ptr, &field as *const _ as *const c_void);
+        }
 
         Ok(())
     }
@@ -604,6 +716,11 @@ impl Drop for MdApi {
 extern "C" fn on_front_connected_callback(user_data: *mut c_void) {
     unsafe {
         if let Some(api) = (user_data as *mut MdApi).as_mut() {
+            if let (Some(recorder), [front]) = (&api.latency, api.fronts.as_slice()) {
+                if let Ok(mut recorder) = recorder.lock() {
+                    recorder.set_md_front(front);
+                }
+            }
             if let Some(ref mut handler) = api.handler {
                 handler.on_front_connected();
             }
@@ -778,13 +895,27 @@ extern "C" fn on_rsp_unsub_market_data_callback(
 extern "C" fn on_rtn_depth_market_data_callback(user_data: *mut c_void, market_data: *mut c_void) {
     unsafe {
         if let Some(api) = (user_data as *mut MdApi).as_mut() {
+            if market_data.is_null() {
+                return;
+            }
+            let received_at = api.latency.as_ref().map(|_| SystemTime::now());
+            let data_ptr = market_data as *const DepthMarketDataField;
+            // 低延迟通道：直接复制到预分配的槽位
+            if let Some(ref mut ring) = api.market_data_ring {
+                ring.publish_raw(data_ptr);
+            }
+            if let (Some(recorder), Some(received_at)) = (&api.latency, received_at) {
+                if let Ok(mut recorder) = recorder.lock() {
+                    recorder.on_tick(&*data_ptr, received_at);
+                }
+            }
+            if api.market_data_ring.is_some() {
+                return;
+            }
             if let Some(ref mut handler) = api.handler {
                 // 解析market_data指针
-                if !market_data.is_null() {
-                    let data_ptr = market_data as *const DepthMarketDataField;
-                    let parsed_data = (*data_ptr).clone();
-                    handler.on_rtn_depth_market_data(parsed_data);
-                }
+                let parsed_data = (*data_ptr).clone();
+                handler.on_rtn_depth_market_data(parsed_data);
             }
         }
     }
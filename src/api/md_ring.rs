@@ -0,0 +1,345 @@
+//! 行情低延迟通道
+//!
+//! 默认情况下每条行情都要在回调中`clone`后交给装箱的处理器，异步API还会再分配一次
+//! 通道节点。对延迟敏感的策略可以在[`MdApi`](crate::api::MdApi)上挂接
+//! [`MarketDataProducer`]：回调线程把`#[repr(C)]`的[`DepthMarketDataField`]直接复制到
+//! 预分配的环形缓冲区，不分配内存也不加锁，消费者通过[`MarketDataConsumer`]轮询或自旋读取。
+//!
+//! 缓冲区为单生产者单消费者。生产者从不等待消费者，消费者落后超过容量时最旧的行情会被
+//! 覆盖，并计入[`RingStats::overruns`]。
+
+use crate::api::md_api::DepthMarketDataField;
+use std::cell::UnsafeCell;
+use std::collections::HashMap;
+use std::hint;
+use std::ptr;
+use std::sync::atomic::{fence, AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+/// 按缓存行对齐，避免生产者和消费者的计数器伪共享
+#[repr(align(64))]
+#[derive(Default)]
+struct CachePadded<T>(T);
+
+struct Slot {
+    // 顺序锁：写入中为奇数，写完序号n的行情后为2n+2
+    seq: AtomicU64,
+    data: UnsafeCell<DepthMarketDataField>,
+}
+
+struct Ring {
+    slots: Box<[Slot]>,
+    mask: u64,
+    // 已发布的行情数，只由生产者写入
+    head: CachePadded<AtomicU64>,
+    consumed: CachePadded<AtomicU64>,
+    overruns: AtomicU64,
+    conflated: AtomicU64,
+}
+
+// 槽位数据由顺序锁保护，读取到被并发改写的数据时会丢弃重读
+unsafe impl Send for Ring {}
+unsafe impl Sync for Ring {}
+
+/// 环形缓冲区计数
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RingStats {
+    /// 缓冲区容量
+    pub capacity: u64,
+    /// 已写入的行情数
+    pub published: u64,
+    /// 已读取的行情数，合并掉的行情也计算在内
+    pub consumed: u64,
+    /// 消费者落后被覆盖的行情数
+    pub overruns: u64,
+    /// 按合约合并时被较新行情取代的行情数
+    pub conflated: u64,
+}
+
+impl RingStats {
+    /// 尚未读取的行情数
+    pub fn pending(&self) -> u64 {
+        self.published
+            .saturating_sub(self.consumed + self.overruns)
+            .min(self.capacity)
+    }
+}
+
+impl Ring {
+    fn stats(&self) -> RingStats {
+        RingStats {
+            capacity: self.mask + 1,
+            published: self.head.0.load(Ordering::Acquire),
+            consumed: self.consumed.0.load(Ordering::Relaxed),
+            overruns: self.overruns.load(Ordering::Relaxed),
+            conflated: self.conflated.load(Ordering::Relaxed),
+        }
+    }
+}
+
+/// 创建行情环形缓冲区
+///
+/// 容量向上取整为2的幂，最小为2。生产者通过
+/// [`MdApi::attach_market_data_ring`](crate::api::MdApi::attach_market_data_ring)
+/// 挂接到行情API，消费者交给策略线程
+pub fn market_data_ring(capacity: usize) -> (MarketDataProducer, MarketDataConsumer) {
+    let capacity = capacity.max(2).next_power_of_two();
+    let slots = (0..capacity)
+        .map(|_| Slot {
+            seq: AtomicU64::new(0),
+            data: UnsafeCell::new(DepthMarketDataField::default()),
+        })
+        .collect();
+    let ring = Arc::new(Ring {
+        slots,
+        mask: capacity as u64 - 1,
+        head: CachePadded::default(),
+        consumed: CachePadded::default(),
+        overruns: AtomicU64::new(0),
+        conflated: AtomicU64::new(0),
+    });
+    let producer = MarketDataProducer { ring: ring.clone() };
+    let consumer = MarketDataConsumer {
+        ring,
+        tail: 0,
+        batch: Vec::new(),
+        latest: HashMap::new(),
+    };
+    (producer, consumer)
+}
+
+/// 环形缓冲区的写入端，由行情回调线程独占
+pub struct MarketDataProducer {
+    ring: Arc<Ring>,
+}
+
+impl MarketDataProducer {
+    /// 写入一条行情，缓冲区满时覆盖最旧的行情
+    pub fn publish(&mut self, market_data: &DepthMarketDataField) {
+        // 引用总是指向有效的行情
+        unsafe { self.publish_raw(market_data) }
+    }
+
+    /// 直接从C++回调传入的指针复制行情
+    ///
+    /// # Safety
+    ///
+    /// `market_data`必须指向有效的`DepthMarketDataField`
+    pub(crate) unsafe fn publish_raw(&mut self, market_data: *const DepthMarketDataField) {
+        let ring = &*self.ring;
+        let head = ring.head.0.load(Ordering::Relaxed);
+        let slot = &ring.slots[(head & ring.mask) as usize];
+
+        slot.seq.store(2 * head + 1, Ordering::Relaxed);
+        fence(Ordering::Release);
+        ptr::copy_nonoverlapping(market_data, slot.data.get(), 1);
+        slot.seq.store(2 * head + 2, Ordering::Release);
+        ring.head.0.store(head + 1, Ordering::Release);
+    }
+
+    /// 缓冲区计数
+    pub fn stats(&self) -> RingStats {
+        self.ring.stats()
+    }
+}
+
+/// 环形缓冲区的读取端
+pub struct MarketDataConsumer {
+    ring: Arc<Ring>,
+    tail: u64,
+    // 合并读取时复用的缓冲，稳定运行后不再分配
+    batch: Vec<DepthMarketDataField>,
+    latest: HashMap<[u8; 81], usize>,
+}
+
+impl MarketDataConsumer {
+    /// 读取下一条行情，没有新行情时立即返回`None`
+    pub fn poll(&mut self) -> Option<DepthMarketDataField> {
+        let ring = &*self.ring;
+        loop {
+            let head = ring.head.0.load(Ordering::Acquire);
+            if self.tail == head {
+                return None;
+            }
+            let capacity = ring.mask + 1;
+            if head - self.tail > capacity {
+                skip_to(ring, &mut self.tail, head - capacity);
+                continue;
+            }
+
+            let slot = &ring.slots[(self.tail & ring.mask) as usize];
+            let expected = 2 * self.tail + 2;
+            if slot.seq.load(Ordering::Acquire) != expected {
+                // 槽位已被更新的行情占用，说明读取期间被生产者追上
+                let next = self.tail + 1;
+                skip_to(ring, &mut self.tail, next);
+                continue;
+            }
+            // 可能与生产者的写入并发，读到的数据在校验序号后才使用
+            let data = unsafe { ptr::read_volatile(slot.data.get()) };
+            fence(Ordering::Acquire);
+            if slot.seq.load(Ordering::Relaxed) != expected {
+                let next = self.tail + 1;
+                skip_to(ring, &mut self.tail, next);
+                continue;
+            }
+
+            self.tail += 1;
+            ring.consumed.0.fetch_add(1, Ordering::Relaxed);
+            return Some(data);
+        }
+    }
+
+    /// 自旋等待下一条行情
+    pub fn spin(&mut self) -> DepthMarketDataField {
+        loop {
+            if let Some(data) = self.poll() {
+                return data;
+            }
+            hint::spin_loop();
+        }
+    }
+
+    /// 在`timeout`内自旋等待下一条行情
+    pub fn spin_timeout(&mut self, timeout: Duration) -> Option<DepthMarketDataField> {
+        let deadline = Instant::now() + timeout;
+        loop {
+            if let Some(data) = self.poll() {
+                return Some(data);
+            }
+            if Instant::now() >= deadline {
+                return None;
+            }
+            hint::spin_loop();
+        }
+    }
+
+    /// 读取当前所有行情，同一合约只保留最新一条，按合约首次出现的顺序回调
+    ///
+    /// 返回回调次数
+    pub fn drain_conflated<F>(&mut self, mut f: F) -> usize
+    where
+        F: FnMut(&DepthMarketDataField),
+    {
+        let mut batch = std::mem::take(&mut self.batch);
+        let mut latest = std::mem::take(&mut self.latest);
+        let mut conflated = 0;
+        while let Some(data) = self.poll() {
+            match latest.get(&data.instrument_id) {
+                Some(&index) => {
+                    batch[index] = data;
+                    conflated += 1;
+                }
+                None => {
+                    latest.insert(data.instrument_id, batch.len());
+                    batch.push(data);
+                }
+            }
+        }
+        if conflated > 0 {
+            self.ring.conflated.fetch_add(conflated, Ordering::Relaxed);
+        }
+
+        for data in &batch {
+            f(data);
+        }
+        let delivered = batch.len();
+        batch.clear();
+        latest.clear();
+        self.batch = batch;
+        self.latest = latest;
+        delivered
+    }
+
+    /// 缓冲区计数
+    pub fn stats(&self) -> RingStats {
+        self.ring.stats()
+    }
+}
+
+// 跳过被覆盖的行情并计数
+fn skip_to(ring: &Ring, tail: &mut u64, to: u64) {
+    ring.overruns.fetch_add(to - *tail, Ordering::Relaxed);
+    *tail = to;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tick(instrument: &str, last_price: f64) -> DepthMarketDataField {
+        let mut data = DepthMarketDataField::default();
+        data.instrument_id[..instrument.len()].copy_from_slice(instrument.as_bytes());
+        data.last_price = last_price;
+        data
+    }
+
+    #[test]
+    fn test_ring_overrun() {
+        let (mut producer, mut consumer) = market_data_ring(3);
+        assert_eq!(producer.stats().capacity, 4);
+        assert!(consumer.poll().is_none());
+
+        for i in 0..6 {
+            producer.publish(&tick("rb2501", i as f64));
+        }
+        assert_eq!(consumer.stats().pending(), 4);
+        let prices: Vec<f64> = std::iter::from_fn(|| consumer.poll())
+            .map(|d| d.last_price)
+            .collect();
+        assert_eq!(prices, vec![2.0, 3.0, 4.0, 5.0]);
+
+        let stats = consumer.stats();
+        assert_eq!(stats.published, 6);
+        assert_eq!(stats.consumed, 4);
+        assert_eq!(stats.overruns, 2);
+        assert_eq!(stats.pending(), 0);
+    }
+
+    #[test]
+    fn test_drain_conflated() {
+        let (mut producer, mut consumer) = market_data_ring(16);
+        producer.publish(&tick("rb2501", 1.0));
+        producer.publish(&tick("cu2502", 2.0));
+        producer.publish(&tick("rb2501", 3.0));
+
+        let mut seen = Vec::new();
+        let delivered = consumer.drain_conflated(|d| {
+            seen.push((d.get_instrument_id().unwrap(), d.last_price));
+        });
+        assert_eq!(delivered, 2);
+        assert_eq!(
+            seen,
+            vec![("rb2501".to_string(), 3.0), ("cu2502".to_string(), 2.0)]
+        );
+        assert_eq!(consumer.stats().conflated, 1);
+        assert_eq!(consumer.drain_conflated(|_| {}), 0);
+    }
+
+    #[test]
+    fn test_ring_across_threads() {
+        let (mut producer, mut consumer) = market_data_ring(64);
+        let total = 10_000;
+        let writer = std::thread::spawn(move || {
+            for i in 0..total {
+                producer.publish(&tick("rb2501", i as f64));
+            }
+        });
+
+        let mut last = -1.0;
+        let mut received = 0u64;
+        while let Some(data) = consumer.spin_timeout(Duration::from_millis(200)) {
+            // 丢失的行情只能是被覆盖的，顺序不会乱
+            assert!(data.last_price > last);
+            last = data.last_price;
+            received += 1;
+        }
+        writer.join().unwrap();
+
+        let stats = consumer.stats();
+        assert_eq!(last, (total - 1) as f64);
+        assert_eq!(received + stats.overruns, total as u64);
+    }
+}
@@ -0,0 +1,691 @@
+//! 报单生命周期跟踪
+//!
+//! CTP只在报单被拒绝时才发送`OnRspOrderInsert`，正常报单的进展全部通过`OnRtnOrder`和
+//! `OnRtnTrade`推送。[`OrderHandle`]把同一笔报单的回报汇总起来，提供可等待的里程碑：
+//!
+//! - [`OrderHandle::accepted`] - CTP已接受（首个报单回报）
+//! - [`OrderHandle::exchange_accepted`] - 交易所已接受（分配了报单编号）
+//! - [`OrderHandle::next_fill`] - 逐笔成交
+//! - [`OrderHandle::finished`] - 终态：全部成交、撤单或被拒绝
+//!
+//! 也可以用[`OrderHandle::next_update`]按顺序接收每一次变化。
+
+use crate::api::trader_api::{InputOrderField, OrderField, TradeField, TraderApi};
+use crate::error::{CtpError, CtpResult};
+use crate::models::{OrderStatus, OrderSubmitStatus};
+use crate::types::{
+    InputOrderActionField, InstrumentIdType, RspInfoField, StringConvert, UserIdType,
+};
+use std::collections::HashMap;
+use std::sync::{Mutex as StdMutex, MutexGuard, Weak};
+use std::time::Duration;
+use tokio::sync::{mpsc, watch, Mutex};
+use tokio::time::timeout;
+
+/// 撤单操作标志
+pub(crate) const ACTION_FLAG_DELETE: u8 = b'0';
+
+/// 报单的一次变化
+#[derive(Debug, Clone)]
+pub enum OrderUpdate {
+    /// CTP已接受，收到首个报单回报
+    Accepted(OrderField),
+    /// 交易所已接受，分配了报单编号
+    ExchangeAccepted(OrderField),
+    /// 报单状态变化
+    Updated(OrderField),
+    /// 成交
+    Filled(TradeField),
+    /// 撤单被拒绝
+    CancelRejected(RspInfoField),
+    /// 报单结束，之后不会再有变化
+    Finished(OrderOutcome),
+}
+
+/// 报单的最终结果
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum OrderOutcome {
+    /// 全部成交
+    Filled { volume: i32 },
+    /// 已撤单，包括FAK/FOK未成交部分被交易所撤销，`volume_traded`为撤单前的成交数量
+    Cancelled {
+        volume_traded: i32,
+        status_msg: String,
+    },
+    /// 被CTP或交易所拒绝，交易所拒绝时`error_id`为0，原因见`error_msg`
+    Rejected { error_id: i32, error_msg: String },
+}
+
+/// 报单当前状况
+#[derive(Debug, Clone, Default)]
+pub struct OrderSnapshot {
+    /// 最新的报单回报
+    pub order: Option<OrderField>,
+    /// 是否已被CTP接受
+    pub accepted: bool,
+    /// 是否已被交易所接受
+    pub exchange_accepted: bool,
+    /// 已收到的成交
+    pub trades: Vec<TradeField>,
+    /// 最终结果，未结束时为`None`
+    pub outcome: Option<OrderOutcome>,
+}
+
+impl OrderSnapshot {
+    /// 已收到的成交数量
+    pub fn traded_volume(&self) -> i32 {
+        self.trades.iter().map(|trade| trade.volume).sum()
+    }
+
+    fn is_finished(&self) -> bool {
+        self.outcome.is_some()
+    }
+
+    // 报单已是终态且成交回报已到齐时给出结果，全部成交的报单回报可能先于最后一笔成交到达
+    fn settled_outcome(&self) -> Option<OrderOutcome> {
+        let order = self.order.as_ref()?;
+        if OrderSubmitStatus::from(order.order_submit_status) == OrderSubmitStatus::InsertRejected {
+            return Some(OrderOutcome::Rejected {
+                error_id: 0,
+                error_msg: order.status_msg.to_utf8_string().unwrap_or_default(),
+            });
+        }
+        let status = OrderStatus::from(order.order_status);
+        if !status.is_finished() || self.traded_volume() < order.volume_traded {
+            return None;
+        }
+        Some(match status {
+            OrderStatus::AllTraded => OrderOutcome::Filled {
+                volume: self.traded_volume(),
+            },
+            _ => OrderOutcome::Cancelled {
+                volume_traded: self.traded_volume(),
+                status_msg: order.status_msg.to_utf8_string().unwrap_or_default(),
+            },
+        })
+    }
+}
+
+/// 报单在会话内的唯一标识
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub(crate) struct OrderKey {
+    front_id: i32,
+    session_id: i32,
+    order_ref: String,
+}
+
+struct TrackedOrder {
+    snapshot: watch::Sender<OrderSnapshot>,
+    updates: mpsc::UnboundedSender<OrderUpdate>,
+    request_id: i32,
+    key: Option<OrderKey>,
+    sys_key: Option<(String, String)>,
+}
+
+#[derive(Default)]
+struct TrackerState {
+    next_id: u64,
+    orders: HashMap<u64, TrackedOrder>,
+    by_request: HashMap<i32, u64>,
+    by_ref: HashMap<OrderKey, u64>,
+    by_sys_id: HashMap<(String, String), u64>,
+    session: (i32, i32),
+}
+
+/// 跟踪异步API发出的报单，回调线程据此把回报分发给对应的[`OrderHandle`]
+#[derive(Default)]
+pub(crate) struct OrderTracker {
+    state: StdMutex<TrackerState>,
+}
+
+pub(crate) fn field_string(bytes: &impl StringConvert) -> String {
+    bytes
+        .to_utf8_string()
+        .unwrap_or_default()
+        .trim()
+        .to_string()
+}
+
+pub(crate) fn order_key(
+    front_id: i32,
+    session_id: i32,
+    order_ref: &impl StringConvert,
+) -> OrderKey {
+    OrderKey {
+        front_id,
+        session_id,
+        order_ref: field_string(order_ref),
+    }
+}
+
+pub(crate) fn sys_key(
+    exchange_id: &impl StringConvert,
+    order_sys_id: &impl StringConvert,
+) -> Option<(String, String)> {
+    let order_sys_id = field_string(order_sys_id);
+    (!order_sys_id.is_empty()).then(|| (field_string(exchange_id), order_sys_id))
+}
+
+impl OrderTracker {
+    fn lock(&self) -> MutexGuard<'_, TrackerState> {
+        self.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// 登记报单并发送
+    ///
+    /// 发送期间持有跟踪表的锁，回调线程收到的首个回报一定能找到这笔报单
+    pub(crate) fn track(
+        &self,
+        front_id: i32,
+        session_id: i32,
+        order_ref: &str,
+        send: impl FnOnce() -> CtpResult<i32>,
+    ) -> CtpResult<(
+        i32,
+        watch::Receiver<OrderSnapshot>,
+        mpsc::UnboundedReceiver<OrderUpdate>,
+    )> {
+        let mut state = self.lock();
+        let request_id = send()?;
+
+        let (snapshot, snapshot_rx) = watch::channel(OrderSnapshot::default());
+        let (updates, updates_rx) = mpsc::unbounded_channel();
+        let id = state.next_id;
+        state.next_id += 1;
+        state.session = (front_id, session_id);
+
+        let key = (!order_ref.trim().is_empty()).then(|| OrderKey {
+            front_id,
+            session_id,
+            order_ref: order_ref.trim().to_string(),
+        });
+        if let Some(key) = &key {
+            state.by_ref.insert(key.clone(), id);
+        }
+        state.by_request.insert(request_id, id);
+        state.orders.insert(
+            id,
+            TrackedOrder {
+                snapshot,
+                updates,
+                request_id,
+                key,
+                sys_key: None,
+            },
+        );
+        Ok((request_id, snapshot_rx, updates_rx))
+    }
+
+    /// 重建API后请求编号从头开始，旧编号不再用于匹配
+    pub(crate) fn reset_requests(&self) {
+        self.lock().by_request.clear();
+    }
+
+    /// 报单回报
+    pub(crate) fn on_order(&self, order: &OrderField) {
+        let mut state = self.lock();
+        let key = order_key(order.front_id, order.session_id, &order.order_ref);
+        // 报单时未填写引用的，按首个回报中的请求编号匹配
+        let Some(id) = state.by_ref.get(&key).copied().or_else(|| {
+            let id = state.by_request.get(&order.request_id).copied()?;
+            state
+                .orders
+                .get(&id)
+                .filter(|o| o.key.is_none())
+                .map(|_| id)
+        }) else {
+            return;
+        };
+
+        let sys_key = sys_key(&order.exchange_id, &order.order_sys_id);
+        if let Some(sys_key) = &sys_key {
+            state.by_sys_id.insert(sys_key.clone(), id);
+        }
+        state.by_ref.insert(key.clone(), id);
+        let Some(tracked) = state.orders.get_mut(&id) else {
+            return;
+        };
+        tracked.key = Some(key);
+        if sys_key.is_some() {
+            tracked.sys_key = sys_key;
+        }
+
+        let mut update = OrderUpdate::Updated(order.clone());
+        tracked.snapshot.send_modify(|snapshot| {
+            if !snapshot.accepted {
+                snapshot.accepted = true;
+                update = OrderUpdate::Accepted(order.clone());
+            } else if !snapshot.exchange_accepted && tracked.sys_key.is_some() {
+                update = OrderUpdate::ExchangeAccepted(order.clone());
+            }
+            snapshot.exchange_accepted |= tracked.sys_key.is_some();
+            snapshot.order = Some(order.clone());
+        });
+        // 首个回报已带报单编号时依次报告两个里程碑
+        let exchange_accepted =
+            matches!(update, OrderUpdate::Accepted(_)) && tracked.sys_key.is_some();
+        let _ = tracked.updates.send(update);
+        if exchange_accepted {
+            let _ = tracked
+                .updates
+                .send(OrderUpdate::ExchangeAccepted(order.clone()));
+        }
+        Self::settle(&mut state, id);
+    }
+
+    /// 成交回报
+    pub(crate) fn on_trade(&self, trade: &TradeField) {
+        let mut state = self.lock();
+        let Some(id) = sys_key(&trade.exchange_id, &trade.order_sys_id)
+            .and_then(|key| state.by_sys_id.get(&key).copied())
+        else {
+            return;
+        };
+        let Some(tracked) = state.orders.get(&id) else {
+            return;
+        };
+
+        // 续传私有流时成交可能重复推送
+        let mut is_new = false;
+        tracked.snapshot.send_if_modified(|snapshot| {
+            is_new = !snapshot
+                .trades
+                .iter()
+                .any(|t| t.trade_id == trade.trade_id && t.direction == trade.direction);
+            if is_new {
+                snapshot.trades.push(trade.clone());
+            }
+            is_new
+        });
+        if is_new {
+            let _ = tracked.updates.send(OrderUpdate::Filled(trade.clone()));
+            Self::settle(&mut state, id);
+        }
+    }
+
+    /// 报单被CTP拒绝（`OnRspOrderInsert`或`OnErrRtnOrderInsert`）
+    pub(crate) fn on_insert_rejected(
+        &self,
+        request_id: i32,
+        input_order: Option<&InputOrderField>,
+        rsp_info: Option<&RspInfoField>,
+    ) {
+        let Some(rsp) = rsp_info.filter(|rsp| !rsp.is_success()) else {
+            return;
+        };
+        let mut state = self.lock();
+        let (front_id, session_id) = state.session;
+        let id = input_order
+            .and_then(|req| {
+                state
+                    .by_ref
+                    .get(&order_key(front_id, session_id, &req.order_ref))
+            })
+            .or_else(|| state.by_request.get(&request_id))
+            .copied();
+        if let Some(id) = id {
+            Self::finish(
+                &mut state,
+                id,
+                OrderOutcome::Rejected {
+                    error_id: rsp.error_id,
+                    error_msg: rsp.get_error_msg().unwrap_or_default(),
+                },
+            );
+        }
+    }
+
+    /// 撤单被拒绝（`OnRspOrderAction`或`OnErrRtnOrderAction`）
+    pub(crate) fn on_action_rejected(
+        &self,
+        front_id: i32,
+        session_id: i32,
+        order_ref: &impl StringConvert,
+        exchange_id: &impl StringConvert,
+        order_sys_id: &impl StringConvert,
+        rsp_info: Option<&RspInfoField>,
+    ) {
+        let Some(rsp) = rsp_info.filter(|rsp| !rsp.is_success()) else {
+            return;
+        };
+        let state = self.lock();
+        let id = state
+            .by_ref
+            .get(&order_key(front_id, session_id, order_ref))
+            .or_else(|| {
+                sys_key(exchange_id, order_sys_id).and_then(|key| state.by_sys_id.get(&key))
+            });
+        if let Some(tracked) = id.and_then(|id| state.orders.get(id)) {
+            let _ = tracked
+                .updates
+                .send(OrderUpdate::CancelRejected(rsp.clone()));
+        }
+    }
+
+    fn settle(state: &mut TrackerState, id: u64) {
+        let outcome = state
+            .orders
+            .get(&id)
+            .and_then(|tracked| tracked.snapshot.borrow().settled_outcome());
+        if let Some(outcome) = outcome {
+            Self::finish(state, id, outcome);
+        }
+    }
+
+    // 记录结果并停止跟踪，通道随发送端释放而关闭
+    fn finish(state: &mut TrackerState, id: u64, outcome: OrderOutcome) {
+        let Some(tracked) = state.orders.remove(&id) else {
+            return;
+        };
+        if state.by_request.get(&tracked.request_id) == Some(&id) {
+            state.by_request.remove(&tracked.request_id);
+        }
+        if let Some(key) = &tracked.key {
+            state.by_ref.remove(key);
+        }
+        if let Some(key) = &tracked.sys_key {
+            state.by_sys_id.remove(key);
+        }
+        tracked.snapshot.send_modify(|snapshot| {
+            snapshot.outcome = Some(outcome.clone());
+        });
+        let _ = tracked.updates.send(OrderUpdate::Finished(outcome));
+    }
+}
+
+/// 一笔报单的句柄，由[`AsyncTraderApi::order_insert`](crate::api::AsyncTraderApi::order_insert)返回
+pub struct OrderHandle {
+    request_id: i32,
+    req: InputOrderField,
+    front_id: i32,
+    session_id: i32,
+    api: Weak<Mutex<TraderApi>>,
+    snapshot: watch::Receiver<OrderSnapshot>,
+    updates: mpsc::UnboundedReceiver<OrderUpdate>,
+    fills_seen: usize,
+}
+
+impl OrderHandle {
+    pub(crate) fn new(
+        request_id: i32,
+        req: InputOrderField,
+        front_id: i32,
+        session_id: i32,
+        api: Weak<Mutex<TraderApi>>,
+        snapshot: watch::Receiver<OrderSnapshot>,
+        updates: mpsc::UnboundedReceiver<OrderUpdate>,
+    ) -> Self {
+        Self {
+            request_id,
+            req,
+            front_id,
+            session_id,
+            api,
+            snapshot,
+            updates,
+            fills_seen: 0,
+        }
+    }
+
+    /// 报单请求编号
+    pub fn request_id(&self) -> i32 {
+        self.request_id
+    }
+
+    /// 报单引用，报单时未填写的在首个报单回报后可知
+    pub fn order_ref(&self) -> String {
+        match &self.snapshot.borrow().order {
+            Some(order) => field_string(&order.order_ref),
+            None => field_string(&self.req.order_ref),
+        }
+    }
+
+    /// 当前状况
+    pub fn snapshot(&self) -> OrderSnapshot {
+        self.snapshot.borrow().clone()
+    }
+
+    /// 等待CTP接受报单，返回首个报单回报
+    pub async fn accepted(&self, timeout_secs: u64) -> CtpResult<OrderField> {
+        let snapshot = self
+            .wait_for(timeout_secs, |s| s.accepted || s.is_finished())
+            .await?;
+        snapshot
+            .order
+            .ok_or_else(|| Self::not_reached(&snapshot.outcome))
+    }
+
+    /// 等待交易所接受报单，返回带报单编号的报单回报
+    pub async fn exchange_accepted(&self, timeout_secs: u64) -> CtpResult<OrderField> {
+        let snapshot = self
+            .wait_for(timeout_secs, |s| s.exchange_accepted || s.is_finished())
+            .await?;
+        match snapshot.order {
+            Some(order) if snapshot.exchange_accepted => Ok(order),
+            _ => Err(Self::not_reached(&snapshot.outcome)),
+        }
+    }
+
+    /// 等待下一笔成交，报单结束且没有未取走的成交时返回`None`
+    pub async fn next_fill(&mut self, timeout_secs: u64) -> CtpResult<Option<TradeField>> {
+        let seen = self.fills_seen;
+        let snapshot = self
+            .wait_for(timeout_secs, |s| s.trades.len() > seen || s.is_finished())
+            .await?;
+        let trade = snapshot.trades.get(seen).cloned();
+        if trade.is_some() {
+            self.fills_seen += 1;
+        }
+        Ok(trade)
+    }
+
+    /// 等待报单结束
+    pub async fn finished(&self, timeout_secs: u64) -> CtpResult<OrderOutcome> {
+        let snapshot = self
+            .wait_for(timeout_secs, OrderSnapshot::is_finished)
+            .await?;
+        snapshot
+            .outcome
+            .ok_or_else(|| CtpError::Other("报单跟踪已停止".to_string()))
+    }
+
+    /// 接收下一次变化，报单结束后所有变化取完时返回`None`
+    pub async fn next_update(&mut self) -> Option<OrderUpdate> {
+        self.updates.recv().await
+    }
+
+    /// 撤单，返回撤单请求编号
+    ///
+    /// 撤单被拒绝时通过[`OrderUpdate::CancelRejected`]报告，撤单成功后报单以
+    /// [`OrderOutcome::Cancelled`]结束
+    pub async fn cancel(&self) -> CtpResult<i32> {
+        let snapshot = self.snapshot();
+        if let Some(outcome) = &snapshot.outcome {
+            return Err(CtpError::InvalidParameterError(format!(
+                "报单已结束，无法撤单: {:?}",
+                outcome
+            )));
+        }
+        let req = self.cancel_request(snapshot.order.as_ref())?;
+        let api = self
+            .api
+            .upgrade()
+            .ok_or_else(|| CtpError::InitializationError("交易API已释放".to_string()))?;
+        let mut api = api.lock().await;
+        api.req_order_action(&req)
+    }
+
+    fn cancel_request(&self, order: Option<&OrderField>) -> CtpResult<InputOrderActionField> {
+        let mut req = match order {
+            Some(order) => InputOrderActionField::new(
+                &field_string(&self.req.broker_id),
+                &field_string(&self.req.investor_id),
+                ACTION_FLAG_DELETE,
+                &field_string(&order.order_ref),
+                order.front_id,
+                order.session_id,
+                &field_string(&order.exchange_id),
+                &field_string(&order.order_sys_id),
+            )?,
+            None => {
+                let order_ref = field_string(&self.req.order_ref);
+                if order_ref.is_empty() {
+                    return Err(CtpError::InvalidParameterError(
+                        "报单引用未知，需等待报单回报后再撤单".to_string(),
+                    ));
+                }
+                InputOrderActionField::new(
+                    &field_string(&self.req.broker_id),
+                    &field_string(&self.req.investor_id),
+                    ACTION_FLAG_DELETE,
+                    &order_ref,
+                    self.front_id,
+                    self.session_id,
+                    &field_string(&self.req.exchange_id),
+                    "",
+                )?
+            }
+        };
+        req.user_id = UserIdType::from_utf8_string(&field_string(&self.req.user_id))?;
+        req.instrument_id =
+            InstrumentIdType::from_utf8_string(&field_string(&self.req.instrument_id))?;
+        Ok(req)
+    }
+
+    async fn wait_for(
+        &self,
+        timeout_secs: u64,
+        predicate: impl FnMut(&OrderSnapshot) -> bool,
+    ) -> CtpResult<OrderSnapshot> {
+        let mut receiver = self.snapshot.clone();
+        let wait = async { receiver.wait_for(predicate).await.map(|s| s.clone()) };
+        match timeout(Duration::from_secs(timeout_secs), wait).await {
+            Ok(Ok(snapshot)) => Ok(snapshot),
+            Ok(Err(_)) => Err(CtpError::Other("报单跟踪已停止".to_string())),
+            Err(_) => Err(CtpError::TimeoutError("等待报单回报超时".to_string())),
+        }
+    }
+
+    // 报单在到达里程碑前结束
+    fn not_reached(outcome: &Option<OrderOutcome>) -> CtpError {
+        match outcome {
+            Some(OrderOutcome::Rejected {
+                error_id,
+                error_msg,
+            }) => CtpError::BusinessError(*error_id, error_msg.clone()),
+            outcome => CtpError::Other(format!("报单已结束: {:?}", outcome)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn order(status: u8, sys_id: &str, volume_traded: i32) -> OrderField {
+        OrderField {
+            front_id: 1,
+            session_id: 2,
+            request_id: 7,
+            order_ref: <[u8; 13]>::from_utf8_string("000000000001").unwrap(),
+            exchange_id: <[u8; 9]>::from_utf8_string("SHFE").unwrap(),
+            order_sys_id: <[u8; 21]>::from_utf8_string(sys_id).unwrap(),
+            order_status: status,
+            order_submit_status: if sys_id.is_empty() { b'0' } else { b'3' },
+            volume_traded,
+            ..Default::default()
+        }
+    }
+
+    fn trade(trade_id: &str, volume: i32) -> TradeField {
+        TradeField {
+            exchange_id: <[u8; 9]>::from_utf8_string("SHFE").unwrap(),
+            order_sys_id: <[u8; 21]>::from_utf8_string("    12345").unwrap(),
+            trade_id: <[u8; 21]>::from_utf8_string(trade_id).unwrap(),
+            volume,
+            ..Default::default()
+        }
+    }
+
+    fn handle(tracker: &OrderTracker, order_ref: &str) -> OrderHandle {
+        let (request_id, snapshot, updates) = tracker.track(1, 2, order_ref, || Ok(7)).unwrap();
+        OrderHandle::new(
+            request_id,
+            InputOrderField::default(),
+            1,
+            2,
+            Weak::new(),
+            snapshot,
+            updates,
+        )
+    }
+
+    #[tokio::test]
+    async fn test_order_lifecycle() {
+        let tracker = OrderTracker::default();
+        let mut handle = handle(&tracker, "000000000001");
+
+        tracker.on_order(&order(b'a', "", 0));
+        assert!(handle.accepted(1).await.is_ok());
+        tracker.on_order(&order(b'3', "    12345", 0));
+        let accepted = handle.exchange_accepted(1).await.unwrap();
+        assert_eq!(field_string(&accepted.order_sys_id), "12345");
+
+        // 全部成交的报单回报先于最后一笔成交到达
+        tracker.on_trade(&trade("1", 1));
+        tracker.on_trade(&trade("1", 1));
+        tracker.on_order(&order(b'0', "    12345", 3));
+        assert!(handle.snapshot().outcome.is_none());
+        tracker.on_trade(&trade("2", 2));
+
+        assert_eq!(handle.next_fill(1).await.unwrap().unwrap().volume, 1);
+        assert_eq!(handle.next_fill(1).await.unwrap().unwrap().volume, 2);
+        assert!(handle.next_fill(1).await.unwrap().is_none());
+        assert_eq!(
+            handle.finished(1).await.unwrap(),
+            OrderOutcome::Filled { volume: 3 }
+        );
+
+        let mut updates = Vec::new();
+        while let Some(update) = handle.next_update().await {
+            updates.push(update);
+        }
+        assert!(matches!(updates[0], OrderUpdate::Accepted(_)));
+        assert!(matches!(updates[1], OrderUpdate::ExchangeAccepted(_)));
+        assert!(matches!(updates[2], OrderUpdate::Filled(_)));
+        assert!(matches!(updates.last(), Some(OrderUpdate::Finished(_))));
+        assert_eq!(updates.len(), 6);
+        assert!(handle.cancel().await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_rejection_and_request_matching() {
+        let tracker = OrderTracker::default();
+        let rejected = handle(&tracker, "000000000001");
+        let rsp = RspInfoField {
+            error_id: 31,
+            ..Default::default()
+        };
+        tracker.on_insert_rejected(7, None, Some(&rsp));
+        match rejected.accepted(1).await {
+            Err(CtpError::BusinessError(31, _)) => {}
+            other => panic!("unexpected {:?}", other),
+        }
+
+        // 未填写引用的报单按请求编号匹配首个回报
+        let handle = handle(&tracker, "");
+        assert!(matches!(
+            handle.finished(0).await,
+            Err(CtpError::TimeoutError(_))
+        ));
+        tracker.on_order(&order(b'5', "", 0));
+        assert_eq!(handle.order_ref(), "000000000001");
+        assert!(matches!(
+            handle.finished(1).await.unwrap(),
+            OrderOutcome::Cancelled {
+                volume_traded: 0,
+                ..
+            }
+        ));
+    }
+}
@@ -3,11 +3,25 @@
 //! 提供期货交易功能，包括下单、撤单、查询等
 
 use crate::api::md_api::DepthMarketDataField;
-use crate::api::utils::normalize_flow_path;
 use crate::api::{safe_cstr_to_string, to_cstring, CtpApi};
+use crate::compliance::ComplianceMonitor;
 use crate::error::{CtpError, CtpResult};
 use crate::ffi::trader_api::*;
 use crate::ffi::{CreateTraderSpiBridge, TraderSpiCallbacks};
+use crate::flow::FlowLock;
+use crate::journal::{AuditJournal, JournalEntry, JournalField};
+use crate::latency::{LatencyKind, LatencyRecorder};
+use crate::models::{encode_text, FensUserInfo, FrontInfo};
+use crate::order_ref::OrderRefAllocator;
+use crate::types::{
+    AccountregisterField, AuthCodeType, ContractBankField, FensUserInfoField, FrontInfoField,
+    NotifyQueryAccountField, QryAccountregisterField, QryContractBankField, QryTransferSerialField,
+    ReqGenUserCaptchaField, ReqGenUserTextField, ReqQueryAccountField, ReqTransferField,
+    ReqUserAuthMethodField, ReqUserLoginWithCaptchaField, ReqUserLoginWithOtpField,
+    ReqUserLoginWithTextField, RspGenUserCaptchaField, RspGenUserTextField, RspRepealField,
+    RspTransferField, RspUserAuthMethodField, TradingAccountPasswordUpdateField,
+    TransferSerialField, UserPasswordUpdateField, UserSystemInfoField, WechatUserSystemInfoField,
+};
 use crate::types::{
     ExchangeField, InputBatchOrderActionField, InputExecOrderActionField, InputExecOrderField,
     InputForQuoteField, InputOrderActionField, InputQuoteActionField, InputQuoteField,
@@ -19,12 +33,14 @@ use crate::types::{
     QryProductField, QrySettlementInfoField, QryTradeField, QryTradingAccountField,
     QryTransferBankField, RemoveParkedOrderActionField, RemoveParkedOrderField, ReqUserLoginField,
     RspInfoField, RspUserLoginField, SettlementInfoConfirmField, SettlementInfoField,
-    TransferBankField,
+    StringConvert, TransferBankField,
 };
 use std::ffi::c_void;
 use std::os::raw::c_int;
 use std::ptr;
 use std::sync::{Arc, Mutex};
+use std::time::Instant;
+use tracing::warn;
 
 // 交易API封装
 #[allow(dead_code)]
@@ -39,6 +55,16 @@ pub struct TraderApi {
     request_id: Arc<Mutex<i32>>,
     // 回调处理器
     handler: Option<Box<dyn TraderSpiHandler + Send + Sync>>,
+    // 流文件目录锁，释放API后解锁
+    flow_lock: Option<FlowLock>,
+    // 合规监控器
+    compliance: Option<Arc<Mutex<ComplianceMonitor>>>,
+    // 延迟记录器
+    latency: Option<Arc<Mutex<LatencyRecorder>>>,
+    // 审计日志
+    journal: Option<Arc<Mutex<AuditJournal>>>,
+    // 报单引用分配器
+    order_refs: Arc<OrderRefAllocator>,
 }
 
 // 交易SPI回调处理器特质
@@ -403,21 +429,184 @@ pub trait TraderSpiHandler {
         is_last: bool,
     ) {
     }
+
+    // 期货发起银行资金转期货应答
+    fn on_rsp_from_bank_to_future_by_future(
+        &mut self,
+        req_transfer: Option<ReqTransferField>,
+        rsp_info: Option<RspInfoField>,
+        request_id: i32,
+        is_last: bool,
+    ) {
+    }
+
+    // 期货发起期货资金转银行应答
+    fn on_rsp_from_future_to_bank_by_future(
+        &mut self,
+        req_transfer: Option<ReqTransferField>,
+        rsp_info: Option<RspInfoField>,
+        request_id: i32,
+        is_last: bool,
+    ) {
+    }
+
+    // 期货发起查询银行余额应答
+    fn on_rsp_query_bank_account_money_by_future(
+        &mut self,
+        req_query_account: Option<ReqQueryAccountField>,
+        rsp_info: Option<RspInfoField>,
+        request_id: i32,
+        is_last: bool,
+    ) {
+    }
+
+    // 期货发起银行资金转期货通知
+    fn on_rtn_from_bank_to_future_by_future(&mut self, rsp_transfer: RspTransferField) {}
+
+    // 期货发起期货资金转银行通知
+    fn on_rtn_from_future_to_bank_by_future(&mut self, rsp_transfer: RspTransferField) {}
+
+    // 期货发起冲正银行转期货请求，银行处理完毕后报盘发回的通知
+    fn on_rtn_repeal_from_bank_to_future_by_future(&mut self, rsp_repeal: RspRepealField) {}
+
+    // 期货发起冲正期货转银行请求，银行处理完毕后报盘发回的通知
+    fn on_rtn_repeal_from_future_to_bank_by_future(&mut self, rsp_repeal: RspRepealField) {}
+
+    // 期货发起银行资金转期货错误回报
+    fn on_err_rtn_bank_to_future_by_future(
+        &mut self,
+        req_transfer: Option<ReqTransferField>,
+        rsp_info: Option<RspInfoField>,
+    ) {
+    }
+
+    // 期货发起期货资金转银行错误回报
+    fn on_err_rtn_future_to_bank_by_future(
+        &mut self,
+        req_transfer: Option<ReqTransferField>,
+        rsp_info: Option<RspInfoField>,
+    ) {
+    }
+
+    // 期货发起查询银行余额通知
+    fn on_rtn_query_bank_balance_by_future(
+        &mut self,
+        notify_query_account: NotifyQueryAccountField,
+    ) {
+    }
+
+    // 期货发起查询银行余额错误回报
+    fn on_err_rtn_query_bank_balance_by_future(
+        &mut self,
+        req_query_account: Option<ReqQueryAccountField>,
+        rsp_info: Option<RspInfoField>,
+    ) {
+    }
+
+    // 请求查询转帐流水响应
+    fn on_rsp_qry_transfer_serial(
+        &mut self,
+        transfer_serial: Option<TransferSerialField>,
+        rsp_info: Option<RspInfoField>,
+        request_id: i32,
+        is_last: bool,
+    ) {
+    }
+
+    // 请求查询签约银行响应
+    fn on_rsp_qry_contract_bank(
+        &mut self,
+        contract_bank: Option<ContractBankField>,
+        rsp_info: Option<RspInfoField>,
+        request_id: i32,
+        is_last: bool,
+    ) {
+    }
+
+    // 请求查询银期签约关系响应
+    fn on_rsp_qry_accountregister(
+        &mut self,
+        accountregister: Option<AccountregisterField>,
+        rsp_info: Option<RspInfoField>,
+        request_id: i32,
+        is_last: bool,
+    ) {
+    }
+
+    // 用户口令更新请求响应
+    fn on_rsp_user_password_update(
+        &mut self,
+        user_password_update: Option<UserPasswordUpdateField>,
+        rsp_info: Option<RspInfoField>,
+        request_id: i32,
+        is_last: bool,
+    ) {
+    }
+
+    // 资金账户口令更新请求响应
+    fn on_rsp_trading_account_password_update(
+        &mut self,
+        trading_account_password_update: Option<TradingAccountPasswordUpdateField>,
+        rsp_info: Option<RspInfoField>,
+        request_id: i32,
+        is_last: bool,
+    ) {
+    }
+
+    // 查询用户当前支持的认证模式响应
+    fn on_rsp_user_auth_method(
+        &mut self,
+        rsp_user_auth_method: Option<RspUserAuthMethodField>,
+        rsp_info: Option<RspInfoField>,
+        request_id: i32,
+        is_last: bool,
+    ) {
+    }
+
+    // 获取图形验证码响应
+    fn on_rsp_gen_user_captcha(
+        &mut self,
+        rsp_gen_user_captcha: Option<RspGenUserCaptchaField>,
+        rsp_info: Option<RspInfoField>,
+        request_id: i32,
+        is_last: bool,
+    ) {
+    }
+
+    // 获取短信验证码响应
+    fn on_rsp_gen_user_text(
+        &mut self,
+        rsp_gen_user_text: Option<RspGenUserTextField>,
+        rsp_info: Option<RspInfoField>,
+        request_id: i32,
+        is_last: bool,
+    ) {
+    }
 }
 
 // 客户端认证响应
 #[repr(C)]
 #[derive(Debug, Clone)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(default)
+)]
 pub struct RspAuthenticateField {
     // 经纪公司代码
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
     pub broker_id: [u8; 11],
     // 用户代码
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
     pub user_id: [u8; 16],
     // 用户端产品信息
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
     pub user_product_info: [u8; 11],
     // 应用单元代码
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
     pub app_id: [u8; 33],
     // 应用类型
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::char_flag"))]
     pub app_type: u8,
 }
 
@@ -430,46 +619,66 @@ impl Default for RspAuthenticateField {
 // 报单录入
 #[repr(C)]
 #[derive(Debug, Clone)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(default)
+)]
 pub struct InputOrderField {
     // 经纪公司代码
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
     pub broker_id: [u8; 11],
     // 投资者代码
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
     pub investor_id: [u8; 13],
     // 合约代码
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
     pub instrument_id: [u8; 31],
     // 报单引用
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
     pub order_ref: [u8; 13],
     // 用户代码
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
     pub user_id: [u8; 16],
     // 报单价格条件
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::char_flag"))]
     pub order_price_type: u8,
     // 买卖方向
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::char_flag"))]
     pub direction: u8,
     // 组合开平标志
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
     pub comb_offset_flag: [u8; 5],
     // 组合投机套保标志
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
     pub comb_hedge_flag: [u8; 5],
     // 价格
     pub limit_price: f64,
     // 数量
     pub volume_total_original: i32,
     // 有效期类型
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::char_flag"))]
     pub time_condition: u8,
     // GTD日期
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
     pub gtd_date: [u8; 9],
     // 成交量类型
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::char_flag"))]
     pub volume_condition: u8,
     // 最小成交量
     pub min_volume: i32,
     // 触发条件
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::char_flag"))]
     pub contingent_condition: u8,
     // 止损价
     pub stop_price: f64,
     // 强平原因
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::char_flag"))]
     pub force_close_reason: u8,
     // 自动挂起标志
     pub is_auto_suspend: i32,
     // 业务单元
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
     pub business_unit: [u8; 21],
     // 请求编号
     pub request_id: i32,
@@ -478,20 +687,28 @@ pub struct InputOrderField {
     // 互换单标志
     pub is_swap_order: i32,
     // 交易所代码
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
     pub exchange_id: [u8; 9],
     // 投资单元代码
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
     pub invest_unit_id: [u8; 17],
     // 资金账号
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
     pub account_id: [u8; 13],
     // 币种代码
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
     pub currency_id: [u8; 4],
     // 交易编码
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
     pub client_id: [u8; 11],
     // Mac地址
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
     pub mac_address: [u8; 21],
     // 合约在交易所的代码
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
     pub exchange_inst_id: [u8; 31],
     // IP地址
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
     pub ip_address: [u8; 16],
 }
 
@@ -504,98 +721,138 @@ impl Default for InputOrderField {
 // 报单
 #[repr(C)]
 #[derive(Debug, Clone)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(default)
+)]
 pub struct OrderField {
     // 经纪公司代码
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
     pub broker_id: [u8; 11],
     // 投资者代码
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
     pub investor_id: [u8; 13],
     // 合约代码
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
     pub instrument_id: [u8; 31],
     // 报单引用
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
     pub order_ref: [u8; 13],
     // 用户代码
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
     pub user_id: [u8; 16],
     // 报单价格条件
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::char_flag"))]
     pub order_price_type: u8,
     // 买卖方向
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::char_flag"))]
     pub direction: u8,
     // 组合开平标志
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
     pub comb_offset_flag: [u8; 5],
     // 组合投机套保标志
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
     pub comb_hedge_flag: [u8; 5],
     // 价格
     pub limit_price: f64,
     // 数量
     pub volume_total_original: i32,
     // 有效期类型
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::char_flag"))]
     pub time_condition: u8,
     // GTD日期
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
     pub gtd_date: [u8; 9],
     // 成交量类型
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::char_flag"))]
     pub volume_condition: u8,
     // 最小成交量
     pub min_volume: i32,
     // 触发条件
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::char_flag"))]
     pub contingent_condition: u8,
     // 止损价
     pub stop_price: f64,
     // 强平原因
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::char_flag"))]
     pub force_close_reason: u8,
     // 自动挂起标志
     pub is_auto_suspend: i32,
     // 业务单元
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
     pub business_unit: [u8; 21],
     // 请求编号
     pub request_id: i32,
     // 本地报单编号
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
     pub order_local_id: [u8; 13],
     // 交易所代码
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
     pub exchange_id: [u8; 9],
     // 会员代码
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
     pub participant_id: [u8; 11],
     // 客户代码
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
     pub client_id: [u8; 11],
     // 合约在交易所的代码
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
     pub exchange_inst_id: [u8; 31],
     // 交易员代码
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
     pub trader_id: [u8; 21],
     // 安装编号
     pub install_id: i32,
     // 报单提交状态
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::char_flag"))]
     pub order_submit_status: u8,
     // 报单提示序号
     pub notify_sequence: i32,
     // 交易日
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
     pub trading_day: [u8; 9],
     // 结算编号
     pub settlement_id: i32,
     // 报单编号
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
     pub order_sys_id: [u8; 21],
     // 报单来源
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::char_flag"))]
     pub order_source: u8,
     // 报单状态
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::char_flag"))]
     pub order_status: u8,
     // 报单类型
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::char_flag"))]
     pub order_type: u8,
     // 今成交数量
     pub volume_traded: i32,
     // 剩余数量
     pub volume_total: i32,
     // 报单日期
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
     pub insert_date: [u8; 9],
     // 委托时间
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
     pub insert_time: [u8; 9],
     // 激活时间
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
     pub active_time: [u8; 9],
     // 挂起时间
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
     pub suspend_time: [u8; 9],
     // 最后修改时间
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
     pub update_time: [u8; 9],
     // 撤销时间
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
     pub cancel_time: [u8; 9],
     // 最后修改交易员代码
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
     pub active_trader_id: [u8; 21],
     // 结算会员编号
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
     pub clearing_part_id: [u8; 11],
     // 序号
     pub sequence_no: i32,
@@ -604,32 +861,42 @@ pub struct OrderField {
     // 会话编号
     pub session_id: i32,
     // 用户端产品信息
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
     pub user_product_info: [u8; 11],
     // 状态信息
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
     pub status_msg: [u8; 81],
     // 用户强平标志
     pub user_force_close: i32,
     // 操作用户代码
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
     pub active_user_id: [u8; 16],
     // 经纪公司报单编号
     pub broker_order_seq: i32,
     // 相关报单
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
     pub relative_order_sys_id: [u8; 21],
     // 郑商所成交数量
     pub zczc_total_traded_volume: i32,
     // 互换单标志
     pub is_swap_order: i32,
     // 营业部编号
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
     pub branch_id: [u8; 9],
     // 投资单元代码
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
     pub invest_unit_id: [u8; 17],
     // 资金账号
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
     pub account_id: [u8; 13],
     // 币种代码
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
     pub currency_id: [u8; 4],
     // Mac地址
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
     pub mac_address: [u8; 21],
     // IP地址
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
     pub ip_address: [u8; 16],
 }
 
@@ -642,68 +909,99 @@ impl Default for OrderField {
 // 成交
 #[repr(C)]
 #[derive(Debug, Clone)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(default)
+)]
 pub struct TradeField {
     // 经纪公司代码
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
     pub broker_id: [u8; 11],
     // 投资者代码
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
     pub investor_id: [u8; 13],
     // 合约代码
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
     pub instrument_id: [u8; 31],
     // 报单引用
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
     pub order_ref: [u8; 13],
     // 用户代码
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
     pub user_id: [u8; 16],
     // 交易所代码
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
     pub exchange_id: [u8; 9],
     // 成交编号
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
     pub trade_id: [u8; 21],
     // 买卖方向
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::char_flag"))]
     pub direction: u8,
     // 报单编号
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
     pub order_sys_id: [u8; 21],
     // 会员代码
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
     pub participant_id: [u8; 11],
     // 客户代码
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
     pub client_id: [u8; 11],
     // 交易角色
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::char_flag"))]
     pub trading_role: u8,
     // 合约在交易所的代码
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
     pub exchange_inst_id: [u8; 31],
     // 开平标志
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::char_flag"))]
     pub offset_flag: u8,
     // 投机套保标志
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::char_flag"))]
     pub hedge_flag: u8,
     // 价格
     pub price: f64,
     // 数量
     pub volume: i32,
     // 成交时期
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
     pub trade_date: [u8; 9],
     // 成交时间
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
     pub trade_time: [u8; 9],
     // 成交类型
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::char_flag"))]
     pub trade_type: u8,
     // 成交价来源
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::char_flag"))]
     pub price_source: u8,
     // 交易员代码
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
     pub trader_id: [u8; 21],
     // 本地报单编号
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
     pub order_local_id: [u8; 13],
     // 结算会员编号
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
     pub clearing_part_id: [u8; 11],
     // 业务单元
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
     pub business_unit: [u8; 21],
     // 序号
     pub sequence_no: i32,
     // 交易日
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
     pub trading_day: [u8; 9],
     // 结算编号
     pub settlement_id: i32,
     // 经纪公司报单编号
     pub broker_order_seq: i32,
     // 成交来源
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::char_flag"))]
     pub trade_source: u8,
     // 投资单元代码
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
     pub invest_unit_id: [u8; 17],
 }
 
@@ -716,32 +1014,49 @@ impl Default for TradeField {
 // 投资者
 #[repr(C)]
 #[derive(Debug, Clone)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(default)
+)]
 pub struct InvestorField {
     // 投资者代码
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
     pub investor_id: [u8; 13],
     // 经纪公司代码
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
     pub broker_id: [u8; 11],
     // 投资者分组代码
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
     pub investor_group_id: [u8; 13],
     // 投资者名称
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
     pub investor_name: [u8; 81],
     // 证件类型
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::char_flag"))]
     pub identity_card_type: u8,
     // 证件号码
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
     pub identity_card_no: [u8; 51],
     // 是否活跃
     pub is_active: i32,
     // 联系电话
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
     pub telephone: [u8; 41],
     // 通讯地址
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
     pub address: [u8; 101],
     // 开户日期
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
     pub open_date: [u8; 9],
     // 手机
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
     pub mobile: [u8; 41],
     // 手续费率模板代码
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
     pub comm_model_id: [u8; 13],
     // 保证金率模板代码
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
     pub margin_model_id: [u8; 13],
 }
 
@@ -754,10 +1069,17 @@ impl Default for InvestorField {
 // 资金账户
 #[repr(C)]
 #[derive(Debug, Clone)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(default)
+)]
 pub struct TradingAccountField {
     // 经纪公司代码
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
     pub broker_id: [u8; 11],
     // 投资者帐号
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
     pub account_id: [u8; 13],
     // 上次质押金额
     pub pre_mortgage: f64,
@@ -802,6 +1124,7 @@ pub struct TradingAccountField {
     // 基本准备金
     pub reserve: f64,
     // 交易日
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
     pub trading_day: [u8; 9],
     // 结算编号
     pub settlement_id: i32,
@@ -818,6 +1141,7 @@ pub struct TradingAccountField {
     // 保底期货结算准备金
     pub reserve_balance: f64,
     // 币种代码
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
     pub currency_id: [u8; 4],
     // 上次货币质入金额
     pub pre_fund_mortgage_in: f64,
@@ -848,6 +1172,7 @@ pub struct TradingAccountField {
     // 特殊产品交易所保证金
     pub spec_product_exchange_margin: f64,
     // 业务类型
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::char_flag"))]
     pub biz_type: u8,
     // 延时换汇冻结金额
     pub frozen_swap: f64,
@@ -864,18 +1189,29 @@ impl Default for TradingAccountField {
 // 投资者持仓
 #[repr(C)]
 #[derive(Debug, Clone)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(default)
+)]
 pub struct InvestorPositionField {
     // 合约代码
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
     pub instrument_id: [u8; 31],
     // 经纪公司代码
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
     pub broker_id: [u8; 11],
     // 投资者代码
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
     pub investor_id: [u8; 13],
     // 持仓多空方向
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::char_flag"))]
     pub posi_direction: u8,
     // 投机套保标志
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::char_flag"))]
     pub hedge_flag: u8,
     // 持仓日期
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::char_flag"))]
     pub position_date: u8,
     // 上日持仓
     pub yd_position: i32,
@@ -922,6 +1258,7 @@ pub struct InvestorPositionField {
     // 本次结算价
     pub settlement_price: f64,
     // 交易日
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
     pub trading_day: [u8; 9],
     // 结算编号
     pub settlement_id: i32,
@@ -952,10 +1289,12 @@ pub struct InvestorPositionField {
     // 放弃执行冻结
     pub abandon_frozen: i32,
     // 交易所代码
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
     pub exchange_id: [u8; 9],
     // 执行冻结的昨仓
     pub yd_strike_frozen: i32,
     // 投资单元代码
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
     pub invest_unit_id: [u8; 17],
     // 大商所持仓成本差值，只有大商所使用
     pub position_cost_offset: f64,
@@ -974,18 +1313,29 @@ impl Default for InvestorPositionField {
 // 合约
 #[repr(C)]
 #[derive(Debug, Clone)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(default)
+)]
 pub struct InstrumentField {
     // 合约代码
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
     pub instrument_id: [u8; 31],
     // 交易所代码
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
     pub exchange_id: [u8; 9],
     // 合约名称
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
     pub instrument_name: [u8; 21],
     // 合约在交易所的代码
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
     pub exchange_inst_id: [u8; 31],
     // 产品代码
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
     pub product_id: [u8; 31],
     // 产品类型
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::char_flag"))]
     pub product_class: u8,
     // 交割年份
     pub delivery_year: i32,
@@ -1004,38 +1354,50 @@ pub struct InstrumentField {
     // 最小变动价位
     pub price_tick: f64,
     // 创建日
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
     pub create_date: [u8; 9],
     // 上市日
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
     pub open_date: [u8; 9],
     // 到期日
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
     pub expire_date: [u8; 9],
     // 开始交割日
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
     pub start_deliv_date: [u8; 9],
     // 结束交割日
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
     pub end_deliv_date: [u8; 9],
     // 合约生命周期状态
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::char_flag"))]
     pub inst_life_phase: u8,
     // 当前是否交易
     pub is_trading: i32,
     // 持仓类型
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::char_flag"))]
     pub position_type: u8,
     // 持仓日期类型
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::char_flag"))]
     pub position_date_type: u8,
     // 多头保证金率
     pub long_margin_ratio: f64,
     // 空头保证金率
     pub short_margin_ratio: f64,
     // 是否使用大额单边保证金算法
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::char_flag"))]
     pub max_margin_side_algorithm: u8,
     // 基础商品代码
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
     pub underlying_instr_id: [u8; 31],
     // 执行价
     pub strike_price: f64,
     // 期权类型
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::char_flag"))]
     pub options_type: u8,
     // 合约基础商品乘数
     pub underlying_multiple: f64,
     // 组合类型
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::char_flag"))]
     pub combination_type: u8,
 }
 
@@ -1048,18 +1410,44 @@ impl Default for InstrumentField {
 unsafe impl Send for TraderApi {}
 unsafe impl Sync for TraderApi {}
 
+// 前置信息查询句柄
+//
+// 回调处理器在连接成功时用它查询实际连接的前置。句柄只能在所属TraderApi存活期间
+// 使用；回调处理器由TraderApi持有，回调线程在Release时结束，因此在回调中使用是安全的
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct FrontInfoProbe(*mut c_void);
+
+unsafe impl Send for FrontInfoProbe {}
+unsafe impl Sync for FrontInfoProbe {}
+
+impl FrontInfoProbe {
+    pub(crate) fn front_info(&self) -> CtpResult<FrontInfo> {
+        if self.0.is_null() {
+            return Err(CtpError::InitializationError("API未初始化".to_string()));
+        }
+
+        let mut field = FrontInfoField::default();
+
+        unsafe {
+            CThostFtdcTraderApi_GetFrontInfo(self.0, &mut field as *mut _ as *mut c_void);
+        }
+
+        Ok(FrontInfo::from(&field))
+    }
+}
+
 impl TraderApi {
     // 创建交易API实例
     //
     // # 参数
-    // * `flow_path` - 存储流文件的目录，默认为当前目录
+    // * `flow_path` - 存储流文件的目录，默认为当前目录。指定时创建目录并加锁，
+    //   同一目录不能同时被多个API实例使用，见`flow::FlowDirectory`
     // * `is_production_mode` - 是否为生产环境模式，默认为true
     pub fn new(flow_path: Option<&str>, is_production_mode: Option<bool>) -> CtpResult<Self> {
-        let flow_path_cstr = match flow_path {
-            Some(path) => {
-                let npath = normalize_flow_path(path)?;
-                Some(to_cstring(npath.as_str())?)
-            }
+        // 指定目录时创建并加锁，防止其他会话共用同一目录
+        let flow_lock = flow_path.map(FlowLock::acquire).transpose()?;
+        let flow_path_cstr = match &flow_lock {
+            Some(lock) => Some(to_cstring(&lock.dir().to_string_lossy())?),
             None => None,
         };
 
@@ -1083,6 +1471,11 @@ impl TraderApi {
             initialized: false,
             request_id: Arc::new(Mutex::new(1)),
             handler: None,
+            flow_lock,
+            compliance: None,
+            latency: None,
+            journal: None,
+            order_refs: Arc::new(OrderRefAllocator::new()),
         })
     }
 
@@ -1143,6 +1536,49 @@ impl TraderApi {
             on_rsp_qry_transfer_bank: Some(on_rsp_qry_transfer_bank_callback),
             on_rsp_qry_investor_position_detail: Some(on_rsp_qry_investor_position_detail_callback),
             on_rsp_qry_notice: Some(on_rsp_qry_notice_callback),
+
+            // 银期转账回调
+            on_rsp_from_bank_to_future_by_future: Some(
+                on_rsp_from_bank_to_future_by_future_callback,
+            ),
+            on_rsp_from_future_to_bank_by_future: Some(
+                on_rsp_from_future_to_bank_by_future_callback,
+            ),
+            on_rsp_query_bank_account_money_by_future: Some(
+                on_rsp_query_bank_account_money_by_future_callback,
+            ),
+            on_rtn_from_bank_to_future_by_future: Some(
+                on_rtn_from_bank_to_future_by_future_callback,
+            ),
+            on_rtn_from_future_to_bank_by_future: Some(
+                on_rtn_from_future_to_bank_by_future_callback,
+            ),
+            on_rtn_repeal_from_bank_to_future_by_future: Some(
+                on_rtn_repeal_from_bank_to_future_by_future_callback,
+            ),
+            on_rtn_repeal_from_future_to_bank_by_future: Some(
+                on_rtn_repeal_from_future_to_bank_by_future_callback,
+            ),
+            on_err_rtn_bank_to_future_by_future: Some(on_err_rtn_bank_to_future_by_future_callback),
+            on_err_rtn_future_to_bank_by_future: Some(on_err_rtn_future_to_bank_by_future_callback),
+            on_rtn_query_bank_balance_by_future: Some(on_rtn_query_bank_balance_by_future_callback),
+            on_err_rtn_query_bank_balance_by_future: Some(
+                on_err_rtn_query_bank_balance_by_future_callback,
+            ),
+            on_rsp_qry_transfer_serial: Some(on_rsp_qry_transfer_serial_callback),
+            on_rsp_qry_contract_bank: Some(on_rsp_qry_contract_bank_callback),
+            on_rsp_qry_accountregister: Some(on_rsp_qry_accountregister_callback),
+
+            // 密码管理回调
+            on_rsp_user_password_update: Some(on_rsp_user_password_update_callback),
+            on_rsp_trading_account_password_update: Some(
+                on_rsp_trading_account_password_update_callback,
+            ),
+
+            // 认证模式回调
+            on_rsp_user_auth_method: Some(on_rsp_user_auth_method_callback),
+            on_rsp_gen_user_captcha: Some(on_rsp_gen_user_captcha_callback),
+            on_rsp_gen_user_text: Some(on_rsp_gen_user_text_callback),
         };
 
         // 创建SPI桥接器并注册到C++ API
@@ -1162,6 +1598,115 @@ impl TraderApi {
         Ok(())
     }
 
+    // 挂接合规监控器
+    //
+    // 挂接后报单、撤单请求在发出前会经过合规检查，超过硬限制或可能自成交时返回错误，
+    // 登录响应中的交易日和报单回报会同步给监控器
+    pub fn set_compliance_monitor(&mut self, monitor: Arc<Mutex<ComplianceMonitor>>) {
+        self.compliance = Some(monitor);
+    }
+
+    // 获取已挂接的合规监控器
+    pub fn compliance_monitor(&self) -> Option<Arc<Mutex<ComplianceMonitor>>> {
+        self.compliance.clone()
+    }
+
+    // 挂接延迟记录器
+    //
+    // 挂接后记录每个请求的发出时间，并与响应、报单回报和成交回报配对计算延迟
+    pub fn set_latency_recorder(&mut self, recorder: Arc<Mutex<LatencyRecorder>>) {
+        self.latency = Some(recorder);
+    }
+
+    // 获取已挂接的延迟记录器
+    pub fn latency_recorder(&self) -> Option<Arc<Mutex<LatencyRecorder>>> {
+        self.latency.clone()
+    }
+
+    // 在延迟记录器上执行操作，时间戳在加锁前获取；未挂接时什么都不做
+    fn with_latency(&self, f: impl FnOnce(&mut LatencyRecorder, Instant)) {
+        if let Some(recorder) = &self.latency {
+            let at = Instant::now();
+            if let Ok(mut recorder) = recorder.lock() {
+                f(&mut recorder, at);
+            }
+        }
+    }
+
+    // 挂接审计日志
+    //
+    // 挂接后每个请求和回调都会连同原始字段字节写入日志，密码等敏感字段清零
+    pub fn set_journal(&mut self, journal: Arc<Mutex<AuditJournal>>) {
+        self.journal = Some(journal);
+    }
+
+    // 获取已挂接的审计日志
+    pub fn journal(&self) -> Option<Arc<Mutex<AuditJournal>>> {
+        self.journal.clone()
+    }
+
+    // 替换报单引用分配器，例如启用持久化的分配器
+    //
+    // 登录成功后分配器按登录响应初始化，之后引用为空的报单和撤单自动填写引用
+    pub fn set_order_ref_allocator(&mut self, allocator: Arc<OrderRefAllocator>) {
+        self.order_refs = allocator;
+    }
+
+    // 获取报单引用分配器
+    pub fn order_ref_allocator(&self) -> Arc<OrderRefAllocator> {
+        self.order_refs.clone()
+    }
+
+    // 写入一条审计记录，未挂接时不构造记录；写入失败只记录警告，不影响交易
+    fn write_journal(&self, entry: impl FnOnce() -> JournalEntry) {
+        if let Some(journal) = &self.journal {
+            let entry = entry();
+            let mut journal = journal.lock().unwrap_or_else(|e| e.into_inner());
+            if let Err(e) = journal.append(entry) {
+                warn!("写入审计日志失败: {}", e);
+            }
+        }
+    }
+
+    // 记录发出的请求，发送失败时丢弃分配请求编号时开始的延迟跟踪
+    fn record_request<T: JournalField>(
+        &self,
+        name: &str,
+        req: Option<&T>,
+        request_id: i32,
+        result: c_int,
+    ) {
+        if result != 0 {
+            self.with_latency(|recorder, _| recorder.on_request_failed(request_id));
+        }
+        self.write_journal(|| {
+            let entry = JournalEntry::request(name, request_id, result);
+            match req {
+                Some(req) => entry.with_field(req),
+                None => entry,
+            }
+        });
+    }
+
+    // 记录收到的回调，`data`和`rsp_info`为C++传入的指针，可以为空
+    unsafe fn journal_callback<T: JournalField>(
+        &self,
+        name: &str,
+        data: *const T,
+        rsp_info: *mut c_void,
+        request_id: i32,
+        is_last: bool,
+    ) {
+        self.write_journal(|| {
+            let entry = JournalEntry::callback(name, request_id, is_last)
+                .with_rsp_info((rsp_info as *const RspInfoField).as_ref());
+            match data.as_ref() {
+                Some(data) => entry.with_field(data),
+                None => entry,
+            }
+        });
+    }
+
     // 客户端认证请求
     pub fn req_authenticate(&mut self, req: &ReqAuthenticateField) -> CtpResult<i32> {
         if self.api_ptr.is_null() {
@@ -1177,14 +1722,113 @@ impl TraderApi {
                 request_id,
             )
         };
+        self.record_request("ReqAuthenticate", Some(req), request_id, result);
 
         if result != 0 {
-            return Err(CtpError::FfiError(format!("认证请求失败: {}", result)));
+            return Err(CtpError::request_failed("认证请求失败", result));
         }
 
         Ok(request_id)
     }
 
+    // 注册用户终端信息，用于中继服务器多连接模式，需要在终端认证成功后、用户登录前调用
+    pub fn register_user_system_info(&mut self, info: &UserSystemInfoField) -> CtpResult<()> {
+        if self.api_ptr.is_null() {
+            return Err(CtpError::InitializationError("API未初始化".to_string()));
+        }
+
+        let result = unsafe {
+            CThostFtdcTraderApi_RegisterUserSystemInfo(
+                self.api_ptr,
+                info as *const _ as *const c_void,
+            )
+        };
+
+        if result != 0 {
+            return Err(CtpError::request_failed("注册用户终端信息失败", result));
+        }
+
+        Ok(())
+    }
+
+    // 上报用户终端信息，用于中继服务器操作员登录模式，操作员登录后可以多次调用
+    pub fn submit_user_system_info(&mut self, info: &UserSystemInfoField) -> CtpResult<()> {
+        if self.api_ptr.is_null() {
+            return Err(CtpError::InitializationError("API未初始化".to_string()));
+        }
+
+        let result = unsafe {
+            CThostFtdcTraderApi_SubmitUserSystemInfo(
+                self.api_ptr,
+                info as *const _ as *const c_void,
+            )
+        };
+
+        if result != 0 {
+            return Err(CtpError::request_failed("上报用户终端信息失败", result));
+        }
+
+        Ok(())
+    }
+
+    // 注册微信小程序等应用的用户终端信息，用于中继服务器多连接模式
+    pub fn register_wechat_user_system_info(
+        &mut self,
+        info: &WechatUserSystemInfoField,
+    ) -> CtpResult<()> {
+        if self.api_ptr.is_null() {
+            return Err(CtpError::InitializationError("API未初始化".to_string()));
+        }
+        let mut info = info.clone();
+
+        let result = unsafe {
+            CThostFtdcTraderApi_RegisterWechatUserSystemInfo(
+                self.api_ptr,
+                &mut info as *mut _ as *mut c_void,
+            )
+        };
+
+        if result != 0 {
+            return Err(CtpError::request_failed("注册微信用户终端信息失败", result));
+        }
+
+        Ok(())
+    }
+
+    // 上报微信小程序等应用的用户终端信息，用于中继服务器操作员登录模式
+    pub fn submit_wechat_user_system_info(
+        &mut self,
+        info: &WechatUserSystemInfoField,
+    ) -> CtpResult<()> {
+        if self.api_ptr.is_null() {
+            return Err(CtpError::InitializationError("API未初始化".to_string()));
+        }
+        let mut info = info.clone();
+
+        let result = unsafe {
+            CThostFtdcTraderApi_SubmitWechatUserSystemInfo(
+                self.api_ptr,
+                &mut info as *mut _ as *mut c_void,
+            )
+        };
+
+        if result != 0 {
+            return Err(CtpError::request_failed("上报微信用户终端信息失败", result));
+        }
+
+        Ok(())
+    }
+
+    // 获取已连接的前置信息，包括前置地址和查询流控参数
+    pub fn front_info(&self) -> CtpResult<FrontInfo> {
+        self.front_info_probe().front_info()
+    }
+
+    // 获取前置信息查询句柄
+    pub(crate) fn front_info_probe(&self) -> FrontInfoProbe {
+        FrontInfoProbe(self.api_ptr)
+    }
+
     // 用户登录请求
     pub fn req_user_login(&mut self, req: &ReqUserLoginField) -> CtpResult<i32> {
         if self.api_ptr.is_null() {
@@ -1200,9 +1844,10 @@ impl TraderApi {
                 request_id,
             )
         };
+        self.record_request("ReqUserLogin", Some(req), request_id, result);
 
         if result != 0 {
-            return Err(CtpError::FfiError(format!("登录请求失败: {}", result)));
+            return Err(CtpError::request_failed("登录请求失败", result));
         }
 
         Ok(request_id)
@@ -1218,9 +1863,10 @@ impl TraderApi {
 
         let result =
             unsafe { CThostFtdcTraderApi_ReqUserLogout(self.api_ptr, ptr::null(), request_id) };
+        self.record_request("ReqUserLogout", None::<&()>, request_id, result);
 
         if result != 0 {
-            return Err(CtpError::FfiError(format!("登出请求失败: {}", result)));
+            return Err(CtpError::request_failed("登出请求失败", result));
         }
 
         Ok(request_id)
@@ -1241,12 +1887,10 @@ impl TraderApi {
                 request_id,
             )
         };
+        self.record_request("ReqQryTradingAccount", Some(req), request_id, result);
 
         if result != 0 {
-            return Err(CtpError::FfiError(format!(
-                "查询资金账户请求失败: {}",
-                result
-            )));
+            return Err(CtpError::request_failed("查询资金账户请求失败", result));
         }
 
         Ok(request_id)
@@ -1267,12 +1911,10 @@ impl TraderApi {
                 request_id,
             )
         };
+        self.record_request("ReqQryInvestorPosition", Some(req), request_id, result);
 
         if result != 0 {
-            return Err(CtpError::FfiError(format!(
-                "查询投资者持仓请求失败: {}",
-                result
-            )));
+            return Err(CtpError::request_failed("查询投资者持仓请求失败", result));
         }
 
         Ok(request_id)
@@ -1286,7 +1928,24 @@ impl TraderApi {
             return Err(CtpError::InitializationError("API未初始化".to_string()));
         }
 
+        // 报单引用为空时自动分配
+        let mut filled = req.clone();
+        let req = if self.order_refs.fill_order(&mut filled)? {
+            &filled
+        } else {
+            req
+        };
+
+        // 检查和登记在同一次加锁内完成，发送失败时撤销登记
+        if let Some(monitor) = &self.compliance {
+            let mut monitor = monitor.lock().unwrap();
+            monitor.check_order_insert(req)?;
+            monitor.record_order_insert(req);
+        }
+
         let request_id = self.next_request_id();
+        // 发送前登记，回调线程可能在发送返回前就收到报单回报
+        self.with_latency(|recorder, _| recorder.on_order_insert(request_id, req));
 
         let result = unsafe {
             CThostFtdcTraderApi_ReqOrderInsert(
@@ -1295,9 +1954,14 @@ impl TraderApi {
                 request_id,
             )
         };
+        self.record_request("ReqOrderInsert", Some(req), request_id, result);
 
         if result != 0 {
-            return Err(CtpError::FfiError(format!("报单录入请求失败: {}", result)));
+            self.with_latency(|recorder, _| recorder.on_err_rtn_order_insert(req));
+            if let Some(monitor) = &self.compliance {
+                monitor.lock().unwrap().on_order_insert_failed(req);
+            }
+            return Err(CtpError::request_failed("报单录入请求失败", result));
         }
 
         Ok(request_id)
@@ -1309,6 +1973,18 @@ impl TraderApi {
             return Err(CtpError::InitializationError("API未初始化".to_string()));
         }
 
+        // 报单操作引用为空时自动分配
+        let mut filled = req.clone();
+        let req = if self.order_refs.fill_action(&mut filled)? {
+            &filled
+        } else {
+            req
+        };
+
+        if let Some(monitor) = &self.compliance {
+            monitor.lock().unwrap().check_order_action(req)?;
+        }
+
         let request_id = self.next_request_id();
 
         let result = unsafe {
@@ -1318,10 +1994,16 @@ impl TraderApi {
                 request_id,
             )
         };
+        self.record_request("ReqOrderAction", Some(req), request_id, result);
 
         if result != 0 {
-            return Err(CtpError::FfiError(format!("报单操作请求失败: {}", result)));
+            return Err(CtpError::request_failed("报单操作请求失败", result));
+        }
+
+        if let Some(monitor) = &self.compliance {
+            monitor.lock().unwrap().record_order_action(req);
         }
+        self.with_latency(|recorder, _| recorder.on_order_action(request_id));
 
         Ok(request_id)
     }
@@ -1341,9 +2023,10 @@ impl TraderApi {
                 request_id,
             )
         };
+        self.record_request("ReqQryOrder", Some(req), request_id, result);
 
         if result != 0 {
-            return Err(CtpError::FfiError(format!("查询报单请求失败: {}", result)));
+            return Err(CtpError::request_failed("查询报单请求失败", result));
         }
 
         Ok(request_id)
@@ -1364,9 +2047,10 @@ impl TraderApi {
                 request_id,
             )
         };
+        self.record_request("ReqQryTrade", Some(req), request_id, result);
 
         if result != 0 {
-            return Err(CtpError::FfiError(format!("查询成交请求失败: {}", result)));
+            return Err(CtpError::request_failed("查询成交请求失败", result));
         }
 
         Ok(request_id)
@@ -1387,9 +2071,10 @@ impl TraderApi {
                 request_id,
             )
         };
+        self.record_request("ReqQryInstrument", Some(req), request_id, result);
 
         if result != 0 {
-            return Err(CtpError::FfiError(format!("查询合约请求失败: {}", result)));
+            return Err(CtpError::request_failed("查询合约请求失败", result));
         }
 
         Ok(request_id)
@@ -1415,12 +2100,10 @@ impl TraderApi {
                 request_id,
             )
         };
+        self.record_request("ReqQryInstrumentMarginRate", Some(req), request_id, result);
 
         if result != 0 {
-            return Err(CtpError::FfiError(format!(
-                "查询合约保证金率请求失败: {}",
-                result
-            )));
+            return Err(CtpError::request_failed("查询合约保证金率请求失败", result));
         }
 
         Ok(request_id)
@@ -1444,12 +2127,15 @@ impl TraderApi {
                 request_id,
             )
         };
+        self.record_request(
+            "ReqQryInstrumentCommissionRate",
+            Some(req),
+            request_id,
+            result,
+        );
 
         if result != 0 {
-            return Err(CtpError::FfiError(format!(
-                "查询合约手续费率请求失败: {}",
-                result
-            )));
+            return Err(CtpError::request_failed("查询合约手续费率请求失败", result));
         }
 
         Ok(request_id)
@@ -1470,12 +2156,10 @@ impl TraderApi {
                 request_id,
             )
         };
+        self.record_request("ReqQryExchange", Some(req), request_id, result);
 
         if result != 0 {
-            return Err(CtpError::FfiError(format!(
-                "查询交易所请求失败: {}",
-                result
-            )));
+            return Err(CtpError::request_failed("查询交易所请求失败", result));
         }
 
         Ok(request_id)
@@ -1496,9 +2180,10 @@ impl TraderApi {
                 request_id,
             )
         };
+        self.record_request("ReqQryProduct", Some(req), request_id, result);
 
         if result != 0 {
-            return Err(CtpError::FfiError(format!("查询产品请求失败: {}", result)));
+            return Err(CtpError::request_failed("查询产品请求失败", result));
         }
 
         Ok(request_id)
@@ -1522,12 +2207,13 @@ impl TraderApi {
                 request_id,
             )
         };
+        self.record_request("ReqSettlementInfoConfirm", Some(req), request_id, result);
 
         if result != 0 {
-            return Err(CtpError::FfiError(format!(
-                "投资者结算结果确认请求失败: {}",
-                result
-            )));
+            return Err(CtpError::request_failed(
+                "投资者结算结果确认请求失败",
+                result,
+            ));
         }
 
         Ok(request_id)
@@ -1548,12 +2234,10 @@ impl TraderApi {
                 request_id,
             )
         };
+        self.record_request("ReqParkedOrderInsert", Some(req), request_id, result);
 
         if result != 0 {
-            return Err(CtpError::FfiError(format!(
-                "预埋单录入请求失败: {}",
-                result
-            )));
+            return Err(CtpError::request_failed("预埋单录入请求失败", result));
         }
 
         Ok(request_id)
@@ -1574,12 +2258,10 @@ impl TraderApi {
                 request_id,
             )
         };
+        self.record_request("ReqParkedOrderAction", Some(req), request_id, result);
 
         if result != 0 {
-            return Err(CtpError::FfiError(format!(
-                "预埋撤单录入请求失败: {}",
-                result
-            )));
+            return Err(CtpError::request_failed("预埋撤单录入请求失败", result));
         }
 
         Ok(request_id)
@@ -1602,12 +2284,10 @@ impl TraderApi {
                 request_id,
             )
         };
+        self.record_request("ReqExecOrderInsert", Some(req), request_id, result);
 
         if result != 0 {
-            return Err(CtpError::FfiError(format!(
-                "执行宣告录入请求失败: {}",
-                result
-            )));
+            return Err(CtpError::request_failed("执行宣告录入请求失败", result));
         }
 
         Ok(request_id)
@@ -1628,12 +2308,10 @@ impl TraderApi {
                 request_id,
             )
         };
+        self.record_request("ReqExecOrderAction", Some(req), request_id, result);
 
         if result != 0 {
-            return Err(CtpError::FfiError(format!(
-                "执行宣告操作请求失败: {}",
-                result
-            )));
+            return Err(CtpError::request_failed("执行宣告操作请求失败", result));
         }
 
         Ok(request_id)
@@ -1654,9 +2332,10 @@ impl TraderApi {
                 request_id,
             )
         };
+        self.record_request("ReqForQuoteInsert", Some(req), request_id, result);
 
         if result != 0 {
-            return Err(CtpError::FfiError(format!("询价录入请求失败: {}", result)));
+            return Err(CtpError::request_failed("询价录入请求失败", result));
         }
 
         Ok(request_id)
@@ -1677,9 +2356,10 @@ impl TraderApi {
                 request_id,
             )
         };
+        self.record_request("ReqQuoteInsert", Some(req), request_id, result);
 
         if result != 0 {
-            return Err(CtpError::FfiError(format!("报价录入请求失败: {}", result)));
+            return Err(CtpError::request_failed("报价录入请求失败", result));
         }
 
         Ok(request_id)
@@ -1700,9 +2380,10 @@ impl TraderApi {
                 request_id,
             )
         };
+        self.record_request("ReqQuoteAction", Some(req), request_id, result);
 
         if result != 0 {
-            return Err(CtpError::FfiError(format!("报价操作请求失败: {}", result)));
+            return Err(CtpError::request_failed("报价操作请求失败", result));
         }
 
         Ok(request_id)
@@ -1723,12 +2404,10 @@ impl TraderApi {
                 request_id,
             )
         };
+        self.record_request("ReqBatchOrderAction", Some(req), request_id, result);
 
         if result != 0 {
-            return Err(CtpError::FfiError(format!(
-                "批量报单操作请求失败: {}",
-                result
-            )));
+            return Err(CtpError::request_failed("批量报单操作请求失败", result));
         }
 
         Ok(request_id)
@@ -1749,12 +2428,10 @@ impl TraderApi {
                 request_id,
             )
         };
+        self.record_request("ReqRemoveParkedOrder", Some(req), request_id, result);
 
         if result != 0 {
-            return Err(CtpError::FfiError(format!(
-                "删除预埋单请求失败: {}",
-                result
-            )));
+            return Err(CtpError::request_failed("删除预埋单请求失败", result));
         }
 
         Ok(request_id)
@@ -1778,12 +2455,10 @@ impl TraderApi {
                 request_id,
             )
         };
+        self.record_request("ReqRemoveParkedOrderAction", Some(req), request_id, result);
 
         if result != 0 {
-            return Err(CtpError::FfiError(format!(
-                "删除预埋撤单请求失败: {}",
-                result
-            )));
+            return Err(CtpError::request_failed("删除预埋撤单请求失败", result));
         }
 
         Ok(request_id)
@@ -1804,12 +2479,10 @@ impl TraderApi {
                 request_id,
             )
         };
+        self.record_request("ReqQryMaxOrderVolume", Some(req), request_id, result);
 
         if result != 0 {
-            return Err(CtpError::FfiError(format!(
-                "查询最大报单数量请求失败: {}",
-                result
-            )));
+            return Err(CtpError::request_failed("查询最大报单数量请求失败", result));
         }
 
         Ok(request_id)
@@ -1830,9 +2503,10 @@ impl TraderApi {
                 request_id,
             )
         };
+        self.record_request("ReqQryDepthMarketData", Some(req), request_id, result);
 
         if result != 0 {
-            return Err(CtpError::FfiError(format!("查询行情请求失败: {}", result)));
+            return Err(CtpError::request_failed("查询行情请求失败", result));
         }
 
         Ok(request_id)
@@ -1853,12 +2527,13 @@ impl TraderApi {
                 request_id,
             )
         };
+        self.record_request("ReqQrySettlementInfo", Some(req), request_id, result);
 
         if result != 0 {
-            return Err(CtpError::FfiError(format!(
-                "查询投资者结算结果请求失败: {}",
-                result
-            )));
+            return Err(CtpError::request_failed(
+                "查询投资者结算结果请求失败",
+                result,
+            ));
         }
 
         Ok(request_id)
@@ -1879,12 +2554,10 @@ impl TraderApi {
                 request_id,
             )
         };
+        self.record_request("ReqQryTransferBank", Some(req), request_id, result);
 
         if result != 0 {
-            return Err(CtpError::FfiError(format!(
-                "查询转帐银行请求失败: {}",
-                result
-            )));
+            return Err(CtpError::request_failed("查询转帐银行请求失败", result));
         }
 
         Ok(request_id)
@@ -1908,12 +2581,18 @@ impl TraderApi {
                 request_id,
             )
         };
+        self.record_request(
+            "ReqQryInvestorPositionDetail",
+            Some(req),
+            request_id,
+            result,
+        );
 
         if result != 0 {
-            return Err(CtpError::FfiError(format!(
-                "查询投资者持仓明细请求失败: {}",
-                result
-            )));
+            return Err(CtpError::request_failed(
+                "查询投资者持仓明细请求失败",
+                result,
+            ));
         }
 
         Ok(request_id)
@@ -1934,169 +2613,1315 @@ impl TraderApi {
                 request_id,
             )
         };
+        self.record_request("ReqQryNotice", Some(req), request_id, result);
 
         if result != 0 {
-            return Err(CtpError::FfiError(format!(
-                "查询客户通知请求失败: {}",
-                result
-            )));
+            return Err(CtpError::request_failed("查询客户通知请求失败", result));
         }
 
         Ok(request_id)
     }
 
-    // 获取下一个请求ID
-    fn next_request_id(&self) -> i32 {
-        let mut id = self.request_id.lock().unwrap();
-        let current = *id;
-        *id += 1;
-        current
-    }
-}
+    // 期货发起银行资金转期货请求
+    pub fn req_from_bank_to_future_by_future(&mut self, req: &ReqTransferField) -> CtpResult<i32> {
+        if self.api_ptr.is_null() {
+            return Err(CtpError::InitializationError("API未初始化".to_string()));
+        }
 
-// 客户端认证请求
+        let request_id = self.next_request_id();
+
+        // 回报和错误回报不带nRequestID，通过请求编号字段关联
+        let mut req = req.clone();
+        req.request_id = request_id;
+
+        let result = unsafe {
+            CThostFtdcTraderApi_ReqFromBankToFutureByFuture(
+                self.api_ptr,
+                &req as *const _ as *const c_void,
+                request_id,
+            )
+        };
+        self.record_request(
+            "ReqFromBankToFutureByFuture",
+            Some(&req),
+            request_id,
+            result,
+        );
+
+        if result != 0 {
+            return Err(CtpError::request_failed("银行资金转期货请求失败", result));
+        }
+
+        Ok(request_id)
+    }
+
+    // 期货发起期货资金转银行请求
+    pub fn req_from_future_to_bank_by_future(&mut self, req: &ReqTransferField) -> CtpResult<i32> {
+        if self.api_ptr.is_null() {
+            return Err(CtpError::InitializationError("API未初始化".to_string()));
+        }
+
+        let request_id = self.next_request_id();
+
+        // 回报和错误回报不带nRequestID，通过请求编号字段关联
+        let mut req = req.clone();
+        req.request_id = request_id;
+
+        let result = unsafe {
+            CThostFtdcTraderApi_ReqFromFutureToBankByFuture(
+                self.api_ptr,
+                &req as *const _ as *const c_void,
+                request_id,
+            )
+        };
+        self.record_request(
+            "ReqFromFutureToBankByFuture",
+            Some(&req),
+            request_id,
+            result,
+        );
+
+        if result != 0 {
+            return Err(CtpError::request_failed("期货资金转银行请求失败", result));
+        }
+
+        Ok(request_id)
+    }
+
+    // 期货发起查询银行余额请求
+    pub fn req_query_bank_account_money_by_future(
+        &mut self,
+        req: &ReqQueryAccountField,
+    ) -> CtpResult<i32> {
+        if self.api_ptr.is_null() {
+            return Err(CtpError::InitializationError("API未初始化".to_string()));
+        }
+
+        let request_id = self.next_request_id();
+
+        // 回报和错误回报不带nRequestID，通过请求编号字段关联
+        let mut req = req.clone();
+        req.request_id = request_id;
+
+        let result = unsafe {
+            CThostFtdcTraderApi_ReqQueryBankAccountMoneyByFuture(
+                self.api_ptr,
+                &req as *const _ as *const c_void,
+                request_id,
+            )
+        };
+        self.record_request(
+            "ReqQueryBankAccountMoneyByFuture",
+            Some(&req),
+            request_id,
+            result,
+        );
+
+        if result != 0 {
+            return Err(CtpError::request_failed("查询银行余额请求失败", result));
+        }
+
+        Ok(request_id)
+    }
+
+    // 请求查询转帐流水
+    pub fn req_qry_transfer_serial(&mut self, req: &QryTransferSerialField) -> CtpResult<i32> {
+        if self.api_ptr.is_null() {
+            return Err(CtpError::InitializationError("API未初始化".to_string()));
+        }
+
+        let request_id = self.next_request_id();
+
+        let result = unsafe {
+            CThostFtdcTraderApi_ReqQryTransferSerial(
+                self.api_ptr,
+                req as *const _ as *const c_void,
+                request_id,
+            )
+        };
+        self.record_request("ReqQryTransferSerial", Some(req), request_id, result);
+
+        if result != 0 {
+            return Err(CtpError::request_failed("查询转帐流水请求失败", result));
+        }
+
+        Ok(request_id)
+    }
+
+    // 请求查询签约银行
+    pub fn req_qry_contract_bank(&mut self, req: &QryContractBankField) -> CtpResult<i32> {
+        if self.api_ptr.is_null() {
+            return Err(CtpError::InitializationError("API未初始化".to_string()));
+        }
+
+        let request_id = self.next_request_id();
+
+        let result = unsafe {
+            CThostFtdcTraderApi_ReqQryContractBank(
+                self.api_ptr,
+                req as *const _ as *const c_void,
+                request_id,
+            )
+        };
+        self.record_request("ReqQryContractBank", Some(req), request_id, result);
+
+        if result != 0 {
+            return Err(CtpError::request_failed("查询签约银行请求失败", result));
+        }
+
+        Ok(request_id)
+    }
+
+    // 请求查询银期签约关系
+    pub fn req_qry_accountregister(&mut self, req: &QryAccountregisterField) -> CtpResult<i32> {
+        if self.api_ptr.is_null() {
+            return Err(CtpError::InitializationError("API未初始化".to_string()));
+        }
+
+        let request_id = self.next_request_id();
+
+        let result = unsafe {
+            CThostFtdcTraderApi_ReqQryAccountregister(
+                self.api_ptr,
+                req as *const _ as *const c_void,
+                request_id,
+            )
+        };
+        self.record_request("ReqQryAccountregister", Some(req), request_id, result);
+
+        if result != 0 {
+            return Err(CtpError::request_failed("查询银期签约关系请求失败", result));
+        }
+
+        Ok(request_id)
+    }
+
+    // 用户口令更新请求
+    pub fn req_user_password_update(&mut self, req: &UserPasswordUpdateField) -> CtpResult<i32> {
+        if self.api_ptr.is_null() {
+            return Err(CtpError::InitializationError("API未初始化".to_string()));
+        }
+
+        let request_id = self.next_request_id();
+
+        let result = unsafe {
+            CThostFtdcTraderApi_ReqUserPasswordUpdate(
+                self.api_ptr,
+                req as *const _ as *const c_void,
+                request_id,
+            )
+        };
+        self.record_request("ReqUserPasswordUpdate", Some(req), request_id, result);
+
+        if result != 0 {
+            return Err(CtpError::request_failed("用户口令更新请求失败", result));
+        }
+
+        Ok(request_id)
+    }
+
+    // 资金账户口令更新请求
+    pub fn req_trading_account_password_update(
+        &mut self,
+        req: &TradingAccountPasswordUpdateField,
+    ) -> CtpResult<i32> {
+        if self.api_ptr.is_null() {
+            return Err(CtpError::InitializationError("API未初始化".to_string()));
+        }
+
+        let request_id = self.next_request_id();
+
+        let result = unsafe {
+            CThostFtdcTraderApi_ReqTradingAccountPasswordUpdate(
+                self.api_ptr,
+                req as *const _ as *const c_void,
+                request_id,
+            )
+        };
+        self.record_request(
+            "ReqTradingAccountPasswordUpdate",
+            Some(req),
+            request_id,
+            result,
+        );
+
+        if result != 0 {
+            return Err(CtpError::request_failed("资金账户口令更新请求失败", result));
+        }
+
+        Ok(request_id)
+    }
+
+    // 查询用户当前支持的认证模式
+    pub fn req_user_auth_method(&mut self, req: &ReqUserAuthMethodField) -> CtpResult<i32> {
+        if self.api_ptr.is_null() {
+            return Err(CtpError::InitializationError("API未初始化".to_string()));
+        }
+
+        let request_id = self.next_request_id();
+
+        let result = unsafe {
+            CThostFtdcTraderApi_ReqUserAuthMethod(
+                self.api_ptr,
+                req as *const _ as *const c_void,
+                request_id,
+            )
+        };
+        self.record_request("ReqUserAuthMethod", Some(req), request_id, result);
+
+        if result != 0 {
+            return Err(CtpError::request_failed("查询认证模式请求失败", result));
+        }
+
+        Ok(request_id)
+    }
+
+    // 获取图形验证码
+    pub fn req_gen_user_captcha(&mut self, req: &ReqGenUserCaptchaField) -> CtpResult<i32> {
+        if self.api_ptr.is_null() {
+            return Err(CtpError::InitializationError("API未初始化".to_string()));
+        }
+
+        let request_id = self.next_request_id();
+
+        let result = unsafe {
+            CThostFtdcTraderApi_ReqGenUserCaptcha(
+                self.api_ptr,
+                req as *const _ as *const c_void,
+                request_id,
+            )
+        };
+        self.record_request("ReqGenUserCaptcha", Some(req), request_id, result);
+
+        if result != 0 {
+            return Err(CtpError::request_failed("获取图形验证码请求失败", result));
+        }
+
+        Ok(request_id)
+    }
+
+    // 获取短信验证码
+    pub fn req_gen_user_text(&mut self, req: &ReqGenUserTextField) -> CtpResult<i32> {
+        if self.api_ptr.is_null() {
+            return Err(CtpError::InitializationError("API未初始化".to_string()));
+        }
+
+        let request_id = self.next_request_id();
+
+        let result = unsafe {
+            CThostFtdcTraderApi_ReqGenUserText(
+                self.api_ptr,
+                req as *const _ as *const c_void,
+                request_id,
+            )
+        };
+        self.record_request("ReqGenUserText", Some(req), request_id, result);
+
+        if result != 0 {
+            return Err(CtpError::request_failed("获取短信验证码请求失败", result));
+        }
+
+        Ok(request_id)
+    }
+
+    // 带图形验证码登录
+    pub fn req_user_login_with_captcha(
+        &mut self,
+        req: &ReqUserLoginWithCaptchaField,
+    ) -> CtpResult<i32> {
+        if self.api_ptr.is_null() {
+            return Err(CtpError::InitializationError("API未初始化".to_string()));
+        }
+
+        let request_id = self.next_request_id();
+
+        let result = unsafe {
+            CThostFtdcTraderApi_ReqUserLoginWithCaptcha(
+                self.api_ptr,
+                req as *const _ as *const c_void,
+                request_id,
+            )
+        };
+        self.record_request("ReqUserLoginWithCaptcha", Some(req), request_id, result);
+
+        if result != 0 {
+            return Err(CtpError::request_failed("图形验证码登录请求失败", result));
+        }
+
+        Ok(request_id)
+    }
+
+    // 带短信验证码登录
+    pub fn req_user_login_with_text(&mut self, req: &ReqUserLoginWithTextField) -> CtpResult<i32> {
+        if self.api_ptr.is_null() {
+            return Err(CtpError::InitializationError("API未初始化".to_string()));
+        }
+
+        let request_id = self.next_request_id();
+
+        let result = unsafe {
+            CThostFtdcTraderApi_ReqUserLoginWithText(
+                self.api_ptr,
+                req as *const _ as *const c_void,
+                request_id,
+            )
+        };
+        self.record_request("ReqUserLoginWithText", Some(req), request_id, result);
+
+        if result != 0 {
+            return Err(CtpError::request_failed("短信验证码登录请求失败", result));
+        }
+
+        Ok(request_id)
+    }
+
+    // 带动态口令登录
+    pub fn req_user_login_with_otp(&mut self, req: &ReqUserLoginWithOtpField) -> CtpResult<i32> {
+        if self.api_ptr.is_null() {
+            return Err(CtpError::InitializationError("API未初始化".to_string()));
+        }
+
+        let request_id = self.next_request_id();
+
+        let result = unsafe {
+            CThostFtdcTraderApi_ReqUserLoginWithOTP(
+                self.api_ptr,
+                req as *const _ as *const c_void,
+                request_id,
+            )
+        };
+        self.record_request("ReqUserLoginWithOTP", Some(req), request_id, result);
+
+        if result != 0 {
+            return Err(CtpError::request_failed("动态口令登录请求失败", result));
+        }
+
+        Ok(request_id)
+    }
+
+    // 获取下一个请求ID
+    fn next_request_id(&self) -> i32 {
+        let current = {
+            let mut id = self.request_id.lock().unwrap();
+            let current = *id;
+            *id += 1;
+            current
+        };
+        self.with_latency(|recorder, at| recorder.on_request(current, at));
+        current
+    }
+}
+
+// 客户端认证请求
 #[repr(C)]
 #[derive(Debug, Clone)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(default)
+)]
 pub struct ReqAuthenticateField {
     // 经纪公司代码
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
     pub broker_id: [u8; 11],
     // 用户代码
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
     pub user_id: [u8; 16],
     // 用户端产品信息
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
     pub user_product_info: [u8; 11],
     // 认证码
-    pub auth_code: [u8; 17],
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
+    pub auth_code: AuthCodeType,
     // 应用单元代码
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
     pub app_id: [u8; 33],
 }
 
-impl Default for ReqAuthenticateField {
-    fn default() -> Self {
-        unsafe { std::mem::zeroed() }
+impl ReqAuthenticateField {
+    // 创建客户端认证请求
+    pub fn new(broker_id: &str, user_id: &str, app_id: &str, auth_code: &str) -> CtpResult<Self> {
+        Ok(Self {
+            broker_id: <[u8; 11]>::from_utf8_string(broker_id)?,
+            user_id: <[u8; 16]>::from_utf8_string(user_id)?,
+            app_id: encode_text(app_id, "app_id")?,
+            auth_code: AuthCodeType::from_utf8_string(auth_code)?,
+            ..Default::default()
+        })
+    }
+
+    // 设置用户端产品信息
+    pub fn with_product_info(mut self, product_info: &str) -> CtpResult<Self> {
+        self.user_product_info = <[u8; 11]>::from_utf8_string(product_info)?;
+        Ok(self)
+    }
+}
+
+impl Default for ReqAuthenticateField {
+    fn default() -> Self {
+        unsafe { std::mem::zeroed() }
+    }
+}
+
+impl CtpApi for TraderApi {
+    fn get_version() -> CtpResult<String> {
+        let version_ptr = unsafe { CThostFtdcTraderApi_GetApiVersion() };
+        safe_cstr_to_string(version_ptr)
+    }
+
+    fn init(&mut self) -> CtpResult<()> {
+        if self.api_ptr.is_null() {
+            return Err(CtpError::InitializationError("API指针为空".to_string()));
+        }
+
+        unsafe {
+            CThostFtdcTraderApi_Init(self.api_ptr);
+        }
+
+        self.initialized = true;
+        Ok(())
+    }
+
+    fn release(&mut self) {
+        if !self.api_ptr.is_null() {
+            unsafe {
+                CThostFtdcTraderApi_Release(self.api_ptr);
+            }
+            self.api_ptr = ptr::null_mut();
+        }
+        self.initialized = false;
+        self.flow_lock = None;
+    }
+
+    fn get_trading_day(&self) -> CtpResult<String> {
+        if self.api_ptr.is_null() {
+            return Err(CtpError::InitializationError("API未初始化".to_string()));
+        }
+
+        let trading_day_ptr = unsafe { CThostFtdcTraderApi_GetTradingDay(self.api_ptr) };
+
+        safe_cstr_to_string(trading_day_ptr)
+    }
+
+    fn register_front(&mut self, front_address: &str) -> CtpResult<()> {
+        if self.api_ptr.is_null() {
+            return Err(CtpError::InitializationError("API未初始化".to_string()));
+        }
+        let front_address_cstr = to_cstring(front_address)?;
+        unsafe {
+            CThostFtdcTraderApi_RegisterFront(self.api_ptr, front_address_cstr.as_ptr());
+        }
+
+        Ok(())
+    }
+
+    fn register_name_server(&mut self, ns_address: &str) -> CtpResult<()> {
+        if self.api_ptr.is_null() {
+            return Err(CtpError::InitializationError("API未初始化".to_string()));
+        }
+
+        let ns_address_cstr = to_cstring(ns_address)?;
+
+        unsafe {
+            CThostFtdcTraderApi_RegisterNameServer(self.api_ptr, ns_address_cstr.as_ptr());
+        }
+
+        Ok(())
+    }
+
+    fn register_fens_user_info(&mut self, user_info: &FensUserInfo) -> CtpResult<()> {
+        if self.api_ptr.is_null() {
+            return Err(CtpError::InitializationError("API未初始化".to_string()));
+        }
+
+        let field = FensUserInfoField::try_from(user_info)?;
+
+        unsafe {
+            CThostFtdcTraderApi_RegisterFensUserInfo(
+                self.api_ptr,
+                &field as *const _ as *const c_void,
+            );
+        }
+
+        Ok(())
+    }
+
+    fn join(&self) -> CtpResult<i32> {
+        if self.api_ptr.is_null() {
+            return Err(CtpError::InitializationError("API未初始化".to_string()));
+        }
+
+        let result = unsafe { CThostFtdcTraderApi_Join(self.api_ptr) };
+
+        Ok(result)
+    }
+}
+
+// 回调函数实现
+extern "C" fn on_front_connected_callback(user_data: *mut c_void) {
+    unsafe {
+        if let Some(api) = (user_data as *mut TraderApi).as_mut() {
+            api.write_journal(|| JournalEntry::callback("OnFrontConnected", 0, true));
+            if api.latency.is_some() {
+                if let Ok(info) = api.front_info() {
+                    api.with_latency(|recorder, _| recorder.set_trader_front(&info.front_addr));
+                }
+            }
+            if let Some(ref mut handler) = api.handler {
+                handler.on_front_connected();
+            }
+        }
+    }
+}
+
+extern "C" fn on_front_disconnected_callback(user_data: *mut c_void, reason: c_int) {
+    unsafe {
+        if let Some(api) = (user_data as *mut TraderApi).as_mut() {
+            api.write_journal(|| {
+                JournalEntry::callback("OnFrontDisconnected", 0, true).with_return_code(reason)
+            });
+            if let Some(ref mut handler) = api.handler {
+                handler.on_front_disconnected(reason);
+            }
+        }
+    }
+}
+
+extern "C" fn on_heart_beat_warning_callback(user_data: *mut c_void, time_lapse: c_int) {
+    unsafe {
+        if let Some(api) = (user_data as *mut TraderApi).as_mut() {
+            api.write_journal(|| {
+                JournalEntry::callback("OnHeartBeatWarning", 0, true).with_return_code(time_lapse)
+            });
+            if let Some(ref mut handler) = api.handler {
+                handler.on_heart_beat_warning(time_lapse);
+            }
+        }
+    }
+}
+
+extern "C" fn on_rsp_authenticate_callback(
+    user_data: *mut c_void,
+    rsp_authenticate: *mut c_void,
+    rsp_info: *mut c_void,
+    request_id: c_int,
+    is_last: c_int,
+) {
+    unsafe {
+        if let Some(api) = (user_data as *mut TraderApi).as_mut() {
+            api.journal_callback(
+                "OnRspAuthenticate",
+                rsp_authenticate as *const RspAuthenticateField,
+                rsp_info,
+                request_id,
+                is_last != 0,
+            );
+            api.with_latency(|recorder, at| {
+                recorder.on_response(LatencyKind::RequestRoundTrip, request_id, is_last != 0, at)
+            });
+            if let Some(ref mut handler) = api.handler {
+                // 解析rsp_authenticate指针
+                let parsed_rsp_authenticate = if !rsp_authenticate.is_null() {
+                    let auth_ptr = rsp_authenticate as *const RspAuthenticateField;
+                    Some((*auth_ptr).clone())
+                } else {
+                    None
+                };
+
+                // 解析rsp_info指针
+                let parsed_rsp_info = if !rsp_info.is_null() {
+                    let rsp_ptr = rsp_info as *const RspInfoField;
+                    Some((*rsp_ptr).clone())
+                } else {
+                    None
+                };
+
+                handler.on_rsp_authenticate(
+                    parsed_rsp_authenticate,
+                    parsed_rsp_info,
+                    request_id,
+                    is_last != 0,
+                );
+            }
+        }
+    }
+}
+
+extern "C" fn on_rsp_user_login_callback(
+    user_data: *mut c_void,
+    user_login: *mut c_void,
+    rsp_info: *mut c_void,
+    request_id: c_int,
+    is_last: c_int,
+) {
+    unsafe {
+        if let Some(api) = (user_data as *mut TraderApi).as_mut() {
+            api.journal_callback(
+                "OnRspUserLogin",
+                user_login as *const RspUserLoginField,
+                rsp_info,
+                request_id,
+                is_last != 0,
+            );
+            api.with_latency(|recorder, at| {
+                recorder.on_response(LatencyKind::RequestRoundTrip, request_id, is_last != 0, at)
+            });
+            let login_succeeded =
+                rsp_info.is_null() || (*(rsp_info as *const RspInfoField)).error_id == 0;
+            if !user_login.is_null() && login_succeeded {
+                let login = &*(user_login as *const RspUserLoginField);
+                api.with_latency(|recorder, _| recorder.on_login(login.front_id, login.session_id));
+                if let Err(e) = api.order_refs.seed_from_login(login) {
+                    warn!("初始化报单引用分配器失败: {}", e);
+                }
+                // 未注册回调处理器时也要切换交易日
+                if let (Some(monitor), Ok(trading_day)) =
+                    (&api.compliance, login.trading_day.to_utf8_string())
+                {
+                    if let Err(e) = monitor.lock().unwrap().set_trading_day(&trading_day) {
+                        warn!("同步合规交易日失败: {}", e);
+                    }
+                }
+            }
+            if let Some(ref mut handler) = api.handler {
+                // 解析user_login指针
+                let parsed_user_login = if !user_login.is_null() {
+                    let login_ptr = user_login as *const RspUserLoginField;
+                    Some((*login_ptr).clone())
+                } else {
+                    None
+                };
+
+                // 解析rsp_info指针
+                let parsed_rsp_info = if !rsp_info.is_null() {
+                    let rsp_ptr = rsp_info as *const RspInfoField;
+                    Some((*rsp_ptr).clone())
+                } else {
+                    None
+                };
+
+                handler.on_rsp_user_login(
+                    parsed_user_login,
+                    parsed_rsp_info,
+                    request_id,
+                    is_last != 0,
+                );
+            }
+        }
+    }
+}
+
+extern "C" fn on_rsp_user_logout_callback(
+    user_data: *mut c_void,
+    _user_logout: *mut c_void,
+    rsp_info: *mut c_void,
+    request_id: c_int,
+    is_last: c_int,
+) {
+    unsafe {
+        if let Some(api) = (user_data as *mut TraderApi).as_mut() {
+            api.journal_callback(
+                "OnRspUserLogout",
+                ptr::null::<()>(),
+                rsp_info,
+                request_id,
+                is_last != 0,
+            );
+            api.with_latency(|recorder, at| {
+                recorder.on_response(LatencyKind::RequestRoundTrip, request_id, is_last != 0, at)
+            });
+            if let Some(ref mut handler) = api.handler {
+                // 解析rsp_info指针
+                let parsed_rsp_info = if !rsp_info.is_null() {
+                    let rsp_ptr = rsp_info as *const RspInfoField;
+                    Some((*rsp_ptr).clone())
+                } else {
+                    None
+                };
+
+                handler.on_rsp_user_logout(None, parsed_rsp_info, request_id, is_last != 0);
+            }
+        }
+    }
+}
+
+extern "C" fn on_rsp_error_callback(
+    user_data: *mut c_void,
+    rsp_info: *mut c_void,
+    request_id: c_int,
+    is_last: c_int,
+) {
+    unsafe {
+        if let Some(api) = (user_data as *mut TraderApi).as_mut() {
+            api.journal_callback(
+                "OnRspError",
+                ptr::null::<()>(),
+                rsp_info,
+                request_id,
+                is_last != 0,
+            );
+            api.with_latency(|recorder, at| {
+                recorder.on_response(LatencyKind::RequestRoundTrip, request_id, is_last != 0, at)
+            });
+            if let Some(ref mut handler) = api.handler {
+                // 解析rsp_info指针
+                let parsed_rsp_info = if !rsp_info.is_null() {
+                    let rsp_ptr = rsp_info as *const RspInfoField;
+                    Some((*rsp_ptr).clone())
+                } else {
+                    None
+                };
+
+                handler.on_rsp_error(parsed_rsp_info, request_id, is_last != 0);
+            }
+        }
     }
 }
 
-impl CtpApi for TraderApi {
-    fn get_version() -> CtpResult<String> {
-        let version_ptr = unsafe { CThostFtdcTraderApi_GetApiVersion() };
-        safe_cstr_to_string(version_ptr)
+extern "C" fn on_rsp_order_insert_callback(
+    user_data: *mut c_void,
+    input_order: *mut c_void,
+    rsp_info: *mut c_void,
+    request_id: c_int,
+    is_last: c_int,
+) {
+    unsafe {
+        if let Some(api) = (user_data as *mut TraderApi).as_mut() {
+            api.journal_callback(
+                "OnRspOrderInsert",
+                input_order as *const InputOrderField,
+                rsp_info,
+                request_id,
+                is_last != 0,
+            );
+            if !input_order.is_null() {
+                let input_order = &*(input_order as *const InputOrderField);
+                api.with_latency(|recorder, at| recorder.on_rsp_order_insert(input_order, at));
+            }
+            if let Some(ref mut handler) = api.handler {
+                // 解析input_order指针
+                let parsed_input_order = if !input_order.is_null() {
+                    let order_ptr = input_order as *const InputOrderField;
+                    Some((*order_ptr).clone())
+                } else {
+                    None
+                };
+
+                // 解析rsp_info指针
+                let parsed_rsp_info = if !rsp_info.is_null() {
+                    let rsp_ptr = rsp_info as *const RspInfoField;
+                    Some((*rsp_ptr).clone())
+                } else {
+                    None
+                };
+
+                handler.on_rsp_order_insert(
+                    parsed_input_order,
+                    parsed_rsp_info,
+                    request_id,
+                    is_last != 0,
+                );
+            }
+        }
     }
+}
 
-    fn init(&mut self) -> CtpResult<()> {
-        if self.api_ptr.is_null() {
-            return Err(CtpError::InitializationError("API指针为空".to_string()));
+extern "C" fn on_rsp_order_action_callback(
+    user_data: *mut c_void,
+    input_order_action: *mut c_void,
+    rsp_info: *mut c_void,
+    request_id: c_int,
+    is_last: c_int,
+) {
+    unsafe {
+        if let Some(api) = (user_data as *mut TraderApi).as_mut() {
+            api.journal_callback(
+                "OnRspOrderAction",
+                input_order_action as *const InputOrderActionField,
+                rsp_info,
+                request_id,
+                is_last != 0,
+            );
+            api.with_latency(|recorder, at| {
+                recorder.on_response(LatencyKind::RequestRoundTrip, request_id, is_last != 0, at)
+            });
+            if let Some(ref mut handler) = api.handler {
+                // 解析input_order_action指针
+                let parsed_input_order_action = if !input_order_action.is_null() {
+                    let action_ptr = input_order_action as *const InputOrderActionField;
+                    Some((*action_ptr).clone())
+                } else {
+                    None
+                };
+
+                // 解析rsp_info指针
+                let parsed_rsp_info = if !rsp_info.is_null() {
+                    let rsp_ptr = rsp_info as *const RspInfoField;
+                    Some((*rsp_ptr).clone())
+                } else {
+                    None
+                };
+
+                handler.on_rsp_order_action(
+                    parsed_input_order_action,
+                    parsed_rsp_info,
+                    request_id,
+                    is_last != 0,
+                );
+            }
         }
+    }
+}
 
-        unsafe {
-            CThostFtdcTraderApi_Init(self.api_ptr);
+extern "C" fn on_rtn_order_callback(user_data: *mut c_void, order: *mut c_void) {
+    unsafe {
+        if let Some(api) = (user_data as *mut TraderApi).as_mut() {
+            api.journal_callback(
+                "OnRtnOrder",
+                order as *const OrderField,
+                ptr::null_mut(),
+                0,
+                true,
+            );
+            if !order.is_null() {
+                let order = &*(order as *const OrderField);
+                api.with_latency(|recorder, at| recorder.on_rtn_order(order, at));
+            }
+            if let (Some(monitor), false) = (&api.compliance, order.is_null()) {
+                monitor
+                    .lock()
+                    .unwrap()
+                    .on_rtn_order(&*(order as *const OrderField));
+            }
+            if let Some(ref mut handler) = api.handler {
+                // 解析order指针
+                if !order.is_null() {
+                    let order_ptr = order as *const OrderField;
+                    let parsed_order = (*order_ptr).clone();
+                    handler.on_rtn_order(parsed_order);
+                } else {
+                    // 如果指针为空，创建默认order
+                    let temp_order = OrderField::default();
+                    handler.on_rtn_order(temp_order);
+                }
+            }
         }
+    }
+}
 
-        self.initialized = true;
-        Ok(())
+extern "C" fn on_rtn_trade_callback(user_data: *mut c_void, trade: *mut c_void) {
+    unsafe {
+        if let Some(api) = (user_data as *mut TraderApi).as_mut() {
+            api.journal_callback(
+                "OnRtnTrade",
+                trade as *const TradeField,
+                ptr::null_mut(),
+                0,
+                true,
+            );
+            if !trade.is_null() {
+                let trade = &*(trade as *const TradeField);
+                api.with_latency(|recorder, at| recorder.on_rtn_trade(trade, at));
+            }
+            if let Some(ref mut handler) = api.handler {
+                // 解析trade指针
+                if !trade.is_null() {
+                    let trade_ptr = trade as *const TradeField;
+                    let parsed_trade = (*trade_ptr).clone();
+                    handler.on_rtn_trade(parsed_trade);
+                } else {
+                    // 如果指针为空，创建默认trade
+                    let temp_trade = TradeField::default();
+                    handler.on_rtn_trade(temp_trade);
+                }
+            }
+        }
     }
+}
 
-    fn release(&mut self) {
-        if !self.api_ptr.is_null() {
-            unsafe {
-                CThostFtdcTraderApi_Release(self.api_ptr);
+extern "C" fn on_rsp_qry_trading_account_callback(
+    user_data: *mut c_void,
+    trading_account: *mut c_void,
+    rsp_info: *mut c_void,
+    request_id: c_int,
+    is_last: c_int,
+) {
+    unsafe {
+        // 添加调试信息
+
+        if let Some(api) = (user_data as *mut TraderApi).as_mut() {
+            api.journal_callback(
+                "OnRspQryTradingAccount",
+                trading_account as *const TradingAccountField,
+                rsp_info,
+                request_id,
+                is_last != 0,
+            );
+            api.with_latency(|recorder, at| {
+                recorder.on_response(LatencyKind::QueryRoundTrip, request_id, is_last != 0, at)
+            });
+            if let Some(ref mut handler) = api.handler {
+                let parsed_trading_account = if !trading_account.is_null() {
+                    let account_ptr = trading_account as *const TradingAccountField;
+                    Some((*account_ptr).clone())
+                } else {
+                    None
+                };
+
+                let parsed_rsp_info = if !rsp_info.is_null() {
+                    let info_ptr = rsp_info as *const RspInfoField;
+                    Some((*info_ptr).clone())
+                } else {
+                    None
+                };
+
+                handler.on_rsp_qry_trading_account(
+                    parsed_trading_account,
+                    parsed_rsp_info,
+                    request_id,
+                    is_last != 0,
+                );
+            }
+        }
+    }
+}
+
+extern "C" fn on_rsp_qry_investor_position_callback(
+    user_data: *mut c_void,
+    investor_position: *mut c_void,
+    rsp_info: *mut c_void,
+    request_id: c_int,
+    is_last: c_int,
+) {
+    unsafe {
+        if let Some(api) = (user_data as *mut TraderApi).as_mut() {
+            api.journal_callback(
+                "OnRspQryInvestorPosition",
+                investor_position as *const InvestorPositionField,
+                rsp_info,
+                request_id,
+                is_last != 0,
+            );
+            api.with_latency(|recorder, at| {
+                recorder.on_response(LatencyKind::QueryRoundTrip, request_id, is_last != 0, at)
+            });
+            if let Some(ref mut handler) = api.handler {
+                let parsed_investor_position = if !investor_position.is_null() {
+                    let position_ptr = investor_position as *const InvestorPositionField;
+                    Some((*position_ptr).clone())
+                } else {
+                    None
+                };
+
+                let parsed_rsp_info = if !rsp_info.is_null() {
+                    let info_ptr = rsp_info as *const RspInfoField;
+                    Some((*info_ptr).clone())
+                } else {
+                    None
+                };
+
+                handler.on_rsp_qry_investor_position(
+                    parsed_investor_position,
+                    parsed_rsp_info,
+                    request_id,
+                    is_last != 0,
+                );
+            }
+        }
+    }
+}
+
+// 第一阶段新增回调函数
+
+extern "C" fn on_err_rtn_order_insert_callback(
+    user_data: *mut c_void,
+    input_order: *mut c_void,
+    rsp_info: *mut c_void,
+) {
+    unsafe {
+        if let Some(api) = (user_data as *mut TraderApi).as_mut() {
+            api.journal_callback(
+                "OnErrRtnOrderInsert",
+                input_order as *const InputOrderField,
+                rsp_info,
+                0,
+                true,
+            );
+            if !input_order.is_null() {
+                let input_order = &*(input_order as *const InputOrderField);
+                api.with_latency(|recorder, _| recorder.on_err_rtn_order_insert(input_order));
+            }
+            if let (Some(monitor), false) = (&api.compliance, input_order.is_null()) {
+                monitor
+                    .lock()
+                    .unwrap()
+                    .on_err_rtn_order_insert(&*(input_order as *const InputOrderField));
+            }
+            if let Some(ref mut handler) = api.handler {
+                let parsed_input_order = if !input_order.is_null() {
+                    let order_ptr = input_order as *const InputOrderField;
+                    Some((*order_ptr).clone())
+                } else {
+                    None
+                };
+
+                let parsed_rsp_info = if !rsp_info.is_null() {
+                    let info_ptr = rsp_info as *const RspInfoField;
+                    Some((*info_ptr).clone())
+                } else {
+                    None
+                };
+
+                handler.on_err_rtn_order_insert(parsed_input_order, parsed_rsp_info);
             }
-            self.api_ptr = ptr::null_mut();
         }
-        self.initialized = false;
     }
+}
 
-    fn get_trading_day(&self) -> CtpResult<String> {
-        if self.api_ptr.is_null() {
-            return Err(CtpError::InitializationError("API未初始化".to_string()));
-        }
-
-        let trading_day_ptr = unsafe { CThostFtdcTraderApi_GetTradingDay(self.api_ptr) };
+extern "C" fn on_err_rtn_order_action_callback(
+    user_data: *mut c_void,
+    order_action: *mut c_void,
+    rsp_info: *mut c_void,
+) {
+    unsafe {
+        if let Some(api) = (user_data as *mut TraderApi).as_mut() {
+            api.journal_callback(
+                "OnErrRtnOrderAction",
+                order_action as *const OrderActionField,
+                rsp_info,
+                0,
+                true,
+            );
+            if let Some(ref mut handler) = api.handler {
+                let parsed_order_action = if !order_action.is_null() {
+                    let action_ptr = order_action as *const OrderActionField;
+                    Some((*action_ptr).clone())
+                } else {
+                    None
+                };
 
-        safe_cstr_to_string(trading_day_ptr)
-    }
+                let parsed_rsp_info = if !rsp_info.is_null() {
+                    let info_ptr = rsp_info as *const RspInfoField;
+                    Some((*info_ptr).clone())
+                } else {
+                    None
+                };
 
-    fn register_front(&mut self, front_address: &str) -> CtpResult<()> {
-        if self.api_ptr.is_null() {
-            return Err(CtpError::InitializationError("API未初始化".to_string()));
-        }
-        let front_address_cstr = to_cstring(front_address)?;
-        unsafe {
-            CThostFtdcTraderApi_RegisterFront(self.api_ptr, front_address_cstr.as_ptr());
+                handler.on_err_rtn_order_action(parsed_order_action, parsed_rsp_info);
+            }
         }
-
-        Ok(())
     }
+}
 
-    fn join(&self) -> CtpResult<i32> {
-        if self.api_ptr.is_null() {
-            return Err(CtpError::InitializationError("API未初始化".to_string()));
-        }
+extern "C" fn on_rsp_qry_order_callback(
+    user_data: *mut c_void,
+    order: *mut c_void,
+    rsp_info: *mut c_void,
+    request_id: c_int,
+    is_last: c_int,
+) {
+    unsafe {
+        if let Some(api) = (user_data as *mut TraderApi).as_mut() {
+            api.journal_callback(
+                "OnRspQryOrder",
+                order as *const OrderField,
+                rsp_info,
+                request_id,
+                is_last != 0,
+            );
+            api.with_latency(|recorder, at| {
+                recorder.on_response(LatencyKind::QueryRoundTrip, request_id, is_last != 0, at)
+            });
+            if let Some(ref mut handler) = api.handler {
+                let parsed_order = if !order.is_null() {
+                    let order_ptr = order as *const OrderField;
+                    Some((*order_ptr).clone())
+                } else {
+                    None
+                };
 
-        let result = unsafe { CThostFtdcTraderApi_Join(self.api_ptr) };
+                let parsed_rsp_info = if !rsp_info.is_null() {
+                    let info_ptr = rsp_info as *const RspInfoField;
+                    Some((*info_ptr).clone())
+                } else {
+                    None
+                };
 
-        Ok(result)
+                handler.on_rsp_qry_order(parsed_order, parsed_rsp_info, request_id, is_last != 0);
+            }
+        }
     }
 }
 
-// 回调函数实现
-extern "C" fn on_front_connected_callback(user_data: *mut c_void) {
+extern "C" fn on_rsp_qry_trade_callback(
+    user_data: *mut c_void,
+    trade: *mut c_void,
+    rsp_info: *mut c_void,
+    request_id: c_int,
+    is_last: c_int,
+) {
     unsafe {
         if let Some(api) = (user_data as *mut TraderApi).as_mut() {
+            api.journal_callback(
+                "OnRspQryTrade",
+                trade as *const TradeField,
+                rsp_info,
+                request_id,
+                is_last != 0,
+            );
+            api.with_latency(|recorder, at| {
+                recorder.on_response(LatencyKind::QueryRoundTrip, request_id, is_last != 0, at)
+            });
             if let Some(ref mut handler) = api.handler {
-                handler.on_front_connected();
+                let parsed_trade = if !trade.is_null() {
+                    let trade_ptr = trade as *const TradeField;
+                    Some((*trade_ptr).clone())
+                } else {
+                    None
+                };
+
+                let parsed_rsp_info = if !rsp_info.is_null() {
+                    let info_ptr = rsp_info as *const RspInfoField;
+                    Some((*info_ptr).clone())
+                } else {
+                    None
+                };
+
+                handler.on_rsp_qry_trade(parsed_trade, parsed_rsp_info, request_id, is_last != 0);
             }
         }
     }
 }
 
-extern "C" fn on_front_disconnected_callback(user_data: *mut c_void, reason: c_int) {
+extern "C" fn on_rsp_qry_instrument_callback(
+    user_data: *mut c_void,
+    instrument: *mut c_void,
+    rsp_info: *mut c_void,
+    request_id: c_int,
+    is_last: c_int,
+) {
     unsafe {
         if let Some(api) = (user_data as *mut TraderApi).as_mut() {
+            api.journal_callback(
+                "OnRspQryInstrument",
+                instrument as *const InstrumentField,
+                rsp_info,
+                request_id,
+                is_last != 0,
+            );
+            api.with_latency(|recorder, at| {
+                recorder.on_response(LatencyKind::QueryRoundTrip, request_id, is_last != 0, at)
+            });
             if let Some(ref mut handler) = api.handler {
-                handler.on_front_disconnected(reason);
+                let parsed_instrument = if !instrument.is_null() {
+                    let instrument_ptr = instrument as *const InstrumentField;
+                    Some((*instrument_ptr).clone())
+                } else {
+                    None
+                };
+
+                let parsed_rsp_info = if !rsp_info.is_null() {
+                    let info_ptr = rsp_info as *const RspInfoField;
+                    Some((*info_ptr).clone())
+                } else {
+                    None
+                };
+
+                handler.on_rsp_qry_instrument(
+                    parsed_instrument,
+                    parsed_rsp_info,
+                    request_id,
+                    is_last != 0,
+                );
             }
         }
     }
 }
 
-extern "C" fn on_heart_beat_warning_callback(user_data: *mut c_void, time_lapse: c_int) {
+// 第二阶段新增回调函数
+
+extern "C" fn on_rsp_qry_instrument_margin_rate_callback(
+    user_data: *mut c_void,
+    margin_rate: *mut c_void,
+    rsp_info: *mut c_void,
+    request_id: c_int,
+    is_last: c_int,
+) {
     unsafe {
         if let Some(api) = (user_data as *mut TraderApi).as_mut() {
+            api.journal_callback(
+                "OnRspQryInstrumentMarginRate",
+                margin_rate as *const InstrumentMarginRateField,
+                rsp_info,
+                request_id,
+                is_last != 0,
+            );
+            api.with_latency(|recorder, at| {
+                recorder.on_response(LatencyKind::QueryRoundTrip, request_id, is_last != 0, at)
+            });
             if let Some(ref mut handler) = api.handler {
-                handler.on_heart_beat_warning(time_lapse);
+                let parsed_margin_rate = if !margin_rate.is_null() {
+                    let margin_rate_ptr = margin_rate as *const InstrumentMarginRateField;
+                    Some((*margin_rate_ptr).clone())
+                } else {
+                    None
+                };
+
+                let parsed_rsp_info = if !rsp_info.is_null() {
+                    let info_ptr = rsp_info as *const RspInfoField;
+                    Some((*info_ptr).clone())
+                } else {
+                    None
+                };
+
+                handler.on_rsp_qry_instrument_margin_rate(
+                    parsed_margin_rate,
+                    parsed_rsp_info,
+                    request_id,
+                    is_last != 0,
+                );
             }
         }
     }
 }
 
-extern "C" fn on_rsp_authenticate_callback(
+extern "C" fn on_rsp_qry_instrument_commission_rate_callback(
     user_data: *mut c_void,
-    rsp_authenticate: *mut c_void,
+    commission_rate: *mut c_void,
     rsp_info: *mut c_void,
     request_id: c_int,
     is_last: c_int,
 ) {
     unsafe {
         if let Some(api) = (user_data as *mut TraderApi).as_mut() {
+            api.journal_callback(
+                "OnRspQryInstrumentCommissionRate",
+                commission_rate as *const InstrumentCommissionRateField,
+                rsp_info,
+                request_id,
+                is_last != 0,
+            );
+            api.with_latency(|recorder, at| {
+                recorder.on_response(LatencyKind::QueryRoundTrip, request_id, is_last != 0, at)
+            });
             if let Some(ref mut handler) = api.handler {
-                // 解析rsp_authenticate指针
-                let parsed_rsp_authenticate = if !rsp_authenticate.is_null() {
-                    let auth_ptr = rsp_authenticate as *const RspAuthenticateField;
-                    Some((*auth_ptr).clone())
+                let parsed_commission_rate = if !commission_rate.is_null() {
+                    let commission_rate_ptr =
+                        commission_rate as *const InstrumentCommissionRateField;
+                    Some((*commission_rate_ptr).clone())
                 } else {
                     None
                 };
 
-                // 解析rsp_info指针
                 let parsed_rsp_info = if !rsp_info.is_null() {
-                    let rsp_ptr = rsp_info as *const RspInfoField;
-                    Some((*rsp_ptr).clone())
+                    let info_ptr = rsp_info as *const RspInfoField;
+                    Some((*info_ptr).clone())
                 } else {
                     None
                 };
 
-                handler.on_rsp_authenticate(
-                    parsed_rsp_authenticate,
+                handler.on_rsp_qry_instrument_commission_rate(
+                    parsed_commission_rate,
                     parsed_rsp_info,
                     request_id,
                     is_last != 0,
@@ -2106,34 +3931,42 @@ extern "C" fn on_rsp_authenticate_callback(
     }
 }
 
-extern "C" fn on_rsp_user_login_callback(
+extern "C" fn on_rsp_qry_exchange_callback(
     user_data: *mut c_void,
-    user_login: *mut c_void,
+    exchange: *mut c_void,
     rsp_info: *mut c_void,
     request_id: c_int,
     is_last: c_int,
 ) {
     unsafe {
         if let Some(api) = (user_data as *mut TraderApi).as_mut() {
+            api.journal_callback(
+                "OnRspQryExchange",
+                exchange as *const ExchangeField,
+                rsp_info,
+                request_id,
+                is_last != 0,
+            );
+            api.with_latency(|recorder, at| {
+                recorder.on_response(LatencyKind::QueryRoundTrip, request_id, is_last != 0, at)
+            });
             if let Some(ref mut handler) = api.handler {
-                // 解析user_login指针
-                let parsed_user_login = if !user_login.is_null() {
-                    let login_ptr = user_login as *const RspUserLoginField;
-                    Some((*login_ptr).clone())
+                let parsed_exchange = if !exchange.is_null() {
+                    let exchange_ptr = exchange as *const ExchangeField;
+                    Some((*exchange_ptr).clone())
                 } else {
                     None
                 };
 
-                // 解析rsp_info指针
                 let parsed_rsp_info = if !rsp_info.is_null() {
-                    let rsp_ptr = rsp_info as *const RspInfoField;
-                    Some((*rsp_ptr).clone())
+                    let info_ptr = rsp_info as *const RspInfoField;
+                    Some((*info_ptr).clone())
                 } else {
                     None
                 };
 
-                handler.on_rsp_user_login(
-                    parsed_user_login,
+                handler.on_rsp_qry_exchange(
+                    parsed_exchange,
                     parsed_rsp_info,
                     request_id,
                     is_last != 0,
@@ -2143,81 +3976,133 @@ extern "C" fn on_rsp_user_login_callback(
     }
 }
 
-extern "C" fn on_rsp_user_logout_callback(
+extern "C" fn on_rsp_qry_product_callback(
     user_data: *mut c_void,
-    _user_logout: *mut c_void,
+    product: *mut c_void,
     rsp_info: *mut c_void,
     request_id: c_int,
     is_last: c_int,
 ) {
     unsafe {
         if let Some(api) = (user_data as *mut TraderApi).as_mut() {
+            api.journal_callback(
+                "OnRspQryProduct",
+                product as *const ProductField,
+                rsp_info,
+                request_id,
+                is_last != 0,
+            );
+            api.with_latency(|recorder, at| {
+                recorder.on_response(LatencyKind::QueryRoundTrip, request_id, is_last != 0, at)
+            });
             if let Some(ref mut handler) = api.handler {
-                // 解析rsp_info指针
+                let parsed_product = if !product.is_null() {
+                    let product_ptr = product as *const ProductField;
+                    Some((*product_ptr).clone())
+                } else {
+                    None
+                };
+
                 let parsed_rsp_info = if !rsp_info.is_null() {
-                    let rsp_ptr = rsp_info as *const RspInfoField;
-                    Some((*rsp_ptr).clone())
+                    let info_ptr = rsp_info as *const RspInfoField;
+                    Some((*info_ptr).clone())
                 } else {
                     None
                 };
 
-                handler.on_rsp_user_logout(None, parsed_rsp_info, request_id, is_last != 0);
+                handler.on_rsp_qry_product(
+                    parsed_product,
+                    parsed_rsp_info,
+                    request_id,
+                    is_last != 0,
+                );
             }
         }
     }
 }
 
-extern "C" fn on_rsp_error_callback(
+extern "C" fn on_rsp_settlement_info_confirm_callback(
     user_data: *mut c_void,
+    settlement_info_confirm: *mut c_void,
     rsp_info: *mut c_void,
     request_id: c_int,
     is_last: c_int,
 ) {
     unsafe {
         if let Some(api) = (user_data as *mut TraderApi).as_mut() {
+            api.journal_callback(
+                "OnRspSettlementInfoConfirm",
+                settlement_info_confirm as *const SettlementInfoConfirmField,
+                rsp_info,
+                request_id,
+                is_last != 0,
+            );
+            api.with_latency(|recorder, at| {
+                recorder.on_response(LatencyKind::RequestRoundTrip, request_id, is_last != 0, at)
+            });
             if let Some(ref mut handler) = api.handler {
-                // 解析rsp_info指针
+                let parsed_settlement_info_confirm = if !settlement_info_confirm.is_null() {
+                    let settlement_ptr =
+                        settlement_info_confirm as *const SettlementInfoConfirmField;
+                    Some((*settlement_ptr).clone())
+                } else {
+                    None
+                };
+
                 let parsed_rsp_info = if !rsp_info.is_null() {
-                    let rsp_ptr = rsp_info as *const RspInfoField;
-                    Some((*rsp_ptr).clone())
+                    let info_ptr = rsp_info as *const RspInfoField;
+                    Some((*info_ptr).clone())
                 } else {
                     None
                 };
 
-                handler.on_rsp_error(parsed_rsp_info, request_id, is_last != 0);
+                handler.on_rsp_settlement_info_confirm(
+                    parsed_settlement_info_confirm,
+                    parsed_rsp_info,
+                    request_id,
+                    is_last != 0,
+                );
             }
         }
     }
 }
 
-extern "C" fn on_rsp_order_insert_callback(
+extern "C" fn on_rsp_parked_order_insert_callback(
     user_data: *mut c_void,
-    input_order: *mut c_void,
+    parked_order: *mut c_void,
     rsp_info: *mut c_void,
     request_id: c_int,
     is_last: c_int,
 ) {
     unsafe {
         if let Some(api) = (user_data as *mut TraderApi).as_mut() {
+            api.journal_callback(
+                "OnRspParkedOrderInsert",
+                parked_order as *const ParkedOrderField,
+                rsp_info,
+                request_id,
+                is_last != 0,
+            );
+            api.with_latency(|recorder, at| {
+                recorder.on_response(LatencyKind::RequestRoundTrip, request_id, is_last != 0, at)
+            });
             if let Some(ref mut handler) = api.handler {
-                // 解析input_order指针
-                let parsed_input_order = if !input_order.is_null() {
-                    let order_ptr = input_order as *const InputOrderField;
-                    Some((*order_ptr).clone())
+                let parsed_parked_order = if !parked_order.is_null() {
+                    let parked_order_ptr = parked_order as *const ParkedOrderField;
+                    Some((*parked_order_ptr).clone())
                 } else {
                     None
                 };
 
-                // 解析rsp_info指针
                 let parsed_rsp_info = if !rsp_info.is_null() {
-                    let rsp_ptr = rsp_info as *const RspInfoField;
-                    Some((*rsp_ptr).clone())
+                    let info_ptr = rsp_info as *const RspInfoField;
+                    Some((*info_ptr).clone())
                 } else {
                     None
                 };
 
-                handler.on_rsp_order_insert(
-                    parsed_input_order,
+                handler.on_rsp_parked_order_insert(
+                    parsed_parked_order,
                     parsed_rsp_info,
                     request_id,
                     is_last != 0,
@@ -2227,34 +4112,42 @@ extern "C" fn on_rsp_order_insert_callback(
     }
 }
 
-extern "C" fn on_rsp_order_action_callback(
+extern "C" fn on_rsp_parked_order_action_callback(
     user_data: *mut c_void,
-    input_order_action: *mut c_void,
+    parked_order_action: *mut c_void,
     rsp_info: *mut c_void,
     request_id: c_int,
     is_last: c_int,
 ) {
     unsafe {
         if let Some(api) = (user_data as *mut TraderApi).as_mut() {
+            api.journal_callback(
+                "OnRspParkedOrderAction",
+                parked_order_action as *const ParkedOrderActionField,
+                rsp_info,
+                request_id,
+                is_last != 0,
+            );
+            api.with_latency(|recorder, at| {
+                recorder.on_response(LatencyKind::RequestRoundTrip, request_id, is_last != 0, at)
+            });
             if let Some(ref mut handler) = api.handler {
-                // 解析input_order_action指针
-                let parsed_input_order_action = if !input_order_action.is_null() {
-                    let action_ptr = input_order_action as *const InputOrderActionField;
-                    Some((*action_ptr).clone())
+                let parsed_parked_order_action = if !parked_order_action.is_null() {
+                    let parked_action_ptr = parked_order_action as *const ParkedOrderActionField;
+                    Some((*parked_action_ptr).clone())
                 } else {
                     None
                 };
 
-                // 解析rsp_info指针
                 let parsed_rsp_info = if !rsp_info.is_null() {
-                    let rsp_ptr = rsp_info as *const RspInfoField;
-                    Some((*rsp_ptr).clone())
+                    let info_ptr = rsp_info as *const RspInfoField;
+                    Some((*info_ptr).clone())
                 } else {
                     None
                 };
 
-                handler.on_rsp_order_action(
-                    parsed_input_order_action,
+                handler.on_rsp_parked_order_action(
+                    parsed_parked_order_action,
                     parsed_rsp_info,
                     request_id,
                     is_last != 0,
@@ -2264,59 +4157,31 @@ extern "C" fn on_rsp_order_action_callback(
     }
 }
 
-extern "C" fn on_rtn_order_callback(user_data: *mut c_void, order: *mut c_void) {
-    unsafe {
-        if let Some(api) = (user_data as *mut TraderApi).as_mut() {
-            if let Some(ref mut handler) = api.handler {
-                // 解析order指针
-                if !order.is_null() {
-                    let order_ptr = order as *const OrderField;
-                    let parsed_order = (*order_ptr).clone();
-                    handler.on_rtn_order(parsed_order);
-                } else {
-                    // 如果指针为空，创建默认order
-                    let temp_order = OrderField::default();
-                    handler.on_rtn_order(temp_order);
-                }
-            }
-        }
-    }
-}
-
-extern "C" fn on_rtn_trade_callback(user_data: *mut c_void, trade: *mut c_void) {
-    unsafe {
-        if let Some(api) = (user_data as *mut TraderApi).as_mut() {
-            if let Some(ref mut handler) = api.handler {
-                // 解析trade指针
-                if !trade.is_null() {
-                    let trade_ptr = trade as *const TradeField;
-                    let parsed_trade = (*trade_ptr).clone();
-                    handler.on_rtn_trade(parsed_trade);
-                } else {
-                    // 如果指针为空，创建默认trade
-                    let temp_trade = TradeField::default();
-                    handler.on_rtn_trade(temp_trade);
-                }
-            }
-        }
-    }
-}
+// 第三阶段回调函数实现
 
-extern "C" fn on_rsp_qry_trading_account_callback(
+extern "C" fn on_rsp_exec_order_insert_callback(
     user_data: *mut c_void,
-    trading_account: *mut c_void,
+    input_exec_order: *mut c_void,
     rsp_info: *mut c_void,
     request_id: c_int,
     is_last: c_int,
 ) {
     unsafe {
-        // 添加调试信息
-
         if let Some(api) = (user_data as *mut TraderApi).as_mut() {
+            api.journal_callback(
+                "OnRspExecOrderInsert",
+                input_exec_order as *const InputExecOrderField,
+                rsp_info,
+                request_id,
+                is_last != 0,
+            );
+            api.with_latency(|recorder, at| {
+                recorder.on_response(LatencyKind::RequestRoundTrip, request_id, is_last != 0, at)
+            });
             if let Some(ref mut handler) = api.handler {
-                let parsed_trading_account = if !trading_account.is_null() {
-                    let account_ptr = trading_account as *const TradingAccountField;
-                    Some((*account_ptr).clone())
+                let parsed_input_exec_order = if !input_exec_order.is_null() {
+                    let exec_order_ptr = input_exec_order as *const InputExecOrderField;
+                    Some((*exec_order_ptr).clone())
                 } else {
                     None
                 };
@@ -2328,8 +4193,8 @@ extern "C" fn on_rsp_qry_trading_account_callback(
                     None
                 };
 
-                handler.on_rsp_qry_trading_account(
-                    parsed_trading_account,
+                handler.on_rsp_exec_order_insert(
+                    parsed_input_exec_order,
                     parsed_rsp_info,
                     request_id,
                     is_last != 0,
@@ -2339,19 +4204,30 @@ extern "C" fn on_rsp_qry_trading_account_callback(
     }
 }
 
-extern "C" fn on_rsp_qry_investor_position_callback(
+extern "C" fn on_rsp_exec_order_action_callback(
     user_data: *mut c_void,
-    investor_position: *mut c_void,
+    input_exec_order_action: *mut c_void,
     rsp_info: *mut c_void,
     request_id: c_int,
     is_last: c_int,
 ) {
     unsafe {
         if let Some(api) = (user_data as *mut TraderApi).as_mut() {
+            api.journal_callback(
+                "OnRspExecOrderAction",
+                input_exec_order_action as *const InputExecOrderActionField,
+                rsp_info,
+                request_id,
+                is_last != 0,
+            );
+            api.with_latency(|recorder, at| {
+                recorder.on_response(LatencyKind::RequestRoundTrip, request_id, is_last != 0, at)
+            });
             if let Some(ref mut handler) = api.handler {
-                let parsed_investor_position = if !investor_position.is_null() {
-                    let position_ptr = investor_position as *const InvestorPositionField;
-                    Some((*position_ptr).clone())
+                let parsed_input_exec_order_action = if !input_exec_order_action.is_null() {
+                    let exec_action_ptr =
+                        input_exec_order_action as *const InputExecOrderActionField;
+                    Some((*exec_action_ptr).clone())
                 } else {
                     None
                 };
@@ -2363,8 +4239,8 @@ extern "C" fn on_rsp_qry_investor_position_callback(
                     None
                 };
 
-                handler.on_rsp_qry_investor_position(
-                    parsed_investor_position,
+                handler.on_rsp_exec_order_action(
+                    parsed_input_exec_order_action,
                     parsed_rsp_info,
                     request_id,
                     is_last != 0,
@@ -2374,19 +4250,29 @@ extern "C" fn on_rsp_qry_investor_position_callback(
     }
 }
 
-// 第一阶段新增回调函数
-
-extern "C" fn on_err_rtn_order_insert_callback(
+extern "C" fn on_rsp_for_quote_insert_callback(
     user_data: *mut c_void,
-    input_order: *mut c_void,
+    input_for_quote: *mut c_void,
     rsp_info: *mut c_void,
+    request_id: c_int,
+    is_last: c_int,
 ) {
     unsafe {
         if let Some(api) = (user_data as *mut TraderApi).as_mut() {
+            api.journal_callback(
+                "OnRspForQuoteInsert",
+                input_for_quote as *const InputForQuoteField,
+                rsp_info,
+                request_id,
+                is_last != 0,
+            );
+            api.with_latency(|recorder, at| {
+                recorder.on_response(LatencyKind::RequestRoundTrip, request_id, is_last != 0, at)
+            });
             if let Some(ref mut handler) = api.handler {
-                let parsed_input_order = if !input_order.is_null() {
-                    let order_ptr = input_order as *const InputOrderField;
-                    Some((*order_ptr).clone())
+                let parsed_input_for_quote = if !input_for_quote.is_null() {
+                    let for_quote_ptr = input_for_quote as *const InputForQuoteField;
+                    Some((*for_quote_ptr).clone())
                 } else {
                     None
                 };
@@ -2398,23 +4284,40 @@ extern "C" fn on_err_rtn_order_insert_callback(
                     None
                 };
 
-                handler.on_err_rtn_order_insert(parsed_input_order, parsed_rsp_info);
+                handler.on_rsp_for_quote_insert(
+                    parsed_input_for_quote,
+                    parsed_rsp_info,
+                    request_id,
+                    is_last != 0,
+                );
             }
         }
     }
 }
 
-extern "C" fn on_err_rtn_order_action_callback(
+extern "C" fn on_rsp_quote_insert_callback(
     user_data: *mut c_void,
-    order_action: *mut c_void,
+    input_quote: *mut c_void,
     rsp_info: *mut c_void,
+    request_id: c_int,
+    is_last: c_int,
 ) {
     unsafe {
         if let Some(api) = (user_data as *mut TraderApi).as_mut() {
+            api.journal_callback(
+                "OnRspQuoteInsert",
+                input_quote as *const InputQuoteField,
+                rsp_info,
+                request_id,
+                is_last != 0,
+            );
+            api.with_latency(|recorder, at| {
+                recorder.on_response(LatencyKind::RequestRoundTrip, request_id, is_last != 0, at)
+            });
             if let Some(ref mut handler) = api.handler {
-                let parsed_order_action = if !order_action.is_null() {
-                    let action_ptr = order_action as *const OrderActionField;
-                    Some((*action_ptr).clone())
+                let parsed_input_quote = if !input_quote.is_null() {
+                    let quote_ptr = input_quote as *const InputQuoteField;
+                    Some((*quote_ptr).clone())
                 } else {
                     None
                 };
@@ -2426,25 +4329,40 @@ extern "C" fn on_err_rtn_order_action_callback(
                     None
                 };
 
-                handler.on_err_rtn_order_action(parsed_order_action, parsed_rsp_info);
+                handler.on_rsp_quote_insert(
+                    parsed_input_quote,
+                    parsed_rsp_info,
+                    request_id,
+                    is_last != 0,
+                );
             }
         }
     }
 }
 
-extern "C" fn on_rsp_qry_order_callback(
+extern "C" fn on_rsp_quote_action_callback(
     user_data: *mut c_void,
-    order: *mut c_void,
+    input_quote_action: *mut c_void,
     rsp_info: *mut c_void,
     request_id: c_int,
     is_last: c_int,
 ) {
     unsafe {
         if let Some(api) = (user_data as *mut TraderApi).as_mut() {
+            api.journal_callback(
+                "OnRspQuoteAction",
+                input_quote_action as *const InputQuoteActionField,
+                rsp_info,
+                request_id,
+                is_last != 0,
+            );
+            api.with_latency(|recorder, at| {
+                recorder.on_response(LatencyKind::RequestRoundTrip, request_id, is_last != 0, at)
+            });
             if let Some(ref mut handler) = api.handler {
-                let parsed_order = if !order.is_null() {
-                    let order_ptr = order as *const OrderField;
-                    Some((*order_ptr).clone())
+                let parsed_input_quote_action = if !input_quote_action.is_null() {
+                    let quote_action_ptr = input_quote_action as *const InputQuoteActionField;
+                    Some((*quote_action_ptr).clone())
                 } else {
                     None
                 };
@@ -2456,25 +4374,41 @@ extern "C" fn on_rsp_qry_order_callback(
                     None
                 };
 
-                handler.on_rsp_qry_order(parsed_order, parsed_rsp_info, request_id, is_last != 0);
+                handler.on_rsp_quote_action(
+                    parsed_input_quote_action,
+                    parsed_rsp_info,
+                    request_id,
+                    is_last != 0,
+                );
             }
         }
     }
 }
 
-extern "C" fn on_rsp_qry_trade_callback(
+extern "C" fn on_rsp_batch_order_action_callback(
     user_data: *mut c_void,
-    trade: *mut c_void,
+    input_batch_order_action: *mut c_void,
     rsp_info: *mut c_void,
     request_id: c_int,
     is_last: c_int,
 ) {
     unsafe {
         if let Some(api) = (user_data as *mut TraderApi).as_mut() {
+            api.journal_callback(
+                "OnRspBatchOrderAction",
+                input_batch_order_action as *const InputBatchOrderActionField,
+                rsp_info,
+                request_id,
+                is_last != 0,
+            );
+            api.with_latency(|recorder, at| {
+                recorder.on_response(LatencyKind::RequestRoundTrip, request_id, is_last != 0, at)
+            });
             if let Some(ref mut handler) = api.handler {
-                let parsed_trade = if !trade.is_null() {
-                    let trade_ptr = trade as *const TradeField;
-                    Some((*trade_ptr).clone())
+                let parsed_input_batch_order_action = if !input_batch_order_action.is_null() {
+                    let batch_action_ptr =
+                        input_batch_order_action as *const InputBatchOrderActionField;
+                    Some((*batch_action_ptr).clone())
                 } else {
                     None
                 };
@@ -2486,25 +4420,40 @@ extern "C" fn on_rsp_qry_trade_callback(
                     None
                 };
 
-                handler.on_rsp_qry_trade(parsed_trade, parsed_rsp_info, request_id, is_last != 0);
+                handler.on_rsp_batch_order_action(
+                    parsed_input_batch_order_action,
+                    parsed_rsp_info,
+                    request_id,
+                    is_last != 0,
+                );
             }
         }
     }
 }
 
-extern "C" fn on_rsp_qry_instrument_callback(
+extern "C" fn on_rsp_remove_parked_order_callback(
     user_data: *mut c_void,
-    instrument: *mut c_void,
+    remove_parked_order: *mut c_void,
     rsp_info: *mut c_void,
     request_id: c_int,
     is_last: c_int,
 ) {
     unsafe {
         if let Some(api) = (user_data as *mut TraderApi).as_mut() {
+            api.journal_callback(
+                "OnRspRemoveParkedOrder",
+                remove_parked_order as *const RemoveParkedOrderField,
+                rsp_info,
+                request_id,
+                is_last != 0,
+            );
+            api.with_latency(|recorder, at| {
+                recorder.on_response(LatencyKind::RequestRoundTrip, request_id, is_last != 0, at)
+            });
             if let Some(ref mut handler) = api.handler {
-                let parsed_instrument = if !instrument.is_null() {
-                    let instrument_ptr = instrument as *const InstrumentField;
-                    Some((*instrument_ptr).clone())
+                let parsed_remove_parked_order = if !remove_parked_order.is_null() {
+                    let remove_ptr = remove_parked_order as *const RemoveParkedOrderField;
+                    Some((*remove_ptr).clone())
                 } else {
                     None
                 };
@@ -2516,8 +4465,8 @@ extern "C" fn on_rsp_qry_instrument_callback(
                     None
                 };
 
-                handler.on_rsp_qry_instrument(
-                    parsed_instrument,
+                handler.on_rsp_remove_parked_order(
+                    parsed_remove_parked_order,
                     parsed_rsp_info,
                     request_id,
                     is_last != 0,
@@ -2527,21 +4476,30 @@ extern "C" fn on_rsp_qry_instrument_callback(
     }
 }
 
-// 第二阶段新增回调函数
-
-extern "C" fn on_rsp_qry_instrument_margin_rate_callback(
+extern "C" fn on_rsp_remove_parked_order_action_callback(
     user_data: *mut c_void,
-    margin_rate: *mut c_void,
+    remove_parked_order_action: *mut c_void,
     rsp_info: *mut c_void,
     request_id: c_int,
     is_last: c_int,
 ) {
     unsafe {
         if let Some(api) = (user_data as *mut TraderApi).as_mut() {
+            api.journal_callback(
+                "OnRspRemoveParkedOrderAction",
+                remove_parked_order_action as *const RemoveParkedOrderActionField,
+                rsp_info,
+                request_id,
+                is_last != 0,
+            );
+            api.with_latency(|recorder, at| {
+                recorder.on_response(LatencyKind::RequestRoundTrip, request_id, is_last != 0, at)
+            });
             if let Some(ref mut handler) = api.handler {
-                let parsed_margin_rate = if !margin_rate.is_null() {
-                    let margin_rate_ptr = margin_rate as *const InstrumentMarginRateField;
-                    Some((*margin_rate_ptr).clone())
+                let parsed_remove_parked_order_action = if !remove_parked_order_action.is_null() {
+                    let remove_action_ptr =
+                        remove_parked_order_action as *const RemoveParkedOrderActionField;
+                    Some((*remove_action_ptr).clone())
                 } else {
                     None
                 };
@@ -2553,8 +4511,8 @@ extern "C" fn on_rsp_qry_instrument_margin_rate_callback(
                     None
                 };
 
-                handler.on_rsp_qry_instrument_margin_rate(
-                    parsed_margin_rate,
+                handler.on_rsp_remove_parked_order_action(
+                    parsed_remove_parked_order_action,
                     parsed_rsp_info,
                     request_id,
                     is_last != 0,
@@ -2564,20 +4522,29 @@ extern "C" fn on_rsp_qry_instrument_margin_rate_callback(
     }
 }
 
-extern "C" fn on_rsp_qry_instrument_commission_rate_callback(
+extern "C" fn on_rsp_qry_max_order_volume_callback(
     user_data: *mut c_void,
-    commission_rate: *mut c_void,
+    qry_max_order_volume: *mut c_void,
     rsp_info: *mut c_void,
     request_id: c_int,
     is_last: c_int,
 ) {
     unsafe {
         if let Some(api) = (user_data as *mut TraderApi).as_mut() {
+            api.journal_callback(
+                "OnRspQryMaxOrderVolume",
+                qry_max_order_volume as *const QryMaxOrderVolumeField,
+                rsp_info,
+                request_id,
+                is_last != 0,
+            );
+            api.with_latency(|recorder, at| {
+                recorder.on_response(LatencyKind::QueryRoundTrip, request_id, is_last != 0, at)
+            });
             if let Some(ref mut handler) = api.handler {
-                let parsed_commission_rate = if !commission_rate.is_null() {
-                    let commission_rate_ptr =
-                        commission_rate as *const InstrumentCommissionRateField;
-                    Some((*commission_rate_ptr).clone())
+                let parsed_qry_max_order_volume = if !qry_max_order_volume.is_null() {
+                    let max_volume_ptr = qry_max_order_volume as *const QryMaxOrderVolumeField;
+                    Some((*max_volume_ptr).clone())
                 } else {
                     None
                 };
@@ -2589,8 +4556,8 @@ extern "C" fn on_rsp_qry_instrument_commission_rate_callback(
                     None
                 };
 
-                handler.on_rsp_qry_instrument_commission_rate(
-                    parsed_commission_rate,
+                handler.on_rsp_qry_max_order_volume(
+                    parsed_qry_max_order_volume,
                     parsed_rsp_info,
                     request_id,
                     is_last != 0,
@@ -2600,19 +4567,29 @@ extern "C" fn on_rsp_qry_instrument_commission_rate_callback(
     }
 }
 
-extern "C" fn on_rsp_qry_exchange_callback(
+extern "C" fn on_rsp_qry_depth_market_data_callback(
     user_data: *mut c_void,
-    exchange: *mut c_void,
+    depth_market_data: *mut c_void,
     rsp_info: *mut c_void,
     request_id: c_int,
     is_last: c_int,
 ) {
     unsafe {
         if let Some(api) = (user_data as *mut TraderApi).as_mut() {
+            api.journal_callback(
+                "OnRspQryDepthMarketData",
+                depth_market_data as *const DepthMarketDataField,
+                rsp_info,
+                request_id,
+                is_last != 0,
+            );
+            api.with_latency(|recorder, at| {
+                recorder.on_response(LatencyKind::QueryRoundTrip, request_id, is_last != 0, at)
+            });
             if let Some(ref mut handler) = api.handler {
-                let parsed_exchange = if !exchange.is_null() {
-                    let exchange_ptr = exchange as *const ExchangeField;
-                    Some((*exchange_ptr).clone())
+                let parsed_depth_market_data = if !depth_market_data.is_null() {
+                    let market_data_ptr = depth_market_data as *const DepthMarketDataField;
+                    Some((*market_data_ptr).clone())
                 } else {
                     None
                 };
@@ -2624,8 +4601,8 @@ extern "C" fn on_rsp_qry_exchange_callback(
                     None
                 };
 
-                handler.on_rsp_qry_exchange(
-                    parsed_exchange,
+                handler.on_rsp_qry_depth_market_data(
+                    parsed_depth_market_data,
                     parsed_rsp_info,
                     request_id,
                     is_last != 0,
@@ -2635,19 +4612,29 @@ extern "C" fn on_rsp_qry_exchange_callback(
     }
 }
 
-extern "C" fn on_rsp_qry_product_callback(
+extern "C" fn on_rsp_qry_settlement_info_callback(
     user_data: *mut c_void,
-    product: *mut c_void,
+    settlement_info: *mut c_void,
     rsp_info: *mut c_void,
     request_id: c_int,
     is_last: c_int,
 ) {
     unsafe {
         if let Some(api) = (user_data as *mut TraderApi).as_mut() {
+            api.journal_callback(
+                "OnRspQrySettlementInfo",
+                settlement_info as *const SettlementInfoField,
+                rsp_info,
+                request_id,
+                is_last != 0,
+            );
+            api.with_latency(|recorder, at| {
+                recorder.on_response(LatencyKind::QueryRoundTrip, request_id, is_last != 0, at)
+            });
             if let Some(ref mut handler) = api.handler {
-                let parsed_product = if !product.is_null() {
-                    let product_ptr = product as *const ProductField;
-                    Some((*product_ptr).clone())
+                let parsed_settlement_info = if !settlement_info.is_null() {
+                    let settlement_ptr = settlement_info as *const SettlementInfoField;
+                    Some((*settlement_ptr).clone())
                 } else {
                     None
                 };
@@ -2659,8 +4646,8 @@ extern "C" fn on_rsp_qry_product_callback(
                     None
                 };
 
-                handler.on_rsp_qry_product(
-                    parsed_product,
+                handler.on_rsp_qry_settlement_info(
+                    parsed_settlement_info,
                     parsed_rsp_info,
                     request_id,
                     is_last != 0,
@@ -2670,20 +4657,29 @@ extern "C" fn on_rsp_qry_product_callback(
     }
 }
 
-extern "C" fn on_rsp_settlement_info_confirm_callback(
+extern "C" fn on_rsp_qry_transfer_bank_callback(
     user_data: *mut c_void,
-    settlement_info_confirm: *mut c_void,
+    transfer_bank: *mut c_void,
     rsp_info: *mut c_void,
     request_id: c_int,
     is_last: c_int,
 ) {
     unsafe {
         if let Some(api) = (user_data as *mut TraderApi).as_mut() {
+            api.journal_callback(
+                "OnRspQryTransferBank",
+                transfer_bank as *const TransferBankField,
+                rsp_info,
+                request_id,
+                is_last != 0,
+            );
+            api.with_latency(|recorder, at| {
+                recorder.on_response(LatencyKind::QueryRoundTrip, request_id, is_last != 0, at)
+            });
             if let Some(ref mut handler) = api.handler {
-                let parsed_settlement_info_confirm = if !settlement_info_confirm.is_null() {
-                    let settlement_ptr =
-                        settlement_info_confirm as *const SettlementInfoConfirmField;
-                    Some((*settlement_ptr).clone())
+                let parsed_transfer_bank = if !transfer_bank.is_null() {
+                    let bank_ptr = transfer_bank as *const TransferBankField;
+                    Some((*bank_ptr).clone())
                 } else {
                     None
                 };
@@ -2695,8 +4691,8 @@ extern "C" fn on_rsp_settlement_info_confirm_callback(
                     None
                 };
 
-                handler.on_rsp_settlement_info_confirm(
-                    parsed_settlement_info_confirm,
+                handler.on_rsp_qry_transfer_bank(
+                    parsed_transfer_bank,
                     parsed_rsp_info,
                     request_id,
                     is_last != 0,
@@ -2706,19 +4702,29 @@ extern "C" fn on_rsp_settlement_info_confirm_callback(
     }
 }
 
-extern "C" fn on_rsp_parked_order_insert_callback(
+extern "C" fn on_rsp_qry_investor_position_detail_callback(
     user_data: *mut c_void,
-    parked_order: *mut c_void,
+    investor_position_detail: *mut c_void,
     rsp_info: *mut c_void,
     request_id: c_int,
     is_last: c_int,
 ) {
     unsafe {
         if let Some(api) = (user_data as *mut TraderApi).as_mut() {
+            api.journal_callback(
+                "OnRspQryInvestorPositionDetail",
+                investor_position_detail as *const InvestorPositionDetailField,
+                rsp_info,
+                request_id,
+                is_last != 0,
+            );
+            api.with_latency(|recorder, at| {
+                recorder.on_response(LatencyKind::QueryRoundTrip, request_id, is_last != 0, at)
+            });
             if let Some(ref mut handler) = api.handler {
-                let parsed_parked_order = if !parked_order.is_null() {
-                    let parked_order_ptr = parked_order as *const ParkedOrderField;
-                    Some((*parked_order_ptr).clone())
+                let parsed_investor_position_detail = if !investor_position_detail.is_null() {
+                    let detail_ptr = investor_position_detail as *const InvestorPositionDetailField;
+                    Some((*detail_ptr).clone())
                 } else {
                     None
                 };
@@ -2730,8 +4736,8 @@ extern "C" fn on_rsp_parked_order_insert_callback(
                     None
                 };
 
-                handler.on_rsp_parked_order_insert(
-                    parsed_parked_order,
+                handler.on_rsp_qry_investor_position_detail(
+                    parsed_investor_position_detail,
                     parsed_rsp_info,
                     request_id,
                     is_last != 0,
@@ -2741,19 +4747,29 @@ extern "C" fn on_rsp_parked_order_insert_callback(
     }
 }
 
-extern "C" fn on_rsp_parked_order_action_callback(
+extern "C" fn on_rsp_qry_notice_callback(
     user_data: *mut c_void,
-    parked_order_action: *mut c_void,
+    notice: *mut c_void,
     rsp_info: *mut c_void,
     request_id: c_int,
     is_last: c_int,
 ) {
     unsafe {
         if let Some(api) = (user_data as *mut TraderApi).as_mut() {
+            api.journal_callback(
+                "OnRspQryNotice",
+                notice as *const NoticeField,
+                rsp_info,
+                request_id,
+                is_last != 0,
+            );
+            api.with_latency(|recorder, at| {
+                recorder.on_response(LatencyKind::QueryRoundTrip, request_id, is_last != 0, at)
+            });
             if let Some(ref mut handler) = api.handler {
-                let parsed_parked_order_action = if !parked_order_action.is_null() {
-                    let parked_action_ptr = parked_order_action as *const ParkedOrderActionField;
-                    Some((*parked_action_ptr).clone())
+                let parsed_notice = if !notice.is_null() {
+                    let notice_ptr = notice as *const NoticeField;
+                    Some((*notice_ptr).clone())
                 } else {
                     None
                 };
@@ -2765,32 +4781,35 @@ extern "C" fn on_rsp_parked_order_action_callback(
                     None
                 };
 
-                handler.on_rsp_parked_order_action(
-                    parsed_parked_order_action,
-                    parsed_rsp_info,
-                    request_id,
-                    is_last != 0,
-                );
+                handler.on_rsp_qry_notice(parsed_notice, parsed_rsp_info, request_id, is_last != 0);
             }
         }
     }
 }
 
-// 第三阶段回调函数实现
-
-extern "C" fn on_rsp_exec_order_insert_callback(
+extern "C" fn on_rsp_from_bank_to_future_by_future_callback(
     user_data: *mut c_void,
-    input_exec_order: *mut c_void,
+    req_transfer: *mut c_void,
     rsp_info: *mut c_void,
     request_id: c_int,
     is_last: c_int,
 ) {
     unsafe {
         if let Some(api) = (user_data as *mut TraderApi).as_mut() {
+            api.journal_callback(
+                "OnRspFromBankToFutureByFuture",
+                req_transfer as *const ReqTransferField,
+                rsp_info,
+                request_id,
+                is_last != 0,
+            );
+            api.with_latency(|recorder, at| {
+                recorder.on_response(LatencyKind::RequestRoundTrip, request_id, is_last != 0, at)
+            });
             if let Some(ref mut handler) = api.handler {
-                let parsed_input_exec_order = if !input_exec_order.is_null() {
-                    let exec_order_ptr = input_exec_order as *const InputExecOrderField;
-                    Some((*exec_order_ptr).clone())
+                let parsed_req_transfer = if !req_transfer.is_null() {
+                    let field_ptr = req_transfer as *const ReqTransferField;
+                    Some((*field_ptr).clone())
                 } else {
                     None
                 };
@@ -2802,8 +4821,8 @@ extern "C" fn on_rsp_exec_order_insert_callback(
                     None
                 };
 
-                handler.on_rsp_exec_order_insert(
-                    parsed_input_exec_order,
+                handler.on_rsp_from_bank_to_future_by_future(
+                    parsed_req_transfer,
                     parsed_rsp_info,
                     request_id,
                     is_last != 0,
@@ -2813,20 +4832,29 @@ extern "C" fn on_rsp_exec_order_insert_callback(
     }
 }
 
-extern "C" fn on_rsp_exec_order_action_callback(
+extern "C" fn on_rsp_from_future_to_bank_by_future_callback(
     user_data: *mut c_void,
-    input_exec_order_action: *mut c_void,
+    req_transfer: *mut c_void,
     rsp_info: *mut c_void,
     request_id: c_int,
     is_last: c_int,
 ) {
     unsafe {
         if let Some(api) = (user_data as *mut TraderApi).as_mut() {
+            api.journal_callback(
+                "OnRspFromFutureToBankByFuture",
+                req_transfer as *const ReqTransferField,
+                rsp_info,
+                request_id,
+                is_last != 0,
+            );
+            api.with_latency(|recorder, at| {
+                recorder.on_response(LatencyKind::RequestRoundTrip, request_id, is_last != 0, at)
+            });
             if let Some(ref mut handler) = api.handler {
-                let parsed_input_exec_order_action = if !input_exec_order_action.is_null() {
-                    let exec_action_ptr =
-                        input_exec_order_action as *const InputExecOrderActionField;
-                    Some((*exec_action_ptr).clone())
+                let parsed_req_transfer = if !req_transfer.is_null() {
+                    let field_ptr = req_transfer as *const ReqTransferField;
+                    Some((*field_ptr).clone())
                 } else {
                     None
                 };
@@ -2838,8 +4866,8 @@ extern "C" fn on_rsp_exec_order_action_callback(
                     None
                 };
 
-                handler.on_rsp_exec_order_action(
-                    parsed_input_exec_order_action,
+                handler.on_rsp_from_future_to_bank_by_future(
+                    parsed_req_transfer,
                     parsed_rsp_info,
                     request_id,
                     is_last != 0,
@@ -2849,19 +4877,29 @@ extern "C" fn on_rsp_exec_order_action_callback(
     }
 }
 
-extern "C" fn on_rsp_for_quote_insert_callback(
+extern "C" fn on_rsp_query_bank_account_money_by_future_callback(
     user_data: *mut c_void,
-    input_for_quote: *mut c_void,
+    req_query_account: *mut c_void,
     rsp_info: *mut c_void,
     request_id: c_int,
     is_last: c_int,
 ) {
     unsafe {
         if let Some(api) = (user_data as *mut TraderApi).as_mut() {
+            api.journal_callback(
+                "OnRspQueryBankAccountMoneyByFuture",
+                req_query_account as *const ReqQueryAccountField,
+                rsp_info,
+                request_id,
+                is_last != 0,
+            );
+            api.with_latency(|recorder, at| {
+                recorder.on_response(LatencyKind::RequestRoundTrip, request_id, is_last != 0, at)
+            });
             if let Some(ref mut handler) = api.handler {
-                let parsed_input_for_quote = if !input_for_quote.is_null() {
-                    let for_quote_ptr = input_for_quote as *const InputForQuoteField;
-                    Some((*for_quote_ptr).clone())
+                let parsed_req_query_account = if !req_query_account.is_null() {
+                    let field_ptr = req_query_account as *const ReqQueryAccountField;
+                    Some((*field_ptr).clone())
                 } else {
                     None
                 };
@@ -2873,8 +4911,8 @@ extern "C" fn on_rsp_for_quote_insert_callback(
                     None
                 };
 
-                handler.on_rsp_for_quote_insert(
-                    parsed_input_for_quote,
+                handler.on_rsp_query_bank_account_money_by_future(
+                    parsed_req_query_account,
                     parsed_rsp_info,
                     request_id,
                     is_last != 0,
@@ -2884,19 +4922,116 @@ extern "C" fn on_rsp_for_quote_insert_callback(
     }
 }
 
-extern "C" fn on_rsp_quote_insert_callback(
+extern "C" fn on_rtn_from_bank_to_future_by_future_callback(
     user_data: *mut c_void,
-    input_quote: *mut c_void,
+    rsp_transfer: *mut c_void,
+) {
+    unsafe {
+        if let Some(api) = (user_data as *mut TraderApi).as_mut() {
+            api.journal_callback(
+                "OnRtnFromBankToFutureByFuture",
+                rsp_transfer as *const RspTransferField,
+                ptr::null_mut(),
+                0,
+                true,
+            );
+            if let Some(ref mut handler) = api.handler {
+                if !rsp_transfer.is_null() {
+                    let field_ptr = rsp_transfer as *const RspTransferField;
+                    handler.on_rtn_from_bank_to_future_by_future((*field_ptr).clone());
+                }
+            }
+        }
+    }
+}
+
+extern "C" fn on_rtn_from_future_to_bank_by_future_callback(
+    user_data: *mut c_void,
+    rsp_transfer: *mut c_void,
+) {
+    unsafe {
+        if let Some(api) = (user_data as *mut TraderApi).as_mut() {
+            api.journal_callback(
+                "OnRtnFromFutureToBankByFuture",
+                rsp_transfer as *const RspTransferField,
+                ptr::null_mut(),
+                0,
+                true,
+            );
+            if let Some(ref mut handler) = api.handler {
+                if !rsp_transfer.is_null() {
+                    let field_ptr = rsp_transfer as *const RspTransferField;
+                    handler.on_rtn_from_future_to_bank_by_future((*field_ptr).clone());
+                }
+            }
+        }
+    }
+}
+
+extern "C" fn on_rtn_repeal_from_bank_to_future_by_future_callback(
+    user_data: *mut c_void,
+    rsp_repeal: *mut c_void,
+) {
+    unsafe {
+        if let Some(api) = (user_data as *mut TraderApi).as_mut() {
+            api.journal_callback(
+                "OnRtnRepealFromBankToFutureByFuture",
+                rsp_repeal as *const RspRepealField,
+                ptr::null_mut(),
+                0,
+                true,
+            );
+            if let Some(ref mut handler) = api.handler {
+                if !rsp_repeal.is_null() {
+                    let field_ptr = rsp_repeal as *const RspRepealField;
+                    handler.on_rtn_repeal_from_bank_to_future_by_future((*field_ptr).clone());
+                }
+            }
+        }
+    }
+}
+
+extern "C" fn on_rtn_repeal_from_future_to_bank_by_future_callback(
+    user_data: *mut c_void,
+    rsp_repeal: *mut c_void,
+) {
+    unsafe {
+        if let Some(api) = (user_data as *mut TraderApi).as_mut() {
+            api.journal_callback(
+                "OnRtnRepealFromFutureToBankByFuture",
+                rsp_repeal as *const RspRepealField,
+                ptr::null_mut(),
+                0,
+                true,
+            );
+            if let Some(ref mut handler) = api.handler {
+                if !rsp_repeal.is_null() {
+                    let field_ptr = rsp_repeal as *const RspRepealField;
+                    handler.on_rtn_repeal_from_future_to_bank_by_future((*field_ptr).clone());
+                }
+            }
+        }
+    }
+}
+
+extern "C" fn on_err_rtn_bank_to_future_by_future_callback(
+    user_data: *mut c_void,
+    req_transfer: *mut c_void,
     rsp_info: *mut c_void,
-    request_id: c_int,
-    is_last: c_int,
 ) {
     unsafe {
         if let Some(api) = (user_data as *mut TraderApi).as_mut() {
+            api.journal_callback(
+                "OnErrRtnBankToFutureByFuture",
+                req_transfer as *const ReqTransferField,
+                rsp_info,
+                0,
+                true,
+            );
             if let Some(ref mut handler) = api.handler {
-                let parsed_input_quote = if !input_quote.is_null() {
-                    let quote_ptr = input_quote as *const InputQuoteField;
-                    Some((*quote_ptr).clone())
+                let parsed_req_transfer = if !req_transfer.is_null() {
+                    let field_ptr = req_transfer as *const ReqTransferField;
+                    Some((*field_ptr).clone())
                 } else {
                     None
                 };
@@ -2908,30 +5043,30 @@ extern "C" fn on_rsp_quote_insert_callback(
                     None
                 };
 
-                handler.on_rsp_quote_insert(
-                    parsed_input_quote,
-                    parsed_rsp_info,
-                    request_id,
-                    is_last != 0,
-                );
+                handler.on_err_rtn_bank_to_future_by_future(parsed_req_transfer, parsed_rsp_info);
             }
         }
     }
 }
 
-extern "C" fn on_rsp_quote_action_callback(
+extern "C" fn on_err_rtn_future_to_bank_by_future_callback(
     user_data: *mut c_void,
-    input_quote_action: *mut c_void,
+    req_transfer: *mut c_void,
     rsp_info: *mut c_void,
-    request_id: c_int,
-    is_last: c_int,
 ) {
     unsafe {
         if let Some(api) = (user_data as *mut TraderApi).as_mut() {
+            api.journal_callback(
+                "OnErrRtnFutureToBankByFuture",
+                req_transfer as *const ReqTransferField,
+                rsp_info,
+                0,
+                true,
+            );
             if let Some(ref mut handler) = api.handler {
-                let parsed_input_quote_action = if !input_quote_action.is_null() {
-                    let quote_action_ptr = input_quote_action as *const InputQuoteActionField;
-                    Some((*quote_action_ptr).clone())
+                let parsed_req_transfer = if !req_transfer.is_null() {
+                    let field_ptr = req_transfer as *const ReqTransferField;
+                    Some((*field_ptr).clone())
                 } else {
                     None
                 };
@@ -2943,31 +5078,53 @@ extern "C" fn on_rsp_quote_action_callback(
                     None
                 };
 
-                handler.on_rsp_quote_action(
-                    parsed_input_quote_action,
-                    parsed_rsp_info,
-                    request_id,
-                    is_last != 0,
-                );
+                handler.on_err_rtn_future_to_bank_by_future(parsed_req_transfer, parsed_rsp_info);
             }
         }
     }
 }
 
-extern "C" fn on_rsp_batch_order_action_callback(
+extern "C" fn on_rtn_query_bank_balance_by_future_callback(
     user_data: *mut c_void,
-    input_batch_order_action: *mut c_void,
+    notify_query_account: *mut c_void,
+) {
+    unsafe {
+        if let Some(api) = (user_data as *mut TraderApi).as_mut() {
+            api.journal_callback(
+                "OnRtnQueryBankBalanceByFuture",
+                notify_query_account as *const NotifyQueryAccountField,
+                ptr::null_mut(),
+                0,
+                true,
+            );
+            if let Some(ref mut handler) = api.handler {
+                if !notify_query_account.is_null() {
+                    let field_ptr = notify_query_account as *const NotifyQueryAccountField;
+                    handler.on_rtn_query_bank_balance_by_future((*field_ptr).clone());
+                }
+            }
+        }
+    }
+}
+
+extern "C" fn on_err_rtn_query_bank_balance_by_future_callback(
+    user_data: *mut c_void,
+    req_query_account: *mut c_void,
     rsp_info: *mut c_void,
-    request_id: c_int,
-    is_last: c_int,
 ) {
     unsafe {
         if let Some(api) = (user_data as *mut TraderApi).as_mut() {
+            api.journal_callback(
+                "OnErrRtnQueryBankBalanceByFuture",
+                req_query_account as *const ReqQueryAccountField,
+                rsp_info,
+                0,
+                true,
+            );
             if let Some(ref mut handler) = api.handler {
-                let parsed_input_batch_order_action = if !input_batch_order_action.is_null() {
-                    let batch_action_ptr =
-                        input_batch_order_action as *const InputBatchOrderActionField;
-                    Some((*batch_action_ptr).clone())
+                let parsed_req_query_account = if !req_query_account.is_null() {
+                    let field_ptr = req_query_account as *const ReqQueryAccountField;
+                    Some((*field_ptr).clone())
                 } else {
                     None
                 };
@@ -2979,30 +5136,38 @@ extern "C" fn on_rsp_batch_order_action_callback(
                     None
                 };
 
-                handler.on_rsp_batch_order_action(
-                    parsed_input_batch_order_action,
+                handler.on_err_rtn_query_bank_balance_by_future(
+                    parsed_req_query_account,
                     parsed_rsp_info,
-                    request_id,
-                    is_last != 0,
                 );
             }
         }
     }
 }
 
-extern "C" fn on_rsp_remove_parked_order_callback(
+extern "C" fn on_rsp_qry_transfer_serial_callback(
     user_data: *mut c_void,
-    remove_parked_order: *mut c_void,
+    transfer_serial: *mut c_void,
     rsp_info: *mut c_void,
     request_id: c_int,
     is_last: c_int,
 ) {
     unsafe {
         if let Some(api) = (user_data as *mut TraderApi).as_mut() {
+            api.journal_callback(
+                "OnRspQryTransferSerial",
+                transfer_serial as *const TransferSerialField,
+                rsp_info,
+                request_id,
+                is_last != 0,
+            );
+            api.with_latency(|recorder, at| {
+                recorder.on_response(LatencyKind::QueryRoundTrip, request_id, is_last != 0, at)
+            });
             if let Some(ref mut handler) = api.handler {
-                let parsed_remove_parked_order = if !remove_parked_order.is_null() {
-                    let remove_ptr = remove_parked_order as *const RemoveParkedOrderField;
-                    Some((*remove_ptr).clone())
+                let parsed_transfer_serial = if !transfer_serial.is_null() {
+                    let field_ptr = transfer_serial as *const TransferSerialField;
+                    Some((*field_ptr).clone())
                 } else {
                     None
                 };
@@ -3014,8 +5179,8 @@ extern "C" fn on_rsp_remove_parked_order_callback(
                     None
                 };
 
-                handler.on_rsp_remove_parked_order(
-                    parsed_remove_parked_order,
+                handler.on_rsp_qry_transfer_serial(
+                    parsed_transfer_serial,
                     parsed_rsp_info,
                     request_id,
                     is_last != 0,
@@ -3025,20 +5190,29 @@ extern "C" fn on_rsp_remove_parked_order_callback(
     }
 }
 
-extern "C" fn on_rsp_remove_parked_order_action_callback(
+extern "C" fn on_rsp_qry_contract_bank_callback(
     user_data: *mut c_void,
-    remove_parked_order_action: *mut c_void,
+    contract_bank: *mut c_void,
     rsp_info: *mut c_void,
     request_id: c_int,
     is_last: c_int,
 ) {
     unsafe {
         if let Some(api) = (user_data as *mut TraderApi).as_mut() {
+            api.journal_callback(
+                "OnRspQryContractBank",
+                contract_bank as *const ContractBankField,
+                rsp_info,
+                request_id,
+                is_last != 0,
+            );
+            api.with_latency(|recorder, at| {
+                recorder.on_response(LatencyKind::QueryRoundTrip, request_id, is_last != 0, at)
+            });
             if let Some(ref mut handler) = api.handler {
-                let parsed_remove_parked_order_action = if !remove_parked_order_action.is_null() {
-                    let remove_action_ptr =
-                        remove_parked_order_action as *const RemoveParkedOrderActionField;
-                    Some((*remove_action_ptr).clone())
+                let parsed_contract_bank = if !contract_bank.is_null() {
+                    let field_ptr = contract_bank as *const ContractBankField;
+                    Some((*field_ptr).clone())
                 } else {
                     None
                 };
@@ -3050,8 +5224,8 @@ extern "C" fn on_rsp_remove_parked_order_action_callback(
                     None
                 };
 
-                handler.on_rsp_remove_parked_order_action(
-                    parsed_remove_parked_order_action,
+                handler.on_rsp_qry_contract_bank(
+                    parsed_contract_bank,
                     parsed_rsp_info,
                     request_id,
                     is_last != 0,
@@ -3061,19 +5235,29 @@ extern "C" fn on_rsp_remove_parked_order_action_callback(
     }
 }
 
-extern "C" fn on_rsp_qry_max_order_volume_callback(
+extern "C" fn on_rsp_qry_accountregister_callback(
     user_data: *mut c_void,
-    qry_max_order_volume: *mut c_void,
+    accountregister: *mut c_void,
     rsp_info: *mut c_void,
     request_id: c_int,
     is_last: c_int,
 ) {
     unsafe {
         if let Some(api) = (user_data as *mut TraderApi).as_mut() {
+            api.journal_callback(
+                "OnRspQryAccountregister",
+                accountregister as *const AccountregisterField,
+                rsp_info,
+                request_id,
+                is_last != 0,
+            );
+            api.with_latency(|recorder, at| {
+                recorder.on_response(LatencyKind::QueryRoundTrip, request_id, is_last != 0, at)
+            });
             if let Some(ref mut handler) = api.handler {
-                let parsed_qry_max_order_volume = if !qry_max_order_volume.is_null() {
-                    let max_volume_ptr = qry_max_order_volume as *const QryMaxOrderVolumeField;
-                    Some((*max_volume_ptr).clone())
+                let parsed_accountregister = if !accountregister.is_null() {
+                    let field_ptr = accountregister as *const AccountregisterField;
+                    Some((*field_ptr).clone())
                 } else {
                     None
                 };
@@ -3085,8 +5269,8 @@ extern "C" fn on_rsp_qry_max_order_volume_callback(
                     None
                 };
 
-                handler.on_rsp_qry_max_order_volume(
-                    parsed_qry_max_order_volume,
+                handler.on_rsp_qry_accountregister(
+                    parsed_accountregister,
                     parsed_rsp_info,
                     request_id,
                     is_last != 0,
@@ -3096,19 +5280,29 @@ extern "C" fn on_rsp_qry_max_order_volume_callback(
     }
 }
 
-extern "C" fn on_rsp_qry_depth_market_data_callback(
+extern "C" fn on_rsp_user_password_update_callback(
     user_data: *mut c_void,
-    depth_market_data: *mut c_void,
+    user_password_update: *mut c_void,
     rsp_info: *mut c_void,
     request_id: c_int,
     is_last: c_int,
 ) {
     unsafe {
         if let Some(api) = (user_data as *mut TraderApi).as_mut() {
+            api.journal_callback(
+                "OnRspUserPasswordUpdate",
+                user_password_update as *const UserPasswordUpdateField,
+                rsp_info,
+                request_id,
+                is_last != 0,
+            );
+            api.with_latency(|recorder, at| {
+                recorder.on_response(LatencyKind::RequestRoundTrip, request_id, is_last != 0, at)
+            });
             if let Some(ref mut handler) = api.handler {
-                let parsed_depth_market_data = if !depth_market_data.is_null() {
-                    let market_data_ptr = depth_market_data as *const DepthMarketDataField;
-                    Some((*market_data_ptr).clone())
+                let parsed_user_password_update = if !user_password_update.is_null() {
+                    let field_ptr = user_password_update as *const UserPasswordUpdateField;
+                    Some((*field_ptr).clone())
                 } else {
                     None
                 };
@@ -3120,8 +5314,8 @@ extern "C" fn on_rsp_qry_depth_market_data_callback(
                     None
                 };
 
-                handler.on_rsp_qry_depth_market_data(
-                    parsed_depth_market_data,
+                handler.on_rsp_user_password_update(
+                    parsed_user_password_update,
                     parsed_rsp_info,
                     request_id,
                     is_last != 0,
@@ -3131,22 +5325,34 @@ extern "C" fn on_rsp_qry_depth_market_data_callback(
     }
 }
 
-extern "C" fn on_rsp_qry_settlement_info_callback(
+extern "C" fn on_rsp_trading_account_password_update_callback(
     user_data: *mut c_void,
-    settlement_info: *mut c_void,
+    trading_account_password_update: *mut c_void,
     rsp_info: *mut c_void,
     request_id: c_int,
     is_last: c_int,
 ) {
     unsafe {
         if let Some(api) = (user_data as *mut TraderApi).as_mut() {
+            api.journal_callback(
+                "OnRspTradingAccountPasswordUpdate",
+                trading_account_password_update as *const TradingAccountPasswordUpdateField,
+                rsp_info,
+                request_id,
+                is_last != 0,
+            );
+            api.with_latency(|recorder, at| {
+                recorder.on_response(LatencyKind::RequestRoundTrip, request_id, is_last != 0, at)
+            });
             if let Some(ref mut handler) = api.handler {
-                let parsed_settlement_info = if !settlement_info.is_null() {
-                    let settlement_ptr = settlement_info as *const SettlementInfoField;
-                    Some((*settlement_ptr).clone())
-                } else {
-                    None
-                };
+                let parsed_trading_account_password_update =
+                    if !trading_account_password_update.is_null() {
+                        let field_ptr = trading_account_password_update
+                            as *const TradingAccountPasswordUpdateField;
+                        Some((*field_ptr).clone())
+                    } else {
+                        None
+                    };
 
                 let parsed_rsp_info = if !rsp_info.is_null() {
                     let info_ptr = rsp_info as *const RspInfoField;
@@ -3155,8 +5361,8 @@ extern "C" fn on_rsp_qry_settlement_info_callback(
                     None
                 };
 
-                handler.on_rsp_qry_settlement_info(
-                    parsed_settlement_info,
+                handler.on_rsp_trading_account_password_update(
+                    parsed_trading_account_password_update,
                     parsed_rsp_info,
                     request_id,
                     is_last != 0,
@@ -3166,19 +5372,29 @@ extern "C" fn on_rsp_qry_settlement_info_callback(
     }
 }
 
-extern "C" fn on_rsp_qry_transfer_bank_callback(
+extern "C" fn on_rsp_user_auth_method_callback(
     user_data: *mut c_void,
-    transfer_bank: *mut c_void,
+    rsp_user_auth_method: *mut c_void,
     rsp_info: *mut c_void,
     request_id: c_int,
     is_last: c_int,
 ) {
     unsafe {
         if let Some(api) = (user_data as *mut TraderApi).as_mut() {
+            api.journal_callback(
+                "OnRspUserAuthMethod",
+                rsp_user_auth_method as *const RspUserAuthMethodField,
+                rsp_info,
+                request_id,
+                is_last != 0,
+            );
+            api.with_latency(|recorder, at| {
+                recorder.on_response(LatencyKind::RequestRoundTrip, request_id, is_last != 0, at)
+            });
             if let Some(ref mut handler) = api.handler {
-                let parsed_transfer_bank = if !transfer_bank.is_null() {
-                    let bank_ptr = transfer_bank as *const TransferBankField;
-                    Some((*bank_ptr).clone())
+                let parsed_rsp_user_auth_method = if !rsp_user_auth_method.is_null() {
+                    let field_ptr = rsp_user_auth_method as *const RspUserAuthMethodField;
+                    Some((*field_ptr).clone())
                 } else {
                     None
                 };
@@ -3190,8 +5406,8 @@ extern "C" fn on_rsp_qry_transfer_bank_callback(
                     None
                 };
 
-                handler.on_rsp_qry_transfer_bank(
-                    parsed_transfer_bank,
+                handler.on_rsp_user_auth_method(
+                    parsed_rsp_user_auth_method,
                     parsed_rsp_info,
                     request_id,
                     is_last != 0,
@@ -3201,19 +5417,29 @@ extern "C" fn on_rsp_qry_transfer_bank_callback(
     }
 }
 
-extern "C" fn on_rsp_qry_investor_position_detail_callback(
+extern "C" fn on_rsp_gen_user_captcha_callback(
     user_data: *mut c_void,
-    investor_position_detail: *mut c_void,
+    rsp_gen_user_captcha: *mut c_void,
     rsp_info: *mut c_void,
     request_id: c_int,
     is_last: c_int,
 ) {
     unsafe {
         if let Some(api) = (user_data as *mut TraderApi).as_mut() {
+            api.journal_callback(
+                "OnRspGenUserCaptcha",
+                rsp_gen_user_captcha as *const RspGenUserCaptchaField,
+                rsp_info,
+                request_id,
+                is_last != 0,
+            );
+            api.with_latency(|recorder, at| {
+                recorder.on_response(LatencyKind::RequestRoundTrip, request_id, is_last != 0, at)
+            });
             if let Some(ref mut handler) = api.handler {
-                let parsed_investor_position_detail = if !investor_position_detail.is_null() {
-                    let detail_ptr = investor_position_detail as *const InvestorPositionDetailField;
-                    Some((*detail_ptr).clone())
+                let parsed_rsp_gen_user_captcha = if !rsp_gen_user_captcha.is_null() {
+                    let field_ptr = rsp_gen_user_captcha as *const RspGenUserCaptchaField;
+                    Some((*field_ptr).clone())
                 } else {
                     None
                 };
@@ -3225,8 +5451,8 @@ extern "C" fn on_rsp_qry_investor_position_detail_callback(
                     None
                 };
 
-                handler.on_rsp_qry_investor_position_detail(
-                    parsed_investor_position_detail,
+                handler.on_rsp_gen_user_captcha(
+                    parsed_rsp_gen_user_captcha,
                     parsed_rsp_info,
                     request_id,
                     is_last != 0,
@@ -3236,19 +5462,29 @@ extern "C" fn on_rsp_qry_investor_position_detail_callback(
     }
 }
 
-extern "C" fn on_rsp_qry_notice_callback(
+extern "C" fn on_rsp_gen_user_text_callback(
     user_data: *mut c_void,
-    notice: *mut c_void,
+    rsp_gen_user_text: *mut c_void,
     rsp_info: *mut c_void,
     request_id: c_int,
     is_last: c_int,
 ) {
     unsafe {
         if let Some(api) = (user_data as *mut TraderApi).as_mut() {
+            api.journal_callback(
+                "OnRspGenUserText",
+                rsp_gen_user_text as *const RspGenUserTextField,
+                rsp_info,
+                request_id,
+                is_last != 0,
+            );
+            api.with_latency(|recorder, at| {
+                recorder.on_response(LatencyKind::RequestRoundTrip, request_id, is_last != 0, at)
+            });
             if let Some(ref mut handler) = api.handler {
-                let parsed_notice = if !notice.is_null() {
-                    let notice_ptr = notice as *const NoticeField;
-                    Some((*notice_ptr).clone())
+                let parsed_rsp_gen_user_text = if !rsp_gen_user_text.is_null() {
+                    let field_ptr = rsp_gen_user_text as *const RspGenUserTextField;
+                    Some((*field_ptr).clone())
                 } else {
                     None
                 };
@@ -3260,7 +5496,12 @@ extern "C" fn on_rsp_qry_notice_callback(
                     None
                 };
 
-                handler.on_rsp_qry_notice(parsed_notice, parsed_rsp_info, request_id, is_last != 0);
+                handler.on_rsp_gen_user_text(
+                    parsed_rsp_gen_user_text,
+                    parsed_rsp_info,
+                    request_id,
+                    is_last != 0,
+                );
             }
         }
     }
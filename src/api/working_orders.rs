@@ -0,0 +1,502 @@
+//! 挂单簿与批量撤单
+//!
+//! [`WorkingOrders`]记录账户当前仍在队列中的报单：由`req_qry_order`的查询结果初始化，
+//! 之后随报单回报更新，包括同一账户其他会话发出的报单。查询期间收到的报单回报会与
+//! 查询结果合并，保留较新的状态。
+//! [`AsyncTraderApi::cancel_orders`](crate::api::AsyncTraderApi::cancel_orders)按
+//! [`CancelFilter`]选出挂单，按前置的FTD流控逐笔发出撤单，并在[`MassCancelReport`]中
+//! 报告每笔撤单的结果。
+
+use crate::api::order_handle::{field_string, order_key, sys_key, OrderKey, ACTION_FLAG_DELETE};
+use crate::api::trader_api::OrderField;
+use crate::error::{CtpError, CtpResult};
+use crate::models::{Direction, OrderStatus, OrderSubmitStatus};
+use crate::types::{
+    InputOrderActionField, InstrumentIdType, RspInfoField, StringConvert, UserIdType,
+};
+use std::collections::HashMap;
+use std::sync::{Mutex, MutexGuard};
+use std::time::Duration;
+use tokio::sync::Notify;
+use tokio::time::Instant;
+
+/// 挂单筛选条件，各条件同时满足才撤单，未设置任何条件时匹配全部挂单
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CancelFilter {
+    instrument_id: Option<String>,
+    exchange_id: Option<String>,
+    direction: Option<Direction>,
+    strategy: Option<String>,
+}
+
+impl CancelFilter {
+    /// 匹配全部挂单
+    pub fn all() -> Self {
+        Self::default()
+    }
+
+    /// 只撤指定合约
+    pub fn with_instrument(mut self, instrument_id: &str) -> Self {
+        self.instrument_id = Some(instrument_id.trim().to_string());
+        self
+    }
+
+    /// 只撤指定交易所
+    pub fn with_exchange(mut self, exchange_id: &str) -> Self {
+        self.exchange_id = Some(exchange_id.trim().to_string());
+        self
+    }
+
+    /// 只撤指定买卖方向
+    pub fn with_direction(mut self, direction: Direction) -> Self {
+        self.direction = Some(direction);
+        self
+    }
+
+    /// 只撤指定策略的报单，策略按`OrderRefAllocator::next_ref_for`分配时记下的引用识别，只适用于本会话的报单
+    pub fn with_strategy(mut self, strategy: &str) -> Self {
+        self.strategy = Some(strategy.to_string());
+        self
+    }
+
+    /// 报单是否满足条件，`strategy`为报单所属的策略
+    pub fn matches(&self, order: &OrderField, strategy: Option<&str>) -> bool {
+        self.instrument_id
+            .as_deref()
+            .is_none_or(|id| field_string(&order.instrument_id) == id)
+            && self
+                .exchange_id
+                .as_deref()
+                .is_none_or(|id| field_string(&order.exchange_id) == id)
+            && self
+                .direction
+                .is_none_or(|direction| Direction::from(order.direction) == direction)
+            && self
+                .strategy
+                .as_deref()
+                .is_none_or(|tag| strategy == Some(tag))
+    }
+
+    /// 是否按策略筛选
+    pub fn strategy(&self) -> Option<&str> {
+        self.strategy.as_deref()
+    }
+}
+
+/// 单笔撤单的结果
+#[derive(Debug, Clone)]
+pub enum CancelOutcome {
+    /// 已撤单
+    Cancelled,
+    /// 撤单生效前报单已全部成交或已结束
+    AlreadyFinished,
+    /// 撤单被拒绝
+    Rejected { error_id: i32, error_msg: String },
+    /// 撤单请求未能发出
+    SendFailed(CtpError),
+    /// 超时前未收到结果
+    Pending,
+}
+
+impl CancelOutcome {
+    /// 撤单后报单是否已不在队列中
+    pub fn is_success(&self) -> bool {
+        matches!(self, Self::Cancelled | Self::AlreadyFinished)
+    }
+}
+
+/// 单笔撤单
+#[derive(Debug, Clone)]
+pub struct CancelResult {
+    /// 撤单时的报单
+    pub order: OrderField,
+    /// 撤单请求编号，未发出时为`None`
+    pub request_id: Option<i32>,
+    /// 结果
+    pub outcome: CancelOutcome,
+}
+
+/// 批量撤单报告
+#[derive(Debug, Clone, Default)]
+pub struct MassCancelReport {
+    /// 每笔撤单的结果，按发出顺序排列
+    pub results: Vec<CancelResult>,
+}
+
+impl MassCancelReport {
+    /// 成功撤掉（或已结束）的报单
+    pub fn succeeded(&self) -> impl Iterator<Item = &CancelResult> {
+        self.results.iter().filter(|r| r.outcome.is_success())
+    }
+
+    /// 撤单失败或未确认的报单
+    pub fn failed(&self) -> impl Iterator<Item = &CancelResult> {
+        self.results.iter().filter(|r| !r.outcome.is_success())
+    }
+
+    /// 是否全部成功
+    pub fn is_complete(&self) -> bool {
+        self.results.iter().all(|r| r.outcome.is_success())
+    }
+}
+
+#[derive(Default)]
+struct WorkingState {
+    orders: HashMap<OrderKey, OrderField>,
+    cancels: HashMap<OrderKey, CancelOutcome>,
+    // 进行中的查询数量，大于0时记录收到的报单回报
+    syncing: usize,
+    // 查询期间收到的报单回报，包括已结束的报单
+    updates: HashMap<OrderKey, OrderField>,
+}
+
+/// 账户当前的挂单
+#[derive(Default)]
+pub struct WorkingOrders {
+    state: Mutex<WorkingState>,
+    changed: Notify,
+}
+
+// 报单是否仍在队列中，尚未确认的报单也可以撤
+fn is_working(order: &OrderField) -> bool {
+    OrderSubmitStatus::from(order.order_submit_status) != OrderSubmitStatus::InsertRejected
+        && !OrderStatus::from(order.order_status).is_finished()
+}
+
+// 报单状态只会向前推进：已结束、成交量、交易所已接受依次比较，越大越新
+fn progress(order: &OrderField) -> (bool, i32, bool) {
+    (
+        !is_working(order),
+        order.volume_traded,
+        !field_string(&order.order_sys_id).is_empty(),
+    )
+}
+
+/// 进行中的挂单查询，期间收到的报单回报会与查询结果合并
+///
+/// 未调用[`apply`](Self::apply)就丢弃时（如查询失败）保持挂单簿不变
+pub(crate) struct OrderSync<'a> {
+    book: &'a WorkingOrders,
+}
+
+impl OrderSync<'_> {
+    /// 用查询到的报单重建挂单簿，查询期间收到的回报不比查询结果旧时以回报为准
+    pub(crate) fn apply(self, orders: impl IntoIterator<Item = OrderField>) {
+        let mut state = self.book.lock();
+        let mut merged: HashMap<OrderKey, OrderField> = orders
+            .into_iter()
+            .map(|order| {
+                (
+                    order_key(order.front_id, order.session_id, &order.order_ref),
+                    order,
+                )
+            })
+            .collect();
+        for (key, update) in &state.updates {
+            match merged.get(key) {
+                Some(order) if progress(order) > progress(update) => {}
+                _ => {
+                    merged.insert(key.clone(), update.clone());
+                }
+            }
+        }
+        merged.retain(|_, order| is_working(order));
+        state.orders = merged;
+    }
+}
+
+impl Drop for OrderSync<'_> {
+    fn drop(&mut self) {
+        let mut state = self.book.lock();
+        state.syncing -= 1;
+        if state.syncing == 0 {
+            state.updates.clear();
+        }
+    }
+}
+
+impl WorkingOrders {
+    fn lock(&self) -> MutexGuard<'_, WorkingState> {
+        self.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// 发出挂单查询前调用，开始记录报单回报
+    pub(crate) fn begin_sync(&self) -> OrderSync<'_> {
+        self.lock().syncing += 1;
+        OrderSync { book: self }
+    }
+
+    /// 报单回报
+    pub(crate) fn on_order(&self, order: &OrderField) {
+        let key = order_key(order.front_id, order.session_id, &order.order_ref);
+        let mut state = self.lock();
+        if state.syncing > 0 {
+            state.updates.insert(key.clone(), order.clone());
+        }
+        if is_working(order) {
+            state.orders.insert(key, order.clone());
+            return;
+        }
+        state.orders.remove(&key);
+        if let Some(outcome) = state.cancels.get_mut(&key) {
+            *outcome = if OrderStatus::from(order.order_status) == OrderStatus::Canceled {
+                CancelOutcome::Cancelled
+            } else {
+                CancelOutcome::AlreadyFinished
+            };
+            self.changed.notify_waiters();
+        }
+    }
+
+    /// 撤单被拒绝（`OnRspOrderAction`或`OnErrRtnOrderAction`）
+    pub(crate) fn on_action_rejected(
+        &self,
+        front_id: i32,
+        session_id: i32,
+        order_ref: &impl StringConvert,
+        exchange_id: &impl StringConvert,
+        order_sys_id: &impl StringConvert,
+        rsp_info: Option<&RspInfoField>,
+    ) {
+        let Some(rsp) = rsp_info.filter(|rsp| !rsp.is_success()) else {
+            return;
+        };
+        let mut state = self.lock();
+        let mut key = order_key(front_id, session_id, order_ref);
+        if !state.cancels.contains_key(&key) {
+            // 按报单编号撤单时回报中可能没有报单引用
+            let Some(found) = sys_key(exchange_id, order_sys_id).and_then(|sys| {
+                state.orders.iter().find_map(|(key, order)| {
+                    (sys_key(&order.exchange_id, &order.order_sys_id).as_ref() == Some(&sys))
+                        .then(|| key.clone())
+                })
+            }) else {
+                return;
+            };
+            key = found;
+        }
+        if let Some(outcome) = state.cancels.get_mut(&key) {
+            if matches!(outcome, CancelOutcome::Pending) {
+                *outcome = CancelOutcome::Rejected {
+                    error_id: rsp.error_id,
+                    error_msg: rsp.get_error_msg().unwrap_or_default(),
+                };
+                self.changed.notify_waiters();
+            }
+        }
+    }
+
+    /// 当前挂单
+    pub fn orders(&self) -> Vec<OrderField> {
+        self.lock().orders.values().cloned().collect()
+    }
+
+    /// 挂单数量
+    pub fn len(&self) -> usize {
+        self.lock().orders.len()
+    }
+
+    /// 是否没有挂单
+    pub fn is_empty(&self) -> bool {
+        self.lock().orders.is_empty()
+    }
+
+    /// 开始等待某笔报单的撤单结果
+    pub(crate) fn begin_cancel(&self, order: &OrderField) {
+        let key = order_key(order.front_id, order.session_id, &order.order_ref);
+        self.lock().cancels.insert(key, CancelOutcome::Pending);
+    }
+
+    /// 等待已发出撤单的结果直到全部确认或超时，之后停止跟踪这些报单
+    pub(crate) async fn wait_cancels(&self, results: &mut [CancelResult], deadline: Instant) {
+        let keys: Vec<OrderKey> = results
+            .iter()
+            .map(|r| order_key(r.order.front_id, r.order.session_id, &r.order.order_ref))
+            .collect();
+        loop {
+            let changed = self.changed.notified();
+            let pending = {
+                let state = self.lock();
+                results.iter().zip(&keys).any(|(result, key)| {
+                    result.request_id.is_some()
+                        && matches!(state.cancels.get(key), Some(CancelOutcome::Pending))
+                })
+            };
+            if !pending || tokio::time::timeout_at(deadline, changed).await.is_err() {
+                break;
+            }
+        }
+
+        let mut state = self.lock();
+        for (result, key) in results.iter_mut().zip(keys) {
+            let outcome = state.cancels.remove(&key);
+            if let (Some(_), Some(outcome)) = (result.request_id, outcome) {
+                result.outcome = outcome;
+            }
+        }
+    }
+}
+
+/// 按报单的前置、会话和引用以及交易所报单编号撤单
+pub(crate) fn cancel_request(
+    order: &OrderField,
+    user_id: &str,
+) -> CtpResult<InputOrderActionField> {
+    let mut req = InputOrderActionField::new(
+        &field_string(&order.broker_id),
+        &field_string(&order.investor_id),
+        ACTION_FLAG_DELETE,
+        &field_string(&order.order_ref),
+        order.front_id,
+        order.session_id,
+        &field_string(&order.exchange_id),
+        &field_string(&order.order_sys_id),
+    )?;
+    req.user_id = UserIdType::from_utf8_string(user_id)?;
+    req.instrument_id = InstrumentIdType::from_utf8_string(&field_string(&order.instrument_id))?;
+    Ok(req)
+}
+
+/// 相邻两笔撤单的最小间隔，`per_second`为0时使用CTP默认的每秒6笔
+pub(crate) fn pacing_interval(per_second: i32) -> Duration {
+    let per_second = if per_second > 0 { per_second } else { 6 };
+    Duration::from_secs(1) / per_second as u32
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn order(order_ref: &str, instrument_id: &str, direction: u8, status: u8) -> OrderField {
+        OrderField {
+            front_id: 1,
+            session_id: 2,
+            order_ref: <[u8; 13]>::from_utf8_string(order_ref).unwrap(),
+            instrument_id: <[u8; 31]>::from_utf8_string(instrument_id).unwrap(),
+            exchange_id: <[u8; 9]>::from_utf8_string("SHFE").unwrap(),
+            direction,
+            order_status: status,
+            order_submit_status: b'3',
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_filter_and_book() {
+        let book = WorkingOrders::default();
+        book.begin_sync().apply([
+            order("1", "rb2601", b'0', b'3'),
+            order("2", "rb2601", b'1', b'1'),
+            order("3", "hc2601", b'0', b'3'),
+            order("4", "rb2601", b'0', b'0'),
+        ]);
+        assert_eq!(book.len(), 3);
+
+        let filter = CancelFilter::all()
+            .with_instrument("rb2601")
+            .with_direction(Direction::Buy);
+        let matched: Vec<_> = book
+            .orders()
+            .into_iter()
+            .filter(|o| filter.matches(o, None))
+            .collect();
+        assert_eq!(matched.len(), 1);
+        assert_eq!(field_string(&matched[0].order_ref), "1");
+
+        let tagged = CancelFilter::all().with_strategy("grid");
+        assert!(tagged.matches(&matched[0], Some("grid")));
+        assert!(!tagged.matches(&matched[0], None));
+
+        book.on_order(&order("2", "rb2601", b'1', b'5'));
+        assert_eq!(book.len(), 2);
+    }
+
+    #[test]
+    fn test_sync_merges_updates() {
+        let book = WorkingOrders::default();
+        book.on_order(&order("9", "rb2601", b'0', b'3'));
+
+        let sync = book.begin_sync();
+        // 查询期间：1已撤单，2部分成交，3新报入
+        book.on_order(&order("1", "rb2601", b'0', b'5'));
+        book.on_order(&OrderField {
+            volume_traded: 1,
+            ..order("2", "rb2601", b'0', b'1')
+        });
+        book.on_order(&order("3", "rb2601", b'0', b'3'));
+        // 查询结果比回报旧，且只包含查询发出时的1、2、4
+        sync.apply([
+            order("1", "rb2601", b'0', b'3'),
+            order("2", "rb2601", b'0', b'3'),
+            order("4", "rb2601", b'0', b'3'),
+        ]);
+
+        let mut refs: Vec<_> = book
+            .orders()
+            .iter()
+            .map(|o| field_string(&o.order_ref))
+            .collect();
+        refs.sort();
+        assert_eq!(refs, ["2", "3", "4"]);
+        let partial = book
+            .orders()
+            .into_iter()
+            .find(|o| field_string(&o.order_ref) == "2")
+            .unwrap();
+        assert_eq!(partial.volume_traded, 1);
+
+        // 查询失败时不改变挂单簿，也不再记录回报
+        drop(book.begin_sync());
+        assert_eq!(book.len(), 3);
+        assert!(book.lock().updates.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_cancel_results() {
+        let book = WorkingOrders::default();
+        let orders = [
+            order("1", "rb2601", b'0', b'3'),
+            order("2", "rb2601", b'0', b'3'),
+            order("3", "rb2601", b'0', b'3'),
+        ];
+        book.begin_sync().apply(orders.clone());
+        let mut results: Vec<_> = orders
+            .iter()
+            .map(|order| {
+                book.begin_cancel(order);
+                CancelResult {
+                    order: order.clone(),
+                    request_id: Some(1),
+                    outcome: CancelOutcome::Pending,
+                }
+            })
+            .collect();
+
+        book.on_order(&order("1", "rb2601", b'0', b'5'));
+        let rsp = RspInfoField {
+            error_id: 26,
+            ..Default::default()
+        };
+        book.on_action_rejected(
+            1,
+            2,
+            &orders[1].order_ref,
+            &[0u8; 9],
+            &[0u8; 21],
+            Some(&rsp),
+        );
+
+        let deadline = Instant::now() + Duration::from_millis(50);
+        book.wait_cancels(&mut results, deadline).await;
+        assert!(matches!(results[0].outcome, CancelOutcome::Cancelled));
+        assert!(matches!(
+            results[1].outcome,
+            CancelOutcome::Rejected { error_id: 26, .. }
+        ));
+        assert!(matches!(results[2].outcome, CancelOutcome::Pending));
+        let report = MassCancelReport { results };
+        assert_eq!(report.succeeded().count(), 1);
+        assert!(!report.is_complete());
+        assert_eq!(pacing_interval(0), Duration::from_secs(1) / 6);
+    }
+}
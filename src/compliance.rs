@@ -0,0 +1,771 @@
+//! 交易所合规计数模块
+//!
+//! 按交易日统计每个合约、品种、交易所的报单与撤单次数，
+//! 在达到软限制时告警、达到硬限制时拦截，并阻止与自身挂单对价成交（自成交）的报单。
+//! 计数可持久化到文件，进程重启后在同一交易日内继续累计。
+
+use crate::api::trader_api::{InputOrderField, OrderField};
+use crate::encoding::GbkConverter;
+use crate::error::{CtpError, CtpResult};
+use crate::types::InputOrderActionField;
+use std::collections::HashMap;
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+use tracing::{debug, warn};
+
+/// 计数维度
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum CounterScope {
+    /// 合约
+    Instrument,
+    /// 品种
+    Product,
+    /// 交易所
+    Exchange,
+}
+
+impl CounterScope {
+    fn tag(&self) -> &'static str {
+        match self {
+            CounterScope::Instrument => "I",
+            CounterScope::Product => "P",
+            CounterScope::Exchange => "E",
+        }
+    }
+
+    fn from_tag(tag: &str) -> Option<Self> {
+        match tag {
+            "I" => Some(CounterScope::Instrument),
+            "P" => Some(CounterScope::Product),
+            "E" => Some(CounterScope::Exchange),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for CounterScope {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CounterScope::Instrument => write!(f, "合约"),
+            CounterScope::Product => write!(f, "品种"),
+            CounterScope::Exchange => write!(f, "交易所"),
+        }
+    }
+}
+
+/// 计数动作
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CounterAction {
+    /// 报单
+    OrderInsert,
+    /// 撤单
+    OrderCancel,
+}
+
+impl fmt::Display for CounterAction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CounterAction::OrderInsert => write!(f, "报单"),
+            CounterAction::OrderCancel => write!(f, "撤单"),
+        }
+    }
+}
+
+/// 单项限制，`None`表示不限制
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Limit {
+    /// 软限制，达到后告警
+    pub soft: Option<u32>,
+    /// 硬限制，达到后拦截
+    pub hard: Option<u32>,
+}
+
+impl Limit {
+    /// 创建限制
+    pub fn new(soft: Option<u32>, hard: Option<u32>) -> Self {
+        Self { soft, hard }
+    }
+
+    /// 不限制
+    pub fn unlimited() -> Self {
+        Self::default()
+    }
+}
+
+/// 某一维度的报单、撤单限制
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ScopeLimits {
+    /// 报单次数限制
+    pub orders: Limit,
+    /// 撤单次数限制
+    pub cancels: Limit,
+}
+
+impl ScopeLimits {
+    /// 创建限制
+    pub fn new(orders: Limit, cancels: Limit) -> Self {
+        Self { orders, cancels }
+    }
+
+    fn for_action(&self, action: CounterAction) -> Limit {
+        match action {
+            CounterAction::OrderInsert => self.orders,
+            CounterAction::OrderCancel => self.cancels,
+        }
+    }
+}
+
+/// 合规配置
+#[derive(Debug, Clone)]
+pub struct ComplianceConfig {
+    /// 每个合约的默认限制
+    pub instrument_limits: ScopeLimits,
+    /// 每个品种的默认限制
+    pub product_limits: ScopeLimits,
+    /// 每个交易所的默认限制
+    pub exchange_limits: ScopeLimits,
+    /// 按品种代码覆盖的限制
+    pub product_overrides: HashMap<String, ScopeLimits>,
+    /// 按交易所代码覆盖的限制
+    pub exchange_overrides: HashMap<String, ScopeLimits>,
+    /// 是否启用自成交防范
+    pub self_trade_prevention: bool,
+    /// 计数持久化文件，每次计数变化都会写入
+    pub state_file: Option<PathBuf>,
+}
+
+impl Default for ComplianceConfig {
+    fn default() -> Self {
+        Self {
+            instrument_limits: ScopeLimits::default(),
+            product_limits: ScopeLimits::default(),
+            exchange_limits: ScopeLimits::default(),
+            product_overrides: HashMap::new(),
+            exchange_overrides: HashMap::new(),
+            self_trade_prevention: true,
+            state_file: None,
+        }
+    }
+}
+
+impl ComplianceConfig {
+    /// 创建默认配置（不限制次数，启用自成交防范）
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 设置每个合约的限制
+    pub fn with_instrument_limits(mut self, limits: ScopeLimits) -> Self {
+        self.instrument_limits = limits;
+        self
+    }
+
+    /// 设置每个品种的限制
+    pub fn with_product_limits(mut self, limits: ScopeLimits) -> Self {
+        self.product_limits = limits;
+        self
+    }
+
+    /// 设置每个交易所的限制
+    pub fn with_exchange_limits(mut self, limits: ScopeLimits) -> Self {
+        self.exchange_limits = limits;
+        self
+    }
+
+    /// 为指定品种设置单独的限制，如中金所股指期货的撤单限制
+    pub fn with_product_override(mut self, product_id: &str, limits: ScopeLimits) -> Self {
+        self.product_overrides
+            .insert(product_id.to_string(), limits);
+        self
+    }
+
+    /// 为指定交易所设置单独的限制
+    pub fn with_exchange_override(mut self, exchange_id: &str, limits: ScopeLimits) -> Self {
+        self.exchange_overrides
+            .insert(exchange_id.to_string(), limits);
+        self
+    }
+
+    /// 设置是否启用自成交防范
+    pub fn with_self_trade_prevention(mut self, enabled: bool) -> Self {
+        self.self_trade_prevention = enabled;
+        self
+    }
+
+    /// 设置计数持久化文件
+    pub fn with_state_file<P: AsRef<Path>>(mut self, path: P) -> Self {
+        self.state_file = Some(path.as_ref().to_path_buf());
+        self
+    }
+
+    fn limits_for(&self, scope: CounterScope, key: &str) -> ScopeLimits {
+        match scope {
+            CounterScope::Instrument => self.instrument_limits,
+            CounterScope::Product => self
+                .product_overrides
+                .get(key)
+                .copied()
+                .unwrap_or(self.product_limits),
+            CounterScope::Exchange => self
+                .exchange_overrides
+                .get(key)
+                .copied()
+                .unwrap_or(self.exchange_limits),
+        }
+    }
+}
+
+/// 软限制告警
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ComplianceWarning {
+    pub scope: CounterScope,
+    pub key: String,
+    pub action: CounterAction,
+    /// 本次操作后的次数
+    pub count: u32,
+    pub soft_limit: u32,
+}
+
+impl fmt::Display for ComplianceWarning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} {} 的{}次数 {} 已达到软限制 {}",
+            self.scope, self.key, self.action, self.count, self.soft_limit
+        )
+    }
+}
+
+/// 报单、撤单次数
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Counts {
+    pub orders: u32,
+    pub cancels: u32,
+}
+
+impl Counts {
+    fn get(&self, action: CounterAction) -> u32 {
+        match action {
+            CounterAction::OrderInsert => self.orders,
+            CounterAction::OrderCancel => self.cancels,
+        }
+    }
+
+    fn incr(&mut self, action: CounterAction) {
+        match action {
+            CounterAction::OrderInsert => self.orders += 1,
+            CounterAction::OrderCancel => self.cancels += 1,
+        }
+    }
+
+    fn decr(&mut self, action: CounterAction) {
+        match action {
+            CounterAction::OrderInsert => self.orders = self.orders.saturating_sub(1),
+            CounterAction::OrderCancel => self.cancels = self.cancels.saturating_sub(1),
+        }
+    }
+}
+
+// 自身挂单
+#[derive(Debug, Clone)]
+struct RestingOrder {
+    instrument_id: String,
+    exchange_id: String,
+    direction: u8,
+    price: f64,
+    order_sys_id: String,
+}
+
+/// 合规监控器
+///
+/// 由`TraderApi::set_compliance_monitor`挂接后，`req_order_insert`和`req_order_action`
+/// 会在发出请求前自动检查并计数，报单回报用于维护自身挂单。
+pub struct ComplianceMonitor {
+    config: ComplianceConfig,
+    trading_day: String,
+    counters: HashMap<(CounterScope, String), Counts>,
+    // 报单引用 -> 挂单
+    resting: HashMap<String, RestingOrder>,
+    // 上次写入持久化文件失败，还有未保存的计数
+    dirty: bool,
+}
+
+impl ComplianceMonitor {
+    /// 创建监控器，若配置了持久化文件则从中恢复计数
+    pub fn new(config: ComplianceConfig) -> CtpResult<Self> {
+        let mut monitor = Self {
+            config,
+            trading_day: String::new(),
+            counters: HashMap::new(),
+            resting: HashMap::new(),
+            dirty: false,
+        };
+
+        if let Some(path) = monitor.config.state_file.clone() {
+            if path.exists() {
+                monitor.load(&path)?;
+            }
+        }
+
+        Ok(monitor)
+    }
+
+    /// 当前交易日
+    pub fn trading_day(&self) -> &str {
+        &self.trading_day
+    }
+
+    /// 设置交易日，交易日变化时清零计数和挂单
+    pub fn set_trading_day(&mut self, trading_day: &str) -> CtpResult<()> {
+        let trading_day = trading_day.trim();
+        if trading_day.is_empty() || trading_day == self.trading_day {
+            return Ok(());
+        }
+
+        if !self.trading_day.is_empty() {
+            debug!(
+                "交易日切换 {} -> {}，清零合规计数",
+                self.trading_day, trading_day
+            );
+            self.counters.clear();
+            self.resting.clear();
+        }
+        self.trading_day = trading_day.to_string();
+        self.flush()
+    }
+
+    /// 查询某一维度的计数
+    pub fn counts(&self, scope: CounterScope, key: &str) -> Counts {
+        self.counters
+            .get(&(scope, key.to_string()))
+            .copied()
+            .unwrap_or_default()
+    }
+
+    /// 检查报单是否允许发送，不修改计数
+    pub fn check_order_insert(&self, req: &InputOrderField) -> CtpResult<Vec<ComplianceWarning>> {
+        let instrument_id = field_str(&req.instrument_id);
+        let exchange_id = field_str(&req.exchange_id);
+
+        if self.config.self_trade_prevention {
+            self.check_self_trade(&instrument_id, req)?;
+        }
+
+        self.check_limits(&instrument_id, &exchange_id, CounterAction::OrderInsert)
+    }
+
+    /// 发送报单前登记计数和挂单
+    ///
+    /// 回调线程可能在发送返回前就收到报单的终态回报，必须在发送前登记，否则迟到的登记会留下
+    /// 一笔永远不会移除的挂单；发送失败时调用[`on_order_insert_failed`](Self::on_order_insert_failed)
+    /// 撤销登记。持久化失败只记录日志
+    pub fn record_order_insert(&mut self, req: &InputOrderField) {
+        let instrument_id = field_str(&req.instrument_id);
+        let exchange_id = field_str(&req.exchange_id);
+        let order_ref = field_str(&req.order_ref);
+
+        self.increment(&instrument_id, &exchange_id, CounterAction::OrderInsert);
+
+        // 回报到达前先按已挂单处理，避免连续报单在回报间隙内形成自成交
+        if !order_ref.is_empty() {
+            self.resting.insert(
+                order_ref,
+                RestingOrder {
+                    instrument_id,
+                    exchange_id,
+                    direction: req.direction,
+                    price: req.limit_price,
+                    order_sys_id: String::new(),
+                },
+            );
+        }
+
+        self.persist_changes();
+    }
+
+    /// 报单未能发出，撤销[`record_order_insert`](Self::record_order_insert)的登记
+    pub fn on_order_insert_failed(&mut self, req: &InputOrderField) {
+        let instrument_id = field_str(&req.instrument_id);
+        let exchange_id = field_str(&req.exchange_id);
+        for key in Self::scope_keys(&instrument_id, &exchange_id) {
+            if let Some(counts) = self.counters.get_mut(&key) {
+                counts.decr(CounterAction::OrderInsert);
+            }
+        }
+        self.resting.remove(&field_str(&req.order_ref));
+        self.persist_changes();
+    }
+
+    /// 检查撤单是否允许发送，不修改计数
+    pub fn check_order_action(
+        &self,
+        req: &InputOrderActionField,
+    ) -> CtpResult<Vec<ComplianceWarning>> {
+        let (instrument_id, exchange_id) = self.resolve_action_target(req);
+        self.check_limits(&instrument_id, &exchange_id, CounterAction::OrderCancel)
+    }
+
+    /// 记录已成功发送的撤单
+    ///
+    /// 请求已经发出，持久化失败只记录日志
+    pub fn record_order_action(&mut self, req: &InputOrderActionField) {
+        let (instrument_id, exchange_id) = self.resolve_action_target(req);
+        self.increment(&instrument_id, &exchange_id, CounterAction::OrderCancel);
+        self.persist_changes();
+    }
+
+    /// 处理报单回报，维护自身挂单
+    pub fn on_rtn_order(&mut self, order: &OrderField) {
+        let order_ref = field_str(&order.order_ref);
+        if order_ref.is_empty() {
+            return;
+        }
+
+        if !is_resting_status(order.order_status, order.order_submit_status) {
+            self.resting.remove(&order_ref);
+            return;
+        }
+
+        self.resting.insert(
+            order_ref,
+            RestingOrder {
+                instrument_id: field_str(&order.instrument_id),
+                exchange_id: field_str(&order.exchange_id),
+                direction: order.direction,
+                price: order.limit_price,
+                order_sys_id: field_str(&order.order_sys_id),
+            },
+        );
+    }
+
+    /// 处理报单录入错误，移除对应的挂单
+    pub fn on_err_rtn_order_insert(&mut self, input_order: &InputOrderField) {
+        self.resting.remove(&field_str(&input_order.order_ref));
+    }
+
+    /// 当前记录的自身挂单数量
+    pub fn resting_order_count(&self) -> usize {
+        self.resting.len()
+    }
+
+    /// 写入计数，失败时保留未保存标记，下次计数变化或释放时重试
+    pub fn flush(&mut self) -> CtpResult<()> {
+        self.dirty = true;
+        self.persist()?;
+        self.dirty = false;
+        Ok(())
+    }
+
+    // 计数变化后立即写入，进程崩溃时不会丢失已发出的报单、撤单计数
+    fn persist_changes(&mut self) {
+        if let Err(e) = self.flush() {
+            warn!("{}", e);
+        }
+    }
+
+    /// 将计数写入持久化文件
+    pub fn persist(&self) -> CtpResult<()> {
+        let Some(path) = &self.config.state_file else {
+            return Ok(());
+        };
+
+        let mut content = format!("trading_day={}\n", self.trading_day);
+        let mut entries: Vec<_> = self.counters.iter().collect();
+        entries.sort_by(|a, b| (a.0 .0.tag(), &a.0 .1).cmp(&(b.0 .0.tag(), &b.0 .1)));
+        for ((scope, key), counts) in entries {
+            content.push_str(&format!(
+                "{}|{}|{}|{}\n",
+                scope.tag(),
+                key,
+                counts.orders,
+                counts.cancels
+            ));
+        }
+
+        // 先写临时文件再改名，避免进程中断时留下半截文件
+        let tmp_path = path.with_extension("tmp");
+        fs::write(&tmp_path, content)
+            .and_then(|_| fs::rename(&tmp_path, path))
+            .map_err(|e| CtpError::Other(format!("写入合规计数文件失败: {}", e)))
+    }
+
+    fn load(&mut self, path: &Path) -> CtpResult<()> {
+        let content = fs::read_to_string(path)
+            .map_err(|e| CtpError::Other(format!("读取合规计数文件失败: {}", e)))?;
+
+        for line in content.lines() {
+            if let Some(day) = line.strip_prefix("trading_day=") {
+                self.trading_day = day.trim().to_string();
+                continue;
+            }
+
+            let parts: Vec<&str> = line.split('|').collect();
+            if parts.len() != 4 {
+                continue;
+            }
+            let (Some(scope), Ok(orders), Ok(cancels)) = (
+                CounterScope::from_tag(parts[0]),
+                parts[2].parse::<u32>(),
+                parts[3].parse::<u32>(),
+            ) else {
+                warn!("忽略无法解析的合规计数行: {}", line);
+                continue;
+            };
+            self.counters
+                .insert((scope, parts[1].to_string()), Counts { orders, cancels });
+        }
+
+        debug!(
+            "从 {} 恢复合规计数，交易日 {}，共 {} 项",
+            path.display(),
+            self.trading_day,
+            self.counters.len()
+        );
+        Ok(())
+    }
+
+    fn scope_keys(instrument_id: &str, exchange_id: &str) -> Vec<(CounterScope, String)> {
+        let mut keys = Vec::with_capacity(3);
+        if !instrument_id.is_empty() {
+            keys.push((CounterScope::Instrument, instrument_id.to_string()));
+            let product_id = product_of(instrument_id);
+            if !product_id.is_empty() {
+                keys.push((CounterScope::Product, product_id));
+            }
+        }
+        if !exchange_id.is_empty() {
+            keys.push((CounterScope::Exchange, exchange_id.to_string()));
+        }
+        keys
+    }
+
+    fn check_limits(
+        &self,
+        instrument_id: &str,
+        exchange_id: &str,
+        action: CounterAction,
+    ) -> CtpResult<Vec<ComplianceWarning>> {
+        let mut warnings = Vec::new();
+
+        for (scope, key) in Self::scope_keys(instrument_id, exchange_id) {
+            let limit = self.config.limits_for(scope, &key).for_action(action);
+            let next = self.counts(scope, &key).get(action) + 1;
+
+            if let Some(hard) = limit.hard {
+                if next > hard {
+                    return Err(CtpError::ComplianceError(format!(
+                        "{} {} 的{}次数将超过硬限制 {}",
+                        scope, key, action, hard
+                    )));
+                }
+            }
+
+            if let Some(soft) = limit.soft {
+                if next >= soft {
+                    let warning = ComplianceWarning {
+                        scope,
+                        key,
+                        action,
+                        count: next,
+                        soft_limit: soft,
+                    };
+                    warn!("{}", warning);
+                    warnings.push(warning);
+                }
+            }
+        }
+
+        Ok(warnings)
+    }
+
+    fn increment(&mut self, instrument_id: &str, exchange_id: &str, action: CounterAction) {
+        for key in Self::scope_keys(instrument_id, exchange_id) {
+            self.counters.entry(key).or_default().incr(action);
+        }
+    }
+
+    fn check_self_trade(&self, instrument_id: &str, req: &InputOrderField) -> CtpResult<()> {
+        // 报单价格条件 '2' 为限价，其余（任意价、最优价等）视为可与任意对手价成交
+        let is_limit = req.order_price_type == b'2';
+
+        let crossed = self.resting.values().find(|resting| {
+            if resting.instrument_id != instrument_id || resting.direction == req.direction {
+                return false;
+            }
+            if !is_limit {
+                return true;
+            }
+            // 买卖方向 '0' 为买，'1' 为卖
+            match req.direction {
+                b'0' => req.limit_price >= resting.price,
+                b'1' => req.limit_price <= resting.price,
+                _ => false,
+            }
+        });
+
+        match crossed {
+            Some(resting) => Err(CtpError::ComplianceError(format!(
+                "报单将与自身挂单成交: 合约 {} 价格 {} 对手挂单价格 {}",
+                instrument_id, req.limit_price, resting.price
+            ))),
+            None => Ok(()),
+        }
+    }
+
+    fn resolve_action_target(&self, req: &InputOrderActionField) -> (String, String) {
+        let mut instrument_id = field_str(&req.instrument_id);
+        let mut exchange_id = field_str(&req.exchange_id);
+
+        if instrument_id.is_empty() || exchange_id.is_empty() {
+            let order_ref = field_str(&req.order_ref);
+            let order_sys_id = field_str(&req.order_sys_id);
+
+            let resting = self.resting.get(&order_ref).or_else(|| {
+                self.resting
+                    .values()
+                    .find(|r| !order_sys_id.is_empty() && r.order_sys_id == order_sys_id)
+            });
+
+            if let Some(resting) = resting {
+                if instrument_id.is_empty() {
+                    instrument_id = resting.instrument_id.clone();
+                }
+                if exchange_id.is_empty() {
+                    exchange_id = resting.exchange_id.clone();
+                }
+            }
+        }
+
+        (instrument_id, exchange_id)
+    }
+}
+
+impl Drop for ComplianceMonitor {
+    fn drop(&mut self) {
+        if self.dirty {
+            if let Err(e) = self.flush() {
+                warn!("{}", e);
+            }
+        }
+    }
+}
+
+/// 由合约代码推导品种代码，如 rb2601 -> rb，m2601-C-3000 -> m
+pub fn product_of(instrument_id: &str) -> String {
+    instrument_id
+        .chars()
+        .take_while(|c| c.is_ascii_alphabetic())
+        .collect()
+}
+
+// 报单是否仍在交易所排队
+fn is_resting_status(order_status: u8, submit_status: u8) -> bool {
+    // 报单提交状态 '4' 为报单已被拒绝
+    if submit_status == b'4' {
+        return false;
+    }
+    // '1' 部分成交还在队列中，'3' 未成交还在队列中，'a' 未知（已提交尚未确认）
+    matches!(order_status, b'1' | b'3' | b'a')
+}
+
+fn field_str(bytes: &impl AsRef<[u8]>) -> String {
+    GbkConverter::gb18030_to_utf8(bytes.as_ref())
+        .map(|s| s.trim_end_matches('\0').trim().to_string())
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::StringConvert;
+
+    fn order(instrument: &str, order_ref: &str, direction: u8, price: f64) -> InputOrderField {
+        InputOrderField {
+            instrument_id: <[u8; 31]>::from_utf8_string(instrument).unwrap(),
+            exchange_id: <[u8; 9]>::from_utf8_string("SHFE").unwrap(),
+            order_ref: <[u8; 13]>::from_utf8_string(order_ref).unwrap(),
+            order_price_type: b'2',
+            direction,
+            limit_price: price,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_product_of() {
+        assert_eq!(product_of("rb2601"), "rb");
+        assert_eq!(product_of("IF2601"), "IF");
+        assert_eq!(product_of("m2601-C-3000"), "m");
+    }
+
+    #[test]
+    fn test_soft_and_hard_limits() {
+        let config = ComplianceConfig::new().with_product_limits(ScopeLimits::new(
+            Limit::new(Some(2), Some(3)),
+            Limit::unlimited(),
+        ));
+        let mut monitor = ComplianceMonitor::new(config).unwrap();
+
+        let req = order("rb2601", "1", b'0', 3500.0);
+        assert!(monitor.check_order_insert(&req).unwrap().is_empty());
+        monitor.record_order_insert(&req);
+
+        let req = order("rb2605", "2", b'0', 3500.0);
+        assert_eq!(monitor.check_order_insert(&req).unwrap().len(), 1);
+        monitor.record_order_insert(&req);
+
+        let req = order("rb2601", "3", b'0', 3500.0);
+        monitor.record_order_insert(&req);
+        assert_eq!(monitor.counts(CounterScope::Product, "rb").orders, 3);
+        assert!(monitor.check_order_insert(&req).is_err());
+    }
+
+    #[test]
+    fn test_self_trade_prevention() {
+        let mut monitor = ComplianceMonitor::new(ComplianceConfig::new()).unwrap();
+        monitor.record_order_insert(&order("rb2601", "1", b'1', 3500.0));
+
+        assert!(monitor
+            .check_order_insert(&order("rb2601", "2", b'0', 3499.0))
+            .is_ok());
+        assert!(monitor
+            .check_order_insert(&order("rb2601", "2", b'0', 3500.0))
+            .is_err());
+        assert!(monitor
+            .check_order_insert(&order("rb2605", "2", b'0', 3600.0))
+            .is_ok());
+
+        // 发送失败时撤销登记，不留下挂单
+        let failed = order("rb2605", "3", b'1', 3600.0);
+        monitor.record_order_insert(&failed);
+        monitor.on_order_insert_failed(&failed);
+        assert_eq!(monitor.resting_order_count(), 1);
+        assert_eq!(monitor.counts(CounterScope::Instrument, "rb2605").orders, 0);
+    }
+
+    #[test]
+    fn test_counters_persist_across_restarts() {
+        let path = std::env::temp_dir().join(format!("ctp_compliance_{}.txt", std::process::id()));
+        let config = ComplianceConfig::new().with_state_file(&path);
+
+        let mut monitor = ComplianceMonitor::new(config.clone()).unwrap();
+        monitor.set_trading_day("20260105").unwrap();
+        monitor.record_order_insert(&order("rb2601", "1", b'0', 3500.0));
+        drop(monitor);
+
+        let mut monitor = ComplianceMonitor::new(config.clone()).unwrap();
+        assert_eq!(monitor.trading_day(), "20260105");
+        assert_eq!(monitor.counts(CounterScope::Exchange, "SHFE").orders, 1);
+
+        // 未释放（如进程被杀）时计数也已写入
+        monitor.record_order_insert(&order("rb2601", "2", b'0', 3500.0));
+        let restored = ComplianceMonitor::new(config.clone()).unwrap();
+        assert_eq!(restored.counts(CounterScope::Exchange, "SHFE").orders, 2);
+
+        monitor.set_trading_day("20260106").unwrap();
+        assert_eq!(monitor.counts(CounterScope::Exchange, "SHFE").orders, 0);
+
+        let _ = fs::remove_file(&path);
+    }
+}
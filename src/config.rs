@@ -1,11 +1,50 @@
+#[cfg(feature = "config-file")]
+use crate::accounts::AccountConfig;
+#[cfg(feature = "config-file")]
+use crate::credentials::CredentialSource;
+use crate::credentials::{CommandCredentials, CredentialProvider, Credentials, FileCredentials};
+use crate::error::{CtpError, CtpResult};
+use crate::flow::{FlowApiKind, FlowDirectory};
+use crate::models::encode_text;
 use dotenvy::dotenv;
+#[cfg(feature = "config-file")]
+use std::collections::BTreeMap;
 use std::env;
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use tracing::warn;
+use zeroize::Zeroize;
+
+// 未配置时使用的SimNow地址和经纪公司代码
+const DEFAULT_MD_FRONT: &str = "tcp://121.37.80.177:20004";
+const DEFAULT_TRADER_FRONT: &str = "tcp://121.37.80.177:20002";
+const DEFAULT_BROKER_ID: &str = "9999";
+
+/// 密码来源
+///
+/// 口令变更成功后，新口令会写回到这里记录的位置
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub enum SecretSource {
+    /// 只保存在内存中，不持久化
+    #[default]
+    Memory,
+    /// 从`.env`文件加载
+    EnvFile(PathBuf),
+}
 
 /// CTP配置信息 (别名，用于异步API)
-#[derive(Debug, Clone)]
+///
+/// `Debug`输出中密码和认证码会被隐去，释放时密码和认证码会被清零
+#[derive(Clone)]
 pub struct CtpConfig {
     pub md_front_address: String,
     pub trader_front_address: String,
+    /// 按优先顺序排列的全部行情前置，第一个与`md_front_address`相同
+    pub md_front_addresses: Vec<String>,
+    /// 按优先顺序排列的全部交易前置，第一个与`trader_front_address`相同
+    pub trader_front_addresses: Vec<String>,
     pub broker_id: String,
     pub investor_id: String,
     pub password: String,
@@ -14,36 +53,51 @@ pub struct CtpConfig {
     pub app_id: String,
     pub auth_code: String,
     pub product_info: String,
+    pub secret_source: SecretSource,
+    /// 凭证提供者，设置后登录时从提供者获取密码和认证码
+    pub credential_provider: Option<Arc<dyn CredentialProvider>>,
 }
 
 impl CtpConfig {
     /// 从环境变量加载配置
+    ///
+    /// `MD_FRONT_ADDRESS`和`TRADER_FRONT_ADDRESS`可以是逗号分隔的多个前置地址。
+    /// 未设置前置地址或经纪公司代码时使用SimNow的默认值并给出警告；加载后会校验配置。
+    ///
+    /// 设置了`CTP_CREDENTIALS_FILE`或`CTP_CREDENTIALS_COMMAND`时，密码和认证码在登录时
+    /// 从对应的文件或命令获取，此时不需要`PASSWORD`
     pub fn from_env() -> Result<Self, Box<dyn std::error::Error>> {
-        dotenv().ok();
+        let secret_source = dotenv().map(SecretSource::EnvFile).unwrap_or_default();
 
-        let md_front_address = env::var("MD_FRONT_ADDRESS")
-            .unwrap_or_else(|_| "tcp://121.37.80.177:20004".to_string());
-        let trader_front_address = env::var("TRADER_FRONT_ADDRESS")
-            .unwrap_or_else(|_| "tcp://121.37.80.177:20002".to_string());
-        let broker_id = env::var("BROKER_ID").unwrap_or_else(|_| "9999".to_string());
+        let md_front_addresses = split_list(&env_or_default("MD_FRONT_ADDRESS", DEFAULT_MD_FRONT));
+        let trader_front_addresses = split_list(&env_or_default(
+            "TRADER_FRONT_ADDRESS",
+            DEFAULT_TRADER_FRONT,
+        ));
+        let md_front_address = md_front_addresses.first().cloned().unwrap_or_default();
+        let trader_front_address = trader_front_addresses.first().cloned().unwrap_or_default();
+        let broker_id = env_or_default("BROKER_ID", DEFAULT_BROKER_ID);
         let investor_id =
             env::var("INVESTOR_ID").map_err(|_| "请在.env文件中设置INVESTOR_ID环境变量")?;
-        let password = env::var("PASSWORD").map_err(|_| "请在.env文件中设置PASSWORD环境变量")?;
+        let credential_provider = credential_provider_from_env()?;
+        let password = match env::var("PASSWORD") {
+            Ok(password) => password,
+            Err(_) if credential_provider.is_some() => String::new(),
+            Err(_) => return Err("请在.env文件中设置PASSWORD环境变量".into()),
+        };
         let flow_path = env::var("FLOW_PATH").unwrap_or_else(|_| "./flow".to_string());
         let instruments_str = env::var("INSTRUMENTS").unwrap_or_else(|_| "rb2601".to_string());
-        let instruments: Vec<String> = instruments_str
-            .split(',')
-            .map(|s| s.trim().to_string())
-            .filter(|s| !s.is_empty())
-            .collect();
+        let instruments = split_list(&instruments_str);
 
         let app_id = env::var("APP_ID").unwrap_or_else(|_| "".to_string());
         let auth_code = env::var("AUTH_CODE").unwrap_or_else(|_| "".to_string());
         let product_info = env::var("PRODUCT_INFO").unwrap_or_else(|_| "".to_string());
 
-        Ok(CtpConfig {
+        let config = CtpConfig {
             md_front_address,
             trader_front_address,
+            md_front_addresses,
+            trader_front_addresses,
             broker_id,
             investor_id,
             password,
@@ -52,6 +106,818 @@ impl CtpConfig {
             app_id,
             auth_code,
             product_info,
+            secret_source,
+            credential_provider,
+        };
+        config.validate()?;
+        Ok(config)
+    }
+
+    /// 从TOML或YAML配置文件加载指定环境的配置
+    ///
+    /// 未指定`profile`时依次使用环境变量`CTP_PROFILE`、文件中的`default_profile`，
+    /// 文件中只有一个环境时使用该环境。加载后用环境变量覆盖（变量名与[`CtpConfig::from_env`]
+    /// 相同），最后校验配置
+    #[cfg(feature = "config-file")]
+    pub fn from_file(path: impl AsRef<Path>, profile: Option<&str>) -> CtpResult<Self> {
+        let file = ConfigFile::load(path)?;
+        let (_, profile) = file.profile(profile)?;
+        let mut config = profile.to_config();
+        config.apply_env_overrides();
+        config.validate()?;
+        Ok(config)
+    }
+
+    /// 从配置文件加载指定环境下的全部账户，每个账户都会单独校验
+    ///
+    /// 环境中没有`accounts`时，把环境本身作为名为投资者代码的单个账户
+    #[cfg(feature = "config-file")]
+    pub fn accounts_from_file(
+        path: impl AsRef<Path>,
+        profile: Option<&str>,
+    ) -> CtpResult<Vec<AccountConfig>> {
+        let file = ConfigFile::load(path)?;
+        let (_, profile) = file.profile(profile)?;
+        let accounts = profile.accounts();
+        for account in &accounts {
+            account.config.validate().map_err(|e| {
+                CtpError::InvalidParameterError(format!("账户 {}: {}", account.name, e))
+            })?;
+        }
+        Ok(accounts)
+    }
+
+    /// 用环境变量覆盖已加载的配置，只覆盖设置了的变量
+    pub fn apply_env_overrides(&mut self) {
+        self.apply_overrides(|name| env::var(name).ok());
+    }
+
+    fn apply_overrides(&mut self, lookup: impl Fn(&str) -> Option<String>) {
+        if let Some(value) = lookup("MD_FRONT_ADDRESS") {
+            self.md_front_addresses = split_list(&value);
+            self.md_front_address = self.md_front_addresses.first().cloned().unwrap_or_default();
+        }
+        if let Some(value) = lookup("TRADER_FRONT_ADDRESS") {
+            self.trader_front_addresses = split_list(&value);
+            self.trader_front_address = self
+                .trader_front_addresses
+                .first()
+                .cloned()
+                .unwrap_or_default();
+        }
+        if let Some(value) = lookup("INSTRUMENTS") {
+            self.instruments = split_list(&value);
+        }
+        for (name, field) in [
+            ("BROKER_ID", &mut self.broker_id),
+            ("INVESTOR_ID", &mut self.investor_id),
+            ("PASSWORD", &mut self.password),
+            ("FLOW_PATH", &mut self.flow_path),
+            ("APP_ID", &mut self.app_id),
+            ("AUTH_CODE", &mut self.auth_code),
+            ("PRODUCT_INFO", &mut self.product_info),
+        ] {
+            if let Some(value) = lookup(name) {
+                *field = value;
+            }
+        }
+    }
+
+    /// 校验前置地址格式和各字段是否超过CTP的长度限制
+    ///
+    /// 长度按GB18030编码后的字节数计算，上限为CTP定长字段去掉结尾空字节后的长度
+    pub fn validate(&self) -> CtpResult<()> {
+        if self.trader_front_address.is_empty() && self.trader_front_addresses.is_empty() {
+            return Err(CtpError::InvalidParameterError(
+                "未配置交易前置地址".to_string(),
+            ));
+        }
+        for address in std::iter::once(&self.md_front_address)
+            .chain(std::iter::once(&self.trader_front_address))
+            .chain(&self.md_front_addresses)
+            .chain(&self.trader_front_addresses)
+            .filter(|a| !a.is_empty())
+        {
+            validate_front_address(address)?;
+        }
+
+        if self.broker_id.is_empty() {
+            return Err(CtpError::InvalidParameterError(
+                "经纪公司代码不能为空".to_string(),
+            ));
+        }
+        if self.investor_id.is_empty() {
+            return Err(CtpError::InvalidParameterError(
+                "投资者代码不能为空".to_string(),
+            ));
+        }
+        encode_text::<11>(&self.broker_id, "broker_id")?;
+        encode_text::<13>(&self.investor_id, "investor_id")?;
+        // 错误信息中不能带出密码
+        if !fits::<41>(&self.password) {
+            return Err(CtpError::InvalidParameterError(
+                "密码超过40字节".to_string(),
+            ));
+        }
+        encode_text::<33>(&self.app_id, "app_id")?;
+        if !fits::<17>(&self.auth_code) {
+            return Err(CtpError::InvalidParameterError(
+                "认证码超过16字节".to_string(),
+            ));
+        }
+        encode_text::<11>(&self.product_info, "product_info")?;
+        for instrument in &self.instruments {
+            encode_text::<31>(instrument, "instruments")?;
+        }
+        Ok(())
+    }
+
+    /// 设置凭证提供者，并清除配置中保存的密码和认证码
+    pub fn with_credential_provider(mut self, provider: impl CredentialProvider + 'static) -> Self {
+        self.password.zeroize();
+        self.auth_code.zeroize();
+        self.credential_provider = Some(Arc::new(provider));
+        self
+    }
+
+    /// 当前账户指定API类型的流文件目录：`<flow_path>/<broker_id>_<investor_id>/<trader|md>/`
+    pub fn flow_directory(&self, kind: FlowApiKind) -> FlowDirectory {
+        FlowDirectory::for_session(&self.flow_path, &self.broker_id, &self.investor_id, kind)
+    }
+
+    /// 获取登录凭证
+    ///
+    /// 设置了凭证提供者时调用提供者并检查长度，否则使用配置中的密码和认证码
+    pub fn credentials(&self) -> CtpResult<Credentials> {
+        let Some(provider) = &self.credential_provider else {
+            return Ok(Credentials::new(
+                self.password.as_str(),
+                self.auth_code.as_str(),
+            ));
+        };
+        let credentials = provider.credentials()?;
+        if !fits::<41>(credentials.password()) {
+            return Err(CtpError::InvalidParameterError(
+                "密码超过40字节".to_string(),
+            ));
+        }
+        if !fits::<17>(credentials.auth_code()) {
+            return Err(CtpError::InvalidParameterError(
+                "认证码超过16字节".to_string(),
+            ));
+        }
+        Ok(credentials)
+    }
+
+    /// 检查密码能否写回，设置了凭证提供者时新密码无法传给提供者
+    pub fn check_password_writable(&self) -> CtpResult<()> {
+        match &self.credential_provider {
+            Some(provider) => Err(CtpError::InvalidParameterError(format!(
+                "密码由凭证提供者 {:?} 管理，无法写回新密码，请在提供者处修改",
+                provider
+            ))),
+            None => Ok(()),
+        }
+    }
+
+    /// 更新密码并写回密码来源
+    ///
+    /// 设置了凭证提供者时返回错误且不修改任何内容。其余情况下内存中的密码总会被更新；
+    /// 写文件失败时返回错误，此时柜台上的口令可能已经变更，调用方需要另行保存`self.password`
+    pub fn update_password(&mut self, new_password: &str) -> CtpResult<()> {
+        self.check_password_writable()?;
+        self.password = new_password.to_string();
+        match &self.secret_source {
+            SecretSource::Memory => Ok(()),
+            SecretSource::EnvFile(path) => write_env_var(path, "PASSWORD", new_password),
+        }
+    }
+}
+
+impl fmt::Debug for CtpConfig {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CtpConfig")
+            .field("md_front_address", &self.md_front_address)
+            .field("trader_front_address", &self.trader_front_address)
+            .field("md_front_addresses", &self.md_front_addresses)
+            .field("trader_front_addresses", &self.trader_front_addresses)
+            .field("broker_id", &self.broker_id)
+            .field("investor_id", &self.investor_id)
+            .field("password", &redact(&self.password))
+            .field("flow_path", &self.flow_path)
+            .field("instruments", &self.instruments)
+            .field("app_id", &self.app_id)
+            .field("auth_code", &redact(&self.auth_code))
+            .field("product_info", &self.product_info)
+            .field("secret_source", &self.secret_source)
+            .field("credential_provider", &self.credential_provider)
+            .finish()
+    }
+}
+
+impl Drop for CtpConfig {
+    fn drop(&mut self) {
+        self.password.zeroize();
+        self.auth_code.zeroize();
+    }
+}
+
+/// 配置文件
+///
+/// 顶层是以环境名称为键的`profiles`表，例如SimNow 7x24、SimNow标准环境以及各期货公司的
+/// 生产环境。示例见仓库根目录的`ctp.example.toml`
+#[cfg(feature = "config-file")]
+#[derive(Debug, Clone, Default, serde::Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ConfigFile {
+    /// 默认使用的环境名称
+    pub default_profile: Option<String>,
+    /// 各环境的配置
+    pub profiles: BTreeMap<String, ProfileConfig>,
+}
+
+/// 配置文件中的一个环境
+#[cfg(feature = "config-file")]
+#[derive(Clone, Default, serde::Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ProfileConfig {
+    /// 经纪公司代码
+    pub broker_id: String,
+    /// 按优先顺序排列的行情前置
+    pub md_fronts: Vec<String>,
+    /// 按优先顺序排列的交易前置
+    pub trader_fronts: Vec<String>,
+    /// 投资者代码
+    pub investor_id: String,
+    /// 密码
+    pub password: String,
+    /// 流文件目录，默认`./flow`
+    pub flow_path: Option<String>,
+    /// 订阅的合约
+    pub instruments: Vec<String>,
+    /// 应用单元代码
+    pub app_id: String,
+    /// 认证码
+    pub auth_code: String,
+    /// 产品信息
+    pub product_info: String,
+    /// 凭证来源，设置后登录时从该来源获取密码和认证码
+    pub credentials: Option<CredentialSource>,
+    /// 该环境下的账户，未填写的字段继承环境的配置
+    pub accounts: Vec<AccountSection>,
+}
+
+/// 配置文件中的一个账户
+#[cfg(feature = "config-file")]
+#[derive(Clone, Default, serde::Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct AccountSection {
+    /// 账户名称
+    pub name: String,
+    /// 投资者代码
+    pub investor_id: String,
+    /// 密码
+    pub password: String,
+    /// 经纪公司代码
+    pub broker_id: Option<String>,
+    /// 应用单元代码
+    pub app_id: Option<String>,
+    /// 认证码
+    pub auth_code: Option<String>,
+    /// 产品信息
+    pub product_info: Option<String>,
+    /// 凭证来源
+    pub credentials: Option<CredentialSource>,
+}
+
+#[cfg(feature = "config-file")]
+impl ConfigFile {
+    /// 读取配置文件，按扩展名识别格式（`.toml`、`.yaml`、`.yml`）
+    pub fn load(path: impl AsRef<Path>) -> CtpResult<Self> {
+        let path = path.as_ref();
+        let content = fs::read_to_string(path).map_err(|e| {
+            CtpError::InvalidPath(format!("读取配置文件 {} 失败: {}", path.display(), e))
+        })?;
+        let extension = path
+            .extension()
+            .and_then(|e| e.to_str())
+            .unwrap_or_default()
+            .to_ascii_lowercase();
+        match extension.as_str() {
+            "toml" => Self::from_toml_str(&content),
+            "yaml" | "yml" => Self::from_yaml_str(&content),
+            _ => Err(CtpError::InvalidParameterError(format!(
+                "无法识别的配置文件格式: {}",
+                path.display()
+            ))),
+        }
+    }
+
+    /// 解析TOML格式的配置
+    pub fn from_toml_str(content: &str) -> CtpResult<Self> {
+        toml::from_str(content)
+            .map_err(|e| CtpError::InvalidParameterError(format!("解析TOML配置失败: {}", e)))
+    }
+
+    /// 解析YAML格式的配置
+    pub fn from_yaml_str(content: &str) -> CtpResult<Self> {
+        serde_yaml::from_str(content)
+            .map_err(|e| CtpError::InvalidParameterError(format!("解析YAML配置失败: {}", e)))
+    }
+
+    /// 选择环境，返回环境名称和配置
+    pub fn profile(&self, name: Option<&str>) -> CtpResult<(&str, &ProfileConfig)> {
+        let name = match name {
+            Some(name) => name.to_string(),
+            None => match env::var("CTP_PROFILE")
+                .ok()
+                .or(self.default_profile.clone())
+            {
+                Some(name) => name,
+                None if self.profiles.len() == 1 => self.profiles.keys().next().unwrap().clone(),
+                None => {
+                    return Err(CtpError::InvalidParameterError(
+                        "配置文件中有多个环境，需要指定profile".to_string(),
+                    ))
+                }
+            },
+        };
+        self.profiles
+            .get_key_value(&name)
+            .map(|(name, profile)| (name.as_str(), profile))
+            .ok_or_else(|| CtpError::InvalidParameterError(format!("配置文件中没有环境 {}", name)))
+    }
+}
+
+#[cfg(feature = "config-file")]
+impl ProfileConfig {
+    /// 转换为运行时配置
+    pub fn to_config(&self) -> CtpConfig {
+        CtpConfig {
+            md_front_address: self.md_fronts.first().cloned().unwrap_or_default(),
+            trader_front_address: self.trader_fronts.first().cloned().unwrap_or_default(),
+            md_front_addresses: self.md_fronts.clone(),
+            trader_front_addresses: self.trader_fronts.clone(),
+            broker_id: self.broker_id.clone(),
+            investor_id: self.investor_id.clone(),
+            password: self.password.clone(),
+            flow_path: self
+                .flow_path
+                .clone()
+                .unwrap_or_else(|| "./flow".to_string()),
+            instruments: self.instruments.clone(),
+            app_id: self.app_id.clone(),
+            auth_code: self.auth_code.clone(),
+            product_info: self.product_info.clone(),
+            secret_source: SecretSource::Memory,
+            credential_provider: self.credentials.as_ref().map(CredentialSource::provider),
+        }
+    }
+
+    /// 该环境下的全部账户
+    pub fn accounts(&self) -> Vec<AccountConfig> {
+        if self.accounts.is_empty() {
+            return vec![AccountConfig::new(&self.investor_id, self.to_config())];
+        }
+        self.accounts
+            .iter()
+            .map(|account| {
+                let mut config = self.to_config();
+                config.investor_id = account.investor_id.clone();
+                config.password = account.password.clone();
+                if let Some(source) = &account.credentials {
+                    config.credential_provider = Some(source.provider());
+                }
+                for (field, value) in [
+                    (&mut config.broker_id, &account.broker_id),
+                    (&mut config.app_id, &account.app_id),
+                    (&mut config.auth_code, &account.auth_code),
+                    (&mut config.product_info, &account.product_info),
+                ] {
+                    if let Some(value) = value {
+                        *field = value.clone();
+                    }
+                }
+                AccountConfig::new(&account.name, config)
+            })
+            .collect()
+    }
+}
+
+#[cfg(feature = "config-file")]
+impl fmt::Debug for ProfileConfig {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ProfileConfig")
+            .field("broker_id", &self.broker_id)
+            .field("md_fronts", &self.md_fronts)
+            .field("trader_fronts", &self.trader_fronts)
+            .field("investor_id", &self.investor_id)
+            .field("password", &redact(&self.password))
+            .field("flow_path", &self.flow_path)
+            .field("instruments", &self.instruments)
+            .field("app_id", &self.app_id)
+            .field("auth_code", &redact(&self.auth_code))
+            .field("product_info", &self.product_info)
+            .field("credentials", &self.credentials)
+            .field("accounts", &self.accounts)
+            .finish()
+    }
+}
+
+#[cfg(feature = "config-file")]
+impl fmt::Debug for AccountSection {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("AccountSection")
+            .field("name", &self.name)
+            .field("investor_id", &self.investor_id)
+            .field("password", &redact(&self.password))
+            .field("broker_id", &self.broker_id)
+            .field("app_id", &self.app_id)
+            .field("auth_code", &self.auth_code.as_deref().map(redact))
+            .field("product_info", &self.product_info)
+            .field("credentials", &self.credentials)
+            .finish()
+    }
+}
+
+// 敏感字段在Debug输出中只显示是否已设置
+pub(crate) fn redact(value: &str) -> &'static str {
+    if value.is_empty() {
+        ""
+    } else {
+        "***"
+    }
+}
+
+// 编码后能否放入长度为N的CTP定长字段
+fn fits<const N: usize>(value: &str) -> bool {
+    crate::encoding::GbkConverter::utf8_to_gb18030(value).is_ok_and(|bytes| bytes.len() < N)
+}
+
+fn credential_provider_from_env() -> CtpResult<Option<Arc<dyn CredentialProvider>>> {
+    if let Ok(path) = env::var("CTP_CREDENTIALS_FILE") {
+        return Ok(Some(Arc::new(FileCredentials::new(path))));
+    }
+    if let Ok(command) = env::var("CTP_CREDENTIALS_COMMAND") {
+        return Ok(Some(Arc::new(CommandCredentials::from_command_line(
+            &command,
+        )?)));
+    }
+    Ok(None)
+}
+
+fn env_or_default(name: &str, default: &str) -> String {
+    env::var(name).unwrap_or_else(|_| {
+        warn!("未设置{}，使用默认值 {}", name, default);
+        default.to_string()
+    })
+}
+
+// 前置地址格式为`协议://主机:端口`，协议支持tcp、ssl和udp
+fn validate_front_address(address: &str) -> CtpResult<()> {
+    let invalid = |reason: &str| {
+        Err(CtpError::InvalidParameterError(format!(
+            "前置地址 {} 无效: {}",
+            address, reason
+        )))
+    };
+    let Some(rest) = ["tcp://", "ssl://", "udp://"]
+        .iter()
+        .find_map(|scheme| address.strip_prefix(scheme))
+    else {
+        return invalid("协议必须是tcp://、ssl://或udp://");
+    };
+    let Some((host, port)) = rest.rsplit_once(':') else {
+        return invalid("缺少端口");
+    };
+    if host.is_empty() || host.contains(['/', ' ']) {
+        return invalid("主机名无效");
+    }
+    match port.parse::<u16>() {
+        Ok(port) if port > 0 => Ok(()),
+        _ => invalid("端口无效"),
+    }
+}
+
+// 逗号分隔的列表，忽略空项
+fn split_list(value: &str) -> Vec<String> {
+    value
+        .split(',')
+        .map(|s| s.trim().to_string())
+        .filter(|s| !s.is_empty())
+        .collect()
+}
+
+// 替换或追加`.env`文件中的一个变量，其他行（包括注释）保持不变
+fn write_env_var(path: &Path, key: &str, value: &str) -> CtpResult<()> {
+    let content = match fs::read_to_string(path) {
+        Ok(content) => content,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => String::new(),
+        Err(e) => {
+            return Err(CtpError::Other(format!(
+                "读取配置文件 {} 失败: {}",
+                path.display(),
+                e
+            )))
+        }
+    };
+
+    let value = quote_env_value(value);
+    let mut replaced = false;
+    let mut lines: Vec<String> = content
+        .lines()
+        .map(|line| {
+            let trimmed = line.trim_start();
+            let body = trimmed.strip_prefix("export ").unwrap_or(trimmed);
+            match body.split_once('=') {
+                Some((name, _)) if !replaced && name.trim() == key => {
+                    replaced = true;
+                    let prefix = &line[..line.len() - body.len()];
+                    format!("{}{}={}", prefix, key, value)
+                }
+                _ => line.to_string(),
+            }
         })
+        .collect();
+    if !replaced {
+        lines.push(format!("{}={}", key, value));
+    }
+    let mut output = lines.join("\n");
+    output.push('\n');
+
+    // 先写临时文件再改名，避免进程中断时留下半截文件
+    let tmp_path = path.with_extension("tmp");
+    write_private(&tmp_path, path, output.as_bytes())
+        .and_then(|_| fs::rename(&tmp_path, path))
+        .map_err(|e| CtpError::Other(format!("写入配置文件 {} 失败: {}", path.display(), e)))
+}
+
+// 临时文件沿用原文件的权限，原文件不存在时为600，避免改名后密码文件变为所有人可读
+#[cfg(unix)]
+fn write_private(tmp_path: &Path, original: &Path, content: &[u8]) -> std::io::Result<()> {
+    use std::io::Write;
+    use std::os::unix::fs::{OpenOptionsExt, PermissionsExt};
+
+    let mode = fs::metadata(original)
+        .map(|m| m.permissions().mode() & 0o777)
+        .unwrap_or(0o600);
+    let mut file = fs::OpenOptions::new()
+        .write(true)
+        .create(true)
+        .truncate(true)
+        .mode(mode)
+        .open(tmp_path)?;
+    // 残留的临时文件不会应用mode，显式设置一次
+    file.set_permissions(fs::Permissions::from_mode(mode))?;
+    file.write_all(content)?;
+    file.sync_all()
+}
+
+#[cfg(not(unix))]
+fn write_private(tmp_path: &Path, _original: &Path, content: &[u8]) -> std::io::Result<()> {
+    fs::write(tmp_path, content)
+}
+
+// 含有空白、引号、`#`等字符的值需要用双引号包裹并转义
+fn quote_env_value(value: &str) -> String {
+    let plain = !value.is_empty()
+        && value
+            .chars()
+            .all(|c| !c.is_whitespace() && !matches!(c, '"' | '\'' | '#' | '\\' | '$' | '`'));
+    if plain {
+        return value.to_string();
+    }
+    let mut quoted = String::with_capacity(value.len() + 2);
+    quoted.push('"');
+    for c in value.chars() {
+        if matches!(c, '"' | '\\' | '$') {
+            quoted.push('\\');
+        }
+        quoted.push(c);
+    }
+    quoted.push('"');
+    quoted
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config(source: SecretSource) -> CtpConfig {
+        CtpConfig {
+            md_front_address: String::new(),
+            trader_front_address: String::new(),
+            md_front_addresses: Vec::new(),
+            trader_front_addresses: Vec::new(),
+            broker_id: "9999".to_string(),
+            investor_id: "00001".to_string(),
+            password: "old".to_string(),
+            flow_path: String::new(),
+            instruments: Vec::new(),
+            app_id: String::new(),
+            auth_code: String::new(),
+            product_info: String::new(),
+            secret_source: source,
+            credential_provider: None,
+        }
+    }
+
+    #[test]
+    fn test_update_password_rewrites_env_file() {
+        let dir = env::temp_dir().join(format!("ctp_config_test_{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join(".env");
+        fs::write(
+            &path,
+            "# 测试账户\nBROKER_ID=9999\nexport PASSWORD=old\nAPP_ID=x\n",
+        )
+        .unwrap();
+
+        let mut config = config(SecretSource::EnvFile(path.clone()));
+        config.update_password("new#pass\"1").unwrap();
+        assert_eq!(config.password, "new#pass\"1");
+
+        let content = fs::read_to_string(&path).unwrap();
+        assert_eq!(
+            content,
+            "# 测试账户\nBROKER_ID=9999\nexport PASSWORD=\"new#pass\\\"1\"\nAPP_ID=x\n"
+        );
+        let parsed: Vec<_> = dotenvy::from_path_iter(&path)
+            .unwrap()
+            .collect::<Result<_, _>>()
+            .unwrap();
+        assert!(parsed.contains(&("PASSWORD".to_string(), "new#pass\"1".to_string())));
+
+        // 文件中没有PASSWORD时追加
+        fs::write(&path, "BROKER_ID=9999").unwrap();
+        config.update_password("abc").unwrap();
+        assert_eq!(
+            fs::read_to_string(&path).unwrap(),
+            "BROKER_ID=9999\nPASSWORD=abc\n"
+        );
+
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            fs::set_permissions(&path, fs::Permissions::from_mode(0o600)).unwrap();
+            config.update_password("def").unwrap();
+            let mode = fs::metadata(&path).unwrap().permissions().mode();
+            assert_eq!(mode & 0o777, 0o600);
+        }
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_update_password_in_memory() {
+        let mut config = config(SecretSource::default());
+        config.update_password("new").unwrap();
+        assert_eq!(config.password, "new");
+    }
+
+    #[test]
+    fn test_validate_and_redact() {
+        let mut config = config(SecretSource::default());
+        config.trader_front_address = "tcp://180.168.146.187:10201".to_string();
+        config.password = "secret-pw".to_string();
+        config.auth_code = "0000000000000000".to_string();
+        assert!(config.validate().is_ok());
+
+        let debug = format!("{:?}", config);
+        assert!(!debug.contains("secret-pw"));
+        assert!(!debug.contains("0000000000000000"));
+        assert!(debug.contains("00001"));
+
+        for address in [
+            "180.168.146.187:10201",
+            "tcp://180.168.146.187",
+            "tcp://:10201",
+            "tcp://180.168.146.187:0",
+            "http://180.168.146.187:10201",
+        ] {
+            let mut bad = config.clone();
+            bad.trader_front_addresses = vec![address.to_string()];
+            assert!(bad.validate().is_err(), "{}", address);
+        }
+
+        let mut bad = config.clone();
+        bad.broker_id = "12345678901".to_string();
+        assert!(bad.validate().is_err());
+
+        let mut bad = config.clone();
+        bad.password = "p".repeat(41);
+        let err = bad.validate().unwrap_err().to_string();
+        assert!(!err.contains(&bad.password));
+    }
+
+    #[test]
+    fn test_overrides() {
+        let mut config = config(SecretSource::default());
+        config.apply_overrides(|name| match name {
+            "TRADER_FRONT_ADDRESS" => Some("tcp://a:1, tcp://b:2".to_string()),
+            "PASSWORD" => Some("from-env".to_string()),
+            _ => None,
+        });
+        assert_eq!(config.trader_front_address, "tcp://a:1");
+        assert_eq!(
+            config.trader_front_addresses,
+            vec!["tcp://a:1", "tcp://b:2"]
+        );
+        assert_eq!(config.password, "from-env");
+        assert_eq!(config.broker_id, "9999");
+    }
+
+    #[derive(Debug)]
+    struct FixedProvider(String);
+
+    impl CredentialProvider for FixedProvider {
+        fn credentials(&self) -> CtpResult<Credentials> {
+            Ok(Credentials::new(self.0.as_str(), "AUTH"))
+        }
+    }
+
+    #[test]
+    fn test_credential_provider() {
+        let config = config(SecretSource::default());
+        assert_eq!(config.credentials().unwrap().password(), "old");
+
+        // 设置提供者后配置中不再保存密码
+        let config = config.with_credential_provider(FixedProvider("from-provider".to_string()));
+        assert!(config.password.is_empty());
+        let credentials = config.credentials().unwrap();
+        assert_eq!(credentials.password(), "from-provider");
+        assert_eq!(credentials.auth_code(), "AUTH");
+
+        let long = "p".repeat(41);
+        let mut config = config.with_credential_provider(FixedProvider(long.clone()));
+        let err = config.credentials().unwrap_err().to_string();
+        assert!(!err.contains(&long));
+
+        // 提供者无法接收新密码，拒绝更新
+        assert!(config.update_password("new").is_err());
+        assert!(config.password.is_empty());
+    }
+
+    #[cfg(feature = "config-file")]
+    #[test]
+    fn test_profiles() {
+        let toml = r#"
+            default_profile = "simnow-7x24"
+
+            [profiles.simnow-7x24]
+            broker_id = "9999"
+            trader_fronts = ["tcp://182.254.243.31:40001"]
+            md_fronts = ["tcp://182.254.243.31:40011"]
+            investor_id = "00001"
+            password = "pw"
+
+            [profiles.production]
+            broker_id = "8888"
+            trader_fronts = ["tcp://10.0.0.1:41205", "tcp://10.0.0.2:41205"]
+            app_id = "client_app_1.0"
+            auth_code = "ABCDEFGH"
+
+            [[profiles.production.accounts]]
+            name = "alpha"
+            investor_id = "10001"
+            password = "pw1"
+
+            [[profiles.production.accounts]]
+            name = "beta"
+            investor_id = "10002"
+            broker_id = "7777"
+            credentials = { command = ["pass", "show", "ctp/beta"] }
+        "#;
+        let file = ConfigFile::from_toml_str(toml).unwrap();
+        let (name, profile) = file.profile(Some("simnow-7x24")).unwrap();
+        assert_eq!(name, "simnow-7x24");
+        let config = profile.to_config();
+        assert_eq!(config.md_front_address, "tcp://182.254.243.31:40011");
+        assert!(config.validate().is_ok());
+
+        let (_, production) = file.profile(Some("production")).unwrap();
+        let accounts = production.accounts();
+        assert_eq!(accounts.len(), 2);
+        assert_eq!(accounts[0].config.broker_id, "8888");
+        assert_eq!(accounts[0].config.app_id, "client_app_1.0");
+        assert_eq!(accounts[1].config.broker_id, "7777");
+        assert_eq!(accounts[1].config.trader_front_addresses.len(), 2);
+        assert!(accounts[0].config.credential_provider.is_none());
+        assert!(accounts[1].config.credential_provider.is_some());
+        assert!(!format!("{:?}", production).contains("pw1"));
+        assert!(file.profile(Some("missing")).is_err());
+
+        let yaml = "profiles:\n  simnow:\n    broker_id: '9999'\n    trader_fronts: ['tcp://182.254.243.31:40001']\n    investor_id: '00001'\n";
+        let file = ConfigFile::from_yaml_str(yaml).unwrap();
+        assert_eq!(file.profile(None).unwrap().0, "simnow");
+
+        assert!(ConfigFile::from_toml_str("[profiles.x]\nunknown = 1").is_err());
+
+        // 仓库中的示例文件必须能够解析
+        let example =
+            ConfigFile::load(Path::new(env!("CARGO_MANIFEST_DIR")).join("ctp.example.toml"))
+                .unwrap();
+        assert_eq!(example.profiles.len(), 3);
+        assert_eq!(example.profiles["production"].accounts().len(), 2);
     }
 }
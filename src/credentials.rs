@@ -0,0 +1,361 @@
+//! 凭证提供者
+//!
+//! 密码和认证码可以不保存在[`CtpConfig`](crate::CtpConfig)中，而是在登录时通过
+//! [`CredentialProvider`]临时获取，用完即清零：
+//!
+//! - [`EnvCredentials`]：从环境变量读取
+//! - [`FileCredentials`]：从`KEY=VALUE`格式的文件读取，Unix下要求文件只有所有者可读写
+//! - [`CommandCredentials`]：执行外部命令（如密码管理器），从标准输出读取
+//!
+//! 配置中设置了提供者时，[`CtpConfig::credentials`](crate::CtpConfig::credentials)
+//! 会调用提供者，否则使用配置中的密码和认证码。
+
+use crate::config::redact;
+use crate::error::{CtpError, CtpResult};
+use std::env;
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::sync::Arc;
+use zeroize::Zeroizing;
+
+/// 登录凭证，释放时清零
+///
+/// `Debug`输出中密码和认证码会被隐去
+#[derive(Clone, Default)]
+pub struct Credentials {
+    password: Zeroizing<String>,
+    auth_code: Zeroizing<String>,
+}
+
+impl Credentials {
+    /// 创建凭证，没有认证码时传入空字符串
+    pub fn new(password: impl Into<String>, auth_code: impl Into<String>) -> Self {
+        Self {
+            password: Zeroizing::new(password.into()),
+            auth_code: Zeroizing::new(auth_code.into()),
+        }
+    }
+
+    /// 密码
+    pub fn password(&self) -> &str {
+        &self.password
+    }
+
+    /// 认证码
+    pub fn auth_code(&self) -> &str {
+        &self.auth_code
+    }
+}
+
+impl fmt::Debug for Credentials {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Credentials")
+            .field("password", &redact(&self.password))
+            .field("auth_code", &redact(&self.auth_code))
+            .finish()
+    }
+}
+
+/// 凭证提供者
+///
+/// 每次登录都会调用一次，实现不应缓存明文
+pub trait CredentialProvider: fmt::Debug + Send + Sync {
+    /// 获取登录凭证
+    fn credentials(&self) -> CtpResult<Credentials>;
+}
+
+/// 从环境变量读取凭证
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EnvCredentials {
+    password_var: String,
+    auth_code_var: String,
+}
+
+impl EnvCredentials {
+    /// 使用`PASSWORD`和`AUTH_CODE`
+    pub fn new() -> Self {
+        Self::with_vars("PASSWORD", "AUTH_CODE")
+    }
+
+    /// 指定环境变量名
+    pub fn with_vars(password_var: &str, auth_code_var: &str) -> Self {
+        Self {
+            password_var: password_var.to_string(),
+            auth_code_var: auth_code_var.to_string(),
+        }
+    }
+}
+
+impl Default for EnvCredentials {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl CredentialProvider for EnvCredentials {
+    fn credentials(&self) -> CtpResult<Credentials> {
+        let password = env::var(&self.password_var).map_err(|_| {
+            CtpError::InvalidParameterError(format!("未设置环境变量{}", self.password_var))
+        })?;
+        let auth_code = env::var(&self.auth_code_var).unwrap_or_default();
+        Ok(Credentials::new(password, auth_code))
+    }
+}
+
+/// 从文件读取凭证
+///
+/// 文件格式与`.env`相同，读取其中的`PASSWORD`和`AUTH_CODE`。Unix下文件权限中
+/// 组和其他用户有任何权限时拒绝读取
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FileCredentials {
+    path: PathBuf,
+}
+
+impl FileCredentials {
+    /// 创建文件凭证提供者
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self { path: path.into() }
+    }
+
+    /// 凭证文件路径
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+}
+
+impl CredentialProvider for FileCredentials {
+    fn credentials(&self) -> CtpResult<Credentials> {
+        check_permissions(&self.path)?;
+        let entries = dotenvy::from_path_iter(&self.path).map_err(|e| {
+            CtpError::InvalidPath(format!("读取凭证文件 {} 失败: {}", self.path.display(), e))
+        })?;
+
+        let mut credentials = Credentials::default();
+        let mut has_password = false;
+        for entry in entries {
+            // 解析错误中可能带有文件内容，只报告文件路径
+            let (key, value) = entry.map_err(|_| {
+                CtpError::InvalidParameterError(format!(
+                    "凭证文件 {} 格式错误",
+                    self.path.display()
+                ))
+            })?;
+            let value = Zeroizing::new(value);
+            match key.as_str() {
+                "PASSWORD" => {
+                    credentials.password = value;
+                    has_password = true;
+                }
+                "AUTH_CODE" => credentials.auth_code = value,
+                _ => {}
+            }
+        }
+        if !has_password {
+            return Err(CtpError::InvalidParameterError(format!(
+                "凭证文件 {} 中没有PASSWORD",
+                self.path.display()
+            )));
+        }
+        Ok(credentials)
+    }
+}
+
+#[cfg(unix)]
+fn check_permissions(path: &Path) -> CtpResult<()> {
+    use std::os::unix::fs::PermissionsExt;
+
+    let metadata = std::fs::metadata(path).map_err(|e| {
+        CtpError::InvalidPath(format!("读取凭证文件 {} 失败: {}", path.display(), e))
+    })?;
+    let mode = metadata.permissions().mode();
+    if mode & 0o077 != 0 {
+        return Err(CtpError::InvalidPath(format!(
+            "凭证文件 {} 的权限 {:o} 过宽，请改为600",
+            path.display(),
+            mode & 0o777
+        )));
+    }
+    Ok(())
+}
+
+#[cfg(not(unix))]
+fn check_permissions(_path: &Path) -> CtpResult<()> {
+    Ok(())
+}
+
+/// 执行外部命令获取凭证
+///
+/// 命令的标准输出第一行为密码，第二行（可选）为认证码。命令退出码不为0时返回错误，
+/// 错误信息中不包含命令输出
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CommandCredentials {
+    program: String,
+    args: Vec<String>,
+}
+
+impl CommandCredentials {
+    /// 创建命令凭证提供者
+    pub fn new<I, S>(program: &str, args: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        Self {
+            program: program.to_string(),
+            args: args.into_iter().map(Into::into).collect(),
+        }
+    }
+
+    /// 从按空白分隔的命令行创建，不支持引号
+    pub fn from_command_line(command_line: &str) -> CtpResult<Self> {
+        let mut parts = command_line.split_whitespace();
+        let program = parts
+            .next()
+            .ok_or_else(|| CtpError::InvalidParameterError("凭证命令为空".to_string()))?;
+        Ok(Self::new(program, parts))
+    }
+}
+
+impl CredentialProvider for CommandCredentials {
+    fn credentials(&self) -> CtpResult<Credentials> {
+        if self.program.is_empty() {
+            return Err(CtpError::InvalidParameterError("凭证命令为空".to_string()));
+        }
+        let output = Command::new(&self.program)
+            .args(&self.args)
+            .stdin(Stdio::null())
+            .stderr(Stdio::inherit())
+            .output()
+            .map_err(|e| CtpError::Other(format!("执行凭证命令 {} 失败: {}", self.program, e)))?;
+        let stdout = Zeroizing::new(output.stdout);
+        if !output.status.success() {
+            return Err(CtpError::Other(format!(
+                "凭证命令 {} 执行失败: {}",
+                self.program, output.status
+            )));
+        }
+
+        let text = std::str::from_utf8(&stdout).map_err(|_| {
+            CtpError::EncodingError(format!("凭证命令 {} 输出不是UTF-8", self.program))
+        })?;
+        let mut lines = text.lines();
+        let password = lines.next().unwrap_or_default();
+        if password.is_empty() {
+            return Err(CtpError::Other(format!(
+                "凭证命令 {} 没有输出密码",
+                self.program
+            )));
+        }
+        Ok(Credentials::new(password, lines.next().unwrap_or_default()))
+    }
+}
+
+/// 配置文件中的凭证来源
+///
+/// TOML示例：`credentials = { file = "/etc/ctp/prod.env" }`、
+/// `credentials = { command = ["pass", "show", "ctp/prod"] }`
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "snake_case", deny_unknown_fields)
+)]
+pub enum CredentialSource {
+    /// 从环境变量读取
+    Env {
+        /// 密码变量名
+        password: String,
+        /// 认证码变量名
+        #[cfg_attr(feature = "serde", serde(default))]
+        auth_code: Option<String>,
+    },
+    /// 从文件读取
+    File(PathBuf),
+    /// 执行命令，第一个元素为程序
+    Command(Vec<String>),
+}
+
+impl CredentialSource {
+    /// 创建对应的凭证提供者
+    pub fn provider(&self) -> Arc<dyn CredentialProvider> {
+        match self {
+            CredentialSource::Env {
+                password,
+                auth_code,
+            } => Arc::new(EnvCredentials::with_vars(
+                password,
+                auth_code.as_deref().unwrap_or("AUTH_CODE"),
+            )),
+            CredentialSource::File(path) => Arc::new(FileCredentials::new(path)),
+            // 空命令在获取凭证时报错
+            CredentialSource::Command(command) => match command.split_first() {
+                Some((program, args)) => {
+                    Arc::new(CommandCredentials::new(program, args.iter().cloned()))
+                }
+                None => Arc::new(CommandCredentials::new("", Vec::<String>::new())),
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    fn temp_file(name: &str, content: &str, mode: u32) -> PathBuf {
+        let dir = env::temp_dir().join(format!("ctp_credentials_{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join(name);
+        fs::write(&path, content).unwrap();
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            fs::set_permissions(&path, fs::Permissions::from_mode(mode)).unwrap();
+        }
+        #[cfg(not(unix))]
+        let _ = mode;
+        path
+    }
+
+    #[test]
+    fn test_debug_is_redacted() {
+        let credentials = Credentials::new("secret-pw", "");
+        let debug = format!("{:?}", credentials);
+        assert!(!debug.contains("secret-pw"));
+        assert!(debug.contains("***"));
+        assert_eq!(credentials.password(), "secret-pw");
+    }
+
+    #[test]
+    fn test_file_credentials() {
+        let path = temp_file("ok.env", "PASSWORD=\"p#w 1\"\nAUTH_CODE=ABCD\n", 0o600);
+        let credentials = FileCredentials::new(&path).credentials().unwrap();
+        assert_eq!(credentials.password(), "p#w 1");
+        assert_eq!(credentials.auth_code(), "ABCD");
+
+        let missing = temp_file("missing.env", "AUTH_CODE=ABCD\n", 0o600);
+        assert!(FileCredentials::new(missing).credentials().is_err());
+
+        #[cfg(unix)]
+        {
+            let open = temp_file("open.env", "PASSWORD=p\n", 0o644);
+            let err = FileCredentials::new(open).credentials().unwrap_err();
+            assert!(err.to_string().contains("644"));
+        }
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_command_credentials() {
+        let provider = CommandCredentials::new("printf", ["pw-from-cmd\\nAUTH"]);
+        let credentials = provider.credentials().unwrap();
+        assert_eq!(credentials.password(), "pw-from-cmd");
+        assert_eq!(credentials.auth_code(), "AUTH");
+
+        let failing = CommandCredentials::from_command_line("false").unwrap();
+        assert!(failing.credentials().is_err());
+        assert!(CommandCredentials::from_command_line("  ").is_err());
+    }
+}
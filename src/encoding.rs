@@ -3,6 +3,7 @@
 //! 处理GB18030和UTF-8之间的编码转换
 
 use crate::error::{encoding_error, CtpResult};
+use crate::fixed_str::FixedStr;
 use encoding::all::GB18030;
 use encoding::{DecoderTrap, EncoderTrap, Encoding};
 use std::ffi::{CStr, CString};
@@ -101,18 +102,9 @@ impl GbkConverter {
     // * `utf8_str` - UTF-8字符串
     //
     // # 返回值
-    // 固定长度的GB18030字节数组
+    // 以空字节结尾的固定长度GB18030字节数组；编码后超过`N - 1`字节时返回错误，不截断
     pub fn utf8_to_fixed_bytes<const N: usize>(utf8_str: &str) -> CtpResult<[u8; N]> {
-        let gb_bytes = Self::utf8_to_gb18030(utf8_str)?;
-        let mut result = [0u8; N];
-
-        if gb_bytes.len() >= N {
-            result.copy_from_slice(&gb_bytes[..N]);
-        } else {
-            result[..gb_bytes.len()].copy_from_slice(&gb_bytes);
-        }
-
-        Ok(result)
+        utf8_str.parse::<FixedStr<N>>().map(FixedStr::into_raw)
     }
 }
 
@@ -2,6 +2,7 @@
 //!
 //! 定义了CTP SDK中使用的错误类型和结果类型
 
+pub use crate::error_code::{CtpErrorCode, ErrorCategory};
 use std::fmt;
 
 /// CTP错误类型
@@ -17,6 +18,8 @@ pub enum CtpError {
     AuthenticationError(String),
     /// 业务逻辑错误
     BusinessError(i32, String),
+    /// 请求函数返回非0值，附带请求说明
+    RequestError(CtpErrorCode, String),
     /// 初始化错误
     InitializationError(String),
     /// 超时错误
@@ -27,6 +30,8 @@ pub enum CtpError {
     MemoryError(String),
     ///无效的路径
     InvalidPath(String),
+    /// 合规检查未通过
+    ComplianceError(String),
     /// 其他错误
     Other(String),
 }
@@ -39,11 +44,15 @@ impl fmt::Display for CtpError {
             CtpError::ConnectionError(msg) => write!(f, "网络连接错误: {}", msg),
             CtpError::AuthenticationError(msg) => write!(f, "登录认证错误: {}", msg),
             CtpError::BusinessError(code, msg) => write!(f, "业务错误 [{}]: {}", code, msg),
+            CtpError::RequestError(code, msg) => {
+                write!(f, "{} [{}]: {}", msg, code.code(), code.message())
+            }
             CtpError::InitializationError(msg) => write!(f, "初始化错误: {}", msg),
             CtpError::TimeoutError(msg) => write!(f, "超时错误: {}", msg),
             CtpError::InvalidParameterError(msg) => write!(f, "无效参数错误: {}", msg),
             CtpError::MemoryError(msg) => write!(f, "内存错误: {}", msg),
             CtpError::InvalidPath(msg) => write!(f, "无效的路径: {}", msg),
+            CtpError::ComplianceError(msg) => write!(f, "合规检查未通过: {}", msg),
             CtpError::Other(msg) => write!(f, "其他错误: {}", msg),
         }
     }
@@ -51,6 +60,55 @@ impl fmt::Display for CtpError {
 
 impl std::error::Error for CtpError {}
 
+impl CtpError {
+    /// 根据`Req*`请求函数的返回值创建错误，`request`为请求说明
+    pub fn request_failed(request: &str, result: i32) -> Self {
+        CtpError::RequestError(
+            CtpErrorCode::from_request_result(result),
+            request.to_string(),
+        )
+    }
+
+    /// 在错误信息前加上上下文，保留错误类型
+    pub fn with_context(self, context: &str) -> Self {
+        let wrap = |msg: String| format!("{}: {}", context, msg);
+        match self {
+            CtpError::FfiError(msg) => CtpError::FfiError(wrap(msg)),
+            CtpError::EncodingError(msg) => CtpError::EncodingError(wrap(msg)),
+            CtpError::ConnectionError(msg) => CtpError::ConnectionError(wrap(msg)),
+            CtpError::AuthenticationError(msg) => CtpError::AuthenticationError(wrap(msg)),
+            CtpError::BusinessError(code, msg) => CtpError::BusinessError(code, wrap(msg)),
+            CtpError::RequestError(code, msg) => CtpError::RequestError(code, wrap(msg)),
+            CtpError::InitializationError(msg) => CtpError::InitializationError(wrap(msg)),
+            CtpError::TimeoutError(msg) => CtpError::TimeoutError(wrap(msg)),
+            CtpError::InvalidParameterError(msg) => CtpError::InvalidParameterError(wrap(msg)),
+            CtpError::MemoryError(msg) => CtpError::MemoryError(wrap(msg)),
+            CtpError::InvalidPath(msg) => CtpError::InvalidPath(wrap(msg)),
+            CtpError::ComplianceError(msg) => CtpError::ComplianceError(wrap(msg)),
+            CtpError::Other(msg) => CtpError::Other(wrap(msg)),
+        }
+    }
+
+    /// CTP错误代码，只有业务错误和请求错误才有
+    pub fn error_code(&self) -> Option<CtpErrorCode> {
+        match self {
+            CtpError::BusinessError(code, _) => Some(CtpErrorCode::from_code(*code)),
+            CtpError::RequestError(code, _) => Some(*code),
+            _ => None,
+        }
+    }
+
+    /// 是否可以稍后重试
+    ///
+    /// 连接和超时错误总是可以重试，业务错误和请求错误按错误代码判断
+    pub fn is_retryable(&self) -> bool {
+        match self {
+            CtpError::ConnectionError(_) | CtpError::TimeoutError(_) => true,
+            _ => self.error_code().is_some_and(|code| code.is_retryable()),
+        }
+    }
+}
+
 /// CTP结果类型
 pub type CtpResult<T> = Result<T, CtpError>;
 
@@ -0,0 +1,323 @@
+//! CTP错误代码
+//!
+//! [`CtpErrorCode`]对应CTP官方错误列表（`error.xml`）中的错误代码，以及`Req*`请求函数
+//! 的负数返回值（-1网络连接失败、-2未处理请求超过许可数、-3每秒发送请求数超过许可数）。
+//! 银期转帐的1xxx错误代码归入[`ErrorCategory::Transfer`]。
+//!
+//! 每个代码都有中英文说明、所属[`ErrorCategory`]以及是否可以重试。列表中没有的代码
+//! 映射为[`CtpErrorCode::Unknown`]，此时以柜台返回的错误信息为准。
+
+use crate::types::RspInfoField;
+use std::fmt;
+
+/// 错误类别
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ErrorCategory {
+    /// 成功
+    Success,
+    /// 网络或前置连接
+    Network,
+    /// 流量控制
+    FlowControl,
+    /// 登录、认证与权限
+    Authentication,
+    /// 报单及报单操作
+    Order,
+    /// 资金
+    Funds,
+    /// 持仓
+    Position,
+    /// 结算
+    Settlement,
+    /// 柜台系统状态与数据
+    System,
+    /// 银期转帐
+    Transfer,
+    /// 未收录的错误
+    Unknown,
+}
+
+macro_rules! ctp_error_codes {
+    ($($variant:ident = $code:literal, $category:ident, $zh:literal, $en:literal;)*) => {
+        /// CTP错误代码
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+        pub enum CtpErrorCode {
+            $(
+                #[doc = $zh]
+                $variant,
+            )*
+            /// 未收录的错误代码
+            Unknown(i32),
+        }
+
+        impl CtpErrorCode {
+            /// 从错误代码创建
+            pub fn from_code(code: i32) -> Self {
+                match code {
+                    $($code => CtpErrorCode::$variant,)*
+                    other => CtpErrorCode::Unknown(other),
+                }
+            }
+
+            /// 错误代码
+            pub fn code(&self) -> i32 {
+                match self {
+                    $(CtpErrorCode::$variant => $code,)*
+                    CtpErrorCode::Unknown(code) => *code,
+                }
+            }
+
+            /// 错误类别
+            pub fn category(&self) -> ErrorCategory {
+                match self {
+                    $(CtpErrorCode::$variant => ErrorCategory::$category,)*
+                    CtpErrorCode::Unknown(_) => ErrorCategory::Unknown,
+                }
+            }
+
+            /// 中文说明
+            pub fn message(&self) -> &'static str {
+                match self {
+                    $(CtpErrorCode::$variant => $zh,)*
+                    CtpErrorCode::Unknown(_) => "未收录的错误",
+                }
+            }
+
+            /// 英文说明
+            pub fn message_en(&self) -> &'static str {
+                match self {
+                    $(CtpErrorCode::$variant => $en,)*
+                    CtpErrorCode::Unknown(_) => "unknown error",
+                }
+            }
+        }
+    };
+}
+
+ctp_error_codes! {
+    RequestNetworkFailure = -1, Network, "网络连接失败", "network connection failed";
+    RequestQueueFull = -2, FlowControl, "未处理请求超过许可数", "too many unprocessed requests";
+    RequestRateLimited = -3, FlowControl, "每秒发送请求数超过许可数", "requests per second over limit";
+    NoError = 0, Success, "正确", "no error";
+    InvalidDataSyncStatus = 1, System, "不在已同步状态", "data not synchronized";
+    InconsistentInformation = 2, System, "会话信息不一致", "inconsistent session information";
+    InvalidLogin = 3, Authentication, "不合法的登录", "invalid login";
+    UserNotActive = 4, Authentication, "用户不活跃", "user not active";
+    DuplicateLogin = 5, Authentication, "重复的登录", "duplicate login";
+    NotLoginYet = 6, Authentication, "还没有登录", "not logged in";
+    NotInited = 7, System, "还没有初始化", "not initialized";
+    FrontNotActive = 8, Network, "前置不活跃", "front not active";
+    NoPrivilege = 9, Authentication, "无此权限", "no privilege";
+    ChangeOtherPassword = 10, Authentication, "修改别人的口令", "cannot change another user's password";
+    UserNotFound = 11, Authentication, "找不到该用户", "user not found";
+    BrokerNotFound = 12, Authentication, "找不到该经纪公司", "broker not found";
+    InvestorNotFound = 13, Authentication, "找不到投资者", "investor not found";
+    OldPasswordMismatch = 14, Authentication, "原口令不匹配", "old password mismatch";
+    BadField = 15, Order, "报单字段有误", "bad order field";
+    InstrumentNotFound = 16, Order, "找不到合约", "instrument not found";
+    InstrumentNotTrading = 17, Order, "合约不能交易", "instrument not trading";
+    NotExchangeParticipant = 18, Order, "经纪公司不是交易所的会员", "broker is not an exchange participant";
+    InvestorNotActive = 19, Authentication, "投资者不活跃", "investor not active";
+    NotExchangeClient = 20, Order, "投资者未在交易所开户", "investor has no exchange client id";
+    NoValidTraderAvailable = 21, Network, "该交易席位未连接到交易所", "trading seat not connected to exchange";
+    DuplicateOrderRef = 22, Order, "不允许重复报单", "duplicate order ref";
+    BadOrderActionField = 23, Order, "错误的报单操作字段", "bad order action field";
+    DuplicateOrderActionRef = 24, Order, "撤单已报送，不允许重复撤单", "duplicate order action";
+    OrderNotFound = 25, Order, "撤单找不到相应报单", "order not found";
+    InsuitableOrderStatus = 26, Order, "报单已全成交或已撤销，不能再撤", "order already filled or canceled";
+    UnsupportedFunction = 27, System, "不支持的功能", "unsupported function";
+    NoTradingRight = 28, Order, "没有报单交易权限", "no trading right";
+    CloseOnly = 29, Order, "只能平仓", "close only";
+    OverClosePosition = 30, Position, "平仓量超过持仓量", "close volume exceeds position";
+    InsufficientMoney = 31, Funds, "资金不足", "insufficient funds";
+    DuplicatePk = 32, System, "主键重复", "duplicate primary key";
+    CannotFindPk = 33, System, "找不到主键", "primary key not found";
+    CanNotInactiveBroker = 34, System, "设置经纪公司不活跃状态失败", "cannot deactivate broker";
+    BrokerSynchronizing = 35, System, "经纪公司正在同步", "broker synchronizing";
+    BrokerSynchronized = 36, System, "经纪公司已同步", "broker already synchronized";
+    ShortSell = 37, Order, "现货交易不能卖空", "short selling not allowed";
+    InvalidSettlementRef = 38, Settlement, "不合法的结算引用", "invalid settlement ref";
+    ExchangeNetworkError = 39, Network, "交易所网络连接失败", "exchange network error";
+    ExchangeOverRequest = 40, FlowControl, "交易所未处理请求超过许可数", "too many unprocessed exchange requests";
+    ExchangeOverRequestPerSecond = 41, FlowControl, "交易所每秒发送请求数超过许可数", "exchange requests per second over limit";
+    SettlementInfoNotConfirmed = 42, Settlement, "结算结果未确认", "settlement not confirmed";
+    DepositNotFound = 43, Funds, "没有对应的入金记录", "deposit not found";
+    ExchangeTrading = 44, Order, "交易所已经进入连续交易状态", "exchange already in continuous trading";
+    ParkedOrderNotFound = 45, Order, "找不到预埋（撤单）单", "parked order not found";
+    ParkedOrderHasSent = 46, Order, "预埋（撤单）单已经发送", "parked order already sent";
+    ParkedOrderHasDeleted = 47, Order, "预埋（撤单）单已经删除", "parked order already deleted";
+    InvalidInvestorIdOrPassword = 48, Authentication, "无效的投资者或者密码", "invalid investor id or password";
+    InvalidLoginIpAddress = 49, Authentication, "不合法的登录IP地址", "invalid login IP address";
+    OverCloseTodayPosition = 50, Position, "平今仓位不足", "insufficient today position to close";
+    OverCloseYesterdayPosition = 51, Position, "平昨仓位不足", "insufficient yesterday position to close";
+    BrokerNotEnoughCondOrder = 52, Order, "经纪公司没有足够可用的条件单数量", "broker conditional order quota exhausted";
+    InvestorNotEnoughCondOrder = 53, Order, "投资者没有足够可用的条件单数量", "investor conditional order quota exhausted";
+    BrokerNotSupportCondOrder = 54, Order, "经纪公司不支持条件单", "broker does not support conditional orders";
+    ResendOrderBrokerInvestor = 55, Order, "重发未知单经纪公司/投资者不匹配", "resend order broker/investor mismatch";
+    SyncOtpFailed = 56, Authentication, "同步动态令牌失败", "OTP synchronization failed";
+    OtpMismatch = 57, Authentication, "动态令牌校验错误", "OTP mismatch";
+    OtpParamNotFound = 58, Authentication, "找不到动态令牌配置信息", "OTP configuration not found";
+    UnsupportedOtpType = 59, Authentication, "不支持的动态令牌类型", "unsupported OTP type";
+    SingleUserSessionExceedLimit = 60, Authentication, "用户在线会话超出上限", "too many sessions for user";
+    ExchangeUnsupportedArbitrage = 61, Order, "该交易所不支持套利类型报单", "exchange does not support arbitrage orders";
+    NoConditionalOrderRight = 62, Order, "没有条件单交易权限", "no conditional order right";
+    AuthFailed = 63, Authentication, "客户端认证失败", "client authentication failed";
+    NotAuthenticated = 64, Authentication, "客户端未认证", "client not authenticated";
+    SwapOrderUnsupported = 65, Order, "该合约不支持互换类型报单", "instrument does not support swap orders";
+    OptionsOnlySupportSpec = 66, Order, "该期权合约只支持投机类型报单", "option only supports speculation";
+    DuplicateExecOrderRef = 67, Order, "执行宣告错误，不允许重复执行", "duplicate exec order ref";
+    ResendExecOrderBrokerInvestor = 68, Order, "重发未知执行宣告经纪公司/投资者不匹配", "resend exec order broker/investor mismatch";
+    ExecOrderNotOptions = 69, Order, "只有期权合约可执行", "only options can be exercised";
+    OptionsNotSupportExec = 70, Order, "该期权合约不支持执行", "option does not support exercise";
+    BadExecOrderActionField = 71, Order, "执行宣告字段有误", "bad exec order action field";
+    DuplicateExecOrderActionRef = 72, Order, "执行宣告撤单已报送，不允许重复撤单", "duplicate exec order action";
+    ExecOrderNotFound = 73, Order, "执行宣告撤单找不到相应执行宣告", "exec order not found";
+    OverExecutePosition = 74, Position, "执行仓位不足", "insufficient position to exercise";
+    LoginForbidden = 75, Authentication, "连续登录失败次数超限，登录被禁止", "login forbidden after too many failures";
+    InvalidTransferAgent = 76, Transfer, "非法银期代理关系", "invalid bank-futures transfer agent";
+    NoFoundFunction = 77, System, "无此功能", "function not found";
+    SendExchangeOrderFailed = 78, Order, "发送报单失败", "failed to send order to exchange";
+    SendExchangeOrderActionFailed = 79, Order, "发送报单操作失败", "failed to send order action to exchange";
+    PriceTypeNotSupportedByExchange = 80, Order, "交易所不支持的价格类型", "price type not supported by exchange";
+    BadExecuteType = 81, Order, "错误的执行类型", "bad execute type";
+    BadOptionInstrument = 82, Order, "无效的组合合约", "invalid combination instrument";
+    InstrumentNotSupportForQuote = 83, Order, "该合约不支持询价", "instrument does not support quote requests";
+    ResendQuoteBrokerInvestor = 84, Order, "重发未知报价经纪公司/投资者不匹配", "resend quote broker/investor mismatch";
+    InstrumentNotSupportQuote = 85, Order, "该合约不支持报价", "instrument does not support quotes";
+    QuoteNotFound = 86, Order, "报价撤单找不到相应报价", "quote not found";
+    OptionsNotSupportAbandon = 87, Order, "该期权合约不支持放弃执行", "option does not support abandoning exercise";
+    CombOptionsSupportIocOnly = 88, Order, "该组合期权合约只支持IOC", "combination option only supports IOC";
+    OpenFileFailed = 89, System, "打开文件失败", "failed to open file";
+    NeedRetry = 90, FlowControl, "查询未就绪，请稍后重试", "query not ready, retry later";
+    ExchangeRtnError = 91, Order, "交易所返回的错误", "error returned by exchange";
+    WeakPassword = 131, Authentication, "口令强度不够", "password too weak";
+    FirstLoginChangePassword = 140, Authentication, "首次登录必须修改密码", "password change required on first login";
+    NoValidBankOfferAvailable = 1000, Transfer, "银期转账：发送机构代码错误", "transfer: bad sending institution code";
+    PasswordMismatch = 1001, Transfer, "银期转账：取平台流水号错误", "transfer: failed to get platform serial";
+    DuplicateBankSerial = 1004, Transfer, "银期转账：重复的银行流水", "transfer: duplicate bank serial";
+    DuplicateOfferSerial = 1005, Transfer, "银期转账：重复的转账流水", "transfer: duplicate transfer serial";
+    SerialNotExist = 1006, Transfer, "银期转账：被冲正流水不存在(冲正交易)", "transfer: serial to repeal not found";
+    SerialIsRepealed = 1007, Transfer, "银期转账：原流水已冲正(冲正交易)", "transfer: serial already repealed";
+    SerialMismatch = 1008, Transfer, "银期转账：与原流水信息不符(冲正交易)", "transfer: repeal does not match original serial";
+    IdentifiedCardNoMismatch = 1009, Transfer, "银期转账：证件号码或类型错误", "transfer: wrong id card number or type";
+    AccountNotFound = 1011, Transfer, "银期转账：资金账户不存在", "transfer: account not found";
+    AccountNotActive = 1012, Transfer, "银期转账：资金账户已经销户", "transfer: account closed";
+    NotAllowRepealByManual = 1013, Transfer, "银期转账：该交易不能执行手工冲正", "transfer: manual repeal not allowed";
+    AmountOutOfTheWay = 1014, Transfer, "银期转账：转帐金额错误", "transfer: invalid amount";
+    ExchangeRateNotFound = 1015, Transfer, "银期转账：找不到汇率", "transfer: exchange rate not found";
+    WaitingOfferRsp = 999999, Transfer, "等待银期报盘处理结果", "waiting for bank-futures offer result";
+}
+
+impl CtpErrorCode {
+    /// 从`Req*`请求函数的返回值创建，0表示请求已发出
+    pub fn from_request_result(result: i32) -> Self {
+        Self::from_code(result)
+    }
+
+    /// 是否为成功
+    pub fn is_success(&self) -> bool {
+        *self == CtpErrorCode::NoError
+    }
+
+    /// 是否可以稍后原样重试
+    ///
+    /// 网络和流量控制类错误，以及柜台数据尚未就绪的错误可以重试；其余错误重试也会得到
+    /// 相同的结果，需要先修正请求或账户状态
+    pub fn is_retryable(&self) -> bool {
+        matches!(
+            self.category(),
+            ErrorCategory::Network | ErrorCategory::FlowControl
+        ) || matches!(
+            self,
+            CtpErrorCode::InvalidDataSyncStatus | CtpErrorCode::BrokerSynchronizing
+        )
+    }
+}
+
+impl fmt::Display for CtpErrorCode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "[{}] {}", self.code(), self.message())
+    }
+}
+
+impl From<i32> for CtpErrorCode {
+    fn from(code: i32) -> Self {
+        Self::from_code(code)
+    }
+}
+
+impl From<&RspInfoField> for CtpErrorCode {
+    fn from(rsp: &RspInfoField) -> Self {
+        Self::from_code(rsp.error_id)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::CtpError;
+
+    #[test]
+    fn test_known_codes() {
+        let code = CtpErrorCode::from_code(31);
+        assert_eq!(code, CtpErrorCode::InsufficientMoney);
+        assert_eq!(code.category(), ErrorCategory::Funds);
+        assert_eq!(code.message(), "资金不足");
+        assert_eq!(code.message_en(), "insufficient funds");
+        assert!(!code.is_retryable());
+
+        assert_eq!(CtpErrorCode::from(22), CtpErrorCode::DuplicateOrderRef);
+        assert_eq!(CtpErrorCode::from(50).category(), ErrorCategory::Position);
+        assert!(CtpErrorCode::from(90).is_retryable());
+        assert_eq!(CtpErrorCode::from(3).to_string(), "[3] 不合法的登录");
+        assert_eq!(CtpErrorCode::from(74), CtpErrorCode::OverExecutePosition);
+        assert_eq!(
+            CtpErrorCode::from(75).category(),
+            ErrorCategory::Authentication
+        );
+        assert_eq!(CtpErrorCode::from(1007).category(), ErrorCategory::Transfer);
+
+        let mut rsp = RspInfoField::default();
+        assert!(CtpErrorCode::from(&rsp).is_success());
+        rsp.error_id = 42;
+        assert_eq!(
+            CtpErrorCode::from(&rsp),
+            CtpErrorCode::SettlementInfoNotConfirmed
+        );
+    }
+
+    #[test]
+    fn test_request_results_and_unknown() {
+        for (result, expected) in [
+            (-1, CtpErrorCode::RequestNetworkFailure),
+            (-2, CtpErrorCode::RequestQueueFull),
+            (-3, CtpErrorCode::RequestRateLimited),
+        ] {
+            let code = CtpErrorCode::from_request_result(result);
+            assert_eq!(code, expected);
+            assert_eq!(code.code(), result);
+            assert!(code.is_retryable());
+        }
+
+        let unknown = CtpErrorCode::from_code(9999);
+        assert_eq!(unknown, CtpErrorCode::Unknown(9999));
+        assert_eq!(unknown.code(), 9999);
+        assert_eq!(unknown.category(), ErrorCategory::Unknown);
+        assert!(!unknown.is_retryable());
+    }
+
+    #[test]
+    fn test_ctp_error_classification() {
+        let err = CtpError::request_failed("登录请求失败", -2);
+        assert_eq!(err.error_code(), Some(CtpErrorCode::RequestQueueFull));
+        assert_eq!(err.to_string(), "登录请求失败 [-2]: 未处理请求超过许可数");
+        assert!(err.is_retryable());
+
+        let err = CtpError::BusinessError(31, "CTP:资金不足".to_string());
+        assert_eq!(err.error_code(), Some(CtpErrorCode::InsufficientMoney));
+        assert!(!err.is_retryable());
+
+        assert!(CtpError::TimeoutError("查询超时".to_string()).is_retryable());
+        assert_eq!(CtpError::Other(String::new()).error_code(), None);
+    }
+}
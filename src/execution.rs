@@ -0,0 +1,1017 @@
+//! 执行算法
+//!
+//! 把一笔母单拆成子单逐步执行，由[`AsyncTraderApi::execute`](crate::api::AsyncTraderApi::execute)
+//! 启动：
+//!
+//! - [`ExecutionAlgo::Twap`] - 在时间窗口内均匀分片
+//! - [`ExecutionAlgo::Vwap`] - 按历史成交量分布（[`VolumeCurve::from_ticks`]）分片
+//! - [`ExecutionAlgo::Iceberg`] - 只挂出可见数量，成交后补单
+//! - [`ExecutionAlgo::Peg`] - 挂在买一/卖一价，盘口离开子单时撤单重挂
+//!
+//! 任意时刻最多只有一笔子单在途：需要换价或进入新分片时先撤掉旧子单，确认结束后再发新子单，
+//! 不会超量成交。母单限价须为最小变动价位的整数倍，子单价格按价位取整且不劣于限价；
+//! 报单和撤单按前置的FTD流控控制节奏。[`ExecutionHandle`]用于暂停、恢复、撤销母单以及查看子单和进度。
+
+use crate::api::md_api::DepthMarketDataField;
+use crate::api::order_handle::{field_string, OrderHandle, OrderOutcome};
+use crate::api::trader_api::InputOrderField;
+use crate::api::working_orders::pacing_interval;
+use crate::api::AsyncTraderApi;
+use crate::error::{CtpError, CtpResult};
+use crate::models::{parse_time, Direction};
+use crate::types::StringConvert;
+use std::sync::Weak;
+use std::time::Duration;
+use tokio::sync::watch;
+use tokio::time::{sleep, sleep_until, timeout, Instant};
+use tracing::{info, warn};
+
+/// 撤单后迟迟未结束时重发撤单的间隔
+const CANCEL_RETRY: Duration = Duration::from_secs(3);
+
+/// 成交量分布曲线，每个分片一个权重
+#[derive(Debug, Clone, PartialEq)]
+pub struct VolumeCurve {
+    weights: Vec<f64>,
+}
+
+impl VolumeCurve {
+    /// 均匀分布
+    pub fn uniform(buckets: usize) -> Self {
+        Self {
+            weights: vec![1.0; buckets.max(1)],
+        }
+    }
+
+    /// 直接给出各分片权重，负值按0处理，全为0时退化为均匀分布
+    pub fn from_weights(weights: Vec<f64>) -> CtpResult<Self> {
+        if weights.is_empty() {
+            return Err(CtpError::InvalidParameterError(
+                "成交量曲线至少需要一个分片".to_string(),
+            ));
+        }
+        let weights: Vec<f64> = weights.into_iter().map(|w| w.max(0.0)).collect();
+        if weights.iter().sum::<f64>() <= 0.0 {
+            return Ok(Self::uniform(weights.len()));
+        }
+        Ok(Self { weights })
+    }
+
+    /// 由录制的行情统计`[start, end)`时段内各分片的成交量
+    ///
+    /// 成交量取相邻两笔行情累计成交量之差，时段不能跨越午夜
+    pub fn from_ticks(
+        ticks: &[DepthMarketDataField],
+        start: &str,
+        end: &str,
+        buckets: usize,
+    ) -> CtpResult<Self> {
+        let seconds = |s: &str| {
+            parse_time(s).map(|t| {
+                f64::from(t.hour()) * 3600.0 + f64::from(t.minute()) * 60.0 + f64::from(t.second())
+            })
+        };
+        let (Some(start), Some(end)) = (seconds(start), seconds(end)) else {
+            return Err(CtpError::InvalidParameterError(format!(
+                "无法解析时段 {} - {}",
+                start, end
+            )));
+        };
+        if buckets == 0 || end <= start {
+            return Err(CtpError::InvalidParameterError(
+                "时段结束时间必须晚于开始时间，分片数必须大于0".to_string(),
+            ));
+        }
+
+        let mut weights = vec![0.0; buckets];
+        let mut previous: Option<i32> = None;
+        for tick in ticks {
+            let Some(at) = tick
+                .update_time
+                .to_utf8_string()
+                .ok()
+                .and_then(|t| seconds(&t))
+            else {
+                continue;
+            };
+            let at = at + f64::from(tick.update_millisec) / 1000.0;
+            let traded = previous.map_or(0, |p| (tick.volume - p).max(0));
+            previous = Some(tick.volume);
+            if at >= start && at < end {
+                let bucket = ((at - start) / (end - start) * buckets as f64) as usize;
+                weights[bucket.min(buckets - 1)] += f64::from(traded);
+            }
+        }
+        Self::from_weights(weights)
+    }
+
+    /// 分片数
+    pub fn len(&self) -> usize {
+        self.weights.len()
+    }
+
+    /// 是否没有分片
+    pub fn is_empty(&self) -> bool {
+        self.weights.is_empty()
+    }
+
+    /// 各分片结束时应完成的累计数量，最后一片等于`total`
+    pub fn cumulative_targets(&self, total: i32) -> Vec<i32> {
+        let sum: f64 = self.weights.iter().sum();
+        let mut acc = 0.0;
+        self.weights
+            .iter()
+            .map(|w| {
+                acc += w;
+                (f64::from(total) * acc / sum).round() as i32
+            })
+            .collect()
+    }
+}
+
+/// 执行算法
+#[derive(Debug, Clone, PartialEq)]
+pub enum ExecutionAlgo {
+    /// 在`duration`内均匀分成`slices`片，每片以对手价发出
+    Twap { duration: Duration, slices: usize },
+    /// 在`duration`内按成交量曲线分片，每片以对手价发出
+    Vwap {
+        duration: Duration,
+        curve: VolumeCurve,
+    },
+    /// 以母单限价只挂出`visible_volume`，成交后补单
+    Iceberg { visible_volume: i32 },
+    /// 挂在本方最优价加`offset_ticks`个价位（正数更激进），最多到对手价内一个价位；
+    /// 本方最优价离开子单时撤单重挂，最优价只有子单自己的挂单时不算作盘口变化
+    Peg { offset_ticks: i32 },
+}
+
+/// 最优买卖价
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Quote {
+    /// 买一价
+    pub bid: f64,
+    /// 买一量
+    pub bid_volume: i32,
+    /// 卖一价
+    pub ask: f64,
+    /// 卖一量
+    pub ask_volume: i32,
+}
+
+impl Quote {
+    /// 从深度行情提取，无效价格（涨跌停时的一侧为空）返回`None`
+    pub fn from_market_data(md: &DepthMarketDataField) -> Option<Self> {
+        let valid = |p: f64| p > 0.0 && p < f64::MAX;
+        (valid(md.bid_price1) && valid(md.ask_price1)).then_some(Self {
+            bid: md.bid_price1,
+            bid_volume: md.bid_volume1,
+            ask: md.ask_price1,
+            ask_volume: md.ask_volume1,
+        })
+    }
+}
+
+/// 母单参数
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct ParentSpec {
+    direction: Direction,
+    total: i32,
+    limit_price: f64,
+    price_tick: f64,
+}
+
+impl ParentSpec {
+    fn validate(&self) -> CtpResult<()> {
+        if self.total <= 0 || self.limit_price <= 0.0 || self.price_tick <= 0.0 {
+            return Err(CtpError::InvalidParameterError(
+                "母单数量、限价和最小变动价位必须大于0".to_string(),
+            ));
+        }
+        if !matches!(self.direction, Direction::Buy | Direction::Sell) {
+            return Err(CtpError::InvalidParameterError(
+                "母单买卖方向无效".to_string(),
+            ));
+        }
+        let ticks = self.limit_price / self.price_tick;
+        if (ticks - ticks.round()).abs() > 1e-6 {
+            return Err(CtpError::InvalidParameterError(format!(
+                "母单限价 {} 不是最小变动价位 {} 的整数倍",
+                self.limit_price, self.price_tick
+            )));
+        }
+        Ok(())
+    }
+
+    // 取整到价位，买单向下、卖单向上，并限制在母单限价以内
+    fn bound(&self, price: f64) -> f64 {
+        let ticks = price / self.price_tick;
+        let ticks = match self.direction {
+            Direction::Sell => (ticks - 1e-6).ceil(),
+            _ => (ticks + 1e-6).floor(),
+        };
+        let price = ticks * self.price_tick;
+        match self.direction {
+            Direction::Sell => price.max(self.limit_price),
+            _ => price.min(self.limit_price),
+        }
+    }
+
+    // 对手价，没有行情时使用限价
+    fn aggressive(&self, quote: Option<Quote>) -> f64 {
+        match (quote, self.direction) {
+            (Some(q), Direction::Sell) => self.bound(q.bid),
+            (Some(q), _) => self.bound(q.ask),
+            (None, _) => self.bound(self.limit_price),
+        }
+    }
+
+    fn ticks(&self, price: f64) -> i64 {
+        (price / self.price_tick).round() as i64
+    }
+
+    // 子单价格是否落后于目标价：买单低于目标价，卖单高于目标价
+    fn is_behind(&self, price: f64, target: f64) -> bool {
+        match self.direction {
+            Direction::Sell => self.ticks(price) > self.ticks(target),
+            _ => self.ticks(price) < self.ticks(target),
+        }
+    }
+}
+
+/// 在途子单的价格和未成交数量
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Resting {
+    price: f64,
+    volume: i32,
+}
+
+/// 当前应在途的子单
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct ChildPlan {
+    volume: i32,
+    price: f64,
+    // 与在途子单不同时需要撤单重发：TWAP/VWAP为分片序号，挂单跟价为价格
+    key: i64,
+}
+
+impl ExecutionAlgo {
+    fn validate(&self) -> CtpResult<()> {
+        let valid = match self {
+            Self::Twap { slices, .. } => *slices > 0,
+            Self::Vwap { curve, .. } => !curve.is_empty(),
+            Self::Iceberg { visible_volume } => *visible_volume > 0,
+            Self::Peg { .. } => true,
+        };
+        if valid {
+            Ok(())
+        } else {
+            Err(CtpError::InvalidParameterError(format!(
+                "执行算法参数无效: {:?}",
+                self
+            )))
+        }
+    }
+
+    fn plan(
+        &self,
+        spec: &ParentSpec,
+        elapsed: Duration,
+        filled: i32,
+        quote: Option<Quote>,
+        resting: Option<Resting>,
+    ) -> Option<ChildPlan> {
+        let remaining = spec.total - filled;
+        let plan = match self {
+            Self::Twap { duration, slices } => {
+                let curve = VolumeCurve::uniform(*slices);
+                Self::scheduled(spec, &curve, *duration, elapsed, filled, quote)
+            }
+            Self::Vwap { duration, curve } => {
+                Self::scheduled(spec, curve, *duration, elapsed, filled, quote)
+            }
+            Self::Iceberg { visible_volume } => ChildPlan {
+                volume: (*visible_volume).min(remaining),
+                price: spec.bound(spec.limit_price),
+                key: 0,
+            },
+            Self::Peg { offset_ticks } => {
+                let quote = quote?;
+                let offset = f64::from(*offset_ticks) * spec.price_tick;
+                let (best, best_volume, target) = match spec.direction {
+                    Direction::Sell => (
+                        quote.ask,
+                        quote.ask_volume,
+                        spec.bound((quote.ask - offset).max(quote.bid + spec.price_tick)),
+                    ),
+                    _ => (
+                        quote.bid,
+                        quote.bid_volume,
+                        spec.bound((quote.bid + offset).min(quote.ask - spec.price_tick)),
+                    ),
+                };
+                let price = match resting {
+                    // 最优价上只有子单自己，或盘口没有离开子单，保持原价
+                    Some(child)
+                        if (spec.ticks(best) == spec.ticks(child.price)
+                            && best_volume <= child.volume)
+                            || !spec.is_behind(child.price, target) =>
+                    {
+                        child.price
+                    }
+                    _ => target,
+                };
+                ChildPlan {
+                    volume: remaining,
+                    price,
+                    key: spec.ticks(price),
+                }
+            }
+        };
+        (plan.volume > 0).then_some(plan)
+    }
+
+    // 按分片计划，当前分片结束时应完成的累计数量减去已成交数量
+    fn scheduled(
+        spec: &ParentSpec,
+        curve: &VolumeCurve,
+        duration: Duration,
+        elapsed: Duration,
+        filled: i32,
+        quote: Option<Quote>,
+    ) -> ChildPlan {
+        let slice_len = duration.as_secs_f64() / curve.len() as f64;
+        let slice = if slice_len > 0.0 {
+            ((elapsed.as_secs_f64() / slice_len) as usize).min(curve.len() - 1)
+        } else {
+            curve.len() - 1
+        };
+        ChildPlan {
+            volume: curve.cumulative_targets(spec.total)[slice] - filled,
+            price: spec.aggressive(quote),
+            key: slice as i64,
+        }
+    }
+}
+
+/// 执行参数
+#[derive(Debug, Clone, PartialEq)]
+pub struct ExecutionConfig {
+    /// 最小变动价位
+    pub price_tick: f64,
+    /// 每秒最多报单、撤单笔数，`None`时使用前置的FTD流控
+    pub max_orders_per_second: Option<u32>,
+    /// 检查子单状态的间隔（毫秒）
+    pub poll_interval_ms: u64,
+}
+
+impl ExecutionConfig {
+    /// 以合约的最小变动价位创建
+    pub fn new(price_tick: f64) -> Self {
+        Self {
+            price_tick,
+            max_orders_per_second: None,
+            poll_interval_ms: 200,
+        }
+    }
+
+    /// 设置每秒最多报单、撤单笔数
+    pub fn with_max_orders_per_second(mut self, per_second: u32) -> Self {
+        self.max_orders_per_second = Some(per_second);
+        self
+    }
+
+    /// 设置检查子单状态的间隔
+    pub fn with_poll_interval_ms(mut self, poll_interval_ms: u64) -> Self {
+        self.poll_interval_ms = poll_interval_ms.max(10);
+        self
+    }
+}
+
+/// 母单状态
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ExecutionStatus {
+    /// 执行中
+    Running,
+    /// 已暂停，在途子单已撤
+    Paused,
+    /// 全部成交
+    Completed,
+    /// 已撤销
+    Cancelled,
+    /// 出错终止
+    Failed(String),
+}
+
+impl ExecutionStatus {
+    /// 是否已结束
+    pub fn is_finished(&self) -> bool {
+        matches!(self, Self::Completed | Self::Cancelled | Self::Failed(_))
+    }
+}
+
+/// 子单
+#[derive(Debug, Clone, PartialEq)]
+pub struct ChildOrder {
+    /// 报单引用
+    pub order_ref: String,
+    /// 价格
+    pub price: f64,
+    /// 数量
+    pub volume: i32,
+    /// 已成交数量
+    pub filled: i32,
+    /// 成交均价，未成交时为0
+    pub avg_price: f64,
+    /// 最终结果，未结束时为`None`
+    pub outcome: Option<OrderOutcome>,
+}
+
+/// 母单进度
+#[derive(Debug, Clone, PartialEq)]
+pub struct ExecutionProgress {
+    /// 状态
+    pub status: ExecutionStatus,
+    /// 母单数量
+    pub total: i32,
+    /// 已成交数量
+    pub filled: i32,
+    /// 成交均价，未成交时为0
+    pub avg_price: f64,
+    /// 已发出的子单
+    pub children: Vec<ChildOrder>,
+}
+
+impl ExecutionProgress {
+    /// 剩余数量
+    pub fn remaining(&self) -> i32 {
+        self.total - self.filled
+    }
+
+    fn refresh_totals(&mut self) {
+        self.filled = self.children.iter().map(|c| c.filled).sum();
+        let amount: f64 = self
+            .children
+            .iter()
+            .map(|c| c.avg_price * f64::from(c.filled))
+            .sum();
+        self.avg_price = if self.filled > 0 {
+            amount / f64::from(self.filled)
+        } else {
+            0.0
+        };
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Control {
+    Run,
+    Pause,
+    Cancel,
+}
+
+/// 母单句柄
+///
+/// 句柄释放视同[`ExecutionHandle::cancel`]：撤掉在途子单后以[`ExecutionStatus::Cancelled`]结束
+pub struct ExecutionHandle {
+    control: watch::Sender<Control>,
+    quote: watch::Sender<Option<Quote>>,
+    progress: watch::Receiver<ExecutionProgress>,
+}
+
+impl ExecutionHandle {
+    /// 更新盘口，由行情回调调用；TWAP/VWAP以此作为对手价，挂单跟价以此重挂
+    pub fn update_quote(&self, market_data: &DepthMarketDataField) {
+        if let Some(quote) = Quote::from_market_data(market_data) {
+            self.quote.send_replace(Some(quote));
+        }
+    }
+
+    /// 暂停：撤掉在途子单且不再发新子单；TWAP/VWAP的时间表不顺延，恢复后补足落后的数量
+    pub fn pause(&self) {
+        self.control.send_replace(Control::Pause);
+    }
+
+    /// 恢复执行
+    pub fn resume(&self) {
+        self.control.send_if_modified(|control| {
+            let paused = *control == Control::Pause;
+            if paused {
+                *control = Control::Run;
+            }
+            paused
+        });
+    }
+
+    /// 撤销母单：撤掉在途子单后结束，已成交部分保留
+    pub fn cancel(&self) {
+        self.control.send_replace(Control::Cancel);
+    }
+
+    /// 当前进度
+    pub fn progress(&self) -> ExecutionProgress {
+        self.progress.borrow().clone()
+    }
+
+    /// 等待母单结束
+    pub async fn wait(&self, timeout_secs: u64) -> CtpResult<ExecutionProgress> {
+        let mut progress = self.progress.clone();
+        let wait = async {
+            progress
+                .wait_for(|p| p.status.is_finished())
+                .await
+                .map(|p| p.clone())
+        };
+        match timeout(Duration::from_secs(timeout_secs), wait).await {
+            Ok(Ok(progress)) => Ok(progress),
+            // 执行任务退出时总会先发布终态
+            Ok(Err(_)) => Ok(self.progress()),
+            Err(_) => Err(CtpError::TimeoutError("等待母单执行结束超时".to_string())),
+        }
+    }
+}
+
+struct ActiveChild {
+    handle: OrderHandle,
+    index: usize,
+    key: i64,
+    cancel_sent: Option<Instant>,
+}
+
+struct Runner {
+    api: Weak<AsyncTraderApi>,
+    template: InputOrderField,
+    algo: ExecutionAlgo,
+    spec: ParentSpec,
+    poll_interval: Duration,
+    pace: Duration,
+    last_sent: Option<Instant>,
+    started: Instant,
+    control: watch::Receiver<Control>,
+    quote: watch::Receiver<Option<Quote>>,
+    progress: watch::Sender<ExecutionProgress>,
+    active: Option<ActiveChild>,
+}
+
+/// 启动执行任务
+pub(crate) async fn spawn(
+    api: &std::sync::Arc<AsyncTraderApi>,
+    template: &InputOrderField,
+    algo: ExecutionAlgo,
+    config: ExecutionConfig,
+) -> CtpResult<ExecutionHandle> {
+    algo.validate()?;
+    let spec = ParentSpec {
+        direction: Direction::from(template.direction),
+        total: template.volume_total_original,
+        limit_price: template.limit_price,
+        price_tick: config.price_tick,
+    };
+    spec.validate()?;
+
+    let per_second = match config.max_orders_per_second {
+        Some(n) => n as i32,
+        None => api
+            .get_state()
+            .await
+            .front_info
+            .map_or(0, |front| front.ftd_pkg_freq),
+    };
+    let (runner, handle) = Runner::new(
+        std::sync::Arc::downgrade(api),
+        template,
+        algo,
+        spec,
+        Duration::from_millis(config.poll_interval_ms),
+        pacing_interval(per_second),
+    );
+    tokio::spawn(runner.run());
+    Ok(handle)
+}
+
+impl Runner {
+    fn new(
+        api: Weak<AsyncTraderApi>,
+        template: &InputOrderField,
+        algo: ExecutionAlgo,
+        spec: ParentSpec,
+        poll_interval: Duration,
+        pace: Duration,
+    ) -> (Self, ExecutionHandle) {
+        let (control, control_rx) = watch::channel(Control::Run);
+        let (quote, quote_rx) = watch::channel(None);
+        let (progress, progress_rx) = watch::channel(ExecutionProgress {
+            status: ExecutionStatus::Running,
+            total: spec.total,
+            filled: 0,
+            avg_price: 0.0,
+            children: Vec::new(),
+        });
+
+        let mut template = template.clone();
+        template.order_ref = Default::default();
+        let runner = Runner {
+            api,
+            template,
+            algo,
+            spec,
+            poll_interval,
+            pace,
+            last_sent: None,
+            started: Instant::now(),
+            control: control_rx,
+            quote: quote_rx,
+            progress,
+            active: None,
+        };
+        let handle = ExecutionHandle {
+            control,
+            quote,
+            progress: progress_rx,
+        };
+        (runner, handle)
+    }
+
+    async fn run(mut self) {
+        // 句柄释放后发送端关闭，`changed`会立即返回错误，此后按撤销处理并只按轮询间隔推进
+        let mut control_open = true;
+        let mut quote_open = true;
+        let status = loop {
+            match self.step().await {
+                Ok(Some(status)) => break status,
+                Ok(None) => {}
+                Err(e) => {
+                    warn!("执行算法: 母单终止: {}", e);
+                    break ExecutionStatus::Failed(e.to_string());
+                }
+            }
+            tokio::select! {
+                changed = self.control.changed(), if control_open => {
+                    control_open = changed.is_ok();
+                }
+                changed = self.quote.changed(), if quote_open => {
+                    quote_open = changed.is_ok();
+                }
+                _ = sleep(self.poll_interval) => {}
+            }
+        };
+        info!("执行算法: 母单结束 {:?}", status);
+        self.progress.send_modify(|p| p.status = status);
+    }
+
+    // 推进一步，母单结束时返回最终状态
+    async fn step(&mut self) -> CtpResult<Option<ExecutionStatus>> {
+        self.sync_child();
+        let filled = self.progress.borrow().filled;
+        if filled >= self.spec.total {
+            return Ok(Some(ExecutionStatus::Completed));
+        }
+
+        // 句柄已释放时按撤销处理
+        let control = if self.control.has_changed().is_err() {
+            Control::Cancel
+        } else {
+            *self.control.borrow()
+        };
+        if control == Control::Cancel && self.active.is_none() {
+            return Ok(Some(ExecutionStatus::Cancelled));
+        }
+        // 交易API释放后无法再报单或撤单，母单随之结束
+        self.api()?;
+
+        match control {
+            Control::Cancel => self.cancel_child().await?,
+            Control::Pause => {
+                self.progress
+                    .send_modify(|p| p.status = ExecutionStatus::Paused);
+                self.cancel_child().await?;
+            }
+            Control::Run => {
+                self.progress
+                    .send_modify(|p| p.status = ExecutionStatus::Running);
+                let quote = *self.quote.borrow();
+                let resting = self.active.as_ref().map(|child| {
+                    let record = &self.progress.borrow().children[child.index];
+                    Resting {
+                        price: record.price,
+                        volume: record.volume - record.filled,
+                    }
+                });
+                let plan =
+                    self.algo
+                        .plan(&self.spec, self.started.elapsed(), filled, quote, resting);
+                match (&self.active, plan) {
+                    (Some(child), Some(plan)) if child.key != plan.key => {
+                        self.cancel_child().await?;
+                    }
+                    (None, Some(plan)) => self.send_child(plan).await?,
+                    _ => {}
+                }
+            }
+        }
+        Ok(None)
+    }
+
+    // 从在途子单的句柄同步成交，子单结束后不再跟踪
+    fn sync_child(&mut self) {
+        let Some(child) = &self.active else {
+            return;
+        };
+        let snapshot = child.handle.snapshot();
+        let index = child.index;
+        let order_ref = child.handle.order_ref();
+        let filled = snapshot.traded_volume();
+        let amount: f64 = snapshot
+            .trades
+            .iter()
+            .map(|t| t.price * f64::from(t.volume))
+            .sum();
+        self.progress.send_modify(|p| {
+            let record = &mut p.children[index];
+            record.order_ref = order_ref;
+            record.filled = filled;
+            record.avg_price = if filled > 0 {
+                amount / f64::from(filled)
+            } else {
+                0.0
+            };
+            record.outcome = snapshot.outcome.clone();
+            p.refresh_totals();
+        });
+        if snapshot.outcome.is_some() {
+            self.active = None;
+        }
+    }
+
+    async fn pace(&mut self) {
+        if let Some(last) = self.last_sent {
+            sleep_until(last + self.pace).await;
+        }
+        self.last_sent = Some(Instant::now());
+    }
+
+    fn api(&self) -> CtpResult<std::sync::Arc<AsyncTraderApi>> {
+        self.api
+            .upgrade()
+            .ok_or_else(|| CtpError::InitializationError("交易API已释放".to_string()))
+    }
+
+    async fn send_child(&mut self, plan: ChildPlan) -> CtpResult<()> {
+        let api = self.api()?;
+        let mut req = self.template.clone();
+        req.volume_total_original = plan.volume;
+        req.limit_price = plan.price;
+        self.pace().await;
+        let handle = match api.order_insert(&req).await {
+            Ok(handle) => handle,
+            // 流控等可重试的错误不终止母单，下一步按报单节奏重新规划并重发
+            Err(e) if e.is_retryable() => {
+                warn!(
+                    "执行算法: 子单 {} 发送失败，稍后重试: {}",
+                    field_string(&self.template.instrument_id),
+                    e
+                );
+                return Ok(());
+            }
+            Err(e) => return Err(e),
+        };
+
+        let mut index = 0;
+        self.progress.send_modify(|p| {
+            index = p.children.len();
+            p.children.push(ChildOrder {
+                order_ref: handle.order_ref(),
+                price: plan.price,
+                volume: plan.volume,
+                filled: 0,
+                avg_price: 0.0,
+                outcome: None,
+            });
+        });
+        self.active = Some(ActiveChild {
+            handle,
+            index,
+            key: plan.key,
+            cancel_sent: None,
+        });
+        Ok(())
+    }
+
+    // 撤掉在途子单，已撤过且未超过重试间隔时不重复发送
+    async fn cancel_child(&mut self) -> CtpResult<()> {
+        let due = match &self.active {
+            Some(child) => child
+                .cancel_sent
+                .is_none_or(|at| at.elapsed() >= CANCEL_RETRY),
+            None => false,
+        };
+        if !due {
+            return Ok(());
+        }
+        self.pace().await;
+        let Some(child) = &mut self.active else {
+            return Ok(());
+        };
+        child.cancel_sent = Some(Instant::now());
+        if let Err(e) = child.handle.cancel().await {
+            // 子单可能刚好结束，下一步同步状态后不再撤单
+            warn!(
+                "执行算法: 撤销子单 {} 失败: {}",
+                field_string(&self.template.instrument_id),
+                e
+            );
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn spec(direction: Direction) -> ParentSpec {
+        ParentSpec {
+            direction,
+            total: 10,
+            limit_price: 3500.0,
+            price_tick: 1.0,
+        }
+    }
+
+    fn best(bid: f64, ask: f64) -> Quote {
+        Quote {
+            bid,
+            bid_volume: 5,
+            ask,
+            ask_volume: 5,
+        }
+    }
+
+    fn tick(time: &str, volume: i32) -> DepthMarketDataField {
+        DepthMarketDataField {
+            update_time: <[u8; 9]>::from_utf8_string(time).unwrap(),
+            volume,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_volume_curve_from_ticks() {
+        let ticks = [
+            tick("09:00:00", 100),
+            tick("09:00:30", 130),
+            tick("09:01:10", 190),
+            tick("09:01:50", 200),
+            tick("09:02:10", 500),
+        ];
+        let curve = VolumeCurve::from_ticks(&ticks, "09:00:00", "09:02:00", 2).unwrap();
+        assert_eq!(curve, VolumeCurve::from_weights(vec![30.0, 70.0]).unwrap());
+        assert_eq!(curve.cumulative_targets(10), vec![3, 10]);
+        assert!(VolumeCurve::from_ticks(&ticks, "09:02:00", "09:00:00", 2).is_err());
+    }
+
+    #[test]
+    fn test_price_bounds() {
+        let buy = spec(Direction::Buy);
+        assert_eq!(buy.bound(3498.6), 3498.0);
+        assert_eq!(buy.bound(3502.0), 3500.0);
+        let sell = spec(Direction::Sell);
+        assert_eq!(sell.bound(3501.2), 3502.0);
+        assert_eq!(sell.bound(3490.0), 3500.0);
+        let quote = best(3497.0, 3499.0);
+        assert_eq!(buy.aggressive(Some(quote)), 3499.0);
+        assert_eq!(sell.aggressive(None), 3500.0);
+
+        assert!(buy.validate().is_ok());
+        let off_tick = ParentSpec {
+            limit_price: 3500.5,
+            ..buy
+        };
+        assert!(off_tick.validate().is_err());
+        let fractional = ParentSpec {
+            limit_price: 3500.4,
+            price_tick: 0.2,
+            ..buy
+        };
+        assert!(fractional.validate().is_ok());
+    }
+
+    #[test]
+    fn test_plans() {
+        let buy = spec(Direction::Buy);
+        let quote = Some(best(3497.0, 3499.0));
+
+        let twap = ExecutionAlgo::Twap {
+            duration: Duration::from_secs(100),
+            slices: 4,
+        };
+        let plan = twap
+            .plan(&buy, Duration::from_secs(30), 2, quote, None)
+            .unwrap();
+        assert_eq!((plan.volume, plan.price, plan.key), (3, 3499.0, 1));
+        // 时间窗口结束后最后一片补足剩余数量
+        let plan = twap
+            .plan(&buy, Duration::from_secs(500), 4, quote, None)
+            .unwrap();
+        assert_eq!((plan.volume, plan.key), (6, 3));
+        assert!(twap.plan(&buy, Duration::ZERO, 3, quote, None).is_none());
+
+        let iceberg = ExecutionAlgo::Iceberg { visible_volume: 4 };
+        let plan = iceberg.plan(&buy, Duration::ZERO, 8, None, None).unwrap();
+        assert_eq!((plan.volume, plan.price), (2, 3500.0));
+
+        let peg = ExecutionAlgo::Peg { offset_ticks: 1 };
+        assert!(peg.plan(&buy, Duration::ZERO, 0, None, None).is_none());
+        let plan = peg.plan(&buy, Duration::ZERO, 0, quote, None).unwrap();
+        assert_eq!((plan.volume, plan.price, plan.key), (10, 3498.0, 3498));
+        let moved = Some(best(3500.0, 3501.0));
+        // 跟价不超过限价
+        assert_eq!(
+            peg.plan(&buy, Duration::ZERO, 0, moved, None)
+                .unwrap()
+                .price,
+            3500.0
+        );
+
+        assert!(ExecutionAlgo::Twap {
+            duration: Duration::from_secs(1),
+            slices: 0
+        }
+        .validate()
+        .is_err());
+    }
+
+    #[test]
+    fn test_peg_ignores_own_order() {
+        let buy = spec(Direction::Buy);
+        let peg = ExecutionAlgo::Peg { offset_ticks: 1 };
+        let plan = |quote, resting| peg.plan(&buy, Duration::ZERO, 0, Some(quote), resting);
+        let child = Some(Resting {
+            price: 3498.0,
+            volume: 10,
+        });
+
+        // 买一只有子单自己，不跟着自己往上挂
+        let own = Quote {
+            bid_volume: 10,
+            ..best(3498.0, 3499.0)
+        };
+        assert_eq!(plan(own, child).unwrap().price, 3498.0);
+        // 买一有他人挂单时加一个价位，但不超过卖一减一个价位
+        let joined = Quote {
+            bid_volume: 15,
+            ..best(3498.0, 3499.0)
+        };
+        assert_eq!(plan(joined, None).unwrap().price, 3498.0);
+        let wide = Quote {
+            bid_volume: 15,
+            ..best(3496.0, 3499.0)
+        };
+        assert_eq!(plan(wide, None).unwrap().price, 3497.0);
+        // 盘口向子单靠近时不重挂，离开时重挂
+        assert_eq!(plan(wide, child).unwrap().price, 3498.0);
+        let away = Quote {
+            bid_volume: 15,
+            ..best(3499.0, 3501.0)
+        };
+        assert_eq!(plan(away, child).unwrap().price, 3500.0);
+
+        let sell = spec(Direction::Sell);
+        let plan = peg
+            .plan(&sell, Duration::ZERO, 0, Some(best(3501.0, 3502.0)), None)
+            .unwrap();
+        assert_eq!(plan.price, 3502.0);
+    }
+
+    fn offline_runner() -> (Runner, ExecutionHandle) {
+        Runner::new(
+            Weak::new(),
+            &InputOrderField::default(),
+            ExecutionAlgo::Peg { offset_ticks: 0 },
+            spec(Direction::Buy),
+            Duration::from_millis(10),
+            Duration::ZERO,
+        )
+    }
+
+    #[tokio::test]
+    async fn test_runner_stops() {
+        // 句柄释放视同撤销
+        let (runner, handle) = offline_runner();
+        let mut progress = handle.progress.clone();
+        drop(handle);
+        runner.run().await;
+        assert_eq!(
+            progress.borrow_and_update().status,
+            ExecutionStatus::Cancelled
+        );
+
+        // 交易API释放后母单结束，不再空转
+        let (runner, handle) = offline_runner();
+        runner.run().await;
+        assert!(matches!(
+            handle.progress().status,
+            ExecutionStatus::Failed(_)
+        ));
+    }
+}
@@ -0,0 +1,420 @@
+//! 多前置故障切换
+//!
+//! 期货公司通常提供多个交易前置（电信、联通、备用等）。[`FrontPool`]按配置顺序保存
+//! 这些前置，记录每个前置的连接次数、连接耗时和断线频率，并根据[`FailoverPolicy`]
+//! 判断当前前置是否需要切换。连接、断线和切换过程通过[`FailoverEvent`]上报。
+//!
+//! 切换本身由`AsyncTraderApi::failover`完成：释放旧的API实例，针对最健康的前置
+//! 重新创建并初始化。
+
+use crate::error::{CtpError, CtpResult};
+use std::collections::VecDeque;
+use std::time::{Duration, Instant};
+
+/// 故障切换策略
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FailoverPolicy {
+    /// 连续连接失败达到该次数时切换，为0时不按连续失败切换
+    pub max_consecutive_failures: u32,
+    /// 统计断线频率的时间窗口
+    pub disconnect_window: Duration,
+    /// 时间窗口内断线达到该次数时切换，为0时不按断线频率切换
+    pub max_disconnects_in_window: u32,
+}
+
+impl Default for FailoverPolicy {
+    fn default() -> Self {
+        Self {
+            max_consecutive_failures: 3,
+            disconnect_window: Duration::from_secs(60),
+            max_disconnects_in_window: 5,
+        }
+    }
+}
+
+impl FailoverPolicy {
+    /// 设置连续连接失败次数上限
+    pub fn with_max_consecutive_failures(mut self, count: u32) -> Self {
+        self.max_consecutive_failures = count;
+        self
+    }
+
+    /// 设置断线频率上限
+    pub fn with_disconnect_limit(mut self, count: u32, window: Duration) -> Self {
+        self.max_disconnects_in_window = count;
+        self.disconnect_window = window;
+        self
+    }
+}
+
+/// 故障切换事件
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum FailoverEvent {
+    /// 已连接到前置
+    FrontConnected {
+        /// 实际连接的前置地址
+        front: String,
+        /// 从开始连接到连接成功的耗时
+        latency: Duration,
+    },
+    /// 前置连接断开，连接成功前的断开视为一次连接失败
+    FrontDisconnected {
+        /// 断开的前置地址，尚未连接成功过时未知
+        front: Option<String>,
+        /// 断开原因
+        reason: i32,
+    },
+    /// 开始切换前置
+    FailingOver {
+        /// 原前置地址
+        from: Option<String>,
+        /// 目标前置地址
+        to: String,
+    },
+    /// 切换失败
+    FailoverFailed {
+        /// 目标前置地址
+        to: String,
+        /// 错误信息
+        error: String,
+    },
+}
+
+/// 单个前置的健康状况
+#[derive(Debug, Clone)]
+pub struct FrontHealth {
+    address: String,
+    connects: u32,
+    disconnects: u32,
+    consecutive_failures: u32,
+    last_latency: Option<Duration>,
+    avg_latency: Option<Duration>,
+    recent_disconnects: VecDeque<Instant>,
+}
+
+impl FrontHealth {
+    fn new(address: String) -> Self {
+        Self {
+            address,
+            connects: 0,
+            disconnects: 0,
+            consecutive_failures: 0,
+            last_latency: None,
+            avg_latency: None,
+            recent_disconnects: VecDeque::new(),
+        }
+    }
+
+    /// 前置地址
+    pub fn address(&self) -> &str {
+        &self.address
+    }
+
+    /// 连接成功次数
+    pub fn connects(&self) -> u32 {
+        self.connects
+    }
+
+    /// 断线次数（含连接失败）
+    pub fn disconnects(&self) -> u32 {
+        self.disconnects
+    }
+
+    /// 当前连续连接失败次数
+    pub fn consecutive_failures(&self) -> u32 {
+        self.consecutive_failures
+    }
+
+    /// 最近一次连接耗时
+    pub fn last_latency(&self) -> Option<Duration> {
+        self.last_latency
+    }
+
+    /// 平均连接耗时（指数加权，新样本权重1/4）
+    pub fn avg_latency(&self) -> Option<Duration> {
+        self.avg_latency
+    }
+
+    /// 时间窗口内的断线次数
+    pub fn disconnects_within(&self, window: Duration, now: Instant) -> u32 {
+        self.recent_disconnects
+            .iter()
+            .filter(|&&t| now.saturating_duration_since(t) <= window)
+            .count() as u32
+    }
+
+    fn record_connected(&mut self, latency: Duration) {
+        self.connects += 1;
+        self.consecutive_failures = 0;
+        self.last_latency = Some(latency);
+        self.avg_latency = Some(match self.avg_latency {
+            Some(avg) => (avg * 3 + latency) / 4,
+            None => latency,
+        });
+    }
+
+    fn record_disconnect(&mut self, now: Instant, window: Duration, connected: bool) {
+        self.disconnects += 1;
+        if !connected {
+            self.consecutive_failures += 1;
+        }
+        self.recent_disconnects.push_back(now);
+        while let Some(&t) = self.recent_disconnects.front() {
+            if now.saturating_duration_since(t) <= window {
+                break;
+            }
+            self.recent_disconnects.pop_front();
+        }
+    }
+}
+
+/// 前置地址池
+///
+/// 只记录和判断，不直接操作API；异步API在回调中更新，在需要切换时调用
+/// [`FrontPool::next_front`]选择目标前置
+#[derive(Debug, Clone, Default)]
+pub struct FrontPool {
+    fronts: Vec<FrontHealth>,
+    policy: FailoverPolicy,
+    // 当前连接或正在连接的前置，注册了多个前置且尚未连接成功时未知
+    current: Option<usize>,
+    connected: bool,
+    connecting_since: Option<Instant>,
+    // 连接目标未知时的连续失败次数
+    unattributed_failures: u32,
+    failover_pending: bool,
+}
+
+impl FrontPool {
+    /// 按优先顺序创建前置池，重复的地址只保留第一个
+    pub fn new<I, S>(addresses: I, policy: FailoverPolicy) -> CtpResult<Self>
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        let mut fronts: Vec<FrontHealth> = Vec::new();
+        for address in addresses {
+            let address = address.into().trim().to_string();
+            if address.is_empty() || fronts.iter().any(|f| f.address == address) {
+                continue;
+            }
+            fronts.push(FrontHealth::new(address));
+        }
+        if fronts.is_empty() {
+            return Err(CtpError::InvalidParameterError(
+                "至少需要一个前置地址".to_string(),
+            ));
+        }
+        Ok(Self {
+            fronts,
+            policy,
+            ..Default::default()
+        })
+    }
+
+    /// 是否没有任何前置
+    pub fn is_empty(&self) -> bool {
+        self.fronts.is_empty()
+    }
+
+    /// 切换策略
+    pub fn policy(&self) -> &FailoverPolicy {
+        &self.policy
+    }
+
+    /// 按配置顺序排列的前置地址
+    pub fn addresses(&self) -> impl Iterator<Item = &str> {
+        self.fronts.iter().map(|f| f.address.as_str())
+    }
+
+    /// 各前置的健康状况
+    pub fn health(&self) -> &[FrontHealth] {
+        &self.fronts
+    }
+
+    /// 当前连接或正在连接的前置地址
+    pub fn current(&self) -> Option<&str> {
+        self.current.map(|i| self.fronts[i].address.as_str())
+    }
+
+    /// 当前是否已连接
+    pub fn is_connected(&self) -> bool {
+        self.connected
+    }
+
+    /// 开始连接，`target`为只注册了单个前置时的连接目标
+    ///
+    /// 池中只有一个前置时，即使不指定目标也视为连接该前置
+    pub fn begin_connect(&mut self, target: Option<&str>, now: Instant) {
+        self.current = match target {
+            Some(address) => self.index_of(address),
+            None if self.fronts.len() == 1 => Some(0),
+            None => None,
+        };
+        self.connected = false;
+        self.connecting_since = Some(now);
+        self.unattributed_failures = 0;
+        self.failover_pending = false;
+    }
+
+    /// 记录连接成功，返回连接耗时
+    ///
+    /// 前置地址来自`GetFrontInfo`，不在池中时只记录耗时不做统计
+    pub fn record_connected(&mut self, address: &str, now: Instant) -> Duration {
+        let latency = self
+            .connecting_since
+            .take()
+            .map(|since| now.saturating_duration_since(since))
+            .unwrap_or_default();
+        self.connected = true;
+        self.unattributed_failures = 0;
+        self.current = self.index_of(address);
+        if let Some(index) = self.current {
+            self.fronts[index].record_connected(latency);
+        }
+        latency
+    }
+
+    /// 记录连接断开，返回是否需要切换前置
+    ///
+    /// 同一轮切换只会返回一次`true`，直到下一次[`FrontPool::begin_connect`]
+    pub fn record_disconnect(&mut self, now: Instant) -> bool {
+        let was_connected = self.connected;
+        self.connected = false;
+        // CTP会自动重连，从断开时刻开始计算下一次连接耗时
+        self.connecting_since = Some(now);
+
+        // 两个条件各自以0表示不启用
+        let max_failures = self.policy.max_consecutive_failures;
+        let max_disconnects = self.policy.max_disconnects_in_window;
+        let exceeded = match self.current {
+            Some(index) => {
+                let window = self.policy.disconnect_window;
+                let front = &mut self.fronts[index];
+                front.record_disconnect(now, window, was_connected);
+                (max_failures > 0 && front.consecutive_failures >= max_failures)
+                    || (max_disconnects > 0
+                        && front.disconnects_within(window, now) >= max_disconnects)
+            }
+            None => {
+                self.unattributed_failures += 1;
+                max_failures > 0 && self.unattributed_failures >= max_failures
+            }
+        };
+
+        if exceeded && !self.failover_pending {
+            self.failover_pending = true;
+            return true;
+        }
+        false
+    }
+
+    /// 选择最健康的前置，优先选择当前前置以外的
+    ///
+    /// 依次比较连续失败次数、窗口内断线次数和平均连接耗时（未连接过的排在后面），
+    /// 都相同时按配置顺序
+    pub fn next_front(&self, now: Instant) -> Option<&str> {
+        let window = self.policy.disconnect_window;
+        self.fronts
+            .iter()
+            .enumerate()
+            .min_by_key(|(index, front)| {
+                (
+                    Some(*index) == self.current && self.fronts.len() > 1,
+                    front.consecutive_failures,
+                    front.disconnects_within(window, now),
+                    front.avg_latency.is_none(),
+                    front.avg_latency,
+                    *index,
+                )
+            })
+            .map(|(_, front)| front.address.as_str())
+    }
+
+    fn index_of(&self, address: &str) -> Option<usize> {
+        self.fronts.iter().position(|f| f.address == address)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const A: &str = "tcp://180.168.146.187:10201";
+    const B: &str = "tcp://180.168.146.187:10202";
+    const C: &str = "tcp://218.202.237.33:10203";
+
+    fn pool() -> FrontPool {
+        FrontPool::new([A, B, C, A], FailoverPolicy::default()).unwrap()
+    }
+
+    #[test]
+    fn test_connect_latency_and_failures() {
+        let start = Instant::now();
+        let mut pool = pool();
+        assert_eq!(pool.health().len(), 3);
+
+        // 注册了全部前置时，连接前不知道目标，失败只计入未归属次数
+        pool.begin_connect(None, start);
+        assert!(!pool.record_disconnect(start));
+        assert!(!pool.record_disconnect(start));
+        let latency = pool.record_connected(B, start + Duration::from_millis(40));
+        assert_eq!(latency, Duration::from_millis(40));
+        assert_eq!(pool.current(), Some(B));
+        assert_eq!(pool.health()[1].connects(), 1);
+
+        // 连接后断开不算连接失败，自动重连失败才算
+        let t = start + Duration::from_secs(1);
+        assert!(!pool.record_disconnect(t));
+        assert!(!pool.record_disconnect(t));
+        assert!(!pool.record_disconnect(t));
+        assert_eq!(pool.health()[1].consecutive_failures(), 2);
+        assert!(pool.record_disconnect(t));
+        // 同一轮只触发一次
+        assert!(!pool.record_disconnect(t));
+        assert_eq!(pool.next_front(t), Some(A));
+
+        pool.begin_connect(Some(A), t);
+        assert_eq!(pool.current(), Some(A));
+        assert!(!pool.is_connected());
+    }
+
+    #[test]
+    fn test_disconnect_frequency_and_ranking() {
+        let start = Instant::now();
+        // 只启用断线频率条件
+        let policy = FailoverPolicy::default()
+            .with_max_consecutive_failures(0)
+            .with_disconnect_limit(2, Duration::from_secs(10));
+        let mut pool = FrontPool::new([A, B, C], policy).unwrap();
+
+        pool.begin_connect(Some(C), start);
+        pool.record_connected(C, start + Duration::from_millis(10));
+        pool.begin_connect(Some(B), start);
+        pool.record_connected(B, start + Duration::from_millis(50));
+        assert!(!pool.record_disconnect(start + Duration::from_secs(1)));
+        pool.record_connected(B, start + Duration::from_secs(2));
+        assert!(pool.record_disconnect(start + Duration::from_secs(3)));
+
+        // 有测速结果的C优先于未连接过的A，当前的B排在最后
+        let now = start + Duration::from_secs(3);
+        assert_eq!(pool.next_front(now), Some(C));
+        assert_eq!(
+            pool.health()[1].disconnects_within(Duration::from_secs(10), now),
+            2
+        );
+        // 窗口过后断线记录失效
+        assert_eq!(
+            pool.health()[1]
+                .disconnects_within(Duration::from_secs(10), now + Duration::from_secs(20)),
+            0
+        );
+    }
+
+    #[test]
+    fn test_empty_pool_is_rejected() {
+        assert!(FrontPool::new(Vec::<String>::new(), FailoverPolicy::default()).is_err());
+        assert!(FrontPool::new([" "], FailoverPolicy::default()).is_err());
+    }
+}
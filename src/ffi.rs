@@ -113,6 +113,47 @@ pub struct TraderSpiCallbacks {
         Option<extern "C" fn(*mut c_void, *mut c_void, *mut c_void, c_int, c_int)>,
     pub on_rsp_qry_notice:
         Option<extern "C" fn(*mut c_void, *mut c_void, *mut c_void, c_int, c_int)>,
+
+    // 银期转账回调
+    pub on_rsp_from_bank_to_future_by_future:
+        Option<extern "C" fn(*mut c_void, *mut c_void, *mut c_void, c_int, c_int)>,
+    pub on_rsp_from_future_to_bank_by_future:
+        Option<extern "C" fn(*mut c_void, *mut c_void, *mut c_void, c_int, c_int)>,
+    pub on_rsp_query_bank_account_money_by_future:
+        Option<extern "C" fn(*mut c_void, *mut c_void, *mut c_void, c_int, c_int)>,
+    pub on_rtn_from_bank_to_future_by_future: Option<extern "C" fn(*mut c_void, *mut c_void)>,
+    pub on_rtn_from_future_to_bank_by_future: Option<extern "C" fn(*mut c_void, *mut c_void)>,
+    pub on_rtn_repeal_from_bank_to_future_by_future:
+        Option<extern "C" fn(*mut c_void, *mut c_void)>,
+    pub on_rtn_repeal_from_future_to_bank_by_future:
+        Option<extern "C" fn(*mut c_void, *mut c_void)>,
+    pub on_err_rtn_bank_to_future_by_future:
+        Option<extern "C" fn(*mut c_void, *mut c_void, *mut c_void)>,
+    pub on_err_rtn_future_to_bank_by_future:
+        Option<extern "C" fn(*mut c_void, *mut c_void, *mut c_void)>,
+    pub on_rtn_query_bank_balance_by_future: Option<extern "C" fn(*mut c_void, *mut c_void)>,
+    pub on_err_rtn_query_bank_balance_by_future:
+        Option<extern "C" fn(*mut c_void, *mut c_void, *mut c_void)>,
+    pub on_rsp_qry_transfer_serial:
+        Option<extern "C" fn(*mut c_void, *mut c_void, *mut c_void, c_int, c_int)>,
+    pub on_rsp_qry_contract_bank:
+        Option<extern "C" fn(*mut c_void, *mut c_void, *mut c_void, c_int, c_int)>,
+    pub on_rsp_qry_accountregister:
+        Option<extern "C" fn(*mut c_void, *mut c_void, *mut c_void, c_int, c_int)>,
+
+    // 密码管理回调
+    pub on_rsp_user_password_update:
+        Option<extern "C" fn(*mut c_void, *mut c_void, *mut c_void, c_int, c_int)>,
+    pub on_rsp_trading_account_password_update:
+        Option<extern "C" fn(*mut c_void, *mut c_void, *mut c_void, c_int, c_int)>,
+
+    // 认证模式回调
+    pub on_rsp_user_auth_method:
+        Option<extern "C" fn(*mut c_void, *mut c_void, *mut c_void, c_int, c_int)>,
+    pub on_rsp_gen_user_captcha:
+        Option<extern "C" fn(*mut c_void, *mut c_void, *mut c_void, c_int, c_int)>,
+    pub on_rsp_gen_user_text:
+        Option<extern "C" fn(*mut c_void, *mut c_void, *mut c_void, c_int, c_int)>,
 }
 
 // SPI桥接函数
@@ -1099,6 +1140,258 @@ pub mod trader_api {
             request_id: c_int,
         ) -> c_int;
     }
+
+    // 期货发起银行资金转期货请求
+    //
+    // # 参数
+    // * `api` - API实例指针
+    // * `req` - 银行资金转期货请求字段
+    // * `request_id` - 请求ID
+    //
+    // # 返回值
+    // 0表示成功，非0表示失败
+    #[link(name = "ctp_wrapper")]
+    extern "C" {
+        pub fn CThostFtdcTraderApi_ReqFromBankToFutureByFuture(
+            api: *mut c_void,
+            req: *const c_void,
+            request_id: c_int,
+        ) -> c_int;
+    }
+
+    // 期货发起期货资金转银行请求
+    //
+    // # 参数
+    // * `api` - API实例指针
+    // * `req` - 期货资金转银行请求字段
+    // * `request_id` - 请求ID
+    //
+    // # 返回值
+    // 0表示成功，非0表示失败
+    #[link(name = "ctp_wrapper")]
+    extern "C" {
+        pub fn CThostFtdcTraderApi_ReqFromFutureToBankByFuture(
+            api: *mut c_void,
+            req: *const c_void,
+            request_id: c_int,
+        ) -> c_int;
+    }
+
+    // 期货发起查询银行余额请求
+    //
+    // # 参数
+    // * `api` - API实例指针
+    // * `req` - 查询银行余额请求字段
+    // * `request_id` - 请求ID
+    //
+    // # 返回值
+    // 0表示成功，非0表示失败
+    #[link(name = "ctp_wrapper")]
+    extern "C" {
+        pub fn CThostFtdcTraderApi_ReqQueryBankAccountMoneyByFuture(
+            api: *mut c_void,
+            req: *const c_void,
+            request_id: c_int,
+        ) -> c_int;
+    }
+
+    // 请求查询转帐流水
+    //
+    // # 参数
+    // * `api` - API实例指针
+    // * `req` - 查询转帐流水字段
+    // * `request_id` - 请求ID
+    //
+    // # 返回值
+    // 0表示成功，非0表示失败
+    #[link(name = "ctp_wrapper")]
+    extern "C" {
+        pub fn CThostFtdcTraderApi_ReqQryTransferSerial(
+            api: *mut c_void,
+            req: *const c_void,
+            request_id: c_int,
+        ) -> c_int;
+    }
+
+    // 请求查询签约银行
+    //
+    // # 参数
+    // * `api` - API实例指针
+    // * `req` - 查询签约银行字段
+    // * `request_id` - 请求ID
+    //
+    // # 返回值
+    // 0表示成功，非0表示失败
+    #[link(name = "ctp_wrapper")]
+    extern "C" {
+        pub fn CThostFtdcTraderApi_ReqQryContractBank(
+            api: *mut c_void,
+            req: *const c_void,
+            request_id: c_int,
+        ) -> c_int;
+    }
+
+    // 请求查询银期签约关系
+    //
+    // # 参数
+    // * `api` - API实例指针
+    // * `req` - 查询银期签约关系字段
+    // * `request_id` - 请求ID
+    //
+    // # 返回值
+    // 0表示成功，非0表示失败
+    #[link(name = "ctp_wrapper")]
+    extern "C" {
+        pub fn CThostFtdcTraderApi_ReqQryAccountregister(
+            api: *mut c_void,
+            req: *const c_void,
+            request_id: c_int,
+        ) -> c_int;
+    }
+
+    // 用户口令更新请求
+    //
+    // # 参数
+    // * `api` - API实例指针
+    // * `req` - 用户口令更新字段
+    // * `request_id` - 请求ID
+    //
+    // # 返回值
+    // 0表示成功，非0表示失败
+    #[link(name = "ctp_wrapper")]
+    extern "C" {
+        pub fn CThostFtdcTraderApi_ReqUserPasswordUpdate(
+            api: *mut c_void,
+            req: *const c_void,
+            request_id: c_int,
+        ) -> c_int;
+    }
+
+    // 资金账户口令更新请求
+    //
+    // # 参数
+    // * `api` - API实例指针
+    // * `req` - 资金账户口令更新字段
+    // * `request_id` - 请求ID
+    //
+    // # 返回值
+    // 0表示成功，非0表示失败
+    #[link(name = "ctp_wrapper")]
+    extern "C" {
+        pub fn CThostFtdcTraderApi_ReqTradingAccountPasswordUpdate(
+            api: *mut c_void,
+            req: *const c_void,
+            request_id: c_int,
+        ) -> c_int;
+    }
+
+    // 查询用户当前支持的认证模式
+    //
+    // # 参数
+    // * `api` - API实例指针
+    // * `req` - 查询认证模式请求字段
+    // * `request_id` - 请求ID
+    //
+    // # 返回值
+    // 0表示成功，非0表示失败
+    #[link(name = "ctp_wrapper")]
+    extern "C" {
+        pub fn CThostFtdcTraderApi_ReqUserAuthMethod(
+            api: *mut c_void,
+            req: *const c_void,
+            request_id: c_int,
+        ) -> c_int;
+    }
+
+    // 用户发出获取图形验证码请求
+    //
+    // # 参数
+    // * `api` - API实例指针
+    // * `req` - 获取图形验证码请求字段
+    // * `request_id` - 请求ID
+    //
+    // # 返回值
+    // 0表示成功，非0表示失败
+    #[link(name = "ctp_wrapper")]
+    extern "C" {
+        pub fn CThostFtdcTraderApi_ReqGenUserCaptcha(
+            api: *mut c_void,
+            req: *const c_void,
+            request_id: c_int,
+        ) -> c_int;
+    }
+
+    // 用户发出获取短信验证码请求
+    //
+    // # 参数
+    // * `api` - API实例指针
+    // * `req` - 获取短信验证码请求字段
+    // * `request_id` - 请求ID
+    //
+    // # 返回值
+    // 0表示成功，非0表示失败
+    #[link(name = "ctp_wrapper")]
+    extern "C" {
+        pub fn CThostFtdcTraderApi_ReqGenUserText(
+            api: *mut c_void,
+            req: *const c_void,
+            request_id: c_int,
+        ) -> c_int;
+    }
+
+    // 用户发出带有图片验证码的登录请求
+    //
+    // # 参数
+    // * `api` - API实例指针
+    // * `req` - 带图形验证码的登录请求字段
+    // * `request_id` - 请求ID
+    //
+    // # 返回值
+    // 0表示成功，非0表示失败
+    #[link(name = "ctp_wrapper")]
+    extern "C" {
+        pub fn CThostFtdcTraderApi_ReqUserLoginWithCaptcha(
+            api: *mut c_void,
+            req: *const c_void,
+            request_id: c_int,
+        ) -> c_int;
+    }
+
+    // 用户发出带有短信验证码的登录请求
+    //
+    // # 参数
+    // * `api` - API实例指针
+    // * `req` - 带短信验证码的登录请求字段
+    // * `request_id` - 请求ID
+    //
+    // # 返回值
+    // 0表示成功，非0表示失败
+    #[link(name = "ctp_wrapper")]
+    extern "C" {
+        pub fn CThostFtdcTraderApi_ReqUserLoginWithText(
+            api: *mut c_void,
+            req: *const c_void,
+            request_id: c_int,
+        ) -> c_int;
+    }
+
+    // 用户发出带有动态口令的登录请求
+    //
+    // # 参数
+    // * `api` - API实例指针
+    // * `req` - 带动态口令的登录请求字段
+    // * `request_id` - 请求ID
+    //
+    // # 返回值
+    // 0表示成功，非0表示失败
+    #[link(name = "ctp_wrapper")]
+    extern "C" {
+        pub fn CThostFtdcTraderApi_ReqUserLoginWithOTP(
+            api: *mut c_void,
+            req: *const c_void,
+            request_id: c_int,
+        ) -> c_int;
+    }
 }
 
 // SPI回调函数类型定义
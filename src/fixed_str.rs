@@ -0,0 +1,495 @@
+//! 定长GB18030字符串
+//!
+//! CTP的字符串字段是以空字节结尾的定长`char`数组。`FixedStr<N>`是对`[u8; N]`的
+//! `#[repr(transparent)]`封装，内存布局与原始数组完全一致，可以直接用于FFI结构体，
+//! 同时保证：
+//!
+//! - 构造时检查长度，编码后超过`N - 1`字节返回错误而不是静默截断
+//! - 只有显式调用[`FixedStr::from_str_truncated`]时才截断，且只在字符边界截断
+//! - 内容之后总有空字节结尾
+//!
+//! 密码、认证码等敏感字段使用[`SecretStr`]，内存布局同样与`[u8; N]`一致，但释放时清零，
+//! `Debug`/`Display`输出也会隐去内容。
+
+use crate::encoding::GbkConverter;
+use crate::error::{CtpError, CtpResult};
+use encoding::all::GB18030;
+use encoding::{EncoderTrap, Encoding};
+use std::cmp::Ordering;
+use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::os::raw::c_char;
+use std::str::FromStr;
+use zeroize::{Zeroize, Zeroizing};
+
+/// 定长GB18030字符串，最多容纳`N - 1`字节内容
+#[repr(transparent)]
+#[derive(Clone, Copy)]
+pub struct FixedStr<const N: usize>([u8; N]);
+
+impl<const N: usize> FixedStr<N> {
+    /// 内容最大字节数（保留一个字节用于空字节结尾）
+    pub const CAPACITY: usize = N - 1;
+
+    /// 创建空字符串
+    pub const fn new() -> Self {
+        Self([0; N])
+    }
+
+    /// 从原始数组创建，强制最后一个字节为空字节
+    pub fn from_raw(mut raw: [u8; N]) -> Self {
+        if let Some(last) = raw.last_mut() {
+            *last = 0;
+        }
+        Self(raw)
+    }
+
+    /// 从GB18030编码的字节创建
+    ///
+    /// 末尾的空字节会被忽略；内容中间包含空字节或超出容量时返回错误
+    pub fn from_bytes(bytes: &[u8]) -> CtpResult<Self> {
+        let end = bytes.iter().rposition(|&b| b != 0).map_or(0, |pos| pos + 1);
+        let bytes = &bytes[..end];
+        if bytes.contains(&0) {
+            return Err(CtpError::InvalidParameterError(
+                "字符串中间不能包含空字节".to_string(),
+            ));
+        }
+        if bytes.len() > Self::CAPACITY {
+            return Err(CtpError::InvalidParameterError(format!(
+                "字符串编码后 {} 字节，超过容量 {}",
+                bytes.len(),
+                Self::CAPACITY
+            )));
+        }
+        let mut raw = [0u8; N];
+        raw[..bytes.len()].copy_from_slice(bytes);
+        Ok(Self(raw))
+    }
+
+    /// 从UTF-8字符串创建，超出容量时在字符边界截断
+    pub fn from_str_truncated(s: &str) -> CtpResult<Self> {
+        if s.contains('\0') {
+            return Err(CtpError::InvalidParameterError(
+                "字符串中间不能包含空字节".to_string(),
+            ));
+        }
+        let mut raw = [0u8; N];
+        let mut len = 0;
+        let mut buf = [0u8; 4];
+        for c in s.chars() {
+            let encoded = encode(c.encode_utf8(&mut buf))?;
+            if len + encoded.len() > Self::CAPACITY {
+                break;
+            }
+            raw[len..len + encoded.len()].copy_from_slice(&encoded);
+            len += encoded.len();
+        }
+        Ok(Self(raw))
+    }
+
+    /// 内容字节（不含结尾的空字节）
+    pub fn as_bytes(&self) -> &[u8] {
+        let end = self.0.iter().position(|&b| b == 0).unwrap_or(N);
+        &self.0[..end]
+    }
+
+    /// 原始数组
+    pub fn as_raw(&self) -> &[u8; N] {
+        &self.0
+    }
+
+    /// 转换为原始数组
+    pub fn into_raw(self) -> [u8; N] {
+        self.0
+    }
+
+    /// C字符串指针
+    pub fn as_ptr(&self) -> *const c_char {
+        self.0.as_ptr() as *const c_char
+    }
+
+    /// 内容字节数
+    pub fn len(&self) -> usize {
+        self.as_bytes().len()
+    }
+
+    /// 是否为空
+    pub fn is_empty(&self) -> bool {
+        self.as_bytes().is_empty()
+    }
+
+    /// 清空内容
+    pub fn clear(&mut self) {
+        self.0 = [0; N];
+    }
+}
+
+fn encode(s: &str) -> CtpResult<Vec<u8>> {
+    GB18030
+        .encode(s, EncoderTrap::Strict)
+        .map_err(|e| CtpError::EncodingError(format!("GB18030编码失败: {}", e)))
+}
+
+impl<const N: usize> Default for FixedStr<N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const N: usize> FromStr for FixedStr<N> {
+    type Err = CtpError;
+
+    fn from_str(s: &str) -> CtpResult<Self> {
+        if s.contains('\0') {
+            return Err(CtpError::InvalidParameterError(
+                "字符串中间不能包含空字节".to_string(),
+            ));
+        }
+        let bytes = encode(s)?;
+        if bytes.len() > Self::CAPACITY {
+            return Err(CtpError::InvalidParameterError(format!(
+                "字符串 {:?} 编码后 {} 字节，超过容量 {}",
+                s,
+                bytes.len(),
+                Self::CAPACITY
+            )));
+        }
+        Self::from_bytes(&bytes)
+    }
+}
+
+impl<const N: usize> TryFrom<&str> for FixedStr<N> {
+    type Error = CtpError;
+
+    fn try_from(s: &str) -> CtpResult<Self> {
+        s.parse()
+    }
+}
+
+impl<const N: usize> From<[u8; N]> for FixedStr<N> {
+    fn from(raw: [u8; N]) -> Self {
+        Self::from_raw(raw)
+    }
+}
+
+impl<const N: usize> From<FixedStr<N>> for [u8; N] {
+    fn from(value: FixedStr<N>) -> Self {
+        value.0
+    }
+}
+
+impl<const N: usize> AsRef<[u8]> for FixedStr<N> {
+    fn as_ref(&self) -> &[u8] {
+        self.as_bytes()
+    }
+}
+
+impl<const N: usize> fmt::Display for FixedStr<N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let text = GbkConverter::gb18030_to_utf8(self.as_bytes()).map_err(|_| fmt::Error)?;
+        f.write_str(&text)
+    }
+}
+
+impl<const N: usize> fmt::Debug for FixedStr<N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:?}", self.to_string())
+    }
+}
+
+impl<const N: usize> PartialEq for FixedStr<N> {
+    fn eq(&self, other: &Self) -> bool {
+        self.as_bytes() == other.as_bytes()
+    }
+}
+
+impl<const N: usize> Eq for FixedStr<N> {}
+
+impl<const N: usize> PartialEq<str> for FixedStr<N> {
+    fn eq(&self, other: &str) -> bool {
+        if other.is_ascii() {
+            return self.as_bytes() == other.as_bytes();
+        }
+        encode(other).is_ok_and(|bytes| self.as_bytes() == bytes.as_slice())
+    }
+}
+
+impl<const N: usize> PartialEq<&str> for FixedStr<N> {
+    fn eq(&self, other: &&str) -> bool {
+        self == *other
+    }
+}
+
+impl<const N: usize> PartialEq<String> for FixedStr<N> {
+    fn eq(&self, other: &String) -> bool {
+        self == other.as_str()
+    }
+}
+
+impl<const N: usize> Hash for FixedStr<N> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.as_bytes().hash(state);
+    }
+}
+
+impl<const N: usize> PartialOrd for FixedStr<N> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<const N: usize> Ord for FixedStr<N> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.as_bytes().cmp(other.as_bytes())
+    }
+}
+
+/// 敏感定长字符串，用于密码、认证码等字段
+///
+/// 与[`FixedStr`]布局相同，区别在于：
+///
+/// - 不实现`Copy`，释放时清零内存
+/// - `Debug`/`Display`输出`***`，空值输出为空
+/// - 构造失败时错误信息中不带出内容
+///
+/// 需要读取明文时调用[`SecretStr::expose_secret`]。启用`serde`时序列化为`"***"`，
+/// 需要明文时使用[`serde_support::secret_str`](crate::serde_support::secret_str)
+#[repr(transparent)]
+#[derive(Clone, Default, PartialEq, Eq)]
+pub struct SecretStr<const N: usize>(FixedStr<N>);
+
+impl<const N: usize> SecretStr<N> {
+    /// 内容最大字节数（保留一个字节用于空字节结尾）
+    pub const CAPACITY: usize = N - 1;
+
+    /// 创建空值
+    pub const fn new() -> Self {
+        Self(FixedStr::new())
+    }
+
+    /// 从GB18030编码的字节创建
+    pub fn from_bytes(bytes: &[u8]) -> CtpResult<Self> {
+        FixedStr::from_bytes(bytes).map(Self)
+    }
+
+    /// 明文内容字节（不含结尾的空字节）
+    pub fn expose_secret(&self) -> &[u8] {
+        self.0.as_bytes()
+    }
+
+    /// C字符串指针
+    pub fn as_ptr(&self) -> *const c_char {
+        self.0.as_ptr()
+    }
+
+    /// 内容字节数
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    /// 是否为空
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// 清零内容
+    pub fn clear(&mut self) {
+        self.0 .0.zeroize();
+    }
+}
+
+impl<const N: usize> Drop for SecretStr<N> {
+    fn drop(&mut self) {
+        self.clear();
+    }
+}
+
+impl<const N: usize> FromStr for SecretStr<N> {
+    type Err = CtpError;
+
+    fn from_str(s: &str) -> CtpResult<Self> {
+        if s.contains('\0') {
+            return Err(CtpError::InvalidParameterError(
+                "字符串中间不能包含空字节".to_string(),
+            ));
+        }
+        let bytes = Zeroizing::new(encode(s)?);
+        if bytes.len() > Self::CAPACITY {
+            return Err(CtpError::InvalidParameterError(format!(
+                "敏感字段编码后 {} 字节，超过容量 {}",
+                bytes.len(),
+                Self::CAPACITY
+            )));
+        }
+        Self::from_bytes(&bytes)
+    }
+}
+
+impl<const N: usize> TryFrom<&str> for SecretStr<N> {
+    type Error = CtpError;
+
+    fn try_from(s: &str) -> CtpResult<Self> {
+        s.parse()
+    }
+}
+
+impl<const N: usize> fmt::Display for SecretStr<N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.is_empty() {
+            Ok(())
+        } else {
+            f.write_str("***")
+        }
+    }
+}
+
+impl<const N: usize> fmt::Debug for SecretStr<N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:?}", self.to_string())
+    }
+}
+
+impl<const N: usize> PartialEq<str> for SecretStr<N> {
+    fn eq(&self, other: &str) -> bool {
+        self.0 == *other
+    }
+}
+
+impl<const N: usize> PartialEq<&str> for SecretStr<N> {
+    fn eq(&self, other: &&str) -> bool {
+        self.0 == **other
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<const N: usize> crate::serde_support::FixedBytes for FixedStr<N> {
+    const CAPACITY: usize = N - 1;
+
+    fn raw_bytes(&self) -> &[u8] {
+        self.as_bytes()
+    }
+
+    fn from_raw_bytes(bytes: &[u8]) -> Option<Self> {
+        Self::from_bytes(bytes).ok()
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<const N: usize> serde::Serialize for FixedStr<N> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        crate::serde_support::fixed_str::serialize(self, serializer)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, const N: usize> serde::Deserialize<'de> for FixedStr<N> {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        crate::serde_support::fixed_str::deserialize(deserializer)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<const N: usize> crate::serde_support::FixedBytes for SecretStr<N> {
+    const CAPACITY: usize = N - 1;
+    const SECRET: bool = true;
+
+    fn raw_bytes(&self) -> &[u8] {
+        self.expose_secret()
+    }
+
+    fn from_raw_bytes(bytes: &[u8]) -> Option<Self> {
+        Self::from_bytes(bytes).ok()
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<const N: usize> serde::Serialize for SecretStr<N> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        crate::serde_support::fixed_str::serialize(self, serializer)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, const N: usize> serde::Deserialize<'de> for SecretStr<N> {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        crate::serde_support::fixed_str::deserialize(deserializer)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+
+    #[test]
+    fn test_checked_construction() {
+        let id: FixedStr<11> = "9999".parse().unwrap();
+        assert_eq!(id, "9999");
+        assert_eq!(id.len(), 4);
+        assert_eq!(id.to_string(), "9999");
+
+        // 10字节是上限，11字节没有空间留给结尾的空字节
+        assert!("0123456789".parse::<FixedStr<11>>().is_ok());
+        assert!(matches!(
+            "01234567890".parse::<FixedStr<11>>(),
+            Err(CtpError::InvalidParameterError(_))
+        ));
+        assert!("测试\0".parse::<FixedStr<11>>().is_err());
+    }
+
+    #[test]
+    fn test_truncate_at_char_boundary() {
+        // 每个汉字编码为2字节，容量为4时"经纪商"只能保留两个字
+        let s = FixedStr::<5>::from_str_truncated("经纪商").unwrap();
+        assert_eq!(s, "经纪");
+        assert_eq!(s.as_raw()[4], 0);
+
+        // 容量为3时第二个汉字放不下，不能拆成半个字符
+        let s = FixedStr::<4>::from_str_truncated("经纪商").unwrap();
+        assert_eq!(s, "经");
+        assert_eq!(s.len(), 2);
+    }
+
+    #[test]
+    fn test_from_raw_is_nul_terminated() {
+        let s = FixedStr::from_raw(*b"abcd");
+        assert_eq!(s, "abc");
+        assert_eq!(s.as_raw(), b"abc\0");
+    }
+
+    #[test]
+    fn test_eq_hash_ord_ignore_padding() {
+        let a: FixedStr<9> = "SHFE".parse().unwrap();
+        let mut raw = *a.as_raw();
+        raw[6] = b'x';
+        let b = FixedStr::from_raw(raw);
+        assert_eq!(a, b);
+
+        let set: HashSet<_> = [a, b].into_iter().collect();
+        assert_eq!(set.len(), 1);
+
+        let c: FixedStr<9> = "DCE".parse().unwrap();
+        assert!(c < a);
+        assert_eq!(c, String::from("DCE"));
+    }
+
+    #[test]
+    fn test_secret_is_redacted() {
+        let secret: SecretStr<9> = "pass1234".parse().unwrap();
+        assert_eq!(secret, "pass1234");
+        assert_eq!(secret.expose_secret(), b"pass1234");
+        assert_eq!(secret.to_string(), "***");
+        assert_eq!(format!("{:?}", secret), "\"***\"");
+        assert_eq!(SecretStr::<9>::new().to_string(), "");
+
+        // 超长时错误信息中不能带出内容
+        let err = "password1".parse::<SecretStr<9>>().unwrap_err().to_string();
+        assert!(!err.contains("password1"));
+
+        let mut secret = secret;
+        secret.clear();
+        assert!(secret.is_empty());
+        assert_eq!(secret.0.as_raw(), &[0; 9]);
+    }
+}
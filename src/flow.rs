@@ -0,0 +1,331 @@
+//! 流文件目录管理
+//!
+//! CTP在流文件目录中写入`*.con`文件记录对话、查询和私有流的序号。两个会话（包括同一账户的
+//! 交易和行情API）共用一个目录时会互相覆盖这些文件，跨交易日保留的流文件也可能导致重复
+//! 接收回报。本模块提供：
+//!
+//! - [`FlowDirectory::for_session`]按经纪公司、投资者和API类型派生独立目录
+//! - [`FlowLock`]锁文件，防止两个进程（或同一进程中的两个API实例）使用同一目录
+//! - 交易日变化时按[`RolloverPolicy`]清理或归档旧的流文件
+//!
+//! [`TraderApi::new`](crate::api::TraderApi::new)和[`MdApi::new`](crate::api::MdApi::new)
+//! 对传入的目录统一执行创建、可写检查和加锁，API释放时解锁。
+
+use crate::api::utils::normalize_flow_path;
+use crate::error::{CtpError, CtpResult};
+use std::fs::{self, File, OpenOptions, TryLockError};
+use std::io::{Read, Seek, SeekFrom, Write};
+use std::path::{Path, PathBuf};
+use tracing::info;
+
+/// 锁文件名
+pub const LOCK_FILE_NAME: &str = ".ctp_rust.lock";
+
+/// 记录流文件所属交易日的文件名
+pub const TRADING_DAY_FILE_NAME: &str = ".trading_day";
+
+/// 使用流文件目录的API类型
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "snake_case")
+)]
+pub enum FlowApiKind {
+    /// 交易API
+    Trader,
+    /// 行情API
+    MarketData,
+}
+
+impl FlowApiKind {
+    /// 目录名称
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            FlowApiKind::Trader => "trader",
+            FlowApiKind::MarketData => "md",
+        }
+    }
+}
+
+/// 交易日变化时对旧流文件的处理方式
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "snake_case")
+)]
+pub enum RolloverPolicy {
+    /// 保留，由CTP自行处理
+    #[default]
+    Keep,
+    /// 删除旧的`*.con`文件
+    Clean,
+    /// 移动到`archive/<旧交易日>/`
+    Archive,
+}
+
+/// 流文件目录
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FlowDirectory {
+    path: PathBuf,
+    rollover: RolloverPolicy,
+    trading_day: Option<String>,
+}
+
+impl FlowDirectory {
+    /// 直接使用给定目录
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self {
+            path: path.into(),
+            rollover: RolloverPolicy::Keep,
+            trading_day: None,
+        }
+    }
+
+    /// 按会话派生目录：`<root>/<broker_id>_<investor_id>/<trader|md>/`
+    pub fn for_session(
+        root: impl AsRef<Path>,
+        broker_id: &str,
+        investor_id: &str,
+        kind: FlowApiKind,
+    ) -> Self {
+        let session = format!(
+            "{}_{}",
+            sanitize_component(broker_id),
+            sanitize_component(investor_id)
+        );
+        Self::new(root.as_ref().join(session).join(kind.as_str()))
+    }
+
+    /// 设置交易日变化时的处理方式
+    pub fn with_rollover(mut self, policy: RolloverPolicy) -> Self {
+        self.rollover = policy;
+        self
+    }
+
+    /// 设置当前交易日，`prepare`时与目录中记录的交易日比较
+    pub fn with_trading_day(mut self, trading_day: &str) -> Self {
+        self.trading_day = Some(trading_day.trim().to_string());
+        self
+    }
+
+    /// 目录路径
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// 创建目录并检查可写，交易日变化时按策略处理旧流文件
+    ///
+    /// 处理期间持有目录锁，目录正被其他进程使用时返回错误。返回的路径可以直接传给
+    /// `TraderApi::new`或`MdApi::new`
+    pub fn prepare(&self) -> CtpResult<String> {
+        let path = self.path.to_string_lossy();
+        let lock = FlowLock::acquire(&path)?;
+        if let Some(trading_day) = self.trading_day.as_deref().filter(|d| !d.is_empty()) {
+            rollover(lock.dir(), trading_day, self.rollover)?;
+        }
+        Ok(lock.dir().to_string_lossy().to_string())
+    }
+}
+
+/// 流文件目录锁，释放时解锁
+///
+/// 使用操作系统的文件锁，持有进程退出后自动失效，不会残留过期的锁
+#[derive(Debug)]
+pub struct FlowLock {
+    dir: PathBuf,
+    // 保持打开以持有锁
+    _file: File,
+}
+
+impl FlowLock {
+    /// 规范化目录、创建目录并加锁
+    pub fn acquire(path: &str) -> CtpResult<Self> {
+        let dir = PathBuf::from(normalize_flow_path(path)?);
+        fs::create_dir_all(&dir).map_err(|e| {
+            CtpError::InvalidPath(format!("创建流文件目录 {} 失败: {}", dir.display(), e))
+        })?;
+
+        let lock_path = dir.join(LOCK_FILE_NAME);
+        let mut file = OpenOptions::new()
+            .read(true)
+            .write(true)
+            .create(true)
+            .truncate(false)
+            .open(&lock_path)
+            .map_err(|e| {
+                CtpError::InvalidPath(format!("流文件目录 {} 不可写: {}", dir.display(), e))
+            })?;
+
+        match file.try_lock() {
+            Ok(()) => {}
+            Err(TryLockError::WouldBlock) => {
+                let mut owner = String::new();
+                let _ = file.read_to_string(&mut owner);
+                let owner = owner.trim();
+                return Err(CtpError::InvalidPath(format!(
+                    "流文件目录 {} 已被{}使用，每个会话需要独立的流文件目录",
+                    dir.display(),
+                    if owner.is_empty() {
+                        "其他API实例".to_string()
+                    } else {
+                        format!("进程 {} ", owner)
+                    }
+                )));
+            }
+            Err(TryLockError::Error(e)) => {
+                return Err(CtpError::InvalidPath(format!(
+                    "锁定流文件目录 {} 失败: {}",
+                    dir.display(),
+                    e
+                )))
+            }
+        }
+
+        // 记录持有者便于排查，写入失败说明目录不可写
+        file.set_len(0)
+            .and_then(|_| file.seek(SeekFrom::Start(0)))
+            .and_then(|_| write!(file, "{}", std::process::id()))
+            .map_err(|e| {
+                CtpError::InvalidPath(format!("流文件目录 {} 不可写: {}", dir.display(), e))
+            })?;
+
+        Ok(Self { dir, _file: file })
+    }
+
+    /// 规范化后的目录，以路径分隔符结尾
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+}
+
+// 替换会把目录嵌套到其他位置的字符
+fn sanitize_component(value: &str) -> String {
+    let value: String = value
+        .trim()
+        .chars()
+        .map(|c| match c {
+            '/' | '\\' | ':' | '.' => '_',
+            c => c,
+        })
+        .collect();
+    if value.is_empty() {
+        "_".to_string()
+    } else {
+        value
+    }
+}
+
+fn path_error(path: &Path, e: std::io::Error) -> CtpError {
+    CtpError::InvalidPath(format!("{}: {}", path.display(), e))
+}
+
+// 交易日与目录中记录的不同时处理旧流文件，并记录新的交易日
+fn rollover(dir: &Path, trading_day: &str, policy: RolloverPolicy) -> CtpResult<()> {
+    let marker = dir.join(TRADING_DAY_FILE_NAME);
+    let previous = fs::read_to_string(&marker)
+        .map(|s| s.trim().to_string())
+        .unwrap_or_default();
+    if previous == trading_day {
+        return Ok(());
+    }
+
+    // 没有记录时无法判断流文件所属交易日，保留不动
+    if !previous.is_empty() && policy != RolloverPolicy::Keep {
+        let archive = dir.join("archive").join(&previous);
+        let mut moved = 0;
+        for entry in fs::read_dir(dir).map_err(|e| path_error(dir, e))? {
+            let path = entry.map_err(|e| path_error(dir, e))?.path();
+            if !path.is_file() || path.extension().is_none_or(|ext| ext != "con") {
+                continue;
+            }
+            match policy {
+                RolloverPolicy::Clean => {
+                    fs::remove_file(&path).map_err(|e| path_error(&path, e))?;
+                }
+                RolloverPolicy::Archive => {
+                    fs::create_dir_all(&archive).map_err(|e| path_error(&archive, e))?;
+                    let target = archive.join(path.file_name().unwrap_or_default());
+                    fs::rename(&path, &target).map_err(|e| path_error(&path, e))?;
+                }
+                RolloverPolicy::Keep => {}
+            }
+            moved += 1;
+        }
+        info!(
+            "流文件目录 {} 交易日 {} -> {}，{:?} {} 个流文件",
+            dir.display(),
+            previous,
+            trading_day,
+            policy,
+            moved
+        );
+    }
+
+    fs::write(&marker, trading_day).map_err(|e| path_error(&marker, e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir =
+            std::env::temp_dir().join(format!("ctp_rust_flow_{}_{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        dir
+    }
+
+    #[test]
+    fn test_session_directory() {
+        let dir =
+            FlowDirectory::for_session("/data/flow", "9999", "../123", FlowApiKind::MarketData);
+        assert_eq!(dir.path(), Path::new("/data/flow/9999____123/md"));
+    }
+
+    #[test]
+    fn test_lock_conflict() {
+        let root = temp_dir("lock");
+        let path = root.to_string_lossy().to_string();
+        let lock = FlowLock::acquire(&path).unwrap();
+        assert!(lock.dir().join(LOCK_FILE_NAME).exists());
+
+        let err = FlowLock::acquire(&path).unwrap_err();
+        assert!(err.to_string().contains(&std::process::id().to_string()));
+
+        drop(lock);
+        assert!(FlowLock::acquire(&path).is_ok());
+        let _ = fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn test_rollover() {
+        let root = temp_dir("rollover");
+        let dir = FlowDirectory::new(&root)
+            .with_rollover(RolloverPolicy::Archive)
+            .with_trading_day("20260105");
+        dir.prepare().unwrap();
+        fs::write(root.join("Private.con"), b"x").unwrap();
+
+        // 交易日未变化时不处理
+        dir.prepare().unwrap();
+        assert!(root.join("Private.con").exists());
+
+        dir.clone().with_trading_day("20260106").prepare().unwrap();
+        assert!(!root.join("Private.con").exists());
+        assert!(root.join("archive/20260105/Private.con").exists());
+
+        fs::write(root.join("Public.con"), b"x").unwrap();
+        dir.with_rollover(RolloverPolicy::Clean)
+            .with_trading_day("20260107")
+            .prepare()
+            .unwrap();
+        assert!(!root.join("Public.con").exists());
+        assert_eq!(
+            fs::read_to_string(root.join(TRADING_DAY_FILE_NAME)).unwrap(),
+            "20260107"
+        );
+        let _ = fs::remove_dir_all(&root);
+    }
+}
@@ -0,0 +1,1341 @@
+//! 审计日志
+//!
+//! 挂接到[`TraderApi::set_journal`](crate::api::TraderApi::set_journal)后，每个`req_*`请求
+//! （原始字段字节、请求编号、返回值、时间戳）和每个回调（响应、回报、错误回报）都会追加到
+//! 按日期滚动的日志文件`journal-YYYYMMDD.log`中。
+//!
+//! 文件为UTF-8文本，每行一条记录，字段以制表符分隔：
+//!
+//! ```text
+//! 序号 时间戳(微秒) 方向(req/cb) 名称 请求编号 返回值 是否最后 错误代码 错误信息 报单引用 合约代码 原始字节(十六进制) 校验和
+//! ```
+//!
+//! 校验和是CRC32，计算时包含上一行的校验和，删除、修改或调换任意一行都会使后续校验失败。
+//! 密码、认证码等敏感字段写入前清零。[`JournalReader`]校验并按报单引用、合约或时间范围查询。
+
+use crate::api::md_api::{DepthMarketDataField, ForQuoteRspField, SpecificInstrumentField};
+use crate::api::trader_api::{
+    InputOrderField, InstrumentField, InvestorPositionField, OrderField, ReqAuthenticateField,
+    RspAuthenticateField, TradeField, TradingAccountField,
+};
+use crate::error::{CtpError, CtpResult};
+use crate::types::{
+    AccountregisterField, ContractBankField, ExchangeField, FixedStr, InputBatchOrderActionField,
+    InputExecOrderActionField, InputExecOrderField, InputForQuoteField, InputOrderActionField,
+    InputQuoteActionField, InputQuoteField, InstrumentCommissionRateField,
+    InstrumentMarginRateField, InvestorPositionDetailField, NoticeField, NotifyQueryAccountField,
+    OrderActionField, ParkedOrderActionField, ParkedOrderField, ProductField,
+    QryAccountregisterField, QryContractBankField, QryDepthMarketDataField, QryExchangeField,
+    QryInstrumentCommissionRateField, QryInstrumentField, QryInstrumentMarginRateField,
+    QryInvestorPositionDetailField, QryInvestorPositionField, QryMaxOrderVolumeField,
+    QryNoticeField, QryOrderField, QryProductField, QrySettlementInfoField, QryTradeField,
+    QryTradingAccountField, QryTransferBankField, QryTransferSerialField,
+    RemoveParkedOrderActionField, RemoveParkedOrderField, ReqGenUserCaptchaField,
+    ReqGenUserTextField, ReqQueryAccountField, ReqTransferField, ReqUserAuthMethodField,
+    ReqUserLoginField, ReqUserLoginWithCaptchaField, ReqUserLoginWithOtpField,
+    ReqUserLoginWithTextField, RspGenUserCaptchaField, RspGenUserTextField, RspInfoField,
+    RspRepealField, RspTransferField, RspUserAuthMethodField, RspUserLoginField,
+    SettlementInfoConfirmField, SettlementInfoField, StringConvert,
+    TradingAccountPasswordUpdateField, TransferBankField, TransferSerialField,
+    UserPasswordUpdateField,
+};
+use std::any::Any;
+use std::fmt::{self, Write as _};
+use std::fs::{self, File, OpenOptions};
+use std::io::{BufRead, BufReader, Write};
+use std::mem;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use time::{OffsetDateTime, UtcOffset};
+use tracing::warn;
+
+/// 记录方向
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "snake_case")
+)]
+pub enum JournalDirection {
+    /// 发出的请求
+    Request,
+    /// 收到的回调
+    Callback,
+}
+
+impl JournalDirection {
+    /// 文件中的写法
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            JournalDirection::Request => "req",
+            JournalDirection::Callback => "cb",
+        }
+    }
+
+    fn parse(value: &str) -> Option<Self> {
+        match value {
+            "req" => Some(JournalDirection::Request),
+            "cb" => Some(JournalDirection::Callback),
+            _ => None,
+        }
+    }
+}
+
+/// 一条审计记录
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct JournalEntry {
+    /// 文件内序号，从1开始，由[`AuditJournal::append`]分配
+    pub seq: u64,
+    /// UNIX时间戳，微秒
+    pub timestamp_micros: i64,
+    /// 方向
+    pub direction: JournalDirection,
+    /// CTP接口名称，如`ReqOrderInsert`、`OnRtnOrder`
+    pub name: String,
+    /// 请求编号，回报类回调为0
+    pub request_id: i32,
+    /// 请求函数的返回值；断线回调为断线原因，心跳警告为时间间隔
+    pub return_code: i32,
+    /// 是否最后一条响应
+    pub is_last: bool,
+    /// 响应中的错误代码
+    pub error_id: i32,
+    /// 响应中的错误信息
+    pub error_msg: String,
+    /// 报单引用
+    pub order_ref: String,
+    /// 合约代码
+    pub instrument_id: String,
+    /// 原始字段字节，敏感字段已清零
+    pub payload: Vec<u8>,
+}
+
+impl JournalEntry {
+    fn new(direction: JournalDirection, name: &str) -> Self {
+        Self {
+            seq: 0,
+            timestamp_micros: unix_micros(SystemTime::now()),
+            direction,
+            name: name.to_string(),
+            request_id: 0,
+            return_code: 0,
+            is_last: true,
+            error_id: 0,
+            error_msg: String::new(),
+            order_ref: String::new(),
+            instrument_id: String::new(),
+            payload: Vec::new(),
+        }
+    }
+
+    /// 请求记录
+    pub fn request(name: &str, request_id: i32, return_code: i32) -> Self {
+        Self {
+            request_id,
+            return_code,
+            ..Self::new(JournalDirection::Request, name)
+        }
+    }
+
+    /// 回调记录
+    pub fn callback(name: &str, request_id: i32, is_last: bool) -> Self {
+        Self {
+            request_id,
+            is_last,
+            ..Self::new(JournalDirection::Callback, name)
+        }
+    }
+
+    /// 设置返回值
+    pub fn with_return_code(mut self, return_code: i32) -> Self {
+        self.return_code = return_code;
+        self
+    }
+
+    /// 记录字段的字节，并提取报单引用和合约代码，敏感字段清零
+    pub(crate) fn with_field<T: JournalField>(mut self, field: &T) -> Self {
+        let (order_ref, instrument_id) = field_keys(field);
+        self.order_ref = order_ref;
+        self.instrument_id = instrument_id;
+        self.payload = field.to_bytes();
+        self
+    }
+
+    /// 记录响应信息
+    pub fn with_rsp_info(mut self, rsp_info: Option<&RspInfoField>) -> Self {
+        if let Some(rsp) = rsp_info {
+            self.error_id = rsp.error_id;
+            self.error_msg = rsp.get_error_msg().unwrap_or_default();
+        }
+        self
+    }
+
+    /// 记录时间
+    pub fn timestamp(&self) -> SystemTime {
+        if self.timestamp_micros >= 0 {
+            UNIX_EPOCH + Duration::from_micros(self.timestamp_micros as u64)
+        } else {
+            UNIX_EPOCH - Duration::from_micros(self.timestamp_micros.unsigned_abs())
+        }
+    }
+
+    // 不含校验和的一行
+    fn body(&self) -> String {
+        let mut line = format!(
+            "{}\t{}\t{}\t{}\t{}\t{}\t{}\t{}\t{}\t{}\t{}\t",
+            self.seq,
+            self.timestamp_micros,
+            self.direction.as_str(),
+            clean(&self.name),
+            self.request_id,
+            self.return_code,
+            self.is_last as u8,
+            self.error_id,
+            clean(&self.error_msg),
+            clean(&self.order_ref),
+            clean(&self.instrument_id),
+        );
+        line.reserve(self.payload.len() * 2);
+        for byte in &self.payload {
+            let _ = write!(line, "{:02x}", byte);
+        }
+        line
+    }
+
+    fn parse(body: &str) -> Option<Self> {
+        let fields: Vec<&str> = body.split('\t').collect();
+        let [seq, timestamp, direction, name, request_id, return_code, is_last, error_id, error_msg, order_ref, instrument_id, payload] =
+            fields.as_slice()
+        else {
+            return None;
+        };
+        Some(Self {
+            seq: seq.parse().ok()?,
+            timestamp_micros: timestamp.parse().ok()?,
+            direction: JournalDirection::parse(direction)?,
+            name: name.to_string(),
+            request_id: request_id.parse().ok()?,
+            return_code: return_code.parse().ok()?,
+            is_last: *is_last == "1",
+            error_id: error_id.parse().ok()?,
+            error_msg: error_msg.to_string(),
+            order_ref: order_ref.to_string(),
+            instrument_id: instrument_id.to_string(),
+            payload: decode_hex(payload)?,
+        })
+    }
+}
+
+impl fmt::Display for JournalEntry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let time = OffsetDateTime::from_unix_timestamp_nanos(self.timestamp_micros as i128 * 1000)
+            .unwrap_or(OffsetDateTime::UNIX_EPOCH);
+        write!(
+            f,
+            "{:04}-{:02}-{:02} {:02}:{:02}:{:02}.{:06}Z #{} {} {} request_id={} ret={}",
+            time.year(),
+            time.month() as u8,
+            time.day(),
+            time.hour(),
+            time.minute(),
+            time.second(),
+            time.microsecond(),
+            self.seq,
+            self.direction.as_str(),
+            self.name,
+            self.request_id,
+            self.return_code,
+        )?;
+        if self.error_id != 0 {
+            write!(f, " error={} {}", self.error_id, self.error_msg)?;
+        }
+        if !self.order_ref.is_empty() {
+            write!(f, " order_ref={}", self.order_ref)?;
+        }
+        if !self.instrument_id.is_empty() {
+            write!(f, " instrument={}", self.instrument_id)?;
+        }
+        write!(f, " {}B", self.payload.len())
+    }
+}
+
+struct JournalFile {
+    date: String,
+    file: File,
+    next_seq: u64,
+    prev_checksum: u32,
+}
+
+/// 按日期滚动的追加写审计日志
+pub struct AuditJournal {
+    dir: PathBuf,
+    utc_offset: UtcOffset,
+    sync_each: bool,
+    current: Option<JournalFile>,
+}
+
+impl AuditJournal {
+    /// 在`dir`下写入审计日志，目录不存在时创建
+    ///
+    /// 默认按北京时间（UTC+8）划分日期
+    pub fn open(dir: impl AsRef<Path>) -> CtpResult<Self> {
+        let dir = dir.as_ref().to_path_buf();
+        fs::create_dir_all(&dir).map_err(|e| io_error(&dir, e))?;
+        Ok(Self {
+            dir,
+            utc_offset: UtcOffset::from_hms(8, 0, 0).unwrap_or(UtcOffset::UTC),
+            sync_each: false,
+            current: None,
+        })
+    }
+
+    /// 设置划分日期使用的时区，单位小时
+    pub fn with_utc_offset_hours(mut self, hours: i8) -> Self {
+        self.utc_offset = UtcOffset::from_hms(hours, 0, 0).unwrap_or(UtcOffset::UTC);
+        self
+    }
+
+    /// 每条记录写入后调用`fsync`，默认只写入操作系统缓存
+    pub fn with_sync_each(mut self, sync_each: bool) -> Self {
+        self.sync_each = sync_each;
+        self
+    }
+
+    /// 日志目录
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// 追加一条记录，返回分配的序号
+    pub fn append(&mut self, mut entry: JournalEntry) -> CtpResult<u64> {
+        let date = date_of(entry.timestamp_micros, self.utc_offset);
+        if self.current.as_ref().is_none_or(|c| c.date != date) {
+            self.current = Some(self.open_file(&date)?);
+        }
+        let current = self.current.as_mut().expect("journal file opened above");
+
+        entry.seq = current.next_seq;
+        let body = entry.body();
+        let checksum = chained_checksum(current.prev_checksum, &body);
+        let line = format!("{}\t{:08x}\n", body, checksum);
+        current
+            .file
+            .write_all(line.as_bytes())
+            .map_err(|e| CtpError::Other(format!("写入审计日志失败: {}", e)))?;
+        if self.sync_each {
+            current
+                .file
+                .sync_data()
+                .map_err(|e| CtpError::Other(format!("同步审计日志失败: {}", e)))?;
+        }
+
+        current.next_seq += 1;
+        current.prev_checksum = checksum;
+        Ok(entry.seq)
+    }
+
+    /// 将已写入的记录同步到磁盘
+    pub fn sync(&mut self) -> CtpResult<()> {
+        if let Some(current) = &self.current {
+            current
+                .file
+                .sync_data()
+                .map_err(|e| CtpError::Other(format!("同步审计日志失败: {}", e)))?;
+        }
+        Ok(())
+    }
+
+    // 打开当天的日志文件，已有文件校验通过后接着写；校验失败时不动原文件，改写下一个分段
+    fn open_file(&self, date: &str) -> CtpResult<JournalFile> {
+        let mut segment = 0;
+        loop {
+            let path = self.dir.join(file_name(date, segment));
+            let (next_seq, prev_checksum) = if path.exists() {
+                match verify_file(&path) {
+                    Ok(entries) => (
+                        entries.last().map_or(1, |(entry, _)| entry.seq + 1),
+                        entries.last().map_or(0, |(_, checksum)| *checksum),
+                    ),
+                    Err(e) => {
+                        warn!("审计日志 {} 校验失败，改写新分段: {}", path.display(), e);
+                        segment += 1;
+                        continue;
+                    }
+                }
+            } else {
+                (1, 0)
+            };
+
+            let file = OpenOptions::new()
+                .create(true)
+                .append(true)
+                .open(&path)
+                .map_err(|e| io_error(&path, e))?;
+            return Ok(JournalFile {
+                date: date.to_string(),
+                file,
+                next_seq,
+                prev_checksum,
+            });
+        }
+    }
+}
+
+/// 查询条件，未设置的条件不参与过滤
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct JournalQuery {
+    /// 报单引用
+    pub order_ref: Option<String>,
+    /// 合约代码
+    pub instrument_id: Option<String>,
+    /// 接口名称
+    pub name: Option<String>,
+    /// 起始时间（含）
+    pub from: Option<SystemTime>,
+    /// 结束时间（不含）
+    pub to: Option<SystemTime>,
+}
+
+impl JournalQuery {
+    /// 按报单引用过滤
+    pub fn with_order_ref(mut self, order_ref: &str) -> Self {
+        self.order_ref = Some(order_ref.to_string());
+        self
+    }
+
+    /// 按合约代码过滤
+    pub fn with_instrument(mut self, instrument_id: &str) -> Self {
+        self.instrument_id = Some(instrument_id.to_string());
+        self
+    }
+
+    /// 按接口名称过滤
+    pub fn with_name(mut self, name: &str) -> Self {
+        self.name = Some(name.to_string());
+        self
+    }
+
+    /// 按时间范围过滤，`[from, to)`
+    pub fn with_time_range(mut self, from: SystemTime, to: SystemTime) -> Self {
+        self.from = Some(from);
+        self.to = Some(to);
+        self
+    }
+
+    /// 记录是否满足条件
+    pub fn matches(&self, entry: &JournalEntry) -> bool {
+        self.order_ref
+            .as_ref()
+            .is_none_or(|v| *v == entry.order_ref)
+            && self
+                .instrument_id
+                .as_ref()
+                .is_none_or(|v| *v == entry.instrument_id)
+            && self.name.as_ref().is_none_or(|v| *v == entry.name)
+            && self
+                .from
+                .is_none_or(|from| entry.timestamp_micros >= unix_micros(from))
+            && self
+                .to
+                .is_none_or(|to| entry.timestamp_micros < unix_micros(to))
+    }
+}
+
+/// 审计日志读取
+pub struct JournalReader {
+    dir: PathBuf,
+}
+
+impl JournalReader {
+    /// 读取`dir`下的审计日志
+    pub fn new(dir: impl AsRef<Path>) -> Self {
+        Self {
+            dir: dir.as_ref().to_path_buf(),
+        }
+    }
+
+    /// 全部日志文件，按日期和分段排序
+    pub fn files(&self) -> CtpResult<Vec<PathBuf>> {
+        let mut files = Vec::new();
+        for entry in fs::read_dir(&self.dir).map_err(|e| io_error(&self.dir, e))? {
+            let path = entry.map_err(|e| io_error(&self.dir, e))?.path();
+            let Some(key) = path
+                .file_name()
+                .and_then(|n| n.to_str())
+                .and_then(parse_file_name)
+            else {
+                continue;
+            };
+            files.push((key, path));
+        }
+        files.sort();
+        Ok(files.into_iter().map(|(_, path)| path).collect())
+    }
+
+    /// 读取并校验一个日志文件
+    pub fn read_file(path: impl AsRef<Path>) -> CtpResult<Vec<JournalEntry>> {
+        Ok(verify_file(path.as_ref())?
+            .into_iter()
+            .map(|(entry, _)| entry)
+            .collect())
+    }
+
+    /// 校验全部日志文件，返回记录总数
+    pub fn verify(&self) -> CtpResult<usize> {
+        let mut count = 0;
+        for path in self.files()? {
+            count += verify_file(&path)?.len();
+        }
+        Ok(count)
+    }
+
+    /// 按条件查询，结果按文件和序号排列
+    ///
+    /// 文件校验失败时保留失败位置之前的记录，失败原因记入`corrupt`，不影响其他文件
+    pub fn query(&self, query: &JournalQuery) -> CtpResult<JournalQueryResult> {
+        let mut result = JournalQueryResult::default();
+        for path in self.files()? {
+            let (entries, error) = match scan_file(&path) {
+                Ok(scanned) => scanned,
+                Err(e) => (Vec::new(), Some(e)),
+            };
+            result.entries.extend(
+                entries
+                    .into_iter()
+                    .map(|(entry, _)| entry)
+                    .filter(|entry| query.matches(entry)),
+            );
+            if let Some(error) = error {
+                result.corrupt.push((path, error));
+            }
+        }
+        Ok(result)
+    }
+}
+
+/// 查询结果
+#[derive(Debug, Default)]
+pub struct JournalQueryResult {
+    /// 校验通过且满足条件的记录
+    pub entries: Vec<JournalEntry>,
+    /// 校验失败的文件及原因，失败位置之前的记录仍在`entries`中
+    pub corrupt: Vec<(PathBuf, CtpError)>,
+}
+
+// 读取文件并逐行校验，返回记录及其校验和
+fn verify_file(path: &Path) -> CtpResult<VerifiedEntries> {
+    match scan_file(path)? {
+        (entries, None) => Ok(entries),
+        (_, Some(e)) => Err(e),
+    }
+}
+
+// 通过校验的记录及其校验和
+type VerifiedEntries = Vec<(JournalEntry, u32)>;
+
+// 逐行校验到第一处失败为止，返回之前通过校验的记录和失败原因
+fn scan_file(path: &Path) -> CtpResult<(VerifiedEntries, Option<CtpError>)> {
+    let file = File::open(path).map_err(|e| io_error(path, e))?;
+    let mut reader = BufReader::new(file);
+    let mut entries = Vec::new();
+    let mut prev_checksum = 0;
+    let mut line = String::new();
+    let mut line_no = 0;
+    loop {
+        line.clear();
+        let n = reader.read_line(&mut line).map_err(|e| io_error(path, e))?;
+        if n == 0 {
+            break;
+        }
+        line_no += 1;
+        let corrupt = |reason: &str| {
+            CtpError::Other(format!(
+                "审计日志 {} 第 {} 行{}",
+                path.display(),
+                line_no,
+                reason
+            ))
+        };
+
+        let Some(line) = line.strip_suffix('\n') else {
+            return Ok((entries, Some(corrupt("不完整"))));
+        };
+        let Some((body, checksum)) = line.rsplit_once('\t') else {
+            return Ok((entries, Some(corrupt("格式错误"))));
+        };
+        let Ok(checksum) = u32::from_str_radix(checksum, 16) else {
+            return Ok((entries, Some(corrupt("格式错误"))));
+        };
+        if chained_checksum(prev_checksum, body) != checksum {
+            return Ok((entries, Some(corrupt("校验和不匹配"))));
+        }
+        let Some(entry) = JournalEntry::parse(body) else {
+            return Ok((entries, Some(corrupt("格式错误"))));
+        };
+        if entry.seq != entries.len() as u64 + 1 {
+            return Ok((entries, Some(corrupt("序号不连续"))));
+        }
+        prev_checksum = checksum;
+        entries.push((entry, checksum));
+    }
+    Ok((entries, None))
+}
+
+fn file_name(date: &str, segment: u32) -> String {
+    if segment == 0 {
+        format!("journal-{}.log", date)
+    } else {
+        format!("journal-{}.{}.log", date, segment)
+    }
+}
+
+fn parse_file_name(name: &str) -> Option<(String, u32)> {
+    let stem = name.strip_prefix("journal-")?.strip_suffix(".log")?;
+    let (date, segment) = match stem.split_once('.') {
+        Some((date, segment)) => (date, segment.parse().ok()?),
+        None => (stem, 0),
+    };
+    if date.len() != 8 || !date.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    Some((date.to_string(), segment))
+}
+
+fn date_of(timestamp_micros: i64, offset: UtcOffset) -> String {
+    let time = OffsetDateTime::from_unix_timestamp(timestamp_micros.div_euclid(1_000_000))
+        .unwrap_or(OffsetDateTime::UNIX_EPOCH)
+        .to_offset(offset);
+    format!(
+        "{:04}{:02}{:02}",
+        time.year(),
+        time.month() as u8,
+        time.day()
+    )
+}
+
+fn unix_micros(time: SystemTime) -> i64 {
+    match time.duration_since(UNIX_EPOCH) {
+        Ok(d) => d.as_micros() as i64,
+        Err(e) => -(e.duration().as_micros() as i64),
+    }
+}
+
+fn io_error(path: &Path, e: std::io::Error) -> CtpError {
+    CtpError::Other(format!("审计日志 {}: {}", path.display(), e))
+}
+
+// 文本字段中的制表符和换行会破坏行格式
+fn clean(value: &str) -> String {
+    value.replace(['\t', '\n', '\r'], " ")
+}
+
+fn decode_hex(value: &str) -> Option<Vec<u8>> {
+    if !value.len().is_multiple_of(2) {
+        return None;
+    }
+    (0..value.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(value.get(i..i + 2)?, 16).ok())
+        .collect()
+}
+
+const CRC32_TABLE: [u32; 256] = {
+    let mut table = [0u32; 256];
+    let mut i = 0;
+    while i < 256 {
+        let mut crc = i as u32;
+        let mut bit = 0;
+        while bit < 8 {
+            crc = if crc & 1 != 0 {
+                (crc >> 1) ^ 0xEDB8_8320
+            } else {
+                crc >> 1
+            };
+            bit += 1;
+        }
+        table[i] = crc;
+        i += 1;
+    }
+    table
+};
+
+fn crc32_update(mut crc: u32, bytes: &[u8]) -> u32 {
+    for &byte in bytes {
+        crc = CRC32_TABLE[((crc ^ byte as u32) & 0xff) as usize] ^ (crc >> 8);
+    }
+    crc
+}
+
+// 上一行的校验和参与计算，使记录形成链
+fn chained_checksum(prev: u32, body: &str) -> u32 {
+    let crc = crc32_update(!0, &prev.to_le_bytes());
+    !crc32_update(crc, body.as_bytes())
+}
+
+// 提取报单引用和合约代码
+fn field_keys(field: &dyn Any) -> (String, String) {
+    macro_rules! keys {
+        ($($ty:ty => $($key:ident),+;)*) => {
+            $(
+                if let Some(value) = field.downcast_ref::<$ty>() {
+                    #[allow(unused_mut)]
+                    let mut keys = (String::new(), String::new());
+                    $(keys!(@set keys, value, $key);)+
+                    return keys;
+                }
+            )*
+        };
+        (@set $keys:ident, $value:ident, order_ref) => {
+            $keys.0 = $value.order_ref.to_utf8_string().unwrap_or_default()
+        };
+        (@set $keys:ident, $value:ident, instrument_id) => {
+            $keys.1 = $value.instrument_id.to_utf8_string().unwrap_or_default()
+        };
+    }
+
+    keys! {
+        InputOrderField => order_ref, instrument_id;
+        InputOrderActionField => order_ref, instrument_id;
+        OrderField => order_ref, instrument_id;
+        OrderActionField => order_ref, instrument_id;
+        TradeField => order_ref, instrument_id;
+        ParkedOrderField => order_ref, instrument_id;
+        ParkedOrderActionField => order_ref, instrument_id;
+        InputExecOrderField => instrument_id;
+        InputExecOrderActionField => instrument_id;
+        InputForQuoteField => instrument_id;
+        InputQuoteField => instrument_id;
+        InputQuoteActionField => instrument_id;
+        InstrumentField => instrument_id;
+        InstrumentMarginRateField => instrument_id;
+        InstrumentCommissionRateField => instrument_id;
+        InvestorPositionField => instrument_id;
+        InvestorPositionDetailField => instrument_id;
+        DepthMarketDataField => instrument_id;
+        ForQuoteRspField => instrument_id;
+        SpecificInstrumentField => instrument_id;
+        QryOrderField => instrument_id;
+        QryTradeField => instrument_id;
+        QryInstrumentField => instrument_id;
+        QryInstrumentMarginRateField => instrument_id;
+        QryInstrumentCommissionRateField => instrument_id;
+        QryInvestorPositionField => instrument_id;
+        QryInvestorPositionDetailField => instrument_id;
+        QryMaxOrderVolumeField => instrument_id;
+        QryDepthMarketDataField => instrument_id;
+    }
+    (String::new(), String::new())
+}
+
+mod sealed {
+    pub trait Sealed {}
+}
+
+/// 可以写入审计日志的CTP字段
+///
+/// 只为`#[repr(C)]`的CTP字段结构实现。逐个字段写入零值缓冲区的对应偏移处，
+/// 填充字节保持为零，与C++端看到的布局一致；敏感字段不写入，保持为零。
+pub(crate) trait JournalField: sealed::Sealed + Any {
+    /// 按`#[repr(C)]`布局编码的字节
+    fn to_bytes(&self) -> Vec<u8>;
+}
+
+// 无字段的请求和回调，如登出
+impl sealed::Sealed for () {}
+
+impl JournalField for () {
+    fn to_bytes(&self) -> Vec<u8> {
+        Vec::new()
+    }
+}
+
+// 字段成员的编码，按C++端的内存表示写入；`SecretStr`不实现，敏感字段必须在`redact`中列出
+trait RawValue {
+    fn write_to(&self, out: &mut [u8]);
+}
+
+impl<const N: usize> RawValue for [u8; N] {
+    fn write_to(&self, out: &mut [u8]) {
+        out[..N].copy_from_slice(self);
+    }
+}
+
+impl<const N: usize> RawValue for FixedStr<N> {
+    fn write_to(&self, out: &mut [u8]) {
+        self.as_raw().write_to(out);
+    }
+}
+
+macro_rules! raw_value_ne {
+    ($($ty:ty),*) => {
+        $(
+            impl RawValue for $ty {
+                fn write_to(&self, out: &mut [u8]) {
+                    self.to_ne_bytes().write_to(out);
+                }
+            }
+        )*
+    };
+}
+
+raw_value_ne!(u8, i32, f64);
+
+// 为字段结构实现`JournalField`：逐个成员写入零值缓冲区的偏移处，`redact`中的成员保持为零。
+// 解构时不使用`..`，结构体增加成员而这里未列出时编译失败
+macro_rules! journal_fields {
+    ($($ty:ident { $($field:ident),* $(,)? } $(redact { $($secret:ident),+ })?;)*) => {
+        $(
+            impl sealed::Sealed for $ty {}
+
+            impl JournalField for $ty {
+                fn to_bytes(&self) -> Vec<u8> {
+                    let mut bytes = vec![0u8; mem::size_of::<$ty>()];
+                    let $ty { $($field,)* $($($secret: _,)+)? } = self;
+                    $($field.write_to(&mut bytes[mem::offset_of!($ty, $field)..]);)*
+                    bytes
+                }
+            }
+        )*
+    };
+}
+
+journal_fields! {
+    AccountregisterField {
+        trade_day, bank_id, bank_branch_id, bank_account, broker_id, broker_branch_id,
+        account_id, id_card_type, identified_card_no, customer_name, currency_id,
+        open_or_destroy, reg_date, out_date, tid, cust_type, bank_acc_type, long_customer_name,
+    };
+    ContractBankField {
+        broker_id, bank_id, bank_brch_id, bank_name,
+    };
+    DepthMarketDataField {
+        trading_day, reserve1, exchange_id, reserve2, last_price, pre_settlement_price,
+        pre_close_price, pre_open_interest, open_price, highest_price, lowest_price, volume,
+        turnover, open_interest, close_price, settlement_price, upper_limit_price,
+        lower_limit_price, pre_delta, curr_delta, update_time, update_millisec, bid_price1,
+        bid_volume1, ask_price1, ask_volume1, bid_price2, bid_volume2, ask_price2, ask_volume2,
+        bid_price3, bid_volume3, ask_price3, ask_volume3, bid_price4, bid_volume4, ask_price4,
+        ask_volume4, bid_price5, bid_volume5, ask_price5, ask_volume5, average_price,
+        action_day, instrument_id, exchange_inst_id, banding_upper_price, banding_lower_price,
+    };
+    ExchangeField {
+        exchange_id, exchange_name, exchange_property,
+    };
+    InputBatchOrderActionField {
+        broker_id, investor_id, order_action_ref, request_id, front_id, session_id, exchange_id,
+        user_id, invest_unit_id, ip_address, mac_address,
+    };
+    InputExecOrderActionField {
+        broker_id, investor_id, exec_order_action_ref, exec_order_ref, request_id, front_id,
+        session_id, exchange_id, exec_order_sys_id, action_flag, user_id, instrument_id,
+        invest_unit_id, ip_address, mac_address,
+    };
+    InputExecOrderField {
+        broker_id, investor_id, instrument_id, exec_order_ref, user_id, volume, request_id,
+        business_unit, offset_flag, hedge_flag, action_type, posidir, reserve_position_flag,
+        close_flag, exchange_id, invest_unit_id, account_id, currency_id, client_id, ip_address,
+        mac_address,
+    };
+    InputForQuoteField {
+        broker_id, investor_id, instrument_id, for_quote_ref, user_id, exchange_id,
+        invest_unit_id, ip_address, mac_address,
+    };
+    InputOrderActionField {
+        broker_id, investor_id, order_action_ref, order_ref, request_id, front_id, session_id,
+        exchange_id, order_sys_id, action_flag, limit_price, volume_change, user_id,
+        instrument_id, invest_unit_id, ip_address, mac_address,
+    };
+    InputOrderField {
+        broker_id, investor_id, instrument_id, order_ref, user_id, order_price_type, direction,
+        comb_offset_flag, comb_hedge_flag, limit_price, volume_total_original, time_condition,
+        gtd_date, volume_condition, min_volume, contingent_condition, stop_price,
+        force_close_reason, is_auto_suspend, business_unit, request_id, user_force_close,
+        is_swap_order, exchange_id, invest_unit_id, account_id, currency_id, client_id,
+        mac_address, exchange_inst_id, ip_address,
+    };
+    InputQuoteActionField {
+        broker_id, investor_id, quote_action_ref, quote_ref, request_id, front_id, session_id,
+        exchange_id, quote_sys_id, action_flag, user_id, instrument_id, invest_unit_id,
+        client_id, ip_address, mac_address,
+    };
+    InputQuoteField {
+        broker_id, investor_id, instrument_id, quote_ref, user_id, ask_price, bid_price,
+        ask_volume, bid_volume, request_id, business_unit, ask_offset_flag, bid_offset_flag,
+        ask_hedge_flag, bid_hedge_flag, ask_order_ref, bid_order_ref, for_quote_sys_id,
+        exchange_id, invest_unit_id, account_id, currency_id, client_id, ip_address,
+        mac_address,
+    };
+    InstrumentCommissionRateField {
+        instrument_id, investor_range, broker_id, investor_id, open_ratio_by_money,
+        open_ratio_by_volume, close_ratio_by_money, close_ratio_by_volume,
+        close_today_ratio_by_money, close_today_ratio_by_volume, exchange_id, biz_type,
+        invest_unit_id,
+    };
+    InstrumentField {
+        instrument_id, exchange_id, instrument_name, exchange_inst_id, product_id,
+        product_class, delivery_year, delivery_month, max_market_order_volume,
+        min_market_order_volume, max_limit_order_volume, min_limit_order_volume,
+        volume_multiple, price_tick, create_date, open_date, expire_date, start_deliv_date,
+        end_deliv_date, inst_life_phase, is_trading, position_type, position_date_type,
+        long_margin_ratio, short_margin_ratio, max_margin_side_algorithm, underlying_instr_id,
+        strike_price, options_type, underlying_multiple, combination_type,
+    };
+    InstrumentMarginRateField {
+        instrument_id, investor_range, broker_id, investor_id, hedge_flag,
+        long_margin_ratio_by_money, long_margin_ratio_by_volume, short_margin_ratio_by_money,
+        short_margin_ratio_by_volume, is_relative, exchange_id, invest_unit_id,
+    };
+    InvestorPositionDetailField {
+        instrument_id, broker_id, investor_id, hedge_flag, direction, open_date, trade_id,
+        volume, open_price, trading_day, settlement_id, trade_type, comb_instrument_id,
+        exchange_id, close_profit_by_date, close_profit_by_trade, position_profit_by_date,
+        position_profit_by_trade, margin, exch_margin, margin_rate_by_money,
+        margin_rate_by_volume, last_settlement_price, settlement_price, close_volume,
+        close_amount, time_first_volume, invest_unit_id, spec_posidir,
+    };
+    InvestorPositionField {
+        instrument_id, broker_id, investor_id, posi_direction, hedge_flag, position_date,
+        yd_position, position, long_frozen, short_frozen, long_frozen_amount,
+        short_frozen_amount, open_volume, close_volume, open_amount, close_amount,
+        position_cost, pre_margin, use_margin, frozen_margin, frozen_cash, frozen_commission,
+        cash_in, commission, close_profit, position_profit, pre_settlement_price,
+        settlement_price, trading_day, settlement_id, open_cost, exchange_margin, comb_position,
+        comb_long_frozen, comb_short_frozen, close_profit_by_date, close_profit_by_trade,
+        today_position, margin_rate_by_money, margin_rate_by_volume, strike_frozen,
+        strike_frozen_amount, abandon_frozen, exchange_id, yd_strike_frozen, invest_unit_id,
+        position_cost_offset, tas_position, tas_position_cost,
+    };
+    NoticeField {
+        broker_id, content, url_link,
+    };
+    NotifyQueryAccountField {
+        trade_code, bank_id, bank_branch_id, broker_id, broker_branch_id, trade_date,
+        trade_time, bank_serial, trading_day, plate_serial, last_fragment, session_id,
+        customer_name, id_card_type, identified_card_no, cust_type, bank_account, account_id,
+        future_serial, install_id, user_id, verify_cert_no_flag, currency_id, digest,
+        bank_acc_type, device_id, bank_secu_acc_type, broker_id_by_bank, bank_secu_acc,
+        bank_pwd_flag, secu_pwd_flag, oper_no, request_id, tid, bank_use_amount,
+        bank_fetch_amount, error_id, error_msg, long_customer_name,
+    } redact { bank_password, password };
+    OrderActionField {
+        broker_id, investor_id, order_action_ref, order_ref, request_id, front_id, session_id,
+        exchange_id, order_sys_id, action_flag, limit_price, volume_change, action_date,
+        action_time, trader_id, install_id, order_local_id, action_local_id, participant_id,
+        client_id, business_unit, order_action_status, user_id, status_msg, instrument_id,
+        branch_id, invest_unit_id, ip_address, mac_address,
+    };
+    OrderField {
+        broker_id, investor_id, instrument_id, order_ref, user_id, order_price_type, direction,
+        comb_offset_flag, comb_hedge_flag, limit_price, volume_total_original, time_condition,
+        gtd_date, volume_condition, min_volume, contingent_condition, stop_price,
+        force_close_reason, is_auto_suspend, business_unit, request_id, order_local_id,
+        exchange_id, participant_id, client_id, exchange_inst_id, trader_id, install_id,
+        order_submit_status, notify_sequence, trading_day, settlement_id, order_sys_id,
+        order_source, order_status, order_type, volume_traded, volume_total, insert_date,
+        insert_time, active_time, suspend_time, update_time, cancel_time, active_trader_id,
+        clearing_part_id, sequence_no, front_id, session_id, user_product_info, status_msg,
+        user_force_close, active_user_id, broker_order_seq, relative_order_sys_id,
+        zczc_total_traded_volume, is_swap_order, branch_id, invest_unit_id, account_id,
+        currency_id, mac_address, ip_address,
+    };
+    ParkedOrderActionField {
+        broker_id, investor_id, order_action_ref, order_ref, request_id, front_id, session_id,
+        exchange_id, order_sys_id, action_flag, limit_price, volume_change, user_id,
+        instrument_id, parked_order_action_id, user_type, status, error_id, error_msg,
+        invest_unit_id, ip_address, mac_address,
+    };
+    ParkedOrderField {
+        broker_id, investor_id, instrument_id, order_ref, user_id, order_price_type, direction,
+        comb_offset_flag, comb_hedge_flag, limit_price, volume_total_original, time_condition,
+        gtd_date, volume_condition, min_volume, contingent_condition, stop_price,
+        force_close_reason, is_auto_suspend, business_unit, request_id, user_force_close,
+        exchange_id, parked_order_id, user_type, status, error_id, error_msg, is_swap_order,
+        invest_unit_id, account_id, currency_id, client_id, ip_address, mac_address,
+    };
+    ProductField {
+        product_id, product_name, exchange_id, product_class, volume_multiple, price_tick,
+        max_market_order_volume, min_market_order_volume, max_limit_order_volume,
+        min_limit_order_volume, position_type, position_date_type, close_deal_type,
+        trade_currency_id, margin_currency_id,
+    };
+    QryAccountregisterField {
+        broker_id, account_id, bank_id, bank_branch_id, currency_id,
+    };
+    QryContractBankField {
+        broker_id, bank_id, bank_brch_id,
+    };
+    QryDepthMarketDataField {
+        instrument_id, exchange_id,
+    };
+    QryExchangeField {
+        exchange_id,
+    };
+    QryInstrumentCommissionRateField {
+        broker_id, investor_id, instrument_id, exchange_id, invest_unit_id,
+    };
+    QryInstrumentField {
+        instrument_id, exchange_id, exchange_inst_id, product_id,
+    };
+    QryInstrumentMarginRateField {
+        broker_id, investor_id, instrument_id, hedge_flag, exchange_id, invest_unit_id,
+    };
+    QryInvestorPositionDetailField {
+        broker_id, investor_id, instrument_id, exchange_id, invest_unit_id,
+    };
+    QryInvestorPositionField {
+        broker_id, investor_id, instrument_id, exchange_id, invest_unit_id,
+    };
+    QryMaxOrderVolumeField {
+        broker_id, investor_id, instrument_id, direction, offset_flag, hedge_flag, max_volume,
+        exchange_id, invest_unit_id,
+    };
+    QryNoticeField {
+        broker_id,
+    };
+    QryOrderField {
+        broker_id, investor_id, instrument_id, exchange_id, order_sys_id, insert_time_start,
+        insert_time_end, invest_unit_id,
+    };
+    QryProductField {
+        product_id, product_class, exchange_id,
+    };
+    QrySettlementInfoField {
+        broker_id, investor_id, trading_day, account_id, currency_id,
+    };
+    QryTradeField {
+        broker_id, investor_id, instrument_id, exchange_id, trade_id, trade_time_start,
+        trade_time_end, invest_unit_id,
+    };
+    QryTradingAccountField {
+        broker_id, investor_id, currency_id, biz_type, account_id,
+    };
+    QryTransferBankField {
+        bank_id, bank_brch_id,
+    };
+    QryTransferSerialField {
+        broker_id, account_id, bank_id, currency_id,
+    };
+    RemoveParkedOrderActionField {
+        broker_id, investor_id, parked_order_action_id, invest_unit_id,
+    };
+    RemoveParkedOrderField {
+        broker_id, investor_id, parked_order_id, invest_unit_id,
+    };
+    ReqAuthenticateField {
+        broker_id, user_id, user_product_info, app_id,
+    } redact { auth_code };
+    ReqGenUserCaptchaField {
+        trading_day, broker_id, user_id,
+    };
+    ReqGenUserTextField {
+        trading_day, broker_id, user_id,
+    };
+    ReqQueryAccountField {
+        trade_code, bank_id, bank_branch_id, broker_id, broker_branch_id, trade_date,
+        trade_time, bank_serial, trading_day, plate_serial, last_fragment, session_id,
+        customer_name, id_card_type, identified_card_no, cust_type, bank_account, account_id,
+        future_serial, install_id, user_id, verify_cert_no_flag, currency_id, digest,
+        bank_acc_type, device_id, bank_secu_acc_type, broker_id_by_bank, bank_secu_acc,
+        bank_pwd_flag, secu_pwd_flag, oper_no, request_id, tid, long_customer_name,
+    } redact { bank_password, password };
+    ReqTransferField {
+        trade_code, bank_id, bank_branch_id, broker_id, broker_branch_id, trade_date,
+        trade_time, bank_serial, trading_day, plate_serial, last_fragment, session_id,
+        customer_name, id_card_type, identified_card_no, cust_type, bank_account, account_id,
+        install_id, future_serial, user_id, verify_cert_no_flag, currency_id, trade_amount,
+        future_fetch_amount, fee_pay_flag, cust_fee, broker_fee, message, digest, bank_acc_type,
+        device_id, bank_secu_acc_type, broker_id_by_bank, bank_secu_acc, bank_pwd_flag,
+        secu_pwd_flag, oper_no, request_id, tid, transfer_status, long_customer_name,
+    } redact { bank_password, password };
+    ReqUserAuthMethodField {
+        trading_day, broker_id, user_id,
+    };
+    ReqUserLoginField {
+        trading_day, broker_id, user_id, user_product_info, interface_product_info,
+        protocol_info, mac_address, client_ip_address, client_ip_port, login_remark,
+    } redact { password, one_time_password };
+    ReqUserLoginWithCaptchaField {
+        trading_day, broker_id, user_id, user_product_info, interface_product_info,
+        protocol_info, mac_address, reserve1, login_remark, client_ip_port, client_ip_address,
+    } redact { password, captcha };
+    ReqUserLoginWithOtpField {
+        trading_day, broker_id, user_id, user_product_info, interface_product_info,
+        protocol_info, mac_address, reserve1, login_remark, client_ip_port, client_ip_address,
+    } redact { password, otp_password };
+    ReqUserLoginWithTextField {
+        trading_day, broker_id, user_id, user_product_info, interface_product_info,
+        protocol_info, mac_address, reserve1, login_remark, client_ip_port, client_ip_address,
+    } redact { password, text };
+    RspAuthenticateField {
+        broker_id, user_id, user_product_info, app_id, app_type,
+    };
+    RspGenUserCaptchaField {
+        broker_id, user_id, captcha_info_len, captcha_info,
+    };
+    RspGenUserTextField {
+        user_text_seq,
+    };
+    RspRepealField {
+        repeal_time_interval, repealed_times, bank_repeal_flag, broker_repeal_flag,
+        plate_repeal_serial, bank_repeal_serial, future_repeal_serial, trade_code, bank_id,
+        bank_branch_id, broker_id, broker_branch_id, trade_date, trade_time, bank_serial,
+        trading_day, plate_serial, last_fragment, session_id, customer_name, id_card_type,
+        identified_card_no, cust_type, bank_account, account_id, install_id, future_serial,
+        user_id, verify_cert_no_flag, currency_id, trade_amount, future_fetch_amount,
+        fee_pay_flag, cust_fee, broker_fee, message, digest, bank_acc_type, device_id,
+        bank_secu_acc_type, broker_id_by_bank, bank_secu_acc, bank_pwd_flag, secu_pwd_flag,
+        oper_no, request_id, tid, transfer_status, error_id, error_msg, long_customer_name,
+    } redact { bank_password, password };
+    RspTransferField {
+        trade_code, bank_id, bank_branch_id, broker_id, broker_branch_id, trade_date,
+        trade_time, bank_serial, trading_day, plate_serial, last_fragment, session_id,
+        customer_name, id_card_type, identified_card_no, cust_type, bank_account, account_id,
+        install_id, future_serial, user_id, verify_cert_no_flag, currency_id, trade_amount,
+        future_fetch_amount, fee_pay_flag, cust_fee, broker_fee, message, digest, bank_acc_type,
+        device_id, bank_secu_acc_type, broker_id_by_bank, bank_secu_acc, bank_pwd_flag,
+        secu_pwd_flag, oper_no, request_id, tid, transfer_status, error_id, error_msg,
+        long_customer_name,
+    } redact { bank_password, password };
+    RspUserAuthMethodField {
+        usable_auth_method,
+    };
+    RspUserLoginField {
+        trading_day, login_time, broker_id, user_id, system_name, front_id, session_id,
+        max_order_ref, shfe_time, dce_time, czce_time, ffex_time, ine_time,
+    };
+    SettlementInfoConfirmField {
+        broker_id, investor_id, confirm_date, confirm_time, settlement_id, account_id,
+        currency_id,
+    };
+    SettlementInfoField {
+        trading_day, settlement_id, broker_id, investor_id, sequence_no, content, account_id,
+        currency_id,
+    };
+    TradeField {
+        broker_id, investor_id, instrument_id, order_ref, user_id, exchange_id, trade_id,
+        direction, order_sys_id, participant_id, client_id, trading_role, exchange_inst_id,
+        offset_flag, hedge_flag, price, volume, trade_date, trade_time, trade_type,
+        price_source, trader_id, order_local_id, clearing_part_id, business_unit, sequence_no,
+        trading_day, settlement_id, broker_order_seq, trade_source, invest_unit_id,
+    };
+    TradingAccountField {
+        broker_id, account_id, pre_mortgage, pre_credit, pre_deposit, pre_balance, pre_margin,
+        interest_base, interest, deposit, withdraw, frozen_margin, frozen_cash,
+        frozen_commission, curr_margin, cash_in, commission, close_profit, position_profit,
+        balance, available, withdraw_quota, reserve, trading_day, settlement_id, credit,
+        mortgage, exchange_margin, delivery_margin, exchange_delivery_margin, reserve_balance,
+        currency_id, pre_fund_mortgage_in, pre_fund_mortgage_out, fund_mortgage_in,
+        fund_mortgage_out, fund_mortgage_available, mortgageable_fund, spec_product_margin,
+        spec_product_frozen_margin, spec_product_commission, spec_product_frozen_commission,
+        spec_product_position_profit, spec_product_close_profit,
+        spec_product_position_profit_by_alg, spec_product_exchange_margin, biz_type,
+        frozen_swap, remain_swap,
+    };
+    TradingAccountPasswordUpdateField {
+        broker_id, account_id, currency_id,
+    } redact { old_password, new_password };
+    TransferBankField {
+        bank_id, bank_brch_id, bank_name, is_active,
+    };
+    TransferSerialField {
+        plate_serial, trade_date, trading_day, trade_time, trade_code, session_id, bank_id,
+        bank_branch_id, bank_acc_type, bank_account, bank_serial, broker_id, broker_branch_id,
+        future_acc_type, account_id, investor_id, future_serial, id_card_type,
+        identified_card_no, currency_id, trade_amount, cust_fee, broker_fee, availability_flag,
+        operator_code, bank_new_account, error_id, error_msg,
+    };
+    UserPasswordUpdateField {
+        broker_id, user_id,
+    } redact { old_password, new_password };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::PasswordType;
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir =
+            std::env::temp_dir().join(format!("ctp_rust_journal_{}_{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        dir
+    }
+
+    fn order(order_ref: &str, instrument: &str) -> InputOrderField {
+        let mut order = InputOrderField::default();
+        order.order_ref[..order_ref.len()].copy_from_slice(order_ref.as_bytes());
+        order.instrument_id[..instrument.len()].copy_from_slice(instrument.as_bytes());
+        order
+    }
+
+    #[test]
+    fn test_append_and_query() {
+        let dir = temp_dir("query");
+        let mut journal = AuditJournal::open(&dir).unwrap();
+        let insert = order("000001", "rb2501");
+        journal
+            .append(JournalEntry::request("ReqOrderInsert", 7, 0).with_field(&insert))
+            .unwrap();
+        journal
+            .append(
+                JournalEntry::request("ReqOrderInsert", 8, 0)
+                    .with_field(&order("000002", "cu2502")),
+            )
+            .unwrap();
+        let rsp = RspInfoField {
+            error_id: 22,
+            ..Default::default()
+        };
+        journal
+            .append(
+                JournalEntry::callback("OnRspOrderInsert", 7, true)
+                    .with_field(&insert)
+                    .with_rsp_info(Some(&rsp)),
+            )
+            .unwrap();
+        drop(journal);
+
+        // 重新打开后接着写，序号和校验链连续
+        let mut journal = AuditJournal::open(&dir).unwrap();
+        assert_eq!(
+            journal
+                .append(JournalEntry::callback("OnFrontConnected", 0, true))
+                .unwrap(),
+            4
+        );
+
+        let reader = JournalReader::new(&dir);
+        assert_eq!(reader.verify().unwrap(), 4);
+        let entries = reader
+            .query(&JournalQuery::default().with_order_ref("000001"))
+            .unwrap()
+            .entries;
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].payload.len(), mem::size_of::<InputOrderField>());
+        assert_eq!(entries[0].instrument_id, "rb2501");
+        assert_eq!(entries[1].error_id, 22);
+        assert_eq!(
+            reader
+                .query(&JournalQuery::default().with_instrument("cu2502"))
+                .unwrap()
+                .entries[0]
+                .request_id,
+            8
+        );
+        let future = SystemTime::now() + Duration::from_secs(3600);
+        assert!(reader
+            .query(
+                &JournalQuery::default().with_time_range(future, future + Duration::from_secs(1))
+            )
+            .unwrap()
+            .entries
+            .is_empty());
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_tamper_detected() {
+        let dir = temp_dir("tamper");
+        let mut journal = AuditJournal::open(&dir).unwrap();
+        for id in 1..=3 {
+            journal
+                .append(JournalEntry::request("ReqQryOrder", id, 0))
+                .unwrap();
+        }
+        drop(journal);
+
+        let path = JournalReader::new(&dir).files().unwrap().remove(0);
+        let content = fs::read_to_string(&path).unwrap();
+        let lines: Vec<&str> = content.lines().collect();
+        fs::write(&path, format!("{}\n{}\n", lines[0], lines[2])).unwrap();
+        let err = JournalReader::read_file(&path).unwrap_err();
+        assert!(err.to_string().contains("第 2 行"));
+
+        // 已损坏的文件不再追加，写入新分段
+        let mut journal = AuditJournal::open(&dir).unwrap();
+        assert_eq!(
+            journal
+                .append(JournalEntry::request("ReqQryOrder", 4, 0))
+                .unwrap(),
+            1
+        );
+        let files = JournalReader::new(&dir).files().unwrap();
+        assert_eq!(files.len(), 2);
+        assert!(files[1].to_string_lossy().ends_with(".1.log"));
+
+        // 查询保留损坏位置之前的记录和其他文件，损坏单独报告
+        let result = JournalReader::new(&dir)
+            .query(&JournalQuery::default())
+            .unwrap();
+        assert_eq!(result.entries.len(), 2);
+        assert_eq!(result.corrupt.len(), 1);
+        assert_eq!(result.corrupt[0].0, path);
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_torn_line_skipped_by_query() {
+        let dir = temp_dir("torn");
+        let mut journal = AuditJournal::open(&dir).unwrap();
+        for id in 1..=2 {
+            journal
+                .append(JournalEntry::request("ReqQryOrder", id, 0))
+                .unwrap();
+        }
+        drop(journal);
+
+        // 模拟写到一半崩溃
+        let path = JournalReader::new(&dir).files().unwrap().remove(0);
+        let mut file = OpenOptions::new().append(true).open(&path).unwrap();
+        file.write_all(b"3\t").unwrap();
+        drop(file);
+
+        let reader = JournalReader::new(&dir);
+        assert!(reader.verify().is_err());
+        let result = reader.query(&JournalQuery::default()).unwrap();
+        assert_eq!(result.entries.len(), 2);
+        assert_eq!(result.corrupt.len(), 1);
+        assert!(result.corrupt[0].1.to_string().contains("第 3 行不完整"));
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_secrets_redacted() {
+        let req = ReqUserLoginField {
+            password: PasswordType::from_bytes(b"secret123").unwrap(),
+            ..Default::default()
+        };
+        let entry = JournalEntry::request("ReqUserLogin", 1, 0).with_field(&req);
+        assert_eq!(entry.payload.len(), mem::size_of::<ReqUserLoginField>());
+        assert!(!entry.payload.windows(9).any(|w| w == b"secret123"));
+        assert!(!entry.body().contains(
+            &"secret123"
+                .bytes()
+                .map(|b| format!("{:02x}", b))
+                .collect::<String>()
+        ));
+    }
+
+    #[test]
+    fn test_field_bytes_layout() {
+        let mut insert = order("000001", "cu2501");
+        insert.limit_price = 75_000.0;
+        insert.volume_total_original = 3;
+        let bytes = insert.to_bytes();
+        assert_eq!(bytes.len(), mem::size_of::<InputOrderField>());
+
+        let price = mem::offset_of!(InputOrderField, limit_price);
+        assert_eq!(bytes[price..price + 8], 75_000.0f64.to_ne_bytes());
+        let volume = mem::offset_of!(InputOrderField, volume_total_original);
+        assert_eq!(bytes[volume..volume + 4], 3i32.to_ne_bytes());
+        let order_ref = mem::offset_of!(InputOrderField, order_ref);
+        assert_eq!(&bytes[order_ref..order_ref + 6], b"000001");
+
+        assert!(InputOrderField::default()
+            .to_bytes()
+            .iter()
+            .all(|&b| b == 0));
+        assert!(().to_bytes().is_empty());
+    }
+}
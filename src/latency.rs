@@ -0,0 +1,757 @@
+//! 延迟统计
+//!
+//! 挂接到[`TraderApi`](crate::TraderApi)和[`MdApi`](crate::MdApi)后，记录请求发出时间，
+//! 并与响应、报单回报、成交回报按请求编号和报单引用配对；每条行情在回调入口打上本地时间，
+//! 与行情中的`update_time`/`update_millisec`比较得到交易所到本地的延迟。
+//!
+//! 延迟按[`LatencyKind`]、前置地址和合约分别记入[`LatencyHistogram`]，直方图采用HDR的
+//! 对数线性分桶，在微秒到小时的范围内相对误差约3%，内存占用固定。
+//!
+//! ```no_run
+//! use ctp_rust::latency::{LatencyKind, LatencyRecorder};
+//! use std::sync::{Arc, Mutex};
+//!
+//! # fn main() -> ctp_rust::CtpResult<()> {
+//! let recorder = Arc::new(Mutex::new(LatencyRecorder::new()));
+//! let mut trader = ctp_rust::TraderApi::new(None, None)?;
+//! trader.set_latency_recorder(recorder.clone());
+//! // ...
+//! let ack = recorder.lock().unwrap().histogram(LatencyKind::OrderAck);
+//! println!("报单确认 p99: {:?}", ack.value_at_quantile(0.99));
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::api::md_api::DepthMarketDataField;
+use crate::api::trader_api::{InputOrderField, OrderField, TradeField};
+use crate::models::parse_time;
+use std::collections::HashMap;
+use std::fmt;
+use std::time::{Duration, Instant, SystemTime};
+use time::{OffsetDateTime, UtcOffset};
+
+/// 延迟类别
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "snake_case")
+)]
+pub enum LatencyKind {
+    /// 报单请求到CTP首次确认（报单回报或错误响应）
+    OrderAck,
+    /// 报单请求到交易所接受（回报中出现报单编号）
+    ExchangeAccept,
+    /// 报单请求到首笔成交回报
+    Fill,
+    /// 查询请求到最后一条响应
+    QueryRoundTrip,
+    /// 其他请求到最后一条响应
+    RequestRoundTrip,
+    /// 行情交易所时间到本地回调
+    TickLag,
+}
+
+impl LatencyKind {
+    /// 全部类别
+    pub const ALL: [LatencyKind; 6] = [
+        LatencyKind::OrderAck,
+        LatencyKind::ExchangeAccept,
+        LatencyKind::Fill,
+        LatencyKind::QueryRoundTrip,
+        LatencyKind::RequestRoundTrip,
+        LatencyKind::TickLag,
+    ];
+
+    /// 类别名称，可用作指标标签
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            LatencyKind::OrderAck => "order_ack",
+            LatencyKind::ExchangeAccept => "exchange_accept",
+            LatencyKind::Fill => "fill",
+            LatencyKind::QueryRoundTrip => "query_round_trip",
+            LatencyKind::RequestRoundTrip => "request_round_trip",
+            LatencyKind::TickLag => "tick_lag",
+        }
+    }
+}
+
+impl fmt::Display for LatencyKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+// 每个2的幂区间分为32个子桶，小于64微秒的值精确记录
+const SUB_BUCKET_BITS: u32 = 5;
+const SUB_BUCKETS: usize = 1 << SUB_BUCKET_BITS;
+const LINEAR_LIMIT: u64 = 2 * SUB_BUCKETS as u64;
+
+fn bucket_index(value: u64) -> usize {
+    if value < LINEAR_LIMIT {
+        return value as usize;
+    }
+    let exponent = 63 - value.leading_zeros();
+    let shift = exponent - SUB_BUCKET_BITS;
+    let sub = (value >> shift) as usize & (SUB_BUCKETS - 1);
+    LINEAR_LIMIT as usize + (exponent - SUB_BUCKET_BITS - 1) as usize * SUB_BUCKETS + sub
+}
+
+// 桶内的最大值
+fn bucket_upper_bound(index: usize) -> u64 {
+    if index < LINEAR_LIMIT as usize {
+        return index as u64;
+    }
+    let offset = index - LINEAR_LIMIT as usize;
+    let shift = (offset / SUB_BUCKETS) as u32 + 1;
+    let sub = (offset % SUB_BUCKETS) as u64;
+    let lower = (SUB_BUCKETS as u64 + sub) << shift;
+    lower + ((1u64 << shift) - 1)
+}
+
+/// 延迟直方图，以微秒为单位
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct LatencyHistogram {
+    counts: Vec<u64>,
+    count: u64,
+    min: u64,
+    max: u64,
+    sum: u128,
+}
+
+impl LatencyHistogram {
+    /// 创建空直方图
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 记录一个延迟
+    pub fn record(&mut self, latency: Duration) {
+        self.record_micros(latency.as_micros().min(u64::MAX as u128) as u64);
+    }
+
+    /// 记录一个以微秒为单位的延迟
+    pub fn record_micros(&mut self, micros: u64) {
+        let index = bucket_index(micros);
+        if self.counts.len() <= index {
+            self.counts.resize(index + 1, 0);
+        }
+        self.counts[index] += 1;
+        if self.count == 0 || micros < self.min {
+            self.min = micros;
+        }
+        self.max = self.max.max(micros);
+        self.count += 1;
+        self.sum += micros as u128;
+    }
+
+    /// 合并另一个直方图
+    pub fn merge(&mut self, other: &LatencyHistogram) {
+        if other.count == 0 {
+            return;
+        }
+        if self.counts.len() < other.counts.len() {
+            self.counts.resize(other.counts.len(), 0);
+        }
+        for (count, other) in self.counts.iter_mut().zip(&other.counts) {
+            *count += other;
+        }
+        if self.count == 0 || other.min < self.min {
+            self.min = other.min;
+        }
+        self.max = self.max.max(other.max);
+        self.count += other.count;
+        self.sum += other.sum;
+    }
+
+    /// 样本数
+    pub fn count(&self) -> u64 {
+        self.count
+    }
+
+    /// 是否没有样本
+    pub fn is_empty(&self) -> bool {
+        self.count == 0
+    }
+
+    /// 最小值
+    pub fn min(&self) -> Duration {
+        Duration::from_micros(self.min)
+    }
+
+    /// 最大值
+    pub fn max(&self) -> Duration {
+        Duration::from_micros(self.max)
+    }
+
+    /// 平均值
+    pub fn mean(&self) -> Duration {
+        if self.count == 0 {
+            return Duration::ZERO;
+        }
+        Duration::from_micros((self.sum / self.count as u128) as u64)
+    }
+
+    /// 分位数，`quantile`取值0.0到1.0
+    pub fn value_at_quantile(&self, quantile: f64) -> Duration {
+        if self.count == 0 {
+            return Duration::ZERO;
+        }
+        let rank = ((quantile.clamp(0.0, 1.0) * self.count as f64).ceil() as u64).max(1);
+        let mut seen = 0;
+        for (index, count) in self.counts.iter().enumerate() {
+            seen += count;
+            if seen >= rank {
+                let value = bucket_upper_bound(index).clamp(self.min, self.max);
+                return Duration::from_micros(value);
+            }
+        }
+        self.max()
+    }
+
+    /// 清空样本
+    pub fn reset(&mut self) {
+        *self = Self::default();
+    }
+}
+
+/// 直方图摘要
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct LatencySummary {
+    /// 延迟类别
+    pub kind: LatencyKind,
+    /// 前置地址，未知时为空
+    pub front: String,
+    /// 合约代码，与合约无关的请求为空
+    pub instrument: String,
+    /// 样本数
+    pub count: u64,
+    /// 最小值
+    pub min: Duration,
+    /// 平均值
+    pub mean: Duration,
+    /// 中位数
+    pub p50: Duration,
+    /// 90分位
+    pub p90: Duration,
+    /// 99分位
+    pub p99: Duration,
+    /// 99.9分位
+    pub p999: Duration,
+    /// 最大值
+    pub max: Duration,
+}
+
+impl LatencySummary {
+    fn new(kind: LatencyKind, front: &str, instrument: &str, hist: &LatencyHistogram) -> Self {
+        Self {
+            kind,
+            front: front.to_string(),
+            instrument: instrument.to_string(),
+            count: hist.count(),
+            min: hist.min(),
+            mean: hist.mean(),
+            p50: hist.value_at_quantile(0.5),
+            p90: hist.value_at_quantile(0.9),
+            p99: hist.value_at_quantile(0.99),
+            p999: hist.value_at_quantile(0.999),
+            max: hist.max(),
+        }
+    }
+}
+
+// 报单的各阶段是否已记录
+#[derive(Debug)]
+struct PendingOrder {
+    sent_at: Instant,
+    instrument: String,
+    acked: bool,
+    accepted: bool,
+}
+
+// 类别 -> 前置 -> 合约 -> 直方图，按&str查找时不分配
+type HistogramTable = HashMap<LatencyKind, HashMap<String, HashMap<String, LatencyHistogram>>>;
+
+// 挂起的请求超过该数量时清理过期项
+const PRUNE_THRESHOLD: usize = 1024;
+
+/// 延迟记录器
+///
+/// 通常包装为`Arc<Mutex<LatencyRecorder>>`，同时挂接到交易和行情API
+#[derive(Debug)]
+pub struct LatencyRecorder {
+    pending_timeout: Duration,
+    exchange_offset: UtcOffset,
+    trader_front: String,
+    md_front: String,
+    session: Option<(i32, i32)>,
+    requests: HashMap<i32, Instant>,
+    orders: HashMap<String, PendingOrder>,
+    order_sys_ids: HashMap<String, String>,
+    histograms: HistogramTable,
+    clock_skewed_ticks: u64,
+}
+
+impl Default for LatencyRecorder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl LatencyRecorder {
+    /// 创建记录器，交易所时间按北京时间（UTC+8）解析
+    pub fn new() -> Self {
+        Self {
+            pending_timeout: Duration::from_secs(60),
+            exchange_offset: UtcOffset::from_hms(8, 0, 0).unwrap_or(UtcOffset::UTC),
+            trader_front: String::new(),
+            md_front: String::new(),
+            session: None,
+            requests: HashMap::new(),
+            orders: HashMap::new(),
+            order_sys_ids: HashMap::new(),
+            histograms: HashMap::new(),
+            clock_skewed_ticks: 0,
+        }
+    }
+
+    /// 设置挂起请求和报单的过期时间，超时未配对的记录会被丢弃，默认60秒
+    pub fn with_pending_timeout(mut self, timeout: Duration) -> Self {
+        self.pending_timeout = timeout;
+        self
+    }
+
+    /// 设置交易所时间相对UTC的偏移小时数，默认8
+    pub fn with_exchange_utc_offset(mut self, hours: i8) -> Self {
+        if let Ok(offset) = UtcOffset::from_hms(hours, 0, 0) {
+            self.exchange_offset = offset;
+        }
+        self
+    }
+
+    /// 设置交易前置地址，之后的交易延迟记入该前置
+    pub fn set_trader_front(&mut self, front: &str) {
+        front.clone_into(&mut self.trader_front);
+    }
+
+    /// 设置行情前置地址，之后的行情延迟记入该前置
+    pub fn set_md_front(&mut self, front: &str) {
+        front.clone_into(&mut self.md_front);
+    }
+
+    /// 记录登录得到的前置编号和会话编号，用于过滤其他会话的报单回报
+    pub fn on_login(&mut self, front_id: i32, session_id: i32) {
+        self.session = Some((front_id, session_id));
+        self.orders.clear();
+        self.order_sys_ids.clear();
+    }
+
+    /// 记录请求发出时间
+    pub fn on_request(&mut self, request_id: i32, sent_at: Instant) {
+        if self.requests.len() >= PRUNE_THRESHOLD {
+            self.prune(sent_at);
+        }
+        self.requests.insert(request_id, sent_at);
+    }
+
+    /// 请求发送失败，不会有响应
+    pub fn on_request_failed(&mut self, request_id: i32) {
+        self.requests.remove(&request_id);
+    }
+
+    /// 报单请求发出前登记，按报单引用跟踪后续回报
+    pub fn on_order_insert(&mut self, request_id: i32, req: &InputOrderField) {
+        let sent_at = self
+            .requests
+            .remove(&request_id)
+            .unwrap_or_else(Instant::now);
+        let order_ref = ascii_field(&req.order_ref);
+        if order_ref.is_empty() {
+            return;
+        }
+        if self.orders.len() >= PRUNE_THRESHOLD {
+            self.prune(sent_at);
+        }
+        self.orders.insert(
+            order_ref.to_string(),
+            PendingOrder {
+                sent_at,
+                instrument: ascii_field(&req.instrument_id).to_string(),
+                acked: false,
+                accepted: false,
+            },
+        );
+    }
+
+    /// 撤单成功时没有响应，不再等待
+    pub fn on_order_action(&mut self, request_id: i32) {
+        self.requests.remove(&request_id);
+    }
+
+    /// 记录请求响应，收到最后一条时计入往返延迟
+    pub fn on_response(&mut self, kind: LatencyKind, request_id: i32, is_last: bool, at: Instant) {
+        if !is_last {
+            return;
+        }
+        if let Some(sent_at) = self.requests.remove(&request_id) {
+            let front = self.trader_front.clone();
+            self.record(kind, &front, "", at.saturating_duration_since(sent_at));
+        }
+    }
+
+    /// CTP拒绝报单，计入报单确认延迟
+    pub fn on_rsp_order_insert(&mut self, input_order: &InputOrderField, at: Instant) {
+        let order_ref = ascii_field(&input_order.order_ref);
+        if let Some(order) = self.orders.remove(order_ref) {
+            let front = self.trader_front.clone();
+            self.record(
+                LatencyKind::OrderAck,
+                &front,
+                &order.instrument,
+                at.saturating_duration_since(order.sent_at),
+            );
+        }
+    }
+
+    /// 报单被拒绝或发送失败，不再等待
+    pub fn on_err_rtn_order_insert(&mut self, input_order: &InputOrderField) {
+        self.orders.remove(ascii_field(&input_order.order_ref));
+    }
+
+    /// 报单回报：首次回报计入报单确认，首次出现报单编号计入交易所接受
+    pub fn on_rtn_order(&mut self, order: &OrderField, at: Instant) {
+        if let Some(session) = self.session {
+            if session != (order.front_id, order.session_id) {
+                return;
+            }
+        }
+        let order_ref = ascii_field(&order.order_ref);
+        let Some(pending) = self.orders.get_mut(order_ref) else {
+            return;
+        };
+        let elapsed = at.saturating_duration_since(pending.sent_at);
+        let instrument = pending.instrument.clone();
+        let ack = !std::mem::replace(&mut pending.acked, true);
+        let order_sys_id = ascii_field(&order.order_sys_id);
+        let accept = !order_sys_id.is_empty() && !std::mem::replace(&mut pending.accepted, true);
+        if accept {
+            self.order_sys_ids.insert(
+                sys_id_key(&order.exchange_id, order_sys_id),
+                order_ref.to_string(),
+            );
+        }
+        // 已撤单的报单不会再有成交
+        if order.order_status == b'5' {
+            self.orders.remove(order_ref);
+        }
+
+        let front = self.trader_front.clone();
+        if ack {
+            self.record(LatencyKind::OrderAck, &front, &instrument, elapsed);
+        }
+        if accept {
+            self.record(LatencyKind::ExchangeAccept, &front, &instrument, elapsed);
+        }
+    }
+
+    /// 成交回报：首笔成交计入成交延迟
+    pub fn on_rtn_trade(&mut self, trade: &TradeField, at: Instant) {
+        let key = sys_id_key(&trade.exchange_id, ascii_field(&trade.order_sys_id));
+        let Some(order_ref) = self.order_sys_ids.remove(&key) else {
+            return;
+        };
+        if let Some(order) = self.orders.remove(&order_ref) {
+            let front = self.trader_front.clone();
+            self.record(
+                LatencyKind::Fill,
+                &front,
+                &order.instrument,
+                at.saturating_duration_since(order.sent_at),
+            );
+        }
+    }
+
+    /// 行情回调入口：计算交易所时间到本地接收时间的延迟
+    ///
+    /// 本地时钟慢于交易所时间时记为0，并计入[`clock_skewed_ticks`](Self::clock_skewed_ticks)
+    pub fn on_tick(&mut self, market_data: &DepthMarketDataField, received_at: SystemTime) {
+        let Some(exchange_ms) = exchange_millis(market_data) else {
+            return;
+        };
+        let local = OffsetDateTime::from(received_at).to_offset(self.exchange_offset);
+        let (hour, minute, second, milli) = local.to_hms_milli();
+        let local_ms =
+            ((hour as i64 * 60 + minute as i64) * 60 + second as i64) * 1000 + milli as i64;
+
+        // 跨越午夜时修正一天
+        const DAY_MS: i64 = 24 * 3600 * 1000;
+        let mut lag = local_ms - exchange_ms;
+        if lag < -DAY_MS / 2 {
+            lag += DAY_MS;
+        } else if lag > DAY_MS / 2 {
+            lag -= DAY_MS;
+        }
+        if lag < 0 {
+            self.clock_skewed_ticks += 1;
+            lag = 0;
+        }
+
+        let instrument = ascii_field(&market_data.instrument_id);
+        let front = std::mem::take(&mut self.md_front);
+        self.record(
+            LatencyKind::TickLag,
+            &front,
+            instrument,
+            Duration::from_millis(lag as u64),
+        );
+        self.md_front = front;
+    }
+
+    /// 记录一个延迟样本
+    pub fn record(&mut self, kind: LatencyKind, front: &str, instrument: &str, latency: Duration) {
+        let fronts = self.histograms.entry(kind).or_default();
+        if !fronts.contains_key(front) {
+            fronts.insert(front.to_string(), HashMap::new());
+        }
+        let instruments = fronts.get_mut(front).expect("front inserted above");
+        match instruments.get_mut(instrument) {
+            Some(hist) => hist.record(latency),
+            None => {
+                let mut hist = LatencyHistogram::new();
+                hist.record(latency);
+                instruments.insert(instrument.to_string(), hist);
+            }
+        }
+    }
+
+    /// 某类延迟在所有前置和合约上的合并直方图
+    pub fn histogram(&self, kind: LatencyKind) -> LatencyHistogram {
+        let mut merged = LatencyHistogram::new();
+        for hist in self
+            .histograms
+            .get(&kind)
+            .into_iter()
+            .flat_map(|fronts| fronts.values())
+            .flat_map(|instruments| instruments.values())
+        {
+            merged.merge(hist);
+        }
+        merged
+    }
+
+    /// 指定前置和合约的直方图
+    pub fn histogram_for(
+        &self,
+        kind: LatencyKind,
+        front: &str,
+        instrument: &str,
+    ) -> Option<&LatencyHistogram> {
+        self.histograms.get(&kind)?.get(front)?.get(instrument)
+    }
+
+    /// 所有直方图的摘要，按类别、前置、合约排序
+    pub fn summaries(&self) -> Vec<LatencySummary> {
+        let mut summaries: Vec<LatencySummary> = self
+            .histograms
+            .iter()
+            .flat_map(|(kind, fronts)| {
+                fronts.iter().flat_map(move |(front, instruments)| {
+                    instruments.iter().map(move |(instrument, hist)| {
+                        LatencySummary::new(*kind, front, instrument, hist)
+                    })
+                })
+            })
+            .collect();
+        summaries.sort_by(|a, b| {
+            (a.kind, &a.front, &a.instrument).cmp(&(b.kind, &b.front, &b.instrument))
+        });
+        summaries
+    }
+
+    /// 本地时钟慢于交易所时间的行情数
+    pub fn clock_skewed_ticks(&self) -> u64 {
+        self.clock_skewed_ticks
+    }
+
+    /// 清空所有样本，挂起的请求和报单保留
+    pub fn reset(&mut self) {
+        self.histograms.clear();
+        self.clock_skewed_ticks = 0;
+    }
+
+    fn prune(&mut self, now: Instant) {
+        let timeout = self.pending_timeout;
+        self.requests
+            .retain(|_, sent_at| now.saturating_duration_since(*sent_at) < timeout);
+        self.orders
+            .retain(|_, order| now.saturating_duration_since(order.sent_at) < timeout);
+        let orders = &self.orders;
+        self.order_sys_ids
+            .retain(|_, order_ref| orders.contains_key(order_ref));
+    }
+}
+
+// 合约、报单引用等ASCII字段，遇到NUL截止，不分配内存
+fn ascii_field(bytes: &[u8]) -> &str {
+    let end = bytes.iter().position(|&b| b == 0).unwrap_or(bytes.len());
+    std::str::from_utf8(&bytes[..end]).unwrap_or("").trim()
+}
+
+fn sys_id_key(exchange_id: &[u8], order_sys_id: &str) -> String {
+    format!("{}:{}", ascii_field(exchange_id), order_sys_id)
+}
+
+// 行情中的交易所时间，当日零点起的毫秒数
+fn exchange_millis(market_data: &DepthMarketDataField) -> Option<i64> {
+    let time = parse_time(ascii_field(&market_data.update_time))?;
+    let (hour, minute, second) = time.as_hms();
+    Some(
+        ((hour as i64 * 60 + minute as i64) * 60 + second as i64) * 1000
+            + market_data.update_millisec as i64,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn set(field: &mut [u8], value: &str) {
+        field[..value.len()].copy_from_slice(value.as_bytes());
+    }
+
+    #[test]
+    fn test_histogram_quantiles() {
+        let mut hist = LatencyHistogram::new();
+        assert_eq!(hist.value_at_quantile(0.99), Duration::ZERO);
+        for micros in 1..=10_000u64 {
+            hist.record_micros(micros);
+        }
+        assert_eq!(hist.count(), 10_000);
+        assert_eq!(hist.min(), Duration::from_micros(1));
+        assert_eq!(hist.max(), Duration::from_micros(10_000));
+        assert_eq!(hist.value_at_quantile(0.0), Duration::from_micros(1));
+        assert_eq!(hist.value_at_quantile(1.0), Duration::from_micros(10_000));
+
+        // 对数线性分桶的相对误差不超过1/32
+        for (quantile, expected) in [(0.5, 5_000.0), (0.9, 9_000.0), (0.99, 9_900.0)] {
+            let value = hist.value_at_quantile(quantile).as_micros() as f64;
+            assert!(
+                (value - expected).abs() / expected <= 1.0 / 32.0,
+                "{}",
+                value
+            );
+        }
+
+        let mut other = LatencyHistogram::new();
+        other.record(Duration::from_secs(3));
+        hist.merge(&other);
+        assert_eq!(hist.max(), Duration::from_secs(3));
+        assert_eq!(hist.count(), 10_001);
+
+        for value in [0, 63, 64, 65, 127, 128, 1 << 20, u64::MAX] {
+            let index = bucket_index(value);
+            assert!(bucket_upper_bound(index) >= value);
+            if index > 0 {
+                assert!(bucket_upper_bound(index - 1) < value);
+            }
+        }
+    }
+
+    #[test]
+    fn test_order_lifecycle() {
+        let mut recorder = LatencyRecorder::new();
+        recorder.set_trader_front("tcp://front-a");
+        recorder.on_login(1, 100);
+
+        let sent = Instant::now();
+        recorder.on_request(7, sent);
+        let mut req = InputOrderField::default();
+        set(&mut req.order_ref, "12");
+        set(&mut req.instrument_id, "rb2501");
+        recorder.on_order_insert(7, &req);
+
+        let mut order = OrderField {
+            front_id: 1,
+            session_id: 100,
+            ..Default::default()
+        };
+        set(&mut order.order_ref, "12");
+        set(&mut order.exchange_id, "SHFE");
+        recorder.on_rtn_order(&order, sent + Duration::from_millis(1));
+        set(&mut order.order_sys_id, "  884");
+        recorder.on_rtn_order(&order, sent + Duration::from_millis(3));
+        recorder.on_rtn_order(&order, sent + Duration::from_millis(4));
+
+        // 其他会话的同号报单不计入
+        order.session_id = 200;
+        recorder.on_rtn_order(&order, sent + Duration::from_millis(5));
+
+        let mut trade = TradeField::default();
+        set(&mut trade.exchange_id, "SHFE");
+        set(&mut trade.order_sys_id, "  884");
+        recorder.on_rtn_trade(&trade, sent + Duration::from_millis(9));
+        recorder.on_rtn_trade(&trade, sent + Duration::from_millis(12));
+
+        let check = |kind, millis| {
+            let hist = recorder
+                .histogram_for(kind, "tcp://front-a", "rb2501")
+                .unwrap();
+            assert_eq!(hist.count(), 1, "{}", kind);
+            assert_eq!(hist.max(), Duration::from_millis(millis), "{}", kind);
+        };
+        check(LatencyKind::OrderAck, 1);
+        check(LatencyKind::ExchangeAccept, 3);
+        check(LatencyKind::Fill, 9);
+        assert!(recorder.orders.is_empty());
+        assert!(recorder.order_sys_ids.is_empty());
+
+        // 发送失败的报单不留下待配对记录
+        recorder.on_request(8, sent);
+        set(&mut req.order_ref, "13");
+        recorder.on_order_insert(8, &req);
+        recorder.on_request_failed(8);
+        recorder.on_err_rtn_order_insert(&req);
+        assert!(recorder.requests.is_empty());
+        assert!(recorder.orders.is_empty());
+    }
+
+    #[test]
+    fn test_query_and_tick_lag() {
+        let mut recorder = LatencyRecorder::new();
+        let sent = Instant::now();
+        recorder.on_request(3, sent);
+        recorder.on_response(LatencyKind::QueryRoundTrip, 3, false, sent);
+        recorder.on_response(
+            LatencyKind::QueryRoundTrip,
+            3,
+            true,
+            sent + Duration::from_millis(20),
+        );
+        let query = recorder.histogram(LatencyKind::QueryRoundTrip);
+        assert_eq!(query.count(), 1);
+        assert_eq!(query.max(), Duration::from_millis(20));
+
+        recorder.set_md_front("tcp://md");
+        let mut tick = DepthMarketDataField::default();
+        set(&mut tick.instrument_id, "cu2502");
+        set(&mut tick.update_time, "23:59:59");
+        tick.update_millisec = 900;
+        // 北京时间次日00:00:00.150
+        let received = SystemTime::UNIX_EPOCH + Duration::from_millis(16 * 3600 * 1000 + 150);
+        recorder.on_tick(&tick, received);
+        let lag = recorder
+            .histogram_for(LatencyKind::TickLag, "tcp://md", "cu2502")
+            .unwrap();
+        assert_eq!(lag.max(), Duration::from_millis(250));
+
+        set(&mut tick.update_time, "00:00:01");
+        recorder.on_tick(&tick, received);
+        assert_eq!(recorder.clock_skewed_ticks(), 1);
+
+        let summaries = recorder.summaries();
+        assert_eq!(summaries.len(), 2);
+        assert_eq!(summaries[0].kind, LatencyKind::QueryRoundTrip);
+        assert_eq!(summaries[1].count, 2);
+    }
+}
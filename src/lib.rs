@@ -15,19 +15,55 @@
 //! - `ffi` - C++库的FFI绑定
 //! - `encoding` - 编码转换工具
 //! - `api` - 高级API接口
+//! - `accounts` - 多账户会话管理
+//! - `compliance` - 交易所合规计数
+//! - `credentials` - 登录凭证提供者
+//! - `models` - 拥有所有权的数据模型
+//! - `settlement` - 结算单拼接与解析
+//! - `transfer` - 银期转账结果与转帐历史
+//! - `relay` - 中继模式终端信息上报
+//! - `failover` - 多前置健康跟踪与故障切换
+//! - `flow` - 流文件目录隔离、加锁与按交易日清理
+//! - `journal` - 请求和回调的审计日志
+//! - `latency` - 请求、回报和行情延迟统计
+//! - `metrics` - 运行指标、Prometheus输出与健康检查
+//! - `order_ref` - 报单引用分配
+//! - `execution` - TWAP、VWAP、冰山和跟价执行算法
 //! - `error` - 错误处理
+//! - `error_code` - CTP错误代码目录
 //! - `types` - 类型定义
+//! - `fixed_str` - 定长GB18030字符串
+//! - `serde_support` - serde序列化支持（需启用`serde` feature）
 
+pub mod accounts;
 pub mod api;
+pub mod compliance;
 pub mod config;
+pub mod credentials;
 pub mod encoding;
 pub mod error;
+pub mod error_code;
+pub mod execution;
+pub mod failover;
 pub mod ffi;
+pub mod fixed_str;
+pub mod flow;
+pub mod journal;
+pub mod latency;
+pub mod metrics;
+pub mod models;
+pub mod order_ref;
+pub mod relay;
+#[cfg(feature = "serde")]
+pub mod serde_support;
+pub mod settlement;
+pub mod transfer;
 pub mod types;
 // 重新导出主要类型和函数
 pub use api::{AsyncMdApi, MdApi, TraderApi};
-pub use config::CtpConfig;
-pub use error::{CtpError, CtpResult};
+pub use config::{CtpConfig, SecretSource};
+pub use credentials::{CredentialProvider, Credentials};
+pub use error::{CtpError, CtpErrorCode, CtpResult, ErrorCategory};
 pub use types::*;
 /// 库版本信息
 pub const VERSION: &str = env!("CARGO_PKG_VERSION");
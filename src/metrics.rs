@@ -0,0 +1,881 @@
+//! 运行指标与健康检查
+//!
+//! [`MetricsRegistry`]按会话名称管理[`SessionMetrics`]，交给
+//! [`AsyncTraderApi::with_metrics`](crate::api::AsyncTraderApi::with_metrics)或
+//! [`AsyncMdApi::with_metrics`](crate::api::AsyncMdApi::with_metrics)后，回调事件会自动更新：
+//!
+//! - 连接和登录状态、心跳超时次数、按原因统计的断线次数
+//! - 事件队列中尚未取走的事件数
+//! - 各合约行情数和每秒行情数，最后一笔行情距今时间
+//! - 报单、撤单总数和每分钟次数，按`error_id`统计的拒绝次数
+//!
+//! [`MetricsRegistry::render_prometheus`]输出Prometheus文本格式，
+//! [`MetricsRegistry::health`]在前置断开或行情停止更新时报告降级。启用`metrics-server`
+//! feature后可用[`serve`]在本地端口提供`/metrics`和`/health`。
+
+use crate::api::async_md_api::AsyncMdEvent;
+use crate::api::async_trader_api::AsyncTraderEvent;
+use crate::api::EventSink;
+use crate::types::RspInfoField;
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt::{self, Write as _};
+use std::sync::atomic::{AtomicBool, AtomicI64, AtomicU64, Ordering};
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::time::{Duration, Instant};
+
+fn lock<T>(mutex: &Mutex<T>) -> MutexGuard<'_, T> {
+    mutex.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+/// 会话类型
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "snake_case")
+)]
+pub enum SessionKind {
+    /// 交易会话
+    Trader,
+    /// 行情会话
+    MarketData,
+}
+
+impl SessionKind {
+    /// 类型名称，用作指标标签
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SessionKind::Trader => "trader",
+            SessionKind::MarketData => "md",
+        }
+    }
+}
+
+/// 按固定窗口统计速率，报告上一个完整窗口内的次数
+#[derive(Debug)]
+struct RateWindow {
+    window: Duration,
+    started: Instant,
+    current: u64,
+    previous: u64,
+}
+
+impl RateWindow {
+    fn new(window: Duration, now: Instant) -> Self {
+        Self {
+            window,
+            started: now,
+            current: 0,
+            previous: 0,
+        }
+    }
+
+    fn roll(&mut self, now: Instant) {
+        let elapsed = now.saturating_duration_since(self.started);
+        if elapsed >= self.window * 2 {
+            self.previous = 0;
+            self.current = 0;
+            self.started = now;
+        } else if elapsed >= self.window {
+            self.previous = self.current;
+            self.current = 0;
+            self.started += self.window;
+        }
+    }
+
+    fn add(&mut self, now: Instant) {
+        self.roll(now);
+        self.current += 1;
+    }
+
+    fn rate(&mut self, now: Instant) -> u64 {
+        self.roll(now);
+        self.previous
+    }
+}
+
+#[derive(Debug)]
+struct InstrumentTicks {
+    total: u64,
+    per_second: RateWindow,
+}
+
+/// 单个会话的指标
+#[derive(Debug)]
+pub struct SessionMetrics {
+    name: String,
+    kind: SessionKind,
+    connected: AtomicBool,
+    logged_in: AtomicBool,
+    heartbeat_warnings: AtomicU64,
+    events_total: AtomicU64,
+    queue_depth: AtomicI64,
+    orders_total: AtomicU64,
+    cancels_total: AtomicU64,
+    disconnects: Mutex<BTreeMap<i32, u64>>,
+    rejects: Mutex<BTreeMap<i32, u64>>,
+    order_rate: Mutex<RateWindow>,
+    cancel_rate: Mutex<RateWindow>,
+    ticks: Mutex<BTreeMap<String, InstrumentTicks>>,
+    subscribed: Mutex<BTreeSet<String>>,
+    // 最后一笔行情或登录时间，用于判断行情是否停止更新
+    last_tick: Mutex<Option<Instant>>,
+    logged_in_at: Mutex<Option<Instant>>,
+}
+
+impl SessionMetrics {
+    fn new(name: &str, kind: SessionKind) -> Self {
+        let now = Instant::now();
+        Self {
+            name: name.to_string(),
+            kind,
+            connected: AtomicBool::new(false),
+            logged_in: AtomicBool::new(false),
+            heartbeat_warnings: AtomicU64::new(0),
+            events_total: AtomicU64::new(0),
+            queue_depth: AtomicI64::new(0),
+            orders_total: AtomicU64::new(0),
+            cancels_total: AtomicU64::new(0),
+            disconnects: Mutex::new(BTreeMap::new()),
+            rejects: Mutex::new(BTreeMap::new()),
+            order_rate: Mutex::new(RateWindow::new(Duration::from_secs(60), now)),
+            cancel_rate: Mutex::new(RateWindow::new(Duration::from_secs(60), now)),
+            ticks: Mutex::new(BTreeMap::new()),
+            subscribed: Mutex::new(BTreeSet::new()),
+            last_tick: Mutex::new(None),
+            logged_in_at: Mutex::new(None),
+        }
+    }
+
+    /// 会话名称
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// 会话类型
+    pub fn kind(&self) -> SessionKind {
+        self.kind
+    }
+
+    /// 是否已连接
+    pub fn is_connected(&self) -> bool {
+        self.connected.load(Ordering::Relaxed)
+    }
+
+    /// 是否已登录
+    pub fn is_logged_in(&self) -> bool {
+        self.logged_in.load(Ordering::Relaxed)
+    }
+
+    /// 连接成功
+    pub fn on_connected(&self) {
+        self.connected.store(true, Ordering::Relaxed);
+    }
+
+    /// 连接断开
+    pub fn on_disconnected(&self, reason: i32) {
+        self.connected.store(false, Ordering::Relaxed);
+        self.logged_in.store(false, Ordering::Relaxed);
+        *lock(&self.disconnects).entry(reason).or_default() += 1;
+    }
+
+    /// 心跳超时警告
+    pub fn on_heartbeat_warning(&self) {
+        self.heartbeat_warnings.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// 登录成功
+    pub fn on_login(&self) {
+        self.logged_in.store(true, Ordering::Relaxed);
+        *lock(&self.logged_in_at) = Some(Instant::now());
+    }
+
+    /// 登出
+    pub fn on_logout(&self) {
+        self.logged_in.store(false, Ordering::Relaxed);
+    }
+
+    /// 事件进入队列
+    pub fn on_event_queued(&self) {
+        self.events_total.fetch_add(1, Ordering::Relaxed);
+        self.queue_depth.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// 事件被取走
+    pub fn on_event_dequeued(&self) {
+        self.queue_depth.fetch_sub(1, Ordering::Relaxed);
+    }
+
+    /// 队列中尚未取走的事件数
+    pub fn queue_depth(&self) -> i64 {
+        self.queue_depth.load(Ordering::Relaxed).max(0)
+    }
+
+    /// 收到行情
+    pub fn on_tick(&self, instrument: &str) {
+        let now = Instant::now();
+        {
+            let mut ticks = lock(&self.ticks);
+            match ticks.get_mut(instrument) {
+                Some(stats) => {
+                    stats.total += 1;
+                    stats.per_second.add(now);
+                }
+                None => {
+                    let mut per_second = RateWindow::new(Duration::from_secs(1), now);
+                    per_second.add(now);
+                    ticks.insert(
+                        instrument.to_string(),
+                        InstrumentTicks {
+                            total: 1,
+                            per_second,
+                        },
+                    );
+                }
+            }
+        }
+        *lock(&self.last_tick) = Some(now);
+    }
+
+    /// 订阅成功
+    pub fn on_subscribed(&self, instrument: &str) {
+        lock(&self.subscribed).insert(instrument.to_string());
+    }
+
+    /// 取消订阅成功
+    pub fn on_unsubscribed(&self, instrument: &str) {
+        lock(&self.subscribed).remove(instrument);
+    }
+
+    /// 报单请求已发出
+    pub fn on_order_insert(&self) {
+        self.orders_total.fetch_add(1, Ordering::Relaxed);
+        lock(&self.order_rate).add(Instant::now());
+    }
+
+    /// 撤单请求已发出
+    pub fn on_order_action(&self) {
+        self.cancels_total.fetch_add(1, Ordering::Relaxed);
+        lock(&self.cancel_rate).add(Instant::now());
+    }
+
+    /// 请求被拒绝
+    pub fn on_reject(&self, error_id: i32) {
+        *lock(&self.rejects).entry(error_id).or_default() += 1;
+    }
+
+    /// 最后一笔行情距今时间，登录后还没有行情时从登录时刻算起
+    pub fn tick_age(&self) -> Option<Duration> {
+        let last = (*lock(&self.last_tick)).max(*lock(&self.logged_in_at))?;
+        Some(last.elapsed())
+    }
+
+    fn observe_rsp_info(&self, rsp_info: Option<&RspInfoField>) {
+        if let Some(rsp) = rsp_info {
+            if !rsp.is_success() {
+                self.on_reject(rsp.error_id);
+            }
+        }
+    }
+
+    /// 根据交易事件更新指标
+    pub fn observe_trader_event(&self, event: &AsyncTraderEvent) {
+        match event {
+            AsyncTraderEvent::Connected => self.on_connected(),
+            AsyncTraderEvent::Disconnected(reason) => self.on_disconnected(*reason),
+            AsyncTraderEvent::HeartBeatWarning(_) => self.on_heartbeat_warning(),
+            AsyncTraderEvent::LoginResponse {
+                user_login: Some(_),
+                rsp_info,
+                ..
+            } if rsp_info.as_ref().is_none_or(|r| r.is_success()) => self.on_login(),
+            AsyncTraderEvent::LogoutResponse { .. } => self.on_logout(),
+            _ => {}
+        }
+        if let Some((_, _, rsp_info)) = event.response_meta() {
+            self.observe_rsp_info(rsp_info);
+        }
+    }
+
+    /// 根据行情事件更新指标
+    pub fn observe_md_event(&self, event: &AsyncMdEvent) {
+        match event {
+            AsyncMdEvent::Connected => self.on_connected(),
+            AsyncMdEvent::Disconnected(reason) => self.on_disconnected(*reason),
+            AsyncMdEvent::HeartBeatWarning(_) => self.on_heartbeat_warning(),
+            AsyncMdEvent::LoginResponse {
+                user_login: Some(_),
+                rsp_info,
+                ..
+            } if rsp_info.as_ref().is_none_or(|r| r.is_success()) => self.on_login(),
+            AsyncMdEvent::LogoutResponse { .. } => self.on_logout(),
+            AsyncMdEvent::LoginResponse { rsp_info, .. }
+            | AsyncMdEvent::ErrorResponse { rsp_info, .. } => {
+                self.observe_rsp_info(rsp_info.as_ref())
+            }
+            AsyncMdEvent::SubMarketDataResponse {
+                specific_instrument,
+                rsp_info,
+                ..
+            } => match (specific_instrument, rsp_info) {
+                (_, Some(rsp)) if !rsp.is_success() => self.on_reject(rsp.error_id),
+                (Some(instrument), _) => {
+                    if let Ok(id) = instrument.get_instrument_id() {
+                        self.on_subscribed(&id);
+                    }
+                }
+                _ => {}
+            },
+            AsyncMdEvent::UnsubMarketDataResponse {
+                specific_instrument: Some(instrument),
+                ..
+            } => {
+                if let Ok(id) = instrument.get_instrument_id() {
+                    self.on_unsubscribed(&id);
+                }
+            }
+            AsyncMdEvent::DepthMarketData(market_data) => {
+                if let Ok(id) = market_data.get_instrument_id() {
+                    self.on_tick(&id);
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+/// 回调事件在进入队列前更新指标
+pub(crate) struct ObservedSink<S> {
+    inner: S,
+    metrics: Arc<SessionMetrics>,
+}
+
+impl<S> ObservedSink<S> {
+    pub(crate) fn new(inner: S, metrics: Arc<SessionMetrics>) -> Self {
+        Self { inner, metrics }
+    }
+}
+
+impl<S: EventSink<AsyncTraderEvent>> EventSink<AsyncTraderEvent> for ObservedSink<S> {
+    fn send(&self, event: AsyncTraderEvent) -> bool {
+        self.metrics.observe_trader_event(&event);
+        let queued = self.inner.send(event);
+        if queued {
+            self.metrics.on_event_queued();
+        }
+        queued
+    }
+}
+
+impl<S: EventSink<AsyncMdEvent>> EventSink<AsyncMdEvent> for ObservedSink<S> {
+    fn send(&self, event: AsyncMdEvent) -> bool {
+        self.metrics.observe_md_event(&event);
+        let queued = self.inner.send(event);
+        if queued {
+            self.metrics.on_event_queued();
+        }
+        queued
+    }
+}
+
+/// 健康检查配置
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HealthConfig {
+    /// 已订阅行情的会话超过该秒数没有行情时视为停止更新
+    pub stale_tick_secs: u64,
+}
+
+impl Default for HealthConfig {
+    fn default() -> Self {
+        Self {
+            stale_tick_secs: 30,
+        }
+    }
+}
+
+impl HealthConfig {
+    /// 设置行情停止更新的判定秒数
+    pub fn with_stale_tick_secs(mut self, secs: u64) -> Self {
+        self.stale_tick_secs = secs;
+        self
+    }
+}
+
+/// 健康状态
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "snake_case")
+)]
+pub enum HealthStatus {
+    /// 正常
+    Healthy,
+    /// 部分前置断开或行情停止更新
+    Degraded,
+}
+
+/// 健康检查结果
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct HealthReport {
+    /// 健康状态
+    pub status: HealthStatus,
+    /// 降级原因
+    pub problems: Vec<String>,
+}
+
+impl HealthReport {
+    /// 是否正常
+    pub fn is_healthy(&self) -> bool {
+        self.status == HealthStatus::Healthy
+    }
+}
+
+impl fmt::Display for HealthReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.status {
+            HealthStatus::Healthy => write!(f, "healthy"),
+            HealthStatus::Degraded => {
+                writeln!(f, "degraded")?;
+                for problem in &self.problems {
+                    writeln!(f, "- {}", problem)?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+/// 指标注册表
+#[derive(Debug, Default)]
+pub struct MetricsRegistry {
+    sessions: Mutex<BTreeMap<String, Arc<SessionMetrics>>>,
+}
+
+impl MetricsRegistry {
+    /// 创建空注册表
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 获取或创建会话指标
+    pub fn session(&self, name: &str, kind: SessionKind) -> Arc<SessionMetrics> {
+        lock(&self.sessions)
+            .entry(name.to_string())
+            .or_insert_with(|| Arc::new(SessionMetrics::new(name, kind)))
+            .clone()
+    }
+
+    /// 全部会话指标
+    pub fn sessions(&self) -> Vec<Arc<SessionMetrics>> {
+        lock(&self.sessions).values().cloned().collect()
+    }
+
+    /// 健康检查
+    pub fn health(&self, config: &HealthConfig) -> HealthReport {
+        let stale_after = Duration::from_secs(config.stale_tick_secs);
+        let mut problems = Vec::new();
+        for session in self.sessions() {
+            if !session.is_connected() {
+                problems.push(format!("{} 前置未连接", session.name));
+                continue;
+            }
+            if session.kind != SessionKind::MarketData
+                || !session.is_logged_in()
+                || lock(&session.subscribed).is_empty()
+            {
+                continue;
+            }
+            if let Some(age) = session.tick_age() {
+                if age > stale_after {
+                    problems.push(format!(
+                        "{} 行情已 {} 秒未更新",
+                        session.name,
+                        age.as_secs()
+                    ));
+                }
+            }
+        }
+        HealthReport {
+            status: if problems.is_empty() {
+                HealthStatus::Healthy
+            } else {
+                HealthStatus::Degraded
+            },
+            problems,
+        }
+    }
+
+    /// 输出Prometheus文本格式
+    pub fn render_prometheus(&self) -> String {
+        let sessions = self.sessions();
+        let now = Instant::now();
+        let mut out = String::new();
+
+        let mut family = |name: &str, kind: &str, help: &str, samples: Vec<(String, String)>| {
+            let _ = writeln!(out, "# HELP {} {}", name, help);
+            let _ = writeln!(out, "# TYPE {} {}", name, kind);
+            for (labels, value) in samples {
+                let _ = writeln!(out, "{}{{{}}} {}", name, labels, value);
+            }
+        };
+        let base = |s: &SessionMetrics| {
+            format!(
+                "session=\"{}\",kind=\"{}\"",
+                escape_label(&s.name),
+                s.kind.as_str()
+            )
+        };
+        let per_session = |value: &dyn Fn(&SessionMetrics) -> String| {
+            sessions
+                .iter()
+                .map(|s| (base(s), value(s)))
+                .collect::<Vec<_>>()
+        };
+
+        family(
+            "ctp_connected",
+            "gauge",
+            "Whether the session is connected to a front",
+            per_session(&|s| (s.is_connected() as u8).to_string()),
+        );
+        family(
+            "ctp_logged_in",
+            "gauge",
+            "Whether the session is logged in",
+            per_session(&|s| (s.is_logged_in() as u8).to_string()),
+        );
+        family(
+            "ctp_heartbeat_warnings_total",
+            "counter",
+            "Heartbeat timeout warnings",
+            per_session(&|s| s.heartbeat_warnings.load(Ordering::Relaxed).to_string()),
+        );
+        family(
+            "ctp_disconnects_total",
+            "counter",
+            "Disconnects by reason code",
+            sessions
+                .iter()
+                .flat_map(|s| {
+                    lock(&s.disconnects)
+                        .iter()
+                        .map(|(reason, count)| {
+                            (
+                                format!("{},reason=\"{}\"", base(s), reason),
+                                count.to_string(),
+                            )
+                        })
+                        .collect::<Vec<_>>()
+                })
+                .collect(),
+        );
+        family(
+            "ctp_events_total",
+            "counter",
+            "Events delivered to the event queue",
+            per_session(&|s| s.events_total.load(Ordering::Relaxed).to_string()),
+        );
+        family(
+            "ctp_event_queue_depth",
+            "gauge",
+            "Events waiting in the event queue",
+            per_session(&|s| s.queue_depth().to_string()),
+        );
+        let mut ticks_total = Vec::new();
+        let mut ticks_rate = Vec::new();
+        for s in &sessions {
+            for (instrument, stats) in lock(&s.ticks).iter_mut() {
+                let labels = format!("{},instrument=\"{}\"", base(s), escape_label(instrument));
+                ticks_total.push((labels.clone(), stats.total.to_string()));
+                ticks_rate.push((labels, stats.per_second.rate(now).to_string()));
+            }
+        }
+        family(
+            "ctp_ticks_total",
+            "counter",
+            "Market data ticks received",
+            ticks_total,
+        );
+        family(
+            "ctp_ticks_per_second",
+            "gauge",
+            "Market data ticks received in the last full second",
+            ticks_rate,
+        );
+        family(
+            "ctp_last_tick_age_seconds",
+            "gauge",
+            "Seconds since the last tick, or since login when no tick arrived",
+            sessions
+                .iter()
+                .filter_map(|s| {
+                    let age = s.tick_age()?;
+                    Some((base(s), format!("{:.3}", age.as_secs_f64())))
+                })
+                .collect(),
+        );
+        family(
+            "ctp_orders_total",
+            "counter",
+            "Order insert requests sent",
+            per_session(&|s| s.orders_total.load(Ordering::Relaxed).to_string()),
+        );
+        family(
+            "ctp_cancels_total",
+            "counter",
+            "Order action requests sent",
+            per_session(&|s| s.cancels_total.load(Ordering::Relaxed).to_string()),
+        );
+        family(
+            "ctp_orders_per_minute",
+            "gauge",
+            "Order insert requests in the last full minute",
+            per_session(&|s| lock(&s.order_rate).rate(now).to_string()),
+        );
+        family(
+            "ctp_cancels_per_minute",
+            "gauge",
+            "Order action requests in the last full minute",
+            per_session(&|s| lock(&s.cancel_rate).rate(now).to_string()),
+        );
+        family(
+            "ctp_rejects_total",
+            "counter",
+            "Rejected requests by CTP error_id",
+            sessions
+                .iter()
+                .flat_map(|s| {
+                    lock(&s.rejects)
+                        .iter()
+                        .map(|(error_id, count)| {
+                            (
+                                format!("{},error_id=\"{}\"", base(s), error_id),
+                                count.to_string(),
+                            )
+                        })
+                        .collect::<Vec<_>>()
+                })
+                .collect(),
+        );
+        out
+    }
+}
+
+fn escape_label(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for c in value.chars() {
+        match c {
+            '\\' => escaped.push_str("\\\\"),
+            '"' => escaped.push_str("\\\""),
+            '\n' => escaped.push_str("\\n"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// 在`addr`上提供`/metrics`（Prometheus文本格式）和`/health`（降级时返回503）
+///
+/// 只处理简单的GET请求，应监听在本地地址上
+#[cfg(feature = "metrics-server")]
+pub async fn serve(
+    registry: Arc<MetricsRegistry>,
+    addr: &str,
+    health: HealthConfig,
+) -> crate::error::CtpResult<tokio::task::JoinHandle<()>> {
+    use crate::error::CtpError;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::TcpListener;
+    use tracing::warn;
+
+    let listener = TcpListener::bind(addr)
+        .await
+        .map_err(|e| CtpError::ConnectionError(format!("监听指标端口 {} 失败: {}", addr, e)))?;
+
+    Ok(tokio::spawn(async move {
+        loop {
+            let (mut stream, _) = match listener.accept().await {
+                Ok(conn) => conn,
+                Err(e) => {
+                    warn!("接受指标连接失败: {}", e);
+                    continue;
+                }
+            };
+            let registry = registry.clone();
+            let health = health.clone();
+            tokio::spawn(async move {
+                let mut buf = [0u8; 1024];
+                let n = match stream.read(&mut buf).await {
+                    Ok(n) => n,
+                    Err(_) => return,
+                };
+                let request = String::from_utf8_lossy(&buf[..n]);
+                let path = request.split_whitespace().nth(1).unwrap_or("/");
+                let (status, content_type, body) = match path {
+                    "/metrics" => (
+                        "200 OK",
+                        "text/plain; version=0.0.4; charset=utf-8",
+                        registry.render_prometheus(),
+                    ),
+                    "/health" => {
+                        let report = registry.health(&health);
+                        let status = if report.is_healthy() {
+                            "200 OK"
+                        } else {
+                            "503 Service Unavailable"
+                        };
+                        (status, "text/plain; charset=utf-8", report.to_string())
+                    }
+                    _ => (
+                        "404 Not Found",
+                        "text/plain; charset=utf-8",
+                        "not found\n".to_string(),
+                    ),
+                };
+                let response = format!(
+                    "HTTP/1.1 {}\r\nContent-Type: {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                    status,
+                    content_type,
+                    body.len(),
+                    body
+                );
+                let _ = stream.write_all(response.as_bytes()).await;
+                let _ = stream.shutdown().await;
+            });
+        }
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::md_api::{DepthMarketDataField, SpecificInstrumentField};
+
+    fn rsp_error(error_id: i32) -> Option<RspInfoField> {
+        Some(RspInfoField {
+            error_id,
+            ..Default::default()
+        })
+    }
+
+    #[test]
+    fn test_rate_window() {
+        let start = Instant::now();
+        let mut rate = RateWindow::new(Duration::from_secs(1), start);
+        for _ in 0..5 {
+            rate.add(start);
+        }
+        assert_eq!(rate.rate(start), 0);
+        assert_eq!(rate.rate(start + Duration::from_millis(1500)), 5);
+        assert_eq!(rate.rate(start + Duration::from_secs(3)), 0);
+    }
+
+    #[test]
+    fn test_session_metrics_and_prometheus() {
+        let registry = MetricsRegistry::new();
+        let trader = registry.session("main", SessionKind::Trader);
+        trader.observe_trader_event(&AsyncTraderEvent::Connected);
+        trader.observe_trader_event(&AsyncTraderEvent::HeartBeatWarning(30));
+        trader.observe_trader_event(&AsyncTraderEvent::OrderInsertResponse {
+            input_order: None,
+            rsp_info: rsp_error(22),
+            request_id: 3,
+            is_last: true,
+        });
+        trader.on_order_insert();
+        trader.on_order_action();
+        trader.observe_trader_event(&AsyncTraderEvent::Disconnected(4097));
+        assert!(!trader.is_connected());
+
+        let md = registry.session("md", SessionKind::MarketData);
+        md.observe_md_event(&AsyncMdEvent::Connected);
+        let mut tick = DepthMarketDataField::default();
+        tick.instrument_id[..6].copy_from_slice(b"rb2501");
+        md.observe_md_event(&AsyncMdEvent::DepthMarketData(tick.clone()));
+        md.observe_md_event(&AsyncMdEvent::DepthMarketData(tick));
+
+        let text = registry.render_prometheus();
+        assert!(text.contains("# TYPE ctp_connected gauge"));
+        assert!(text.contains("ctp_connected{session=\"md\",kind=\"md\"} 1"));
+        assert!(text.contains("ctp_connected{session=\"main\",kind=\"trader\"} 0"));
+        assert!(text
+            .contains("ctp_disconnects_total{session=\"main\",kind=\"trader\",reason=\"4097\"} 1"));
+        assert!(
+            text.contains("ctp_rejects_total{session=\"main\",kind=\"trader\",error_id=\"22\"} 1")
+        );
+        assert!(
+            text.contains("ctp_ticks_total{session=\"md\",kind=\"md\",instrument=\"rb2501\"} 2")
+        );
+        assert!(text.contains("ctp_orders_total{session=\"main\",kind=\"trader\"} 1"));
+        assert!(text.contains("ctp_heartbeat_warnings_total{session=\"main\",kind=\"trader\"} 1"));
+        assert_eq!(escape_label("a\"b\\"), "a\\\"b\\\\");
+    }
+
+    #[test]
+    fn test_health() {
+        let registry = MetricsRegistry::new();
+        let config = HealthConfig::default().with_stale_tick_secs(0);
+        let md = registry.session("md", SessionKind::MarketData);
+
+        let report = registry.health(&config);
+        assert_eq!(report.status, HealthStatus::Degraded);
+        assert!(report.problems[0].contains("前置未连接"));
+
+        md.on_connected();
+        md.on_login();
+        assert!(registry.health(&config).is_healthy());
+
+        let mut instrument = SpecificInstrumentField::default();
+        instrument.instrument_id[..6].copy_from_slice(b"rb2501");
+        md.observe_md_event(&AsyncMdEvent::SubMarketDataResponse {
+            specific_instrument: Some(instrument),
+            rsp_info: None,
+            request_id: 0,
+            is_last: true,
+        });
+        std::thread::sleep(Duration::from_millis(5));
+        let report = registry.health(&config);
+        assert_eq!(report.status, HealthStatus::Degraded);
+        assert!(report.problems[0].contains("未更新"));
+
+        let config = HealthConfig::default();
+        md.on_tick("rb2501");
+        assert!(registry.health(&config).is_healthy());
+    }
+
+    #[cfg(feature = "metrics-server")]
+    #[tokio::test]
+    async fn test_serve() {
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+        let registry = Arc::new(MetricsRegistry::new());
+        registry.session("main", SessionKind::Trader).on_connected();
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap().to_string();
+        drop(listener);
+        let server = serve(registry, &addr, HealthConfig::default())
+            .await
+            .unwrap();
+
+        for (path, expected) in [
+            (
+                "/metrics",
+                "ctp_connected{session=\"main\",kind=\"trader\"} 1",
+            ),
+            ("/health", "healthy"),
+        ] {
+            let mut stream = tokio::net::TcpStream::connect(&addr).await.unwrap();
+            stream
+                .write_all(format!("GET {} HTTP/1.1\r\n\r\n", path).as_bytes())
+                .await
+                .unwrap();
+            let mut response = String::new();
+            stream.read_to_string(&mut response).await.unwrap();
+            assert!(response.starts_with("HTTP/1.1 200 OK"), "{}", response);
+            assert!(response.contains(expected), "{}", response);
+        }
+        server.abort();
+    }
+}
@@ -0,0 +1,1858 @@
+//! 拥有所有权的Rust数据模型
+//!
+//! CTP回调中的字段结构体是`#[repr(C)]`布局，字符串为GB18030编码的定长字节数组，
+//! 标志位为单字节字符，价格用`f64::MAX`表示无效值。本模块为常用结构体提供对应的
+//! 拥有所有权的模型：字符串为`String`，标志位为类型化枚举，无效价格为`None`，
+//! 日期和时间解析为`time::Date`/`time::Time`。
+//!
+//! 目前提供模型的结构体：`FensUserInfoField`、`FrontInfoField`、`InputOrderField`、
+//! `OrderField`、`TradeField`、`InvestorPositionField`、`TradingAccountField`、
+//! `InstrumentField`、`DepthMarketDataField`、`RspUserLoginField`和`TransferSerialField`，
+//! 其余结构体仍直接使用原始字段。模型映射原始结构体的全部字段，唯一的例外是
+//! `DepthMarketData`不包含保留的无效字段`reserve1`/`reserve2`；原始结构体新增字段而
+//! 模型未映射时编译失败。
+//!
+//! 每个模型都实现了`From<&XxxField>`和`TryFrom<&Xxx> for XxxField`，
+//! 应用代码无需直接处理字节数组：
+//!
+//! ```rust
+//! use ctp_rust::api::trader_api::OrderField;
+//! use ctp_rust::models::{Direction, Order};
+//!
+//! let mut field = OrderField::default();
+//! field.direction = b'0';
+//! let order = Order::from(&field);
+//! assert_eq!(order.direction, Direction::Buy);
+//! let back = OrderField::try_from(&order).unwrap();
+//! assert_eq!(back.direction, b'0');
+//! ```
+
+use crate::api::md_api::DepthMarketDataField;
+use crate::api::trader_api::{
+    InputOrderField, InstrumentField, InvestorPositionField, OrderField, TradeField,
+    TradingAccountField,
+};
+use crate::encoding::GbkConverter;
+use crate::error::{CtpError, CtpResult};
+use crate::transfer::TransferDirection;
+use crate::types::{
+    FensUserInfoField, FixedStr, FrontInfoField, RspUserLoginField, TransferSerialField,
+};
+use time::{Date, Month, PrimitiveDateTime, Time};
+
+// 字段结构体与模型字段之间的转换
+trait RawField<R>: Sized {
+    fn from_raw(raw: &R) -> Self;
+    fn to_raw(&self, name: &str) -> CtpResult<R>;
+}
+
+impl RawField<i32> for i32 {
+    fn from_raw(raw: &i32) -> Self {
+        *raw
+    }
+
+    fn to_raw(&self, _name: &str) -> CtpResult<i32> {
+        Ok(*self)
+    }
+}
+
+impl RawField<f64> for f64 {
+    fn from_raw(raw: &f64) -> Self {
+        *raw
+    }
+
+    fn to_raw(&self, _name: &str) -> CtpResult<f64> {
+        Ok(*self)
+    }
+}
+
+// CTP布尔值为整数，非0即真
+impl RawField<i32> for bool {
+    fn from_raw(raw: &i32) -> Self {
+        *raw != 0
+    }
+
+    fn to_raw(&self, _name: &str) -> CtpResult<i32> {
+        Ok(i32::from(*self))
+    }
+}
+
+// 无效价格（f64::MAX或NaN）映射为None
+impl RawField<f64> for Option<f64> {
+    fn from_raw(raw: &f64) -> Self {
+        price_or_none(*raw)
+    }
+
+    fn to_raw(&self, _name: &str) -> CtpResult<f64> {
+        Ok(self.unwrap_or(f64::MAX))
+    }
+}
+
+// 未归类的字符标志，0映射为None
+impl RawField<u8> for Option<char> {
+    fn from_raw(raw: &u8) -> Self {
+        (*raw != 0).then_some(*raw as char)
+    }
+
+    fn to_raw(&self, name: &str) -> CtpResult<u8> {
+        match self {
+            None => Ok(0),
+            Some(c) if c.is_ascii() => Ok(*c as u8),
+            Some(c) => Err(CtpError::InvalidParameterError(format!(
+                "字段 {} 的取值 {:?} 不是ASCII字符",
+                name, c
+            ))),
+        }
+    }
+}
+
+impl<const N: usize> RawField<[u8; N]> for String {
+    fn from_raw(raw: &[u8; N]) -> Self {
+        decode_text(raw)
+    }
+
+    fn to_raw(&self, name: &str) -> CtpResult<[u8; N]> {
+        encode_text(self, name)
+    }
+}
+
+impl<const N: usize> RawField<FixedStr<N>> for String {
+    fn from_raw(raw: &FixedStr<N>) -> Self {
+        raw.to_string()
+    }
+
+    fn to_raw(&self, name: &str) -> CtpResult<FixedStr<N>> {
+        Ok(FixedStr::from_raw(encode_text(self, name)?))
+    }
+}
+
+impl RawField<[u8; 9]> for Option<Date> {
+    fn from_raw(raw: &[u8; 9]) -> Self {
+        parse_date(&decode_text(raw))
+    }
+
+    fn to_raw(&self, name: &str) -> CtpResult<[u8; 9]> {
+        match self {
+            Some(date) => encode_text(&format_date(*date), name),
+            None => Ok([0; 9]),
+        }
+    }
+}
+
+impl RawField<[u8; 9]> for Option<Time> {
+    fn from_raw(raw: &[u8; 9]) -> Self {
+        parse_time(&decode_text(raw))
+    }
+
+    fn to_raw(&self, name: &str) -> CtpResult<[u8; 9]> {
+        match self {
+            Some(time) => encode_text(&format_time(*time), name),
+            None => Ok([0; 9]),
+        }
+    }
+}
+
+// 组合开平/投保标志，每个字节对应一条腿
+impl<T: From<u8> + Into<u8> + Copy, const N: usize> RawField<[u8; N]> for Vec<T> {
+    fn from_raw(raw: &[u8; N]) -> Self {
+        raw.iter()
+            .take_while(|&&b| b != 0)
+            .map(|&b| T::from(b))
+            .collect()
+    }
+
+    fn to_raw(&self, name: &str) -> CtpResult<[u8; N]> {
+        if self.len() >= N {
+            return Err(CtpError::InvalidParameterError(format!(
+                "字段 {} 最多 {} 项，实际 {} 项",
+                name,
+                N - 1,
+                self.len()
+            )));
+        }
+        let mut result = [0u8; N];
+        for (slot, flag) in result.iter_mut().zip(self) {
+            *slot = (*flag).into();
+        }
+        Ok(result)
+    }
+}
+
+/// 将GB18030定长字节数组解码为字符串，去除末尾的空字节
+pub fn decode_text(bytes: &[u8]) -> String {
+    GbkConverter::gb18030_to_utf8(bytes).unwrap_or_default()
+}
+
+/// 将字符串编码为GB18030定长字节数组
+///
+/// 编码后的长度必须小于`N`，以保留结尾的空字节；超长时返回错误而不是截断
+pub fn encode_text<const N: usize>(value: &str, name: &str) -> CtpResult<[u8; N]> {
+    let bytes = GbkConverter::utf8_to_gb18030(value)?;
+    if bytes.len() >= N {
+        return Err(CtpError::InvalidParameterError(format!(
+            "字段 {} 的值 {:?} 编码后 {} 字节，超过上限 {}",
+            name,
+            value,
+            bytes.len(),
+            N - 1
+        )));
+    }
+    let mut result = [0u8; N];
+    result[..bytes.len()].copy_from_slice(&bytes);
+    Ok(result)
+}
+
+/// 将CTP价格转换为`Option`，`f64::MAX`和NaN表示无效
+pub fn price_or_none(price: f64) -> Option<f64> {
+    if price.is_nan() || price >= f64::MAX {
+        None
+    } else {
+        Some(price)
+    }
+}
+
+/// 解析`YYYYMMDD`格式的日期
+pub fn parse_date(s: &str) -> Option<Date> {
+    let s = s.trim();
+    if s.len() != 8 || !s.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    let year = s[0..4].parse().ok()?;
+    let month = Month::try_from(s[4..6].parse::<u8>().ok()?).ok()?;
+    let day = s[6..8].parse().ok()?;
+    Date::from_calendar_date(year, month, day).ok()
+}
+
+/// 解析`HH:MM:SS`格式的时间
+pub fn parse_time(s: &str) -> Option<Time> {
+    let mut parts = s.trim().splitn(3, ':');
+    let hour = parts.next()?.parse().ok()?;
+    let minute = parts.next()?.parse().ok()?;
+    let second = parts.next()?.parse().ok()?;
+    Time::from_hms(hour, minute, second).ok()
+}
+
+/// 格式化为`YYYYMMDD`
+pub fn format_date(date: Date) -> String {
+    format!(
+        "{:04}{:02}{:02}",
+        date.year(),
+        u8::from(date.month()),
+        date.day()
+    )
+}
+
+/// 格式化为`HH:MM:SS`
+pub fn format_time(time: Time) -> String {
+    format!(
+        "{:02}:{:02}:{:02}",
+        time.hour(),
+        time.minute(),
+        time.second()
+    )
+}
+
+macro_rules! ctp_char_enum {
+    (
+        $(#[$meta:meta])*
+        $name:ident {
+            $($(#[$vmeta:meta])* $variant:ident = $code:literal,)*
+        }
+    ) => {
+        $(#[$meta])*
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+        #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+        pub enum $name {
+            $($(#[$vmeta])* $variant,)*
+            /// 未识别的取值，保留原始字节
+            Unknown(u8),
+        }
+
+        impl $name {
+            /// CTP字符代码
+            pub fn code(self) -> u8 {
+                match self {
+                    $(Self::$variant => $code,)*
+                    Self::Unknown(code) => code,
+                }
+            }
+        }
+
+        impl From<u8> for $name {
+            fn from(code: u8) -> Self {
+                match code {
+                    $($code => Self::$variant,)*
+                    other => Self::Unknown(other),
+                }
+            }
+        }
+
+        impl From<$name> for u8 {
+            fn from(value: $name) -> u8 {
+                value.code()
+            }
+        }
+
+        impl Default for $name {
+            fn default() -> Self {
+                Self::Unknown(0)
+            }
+        }
+
+        impl RawField<u8> for $name {
+            fn from_raw(raw: &u8) -> Self {
+                Self::from(*raw)
+            }
+
+            fn to_raw(&self, _name: &str) -> CtpResult<u8> {
+                Ok(self.code())
+            }
+        }
+    };
+}
+
+ctp_char_enum! {
+    /// 买卖方向
+    Direction {
+        /// 买
+        Buy = b'0',
+        /// 卖
+        Sell = b'1',
+    }
+}
+
+ctp_char_enum! {
+    /// 开平标志
+    OffsetFlag {
+        /// 开仓
+        Open = b'0',
+        /// 平仓
+        Close = b'1',
+        /// 强平
+        ForceClose = b'2',
+        /// 平今
+        CloseToday = b'3',
+        /// 平昨
+        CloseYesterday = b'4',
+        /// 强减
+        ForceOff = b'5',
+        /// 本地强平
+        LocalForceClose = b'6',
+    }
+}
+
+ctp_char_enum! {
+    /// 投机套保标志
+    HedgeFlag {
+        /// 投机
+        Speculation = b'1',
+        /// 套利
+        Arbitrage = b'2',
+        /// 套保
+        Hedge = b'3',
+        /// 做市商
+        MarketMaker = b'5',
+        /// 第一腿投机第二腿套保
+        SpecHedge = b'6',
+        /// 第一腿套保第二腿投机
+        HedgeSpec = b'7',
+    }
+}
+
+ctp_char_enum! {
+    /// 报单价格条件
+    OrderPriceType {
+        /// 任意价
+        AnyPrice = b'1',
+        /// 限价
+        LimitPrice = b'2',
+        /// 最优价
+        BestPrice = b'3',
+        /// 最新价
+        LastPrice = b'4',
+        /// 最新价浮动上浮1个ticks
+        LastPricePlusOneTicks = b'5',
+        /// 最新价浮动上浮2个ticks
+        LastPricePlusTwoTicks = b'6',
+        /// 最新价浮动上浮3个ticks
+        LastPricePlusThreeTicks = b'7',
+        /// 卖一价
+        AskPrice1 = b'8',
+        /// 卖一价浮动上浮1个ticks
+        AskPrice1PlusOneTicks = b'9',
+        /// 卖一价浮动上浮2个ticks
+        AskPrice1PlusTwoTicks = b'A',
+        /// 卖一价浮动上浮3个ticks
+        AskPrice1PlusThreeTicks = b'B',
+        /// 买一价
+        BidPrice1 = b'C',
+        /// 买一价浮动上浮1个ticks
+        BidPrice1PlusOneTicks = b'D',
+        /// 买一价浮动上浮2个ticks
+        BidPrice1PlusTwoTicks = b'E',
+        /// 买一价浮动上浮3个ticks
+        BidPrice1PlusThreeTicks = b'F',
+        /// 五档价
+        FiveLevelPrice = b'G',
+    }
+}
+
+ctp_char_enum! {
+    /// 有效期类型
+    TimeCondition {
+        /// 立即完成，否则撤销
+        Ioc = b'1',
+        /// 本节有效
+        Gfs = b'2',
+        /// 当日有效
+        Gfd = b'3',
+        /// 指定日期前有效
+        Gtd = b'4',
+        /// 撤销前有效
+        Gtc = b'5',
+        /// 集合竞价有效
+        Gfa = b'6',
+    }
+}
+
+ctp_char_enum! {
+    /// 成交量类型
+    VolumeCondition {
+        /// 任何数量
+        Any = b'1',
+        /// 最小数量
+        Min = b'2',
+        /// 全部数量
+        All = b'3',
+    }
+}
+
+ctp_char_enum! {
+    /// 触发条件
+    ContingentCondition {
+        /// 立即
+        Immediately = b'1',
+        /// 止损
+        Touch = b'2',
+        /// 止赢
+        TouchProfit = b'3',
+        /// 预埋单
+        ParkedOrder = b'4',
+        /// 最新价大于条件价
+        LastPriceGreaterThanStopPrice = b'5',
+        /// 最新价大于等于条件价
+        LastPriceGreaterEqualStopPrice = b'6',
+        /// 最新价小于条件价
+        LastPriceLesserThanStopPrice = b'7',
+        /// 最新价小于等于条件价
+        LastPriceLesserEqualStopPrice = b'8',
+        /// 卖一价大于条件价
+        AskPriceGreaterThanStopPrice = b'9',
+        /// 卖一价大于等于条件价
+        AskPriceGreaterEqualStopPrice = b'A',
+        /// 卖一价小于条件价
+        AskPriceLesserThanStopPrice = b'B',
+        /// 卖一价小于等于条件价
+        AskPriceLesserEqualStopPrice = b'C',
+        /// 买一价大于条件价
+        BidPriceGreaterThanStopPrice = b'D',
+        /// 买一价大于等于条件价
+        BidPriceGreaterEqualStopPrice = b'E',
+        /// 买一价小于条件价
+        BidPriceLesserThanStopPrice = b'F',
+        /// 买一价小于等于条件价
+        BidPriceLesserEqualStopPrice = b'H',
+    }
+}
+
+ctp_char_enum! {
+    /// 强平原因
+    ForceCloseReason {
+        /// 非强平
+        NotForceClose = b'0',
+        /// 资金不足
+        LackDeposit = b'1',
+        /// 客户超仓
+        ClientOverPositionLimit = b'2',
+        /// 会员超仓
+        MemberOverPositionLimit = b'3',
+        /// 持仓非整数倍
+        NotMultiple = b'4',
+        /// 违规
+        Violation = b'5',
+        /// 其它
+        Other = b'6',
+        /// 自然人临近交割
+        PersonDeliv = b'7',
+        /// 本地强平资金不足忽略敞口
+        NotVerifyCapital = b'8',
+        /// 本地强平资金不足
+        LocalLackDeposit = b'9',
+        /// 本地强平违规持仓忽略敞口
+        LocalViolationNocheck = b'a',
+        /// 本地强平违规持仓
+        LocalViolation = b'b',
+    }
+}
+
+ctp_char_enum! {
+    /// 报单状态
+    OrderStatus {
+        /// 全部成交
+        AllTraded = b'0',
+        /// 部分成交还在队列中
+        PartTradedQueueing = b'1',
+        /// 部分成交不在队列中
+        PartTradedNotQueueing = b'2',
+        /// 未成交还在队列中
+        NoTradeQueueing = b'3',
+        /// 未成交不在队列中
+        NoTradeNotQueueing = b'4',
+        /// 撤单
+        Canceled = b'5',
+        /// 未知
+        Pending = b'a',
+        /// 尚未触发
+        NotTouched = b'b',
+        /// 已触发
+        Touched = b'c',
+    }
+}
+
+impl OrderStatus {
+    /// 是否为终态（全部成交、撤单或不在队列中）
+    pub fn is_finished(self) -> bool {
+        matches!(
+            self,
+            Self::AllTraded
+                | Self::PartTradedNotQueueing
+                | Self::NoTradeNotQueueing
+                | Self::Canceled
+        )
+    }
+}
+
+ctp_char_enum! {
+    /// 报单提交状态
+    OrderSubmitStatus {
+        /// 已经提交
+        InsertSubmitted = b'0',
+        /// 撤单已经提交
+        CancelSubmitted = b'1',
+        /// 修改已经提交
+        ModifySubmitted = b'2',
+        /// 已经接受
+        Accepted = b'3',
+        /// 报单已经被拒绝
+        InsertRejected = b'4',
+        /// 撤单已经被拒绝
+        CancelRejected = b'5',
+        /// 改单已经被拒绝
+        ModifyRejected = b'6',
+    }
+}
+
+ctp_char_enum! {
+    /// 报单类型
+    OrderType {
+        /// 正常
+        Normal = b'0',
+        /// 报价衍生
+        DeriveFromQuote = b'1',
+        /// 组合衍生
+        DeriveFromCombination = b'2',
+        /// 组合报单
+        Combination = b'3',
+        /// 条件单
+        ConditionalOrder = b'4',
+        /// 互换单
+        Swap = b'5',
+        /// 大宗交易成交衍生
+        DeriveFromBlockTrade = b'6',
+        /// 期转现成交衍生
+        DeriveFromEfpTrade = b'7',
+    }
+}
+
+ctp_char_enum! {
+    /// 报单来源
+    OrderSource {
+        /// 来自参与者
+        Participant = b'0',
+        /// 来自管理员
+        Administrator = b'1',
+    }
+}
+
+ctp_char_enum! {
+    /// 持仓多空方向
+    PosiDirection {
+        /// 净
+        Net = b'1',
+        /// 多头
+        Long = b'2',
+        /// 空头
+        Short = b'3',
+    }
+}
+
+ctp_char_enum! {
+    /// 持仓日期
+    PositionDate {
+        /// 今日持仓
+        Today = b'1',
+        /// 历史持仓
+        History = b'2',
+    }
+}
+
+ctp_char_enum! {
+    /// 成交类型
+    TradeType {
+        /// 组合持仓拆分为单一持仓
+        SplitCombination = b'#',
+        /// 普通成交
+        Common = b'0',
+        /// 期权执行
+        OptionsExecution = b'1',
+        /// OTC成交
+        Otc = b'2',
+        /// 期转现衍生成交
+        EfpDerived = b'3',
+        /// 组合衍生成交
+        CombinationDerived = b'4',
+        /// 大宗交易成交
+        BlockTrade = b'5',
+    }
+}
+
+ctp_char_enum! {
+    /// 成交价来源
+    PriceSource {
+        /// 前成交价
+        LastPrice = b'0',
+        /// 买委托价
+        Buy = b'1',
+        /// 卖委托价
+        Sell = b'2',
+        /// 场外成交价
+        Otc = b'3',
+    }
+}
+
+ctp_char_enum! {
+    /// 交易角色
+    TradingRole {
+        /// 代理
+        Broker = b'1',
+        /// 自营
+        Host = b'2',
+        /// 做市商
+        Maker = b'3',
+    }
+}
+
+ctp_char_enum! {
+    /// 成交来源
+    TradeSource {
+        /// 来自交易所普通回报
+        Normal = b'0',
+        /// 来自查询
+        Query = b'1',
+    }
+}
+
+ctp_char_enum! {
+    /// 产品类型
+    ProductClass {
+        /// 期货
+        Futures = b'1',
+        /// 期货期权
+        Options = b'2',
+        /// 组合
+        Combination = b'3',
+        /// 即期
+        Spot = b'4',
+        /// 期转现
+        Efp = b'5',
+        /// 现货期权
+        SpotOption = b'6',
+        /// TAS合约
+        Tas = b'7',
+        /// 金属指数
+        MetalIndex = b'I',
+    }
+}
+
+ctp_char_enum! {
+    /// 合约生命周期状态
+    InstLifePhase {
+        /// 未上市
+        NotStart = b'0',
+        /// 上市
+        Started = b'1',
+        /// 停牌
+        Pause = b'2',
+        /// 到期
+        Expired = b'3',
+    }
+}
+
+ctp_char_enum! {
+    /// 持仓类型
+    PositionType {
+        /// 净持仓
+        Net = b'1',
+        /// 综合持仓
+        Gross = b'2',
+    }
+}
+
+ctp_char_enum! {
+    /// 持仓日期类型
+    PositionDateType {
+        /// 使用历史持仓
+        UseHistory = b'1',
+        /// 不使用历史持仓
+        NoUseHistory = b'2',
+    }
+}
+
+ctp_char_enum! {
+    /// 期权类型
+    OptionsType {
+        /// 看涨
+        Call = b'1',
+        /// 看跌
+        Put = b'2',
+    }
+}
+
+ctp_char_enum! {
+    /// 组合类型
+    CombinationType {
+        /// 期货组合
+        Future = b'0',
+        /// 垂直价差BUL
+        Bul = b'1',
+        /// 垂直价差BER
+        Ber = b'2',
+        /// 跨式组合
+        Std = b'3',
+        /// 宽跨式组合
+        Stg = b'4',
+        /// 备兑组合
+        Prt = b'5',
+        /// 时间价差组合
+        Cas = b'6',
+        /// 期权对锁组合
+        Opl = b'7',
+        /// 买备兑组合
+        Bfo = b'8',
+        /// 买入期权垂直价差组合
+        Bls = b'9',
+        /// 卖出期权垂直价差组合
+        Bes = b'a',
+    }
+}
+
+ctp_char_enum! {
+    /// 名字服务器登录模式
+    LoginMode {
+        /// 交易
+        Trade = b'0',
+        /// 转账
+        Transfer = b'1',
+    }
+}
+
+ctp_char_enum! {
+    /// 银行帐号类型
+    BankAccType {
+        /// 银行存折
+        BankBook = b'1',
+        /// 储蓄卡
+        SavingCard = b'2',
+        /// 信用卡
+        CreditCard = b'3',
+    }
+}
+
+ctp_char_enum! {
+    /// 证件类型
+    IdCardType {
+        /// 组织机构代码
+        Eid = b'0',
+        /// 中国公民身份证
+        IdCard = b'1',
+        /// 军官证
+        OfficerIdCard = b'2',
+        /// 警官证
+        PoliceIdCard = b'3',
+        /// 士兵证
+        SoldierIdCard = b'4',
+        /// 户口簿
+        HouseholdRegister = b'5',
+        /// 护照
+        Passport = b'6',
+        /// 台胞证
+        TaiwanCompatriotIdCard = b'7',
+        /// 回乡证
+        HomeComingCard = b'8',
+        /// 营业执照号
+        LicenseNo = b'9',
+        /// 税务登记号/当地纳税ID
+        TaxNo = b'A',
+        /// 港澳居民来往内地通行证
+        HmMainlandTravelPermit = b'B',
+        /// 台湾居民来往大陆通行证
+        TwMainlandTravelPermit = b'C',
+        /// 驾照
+        DrivingLicense = b'D',
+        /// 当地社保ID
+        SocialId = b'F',
+        /// 当地身份证
+        LocalId = b'G',
+        /// 商业登记证
+        BusinessRegistration = b'H',
+        /// 港澳永久性居民身份证
+        HkmcIdCard = b'I',
+        /// 人行开户许可证
+        AccountsPermits = b'J',
+        /// 外国人永久居留证
+        FrgPrmtRdCard = b'K',
+        /// 资管产品备案函
+        CptMngPrdLetter = b'L',
+        /// 港澳台居民居住证
+        HkmcTwResidencePermit = b'M',
+        /// 统一社会信用代码
+        UniformSocialCreditCode = b'N',
+        /// 机构成立证明文件
+        CorporationCertNo = b'O',
+        /// 其他证件
+        OtherCard = b'x',
+    }
+}
+
+ctp_char_enum! {
+    /// 转帐流水有效标志
+    AvailabilityFlag {
+        /// 未确认
+        Invalid = b'0',
+        /// 有效
+        Valid = b'1',
+        /// 冲正
+        Repeal = b'2',
+    }
+}
+
+macro_rules! ctp_model {
+    (
+        $(#[$meta:meta])*
+        $name:ident <=> $raw:ident {
+            $($(#[$fmeta:meta])* $field:ident: $ty:ty,)*
+        }
+        $(excluding { $($skipped:ident,)* })?
+    ) => {
+        $(#[$meta])*
+        #[derive(Debug, Clone, Default, PartialEq)]
+        #[cfg_attr(
+            feature = "serde",
+            derive(serde::Serialize, serde::Deserialize),
+            serde(default)
+        )]
+        pub struct $name {
+            $($(#[$fmeta])* pub $field: $ty,)*
+        }
+
+        impl From<&$raw> for $name {
+            fn from(raw: &$raw) -> Self {
+                Self {
+                    $($field: RawField::from_raw(&raw.$field),)*
+                }
+            }
+        }
+
+        impl TryFrom<&$name> for $raw {
+            type Error = CtpError;
+
+            #[allow(clippy::needless_update)]
+            fn try_from(model: &$name) -> CtpResult<Self> {
+                Ok(Self {
+                    $($field: model.$field.to_raw(stringify!($field))?,)*
+                    ..Default::default()
+                })
+            }
+        }
+
+        impl $name {
+            // 编译期检查：原始结构体新增字段时必须映射或显式列入excluding
+            #[allow(dead_code)]
+            fn assert_fields_covered(raw: &$raw) {
+                let $raw {
+                    $($field: _,)*
+                    $($($skipped: _,)*)?
+                } = raw;
+            }
+        }
+    };
+}
+
+ctp_model! {
+    /// 名字服务器用户信息
+    FensUserInfo <=> FensUserInfoField {
+        /// 经纪公司代码
+        broker_id: String,
+        /// 用户代码
+        user_id: String,
+        /// 登录模式
+        login_mode: LoginMode,
+    }
+}
+
+impl FensUserInfo {
+    /// 创建交易模式的名字服务器用户信息
+    pub fn new(broker_id: &str, user_id: &str) -> Self {
+        Self {
+            broker_id: broker_id.to_string(),
+            user_id: user_id.to_string(),
+            login_mode: LoginMode::Trade,
+        }
+    }
+
+    /// 设置登录模式
+    pub fn with_login_mode(mut self, login_mode: LoginMode) -> Self {
+        self.login_mode = login_mode;
+        self
+    }
+}
+
+ctp_model! {
+    /// 当前连接的前置信息
+    FrontInfo <=> FrontInfoField {
+        /// 前置地址
+        front_addr: String,
+        /// 查询流控（每秒查询次数）
+        qry_freq: i32,
+        /// FTD流控（每秒FTD报文数）
+        ftd_pkg_freq: i32,
+    }
+}
+
+ctp_model! {
+    /// 报单录入请求
+    InputOrder <=> InputOrderField {
+        /// 经纪公司代码
+        broker_id: String,
+        /// 投资者代码
+        investor_id: String,
+        /// 合约代码
+        instrument_id: String,
+        /// 报单引用
+        order_ref: String,
+        /// 用户代码
+        user_id: String,
+        /// 报单价格条件
+        order_price_type: OrderPriceType,
+        /// 买卖方向
+        direction: Direction,
+        /// 组合开平标志
+        comb_offset_flag: Vec<OffsetFlag>,
+        /// 组合投机套保标志
+        comb_hedge_flag: Vec<HedgeFlag>,
+        /// 价格
+        limit_price: f64,
+        /// 数量
+        volume_total_original: i32,
+        /// 有效期类型
+        time_condition: TimeCondition,
+        /// GTD日期
+        gtd_date: Option<Date>,
+        /// 成交量类型
+        volume_condition: VolumeCondition,
+        /// 最小成交量
+        min_volume: i32,
+        /// 触发条件
+        contingent_condition: ContingentCondition,
+        /// 止损价
+        stop_price: f64,
+        /// 强平原因
+        force_close_reason: ForceCloseReason,
+        /// 自动挂起标志
+        is_auto_suspend: bool,
+        /// 业务单元
+        business_unit: String,
+        /// 请求编号
+        request_id: i32,
+        /// 用户强平标志
+        user_force_close: bool,
+        /// 互换单标志
+        is_swap_order: bool,
+        /// 交易所代码
+        exchange_id: String,
+        /// 投资单元代码
+        invest_unit_id: String,
+        /// 资金账号
+        account_id: String,
+        /// 币种代码
+        currency_id: String,
+        /// 交易编码
+        client_id: String,
+        /// Mac地址
+        mac_address: String,
+        /// 合约在交易所的代码
+        exchange_inst_id: String,
+        /// IP地址
+        ip_address: String,
+    }
+}
+
+ctp_model! {
+    /// 报单
+    Order <=> OrderField {
+        /// 经纪公司代码
+        broker_id: String,
+        /// 投资者代码
+        investor_id: String,
+        /// 合约代码
+        instrument_id: String,
+        /// 报单引用
+        order_ref: String,
+        /// 用户代码
+        user_id: String,
+        /// 报单价格条件
+        order_price_type: OrderPriceType,
+        /// 买卖方向
+        direction: Direction,
+        /// 组合开平标志
+        comb_offset_flag: Vec<OffsetFlag>,
+        /// 组合投机套保标志
+        comb_hedge_flag: Vec<HedgeFlag>,
+        /// 价格
+        limit_price: f64,
+        /// 数量
+        volume_total_original: i32,
+        /// 有效期类型
+        time_condition: TimeCondition,
+        /// GTD日期
+        gtd_date: Option<Date>,
+        /// 成交量类型
+        volume_condition: VolumeCondition,
+        /// 最小成交量
+        min_volume: i32,
+        /// 触发条件
+        contingent_condition: ContingentCondition,
+        /// 止损价
+        stop_price: f64,
+        /// 强平原因
+        force_close_reason: ForceCloseReason,
+        /// 自动挂起标志
+        is_auto_suspend: bool,
+        /// 业务单元
+        business_unit: String,
+        /// 请求编号
+        request_id: i32,
+        /// 本地报单编号
+        order_local_id: String,
+        /// 交易所代码
+        exchange_id: String,
+        /// 会员代码
+        participant_id: String,
+        /// 客户代码
+        client_id: String,
+        /// 合约在交易所的代码
+        exchange_inst_id: String,
+        /// 交易所交易员代码
+        trader_id: String,
+        /// 安装编号
+        install_id: i32,
+        /// 报单提交状态
+        order_submit_status: OrderSubmitStatus,
+        /// 报单提示序号
+        notify_sequence: i32,
+        /// 交易日
+        trading_day: Option<Date>,
+        /// 结算编号
+        settlement_id: i32,
+        /// 报单编号
+        order_sys_id: String,
+        /// 报单来源
+        order_source: OrderSource,
+        /// 报单状态
+        order_status: OrderStatus,
+        /// 报单类型
+        order_type: OrderType,
+        /// 今成交数量
+        volume_traded: i32,
+        /// 剩余数量
+        volume_total: i32,
+        /// 报单日期
+        insert_date: Option<Date>,
+        /// 委托时间
+        insert_time: Option<Time>,
+        /// 激活时间
+        active_time: Option<Time>,
+        /// 挂起时间
+        suspend_time: Option<Time>,
+        /// 最后修改时间
+        update_time: Option<Time>,
+        /// 撤销时间
+        cancel_time: Option<Time>,
+        /// 最后修改交易所交易员代码
+        active_trader_id: String,
+        /// 结算会员编号
+        clearing_part_id: String,
+        /// 序号
+        sequence_no: i32,
+        /// 前置编号
+        front_id: i32,
+        /// 会话编号
+        session_id: i32,
+        /// 用户端产品信息
+        user_product_info: String,
+        /// 状态信息
+        status_msg: String,
+        /// 用户强平标志
+        user_force_close: bool,
+        /// 操作用户代码
+        active_user_id: String,
+        /// 经纪公司报单编号
+        broker_order_seq: i32,
+        /// 相关报单
+        relative_order_sys_id: String,
+        /// 郑商所成交数量
+        zczc_total_traded_volume: i32,
+        /// 互换单标志
+        is_swap_order: bool,
+        /// 营业部编号
+        branch_id: String,
+        /// 投资单元代码
+        invest_unit_id: String,
+        /// 资金账号
+        account_id: String,
+        /// 币种代码
+        currency_id: String,
+        /// Mac地址
+        mac_address: String,
+        /// IP地址
+        ip_address: String,
+    }
+}
+
+impl Order {
+    /// 报单是否已结束（不会再有成交）
+    pub fn is_finished(&self) -> bool {
+        self.order_status.is_finished()
+            || self.order_submit_status == OrderSubmitStatus::InsertRejected
+    }
+}
+
+ctp_model! {
+    /// 成交
+    Trade <=> TradeField {
+        /// 经纪公司代码
+        broker_id: String,
+        /// 投资者代码
+        investor_id: String,
+        /// 合约代码
+        instrument_id: String,
+        /// 报单引用
+        order_ref: String,
+        /// 用户代码
+        user_id: String,
+        /// 交易所代码
+        exchange_id: String,
+        /// 成交编号
+        trade_id: String,
+        /// 买卖方向
+        direction: Direction,
+        /// 报单编号
+        order_sys_id: String,
+        /// 会员代码
+        participant_id: String,
+        /// 客户代码
+        client_id: String,
+        /// 交易角色
+        trading_role: TradingRole,
+        /// 合约在交易所的代码
+        exchange_inst_id: String,
+        /// 开平标志
+        offset_flag: OffsetFlag,
+        /// 投机套保标志
+        hedge_flag: HedgeFlag,
+        /// 价格
+        price: f64,
+        /// 数量
+        volume: i32,
+        /// 成交日期
+        trade_date: Option<Date>,
+        /// 成交时间
+        trade_time: Option<Time>,
+        /// 成交类型
+        trade_type: TradeType,
+        /// 成交价来源
+        price_source: PriceSource,
+        /// 交易所交易员代码
+        trader_id: String,
+        /// 本地报单编号
+        order_local_id: String,
+        /// 结算会员编号
+        clearing_part_id: String,
+        /// 业务单元
+        business_unit: String,
+        /// 序号
+        sequence_no: i32,
+        /// 交易日
+        trading_day: Option<Date>,
+        /// 结算编号
+        settlement_id: i32,
+        /// 经纪公司报单编号
+        broker_order_seq: i32,
+        /// 成交来源
+        trade_source: TradeSource,
+        /// 投资单元代码
+        invest_unit_id: String,
+    }
+}
+
+ctp_model! {
+    /// 投资者持仓
+    Position <=> InvestorPositionField {
+        /// 合约代码
+        instrument_id: String,
+        /// 经纪公司代码
+        broker_id: String,
+        /// 投资者代码
+        investor_id: String,
+        /// 持仓多空方向
+        posi_direction: PosiDirection,
+        /// 投机套保标志
+        hedge_flag: HedgeFlag,
+        /// 持仓日期
+        position_date: PositionDate,
+        /// 上日持仓
+        yd_position: i32,
+        /// 今日持仓
+        position: i32,
+        /// 多头冻结
+        long_frozen: i32,
+        /// 空头冻结
+        short_frozen: i32,
+        /// 开仓冻结金额
+        long_frozen_amount: f64,
+        /// 开仓冻结金额
+        short_frozen_amount: f64,
+        /// 开仓量
+        open_volume: i32,
+        /// 平仓量
+        close_volume: i32,
+        /// 开仓金额
+        open_amount: f64,
+        /// 平仓金额
+        close_amount: f64,
+        /// 持仓成本
+        position_cost: f64,
+        /// 上次占用的保证金
+        pre_margin: f64,
+        /// 占用的保证金
+        use_margin: f64,
+        /// 冻结的保证金
+        frozen_margin: f64,
+        /// 冻结的资金
+        frozen_cash: f64,
+        /// 冻结的手续费
+        frozen_commission: f64,
+        /// 资金差额
+        cash_in: f64,
+        /// 手续费
+        commission: f64,
+        /// 平仓盈亏
+        close_profit: f64,
+        /// 持仓盈亏
+        position_profit: f64,
+        /// 上次结算价
+        pre_settlement_price: f64,
+        /// 本次结算价
+        settlement_price: f64,
+        /// 交易日
+        trading_day: Option<Date>,
+        /// 结算编号
+        settlement_id: i32,
+        /// 开仓成本
+        open_cost: f64,
+        /// 交易所保证金
+        exchange_margin: f64,
+        /// 组合成交形成的持仓
+        comb_position: i32,
+        /// 组合多头冻结
+        comb_long_frozen: i32,
+        /// 组合空头冻结
+        comb_short_frozen: i32,
+        /// 逐日盯市平仓盈亏
+        close_profit_by_date: f64,
+        /// 逐笔对冲平仓盈亏
+        close_profit_by_trade: f64,
+        /// 今日持仓
+        today_position: i32,
+        /// 保证金率
+        margin_rate_by_money: f64,
+        /// 保证金率(按手数)
+        margin_rate_by_volume: f64,
+        /// 执行冻结
+        strike_frozen: i32,
+        /// 执行冻结金额
+        strike_frozen_amount: f64,
+        /// 放弃执行冻结
+        abandon_frozen: i32,
+        /// 交易所代码
+        exchange_id: String,
+        /// 执行冻结的昨仓
+        yd_strike_frozen: i32,
+        /// 投资单元代码
+        invest_unit_id: String,
+        /// 持仓成本差值
+        position_cost_offset: f64,
+        /// tas持仓手数
+        tas_position: i32,
+        /// tas持仓成本
+        tas_position_cost: f64,
+    }
+}
+
+ctp_model! {
+    /// 资金账户
+    Account <=> TradingAccountField {
+        /// 经纪公司代码
+        broker_id: String,
+        /// 投资者帐号
+        account_id: String,
+        /// 上次质押金额
+        pre_mortgage: f64,
+        /// 上次信用额度
+        pre_credit: f64,
+        /// 上次存款额
+        pre_deposit: f64,
+        /// 上次结算准备金
+        pre_balance: f64,
+        /// 上次占用的保证金
+        pre_margin: f64,
+        /// 利息基数
+        interest_base: f64,
+        /// 利息收入
+        interest: f64,
+        /// 入金金额
+        deposit: f64,
+        /// 出金金额
+        withdraw: f64,
+        /// 冻结的保证金
+        frozen_margin: f64,
+        /// 冻结的资金
+        frozen_cash: f64,
+        /// 冻结的手续费
+        frozen_commission: f64,
+        /// 当前保证金总额
+        curr_margin: f64,
+        /// 资金差额
+        cash_in: f64,
+        /// 手续费
+        commission: f64,
+        /// 平仓盈亏
+        close_profit: f64,
+        /// 持仓盈亏
+        position_profit: f64,
+        /// 期货结算准备金
+        balance: f64,
+        /// 可用资金
+        available: f64,
+        /// 可取资金
+        withdraw_quota: f64,
+        /// 基本准备金
+        reserve: f64,
+        /// 交易日
+        trading_day: Option<Date>,
+        /// 结算编号
+        settlement_id: i32,
+        /// 信用额度
+        credit: f64,
+        /// 质押金额
+        mortgage: f64,
+        /// 交易所保证金
+        exchange_margin: f64,
+        /// 投资者交割保证金
+        delivery_margin: f64,
+        /// 交易所交割保证金
+        exchange_delivery_margin: f64,
+        /// 保底期货结算准备金
+        reserve_balance: f64,
+        /// 币种代码
+        currency_id: String,
+        /// 上次货币质入金额
+        pre_fund_mortgage_in: f64,
+        /// 上次货币质出金额
+        pre_fund_mortgage_out: f64,
+        /// 货币质入金额
+        fund_mortgage_in: f64,
+        /// 货币质出金额
+        fund_mortgage_out: f64,
+        /// 货币质押余额
+        fund_mortgage_available: f64,
+        /// 可质押货币金额
+        mortgageable_fund: f64,
+        /// 特殊产品占用保证金
+        spec_product_margin: f64,
+        /// 特殊产品冻结保证金
+        spec_product_frozen_margin: f64,
+        /// 特殊产品手续费
+        spec_product_commission: f64,
+        /// 特殊产品冻结手续费
+        spec_product_frozen_commission: f64,
+        /// 特殊产品持仓盈亏
+        spec_product_position_profit: f64,
+        /// 特殊产品平仓盈亏
+        spec_product_close_profit: f64,
+        /// 根据持仓盈亏算法计算的特殊产品持仓盈亏
+        spec_product_position_profit_by_alg: f64,
+        /// 特殊产品交易所保证金
+        spec_product_exchange_margin: f64,
+        /// 业务类型
+        biz_type: Option<char>,
+        /// 延时换汇冻结金额
+        frozen_swap: f64,
+        /// 剩余换汇额度
+        remain_swap: f64,
+    }
+}
+
+ctp_model! {
+    /// 合约
+    Instrument <=> InstrumentField {
+        /// 合约代码
+        instrument_id: String,
+        /// 交易所代码
+        exchange_id: String,
+        /// 合约名称
+        instrument_name: String,
+        /// 合约在交易所的代码
+        exchange_inst_id: String,
+        /// 产品代码
+        product_id: String,
+        /// 产品类型
+        product_class: ProductClass,
+        /// 交割年份
+        delivery_year: i32,
+        /// 交割月
+        delivery_month: i32,
+        /// 市价单最大下单量
+        max_market_order_volume: i32,
+        /// 市价单最小下单量
+        min_market_order_volume: i32,
+        /// 限价单最大下单量
+        max_limit_order_volume: i32,
+        /// 限价单最小下单量
+        min_limit_order_volume: i32,
+        /// 合约数量乘数
+        volume_multiple: i32,
+        /// 最小变动价位
+        price_tick: f64,
+        /// 创建日
+        create_date: Option<Date>,
+        /// 上市日
+        open_date: Option<Date>,
+        /// 到期日
+        expire_date: Option<Date>,
+        /// 开始交割日
+        start_deliv_date: Option<Date>,
+        /// 结束交割日
+        end_deliv_date: Option<Date>,
+        /// 合约生命周期状态
+        inst_life_phase: InstLifePhase,
+        /// 当前是否交易
+        is_trading: bool,
+        /// 持仓类型
+        position_type: PositionType,
+        /// 持仓日期类型
+        position_date_type: PositionDateType,
+        /// 多头保证金率
+        long_margin_ratio: Option<f64>,
+        /// 空头保证金率
+        short_margin_ratio: Option<f64>,
+        /// 是否使用大额单边保证金算法
+        max_margin_side_algorithm: Option<char>,
+        /// 基础商品代码
+        underlying_instr_id: String,
+        /// 执行价
+        strike_price: Option<f64>,
+        /// 期权类型
+        options_type: OptionsType,
+        /// 合约基础商品乘数
+        underlying_multiple: Option<f64>,
+        /// 组合类型
+        combination_type: CombinationType,
+    }
+}
+
+ctp_model! {
+    /// 深度行情
+    DepthMarketData <=> DepthMarketDataField {
+        /// 交易日
+        trading_day: Option<Date>,
+        /// 交易所代码
+        exchange_id: String,
+        /// 最新价
+        last_price: Option<f64>,
+        /// 上次结算价
+        pre_settlement_price: Option<f64>,
+        /// 昨收盘
+        pre_close_price: Option<f64>,
+        /// 昨持仓量
+        pre_open_interest: f64,
+        /// 今开盘
+        open_price: Option<f64>,
+        /// 最高价
+        highest_price: Option<f64>,
+        /// 最低价
+        lowest_price: Option<f64>,
+        /// 数量
+        volume: i32,
+        /// 成交金额
+        turnover: f64,
+        /// 持仓量
+        open_interest: f64,
+        /// 今收盘
+        close_price: Option<f64>,
+        /// 本次结算价
+        settlement_price: Option<f64>,
+        /// 涨停板价
+        upper_limit_price: Option<f64>,
+        /// 跌停板价
+        lower_limit_price: Option<f64>,
+        /// 昨虚实度
+        pre_delta: Option<f64>,
+        /// 今虚实度
+        curr_delta: Option<f64>,
+        /// 最后修改时间
+        update_time: Option<Time>,
+        /// 最后修改毫秒
+        update_millisec: i32,
+        /// 申买价一
+        bid_price1: Option<f64>,
+        /// 申买量一
+        bid_volume1: i32,
+        /// 申卖价一
+        ask_price1: Option<f64>,
+        /// 申卖量一
+        ask_volume1: i32,
+        /// 申买价二
+        bid_price2: Option<f64>,
+        /// 申买量二
+        bid_volume2: i32,
+        /// 申卖价二
+        ask_price2: Option<f64>,
+        /// 申卖量二
+        ask_volume2: i32,
+        /// 申买价三
+        bid_price3: Option<f64>,
+        /// 申买量三
+        bid_volume3: i32,
+        /// 申卖价三
+        ask_price3: Option<f64>,
+        /// 申卖量三
+        ask_volume3: i32,
+        /// 申买价四
+        bid_price4: Option<f64>,
+        /// 申买量四
+        bid_volume4: i32,
+        /// 申卖价四
+        ask_price4: Option<f64>,
+        /// 申卖量四
+        ask_volume4: i32,
+        /// 申买价五
+        bid_price5: Option<f64>,
+        /// 申买量五
+        bid_volume5: i32,
+        /// 申卖价五
+        ask_price5: Option<f64>,
+        /// 申卖量五
+        ask_volume5: i32,
+        /// 当日均价
+        average_price: Option<f64>,
+        /// 业务日期
+        action_day: Option<Date>,
+        /// 合约代码
+        instrument_id: String,
+        /// 合约在交易所的代码
+        exchange_inst_id: String,
+        /// 上带价
+        banding_upper_price: Option<f64>,
+        /// 下带价
+        banding_lower_price: Option<f64>,
+    }
+    excluding {
+        reserve1,
+        reserve2,
+    }
+}
+
+impl DepthMarketData {
+    /// 行情时间戳（业务日期 + 最后修改时间 + 毫秒）
+    pub fn timestamp(&self) -> Option<PrimitiveDateTime> {
+        let millis = u16::try_from(self.update_millisec).ok()?;
+        let time = self.update_time?.replace_millisecond(millis).ok()?;
+        Some(PrimitiveDateTime::new(self.action_day?, time))
+    }
+}
+
+ctp_model! {
+    /// 登录应答
+    LoginInfo <=> RspUserLoginField {
+        /// 交易日
+        trading_day: Option<Date>,
+        /// 登录成功时间
+        login_time: Option<Time>,
+        /// 经纪公司代码
+        broker_id: String,
+        /// 用户代码
+        user_id: String,
+        /// 交易系统名称
+        system_name: String,
+        /// 前置编号
+        front_id: i32,
+        /// 会话编号
+        session_id: i32,
+        /// 最大报单引用
+        max_order_ref: String,
+        /// 上期所时间
+        shfe_time: Option<Time>,
+        /// 大商所时间
+        dce_time: Option<Time>,
+        /// 郑商所时间
+        czce_time: Option<Time>,
+        /// 中金所时间
+        ffex_time: Option<Time>,
+        /// 能源中心时间
+        ine_time: Option<Time>,
+    }
+}
+
+ctp_model! {
+    /// 银期转帐流水
+    TransferSerial <=> TransferSerialField {
+        /// 平台流水号
+        plate_serial: i32,
+        /// 交易发起方日期
+        trade_date: Option<Date>,
+        /// 交易日期
+        trading_day: Option<Date>,
+        /// 交易时间
+        trade_time: Option<Time>,
+        /// 业务功能码
+        trade_code: String,
+        /// 会话编号
+        session_id: i32,
+        /// 银行编码
+        bank_id: String,
+        /// 银行分支机构编码
+        bank_branch_id: String,
+        /// 银行帐号类型
+        bank_acc_type: BankAccType,
+        /// 银行帐号
+        bank_account: String,
+        /// 银行流水号
+        bank_serial: String,
+        /// 期货公司编码
+        broker_id: String,
+        /// 期商分支机构代码
+        broker_branch_id: String,
+        /// 期货公司帐号类型，取值与银行帐号类型相同
+        future_acc_type: BankAccType,
+        /// 投资者帐号
+        account_id: String,
+        /// 投资者代码
+        investor_id: String,
+        /// 期货公司流水号
+        future_serial: i32,
+        /// 证件类型
+        id_card_type: IdCardType,
+        /// 证件号码
+        identified_card_no: String,
+        /// 币种代码
+        currency_id: String,
+        /// 交易金额
+        trade_amount: f64,
+        /// 应收客户费用
+        cust_fee: f64,
+        /// 应收期货公司费用
+        broker_fee: f64,
+        /// 有效标志
+        availability_flag: AvailabilityFlag,
+        /// 操作员
+        operator_code: String,
+        /// 新银行帐号
+        bank_new_account: String,
+        /// 错误代码
+        error_id: i32,
+        /// 错误信息
+        error_msg: String,
+    }
+}
+
+impl TransferSerial {
+    /// 转帐方向，业务功能码无法识别时返回`None`
+    pub fn direction(&self) -> Option<TransferDirection> {
+        TransferDirection::from_trade_code(&self.trade_code)
+    }
+
+    /// 流水是否有效（未冲正且已确认）
+    pub fn is_valid(&self) -> bool {
+        self.availability_flag == AvailabilityFlag::Valid
+    }
+
+    /// 带符号的金额：转入期货为正，转出期货为负，方向未知时为0
+    pub fn signed_amount(&self) -> f64 {
+        match self.direction() {
+            Some(TransferDirection::BankToFuture) => self.trade_amount,
+            Some(TransferDirection::FutureToBank) => -self.trade_amount,
+            None => 0.0,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::StringConvert;
+
+    #[test]
+    fn test_order_round_trip() {
+        let field = OrderField {
+            instrument_id: <[u8; 31]>::from_utf8_string("rb2601").unwrap(),
+            status_msg: <[u8; 81]>::from_utf8_string("未成交").unwrap(),
+            direction: b'1',
+            comb_offset_flag: *b"3\0\0\0\0",
+            comb_hedge_flag: *b"1\0\0\0\0",
+            order_status: b'3',
+            insert_date: *b"20250102\0",
+            insert_time: *b"21:00:01\0",
+            limit_price: 3500.0,
+            is_swap_order: 0,
+            ..Default::default()
+        };
+
+        let order = Order::from(&field);
+        assert_eq!(order.instrument_id, "rb2601");
+        assert_eq!(order.status_msg, "未成交");
+        assert_eq!(order.direction, Direction::Sell);
+        assert_eq!(order.comb_offset_flag, vec![OffsetFlag::CloseToday]);
+        assert_eq!(order.comb_hedge_flag, vec![HedgeFlag::Speculation]);
+        assert_eq!(order.order_status, OrderStatus::NoTradeQueueing);
+        assert_eq!(
+            order.insert_date,
+            Date::from_calendar_date(2025, Month::January, 2).ok()
+        );
+        assert_eq!(order.insert_time, Time::from_hms(21, 0, 1).ok());
+        assert!(order.trading_day.is_none());
+        assert!(!order.is_finished());
+
+        let back = OrderField::try_from(&order).unwrap();
+        assert_eq!(back.instrument_id, field.instrument_id);
+        assert_eq!(back.status_msg, field.status_msg);
+        assert_eq!(back.comb_offset_flag, field.comb_offset_flag);
+        assert_eq!(back.insert_date, field.insert_date);
+        assert_eq!(back.insert_time, field.insert_time);
+        assert_eq!(back.direction, field.direction);
+    }
+
+    #[test]
+    fn test_unknown_flag_is_preserved() {
+        assert_eq!(Direction::from(b'9'), Direction::Unknown(b'9'));
+        assert_eq!(u8::from(Direction::Unknown(b'9')), b'9');
+        assert_eq!(u8::from(OrderStatus::default()), 0);
+    }
+
+    #[test]
+    fn test_market_data_sentinels() {
+        let field = DepthMarketDataField {
+            last_price: 3500.0,
+            settlement_price: f64::MAX,
+            action_day: *b"20250102\0",
+            update_time: *b"09:30:15\0",
+            update_millisec: 500,
+            ..Default::default()
+        };
+
+        let tick = DepthMarketData::from(&field);
+        assert_eq!(tick.last_price, Some(3500.0));
+        assert_eq!(tick.settlement_price, None);
+        let timestamp = tick.timestamp().unwrap();
+        assert_eq!(timestamp.millisecond(), 500);
+
+        let back = DepthMarketDataField::try_from(&tick).unwrap();
+        assert_eq!(back.settlement_price, f64::MAX);
+        assert_eq!(back.last_price, 3500.0);
+    }
+
+    #[test]
+    fn test_overflow_is_rejected() {
+        let input = InputOrder {
+            instrument_id: "x".repeat(31),
+            ..Default::default()
+        };
+        assert!(matches!(
+            InputOrderField::try_from(&input),
+            Err(CtpError::InvalidParameterError(_))
+        ));
+
+        let input = InputOrder {
+            comb_offset_flag: vec![OffsetFlag::Open; 5],
+            ..Default::default()
+        };
+        assert!(InputOrderField::try_from(&input).is_err());
+    }
+
+    #[test]
+    fn test_transfer_serial_round_trip() {
+        let field = TransferSerialField {
+            plate_serial: 12,
+            trade_date: *b"20250102\0",
+            trade_code: encode_text("202001", "trade_code").unwrap(),
+            bank_acc_type: b'2',
+            future_acc_type: b'1',
+            id_card_type: b'1',
+            identified_card_no: encode_text("110101199001011234", "identified_card_no").unwrap(),
+            availability_flag: b'1',
+            trade_amount: 1000.0,
+            error_msg: encode_text("正确", "error_msg").unwrap(),
+            ..Default::default()
+        };
+
+        let serial = TransferSerial::from(&field);
+        assert_eq!(serial.future_acc_type, BankAccType::BankBook);
+        assert_eq!(serial.id_card_type, IdCardType::IdCard);
+        assert_eq!(serial.identified_card_no, "110101199001011234");
+
+        let back = TransferSerialField::try_from(&serial).unwrap();
+        assert_eq!(back.future_acc_type, field.future_acc_type);
+        assert_eq!(back.id_card_type, field.id_card_type);
+        assert_eq!(back.identified_card_no, field.identified_card_no);
+        assert_eq!(back.trade_code, field.trade_code);
+        assert_eq!(back.error_msg, field.error_msg);
+        assert_eq!(TransferSerial::from(&back), serial);
+    }
+
+    #[test]
+    fn test_fens_and_front_info() {
+        let fens = FensUserInfo::new("9999", "00001").with_login_mode(LoginMode::Transfer);
+        let field = FensUserInfoField::try_from(&fens).unwrap();
+        assert_eq!(field.broker_id, "9999");
+        assert_eq!(field.login_mode, b'1');
+
+        let field = FrontInfoField {
+            front_addr: encode_text("tcp://180.168.146.187:10201", "front_addr").unwrap(),
+            qry_freq: 1,
+            ftd_pkg_freq: 6,
+        };
+        let info = FrontInfo::from(&field);
+        assert_eq!(info.front_addr, "tcp://180.168.146.187:10201");
+        assert_eq!(info.qry_freq, 1);
+    }
+}
@@ -0,0 +1,397 @@
+//! 报单引用分配
+//!
+//! 同一会话内报单引用不能重复。[`OrderRefAllocator`]在登录成功后根据
+//! [`RspUserLoginField::max_order_ref`]初始化，之后多个线程并发分配也不会重复。
+//! [`TraderApi`](crate::api::TraderApi)自带一个分配器，发送`order_ref`为空的报单或
+//! `order_action_ref`为0的撤单时自动填写，分配器尚未初始化时返回错误。
+//!
+//! 设置持久化文件后，分配器按块预留引用并先把预留上限写入文件，进程在同一交易日内重启时
+//! 从文件中的上限继续分配，不会与崩溃前发出的引用重复。
+//!
+//! CTP要求同一会话内报单引用严格递增，所有引用都从同一个计数器分配，不为策略划分
+//! 独立的引用区间或前缀：预先划给某个策略的区间会被其他策略更大的引用越过而作废，
+//! 加字母前缀的引用也无法与数字引用比较大小。策略用[`OrderRefAllocator::next_ref_for`]
+//! 分配时记下引用所属的策略，之后按[`OrderRefAllocator::strategy_of`]从回报中的引用找回。
+//! 同一策略连续分配的引用合并为一段区间保存，占用的内存随策略交替的次数而不是报单数增长。
+
+use crate::api::trader_api::InputOrderField;
+use crate::error::{CtpError, CtpResult};
+use crate::types::{InputOrderActionField, RspUserLoginField, StringConvert};
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex, MutexGuard};
+
+/// 报单引用最多12个字符
+const ORDER_REF_WIDTH: usize = 12;
+
+/// 默认每次持久化预留的引用数
+const DEFAULT_RESERVE: u64 = 100;
+
+#[derive(Debug, Default)]
+struct RefState {
+    seeded: bool,
+    trading_day: String,
+    // 下一个可分配的引用
+    next: u64,
+    // 已写入持久化文件的预留上限（不含），持久化时分配不能超过该值
+    reserved: u64,
+    // 策略分配的引用区间，按起点递增
+    strategies: Vec<StrategyRun>,
+}
+
+// 同一策略连续分配的引用区间[start, end)
+#[derive(Debug)]
+struct StrategyRun {
+    start: u64,
+    end: u64,
+    strategy: Arc<str>,
+}
+
+/// 线程安全的报单引用分配器
+#[derive(Debug)]
+pub struct OrderRefAllocator {
+    state: Mutex<RefState>,
+    store: Option<PathBuf>,
+    reserve: u64,
+}
+
+impl Default for OrderRefAllocator {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl OrderRefAllocator {
+    /// 创建只在内存中计数的分配器
+    pub fn new() -> Self {
+        Self {
+            state: Mutex::new(RefState::default()),
+            store: None,
+            reserve: DEFAULT_RESERVE,
+        }
+    }
+
+    /// 把已预留的引用上限持久化到`path`，重启后同一交易日内从该上限继续分配
+    pub fn with_persistence(mut self, path: impl AsRef<Path>) -> Self {
+        self.store = Some(path.as_ref().to_path_buf());
+        self
+    }
+
+    /// 设置每次持久化预留的引用数，越大写文件越少，重启后跳过的引用越多
+    pub fn with_reserve(mut self, reserve: u64) -> Self {
+        self.reserve = reserve.max(1);
+        self
+    }
+
+    fn lock(&self) -> MutexGuard<'_, RefState> {
+        self.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// 根据登录响应初始化
+    pub fn seed_from_login(&self, login: &RspUserLoginField) -> CtpResult<()> {
+        let trading_day = login.trading_day.to_utf8_string()?;
+        let max_order_ref = parse_order_ref(&login.max_order_ref.to_utf8_string()?)?;
+        self.seed(&trading_day, max_order_ref)
+    }
+
+    /// 以交易日和已使用的最大引用初始化
+    ///
+    /// 同一交易日内重复初始化（如断线重登）不会让引用回退；持久化文件中记录的同一交易日
+    /// 上限也会参与比较
+    pub fn seed(&self, trading_day: &str, max_order_ref: u64) -> CtpResult<()> {
+        let trading_day = trading_day.trim();
+        let mut state = self.lock();
+        let mut next = max_order_ref + 1;
+        if state.seeded && state.trading_day == trading_day {
+            next = next.max(state.next);
+        } else {
+            state.strategies.clear();
+            state.reserved = 0;
+        }
+        if let Some(path) = &self.store {
+            if let Some((day, reserved)) = read_store(path)? {
+                if day == trading_day {
+                    next = next.max(reserved);
+                }
+            }
+        }
+
+        state.seeded = true;
+        state.trading_day = trading_day.to_string();
+        state.next = next;
+        state.reserved = state.reserved.max(next);
+        Ok(())
+    }
+
+    /// 是否已初始化
+    pub fn is_seeded(&self) -> bool {
+        self.lock().seeded
+    }
+
+    /// 已分配的最大引用，未分配过时为初始化时的最大引用
+    pub fn high_water_mark(&self) -> u64 {
+        self.lock().next.saturating_sub(1)
+    }
+
+    /// 分配一个引用
+    pub fn next_value(&self) -> CtpResult<u64> {
+        self.allocate(None)
+    }
+
+    /// 分配一个引用，格式为12位数字
+    pub fn next_ref(&self) -> CtpResult<String> {
+        self.next_value().map(format_order_ref)
+    }
+
+    /// 为策略分配一个引用，格式为12位数字
+    ///
+    /// 与其他引用共用同一个递增计数器，只是记下所属策略，交易日切换后清空
+    pub fn next_ref_for(&self, strategy: &str) -> CtpResult<String> {
+        self.allocate(Some(strategy)).map(format_order_ref)
+    }
+
+    /// 引用所属的策略，只识别本交易日用[`next_ref_for`](Self::next_ref_for)分配的引用
+    pub fn strategy_of(&self, order_ref: &str) -> Option<String> {
+        let value = parse_order_ref(order_ref).ok()?;
+        let state = self.lock();
+        let index = state.strategies.partition_point(|run| run.start <= value);
+        let run = state.strategies[..index].last()?;
+        (value < run.end).then(|| run.strategy.to_string())
+    }
+
+    /// `order_ref`为空时填写新引用，返回是否填写；需要填写但未初始化时返回错误
+    pub fn fill_order(&self, req: &mut InputOrderField) -> CtpResult<bool> {
+        if !is_blank(&req.order_ref) {
+            return Ok(false);
+        }
+        req.order_ref = <[u8; 13]>::from_utf8_string(&self.next_ref()?)?;
+        Ok(true)
+    }
+
+    /// `order_action_ref`为0时填写新引用，返回是否填写；需要填写但未初始化时返回错误
+    pub fn fill_action(&self, req: &mut InputOrderActionField) -> CtpResult<bool> {
+        if req.order_action_ref != 0 {
+            return Ok(false);
+        }
+        req.order_action_ref = i32::try_from(self.next_value()?)
+            .map_err(|_| CtpError::InvalidParameterError("报单操作引用超出int范围".to_string()))?;
+        Ok(true)
+    }
+
+    // 分配一个引用并记下所属策略；持久化时先写入新的预留上限
+    fn allocate(&self, strategy: Option<&str>) -> CtpResult<u64> {
+        let mut state = self.lock();
+        if !state.seeded {
+            return Err(CtpError::InitializationError(
+                "报单引用分配器尚未根据登录响应初始化".to_string(),
+            ));
+        }
+        let value = state.next;
+        let end = value + 1;
+        if end > 10u64.pow(ORDER_REF_WIDTH as u32) {
+            return Err(CtpError::InvalidParameterError(
+                "报单引用已超过12位".to_string(),
+            ));
+        }
+        if let Some(path) = &self.store {
+            if end > state.reserved {
+                let reserved = end + self.reserve;
+                write_store(path, &state.trading_day, reserved)?;
+                state.reserved = reserved;
+            }
+        }
+        state.next = end;
+        if let Some(strategy) = strategy {
+            let strategies = &mut state.strategies;
+            match strategies.last_mut() {
+                Some(run) if run.end == value && &*run.strategy == strategy => run.end = end,
+                _ => {
+                    let strategy = strategies
+                        .iter()
+                        .rev()
+                        .find(|run| &*run.strategy == strategy)
+                        .map_or_else(|| Arc::from(strategy), |run| run.strategy.clone());
+                    strategies.push(StrategyRun {
+                        start: value,
+                        end,
+                        strategy,
+                    });
+                }
+            }
+        }
+        Ok(value)
+    }
+}
+
+/// 格式化为12位数字引用
+pub fn format_order_ref(value: u64) -> String {
+    format!("{:0width$}", value, width = ORDER_REF_WIDTH)
+}
+
+// 解析登录响应中的最大报单引用，空值视为0
+fn parse_order_ref(value: &str) -> CtpResult<u64> {
+    let value = value.trim_matches(|c: char| c == '\0' || c.is_whitespace());
+    if value.is_empty() {
+        return Ok(0);
+    }
+    value
+        .parse()
+        .map_err(|_| CtpError::InvalidParameterError(format!("无法解析最大报单引用: {:?}", value)))
+}
+
+fn is_blank(field: &[u8]) -> bool {
+    field
+        .iter()
+        .take_while(|&&b| b != 0)
+        .all(|b| b.is_ascii_whitespace())
+}
+
+fn store_error(path: &Path, e: std::io::Error) -> CtpError {
+    CtpError::InvalidPath(format!("报单引用文件 {}: {}", path.display(), e))
+}
+
+fn read_store(path: &Path) -> CtpResult<Option<(String, u64)>> {
+    let content = match fs::read_to_string(path) {
+        Ok(content) => content,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+        Err(e) => return Err(store_error(path, e)),
+    };
+    let mut trading_day = None;
+    let mut reserved = None;
+    for line in content.lines() {
+        if let Some(day) = line.strip_prefix("trading_day=") {
+            trading_day = Some(day.trim().to_string());
+        } else if let Some(value) = line.strip_prefix("reserved=") {
+            reserved = value.trim().parse().ok();
+        }
+    }
+    Ok(trading_day.zip(reserved))
+}
+
+// 先写临时文件再改名，崩溃时不会留下写了一半的文件
+fn write_store(path: &Path, trading_day: &str, reserved: u64) -> CtpResult<()> {
+    let tmp = path.with_extension("tmp");
+    let mut file = fs::File::create(&tmp).map_err(|e| store_error(&tmp, e))?;
+    write!(file, "trading_day={}\nreserved={}\n", trading_day, reserved)
+        .and_then(|_| file.sync_all())
+        .map_err(|e| store_error(&tmp, e))?;
+    fs::rename(&tmp, path).map_err(|e| store_error(path, e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+
+    fn login(trading_day: &str, max_order_ref: &str) -> RspUserLoginField {
+        let mut login = RspUserLoginField::default();
+        login.trading_day[..trading_day.len()].copy_from_slice(trading_day.as_bytes());
+        login.max_order_ref[..max_order_ref.len()].copy_from_slice(max_order_ref.as_bytes());
+        login
+    }
+
+    #[test]
+    fn test_seed_and_concurrent_allocation() {
+        let allocator = Arc::new(OrderRefAllocator::new());
+        assert!(allocator.next_ref().is_err());
+
+        allocator
+            .seed_from_login(&login("20260105", "        42"))
+            .unwrap();
+        assert_eq!(allocator.next_ref().unwrap(), "000000000043");
+
+        let handles: Vec<_> = (0..4)
+            .map(|_| {
+                let allocator = allocator.clone();
+                std::thread::spawn(move || {
+                    (0..250)
+                        .map(|_| allocator.next_value().unwrap())
+                        .collect::<Vec<_>>()
+                })
+            })
+            .collect();
+        let mut all: Vec<u64> = handles
+            .into_iter()
+            .flat_map(|h| h.join().unwrap())
+            .collect();
+        all.sort();
+        all.dedup();
+        assert_eq!(all.len(), 1000);
+        assert_eq!(allocator.high_water_mark(), 1043);
+
+        // 断线重登时登录响应中的最大引用较小，不回退
+        allocator.seed("20260105", 10).unwrap();
+        assert_eq!(allocator.next_value().unwrap(), 1044);
+        allocator.seed("20260106", 0).unwrap();
+        assert_eq!(allocator.next_value().unwrap(), 1);
+    }
+
+    #[test]
+    fn test_persistence_survives_restart() {
+        let path = std::env::temp_dir().join(format!("ctp_rust_order_ref_{}", std::process::id()));
+        let _ = fs::remove_file(&path);
+
+        let allocator = OrderRefAllocator::new()
+            .with_persistence(&path)
+            .with_reserve(10);
+        allocator.seed("20260105", 0).unwrap();
+        for _ in 0..5 {
+            allocator.next_value().unwrap();
+        }
+        drop(allocator);
+
+        // 模拟崩溃后重启，登录响应中的最大引用落后于已发出的引用
+        let restarted = OrderRefAllocator::new().with_persistence(&path);
+        restarted.seed("20260105", 2).unwrap();
+        assert!(restarted.next_value().unwrap() > 5);
+
+        let next_day = OrderRefAllocator::new().with_persistence(&path);
+        next_day.seed("20260106", 0).unwrap();
+        assert_eq!(next_day.next_value().unwrap(), 1);
+        let _ = fs::remove_file(&path);
+    }
+
+    #[test]
+    fn test_strategy_tag_and_fill() {
+        let allocator = OrderRefAllocator::new();
+        let mut order = InputOrderField::default();
+        let mut action = InputOrderActionField::default();
+        assert!(allocator.fill_order(&mut order).is_err());
+        assert!(allocator.fill_action(&mut action).is_err());
+
+        allocator.seed("20260105", 100).unwrap();
+        let grid = allocator.next_ref_for("grid").unwrap();
+        assert!(allocator.fill_order(&mut order).unwrap());
+        let trend = allocator.next_ref_for("trend").unwrap();
+
+        // 策略引用与普通引用交错，仍然严格递增
+        assert_eq!(grid, "000000000101");
+        assert_eq!(order.order_ref.to_utf8_string().unwrap(), "000000000102");
+        assert_eq!(trend, "000000000103");
+        assert!(!allocator.fill_order(&mut order).unwrap());
+
+        assert_eq!(allocator.strategy_of(&grid).as_deref(), Some("grid"));
+        assert_eq!(
+            allocator.strategy_of("        103").as_deref(),
+            Some("trend")
+        );
+        assert_eq!(allocator.strategy_of("000000000102"), None);
+        assert_eq!(allocator.strategy_of("G00000000101"), None);
+
+        // 同一策略连续分配的引用合并为一段
+        let grid2 = allocator.next_ref_for("grid").unwrap();
+        let grid3 = allocator.next_ref_for("grid").unwrap();
+        assert_eq!(allocator.lock().strategies.len(), 3);
+        assert_eq!(allocator.strategy_of(&grid2).as_deref(), Some("grid"));
+        assert_eq!(allocator.strategy_of(&grid3).as_deref(), Some("grid"));
+        assert_eq!(allocator.strategy_of("000000000106"), None);
+
+        assert!(allocator.fill_action(&mut action).unwrap());
+        assert_eq!(action.order_action_ref, 106);
+        assert!(!allocator.fill_action(&mut action).unwrap());
+
+        allocator.seed("20260106", 0).unwrap();
+        assert_eq!(allocator.strategy_of(&grid), None);
+    }
+}
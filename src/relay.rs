@@ -0,0 +1,211 @@
+//! 中继模式终端信息上报
+//!
+//! 按照穿透式监管要求，作为中继（为终端客户转发交易请求）接入的系统需要为每个终端
+//! 客户上报其采集到的加密系统信息。CTP提供两种方式：
+//!
+//! - 多连接模式：每个终端客户一个连接，在终端认证成功后、用户登录前调用
+//!   `RegisterUserSystemInfo`
+//! - 操作员登录模式：中继以操作员身份登录后，多次调用`SubmitUserSystemInfo`上报
+//!   各终端客户的信息
+//!
+//! [`UserSystemInfo`]用于组装终端信息，并在转换为FFI结构体时检查长度。
+
+use crate::error::{CtpError, CtpResult};
+use crate::models::{encode_text, format_time};
+use crate::types::{
+    BrokerIdType, ClientAppIdType, ClientSystemInfoType, StringConvert, UserIdType,
+    UserSystemInfoField, WechatUserSystemInfoField,
+};
+use time::Time;
+
+/// 终端客户系统信息
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct UserSystemInfo {
+    /// 经纪公司代码
+    pub broker_id: String,
+    /// 用户代码
+    pub user_id: String,
+    /// 终端采集的加密系统信息
+    pub system_info: Vec<u8>,
+    /// 终端公网IP
+    pub client_ip: String,
+    /// 终端端口
+    pub client_port: u16,
+    /// 终端登录成功时间
+    pub login_time: Option<Time>,
+    /// 终端App代码
+    pub app_id: String,
+    /// 登录备注
+    pub login_remark: String,
+}
+
+impl UserSystemInfo {
+    /// 创建终端信息，`system_info`为终端通过看穿式监管数据采集库获取的原始数据
+    pub fn new(broker_id: &str, user_id: &str, system_info: impl Into<Vec<u8>>) -> Self {
+        Self {
+            broker_id: broker_id.to_string(),
+            user_id: user_id.to_string(),
+            system_info: system_info.into(),
+            client_ip: String::new(),
+            client_port: 0,
+            login_time: None,
+            app_id: String::new(),
+            login_remark: String::new(),
+        }
+    }
+
+    /// 设置终端公网IP和端口
+    pub fn with_client_ip(mut self, ip: &str, port: u16) -> Self {
+        self.client_ip = ip.to_string();
+        self.client_port = port;
+        self
+    }
+
+    /// 设置终端登录成功时间
+    pub fn with_login_time(mut self, login_time: Time) -> Self {
+        self.login_time = Some(login_time);
+        self
+    }
+
+    /// 设置终端App代码
+    pub fn with_app_id(mut self, app_id: &str) -> Self {
+        self.app_id = app_id.to_string();
+        self
+    }
+
+    /// 设置登录备注
+    pub fn with_login_remark(mut self, login_remark: &str) -> Self {
+        self.login_remark = login_remark.to_string();
+        self
+    }
+
+    /// 转换为`RegisterUserSystemInfo`/`SubmitUserSystemInfo`使用的结构体
+    pub fn to_field(&self) -> CtpResult<UserSystemInfoField> {
+        let (client_system_info, client_system_info_len) = self.system_info()?;
+        Ok(UserSystemInfoField {
+            broker_id: BrokerIdType::from_utf8_string(&self.broker_id)?,
+            user_id: UserIdType::from_utf8_string(&self.user_id)?,
+            client_system_info,
+            client_system_info_len,
+            client_ip_port: i32::from(self.client_port),
+            client_login_time: self.login_time()?,
+            client_app_id: self.app_id()?,
+            client_public_ip: encode_text(&self.client_ip, "client_ip")?,
+            client_login_remark: encode_text(&self.login_remark, "login_remark")?,
+            ..Default::default()
+        })
+    }
+
+    /// 转换为微信小程序等应用使用的结构体
+    pub fn to_wechat_field(&self) -> CtpResult<WechatUserSystemInfoField> {
+        let (wechat_clt_sys_info, wechat_clt_sys_info_len) = self.system_info()?;
+        Ok(WechatUserSystemInfoField {
+            broker_id: BrokerIdType::from_utf8_string(&self.broker_id)?,
+            user_id: UserIdType::from_utf8_string(&self.user_id)?,
+            wechat_clt_sys_info,
+            wechat_clt_sys_info_len,
+            client_ip_port: i32::from(self.client_port),
+            client_login_time: self.login_time()?,
+            client_app_id: self.app_id()?,
+            client_public_ip: encode_text(&self.client_ip, "client_ip")?,
+            client_login_remark: encode_text(&self.login_remark, "login_remark")?,
+        })
+    }
+
+    // 系统信息是二进制数据，按长度字段解读，不需要空字节结尾
+    fn system_info(&self) -> CtpResult<(ClientSystemInfoType, i32)> {
+        let mut raw = [0u8; 273];
+        if self.system_info.is_empty() {
+            return Err(CtpError::InvalidParameterError(
+                "终端系统信息不能为空".to_string(),
+            ));
+        }
+        if self.system_info.len() > raw.len() {
+            return Err(CtpError::InvalidParameterError(format!(
+                "终端系统信息 {} 字节，超过上限 {}",
+                self.system_info.len(),
+                raw.len()
+            )));
+        }
+        raw[..self.system_info.len()].copy_from_slice(&self.system_info);
+        Ok((raw, self.system_info.len() as i32))
+    }
+
+    fn login_time(&self) -> CtpResult<[u8; 9]> {
+        let login_time = self
+            .login_time
+            .ok_or_else(|| CtpError::InvalidParameterError("缺少终端登录成功时间".to_string()))?;
+        encode_text(&format_time(login_time), "login_time")
+    }
+
+    fn app_id(&self) -> CtpResult<ClientAppIdType> {
+        if self.app_id.is_empty() {
+            return Err(CtpError::InvalidParameterError(
+                "缺少终端App代码".to_string(),
+            ));
+        }
+        ClientAppIdType::from_utf8_string(&self.app_id)
+    }
+}
+
+impl TryFrom<&UserSystemInfo> for UserSystemInfoField {
+    type Error = CtpError;
+
+    fn try_from(info: &UserSystemInfo) -> CtpResult<Self> {
+        info.to_field()
+    }
+}
+
+impl TryFrom<&UserSystemInfo> for WechatUserSystemInfoField {
+    type Error = CtpError;
+
+    fn try_from(info: &UserSystemInfo) -> CtpResult<Self> {
+        info.to_wechat_field()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::parse_time;
+
+    fn info() -> UserSystemInfo {
+        UserSystemInfo::new("9999", "00001", vec![0x01, 0x00, 0xff])
+            .with_client_ip("203.0.113.7", 50123)
+            .with_login_time(parse_time("09:15:30").unwrap())
+            .with_app_id("client_app_1.0")
+    }
+
+    #[test]
+    fn test_to_field() {
+        let field = info().to_field().unwrap();
+        assert_eq!(field.user_id, "00001");
+        // 系统信息中的空字节按长度保留
+        assert_eq!(field.client_system_info_len, 3);
+        assert_eq!(&field.client_system_info[..3], &[0x01, 0x00, 0xff]);
+        assert_eq!(field.client_ip_port, 50123);
+        assert_eq!(&field.client_login_time, b"09:15:30\0");
+        assert_eq!(field.client_app_id, "client_app_1.0");
+        assert_eq!(&field.client_public_ip[..12], b"203.0.113.7\0");
+
+        let wechat = info().to_wechat_field().unwrap();
+        assert_eq!(wechat.wechat_clt_sys_info_len, 3);
+    }
+
+    #[test]
+    fn test_validation() {
+        let mut missing_time = info();
+        missing_time.login_time = None;
+        assert!(missing_time.to_field().is_err());
+
+        assert!(info().with_app_id("").to_field().is_err());
+
+        let mut too_long = info();
+        too_long.system_info = vec![1; 274];
+        assert!(matches!(
+            too_long.to_field(),
+            Err(CtpError::InvalidParameterError(_))
+        ));
+    }
+}
@@ -0,0 +1,319 @@
+//! serde支持模块
+//!
+//! 启用`serde` feature后，所有字段结构体和异步事件都可以序列化。
+//! 固定长度字节数组按GB18030解码为UTF-8字符串输出，字符型标志输出为单字符字符串，
+//! 反序列化时重新编码回`#[repr(C)]`结构体，保证往返无损。
+//!
+//! 密码、认证码等[`SecretStr`](crate::fixed_str::SecretStr)字段默认输出为`"***"`，反序列化
+//! 时`"***"`还原为空值；确实需要明文时在自己的结构体上使用[`secret_str`]。
+
+use encoding::all::GB18030;
+use encoding::{DecoderTrap, EncoderTrap, Encoding};
+use serde::de::{self, SeqAccess, Visitor};
+use serde::{Deserializer, Serializer};
+use std::fmt;
+
+/// 可按固定长度字节数组处理的类型
+pub trait FixedBytes: Sized {
+    /// 容量（字节数）
+    const CAPACITY: usize;
+
+    /// 原始字节
+    fn raw_bytes(&self) -> &[u8];
+
+    /// 从字节创建，超出容量时返回`None`
+    fn from_raw_bytes(bytes: &[u8]) -> Option<Self>;
+
+    /// 是否为敏感内容，序列化时隐去
+    const SECRET: bool = false;
+}
+
+/// 敏感字段序列化后的占位值
+pub const REDACTED: &str = "***";
+
+impl<const N: usize> FixedBytes for [u8; N] {
+    const CAPACITY: usize = N;
+
+    fn raw_bytes(&self) -> &[u8] {
+        self
+    }
+
+    fn from_raw_bytes(bytes: &[u8]) -> Option<Self> {
+        if bytes.len() > N {
+            return None;
+        }
+        let mut result = [0u8; N];
+        result[..bytes.len()].copy_from_slice(bytes);
+        Some(result)
+    }
+}
+
+// 移除末尾的空字节
+fn trim_nul(bytes: &[u8]) -> &[u8] {
+    let end = bytes.iter().rposition(|&b| b != 0).map_or(0, |pos| pos + 1);
+    &bytes[..end]
+}
+
+/// 固定长度字节数组的序列化
+///
+/// 能无损解码的内容输出为字符串，否则输出为字节序列；敏感内容非空时输出[`REDACTED`]
+pub mod fixed_str {
+    use super::*;
+
+    pub fn serialize<T, S>(value: &T, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        T: FixedBytes,
+        S: Serializer,
+    {
+        if T::SECRET && !trim_nul(value.raw_bytes()).is_empty() {
+            return serializer.serialize_str(REDACTED);
+        }
+        serialize_plain(value, serializer)
+    }
+
+    pub(super) fn serialize_plain<T, S>(value: &T, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        T: FixedBytes,
+        S: Serializer,
+    {
+        let bytes = trim_nul(value.raw_bytes());
+        match GB18030.decode(bytes, DecoderTrap::Strict) {
+            Ok(text)
+                if GB18030
+                    .encode(&text, EncoderTrap::Strict)
+                    .is_ok_and(|encoded| encoded == bytes) =>
+            {
+                serializer.serialize_str(&text)
+            }
+            _ => serializer.collect_seq(bytes),
+        }
+    }
+
+    pub fn deserialize<'de, T, D>(deserializer: D) -> Result<T, D::Error>
+    where
+        T: FixedBytes,
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_any(FixedStrVisitor {
+            plain: false,
+            marker: std::marker::PhantomData,
+        })
+    }
+
+    pub(super) fn deserialize_plain<'de, T, D>(deserializer: D) -> Result<T, D::Error>
+    where
+        T: FixedBytes,
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_any(FixedStrVisitor {
+            plain: true,
+            marker: std::marker::PhantomData,
+        })
+    }
+
+    struct FixedStrVisitor<T> {
+        // 为真时敏感内容的占位值按字面值处理
+        plain: bool,
+        marker: std::marker::PhantomData<T>,
+    }
+
+    impl<'de, T: FixedBytes> Visitor<'de> for FixedStrVisitor<T> {
+        type Value = T;
+
+        fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            write!(f, "不超过{}字节的字符串或字节序列", T::CAPACITY)
+        }
+
+        fn visit_str<E: de::Error>(self, v: &str) -> Result<T, E> {
+            let v = if T::SECRET && !self.plain && v == REDACTED {
+                ""
+            } else {
+                v
+            };
+            let bytes = GB18030
+                .encode(v, EncoderTrap::Strict)
+                .map_err(|e| E::custom(format!("GB18030编码失败: {}", e)))?;
+            T::from_raw_bytes(&bytes).ok_or_else(|| {
+                E::custom(format!(
+                    "字符串 {:?} 编码后 {} 字节，超过容量 {}",
+                    v,
+                    bytes.len(),
+                    T::CAPACITY
+                ))
+            })
+        }
+
+        fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<T, A::Error> {
+            let mut bytes = Vec::with_capacity(T::CAPACITY);
+            while let Some(b) = seq.next_element::<u8>()? {
+                bytes.push(b);
+            }
+            T::from_raw_bytes(&bytes).ok_or_else(|| {
+                de::Error::custom(format!(
+                    "字节序列长度 {} 超过容量 {}",
+                    bytes.len(),
+                    T::CAPACITY
+                ))
+            })
+        }
+    }
+}
+
+/// 敏感字段的明文序列化
+///
+/// 默认的[`fixed_str`]会隐去[`SecretStr`](crate::fixed_str::SecretStr)的内容，需要保存明文
+/// （如写入受保护的凭证文件）时显式使用`#[serde(with = "ctp_rust::serde_support::secret_str")]`
+pub mod secret_str {
+    use super::*;
+
+    pub fn serialize<T, S>(value: &T, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        T: FixedBytes,
+        S: Serializer,
+    {
+        fixed_str::serialize_plain(value, serializer)
+    }
+
+    pub fn deserialize<'de, T, D>(deserializer: D) -> Result<T, D::Error>
+    where
+        T: FixedBytes,
+        D: Deserializer<'de>,
+    {
+        fixed_str::deserialize_plain(deserializer)
+    }
+}
+
+/// 字符型标志的序列化
+///
+/// 可打印ASCII字符输出为单字符字符串，0输出为空字符串，其余值输出为整数
+pub mod char_flag {
+    use super::*;
+
+    pub fn serialize<S: Serializer>(value: &u8, serializer: S) -> Result<S::Ok, S::Error> {
+        match *value {
+            0 => serializer.serialize_str(""),
+            v if v.is_ascii_graphic() || v == b' ' => {
+                let mut buf = [0u8; 4];
+                serializer.serialize_str((v as char).encode_utf8(&mut buf))
+            }
+            v => serializer.serialize_u8(v),
+        }
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<u8, D::Error> {
+        deserializer.deserialize_any(CharFlagVisitor)
+    }
+
+    struct CharFlagVisitor;
+
+    impl Visitor<'_> for CharFlagVisitor {
+        type Value = u8;
+
+        fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            write!(f, "单个ASCII字符、空字符串或0-255的整数")
+        }
+
+        fn visit_str<E: de::Error>(self, v: &str) -> Result<u8, E> {
+            match v.as_bytes() {
+                [] => Ok(0),
+                [b] if b.is_ascii() => Ok(*b),
+                _ => Err(E::custom(format!("无效的字符标志: {:?}", v))),
+            }
+        }
+
+        fn visit_char<E: de::Error>(self, v: char) -> Result<u8, E> {
+            u8::try_from(v as u32)
+                .ok()
+                .filter(|b| b.is_ascii())
+                .ok_or_else(|| E::custom(format!("无效的字符标志: {:?}", v)))
+        }
+
+        fn visit_u64<E: de::Error>(self, v: u64) -> Result<u8, E> {
+            u8::try_from(v).map_err(|_| E::custom(format!("字符标志超出范围: {}", v)))
+        }
+
+        fn visit_i64<E: de::Error>(self, v: i64) -> Result<u8, E> {
+            u8::try_from(v).map_err(|_| E::custom(format!("字符标志超出范围: {}", v)))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::api::async_trader_api::AsyncTraderEvent;
+    use crate::api::trader_api::OrderField;
+    use crate::types::{PasswordType, ReqUserLoginField, StringConvert};
+
+    #[test]
+    fn test_order_field_round_trip() {
+        let order = OrderField {
+            instrument_id: <[u8; 31]>::from_utf8_string("rb2601").unwrap(),
+            status_msg: <[u8; 81]>::from_utf8_string("全部成交报单已提交").unwrap(),
+            direction: b'0',
+            order_status: b'0',
+            limit_price: 3500.5,
+            volume_total_original: 3,
+            ..Default::default()
+        };
+
+        let json = serde_json::to_value(&order).unwrap();
+        assert_eq!(json["instrument_id"], "rb2601");
+        assert_eq!(json["status_msg"], "全部成交报单已提交");
+        assert_eq!(json["direction"], "0");
+        assert_eq!(json["order_type"], "");
+
+        let back: OrderField = serde_json::from_value(json).unwrap();
+        assert_eq!(back.instrument_id, order.instrument_id);
+        assert_eq!(back.status_msg, order.status_msg);
+        assert_eq!(back.direction, order.direction);
+        assert_eq!(back.limit_price, order.limit_price);
+    }
+
+    #[test]
+    fn test_invalid_bytes_round_trip() {
+        let mut login = ReqUserLoginField::new("9999", "investor1", "123456").unwrap();
+        login.login_remark[..2].copy_from_slice(&[0xff, 0xfe]);
+
+        let json = serde_json::to_string(&login).unwrap();
+        let back: ReqUserLoginField = serde_json::from_str(&json).unwrap();
+        assert_eq!(back.login_remark, login.login_remark);
+        assert_eq!(back.broker_id, login.broker_id);
+    }
+
+    #[test]
+    fn test_secrets_redacted() {
+        let login = ReqUserLoginField::new("9999", "investor1", "123456").unwrap();
+        let json = serde_json::to_value(&login).unwrap();
+        assert_eq!(json["password"], "***");
+        assert_eq!(json["one_time_password"], "");
+        let back: ReqUserLoginField = serde_json::from_value(json).unwrap();
+        assert!(back.password.is_empty());
+
+        #[derive(serde::Serialize, serde::Deserialize)]
+        struct Stored {
+            #[serde(with = "crate::serde_support::secret_str")]
+            password: PasswordType,
+        }
+        let stored = Stored {
+            password: login.password.clone(),
+        };
+        let json = serde_json::to_string(&stored).unwrap();
+        assert!(json.contains("123456"));
+        let back: Stored = serde_json::from_str(&json).unwrap();
+        assert_eq!(back.password, "123456");
+    }
+
+    #[test]
+    fn test_overflow_is_rejected() {
+        let json = r#"{"broker_id": "123456789012"}"#;
+        assert!(serde_json::from_str::<ReqUserLoginField>(json).is_err());
+    }
+
+    #[test]
+    fn test_event_serialization() {
+        let event = AsyncTraderEvent::Disconnected(0x1001);
+        let json = serde_json::to_string(&event).unwrap();
+        let back: AsyncTraderEvent = serde_json::from_str(&json).unwrap();
+        assert!(matches!(back, AsyncTraderEvent::Disconnected(0x1001)));
+    }
+}
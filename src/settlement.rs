@@ -0,0 +1,477 @@
+//! 结算单获取与解析
+//!
+//! `ReqQrySettlementInfo`以多个`SettlementInfoField`分片返回结算单，每个分片的
+//! `content`只是整份报表的一段字节，多字节的GB18030字符可能被拆在两个分片之间。
+//! [`SettlementAssembler`]先按序拼接全部字节再统一解码，
+//! [`SettlementStatement`]再把定宽报表解析为资金状况、出入金、成交、平仓、持仓等分区。
+
+use crate::encoding::GbkConverter;
+use crate::error::{CtpError, CtpResult};
+use crate::types::{SettlementInfoField, StringConvert};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// 结算单分片拼接器
+#[derive(Debug, Clone, Default)]
+pub struct SettlementAssembler {
+    trading_day: String,
+    investor_id: String,
+    settlement_id: i32,
+    fragments: Vec<(i32, Vec<u8>)>,
+}
+
+impl SettlementAssembler {
+    /// 创建拼接器
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 追加一个分片
+    pub fn push(&mut self, info: &SettlementInfoField) {
+        if self.fragments.is_empty() {
+            self.trading_day = info.trading_day.to_utf8_string().unwrap_or_default();
+            self.investor_id = info.investor_id.to_string();
+            self.settlement_id = info.settlement_id;
+        }
+        let end = info
+            .content
+            .iter()
+            .position(|&b| b == 0)
+            .unwrap_or(info.content.len());
+        self.fragments
+            .push((info.sequence_no, info.content[..end].to_vec()));
+    }
+
+    /// 已接收的分片数
+    pub fn len(&self) -> usize {
+        self.fragments.len()
+    }
+
+    /// 是否未收到任何分片
+    pub fn is_empty(&self) -> bool {
+        self.fragments.is_empty()
+    }
+
+    /// 按序号拼接全部分片后解码为结算单
+    pub fn finish(mut self) -> CtpResult<SettlementStatement> {
+        self.fragments.sort_by_key(|(sequence_no, _)| *sequence_no);
+        let bytes: Vec<u8> = self
+            .fragments
+            .into_iter()
+            .flat_map(|(_, bytes)| bytes)
+            .collect();
+        let text = GbkConverter::gb18030_to_utf8(&bytes)?;
+
+        let mut statement = SettlementStatement::parse(&text);
+        statement.trading_day = self.trading_day;
+        statement.investor_id = self.investor_id;
+        statement.settlement_id = self.settlement_id;
+        Ok(statement)
+    }
+}
+
+/// 结算单中的表格
+#[derive(Debug, Clone, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SettlementTable {
+    /// 中文列名
+    pub headers: Vec<String>,
+    /// 英文列名
+    pub english_headers: Vec<String>,
+    /// 数据行
+    pub rows: Vec<Vec<String>>,
+    /// 合计行
+    pub totals: Option<Vec<String>>,
+}
+
+impl SettlementTable {
+    /// 按中文或英文列名查找列序号
+    pub fn column(&self, name: &str) -> Option<usize> {
+        self.headers.iter().position(|h| h == name).or_else(|| {
+            self.english_headers
+                .iter()
+                .position(|h| h.eq_ignore_ascii_case(name))
+        })
+    }
+
+    /// 获取指定行、指定列的单元格
+    pub fn get(&self, row: usize, name: &str) -> Option<&str> {
+        let column = self.column(name)?;
+        self.rows.get(row)?.get(column).map(String::as_str)
+    }
+
+    /// 获取指定行、指定列的数值
+    pub fn get_f64(&self, row: usize, name: &str) -> Option<f64> {
+        self.get(row, name).and_then(parse_amount)
+    }
+
+    /// 数据行数
+    pub fn len(&self) -> usize {
+        self.rows.len()
+    }
+
+    /// 是否没有数据行
+    pub fn is_empty(&self) -> bool {
+        self.rows.is_empty()
+    }
+}
+
+/// 结算单分区
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Section {
+    Header,
+    AccountSummary,
+    Deposits,
+    Trades,
+    ClosedPositions,
+    OpenPositions,
+    PositionSummary,
+}
+
+impl Section {
+    fn from_title(line: &str) -> Option<Self> {
+        const TITLES: &[(&str, Section)] = &[
+            ("资金状况", Section::AccountSummary),
+            ("出入金明细", Section::Deposits),
+            ("成交记录", Section::Trades),
+            ("成交明细", Section::Trades),
+            ("平仓明细", Section::ClosedPositions),
+            ("持仓明细", Section::OpenPositions),
+            ("持仓汇总", Section::PositionSummary),
+        ];
+        TITLES
+            .iter()
+            .find(|(title, _)| line.contains(title))
+            .map(|(_, section)| *section)
+    }
+}
+
+/// 解析后的结算单
+#[derive(Debug, Clone, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SettlementStatement {
+    /// 交易日
+    pub trading_day: String,
+    /// 投资者代码
+    pub investor_id: String,
+    /// 结算编号
+    pub settlement_id: i32,
+    /// 解码后的原文
+    pub text: String,
+    /// 表头信息（客户号、日期等）
+    pub header: Vec<(String, String)>,
+    /// 资金状况
+    pub account_summary: Vec<(String, String)>,
+    /// 出入金明细
+    pub deposits: SettlementTable,
+    /// 成交记录
+    pub trades: SettlementTable,
+    /// 平仓明细
+    pub closed_positions: SettlementTable,
+    /// 持仓明细
+    pub open_positions: SettlementTable,
+    /// 持仓汇总
+    pub position_summary: SettlementTable,
+    /// 按合约汇总的成交手续费
+    pub commissions: BTreeMap<String, f64>,
+}
+
+impl SettlementStatement {
+    /// 解析结算单文本
+    pub fn parse(text: &str) -> Self {
+        let mut statement = Self {
+            text: text.to_string(),
+            ..Default::default()
+        };
+
+        let mut section = Section::Header;
+        let mut table = SettlementTable::default();
+        // 0: 列名, 1: 数据, 2: 合计
+        let mut block = 0;
+        let mut block_has_rows = false;
+
+        for line in text.lines() {
+            let trimmed = line.trim();
+            if trimmed.is_empty() {
+                continue;
+            }
+
+            if is_separator(trimmed) {
+                if block_has_rows {
+                    block += 1;
+                    block_has_rows = false;
+                }
+                continue;
+            }
+
+            if trimmed.starts_with('|') {
+                let cells = split_row(trimmed);
+                match block {
+                    0 if table.headers.is_empty() => table.headers = cells,
+                    0 => table.english_headers = cells,
+                    _ if cells.first().is_some_and(|c| c.starts_with('共')) => {
+                        table.totals = Some(cells)
+                    }
+                    1 => table.rows.push(cells),
+                    _ => {}
+                }
+                block_has_rows = true;
+                continue;
+            }
+
+            if let Some(next) = Section::from_title(trimmed) {
+                statement.store_table(section, std::mem::take(&mut table));
+                section = next;
+                block = 0;
+                block_has_rows = false;
+                continue;
+            }
+
+            match section {
+                Section::Header => statement.header.extend(parse_pairs(trimmed)),
+                Section::AccountSummary => statement.account_summary.extend(parse_pairs(trimmed)),
+                _ => {}
+            }
+        }
+        statement.store_table(section, table);
+
+        statement.commissions = statement.collect_commissions();
+        statement
+    }
+
+    fn store_table(&mut self, section: Section, table: SettlementTable) {
+        let target = match section {
+            Section::Deposits => &mut self.deposits,
+            Section::Trades => &mut self.trades,
+            Section::ClosedPositions => &mut self.closed_positions,
+            Section::OpenPositions => &mut self.open_positions,
+            Section::PositionSummary => &mut self.position_summary,
+            Section::Header | Section::AccountSummary => return,
+        };
+        *target = table;
+    }
+
+    fn collect_commissions(&self) -> BTreeMap<String, f64> {
+        let mut commissions = BTreeMap::new();
+        for row in 0..self.trades.len() {
+            let instrument = self.trades.get(row, "合约").unwrap_or_default();
+            let fee = self.trades.get_f64(row, "手续费").unwrap_or(0.0);
+            if !instrument.is_empty() {
+                *commissions.entry(instrument.to_string()).or_insert(0.0) += fee;
+            }
+        }
+        commissions
+    }
+
+    /// 按标签查找资金状况项，标签可以是中文或英文部分
+    pub fn summary_value(&self, label: &str) -> Option<&str> {
+        self.account_summary
+            .iter()
+            .find(|(key, _)| key_matches(key, label))
+            .map(|(_, value)| value.as_str())
+    }
+
+    /// 按标签查找资金状况金额
+    pub fn summary_amount(&self, label: &str) -> Option<f64> {
+        self.summary_value(label).and_then(parse_amount)
+    }
+
+    /// 是否为空结算单（当日无结算内容）
+    pub fn is_empty(&self) -> bool {
+        self.text.trim().is_empty()
+    }
+
+    /// 将原文归档到目录，文件名为`settlement_<投资者>_<交易日>.txt`
+    pub fn archive(&self, dir: impl AsRef<Path>) -> CtpResult<PathBuf> {
+        let dir = dir.as_ref();
+        fs::create_dir_all(dir).map_err(|e| {
+            CtpError::InvalidPath(format!("无法创建结算单目录 {}: {}", dir.display(), e))
+        })?;
+
+        let path = dir.join(format!(
+            "settlement_{}_{}.txt",
+            self.investor_id, self.trading_day
+        ));
+        fs::write(&path, &self.text).map_err(|e| {
+            CtpError::InvalidPath(format!("无法写入结算单 {}: {}", path.display(), e))
+        })?;
+        Ok(path)
+    }
+}
+
+fn is_separator(line: &str) -> bool {
+    line.len() >= 3 && line.chars().all(|c| matches!(c, '-' | '=' | '+'))
+}
+
+fn split_row(line: &str) -> Vec<String> {
+    let inner = line.trim_matches('|');
+    inner
+        .split('|')
+        .map(|cell| cell.trim().to_string())
+        .collect()
+}
+
+// 解析一行中的多个“标签：值”对
+fn parse_pairs(line: &str) -> Vec<(String, String)> {
+    let mut pairs = Vec::new();
+    let mut parts = line.split(['：', ':']);
+    let mut label = parts.next().unwrap_or_default().trim().to_string();
+
+    for part in parts {
+        let part = part.trim();
+        if part.is_empty() {
+            continue;
+        }
+        let (value, rest) = part.split_once(char::is_whitespace).unwrap_or((part, ""));
+        if !label.is_empty() {
+            pairs.push((label, value.to_string()));
+        }
+        label = rest.trim().to_string();
+    }
+    pairs
+}
+
+// 标签形如“期初结存 Balance b/f”，按中文（忽略空格）或英文部分匹配
+fn key_matches(key: &str, label: &str) -> bool {
+    let compact: String = key.chars().filter(|c| !c.is_whitespace()).collect();
+    let label_compact: String = label.chars().filter(|c| !c.is_whitespace()).collect();
+    compact.starts_with(&label_compact)
+        || key
+            .to_ascii_lowercase()
+            .contains(&label.to_ascii_lowercase())
+}
+
+fn parse_amount(value: &str) -> Option<f64> {
+    let cleaned: String = value.chars().filter(|&c| c != ',').collect();
+    cleaned.trim().parse().ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use encoding::all::GB18030;
+    use encoding::{EncoderTrap, Encoding};
+
+    const SAMPLE: &str = "\
+                              交易结算单(盯市) Settlement Statement(MTM)
+客户号 Client ID：  00001          客户名称 Client Name：张三
+日期 Date：20250102
+
+                   资金状况  币种：人民币  Account Summary  Currency：CNY
+----------------------------------------------------------------------
+期初结存 Balance b/f：            100000.00  基础保证金 Initial Margin：     0.00
+出 入 金 Deposit/Withdrawal：      5,000.00  期末结存 Balance c/f：     104985.00
+手 续 费 Commission：                 15.00  客户权益 Client Equity：：  104985.00
+----------------------------------------------------------------------
+
+                         出入金明细 Deposit/Withdrawal
+----------------------------------------------------------------------
+|发生日期|       出入金类型       |      入金      |      出金      |     说明     |
+|  Date  |          Type          |    Deposit     |   Withdrawal   |     Note     |
+----------------------------------------------------------------------
+|20250102|银期转账                |         5000.00|            0.00|              |
+----------------------------------------------------------------------
+|共   1条|                        |         5000.00|            0.00|              |
+----------------------------------------------------------------------
+
+                         成交记录 Transaction Record
+----------------------------------------------------------------------
+|成交日期| 交易所 |  品种  |  合约  |买/卖|  成交价  | 手数 |       开平       |  手续费  |
+|  Date  |Exchange|Product |Instrument| B/S |   Price  | Lots |       O/C        |   Fee    |
+----------------------------------------------------------------------
+|20250102|上期所  |螺纹钢  | rb2505 |买   |  3300.000|     2|开                |     10.00|
+|20250102|上期所  |螺纹钢  | rb2505 |卖   |  3310.000|     1|平                |      5.00|
+----------------------------------------------------------------------
+|共   2条|        |        |        |     |          |     3|                  |     15.00|
+----------------------------------------------------------------------
+
+                         持仓明细 Positions Detail
+----------------------------------------------------------------------
+| 交易所 |  品种  |  合约  |开仓日期|买/卖| 持仓量 |
+|Exchange|Product |Instrument|Open Date| B/S | Positon|
+----------------------------------------------------------------------
+|上期所  |螺纹钢  | rb2505 |20250102|买   |       1|
+----------------------------------------------------------------------
+|共   1条|        |        |        |     |       1|
+----------------------------------------------------------------------
+";
+
+    fn fragments(text: &str, size: usize) -> Vec<SettlementInfoField> {
+        let bytes = GB18030.encode(text, EncoderTrap::Strict).unwrap();
+        bytes
+            .chunks(size)
+            .enumerate()
+            .map(|(i, chunk)| {
+                let mut info = SettlementInfoField {
+                    sequence_no: i as i32 + 1,
+                    trading_day: *b"20250102\0",
+                    investor_id: "00001".parse().unwrap(),
+                    ..Default::default()
+                };
+                info.content[..chunk.len()].copy_from_slice(chunk);
+                info
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_assemble_split_characters() {
+        // 奇数长度分片必然把某些汉字拆成两半
+        let mut parts = fragments(SAMPLE, 37);
+        parts.swap(0, 1);
+
+        let mut assembler = SettlementAssembler::new();
+        for part in &parts {
+            assembler.push(part);
+        }
+        let statement = assembler.finish().unwrap();
+
+        assert_eq!(statement.text, SAMPLE);
+        assert_eq!(statement.trading_day, "20250102");
+        assert_eq!(statement.investor_id, "00001");
+    }
+
+    #[test]
+    fn test_parse_sections() {
+        let statement = SettlementStatement::parse(SAMPLE);
+
+        assert_eq!(
+            statement.header.first(),
+            Some(&("客户号 Client ID".to_string(), "00001".to_string()))
+        );
+        assert_eq!(statement.summary_amount("期初结存"), Some(100000.0));
+        assert_eq!(statement.summary_amount("Deposit/Withdrawal"), Some(5000.0));
+        assert_eq!(statement.summary_amount("客户权益"), Some(104985.0));
+
+        assert_eq!(statement.deposits.len(), 1);
+        assert_eq!(statement.deposits.get_f64(0, "Deposit"), Some(5000.0));
+
+        assert_eq!(statement.trades.len(), 2);
+        assert_eq!(statement.trades.get(1, "买/卖"), Some("卖"));
+        assert_eq!(statement.trades.get_f64(0, "Price"), Some(3300.0));
+        assert_eq!(
+            statement.trades.totals.as_ref().map(|t| t[0].as_str()),
+            Some("共   2条")
+        );
+        assert_eq!(statement.commissions.get("rb2505"), Some(&15.0));
+
+        assert!(statement.closed_positions.is_empty());
+        assert_eq!(statement.open_positions.get(0, "合约"), Some("rb2505"));
+    }
+
+    #[test]
+    fn test_archive() {
+        let dir = std::env::temp_dir().join(format!("ctp_settlement_{}", std::process::id()));
+        let statement = SettlementStatement {
+            trading_day: "20250102".to_string(),
+            investor_id: "00001".to_string(),
+            text: SAMPLE.to_string(),
+            ..Default::default()
+        };
+
+        let path = statement.archive(&dir).unwrap();
+        assert_eq!(fs::read_to_string(&path).unwrap(), SAMPLE);
+        let _ = fs::remove_dir_all(&dir);
+    }
+}
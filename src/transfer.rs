@@ -0,0 +1,291 @@
+//! 银期转账
+//!
+//! 期货端发起的银期转账是一个多阶段过程：请求先得到前置的应答，随后由银行处理，
+//! 处理完成后通过`OnRtnFromBankToFutureByFuture`/`OnRtnFromFutureToBankByFuture`
+//! 回报结果；银行超时未应答时系统自动冲正，通过`OnRtnRepeal*`回报。本模块提供
+//! 转帐方向、转帐结果以及基于转帐流水的历史记录汇总。
+
+use crate::models::TransferSerial;
+use crate::types::{RspRepealField, RspTransferField, TransferSerialField};
+use std::collections::BTreeMap;
+use time::Date;
+
+/// 转帐方向
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum TransferDirection {
+    /// 银行资金转期货（入金）
+    BankToFuture,
+    /// 期货资金转银行（出金）
+    FutureToBank,
+}
+
+impl TransferDirection {
+    /// 期货端发起时的业务功能码
+    pub fn trade_code(self) -> &'static str {
+        match self {
+            Self::BankToFuture => "202001",
+            Self::FutureToBank => "202002",
+        }
+    }
+
+    /// 根据业务功能码识别转帐方向，末尾的空字节会被忽略
+    pub fn from_trade_code(code: impl AsRef<[u8]>) -> Option<Self> {
+        let code = code.as_ref();
+        let end = code.iter().position(|&b| b == 0).unwrap_or(code.len());
+        match &code[..end] {
+            // 银行发起的转帐使用102001/102002，方向相同
+            b"202001" | b"102001" => Some(Self::BankToFuture),
+            b"202002" | b"102002" => Some(Self::FutureToBank),
+            _ => None,
+        }
+    }
+}
+
+/// 转帐结果
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum TransferOutcome {
+    /// 银行处理完成，资金已划转
+    Completed(RspTransferField),
+    /// 转帐被冲正，资金未划转
+    Repealed(RspRepealField),
+}
+
+impl TransferOutcome {
+    /// 资金是否已划转
+    pub fn is_completed(&self) -> bool {
+        matches!(self, Self::Completed(_))
+    }
+
+    /// 转帐金额
+    pub fn amount(&self) -> f64 {
+        match self {
+            Self::Completed(rsp) => rsp.trade_amount,
+            Self::Repealed(rsp) => rsp.trade_amount,
+        }
+    }
+
+    /// 期货公司流水号
+    pub fn future_serial(&self) -> i32 {
+        match self {
+            Self::Completed(rsp) => rsp.future_serial,
+            Self::Repealed(rsp) => rsp.future_serial,
+        }
+    }
+
+    /// 银期平台消息流水号
+    pub fn plate_serial(&self) -> i32 {
+        match self {
+            Self::Completed(rsp) => rsp.plate_serial,
+            Self::Repealed(rsp) => rsp.plate_serial,
+        }
+    }
+}
+
+/// 转帐历史
+///
+/// 按交易日、交易时间和平台流水号排序，汇总时只统计有效流水
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TransferHistory {
+    records: Vec<TransferSerial>,
+}
+
+impl TransferHistory {
+    /// 创建空的转帐历史
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 添加一条流水，同一交易日重复的平台流水号会覆盖旧记录
+    pub fn push(&mut self, record: TransferSerial) {
+        // 更正记录的交易时间可能变化，先移除旧记录再按新位置插入
+        if let Some(pos) = self.records.iter().position(|r| {
+            r.trading_day == record.trading_day && r.plate_serial == record.plate_serial
+        }) {
+            self.records.remove(pos);
+        }
+        let key = Self::sort_key(&record);
+        let pos = self.records.partition_point(|r| Self::sort_key(r) <= key);
+        self.records.insert(pos, record);
+    }
+
+    /// 添加一条原始流水
+    pub fn push_field(&mut self, field: &TransferSerialField) {
+        self.push(TransferSerial::from(field));
+    }
+
+    // 交易日和平台流水号唯一确定一条流水
+    fn sort_key(record: &TransferSerial) -> (Option<Date>, Option<time::Time>, i32) {
+        (record.trading_day, record.trade_time, record.plate_serial)
+    }
+
+    /// 全部流水
+    pub fn records(&self) -> &[TransferSerial] {
+        &self.records
+    }
+
+    /// 流水数量
+    pub fn len(&self) -> usize {
+        self.records.len()
+    }
+
+    /// 是否为空
+    pub fn is_empty(&self) -> bool {
+        self.records.is_empty()
+    }
+
+    /// 有效流水
+    pub fn valid(&self) -> impl Iterator<Item = &TransferSerial> {
+        self.records.iter().filter(|r| r.is_valid())
+    }
+
+    /// 指定交易日的流水
+    pub fn on(&self, trading_day: Date) -> impl Iterator<Item = &TransferSerial> {
+        self.records
+            .iter()
+            .filter(move |r| r.trading_day == Some(trading_day))
+    }
+
+    /// 有效入金总额
+    pub fn total_deposit(&self) -> f64 {
+        self.total(TransferDirection::BankToFuture)
+    }
+
+    /// 有效出金总额
+    pub fn total_withdrawal(&self) -> f64 {
+        self.total(TransferDirection::FutureToBank)
+    }
+
+    fn total(&self, direction: TransferDirection) -> f64 {
+        self.valid()
+            .filter(|r| r.direction() == Some(direction))
+            .map(|r| r.trade_amount)
+            .sum()
+    }
+
+    /// 净入金（入金减出金）
+    pub fn net_amount(&self) -> f64 {
+        self.valid().map(TransferSerial::signed_amount).sum()
+    }
+
+    /// 有效流水的客户费用合计
+    pub fn total_fees(&self) -> f64 {
+        self.valid().map(|r| r.cust_fee).sum()
+    }
+
+    /// 按交易日汇总的净入金
+    pub fn net_by_day(&self) -> BTreeMap<Date, f64> {
+        let mut result = BTreeMap::new();
+        for record in self.valid() {
+            if let Some(day) = record.trading_day {
+                *result.entry(day).or_insert(0.0) += record.signed_amount();
+            }
+        }
+        result
+    }
+}
+
+impl FromIterator<TransferSerial> for TransferHistory {
+    fn from_iter<I: IntoIterator<Item = TransferSerial>>(iter: I) -> Self {
+        let mut history = Self::new();
+        history.extend(iter);
+        history
+    }
+}
+
+impl Extend<TransferSerial> for TransferHistory {
+    fn extend<I: IntoIterator<Item = TransferSerial>>(&mut self, iter: I) {
+        for record in iter {
+            self.push(record);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{parse_date, AvailabilityFlag};
+    use crate::types::{ReqTransferField, StringConvert};
+    use time::Time;
+
+    fn serial(plate_serial: i32, day: &str, code: &str, amount: f64, flag: u8) -> TransferSerial {
+        let field = TransferSerialField {
+            plate_serial,
+            trading_day: <[u8; 9]>::from_utf8_string(day).unwrap(),
+            trade_time: <[u8; 9]>::from_utf8_string("10:00:00").unwrap(),
+            trade_code: <[u8; 7]>::from_utf8_string(code).unwrap(),
+            trade_amount: amount,
+            availability_flag: flag,
+            ..Default::default()
+        };
+        TransferSerial::from(&field)
+    }
+
+    fn day(s: &str) -> Date {
+        parse_date(s).unwrap()
+    }
+
+    #[test]
+    fn test_direction_from_trade_code() {
+        let req = ReqTransferField::bank_to_future("9999", "00001", "1", 1000.0).unwrap();
+        assert_eq!(req.direction(), Some(TransferDirection::BankToFuture));
+        assert_eq!(req.bank_branch_id, "0000");
+
+        let req = ReqTransferField::future_to_bank("9999", "00001", "1", 500.0).unwrap();
+        assert_eq!(req.direction(), Some(TransferDirection::FutureToBank));
+
+        assert!(ReqTransferField::bank_to_future("9999", "00001", "1", 0.0).is_err());
+        assert!(ReqTransferField::bank_to_future("9999", "00001", "1", f64::NAN).is_err());
+        assert_eq!(TransferDirection::from_trade_code("204002"), None);
+    }
+
+    #[test]
+    fn test_history_totals() {
+        let history: TransferHistory = vec![
+            serial(3, "20250102", "202002", 300.0, b'1'),
+            serial(1, "20250101", "202001", 1000.0, b'1'),
+            serial(2, "20250101", "202001", 200.0, b'2'),
+            serial(4, "20250102", "102001", 50.0, b'1'),
+        ]
+        .into_iter()
+        .collect();
+
+        assert_eq!(history.len(), 4);
+        assert_eq!(history.records()[0].plate_serial, 1);
+        assert_eq!(history.records()[2].plate_serial, 3);
+        assert_eq!(
+            history.records()[1].availability_flag,
+            AvailabilityFlag::Repeal
+        );
+
+        assert_eq!(history.total_deposit(), 1050.0);
+        assert_eq!(history.total_withdrawal(), 300.0);
+        assert_eq!(history.net_amount(), 750.0);
+        assert_eq!(history.on(day("20250101")).count(), 2);
+
+        let by_day = history.net_by_day();
+        assert_eq!(by_day[&day("20250101")], 1000.0);
+        assert_eq!(by_day[&day("20250102")], -250.0);
+    }
+
+    #[test]
+    fn test_duplicate_serial_is_replaced() {
+        let mut history = TransferHistory::new();
+        history.push(serial(1, "20250101", "202001", 1000.0, b'0'));
+        history.push(serial(1, "20250101", "202001", 1000.0, b'1'));
+        assert_eq!(history.len(), 1);
+        assert!(history.records()[0].is_valid());
+
+        // 发起方日期不同的更正记录仍是同一笔流水
+        history.push(serial(2, "20250101", "202001", 500.0, b'1'));
+        let mut correction = serial(1, "20250101", "202001", 1000.0, b'2');
+        correction.trade_date = Some(day("20241231"));
+        correction.trade_time = Time::from_hms(11, 0, 0).ok();
+        history.push(correction);
+        assert_eq!(history.len(), 2);
+        assert_eq!(history.records()[0].plate_serial, 2);
+        assert!(!history.records()[1].is_valid());
+    }
+}
@@ -3,16 +3,18 @@
 //! 对应CTP C++库中的数据类型，提供安全的Rust封装
 
 use crate::encoding::GbkConverter;
-use crate::error::CtpResult;
+use crate::error::{CtpError, CtpResult};
+pub use crate::fixed_str::{FixedStr, SecretStr};
+use crate::transfer::TransferDirection;
 
 /// 交易员代码类型 (21字符)
-pub type TraderIdType = [u8; 21];
+pub type TraderIdType = FixedStr<21>;
 
 /// 投资者代码类型 (13字符)
-pub type InvestorIdType = [u8; 13];
+pub type InvestorIdType = FixedStr<13>;
 
 /// 经纪公司代码类型 (11字符)
-pub type BrokerIdType = [u8; 11];
+pub type BrokerIdType = FixedStr<11>;
 
 /// 经纪公司简称类型 (9字符)
 pub type BrokerAbbrType = [u8; 9];
@@ -21,31 +23,31 @@ pub type BrokerAbbrType = [u8; 9];
 pub type BrokerNameType = [u8; 81];
 
 /// 合约代码类型 (31字符)
-pub type InstrumentIdType = [u8; 31];
+pub type InstrumentIdType = FixedStr<31>;
 
-/// 密码类型 (41字符)
-pub type PasswordType = [u8; 41];
+/// 密码类型 (41字符)，释放时清零
+pub type PasswordType = SecretStr<41>;
 
 /// 用户代码类型 (16字符)
-pub type UserIdType = [u8; 16];
+pub type UserIdType = FixedStr<16>;
 
 /// 产品信息类型 (11字符)
 pub type ProductInfoType = [u8; 11];
 
 /// 币种代码类型 (4字符)
-pub type CurrencyIdType = [u8; 4];
+pub type CurrencyIdType = FixedStr<4>;
 
 /// 业务类型 (1字符)
 pub type BizTypeType = [u8; 1];
 
 /// 投资者账户代码类型 (13字符)
-pub type AccountIdType = [u8; 13];
+pub type AccountIdType = FixedStr<13>;
 
 /// 交易所代码类型 (9字符)
-pub type ExchangeIdType = [u8; 9];
+pub type ExchangeIdType = FixedStr<9>;
 
 /// 投资单元代码类型 (17字符)
-pub type InvestUnitIdType = [u8; 17];
+pub type InvestUnitIdType = FixedStr<17>;
 
 /// 协议信息类型 (11字符)
 pub type ProtocolInfoType = [u8; 11];
@@ -53,11 +55,11 @@ pub type ProtocolInfoType = [u8; 11];
 /// Mac地址类型 (21字符)
 pub type MacAddressType = [u8; 21];
 
-/// 认证码类型 (17字符)
-pub type AuthCodeType = [u8; 17];
+/// 认证码类型 (17字符)，释放时清零
+pub type AuthCodeType = SecretStr<17>;
 
 /// 应用单元代码类型 (21字符)
-pub type AppIdType = [u8; 21];
+pub type AppIdType = FixedStr<21>;
 
 /// 客户端IP地址类型 (16字符)
 pub type IpAddressType = [u8; 16];
@@ -68,19 +70,19 @@ pub type IpPortType = [u8; 6];
 /// 第一阶段新增类型定义
 
 /// 报单编号类型 (21字符)
-pub type OrderSysIdType = [u8; 21];
+pub type OrderSysIdType = FixedStr<21>;
 
 /// 时间类型 (9字符)
 pub type TimeType = [u8; 9];
 
 /// 成交编号类型 (21字符)
-pub type TradeIdType = [u8; 21];
+pub type TradeIdType = FixedStr<21>;
 
 /// 合约在交易所的代码类型 (31字符)
-pub type ExchangeInstIdType = [u8; 31];
+pub type ExchangeInstIdType = FixedStr<31>;
 
-/// 报单操作引用类型 (13字符)
-pub type OrderActionRefType = [u8; 13];
+/// 报单操作引用类型
+pub type OrderActionRefType = i32;
 
 /// 操作标志类型
 pub type ActionFlagType = u8;
@@ -103,17 +105,43 @@ pub type SessionIdType = i32;
 /// 业务单元类型
 pub type BusinessUnitType = [u8; 21];
 /// 客户代码类型
-pub type ClientIdType = [u8; 11];
+pub type ClientIdType = FixedStr<11>;
 /// 预埋单编号类型
-pub type ParkedOrderIdType = [u8; 13];
+pub type ParkedOrderIdType = FixedStr<13>;
 /// 交易日类型
 pub type TradingDayType = [u8; 9];
 /// 银行代码类型
-pub type BankIdType = [u8; 4];
+pub type BankIdType = FixedStr<4>;
 /// 银行分支机构代码类型
-pub type BankBrchIdType = [u8; 5];
+pub type BankBrchIdType = FixedStr<5>;
 /// 银行名称类型
 pub type BankNameType = [u8; 101];
+/// 银行帐号类型
+pub type BankAccountType = [u8; 41];
+/// 银行流水号类型
+pub type BankSerialType = [u8; 13];
+/// 业务功能码类型
+pub type TradeCodeType = [u8; 7];
+/// 期商分支机构代码类型
+pub type FutureBranchIdType = FixedStr<31>;
+/// 客户姓名类型
+pub type IndividualNameType = [u8; 51];
+/// 长客户姓名类型
+pub type LongIndividualNameType = [u8; 161];
+/// 证件号码类型
+pub type IdentifiedCardNoType = [u8; 51];
+/// 附加信息类型
+pub type AddInfoType = [u8; 129];
+/// 摘要类型
+pub type DigestType = [u8; 36];
+/// 渠道标志类型
+pub type DeviceIdType = FixedStr<3>;
+/// 期货公司银行编码类型
+pub type BankCodingForFutureType = [u8; 33];
+/// 交易柜员类型
+pub type OperNoType = [u8; 17];
+/// 操作员类型
+pub type OperatorCodeType = [u8; 17];
 
 /// 价格类型
 pub type PriceType = f64;
@@ -125,10 +153,10 @@ pub type DateType = [u8; 9];
 pub type InstallIdType = i32;
 
 /// 本地报单编号类型 (13字符)
-pub type OrderLocalIdType = [u8; 13];
+pub type OrderLocalIdType = FixedStr<13>;
 
 /// 会员代码类型 (11字符)
-pub type ParticipantIdType = [u8; 11];
+pub type ParticipantIdType = FixedStr<11>;
 
 /// 报单操作状态类型
 pub type OrderActionStatusType = u8;
@@ -137,7 +165,28 @@ pub type OrderActionStatusType = u8;
 pub type ErrorMsgType = [u8; 81];
 
 /// 营业部编号类型 (9字符)
-pub type BranchIdType = [u8; 9];
+pub type BranchIdType = FixedStr<9>;
+
+/// 登录备注类型 (36字符)
+pub type LoginRemarkType = [u8; 36];
+
+/// 终端IP地址类型 (33字符)
+pub type ClientIpAddressType = [u8; 33];
+
+/// 图形验证码图片信息类型 (2561字符)
+pub type CaptchaInfoType = [u8; 2561];
+
+/// 用户端系统内部信息类型 (273字节)
+pub type ClientSystemInfoType = [u8; 273];
+
+/// 终端App代码类型 (33字符)
+pub type ClientAppIdType = FixedStr<33>;
+
+/// 客户登录备注类型 (151字符)
+pub type ClientLoginRemarkType = [u8; 151];
+
+/// 前置地址类型 (101字符)
+pub type AddressType = [u8; 101];
 
 /// 数量类型
 pub type VolumeType = i32;
@@ -145,6 +194,7 @@ pub type VolumeType = i32;
 /// 恢复类型枚举
 #[repr(C)]
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum ResumeType {
     /// 重新开始
     Restart = 0,
@@ -190,46 +240,91 @@ macro_rules! impl_string_convert {
 }
 
 // 为所有固定长度类型实现字符串转换
-impl_string_convert!([u8; 21], 21); // TraderIdType, MacAddressType, AppIdType, OrderSysIdType, TradeIdType
-impl_string_convert!([u8; 13], 13); // InvestorIdType, AccountIdType, OrderActionRefType, OrderRefType
-impl_string_convert!([u8; 11], 11); // BrokerIdType
-impl_string_convert!([u8; 9], 9); // BrokerAbbrType, ExchangeIdType, TimeType
+impl_string_convert!([u8; 21], 21); // MacAddressType, BusinessUnitType
+impl_string_convert!([u8; 13], 13); // OrderRefType
+impl_string_convert!([u8; 11], 11); // ProductInfoType, ProtocolInfoType
+impl_string_convert!([u8; 9], 9); // BrokerAbbrType, TimeType, DateType
 impl_string_convert!([u8; 81], 81); // BrokerNameType
-impl_string_convert!([u8; 31], 31); // InstrumentIdType, ExchangeInstIdType
-impl_string_convert!([u8; 41], 41); // PasswordType
-impl_string_convert!([u8; 17], 17); // AuthCodeType, InvestUnitIdType
-impl_string_convert!([u8; 16], 16); // UserIdType, IpAddressType, IPAddressType
+impl_string_convert!([u8; 31], 31);
+impl_string_convert!([u8; 41], 41);
+impl_string_convert!([u8; 17], 17);
+impl_string_convert!([u8; 16], 16); // IpAddressType, IPAddressType
 impl_string_convert!([u8; 6], 6); // IpPortType
-impl_string_convert!([u8; 4], 4); // CurrencyIdType
+impl_string_convert!([u8; 4], 4);
 impl_string_convert!([u8; 1], 1); // BizTypeType
+impl_string_convert!([u8; 7], 7); // TradeCodeType
+impl_string_convert!([u8; 51], 51); // IndividualNameType, IdentifiedCardNoType
+impl_string_convert!([u8; 161], 161); // LongIndividualNameType
+impl_string_convert!([u8; 129], 129); // AddInfoType
+impl_string_convert!([u8; 36], 36); // DigestType
+impl_string_convert!([u8; 33], 33); // BankCodingForFutureType
+
+// 定长字符串构造时检查长度，超长返回错误
+impl<const N: usize> StringConvert for FixedStr<N> {
+    fn to_utf8_string(&self) -> CtpResult<String> {
+        GbkConverter::gb18030_to_utf8(self.as_bytes())
+    }
+
+    fn from_utf8_string(s: &str) -> CtpResult<Self> {
+        s.parse()
+    }
+}
+
+// 敏感字段转换出的明文由调用方负责处理，超长时错误信息中不带出内容
+impl<const N: usize> StringConvert for SecretStr<N> {
+    fn to_utf8_string(&self) -> CtpResult<String> {
+        GbkConverter::gb18030_to_utf8(self.expose_secret())
+    }
+
+    fn from_utf8_string(s: &str) -> CtpResult<Self> {
+        s.parse()
+    }
+}
 
 /// 用户登录请求
 #[repr(C)]
 #[derive(Debug, Clone)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(default)
+)]
 pub struct ReqUserLoginField {
     /// 交易日
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
     pub trading_day: [u8; 9],
     /// 经纪公司代码
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
     pub broker_id: BrokerIdType,
     /// 用户代码
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
     pub user_id: UserIdType,
     /// 密码
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
     pub password: PasswordType,
     /// 用户端产品信息
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
     pub user_product_info: ProductInfoType,
     /// 接口端产品信息
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
     pub interface_product_info: ProductInfoType,
     /// 协议信息
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
     pub protocol_info: ProtocolInfoType,
     /// Mac地址
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
     pub mac_address: MacAddressType,
     /// 动态密码
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
     pub one_time_password: PasswordType,
     /// 客户端IP地址
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
     pub client_ip_address: IpAddressType,
     /// 客户端IP端口
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
     pub client_ip_port: IpPortType,
     /// 登录备注
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
     pub login_remark: [u8; 36],
 }
 
@@ -237,14 +332,14 @@ impl Default for ReqUserLoginField {
     fn default() -> Self {
         Self {
             trading_day: [0; 9],
-            broker_id: [0; 11],
-            user_id: [0; 16],
-            password: [0; 41],
+            broker_id: FixedStr::new(),
+            user_id: FixedStr::new(),
+            password: SecretStr::new(),
             user_product_info: [0; 11],
             interface_product_info: [0; 11],
             protocol_info: [0; 11],
             mac_address: [0; 21],
-            one_time_password: [0; 41],
+            one_time_password: SecretStr::new(),
             client_ip_address: [0; 16],
             client_ip_port: [0; 6],
             login_remark: [0; 36],
@@ -289,37 +384,71 @@ impl ReqUserLoginField {
         self.client_ip_port = IpPortType::from_utf8_string(port)?;
         Ok(self)
     }
+
+    // 验证码登录请求的终端地址字段更长，端口为整数
+    fn client_ip(&self) -> CtpResult<(ClientIpAddressType, i32)> {
+        let mut address = [0u8; 33];
+        let ip = self.client_ip_address.as_slice();
+        let end = ip.iter().position(|&b| b == 0).unwrap_or(ip.len());
+        address[..end].copy_from_slice(&ip[..end]);
+        let port = self.client_ip_port.to_utf8_string()?;
+        let port = port.trim_end_matches('\0').trim();
+        let port = if port.is_empty() {
+            0
+        } else {
+            port.parse().map_err(|_| {
+                CtpError::InvalidParameterError(format!("无效的客户端端口: {}", port))
+            })?
+        };
+        Ok((address, port))
+    }
 }
 
 /// 用户登录响应
 #[repr(C)]
 #[derive(Debug, Clone)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(default)
+)]
 pub struct RspUserLoginField {
     /// 交易日
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
     pub trading_day: [u8; 9],
     /// 登录成功时间
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
     pub login_time: [u8; 9],
     /// 经纪公司代码
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
     pub broker_id: BrokerIdType,
     /// 用户代码
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
     pub user_id: UserIdType,
     /// 交易系统名称
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
     pub system_name: [u8; 41],
     /// 前置编号
     pub front_id: i32,
     /// 会话编号
     pub session_id: i32,
     /// 最大报单引用
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
     pub max_order_ref: [u8; 13],
     /// 上期所时间
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
     pub shfe_time: [u8; 9],
     /// 大商所时间
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
     pub dce_time: [u8; 9],
     /// 郑商所时间
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
     pub czce_time: [u8; 9],
     /// 中金所时间
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
     pub ffex_time: [u8; 9],
     /// 能源中心时间
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
     pub ine_time: [u8; 9],
 }
 
@@ -328,8 +457,8 @@ impl Default for RspUserLoginField {
         Self {
             trading_day: [0; 9],
             login_time: [0; 9],
-            broker_id: [0; 11],
-            user_id: [0; 16],
+            broker_id: FixedStr::new(),
+            user_id: FixedStr::new(),
             system_name: [0; 41],
             front_id: 0,
             session_id: 0,
@@ -346,10 +475,16 @@ impl Default for RspUserLoginField {
 /// 响应信息
 #[repr(C)]
 #[derive(Debug, Clone)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(default)
+)]
 pub struct RspInfoField {
     /// 错误代码
     pub error_id: i32,
     /// 错误信息
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
     pub error_msg: [u8; 81],
 }
 
@@ -372,330 +507,1627 @@ impl RspInfoField {
     pub fn is_success(&self) -> bool {
         self.error_id == 0
     }
-}
-
-#[cfg(test)]
-mod tests {
-    use super::*;
 
-    #[test]
-    fn test_string_convert() {
-        let test_str = "测试用户";
-        let user_id = UserIdType::from_utf8_string(test_str).unwrap();
-        let converted = user_id.to_utf8_string().unwrap();
-        assert_eq!(converted.trim_end_matches('\0'), test_str);
+    /// 错误代码
+    pub fn error_code(&self) -> crate::error::CtpErrorCode {
+        crate::error::CtpErrorCode::from_code(self.error_id)
     }
+}
 
-    #[test]
-    fn test_login_request_creation() {
-        let req = ReqUserLoginField::new("9999", "investor1", "123456").unwrap();
+/// 用户口令变更
+#[repr(C)]
+#[derive(Debug, Clone)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(default)
+)]
+pub struct UserPasswordUpdateField {
+    /// 经纪公司代码
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
+    pub broker_id: BrokerIdType,
+    /// 用户代码
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
+    pub user_id: UserIdType,
+    /// 原来的口令
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
+    pub old_password: PasswordType,
+    /// 新的口令
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
+    pub new_password: PasswordType,
+}
 
-        assert_eq!(
-            req.broker_id
-                .to_utf8_string()
-                .unwrap()
-                .trim_end_matches('\0'),
-            "9999"
-        );
-        assert_eq!(
-            req.user_id.to_utf8_string().unwrap().trim_end_matches('\0'),
-            "investor1"
-        );
-        assert_eq!(
-            req.password
-                .to_utf8_string()
-                .unwrap()
-                .trim_end_matches('\0'),
-            "123456"
-        );
+impl Default for UserPasswordUpdateField {
+    fn default() -> Self {
+        unsafe { std::mem::zeroed() }
     }
+}
 
-    #[test]
-    fn test_rsp_info_success() {
-        let mut rsp = RspInfoField::default();
-        assert!(rsp.is_success());
-
-        rsp.error_id = -1;
-        assert!(!rsp.is_success());
+impl UserPasswordUpdateField {
+    /// 创建用户口令变更请求
+    ///
+    /// 口令按GB18030编码，超长时返回错误而不是截断；新口令不能为空，也不能与原口令相同
+    pub fn new(
+        broker_id: &str,
+        user_id: &str,
+        old_password: &str,
+        new_password: &str,
+    ) -> CtpResult<Self> {
+        let (old_password, new_password) = encode_password_pair(old_password, new_password)?;
+        Ok(Self {
+            broker_id: BrokerIdType::from_utf8_string(broker_id)?,
+            user_id: UserIdType::from_utf8_string(user_id)?,
+            old_password,
+            new_password,
+        })
     }
 }
 
-// 查询资金账户字段
+/// 资金账户口令变更
 #[repr(C)]
 #[derive(Debug, Clone)]
-pub struct QryTradingAccountField {
-    // 经纪公司代码
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(default)
+)]
+pub struct TradingAccountPasswordUpdateField {
+    /// 经纪公司代码
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
     pub broker_id: BrokerIdType,
-    // 投资者代码
-    pub investor_id: InvestorIdType,
-    // 币种代码
-    pub currency_id: CurrencyIdType,
-    // 业务类型
-    pub biz_type: BizTypeType,
-    // 投资者账户代码
+    /// 投资者帐号
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
     pub account_id: AccountIdType,
+    /// 原来的口令
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
+    pub old_password: PasswordType,
+    /// 新的口令
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
+    pub new_password: PasswordType,
+    /// 币种代码
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
+    pub currency_id: CurrencyIdType,
 }
 
-impl Default for QryTradingAccountField {
+impl Default for TradingAccountPasswordUpdateField {
     fn default() -> Self {
         unsafe { std::mem::zeroed() }
     }
 }
 
-impl QryTradingAccountField {
-    pub fn new(broker_id: &str, investor_id: &str) -> CtpResult<Self> {
-        let mut req = Self::default();
-        req.broker_id = BrokerIdType::from_utf8_string(broker_id)?;
-        req.investor_id = InvestorIdType::from_utf8_string(investor_id)?;
-        // 不设置币种代码，让CTP服务器使用默认值
-        Ok(req)
+impl TradingAccountPasswordUpdateField {
+    /// 创建资金账户口令变更请求，币种默认为人民币
+    ///
+    /// 口令的检查规则与[`UserPasswordUpdateField::new`]相同
+    pub fn new(
+        broker_id: &str,
+        account_id: &str,
+        old_password: &str,
+        new_password: &str,
+    ) -> CtpResult<Self> {
+        let (old_password, new_password) = encode_password_pair(old_password, new_password)?;
+        Ok(Self {
+            broker_id: BrokerIdType::from_utf8_string(broker_id)?,
+            account_id: AccountIdType::from_utf8_string(account_id)?,
+            old_password,
+            new_password,
+            currency_id: CurrencyIdType::from_utf8_string("CNY")?,
+        })
     }
 
+    /// 设置币种
     pub fn with_currency(mut self, currency_id: &str) -> CtpResult<Self> {
         self.currency_id = CurrencyIdType::from_utf8_string(currency_id)?;
         Ok(self)
     }
 }
 
-// 查询投资者持仓字段
+/// 查询用户当前支持的认证模式
 #[repr(C)]
 #[derive(Debug, Clone)]
-pub struct QryInvestorPositionField {
-    // 经纪公司代码
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(default)
+)]
+pub struct ReqUserAuthMethodField {
+    /// 交易日
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
+    pub trading_day: DateType,
+    /// 经纪公司代码
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
     pub broker_id: BrokerIdType,
-    // 投资者代码
-    pub investor_id: InvestorIdType,
-    // 合约代码（为空表示查询所有）
-    pub instrument_id: InstrumentIdType,
-    // 交易所代码
-    pub exchange_id: ExchangeIdType,
-    // 投资单元代码
-    pub invest_unit_id: InvestUnitIdType,
+    /// 用户代码
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
+    pub user_id: UserIdType,
 }
 
-impl Default for QryInvestorPositionField {
+impl Default for ReqUserAuthMethodField {
     fn default() -> Self {
         unsafe { std::mem::zeroed() }
     }
 }
 
-impl QryInvestorPositionField {
-    pub fn new(broker_id: &str, investor_id: &str) -> CtpResult<Self> {
-        let mut req = Self::default();
-        req.broker_id = BrokerIdType::from_utf8_string(broker_id)?;
-        req.investor_id = InvestorIdType::from_utf8_string(investor_id)?;
-        Ok(req)
+impl ReqUserAuthMethodField {
+    pub fn new(broker_id: &str, user_id: &str) -> CtpResult<Self> {
+        Ok(Self {
+            broker_id: BrokerIdType::from_utf8_string(broker_id)?,
+            user_id: UserIdType::from_utf8_string(user_id)?,
+            ..Default::default()
+        })
     }
+}
 
-    pub fn with_instrument_id(mut self, instrument_id: &str) -> CtpResult<Self> {
-        self.instrument_id = InstrumentIdType::from_utf8_string(instrument_id)?;
-        Ok(self)
-    }
+/// 用户当前支持的认证模式
+#[repr(C)]
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(default)
+)]
+pub struct RspUserAuthMethodField {
+    /// 当前可以用的认证模式，0表示只需密码
+    pub usable_auth_method: i32,
 }
 
-// 第一阶段新增结构体
+impl RspUserAuthMethodField {
+    const AUTH_METHOD_CAPTCHA: i32 = 1;
+    const AUTH_METHOD_OTP: i32 = 1 << 1;
+    const AUTH_METHOD_TEXT: i32 = 1 << 2;
 
-// 查询报单字段
+    /// 是否需要额外验证
+    pub fn requires_verification(&self) -> bool {
+        self.usable_auth_method != 0
+    }
+
+    /// 是否支持图形验证码
+    pub fn supports_captcha(&self) -> bool {
+        self.usable_auth_method & Self::AUTH_METHOD_CAPTCHA != 0
+    }
+
+    /// 是否支持动态口令
+    pub fn supports_otp(&self) -> bool {
+        self.usable_auth_method & Self::AUTH_METHOD_OTP != 0
+    }
+
+    /// 是否支持短信验证码
+    pub fn supports_text(&self) -> bool {
+        self.usable_auth_method & Self::AUTH_METHOD_TEXT != 0
+    }
+}
+
+/// 获取图形验证码请求
 #[repr(C)]
 #[derive(Debug, Clone)]
-pub struct QryOrderField {
-    // 经纪公司代码
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(default)
+)]
+pub struct ReqGenUserCaptchaField {
+    /// 交易日
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
+    pub trading_day: DateType,
+    /// 经纪公司代码
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
     pub broker_id: BrokerIdType,
-    // 投资者代码
-    pub investor_id: InvestorIdType,
-    // 合约代码
-    pub instrument_id: InstrumentIdType,
-    // 交易所代码
-    pub exchange_id: ExchangeIdType,
-    // 报单编号
-    pub order_sys_id: OrderSysIdType,
-    // 开始时间
-    pub insert_time_start: TimeType,
-    // 结束时间
-    pub insert_time_end: TimeType,
-    // 投资单元代码
-    pub invest_unit_id: InvestUnitIdType,
+    /// 用户代码
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
+    pub user_id: UserIdType,
 }
 
-impl Default for QryOrderField {
+impl Default for ReqGenUserCaptchaField {
     fn default() -> Self {
         unsafe { std::mem::zeroed() }
     }
 }
 
-impl QryOrderField {
-    pub fn new(broker_id: &str, investor_id: &str) -> CtpResult<Self> {
-        let mut req = Self::default();
-        req.broker_id = BrokerIdType::from_utf8_string(broker_id)?;
-        req.investor_id = InvestorIdType::from_utf8_string(investor_id)?;
-        Ok(req)
-    }
-
-    pub fn with_instrument_id(mut self, instrument_id: &str) -> CtpResult<Self> {
-        self.instrument_id = InstrumentIdType::from_utf8_string(instrument_id)?;
-        Ok(self)
+impl ReqGenUserCaptchaField {
+    pub fn new(broker_id: &str, user_id: &str) -> CtpResult<Self> {
+        Ok(Self {
+            broker_id: BrokerIdType::from_utf8_string(broker_id)?,
+            user_id: UserIdType::from_utf8_string(user_id)?,
+            ..Default::default()
+        })
     }
 }
 
-// 查询成交字段
+/// 图形验证码
 #[repr(C)]
 #[derive(Debug, Clone)]
-pub struct QryTradeField {
-    // 经纪公司代码
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(default)
+)]
+pub struct RspGenUserCaptchaField {
+    /// 经纪公司代码
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
     pub broker_id: BrokerIdType,
-    // 投资者代码
-    pub investor_id: InvestorIdType,
-    // 合约代码
-    pub instrument_id: InstrumentIdType,
-    // 交易所代码
-    pub exchange_id: ExchangeIdType,
-    // 成交编号
-    pub trade_id: TradeIdType,
-    // 开始时间
-    pub trade_time_start: TimeType,
-    // 结束时间
-    pub trade_time_end: TimeType,
-    // 投资单元代码
-    pub invest_unit_id: InvestUnitIdType,
+    /// 用户代码
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
+    pub user_id: UserIdType,
+    /// 图片信息长度
+    pub captcha_info_len: i32,
+    /// 图片信息
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
+    pub captcha_info: CaptchaInfoType,
 }
 
-impl Default for QryTradeField {
+impl Default for RspGenUserCaptchaField {
     fn default() -> Self {
         unsafe { std::mem::zeroed() }
     }
 }
 
-impl QryTradeField {
-    pub fn new(broker_id: &str, investor_id: &str) -> CtpResult<Self> {
-        let mut req = Self::default();
-        req.broker_id = BrokerIdType::from_utf8_string(broker_id)?;
-        req.investor_id = InvestorIdType::from_utf8_string(investor_id)?;
-        Ok(req)
-    }
-
-    pub fn with_instrument_id(mut self, instrument_id: &str) -> CtpResult<Self> {
-        self.instrument_id = InstrumentIdType::from_utf8_string(instrument_id)?;
-        Ok(self)
+impl RspGenUserCaptchaField {
+    /// 图片数据，按`captcha_info_len`截取
+    pub fn captcha_image(&self) -> &[u8] {
+        let len = usize::try_from(self.captcha_info_len)
+            .unwrap_or(0)
+            .min(self.captcha_info.len());
+        &self.captcha_info[..len]
     }
 }
 
-// 查询合约字段
+/// 获取短信验证码请求
 #[repr(C)]
 #[derive(Debug, Clone)]
-pub struct QryInstrumentField {
-    // 合约代码
-    pub instrument_id: InstrumentIdType,
-    // 交易所代码
-    pub exchange_id: ExchangeIdType,
-    // 合约在交易所的代码
-    pub exchange_inst_id: ExchangeInstIdType,
-    // 产品代码
-    pub product_id: InstrumentIdType,
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(default)
+)]
+pub struct ReqGenUserTextField {
+    /// 交易日
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
+    pub trading_day: DateType,
+    /// 经纪公司代码
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
+    pub broker_id: BrokerIdType,
+    /// 用户代码
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
+    pub user_id: UserIdType,
 }
 
-impl Default for QryInstrumentField {
+impl Default for ReqGenUserTextField {
     fn default() -> Self {
         unsafe { std::mem::zeroed() }
     }
 }
 
-impl QryInstrumentField {
-    pub fn new() -> Self {
-        Self::default()
+impl ReqGenUserTextField {
+    pub fn new(broker_id: &str, user_id: &str) -> CtpResult<Self> {
+        Ok(Self {
+            broker_id: BrokerIdType::from_utf8_string(broker_id)?,
+            user_id: UserIdType::from_utf8_string(user_id)?,
+            ..Default::default()
+        })
     }
+}
 
-    pub fn with_instrument_id(mut self, instrument_id: &str) -> CtpResult<Self> {
-        self.instrument_id = InstrumentIdType::from_utf8_string(instrument_id)?;
-        Ok(self)
+/// 短信验证码
+#[repr(C)]
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(default)
+)]
+pub struct RspGenUserTextField {
+    /// 短信验证码序号
+    pub user_text_seq: i32,
+}
+
+/// 带图形验证码的登录请求
+#[repr(C)]
+#[derive(Debug, Clone)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(default)
+)]
+pub struct ReqUserLoginWithCaptchaField {
+    /// 交易日
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
+    pub trading_day: DateType,
+    /// 经纪公司代码
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
+    pub broker_id: BrokerIdType,
+    /// 用户代码
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
+    pub user_id: UserIdType,
+    /// 密码
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
+    pub password: PasswordType,
+    /// 用户端产品信息
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
+    pub user_product_info: ProductInfoType,
+    /// 接口端产品信息
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
+    pub interface_product_info: ProductInfoType,
+    /// 协议信息
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
+    pub protocol_info: ProtocolInfoType,
+    /// Mac地址
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
+    pub mac_address: MacAddressType,
+    /// 保留的无效字段
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
+    pub reserve1: IpAddressType,
+    /// 登录备注
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
+    pub login_remark: LoginRemarkType,
+    /// 图形验证码的文字内容
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
+    pub captcha: PasswordType,
+    /// 终端IP端口
+    pub client_ip_port: i32,
+    /// 终端IP地址
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
+    pub client_ip_address: ClientIpAddressType,
+}
+
+impl Default for ReqUserLoginWithCaptchaField {
+    fn default() -> Self {
+        unsafe { std::mem::zeroed() }
     }
+}
 
-    pub fn with_exchange_id(mut self, exchange_id: &str) -> CtpResult<Self> {
-        self.exchange_id = ExchangeIdType::from_utf8_string(exchange_id)?;
-        Ok(self)
+impl ReqUserLoginWithCaptchaField {
+    /// 以普通登录请求为模板创建，复制账户、密码、产品信息和终端信息
+    ///
+    /// 验证码按GB18030编码，超长时返回错误
+    pub fn from_login(login: &ReqUserLoginField, captcha: &str) -> CtpResult<Self> {
+        let (client_ip_address, client_ip_port) = login.client_ip()?;
+        Ok(Self {
+            trading_day: login.trading_day,
+            broker_id: login.broker_id,
+            user_id: login.user_id,
+            password: login.password.clone(),
+            user_product_info: login.user_product_info,
+            interface_product_info: login.interface_product_info,
+            protocol_info: login.protocol_info,
+            mac_address: login.mac_address,
+            login_remark: login.login_remark,
+            client_ip_address,
+            client_ip_port,
+            captcha: captcha.parse()?,
+            ..Default::default()
+        })
     }
 }
 
-// 报单操作字段（撤单使用）
+/// 带短信验证码的登录请求
 #[repr(C)]
 #[derive(Debug, Clone)]
-pub struct InputOrderActionField {
-    // 经纪公司代码
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(default)
+)]
+pub struct ReqUserLoginWithTextField {
+    /// 交易日
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
+    pub trading_day: DateType,
+    /// 经纪公司代码
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
+    pub broker_id: BrokerIdType,
+    /// 用户代码
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
+    pub user_id: UserIdType,
+    /// 密码
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
+    pub password: PasswordType,
+    /// 用户端产品信息
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
+    pub user_product_info: ProductInfoType,
+    /// 接口端产品信息
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
+    pub interface_product_info: ProductInfoType,
+    /// 协议信息
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
+    pub protocol_info: ProtocolInfoType,
+    /// Mac地址
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
+    pub mac_address: MacAddressType,
+    /// 保留的无效字段
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
+    pub reserve1: IpAddressType,
+    /// 登录备注
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
+    pub login_remark: LoginRemarkType,
+    /// 短信验证码文字内容
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
+    pub text: PasswordType,
+    /// 终端IP端口
+    pub client_ip_port: i32,
+    /// 终端IP地址
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
+    pub client_ip_address: ClientIpAddressType,
+}
+
+impl Default for ReqUserLoginWithTextField {
+    fn default() -> Self {
+        unsafe { std::mem::zeroed() }
+    }
+}
+
+impl ReqUserLoginWithTextField {
+    /// 以普通登录请求为模板创建，复制账户、密码、产品信息和终端信息
+    ///
+    /// 验证码按GB18030编码，超长时返回错误
+    pub fn from_login(login: &ReqUserLoginField, text: &str) -> CtpResult<Self> {
+        let (client_ip_address, client_ip_port) = login.client_ip()?;
+        Ok(Self {
+            trading_day: login.trading_day,
+            broker_id: login.broker_id,
+            user_id: login.user_id,
+            password: login.password.clone(),
+            user_product_info: login.user_product_info,
+            interface_product_info: login.interface_product_info,
+            protocol_info: login.protocol_info,
+            mac_address: login.mac_address,
+            login_remark: login.login_remark,
+            client_ip_address,
+            client_ip_port,
+            text: text.parse()?,
+            ..Default::default()
+        })
+    }
+}
+
+/// 带动态口令的登录请求
+#[repr(C)]
+#[derive(Debug, Clone)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(default)
+)]
+pub struct ReqUserLoginWithOtpField {
+    /// 交易日
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
+    pub trading_day: DateType,
+    /// 经纪公司代码
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
+    pub broker_id: BrokerIdType,
+    /// 用户代码
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
+    pub user_id: UserIdType,
+    /// 密码
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
+    pub password: PasswordType,
+    /// 用户端产品信息
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
+    pub user_product_info: ProductInfoType,
+    /// 接口端产品信息
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
+    pub interface_product_info: ProductInfoType,
+    /// 协议信息
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
+    pub protocol_info: ProtocolInfoType,
+    /// Mac地址
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
+    pub mac_address: MacAddressType,
+    /// 保留的无效字段
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
+    pub reserve1: IpAddressType,
+    /// 登录备注
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
+    pub login_remark: LoginRemarkType,
+    /// OTP密码
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
+    pub otp_password: PasswordType,
+    /// 终端IP端口
+    pub client_ip_port: i32,
+    /// 终端IP地址
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
+    pub client_ip_address: ClientIpAddressType,
+}
+
+impl Default for ReqUserLoginWithOtpField {
+    fn default() -> Self {
+        unsafe { std::mem::zeroed() }
+    }
+}
+
+impl ReqUserLoginWithOtpField {
+    /// 以普通登录请求为模板创建，复制账户、密码、产品信息和终端信息
+    ///
+    /// 动态口令按GB18030编码，超长时返回错误
+    pub fn from_login(login: &ReqUserLoginField, otp_password: &str) -> CtpResult<Self> {
+        let (client_ip_address, client_ip_port) = login.client_ip()?;
+        Ok(Self {
+            trading_day: login.trading_day,
+            broker_id: login.broker_id,
+            user_id: login.user_id,
+            password: login.password.clone(),
+            user_product_info: login.user_product_info,
+            interface_product_info: login.interface_product_info,
+            protocol_info: login.protocol_info,
+            mac_address: login.mac_address,
+            login_remark: login.login_remark,
+            client_ip_address,
+            client_ip_port,
+            otp_password: otp_password.parse()?,
+            ..Default::default()
+        })
+    }
+}
+
+/// 用户终端系统信息（中继模式）
+#[repr(C)]
+#[derive(Debug, Clone)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(default)
+)]
+pub struct UserSystemInfoField {
+    /// 经纪公司代码
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
+    pub broker_id: BrokerIdType,
+    /// 用户代码
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
+    pub user_id: UserIdType,
+    /// 用户端系统内部信息长度
+    pub client_system_info_len: i32,
+    /// 用户端系统内部信息
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
+    pub client_system_info: ClientSystemInfoType,
+    /// 保留的无效字段
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
+    pub reserve1: IpAddressType,
+    /// 终端IP端口
+    pub client_ip_port: i32,
+    /// 登录成功时间
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
+    pub client_login_time: TimeType,
+    /// App代码
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
+    pub client_app_id: ClientAppIdType,
+    /// 用户公网IP
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
+    pub client_public_ip: ClientIpAddressType,
+    /// 客户登录备注2
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
+    pub client_login_remark: ClientLoginRemarkType,
+}
+
+impl Default for UserSystemInfoField {
+    fn default() -> Self {
+        unsafe { std::mem::zeroed() }
+    }
+}
+
+/// 微信小程序等用户终端系统信息（中继模式）
+#[repr(C)]
+#[derive(Debug, Clone)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(default)
+)]
+pub struct WechatUserSystemInfoField {
+    /// 经纪公司代码
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
+    pub broker_id: BrokerIdType,
+    /// 用户代码
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
+    pub user_id: UserIdType,
+    /// 用户端系统内部信息长度
+    pub wechat_clt_sys_info_len: i32,
+    /// 用户端系统内部信息
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
+    pub wechat_clt_sys_info: ClientSystemInfoType,
+    /// 终端IP端口
+    pub client_ip_port: i32,
+    /// 登录成功时间
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
+    pub client_login_time: TimeType,
+    /// App代码
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
+    pub client_app_id: ClientAppIdType,
+    /// 用户公网IP
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
+    pub client_public_ip: ClientIpAddressType,
+    /// 客户登录备注2
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
+    pub client_login_remark: ClientLoginRemarkType,
+}
+
+impl Default for WechatUserSystemInfoField {
+    fn default() -> Self {
+        unsafe { std::mem::zeroed() }
+    }
+}
+
+/// 名字服务器用户信息
+#[repr(C)]
+#[derive(Debug, Clone)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(default)
+)]
+pub struct FensUserInfoField {
+    /// 经纪公司代码
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
+    pub broker_id: BrokerIdType,
+    /// 用户代码
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
+    pub user_id: UserIdType,
+    /// 登录模式，'0'交易，'1'转账
+    pub login_mode: u8,
+}
+
+impl Default for FensUserInfoField {
+    fn default() -> Self {
+        unsafe { std::mem::zeroed() }
+    }
+}
+
+/// 前置信息
+#[repr(C)]
+#[derive(Debug, Clone)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(default)
+)]
+pub struct FrontInfoField {
+    /// 前置地址
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
+    pub front_addr: AddressType,
+    /// 查询流控
+    pub qry_freq: i32,
+    /// FTD流控
+    pub ftd_pkg_freq: i32,
+}
+
+impl Default for FrontInfoField {
+    fn default() -> Self {
+        unsafe { std::mem::zeroed() }
+    }
+}
+
+// 口令截断后会与柜台实际保存的口令不一致，因此超长时直接报错
+fn encode_password_pair(
+    old_password: &str,
+    new_password: &str,
+) -> CtpResult<(PasswordType, PasswordType)> {
+    if new_password.is_empty() {
+        return Err(CtpError::InvalidParameterError(
+            "新口令不能为空".to_string(),
+        ));
+    }
+    if new_password == old_password {
+        return Err(CtpError::InvalidParameterError(
+            "新口令不能与原口令相同".to_string(),
+        ));
+    }
+    Ok((old_password.parse()?, new_password.parse()?))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_string_convert() {
+        let test_str = "测试用户";
+        let user_id = UserIdType::from_utf8_string(test_str).unwrap();
+        let converted = user_id.to_utf8_string().unwrap();
+        assert_eq!(converted.trim_end_matches('\0'), test_str);
+    }
+
+    #[test]
+    fn test_login_request_creation() {
+        let req = ReqUserLoginField::new("9999", "investor1", "123456").unwrap();
+
+        assert_eq!(
+            req.broker_id
+                .to_utf8_string()
+                .unwrap()
+                .trim_end_matches('\0'),
+            "9999"
+        );
+        assert_eq!(
+            req.user_id.to_utf8_string().unwrap().trim_end_matches('\0'),
+            "investor1"
+        );
+        assert_eq!(
+            req.password
+                .to_utf8_string()
+                .unwrap()
+                .trim_end_matches('\0'),
+            "123456"
+        );
+    }
+
+    #[test]
+    fn test_login_with_captcha_from_login() {
+        let login = ReqUserLoginField::new("9999", "00001", "secret")
+            .unwrap()
+            .with_product_info("demo")
+            .unwrap()
+            .with_client_ip("10.0.0.1", "8080")
+            .unwrap();
+        let req = ReqUserLoginWithCaptchaField::from_login(&login, "A3x9").unwrap();
+        assert_eq!(req.user_id, "00001");
+        assert_eq!(req.password, login.password);
+        assert_eq!(req.captcha.expose_secret(), b"A3x9");
+        assert_eq!(&req.client_ip_address[..9], b"10.0.0.1\0");
+        assert_eq!(req.client_ip_port, 8080);
+
+        let auth = RspUserAuthMethodField {
+            usable_auth_method: 0b101,
+        };
+        assert!(auth.requires_verification());
+        assert!(auth.supports_captcha() && auth.supports_text());
+        assert!(!auth.supports_otp());
+    }
+
+    #[test]
+    fn test_password_update_validation() {
+        let req = UserPasswordUpdateField::new("9999", "00001", "old", "新口令1").unwrap();
+        assert_eq!(req.user_id, "00001");
+        // 汉字按GB18030编码为2字节
+        assert_eq!(
+            req.new_password.expose_secret(),
+            &[0xd0, 0xc2, 0xbf, 0xda, 0xc1, 0xee, b'1']
+        );
+        // Debug输出中口令被隐去
+        assert!(format!("{:?}", req).contains("new_password: \"***\""));
+
+        assert!(UserPasswordUpdateField::new("9999", "00001", "same", "same").is_err());
+        assert!(UserPasswordUpdateField::new("9999", "00001", "old", "").is_err());
+        // 40字节是上限，超出时报错而不是截断
+        assert!(UserPasswordUpdateField::new("9999", "00001", "old", &"a".repeat(40)).is_ok());
+        assert!(matches!(
+            UserPasswordUpdateField::new("9999", "00001", "old", &"口".repeat(21)),
+            Err(CtpError::InvalidParameterError(_))
+        ));
+
+        let req = TradingAccountPasswordUpdateField::new("9999", "00001", "a", "b")
+            .unwrap()
+            .with_currency("USD")
+            .unwrap();
+        assert_eq!(req.currency_id, "USD");
+    }
+
+    #[test]
+    fn test_rsp_info_success() {
+        let mut rsp = RspInfoField::default();
+        assert!(rsp.is_success());
+
+        rsp.error_id = -1;
+        assert!(!rsp.is_success());
+    }
+}
+
+// 查询资金账户字段
+#[repr(C)]
+#[derive(Debug, Clone)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(default)
+)]
+pub struct QryTradingAccountField {
+    // 经纪公司代码
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
+    pub broker_id: BrokerIdType,
+    // 投资者代码
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
+    pub investor_id: InvestorIdType,
+    // 币种代码
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
+    pub currency_id: CurrencyIdType,
+    // 业务类型
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
+    pub biz_type: BizTypeType,
+    // 投资者账户代码
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
+    pub account_id: AccountIdType,
+}
+
+impl Default for QryTradingAccountField {
+    fn default() -> Self {
+        unsafe { std::mem::zeroed() }
+    }
+}
+
+impl QryTradingAccountField {
+    pub fn new(broker_id: &str, investor_id: &str) -> CtpResult<Self> {
+        let mut req = Self::default();
+        req.broker_id = BrokerIdType::from_utf8_string(broker_id)?;
+        req.investor_id = InvestorIdType::from_utf8_string(investor_id)?;
+        // 不设置币种代码，让CTP服务器使用默认值
+        Ok(req)
+    }
+
+    pub fn with_currency(mut self, currency_id: &str) -> CtpResult<Self> {
+        self.currency_id = CurrencyIdType::from_utf8_string(currency_id)?;
+        Ok(self)
+    }
+}
+
+// 查询投资者持仓字段
+#[repr(C)]
+#[derive(Debug, Clone)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(default)
+)]
+pub struct QryInvestorPositionField {
+    // 经纪公司代码
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
+    pub broker_id: BrokerIdType,
+    // 投资者代码
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
+    pub investor_id: InvestorIdType,
+    // 合约代码（为空表示查询所有）
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
+    pub instrument_id: InstrumentIdType,
+    // 交易所代码
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
+    pub exchange_id: ExchangeIdType,
+    // 投资单元代码
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
+    pub invest_unit_id: InvestUnitIdType,
+}
+
+impl Default for QryInvestorPositionField {
+    fn default() -> Self {
+        unsafe { std::mem::zeroed() }
+    }
+}
+
+impl QryInvestorPositionField {
+    pub fn new(broker_id: &str, investor_id: &str) -> CtpResult<Self> {
+        let mut req = Self::default();
+        req.broker_id = BrokerIdType::from_utf8_string(broker_id)?;
+        req.investor_id = InvestorIdType::from_utf8_string(investor_id)?;
+        Ok(req)
+    }
+
+    pub fn with_instrument_id(mut self, instrument_id: &str) -> CtpResult<Self> {
+        self.instrument_id = InstrumentIdType::from_utf8_string(instrument_id)?;
+        Ok(self)
+    }
+}
+
+// 第一阶段新增结构体
+
+// 查询报单字段
+#[repr(C)]
+#[derive(Debug, Clone)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(default)
+)]
+pub struct QryOrderField {
+    // 经纪公司代码
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
+    pub broker_id: BrokerIdType,
+    // 投资者代码
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
+    pub investor_id: InvestorIdType,
+    // 合约代码
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
+    pub instrument_id: InstrumentIdType,
+    // 交易所代码
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
+    pub exchange_id: ExchangeIdType,
+    // 报单编号
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
+    pub order_sys_id: OrderSysIdType,
+    // 开始时间
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
+    pub insert_time_start: TimeType,
+    // 结束时间
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
+    pub insert_time_end: TimeType,
+    // 投资单元代码
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
+    pub invest_unit_id: InvestUnitIdType,
+}
+
+impl Default for QryOrderField {
+    fn default() -> Self {
+        unsafe { std::mem::zeroed() }
+    }
+}
+
+impl QryOrderField {
+    pub fn new(broker_id: &str, investor_id: &str) -> CtpResult<Self> {
+        let mut req = Self::default();
+        req.broker_id = BrokerIdType::from_utf8_string(broker_id)?;
+        req.investor_id = InvestorIdType::from_utf8_string(investor_id)?;
+        Ok(req)
+    }
+
+    pub fn with_instrument_id(mut self, instrument_id: &str) -> CtpResult<Self> {
+        self.instrument_id = InstrumentIdType::from_utf8_string(instrument_id)?;
+        Ok(self)
+    }
+}
+
+// 查询成交字段
+#[repr(C)]
+#[derive(Debug, Clone)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(default)
+)]
+pub struct QryTradeField {
+    // 经纪公司代码
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
+    pub broker_id: BrokerIdType,
+    // 投资者代码
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
+    pub investor_id: InvestorIdType,
+    // 合约代码
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
+    pub instrument_id: InstrumentIdType,
+    // 交易所代码
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
+    pub exchange_id: ExchangeIdType,
+    // 成交编号
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
+    pub trade_id: TradeIdType,
+    // 开始时间
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
+    pub trade_time_start: TimeType,
+    // 结束时间
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
+    pub trade_time_end: TimeType,
+    // 投资单元代码
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
+    pub invest_unit_id: InvestUnitIdType,
+}
+
+impl Default for QryTradeField {
+    fn default() -> Self {
+        unsafe { std::mem::zeroed() }
+    }
+}
+
+impl QryTradeField {
+    pub fn new(broker_id: &str, investor_id: &str) -> CtpResult<Self> {
+        let mut req = Self::default();
+        req.broker_id = BrokerIdType::from_utf8_string(broker_id)?;
+        req.investor_id = InvestorIdType::from_utf8_string(investor_id)?;
+        Ok(req)
+    }
+
+    pub fn with_instrument_id(mut self, instrument_id: &str) -> CtpResult<Self> {
+        self.instrument_id = InstrumentIdType::from_utf8_string(instrument_id)?;
+        Ok(self)
+    }
+}
+
+// 查询合约字段
+#[repr(C)]
+#[derive(Debug, Clone)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(default)
+)]
+pub struct QryInstrumentField {
+    // 合约代码
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
+    pub instrument_id: InstrumentIdType,
+    // 交易所代码
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
+    pub exchange_id: ExchangeIdType,
+    // 合约在交易所的代码
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
+    pub exchange_inst_id: ExchangeInstIdType,
+    // 产品代码
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
+    pub product_id: InstrumentIdType,
+}
+
+impl Default for QryInstrumentField {
+    fn default() -> Self {
+        unsafe { std::mem::zeroed() }
+    }
+}
+
+impl QryInstrumentField {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_instrument_id(mut self, instrument_id: &str) -> CtpResult<Self> {
+        self.instrument_id = InstrumentIdType::from_utf8_string(instrument_id)?;
+        Ok(self)
+    }
+
+    pub fn with_exchange_id(mut self, exchange_id: &str) -> CtpResult<Self> {
+        self.exchange_id = ExchangeIdType::from_utf8_string(exchange_id)?;
+        Ok(self)
+    }
+}
+
+// 报单操作字段（撤单使用）
+#[repr(C)]
+#[derive(Debug, Clone)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(default)
+)]
+pub struct InputOrderActionField {
+    // 经纪公司代码
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
+    pub broker_id: BrokerIdType,
+    // 投资者代码
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
+    pub investor_id: InvestorIdType,
+    // 报单操作引用
+    pub order_action_ref: OrderActionRefType,
+    // 报单引用
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
+    pub order_ref: OrderRefType,
+    // 请求编号
+    pub request_id: RequestIdType,
+    // 前置编号
+    pub front_id: FrontIdType,
+    // 会话编号
+    pub session_id: SessionIdType,
+    // 交易所代码
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
+    pub exchange_id: ExchangeIdType,
+    // 报单编号
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
+    pub order_sys_id: OrderSysIdType,
+    // 操作标志
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::char_flag"))]
+    pub action_flag: ActionFlagType,
+    // 价格
+    pub limit_price: PriceType,
+    // 数量变化
+    pub volume_change: VolumeType,
+    // 用户代码
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
+    pub user_id: UserIdType,
+    // 合约代码
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
+    pub instrument_id: InstrumentIdType,
+    // 投资单元代码
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
+    pub invest_unit_id: InvestUnitIdType,
+    // IP地址
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
+    pub ip_address: IPAddressType,
+    // Mac地址
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
+    pub mac_address: MacAddressType,
+}
+
+impl Default for InputOrderActionField {
+    fn default() -> Self {
+        unsafe { std::mem::zeroed() }
+    }
+}
+
+impl InputOrderActionField {
+    pub fn new(
+        broker_id: &str,
+        investor_id: &str,
+        action_flag: u8,
+        order_ref: &str,
+        front_id: i32,
+        session_id: i32,
+        exchange_id: &str,
+        order_sys_id: &str,
+    ) -> CtpResult<Self> {
+        let mut req = Self::default();
+        req.broker_id = BrokerIdType::from_utf8_string(broker_id)?;
+        req.investor_id = InvestorIdType::from_utf8_string(investor_id)?;
+        req.action_flag = action_flag;
+        req.order_ref = OrderRefType::from_utf8_string(order_ref)?;
+        req.front_id = front_id;
+        req.session_id = session_id;
+        req.exchange_id = ExchangeIdType::from_utf8_string(exchange_id)?;
+        req.order_sys_id = OrderSysIdType::from_utf8_string(order_sys_id)?;
+        Ok(req)
+    }
+}
+
+// 报单操作回报字段（用于错误回报）
+#[repr(C)]
+#[derive(Debug, Clone)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(default)
+)]
+pub struct OrderActionField {
+    // 经纪公司代码
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
+    pub broker_id: BrokerIdType,
+    // 投资者代码
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
+    pub investor_id: InvestorIdType,
+    // 报单操作引用
+    pub order_action_ref: OrderActionRefType,
+    // 报单引用
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
+    pub order_ref: OrderRefType,
+    // 请求编号
+    pub request_id: RequestIdType,
+    // 前置编号
+    pub front_id: FrontIdType,
+    // 会话编号
+    pub session_id: SessionIdType,
+    // 交易所代码
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
+    pub exchange_id: ExchangeIdType,
+    // 报单编号
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
+    pub order_sys_id: OrderSysIdType,
+    // 操作标志
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::char_flag"))]
+    pub action_flag: ActionFlagType,
+    // 价格
+    pub limit_price: PriceType,
+    // 数量变化
+    pub volume_change: VolumeType,
+    // 操作日期
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
+    pub action_date: DateType,
+    // 操作时间
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
+    pub action_time: TimeType,
+    // 交易所交易员代码
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
+    pub trader_id: TraderIdType,
+    // 安装编号
+    pub install_id: InstallIdType,
+    // 本地报单编号
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
+    pub order_local_id: OrderLocalIdType,
+    // 操作本地编号
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
+    pub action_local_id: OrderLocalIdType,
+    // 会员代码
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
+    pub participant_id: ParticipantIdType,
+    // 客户代码
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
+    pub client_id: ClientIdType,
+    // 业务单元
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
+    pub business_unit: BusinessUnitType,
+    // 报单操作状态
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::char_flag"))]
+    pub order_action_status: OrderActionStatusType,
+    // 用户代码
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
+    pub user_id: UserIdType,
+    // 状态信息
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
+    pub status_msg: ErrorMsgType,
+    // 合约代码
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
+    pub instrument_id: InstrumentIdType,
+    // 营业部编号
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
+    pub branch_id: BranchIdType,
+    // 投资单元代码
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
+    pub invest_unit_id: InvestUnitIdType,
+    // IP地址
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
+    pub ip_address: IPAddressType,
+    // Mac地址
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
+    pub mac_address: MacAddressType,
+}
+
+impl Default for OrderActionField {
+    fn default() -> Self {
+        unsafe { std::mem::zeroed() }
+    }
+}
+
+// 第二阶段新增结构体
+
+// 查询合约保证金率字段
+#[repr(C)]
+#[derive(Debug, Clone)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(default)
+)]
+pub struct QryInstrumentMarginRateField {
+    // 经纪公司代码
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
+    pub broker_id: BrokerIdType,
+    // 投资者代码
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
+    pub investor_id: InvestorIdType,
+    // 合约代码
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
+    pub instrument_id: InstrumentIdType,
+    // 投机套保标志
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::char_flag"))]
+    pub hedge_flag: u8,
+    // 交易所代码
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
+    pub exchange_id: ExchangeIdType,
+    // 投资单元代码
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
+    pub invest_unit_id: InvestUnitIdType,
+}
+
+impl Default for QryInstrumentMarginRateField {
+    fn default() -> Self {
+        unsafe { std::mem::zeroed() }
+    }
+}
+
+impl QryInstrumentMarginRateField {
+    pub fn new(broker_id: &str, investor_id: &str, instrument_id: &str) -> CtpResult<Self> {
+        let mut req = Self::default();
+        req.broker_id = BrokerIdType::from_utf8_string(broker_id)?;
+        req.investor_id = InvestorIdType::from_utf8_string(investor_id)?;
+        req.instrument_id = InstrumentIdType::from_utf8_string(instrument_id)?;
+        Ok(req)
+    }
+
+    pub fn with_hedge_flag(mut self, hedge_flag: u8) -> Self {
+        self.hedge_flag = hedge_flag;
+        self
+    }
+
+    pub fn with_exchange_id(mut self, exchange_id: &str) -> CtpResult<Self> {
+        self.exchange_id = ExchangeIdType::from_utf8_string(exchange_id)?;
+        Ok(self)
+    }
+}
+
+// 查询合约手续费率字段
+#[repr(C)]
+#[derive(Debug, Clone)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(default)
+)]
+pub struct QryInstrumentCommissionRateField {
+    // 经纪公司代码
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
+    pub broker_id: BrokerIdType,
+    // 投资者代码
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
+    pub investor_id: InvestorIdType,
+    // 合约代码
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
+    pub instrument_id: InstrumentIdType,
+    // 交易所代码
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
+    pub exchange_id: ExchangeIdType,
+    // 投资单元代码
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
+    pub invest_unit_id: InvestUnitIdType,
+}
+
+impl Default for QryInstrumentCommissionRateField {
+    fn default() -> Self {
+        unsafe { std::mem::zeroed() }
+    }
+}
+
+impl QryInstrumentCommissionRateField {
+    pub fn new(broker_id: &str, investor_id: &str, instrument_id: &str) -> CtpResult<Self> {
+        let mut req = Self::default();
+        req.broker_id = BrokerIdType::from_utf8_string(broker_id)?;
+        req.investor_id = InvestorIdType::from_utf8_string(investor_id)?;
+        req.instrument_id = InstrumentIdType::from_utf8_string(instrument_id)?;
+        Ok(req)
+    }
+
+    pub fn with_exchange_id(mut self, exchange_id: &str) -> CtpResult<Self> {
+        self.exchange_id = ExchangeIdType::from_utf8_string(exchange_id)?;
+        Ok(self)
+    }
+}
+
+// 查询交易所字段
+#[repr(C)]
+#[derive(Debug, Clone)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(default)
+)]
+pub struct QryExchangeField {
+    // 交易所代码
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
+    pub exchange_id: ExchangeIdType,
+}
+
+impl Default for QryExchangeField {
+    fn default() -> Self {
+        unsafe { std::mem::zeroed() }
+    }
+}
+
+impl QryExchangeField {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_exchange_id(mut self, exchange_id: &str) -> CtpResult<Self> {
+        self.exchange_id = ExchangeIdType::from_utf8_string(exchange_id)?;
+        Ok(self)
+    }
+}
+
+// 查询产品字段
+#[repr(C)]
+#[derive(Debug, Clone)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(default)
+)]
+pub struct QryProductField {
+    // 产品代码
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
+    pub product_id: InstrumentIdType,
+    // 产品类型
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::char_flag"))]
+    pub product_class: u8,
+    // 交易所代码
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
+    pub exchange_id: ExchangeIdType,
+}
+
+impl Default for QryProductField {
+    fn default() -> Self {
+        unsafe { std::mem::zeroed() }
+    }
+}
+
+impl QryProductField {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_product_id(mut self, product_id: &str) -> CtpResult<Self> {
+        self.product_id = InstrumentIdType::from_utf8_string(product_id)?;
+        Ok(self)
+    }
+
+    pub fn with_exchange_id(mut self, exchange_id: &str) -> CtpResult<Self> {
+        self.exchange_id = ExchangeIdType::from_utf8_string(exchange_id)?;
+        Ok(self)
+    }
+
+    pub fn with_product_class(mut self, product_class: u8) -> Self {
+        self.product_class = product_class;
+        self
+    }
+}
+
+// 结算信息确认字段
+#[repr(C)]
+#[derive(Debug, Clone)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(default)
+)]
+pub struct SettlementInfoConfirmField {
+    // 经纪公司代码
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
     pub broker_id: BrokerIdType,
     // 投资者代码
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
     pub investor_id: InvestorIdType,
-    // 报单操作引用
-    pub order_action_ref: OrderActionRefType,
+    // 确认日期
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
+    pub confirm_date: [u8; 9],
+    // 确认时间
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
+    pub confirm_time: [u8; 9],
+    // 结算编号
+    pub settlement_id: i32,
+    // 投资者账户代码
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
+    pub account_id: AccountIdType,
+    // 币种代码
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
+    pub currency_id: CurrencyIdType,
+}
+
+impl Default for SettlementInfoConfirmField {
+    fn default() -> Self {
+        unsafe { std::mem::zeroed() }
+    }
+}
+
+impl SettlementInfoConfirmField {
+    pub fn new(broker_id: &str, investor_id: &str) -> CtpResult<Self> {
+        let mut req = Self::default();
+        req.broker_id = BrokerIdType::from_utf8_string(broker_id)?;
+        req.investor_id = InvestorIdType::from_utf8_string(investor_id)?;
+        Ok(req)
+    }
+
+    pub fn with_account_id(mut self, account_id: &str) -> CtpResult<Self> {
+        self.account_id = AccountIdType::from_utf8_string(account_id)?;
+        Ok(self)
+    }
+}
+
+// 预埋单字段
+#[repr(C)]
+#[derive(Debug, Clone)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(default)
+)]
+pub struct ParkedOrderField {
+    // 经纪公司代码
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
+    pub broker_id: BrokerIdType,
+    // 投资者代码
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
+    pub investor_id: InvestorIdType,
+    // 合约代码
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
+    pub instrument_id: InstrumentIdType,
     // 报单引用
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
     pub order_ref: OrderRefType,
+    // 用户代码
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
+    pub user_id: UserIdType,
+    // 报单价格条件
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::char_flag"))]
+    pub order_price_type: u8,
+    // 买卖方向
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::char_flag"))]
+    pub direction: u8,
+    // 组合开平标志
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
+    pub comb_offset_flag: [u8; 5],
+    // 组合投机套保标志
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
+    pub comb_hedge_flag: [u8; 5],
+    // 价格
+    pub limit_price: PriceType,
+    // 数量
+    pub volume_total_original: VolumeType,
+    // 有效期类型
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::char_flag"))]
+    pub time_condition: u8,
+    // GTD日期
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
+    pub gtd_date: [u8; 9],
+    // 成交量类型
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::char_flag"))]
+    pub volume_condition: u8,
+    // 最小成交量
+    pub min_volume: VolumeType,
+    // 触发条件
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::char_flag"))]
+    pub contingent_condition: u8,
+    // 止损价
+    pub stop_price: PriceType,
+    // 强平原因
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::char_flag"))]
+    pub force_close_reason: u8,
+    // 自动挂起标志
+    pub is_auto_suspend: i32,
+    // 业务单元
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
+    pub business_unit: [u8; 21],
     // 请求编号
     pub request_id: RequestIdType,
-    // 前置编号
-    pub front_id: FrontIdType,
-    // 会话编号
-    pub session_id: SessionIdType,
+    // 用户强平标志
+    pub user_force_close: i32,
     // 交易所代码
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
     pub exchange_id: ExchangeIdType,
-    // 报单编号
-    pub order_sys_id: OrderSysIdType,
-    // 操作标志
-    pub action_flag: ActionFlagType,
-    // 价格
-    pub limit_price: PriceType,
-    // 数量变化
-    pub volume_change: VolumeType,
-    // 用户代码
-    pub user_id: UserIdType,
-    // 合约代码
-    pub instrument_id: InstrumentIdType,
+    // 预埋报单编号
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
+    pub parked_order_id: [u8; 13],
+    // 用户类型
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::char_flag"))]
+    pub user_type: u8,
+    // 预埋单状态
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::char_flag"))]
+    pub status: u8,
+    // 错误代码
+    pub error_id: i32,
+    // 错误信息
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
+    pub error_msg: [u8; 81],
+    // 互换单标志
+    pub is_swap_order: i32,
     // 投资单元代码
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
     pub invest_unit_id: InvestUnitIdType,
+    // 资金账号
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
+    pub account_id: AccountIdType,
+    // 币种代码
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
+    pub currency_id: CurrencyIdType,
+    // 客户代码
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
+    pub client_id: [u8; 11],
     // IP地址
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
     pub ip_address: IPAddressType,
     // Mac地址
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
     pub mac_address: MacAddressType,
 }
 
-impl Default for InputOrderActionField {
+impl Default for ParkedOrderField {
     fn default() -> Self {
         unsafe { std::mem::zeroed() }
     }
 }
 
-impl InputOrderActionField {
+impl ParkedOrderField {
     pub fn new(
         broker_id: &str,
         investor_id: &str,
-        action_flag: u8,
+        instrument_id: &str,
         order_ref: &str,
-        front_id: i32,
-        session_id: i32,
-        exchange_id: &str,
-        order_sys_id: &str,
+        direction: u8,
+        limit_price: f64,
+        volume: i32,
     ) -> CtpResult<Self> {
         let mut req = Self::default();
         req.broker_id = BrokerIdType::from_utf8_string(broker_id)?;
         req.investor_id = InvestorIdType::from_utf8_string(investor_id)?;
-        req.action_flag = action_flag;
+        req.instrument_id = InstrumentIdType::from_utf8_string(instrument_id)?;
         req.order_ref = OrderRefType::from_utf8_string(order_ref)?;
-        req.front_id = front_id;
-        req.session_id = session_id;
-        req.exchange_id = ExchangeIdType::from_utf8_string(exchange_id)?;
-        req.order_sys_id = OrderSysIdType::from_utf8_string(order_sys_id)?;
+        req.direction = direction;
+        req.limit_price = limit_price;
+        req.volume_total_original = volume;
         Ok(req)
     }
 }
 
-// 报单操作回报字段（用于错误回报）
+// 预埋单操作字段
 #[repr(C)]
 #[derive(Debug, Clone)]
-pub struct OrderActionField {
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(default)
+)]
+pub struct ParkedOrderActionField {
     // 经纪公司代码
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
     pub broker_id: BrokerIdType,
     // 投资者代码
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
     pub investor_id: InvestorIdType,
     // 报单操作引用
     pub order_action_ref: OrderActionRefType,
     // 报单引用
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
     pub order_ref: OrderRefType,
     // 请求编号
     pub request_id: RequestIdType,
@@ -704,1159 +2136,2007 @@ pub struct OrderActionField {
     // 会话编号
     pub session_id: SessionIdType,
     // 交易所代码
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
     pub exchange_id: ExchangeIdType,
     // 报单编号
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
     pub order_sys_id: OrderSysIdType,
     // 操作标志
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::char_flag"))]
     pub action_flag: ActionFlagType,
     // 价格
     pub limit_price: PriceType,
     // 数量变化
     pub volume_change: VolumeType,
-    // 操作日期
-    pub action_date: DateType,
-    // 操作时间
-    pub action_time: TimeType,
-    // 交易所交易员代码
-    pub trader_id: TraderIdType,
-    // 安装编号
-    pub install_id: InstallIdType,
-    // 本地报单编号
-    pub order_local_id: OrderLocalIdType,
-    // 操作本地编号
-    pub action_local_id: OrderLocalIdType,
-    // 会员代码
-    pub participant_id: ParticipantIdType,
-    // 客户代码
-    pub client_id: ClientIdType,
-    // 业务单元
-    pub business_unit: BusinessUnitType,
-    // 报单操作状态
-    pub order_action_status: OrderActionStatusType,
     // 用户代码
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
     pub user_id: UserIdType,
-    // 状态信息
-    pub status_msg: ErrorMsgType,
     // 合约代码
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
     pub instrument_id: InstrumentIdType,
-    // 营业部编号
-    pub branch_id: BranchIdType,
+    // 预埋撤单单编号
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
+    pub parked_order_action_id: [u8; 13],
+    // 用户类型
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::char_flag"))]
+    pub user_type: u8,
+    // 预埋撤单状态
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::char_flag"))]
+    pub status: u8,
+    // 错误代码
+    pub error_id: i32,
+    // 错误信息
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
+    pub error_msg: [u8; 81],
     // 投资单元代码
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
     pub invest_unit_id: InvestUnitIdType,
     // IP地址
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
     pub ip_address: IPAddressType,
     // Mac地址
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
     pub mac_address: MacAddressType,
 }
 
-impl Default for OrderActionField {
+impl Default for ParkedOrderActionField {
     fn default() -> Self {
         unsafe { std::mem::zeroed() }
     }
 }
 
-// 第二阶段新增结构体
+impl ParkedOrderActionField {
+    pub fn new(
+        broker_id: &str,
+        investor_id: &str,
+        action_flag: u8,
+        order_ref: &str,
+        front_id: i32,
+        session_id: i32,
+        exchange_id: &str,
+        order_sys_id: &str,
+    ) -> CtpResult<Self> {
+        let mut req = Self::default();
+        req.broker_id = BrokerIdType::from_utf8_string(broker_id)?;
+        req.investor_id = InvestorIdType::from_utf8_string(investor_id)?;
+        req.action_flag = action_flag;
+        req.order_ref = OrderRefType::from_utf8_string(order_ref)?;
+        req.front_id = front_id;
+        req.session_id = session_id;
+        req.exchange_id = ExchangeIdType::from_utf8_string(exchange_id)?;
+        req.order_sys_id = OrderSysIdType::from_utf8_string(order_sys_id)?;
+        Ok(req)
+    }
+}
+
+// 第二阶段响应字段定义
 
-// 查询合约保证金率字段
+// 合约保证金率字段
 #[repr(C)]
 #[derive(Debug, Clone)]
-pub struct QryInstrumentMarginRateField {
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(default)
+)]
+pub struct InstrumentMarginRateField {
+    // 合约代码
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
+    pub instrument_id: InstrumentIdType,
+    // 投资者范围
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::char_flag"))]
+    pub investor_range: u8,
     // 经纪公司代码
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
     pub broker_id: BrokerIdType,
     // 投资者代码
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
     pub investor_id: InvestorIdType,
-    // 合约代码
-    pub instrument_id: InstrumentIdType,
     // 投机套保标志
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::char_flag"))]
     pub hedge_flag: u8,
+    // 多头保证金率
+    pub long_margin_ratio_by_money: f64,
+    // 多头保证金费
+    pub long_margin_ratio_by_volume: f64,
+    // 空头保证金率
+    pub short_margin_ratio_by_money: f64,
+    // 空头保证金费
+    pub short_margin_ratio_by_volume: f64,
+    // 是否相对交易所收取
+    pub is_relative: i32,
     // 交易所代码
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
     pub exchange_id: ExchangeIdType,
     // 投资单元代码
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
     pub invest_unit_id: InvestUnitIdType,
 }
 
-impl Default for QryInstrumentMarginRateField {
+impl Default for InstrumentMarginRateField {
     fn default() -> Self {
         unsafe { std::mem::zeroed() }
     }
 }
 
-impl QryInstrumentMarginRateField {
-    pub fn new(broker_id: &str, investor_id: &str, instrument_id: &str) -> CtpResult<Self> {
-        let mut req = Self::default();
-        req.broker_id = BrokerIdType::from_utf8_string(broker_id)?;
-        req.investor_id = InvestorIdType::from_utf8_string(investor_id)?;
-        req.instrument_id = InstrumentIdType::from_utf8_string(instrument_id)?;
-        Ok(req)
-    }
-
-    pub fn with_hedge_flag(mut self, hedge_flag: u8) -> Self {
-        self.hedge_flag = hedge_flag;
-        self
-    }
-
-    pub fn with_exchange_id(mut self, exchange_id: &str) -> CtpResult<Self> {
-        self.exchange_id = ExchangeIdType::from_utf8_string(exchange_id)?;
-        Ok(self)
-    }
-}
-
-// 查询合约手续费率字段
+// 合约手续费率字段
 #[repr(C)]
 #[derive(Debug, Clone)]
-pub struct QryInstrumentCommissionRateField {
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(default)
+)]
+pub struct InstrumentCommissionRateField {
+    // 合约代码
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
+    pub instrument_id: InstrumentIdType,
+    // 投资者范围
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::char_flag"))]
+    pub investor_range: u8,
     // 经纪公司代码
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
     pub broker_id: BrokerIdType,
     // 投资者代码
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
     pub investor_id: InvestorIdType,
-    // 合约代码
-    pub instrument_id: InstrumentIdType,
+    // 开仓手续费率
+    pub open_ratio_by_money: f64,
+    // 开仓手续费
+    pub open_ratio_by_volume: f64,
+    // 平仓手续费率
+    pub close_ratio_by_money: f64,
+    // 平仓手续费
+    pub close_ratio_by_volume: f64,
+    // 平今手续费率
+    pub close_today_ratio_by_money: f64,
+    // 平今手续费
+    pub close_today_ratio_by_volume: f64,
     // 交易所代码
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
     pub exchange_id: ExchangeIdType,
+    // 业务类型
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::char_flag"))]
+    pub biz_type: u8,
     // 投资单元代码
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
     pub invest_unit_id: InvestUnitIdType,
 }
 
-impl Default for QryInstrumentCommissionRateField {
+impl Default for InstrumentCommissionRateField {
     fn default() -> Self {
         unsafe { std::mem::zeroed() }
     }
 }
 
-impl QryInstrumentCommissionRateField {
-    pub fn new(broker_id: &str, investor_id: &str, instrument_id: &str) -> CtpResult<Self> {
-        let mut req = Self::default();
-        req.broker_id = BrokerIdType::from_utf8_string(broker_id)?;
-        req.investor_id = InvestorIdType::from_utf8_string(investor_id)?;
-        req.instrument_id = InstrumentIdType::from_utf8_string(instrument_id)?;
-        Ok(req)
-    }
+// 交易所字段
+#[repr(C)]
+#[derive(Debug, Clone)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(default)
+)]
+pub struct ExchangeField {
+    // 交易所代码
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
+    pub exchange_id: ExchangeIdType,
+    // 交易所名称
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
+    pub exchange_name: [u8; 61],
+    // 交易所属性
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::char_flag"))]
+    pub exchange_property: u8,
+}
 
-    pub fn with_exchange_id(mut self, exchange_id: &str) -> CtpResult<Self> {
-        self.exchange_id = ExchangeIdType::from_utf8_string(exchange_id)?;
-        Ok(self)
+impl Default for ExchangeField {
+    fn default() -> Self {
+        unsafe { std::mem::zeroed() }
     }
 }
 
-// 查询交易所字段
+// 产品字段
 #[repr(C)]
 #[derive(Debug, Clone)]
-pub struct QryExchangeField {
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(default)
+)]
+pub struct ProductField {
+    // 产品代码
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
+    pub product_id: InstrumentIdType,
+    // 产品名称
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
+    pub product_name: [u8; 21],
     // 交易所代码
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
     pub exchange_id: ExchangeIdType,
+    // 产品类型
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::char_flag"))]
+    pub product_class: u8,
+    // 合约数量乘数
+    pub volume_multiple: i32,
+    // 最小变动价位
+    pub price_tick: f64,
+    // 市价单最大下单量
+    pub max_market_order_volume: i32,
+    // 市价单最小下单量
+    pub min_market_order_volume: i32,
+    // 限价单最大下单量
+    pub max_limit_order_volume: i32,
+    // 限价单最小下单量
+    pub min_limit_order_volume: i32,
+    // 持仓类型
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::char_flag"))]
+    pub position_type: u8,
+    // 持仓日期类型
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::char_flag"))]
+    pub position_date_type: u8,
+    // 平仓处理类型
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::char_flag"))]
+    pub close_deal_type: u8,
+    // 交易币种类型
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
+    pub trade_currency_id: CurrencyIdType,
+    // 保证金币种类型
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
+    pub margin_currency_id: CurrencyIdType,
 }
 
-impl Default for QryExchangeField {
+impl Default for ProductField {
     fn default() -> Self {
         unsafe { std::mem::zeroed() }
     }
 }
 
-impl QryExchangeField {
-    pub fn new() -> Self {
-        Self::default()
-    }
-
-    pub fn with_exchange_id(mut self, exchange_id: &str) -> CtpResult<Self> {
-        self.exchange_id = ExchangeIdType::from_utf8_string(exchange_id)?;
-        Ok(self)
-    }
-}
+// 第三阶段请求和响应字段定义
 
-// 查询产品字段
+// 执行宣告录入字段
 #[repr(C)]
 #[derive(Debug, Clone)]
-pub struct QryProductField {
-    // 产品代码
-    pub product_id: InstrumentIdType,
-    // 产品类型
-    pub product_class: u8,
-    // 交易所代码
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(default)
+)]
+pub struct InputExecOrderField {
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
+    pub broker_id: BrokerIdType,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
+    pub investor_id: InvestorIdType,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
+    pub instrument_id: InstrumentIdType,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
+    pub exec_order_ref: OrderRefType,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
+    pub user_id: UserIdType,
+    pub volume: i32,
+    pub request_id: i32,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
+    pub business_unit: BusinessUnitType,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::char_flag"))]
+    pub offset_flag: u8,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::char_flag"))]
+    pub hedge_flag: u8,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::char_flag"))]
+    pub action_type: u8,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::char_flag"))]
+    pub posidir: u8,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::char_flag"))]
+    pub reserve_position_flag: u8,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::char_flag"))]
+    pub close_flag: u8,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
     pub exchange_id: ExchangeIdType,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
+    pub invest_unit_id: InvestUnitIdType,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
+    pub account_id: AccountIdType,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
+    pub currency_id: CurrencyIdType,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
+    pub client_id: ClientIdType,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
+    pub ip_address: [u8; 16],
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
+    pub mac_address: [u8; 21],
 }
 
-impl Default for QryProductField {
+impl Default for InputExecOrderField {
     fn default() -> Self {
         unsafe { std::mem::zeroed() }
     }
 }
 
-impl QryProductField {
-    pub fn new() -> Self {
-        Self::default()
+impl InputExecOrderField {
+    pub fn new(
+        broker_id: &str,
+        investor_id: &str,
+        instrument_id: &str,
+        exec_order_ref: &str,
+        user_id: &str,
+        volume: i32,
+        action_type: u8,
+    ) -> CtpResult<Self> {
+        let mut req = Self::default();
+        req.broker_id = BrokerIdType::from_utf8_string(broker_id)?;
+        req.investor_id = InvestorIdType::from_utf8_string(investor_id)?;
+        req.instrument_id = InstrumentIdType::from_utf8_string(instrument_id)?;
+        req.exec_order_ref = OrderRefType::from_utf8_string(exec_order_ref)?;
+        req.user_id = UserIdType::from_utf8_string(user_id)?;
+        req.volume = volume;
+        req.action_type = action_type;
+        Ok(req)
     }
+}
 
-    pub fn with_product_id(mut self, product_id: &str) -> CtpResult<Self> {
-        self.product_id = InstrumentIdType::from_utf8_string(product_id)?;
-        Ok(self)
-    }
+// 执行宣告操作字段
+#[repr(C)]
+#[derive(Debug, Clone)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(default)
+)]
+pub struct InputExecOrderActionField {
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
+    pub broker_id: BrokerIdType,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
+    pub investor_id: InvestorIdType,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
+    pub exec_order_action_ref: OrderRefType,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
+    pub exec_order_ref: OrderRefType,
+    pub request_id: i32,
+    pub front_id: i32,
+    pub session_id: i32,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
+    pub exchange_id: ExchangeIdType,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
+    pub exec_order_sys_id: OrderSysIdType,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::char_flag"))]
+    pub action_flag: u8,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
+    pub user_id: UserIdType,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
+    pub instrument_id: InstrumentIdType,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
+    pub invest_unit_id: InvestUnitIdType,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
+    pub ip_address: [u8; 16],
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
+    pub mac_address: [u8; 21],
+}
 
-    pub fn with_exchange_id(mut self, exchange_id: &str) -> CtpResult<Self> {
-        self.exchange_id = ExchangeIdType::from_utf8_string(exchange_id)?;
-        Ok(self)
+impl Default for InputExecOrderActionField {
+    fn default() -> Self {
+        unsafe { std::mem::zeroed() }
     }
+}
 
-    pub fn with_product_class(mut self, product_class: u8) -> Self {
-        self.product_class = product_class;
-        self
+impl InputExecOrderActionField {
+    pub fn new(
+        broker_id: &str,
+        investor_id: &str,
+        exec_order_action_ref: &str,
+        exec_order_ref: &str,
+        user_id: &str,
+        action_flag: u8,
+    ) -> CtpResult<Self> {
+        let mut req = Self::default();
+        req.broker_id = BrokerIdType::from_utf8_string(broker_id)?;
+        req.investor_id = InvestorIdType::from_utf8_string(investor_id)?;
+        req.exec_order_action_ref = OrderRefType::from_utf8_string(exec_order_action_ref)?;
+        req.exec_order_ref = OrderRefType::from_utf8_string(exec_order_ref)?;
+        req.user_id = UserIdType::from_utf8_string(user_id)?;
+        req.action_flag = action_flag;
+        Ok(req)
     }
 }
 
-// 结算信息确认字段
+// 询价录入字段
 #[repr(C)]
 #[derive(Debug, Clone)]
-pub struct SettlementInfoConfirmField {
-    // 经纪公司代码
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(default)
+)]
+pub struct InputForQuoteField {
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
     pub broker_id: BrokerIdType,
-    // 投资者代码
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
     pub investor_id: InvestorIdType,
-    // 确认日期
-    pub confirm_date: [u8; 9],
-    // 确认时间
-    pub confirm_time: [u8; 9],
-    // 结算编号
-    pub settlement_id: i32,
-    // 投资者账户代码
-    pub account_id: AccountIdType,
-    // 币种代码
-    pub currency_id: CurrencyIdType,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
+    pub instrument_id: InstrumentIdType,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
+    pub for_quote_ref: OrderRefType,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
+    pub user_id: UserIdType,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
+    pub exchange_id: ExchangeIdType,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
+    pub invest_unit_id: InvestUnitIdType,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
+    pub ip_address: [u8; 16],
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
+    pub mac_address: [u8; 21],
 }
 
-impl Default for SettlementInfoConfirmField {
+impl Default for InputForQuoteField {
     fn default() -> Self {
         unsafe { std::mem::zeroed() }
     }
 }
 
-impl SettlementInfoConfirmField {
-    pub fn new(broker_id: &str, investor_id: &str) -> CtpResult<Self> {
+impl InputForQuoteField {
+    pub fn new(
+        broker_id: &str,
+        investor_id: &str,
+        instrument_id: &str,
+        for_quote_ref: &str,
+        user_id: &str,
+    ) -> CtpResult<Self> {
         let mut req = Self::default();
         req.broker_id = BrokerIdType::from_utf8_string(broker_id)?;
         req.investor_id = InvestorIdType::from_utf8_string(investor_id)?;
+        req.instrument_id = InstrumentIdType::from_utf8_string(instrument_id)?;
+        req.for_quote_ref = OrderRefType::from_utf8_string(for_quote_ref)?;
+        req.user_id = UserIdType::from_utf8_string(user_id)?;
         Ok(req)
     }
-
-    pub fn with_account_id(mut self, account_id: &str) -> CtpResult<Self> {
-        self.account_id = AccountIdType::from_utf8_string(account_id)?;
-        Ok(self)
-    }
 }
 
-// 预埋单字段
+// 报价录入字段
 #[repr(C)]
 #[derive(Debug, Clone)]
-pub struct ParkedOrderField {
-    // 经纪公司代码
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(default)
+)]
+pub struct InputQuoteField {
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
     pub broker_id: BrokerIdType,
-    // 投资者代码
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
     pub investor_id: InvestorIdType,
-    // 合约代码
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
     pub instrument_id: InstrumentIdType,
-    // 报单引用
-    pub order_ref: OrderRefType,
-    // 用户代码
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
+    pub quote_ref: OrderRefType,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
     pub user_id: UserIdType,
-    // 报单价格条件
-    pub order_price_type: u8,
-    // 买卖方向
-    pub direction: u8,
-    // 组合开平标志
-    pub comb_offset_flag: [u8; 5],
-    // 组合投机套保标志
-    pub comb_hedge_flag: [u8; 5],
-    // 价格
-    pub limit_price: PriceType,
-    // 数量
-    pub volume_total_original: VolumeType,
-    // 有效期类型
-    pub time_condition: u8,
-    // GTD日期
-    pub gtd_date: [u8; 9],
-    // 成交量类型
-    pub volume_condition: u8,
-    // 最小成交量
-    pub min_volume: VolumeType,
-    // 触发条件
-    pub contingent_condition: u8,
-    // 止损价
-    pub stop_price: PriceType,
-    // 强平原因
-    pub force_close_reason: u8,
-    // 自动挂起标志
-    pub is_auto_suspend: i32,
-    // 业务单元
-    pub business_unit: [u8; 21],
-    // 请求编号
-    pub request_id: RequestIdType,
-    // 用户强平标志
-    pub user_force_close: i32,
-    // 交易所代码
+    pub ask_price: f64,
+    pub bid_price: f64,
+    pub ask_volume: i32,
+    pub bid_volume: i32,
+    pub request_id: i32,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
+    pub business_unit: BusinessUnitType,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::char_flag"))]
+    pub ask_offset_flag: u8,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::char_flag"))]
+    pub bid_offset_flag: u8,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::char_flag"))]
+    pub ask_hedge_flag: u8,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::char_flag"))]
+    pub bid_hedge_flag: u8,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
+    pub ask_order_ref: OrderRefType,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
+    pub bid_order_ref: OrderRefType,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
+    pub for_quote_sys_id: OrderSysIdType,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
     pub exchange_id: ExchangeIdType,
-    // 预埋报单编号
-    pub parked_order_id: [u8; 13],
-    // 用户类型
-    pub user_type: u8,
-    // 预埋单状态
-    pub status: u8,
-    // 错误代码
-    pub error_id: i32,
-    // 错误信息
-    pub error_msg: [u8; 81],
-    // 互换单标志
-    pub is_swap_order: i32,
-    // 投资单元代码
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
     pub invest_unit_id: InvestUnitIdType,
-    // 资金账号
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
     pub account_id: AccountIdType,
-    // 币种代码
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
     pub currency_id: CurrencyIdType,
-    // 客户代码
-    pub client_id: [u8; 11],
-    // IP地址
-    pub ip_address: IPAddressType,
-    // Mac地址
-    pub mac_address: MacAddressType,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
+    pub client_id: ClientIdType,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
+    pub ip_address: [u8; 16],
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
+    pub mac_address: [u8; 21],
 }
 
-impl Default for ParkedOrderField {
+impl Default for InputQuoteField {
     fn default() -> Self {
         unsafe { std::mem::zeroed() }
     }
 }
 
-impl ParkedOrderField {
+impl InputQuoteField {
     pub fn new(
         broker_id: &str,
         investor_id: &str,
         instrument_id: &str,
-        order_ref: &str,
-        direction: u8,
-        limit_price: f64,
-        volume: i32,
+        quote_ref: &str,
+        user_id: &str,
+        ask_price: f64,
+        bid_price: f64,
+        ask_volume: i32,
+        bid_volume: i32,
     ) -> CtpResult<Self> {
         let mut req = Self::default();
         req.broker_id = BrokerIdType::from_utf8_string(broker_id)?;
         req.investor_id = InvestorIdType::from_utf8_string(investor_id)?;
         req.instrument_id = InstrumentIdType::from_utf8_string(instrument_id)?;
-        req.order_ref = OrderRefType::from_utf8_string(order_ref)?;
-        req.direction = direction;
-        req.limit_price = limit_price;
-        req.volume_total_original = volume;
+        req.quote_ref = OrderRefType::from_utf8_string(quote_ref)?;
+        req.user_id = UserIdType::from_utf8_string(user_id)?;
+        req.ask_price = ask_price;
+        req.bid_price = bid_price;
+        req.ask_volume = ask_volume;
+        req.bid_volume = bid_volume;
         Ok(req)
     }
 }
 
-// 预埋单操作字段
+// 报价操作字段
 #[repr(C)]
 #[derive(Debug, Clone)]
-pub struct ParkedOrderActionField {
-    // 经纪公司代码
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(default)
+)]
+pub struct InputQuoteActionField {
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
     pub broker_id: BrokerIdType,
-    // 投资者代码
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
     pub investor_id: InvestorIdType,
-    // 报单操作引用
-    pub order_action_ref: OrderActionRefType,
-    // 报单引用
-    pub order_ref: OrderRefType,
-    // 请求编号
-    pub request_id: RequestIdType,
-    // 前置编号
-    pub front_id: FrontIdType,
-    // 会话编号
-    pub session_id: SessionIdType,
-    // 交易所代码
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
+    pub quote_action_ref: OrderRefType,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
+    pub quote_ref: OrderRefType,
+    pub request_id: i32,
+    pub front_id: i32,
+    pub session_id: i32,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
     pub exchange_id: ExchangeIdType,
-    // 报单编号
-    pub order_sys_id: OrderSysIdType,
-    // 操作标志
-    pub action_flag: ActionFlagType,
-    // 价格
-    pub limit_price: PriceType,
-    // 数量变化
-    pub volume_change: VolumeType,
-    // 用户代码
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
+    pub quote_sys_id: OrderSysIdType,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::char_flag"))]
+    pub action_flag: u8,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
     pub user_id: UserIdType,
-    // 合约代码
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
     pub instrument_id: InstrumentIdType,
-    // 预埋撤单单编号
-    pub parked_order_action_id: [u8; 13],
-    // 用户类型
-    pub user_type: u8,
-    // 预埋撤单状态
-    pub status: u8,
-    // 错误代码
-    pub error_id: i32,
-    // 错误信息
-    pub error_msg: [u8; 81],
-    // 投资单元代码
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
     pub invest_unit_id: InvestUnitIdType,
-    // IP地址
-    pub ip_address: IPAddressType,
-    // Mac地址
-    pub mac_address: MacAddressType,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
+    pub client_id: ClientIdType,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
+    pub ip_address: [u8; 16],
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
+    pub mac_address: [u8; 21],
 }
 
-impl Default for ParkedOrderActionField {
+impl Default for InputQuoteActionField {
     fn default() -> Self {
         unsafe { std::mem::zeroed() }
     }
 }
 
-impl ParkedOrderActionField {
+impl InputQuoteActionField {
     pub fn new(
         broker_id: &str,
         investor_id: &str,
+        quote_action_ref: &str,
+        quote_ref: &str,
+        user_id: &str,
         action_flag: u8,
-        order_ref: &str,
-        front_id: i32,
-        session_id: i32,
+    ) -> CtpResult<Self> {
+        let mut req = Self::default();
+        req.broker_id = BrokerIdType::from_utf8_string(broker_id)?;
+        req.investor_id = InvestorIdType::from_utf8_string(investor_id)?;
+        req.quote_action_ref = OrderRefType::from_utf8_string(quote_action_ref)?;
+        req.quote_ref = OrderRefType::from_utf8_string(quote_ref)?;
+        req.user_id = UserIdType::from_utf8_string(user_id)?;
+        req.action_flag = action_flag;
+        Ok(req)
+    }
+}
+
+// 批量报单操作字段
+#[repr(C)]
+#[derive(Debug, Clone)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(default)
+)]
+pub struct InputBatchOrderActionField {
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
+    pub broker_id: BrokerIdType,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
+    pub investor_id: InvestorIdType,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
+    pub order_action_ref: OrderRefType,
+    pub request_id: i32,
+    pub front_id: i32,
+    pub session_id: i32,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
+    pub exchange_id: ExchangeIdType,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
+    pub user_id: UserIdType,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
+    pub invest_unit_id: InvestUnitIdType,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
+    pub ip_address: [u8; 16],
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
+    pub mac_address: [u8; 21],
+}
+
+impl Default for InputBatchOrderActionField {
+    fn default() -> Self {
+        unsafe { std::mem::zeroed() }
+    }
+}
+
+impl InputBatchOrderActionField {
+    pub fn new(
+        broker_id: &str,
+        investor_id: &str,
+        order_action_ref: &str,
+        user_id: &str,
         exchange_id: &str,
-        order_sys_id: &str,
     ) -> CtpResult<Self> {
         let mut req = Self::default();
         req.broker_id = BrokerIdType::from_utf8_string(broker_id)?;
         req.investor_id = InvestorIdType::from_utf8_string(investor_id)?;
-        req.action_flag = action_flag;
-        req.order_ref = OrderRefType::from_utf8_string(order_ref)?;
-        req.front_id = front_id;
-        req.session_id = session_id;
+        req.order_action_ref = OrderRefType::from_utf8_string(order_action_ref)?;
+        req.user_id = UserIdType::from_utf8_string(user_id)?;
         req.exchange_id = ExchangeIdType::from_utf8_string(exchange_id)?;
-        req.order_sys_id = OrderSysIdType::from_utf8_string(order_sys_id)?;
         Ok(req)
     }
 }
 
-// 第二阶段响应字段定义
-
-// 合约保证金率字段
+// 删除预埋单字段
 #[repr(C)]
 #[derive(Debug, Clone)]
-pub struct InstrumentMarginRateField {
-    // 合约代码
-    pub instrument_id: InstrumentIdType,
-    // 投资者范围
-    pub investor_range: u8,
-    // 经纪公司代码
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(default)
+)]
+pub struct RemoveParkedOrderField {
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
     pub broker_id: BrokerIdType,
-    // 投资者代码
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
     pub investor_id: InvestorIdType,
-    // 投机套保标志
-    pub hedge_flag: u8,
-    // 多头保证金率
-    pub long_margin_ratio_by_money: f64,
-    // 多头保证金费
-    pub long_margin_ratio_by_volume: f64,
-    // 空头保证金率
-    pub short_margin_ratio_by_money: f64,
-    // 空头保证金费
-    pub short_margin_ratio_by_volume: f64,
-    // 是否相对交易所收取
-    pub is_relative: i32,
-    // 交易所代码
-    pub exchange_id: ExchangeIdType,
-    // 投资单元代码
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
+    pub parked_order_id: ParkedOrderIdType,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
     pub invest_unit_id: InvestUnitIdType,
 }
 
-impl Default for InstrumentMarginRateField {
+impl Default for RemoveParkedOrderField {
     fn default() -> Self {
         unsafe { std::mem::zeroed() }
     }
 }
 
-// 合约手续费率字段
+impl RemoveParkedOrderField {
+    pub fn new(broker_id: &str, investor_id: &str, parked_order_id: &str) -> CtpResult<Self> {
+        let mut req = Self::default();
+        req.broker_id = BrokerIdType::from_utf8_string(broker_id)?;
+        req.investor_id = InvestorIdType::from_utf8_string(investor_id)?;
+        req.parked_order_id = ParkedOrderIdType::from_utf8_string(parked_order_id)?;
+        Ok(req)
+    }
+}
+
+// 删除预埋撤单字段
 #[repr(C)]
 #[derive(Debug, Clone)]
-pub struct InstrumentCommissionRateField {
-    // 合约代码
-    pub instrument_id: InstrumentIdType,
-    // 投资者范围
-    pub investor_range: u8,
-    // 经纪公司代码
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(default)
+)]
+pub struct RemoveParkedOrderActionField {
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
     pub broker_id: BrokerIdType,
-    // 投资者代码
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
     pub investor_id: InvestorIdType,
-    // 开仓手续费率
-    pub open_ratio_by_money: f64,
-    // 开仓手续费
-    pub open_ratio_by_volume: f64,
-    // 平仓手续费率
-    pub close_ratio_by_money: f64,
-    // 平仓手续费
-    pub close_ratio_by_volume: f64,
-    // 平今手续费率
-    pub close_today_ratio_by_money: f64,
-    // 平今手续费
-    pub close_today_ratio_by_volume: f64,
-    // 交易所代码
-    pub exchange_id: ExchangeIdType,
-    // 业务类型
-    pub biz_type: u8,
-    // 投资单元代码
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
+    pub parked_order_action_id: ParkedOrderIdType,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
     pub invest_unit_id: InvestUnitIdType,
 }
 
-impl Default for InstrumentCommissionRateField {
+impl Default for RemoveParkedOrderActionField {
     fn default() -> Self {
         unsafe { std::mem::zeroed() }
     }
 }
 
-// 交易所字段
+impl RemoveParkedOrderActionField {
+    pub fn new(
+        broker_id: &str,
+        investor_id: &str,
+        parked_order_action_id: &str,
+    ) -> CtpResult<Self> {
+        let mut req = Self::default();
+        req.broker_id = BrokerIdType::from_utf8_string(broker_id)?;
+        req.investor_id = InvestorIdType::from_utf8_string(investor_id)?;
+        req.parked_order_action_id = ParkedOrderIdType::from_utf8_string(parked_order_action_id)?;
+        Ok(req)
+    }
+}
+
+// 查询最大报单数量字段
 #[repr(C)]
 #[derive(Debug, Clone)]
-pub struct ExchangeField {
-    // 交易所代码
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(default)
+)]
+pub struct QryMaxOrderVolumeField {
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
+    pub broker_id: BrokerIdType,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
+    pub investor_id: InvestorIdType,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
+    pub instrument_id: InstrumentIdType,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::char_flag"))]
+    pub direction: u8,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::char_flag"))]
+    pub offset_flag: u8,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::char_flag"))]
+    pub hedge_flag: u8,
+    pub max_volume: i32,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
     pub exchange_id: ExchangeIdType,
-    // 交易所名称
-    pub exchange_name: [u8; 61],
-    // 交易所属性
-    pub exchange_property: u8,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
+    pub invest_unit_id: InvestUnitIdType,
 }
 
-impl Default for ExchangeField {
+impl Default for QryMaxOrderVolumeField {
     fn default() -> Self {
         unsafe { std::mem::zeroed() }
     }
 }
 
-// 产品字段
+impl QryMaxOrderVolumeField {
+    pub fn new(
+        broker_id: &str,
+        investor_id: &str,
+        instrument_id: &str,
+        direction: u8,
+    ) -> CtpResult<Self> {
+        let mut req = Self::default();
+        req.broker_id = BrokerIdType::from_utf8_string(broker_id)?;
+        req.investor_id = InvestorIdType::from_utf8_string(investor_id)?;
+        req.instrument_id = InstrumentIdType::from_utf8_string(instrument_id)?;
+        req.direction = direction;
+        Ok(req)
+    }
+}
+
+// 查询行情字段
 #[repr(C)]
 #[derive(Debug, Clone)]
-pub struct ProductField {
-    // 产品代码
-    pub product_id: InstrumentIdType,
-    // 产品名称
-    pub product_name: [u8; 21],
-    // 交易所代码
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(default)
+)]
+pub struct QryDepthMarketDataField {
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
+    pub instrument_id: InstrumentIdType,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
     pub exchange_id: ExchangeIdType,
-    // 产品类型
-    pub product_class: u8,
-    // 合约数量乘数
-    pub volume_multiple: i32,
-    // 最小变动价位
-    pub price_tick: f64,
-    // 市价单最大下单量
-    pub max_market_order_volume: i32,
-    // 市价单最小下单量
-    pub min_market_order_volume: i32,
-    // 限价单最大下单量
-    pub max_limit_order_volume: i32,
-    // 限价单最小下单量
-    pub min_limit_order_volume: i32,
-    // 持仓类型
-    pub position_type: u8,
-    // 持仓日期类型
-    pub position_date_type: u8,
-    // 平仓处理类型
-    pub close_deal_type: u8,
-    // 交易币种类型
-    pub trade_currency_id: CurrencyIdType,
-    // 保证金币种类型
-    pub margin_currency_id: CurrencyIdType,
 }
 
-impl Default for ProductField {
+impl Default for QryDepthMarketDataField {
     fn default() -> Self {
         unsafe { std::mem::zeroed() }
     }
 }
 
-// 第三阶段请求和响应字段定义
+impl QryDepthMarketDataField {
+    pub fn new() -> Self {
+        Self::default()
+    }
 
-// 执行宣告录入字段
+    pub fn with_instrument_id(mut self, instrument_id: &str) -> CtpResult<Self> {
+        self.instrument_id = InstrumentIdType::from_utf8_string(instrument_id)?;
+        Ok(self)
+    }
+}
+
+// 查询投资者结算结果字段
 #[repr(C)]
 #[derive(Debug, Clone)]
-pub struct InputExecOrderField {
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(default)
+)]
+pub struct QrySettlementInfoField {
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
     pub broker_id: BrokerIdType,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
     pub investor_id: InvestorIdType,
-    pub instrument_id: InstrumentIdType,
-    pub exec_order_ref: OrderRefType,
-    pub user_id: UserIdType,
-    pub volume: i32,
-    pub request_id: i32,
-    pub business_unit: BusinessUnitType,
-    pub offset_flag: u8,
-    pub hedge_flag: u8,
-    pub action_type: u8,
-    pub posidir: u8,
-    pub reserve_position_flag: u8,
-    pub close_flag: u8,
-    pub exchange_id: ExchangeIdType,
-    pub invest_unit_id: InvestUnitIdType,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
+    pub trading_day: TradingDayType,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
     pub account_id: AccountIdType,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
     pub currency_id: CurrencyIdType,
-    pub client_id: ClientIdType,
-    pub ip_address: [u8; 16],
-    pub mac_address: [u8; 21],
 }
 
-impl Default for InputExecOrderField {
+impl Default for QrySettlementInfoField {
     fn default() -> Self {
         unsafe { std::mem::zeroed() }
     }
 }
 
-impl InputExecOrderField {
-    pub fn new(
-        broker_id: &str,
-        investor_id: &str,
-        instrument_id: &str,
-        exec_order_ref: &str,
-        user_id: &str,
-        volume: i32,
-        action_type: u8,
-    ) -> CtpResult<Self> {
+impl QrySettlementInfoField {
+    pub fn new(broker_id: &str, investor_id: &str) -> CtpResult<Self> {
         let mut req = Self::default();
         req.broker_id = BrokerIdType::from_utf8_string(broker_id)?;
         req.investor_id = InvestorIdType::from_utf8_string(investor_id)?;
-        req.instrument_id = InstrumentIdType::from_utf8_string(instrument_id)?;
-        req.exec_order_ref = OrderRefType::from_utf8_string(exec_order_ref)?;
-        req.user_id = UserIdType::from_utf8_string(user_id)?;
-        req.volume = volume;
-        req.action_type = action_type;
         Ok(req)
     }
 }
 
-// 执行宣告操作字段
+// 查询转帐银行字段
 #[repr(C)]
 #[derive(Debug, Clone)]
-pub struct InputExecOrderActionField {
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(default)
+)]
+pub struct QryTransferBankField {
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
+    pub bank_id: BankIdType,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
+    pub bank_brch_id: BankBrchIdType,
+}
+
+impl Default for QryTransferBankField {
+    fn default() -> Self {
+        unsafe { std::mem::zeroed() }
+    }
+}
+
+impl QryTransferBankField {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+// 查询投资者持仓明细字段
+#[repr(C)]
+#[derive(Debug, Clone)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(default)
+)]
+pub struct QryInvestorPositionDetailField {
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
     pub broker_id: BrokerIdType,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
     pub investor_id: InvestorIdType,
-    pub exec_order_action_ref: OrderRefType,
-    pub exec_order_ref: OrderRefType,
-    pub request_id: i32,
-    pub front_id: i32,
-    pub session_id: i32,
-    pub exchange_id: ExchangeIdType,
-    pub exec_order_sys_id: OrderSysIdType,
-    pub action_flag: u8,
-    pub user_id: UserIdType,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
     pub instrument_id: InstrumentIdType,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
+    pub exchange_id: ExchangeIdType,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
     pub invest_unit_id: InvestUnitIdType,
-    pub ip_address: [u8; 16],
-    pub mac_address: [u8; 21],
 }
 
-impl Default for InputExecOrderActionField {
+impl Default for QryInvestorPositionDetailField {
     fn default() -> Self {
         unsafe { std::mem::zeroed() }
     }
 }
 
-impl InputExecOrderActionField {
-    pub fn new(
-        broker_id: &str,
-        investor_id: &str,
-        exec_order_action_ref: &str,
-        exec_order_ref: &str,
-        user_id: &str,
-        action_flag: u8,
-    ) -> CtpResult<Self> {
+impl QryInvestorPositionDetailField {
+    pub fn new(broker_id: &str, investor_id: &str) -> CtpResult<Self> {
         let mut req = Self::default();
         req.broker_id = BrokerIdType::from_utf8_string(broker_id)?;
         req.investor_id = InvestorIdType::from_utf8_string(investor_id)?;
-        req.exec_order_action_ref = OrderRefType::from_utf8_string(exec_order_action_ref)?;
-        req.exec_order_ref = OrderRefType::from_utf8_string(exec_order_ref)?;
-        req.user_id = UserIdType::from_utf8_string(user_id)?;
-        req.action_flag = action_flag;
         Ok(req)
     }
 }
 
-// 询价录入字段
+// 查询客户通知字段
 #[repr(C)]
 #[derive(Debug, Clone)]
-pub struct InputForQuoteField {
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(default)
+)]
+pub struct QryNoticeField {
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
     pub broker_id: BrokerIdType,
-    pub investor_id: InvestorIdType,
-    pub instrument_id: InstrumentIdType,
-    pub for_quote_ref: OrderRefType,
-    pub user_id: UserIdType,
-    pub exchange_id: ExchangeIdType,
-    pub invest_unit_id: InvestUnitIdType,
-    pub ip_address: [u8; 16],
-    pub mac_address: [u8; 21],
 }
 
-impl Default for InputForQuoteField {
+impl Default for QryNoticeField {
     fn default() -> Self {
         unsafe { std::mem::zeroed() }
     }
 }
 
-impl InputForQuoteField {
-    pub fn new(
-        broker_id: &str,
-        investor_id: &str,
-        instrument_id: &str,
-        for_quote_ref: &str,
-        user_id: &str,
-    ) -> CtpResult<Self> {
+impl QryNoticeField {
+    pub fn new(broker_id: &str) -> CtpResult<Self> {
         let mut req = Self::default();
         req.broker_id = BrokerIdType::from_utf8_string(broker_id)?;
-        req.investor_id = InvestorIdType::from_utf8_string(investor_id)?;
-        req.instrument_id = InstrumentIdType::from_utf8_string(instrument_id)?;
-        req.for_quote_ref = OrderRefType::from_utf8_string(for_quote_ref)?;
-        req.user_id = UserIdType::from_utf8_string(user_id)?;
         Ok(req)
     }
 }
 
-// 报价录入字段
+// 第三阶段响应字段定义
+
+// 投资者结算结果字段
 #[repr(C)]
 #[derive(Debug, Clone)]
-pub struct InputQuoteField {
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(default)
+)]
+pub struct SettlementInfoField {
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
+    pub trading_day: TradingDayType,
+    pub settlement_id: i32,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
     pub broker_id: BrokerIdType,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
     pub investor_id: InvestorIdType,
-    pub instrument_id: InstrumentIdType,
-    pub quote_ref: OrderRefType,
-    pub user_id: UserIdType,
-    pub ask_price: f64,
-    pub bid_price: f64,
-    pub ask_volume: i32,
-    pub bid_volume: i32,
-    pub request_id: i32,
-    pub business_unit: BusinessUnitType,
-    pub ask_offset_flag: u8,
-    pub bid_offset_flag: u8,
-    pub ask_hedge_flag: u8,
-    pub bid_hedge_flag: u8,
-    pub ask_order_ref: OrderRefType,
-    pub bid_order_ref: OrderRefType,
-    pub for_quote_sys_id: OrderSysIdType,
-    pub exchange_id: ExchangeIdType,
-    pub invest_unit_id: InvestUnitIdType,
+    pub sequence_no: i32,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
+    pub content: [u8; 501],
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
     pub account_id: AccountIdType,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
     pub currency_id: CurrencyIdType,
-    pub client_id: ClientIdType,
-    pub ip_address: [u8; 16],
-    pub mac_address: [u8; 21],
 }
 
-impl Default for InputQuoteField {
+impl Default for SettlementInfoField {
     fn default() -> Self {
         unsafe { std::mem::zeroed() }
     }
 }
 
-impl InputQuoteField {
-    pub fn new(
-        broker_id: &str,
-        investor_id: &str,
-        instrument_id: &str,
-        quote_ref: &str,
-        user_id: &str,
-        ask_price: f64,
-        bid_price: f64,
-        ask_volume: i32,
-        bid_volume: i32,
-    ) -> CtpResult<Self> {
-        let mut req = Self::default();
-        req.broker_id = BrokerIdType::from_utf8_string(broker_id)?;
-        req.investor_id = InvestorIdType::from_utf8_string(investor_id)?;
-        req.instrument_id = InstrumentIdType::from_utf8_string(instrument_id)?;
-        req.quote_ref = OrderRefType::from_utf8_string(quote_ref)?;
-        req.user_id = UserIdType::from_utf8_string(user_id)?;
-        req.ask_price = ask_price;
-        req.bid_price = bid_price;
-        req.ask_volume = ask_volume;
-        req.bid_volume = bid_volume;
-        Ok(req)
+// 转帐银行字段
+#[repr(C)]
+#[derive(Debug, Clone)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(default)
+)]
+pub struct TransferBankField {
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
+    pub bank_id: BankIdType,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
+    pub bank_brch_id: BankBrchIdType,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
+    pub bank_name: BankNameType,
+    pub is_active: i32,
+}
+
+impl Default for TransferBankField {
+    fn default() -> Self {
+        unsafe { std::mem::zeroed() }
     }
 }
 
-// 报价操作字段
+// 投资者持仓明细字段
 #[repr(C)]
 #[derive(Debug, Clone)]
-pub struct InputQuoteActionField {
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(default)
+)]
+pub struct InvestorPositionDetailField {
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
+    pub instrument_id: InstrumentIdType,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
     pub broker_id: BrokerIdType,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
     pub investor_id: InvestorIdType,
-    pub quote_action_ref: OrderRefType,
-    pub quote_ref: OrderRefType,
-    pub request_id: i32,
-    pub front_id: i32,
-    pub session_id: i32,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::char_flag"))]
+    pub hedge_flag: u8,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::char_flag"))]
+    pub direction: u8,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
+    pub open_date: TradingDayType,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
+    pub trade_id: TradeIdType,
+    pub volume: i32,
+    pub open_price: f64,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
+    pub trading_day: TradingDayType,
+    pub settlement_id: i32,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::char_flag"))]
+    pub trade_type: u8,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
+    pub comb_instrument_id: InstrumentIdType,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
     pub exchange_id: ExchangeIdType,
-    pub quote_sys_id: OrderSysIdType,
-    pub action_flag: u8,
-    pub user_id: UserIdType,
-    pub instrument_id: InstrumentIdType,
+    pub close_profit_by_date: f64,
+    pub close_profit_by_trade: f64,
+    pub position_profit_by_date: f64,
+    pub position_profit_by_trade: f64,
+    pub margin: f64,
+    pub exch_margin: f64,
+    pub margin_rate_by_money: f64,
+    pub margin_rate_by_volume: f64,
+    pub last_settlement_price: f64,
+    pub settlement_price: f64,
+    pub close_volume: i32,
+    pub close_amount: f64,
+    pub time_first_volume: i32,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
     pub invest_unit_id: InvestUnitIdType,
-    pub client_id: ClientIdType,
-    pub ip_address: [u8; 16],
-    pub mac_address: [u8; 21],
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::char_flag"))]
+    pub spec_posidir: u8,
 }
 
-impl Default for InputQuoteActionField {
+impl Default for InvestorPositionDetailField {
     fn default() -> Self {
         unsafe { std::mem::zeroed() }
     }
 }
 
-impl InputQuoteActionField {
-    pub fn new(
-        broker_id: &str,
-        investor_id: &str,
-        quote_action_ref: &str,
-        quote_ref: &str,
-        user_id: &str,
-        action_flag: u8,
-    ) -> CtpResult<Self> {
-        let mut req = Self::default();
-        req.broker_id = BrokerIdType::from_utf8_string(broker_id)?;
-        req.investor_id = InvestorIdType::from_utf8_string(investor_id)?;
-        req.quote_action_ref = OrderRefType::from_utf8_string(quote_action_ref)?;
-        req.quote_ref = OrderRefType::from_utf8_string(quote_ref)?;
-        req.user_id = UserIdType::from_utf8_string(user_id)?;
-        req.action_flag = action_flag;
-        Ok(req)
+// 客户通知字段
+#[repr(C)]
+#[derive(Debug, Clone)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(default)
+)]
+pub struct NoticeField {
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
+    pub broker_id: BrokerIdType,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
+    pub content: [u8; 501],
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
+    pub url_link: [u8; 201],
+}
+
+impl Default for NoticeField {
+    fn default() -> Self {
+        unsafe { std::mem::zeroed() }
     }
 }
 
-// 批量报单操作字段
+// 银期转账字段定义
+
+// 密码明文核对标志
+const PWD_FLAG_BLANK_CHECK: u8 = b'1';
+// 转账交易状态：被冲正
+const TRANSFER_STATUS_REPEALED: u8 = b'1';
+// 查询银行余额业务功能码
+const TRADE_CODE_QUERY_BANK_BALANCE: &str = "204002";
+
+// 转帐请求字段
 #[repr(C)]
 #[derive(Debug, Clone)]
-pub struct InputBatchOrderActionField {
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(default)
+)]
+pub struct ReqTransferField {
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
+    pub trade_code: TradeCodeType,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
+    pub bank_id: BankIdType,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
+    pub bank_branch_id: BankBrchIdType,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
     pub broker_id: BrokerIdType,
-    pub investor_id: InvestorIdType,
-    pub order_action_ref: OrderRefType,
-    pub request_id: i32,
-    pub front_id: i32,
-    pub session_id: i32,
-    pub exchange_id: ExchangeIdType,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
+    pub broker_branch_id: FutureBranchIdType,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
+    pub trade_date: DateType,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
+    pub trade_time: TimeType,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
+    pub bank_serial: BankSerialType,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
+    pub trading_day: DateType,
+    pub plate_serial: i32,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::char_flag"))]
+    pub last_fragment: u8,
+    pub session_id: SessionIdType,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
+    pub customer_name: IndividualNameType,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::char_flag"))]
+    pub id_card_type: u8,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
+    pub identified_card_no: IdentifiedCardNoType,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::char_flag"))]
+    pub cust_type: u8,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
+    pub bank_account: BankAccountType,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
+    pub bank_password: PasswordType,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
+    pub account_id: AccountIdType,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
+    pub password: PasswordType,
+    pub install_id: InstallIdType,
+    pub future_serial: i32,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
     pub user_id: UserIdType,
-    pub invest_unit_id: InvestUnitIdType,
-    pub ip_address: [u8; 16],
-    pub mac_address: [u8; 21],
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::char_flag"))]
+    pub verify_cert_no_flag: u8,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
+    pub currency_id: CurrencyIdType,
+    pub trade_amount: f64,
+    pub future_fetch_amount: f64,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::char_flag"))]
+    pub fee_pay_flag: u8,
+    pub cust_fee: f64,
+    pub broker_fee: f64,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
+    pub message: AddInfoType,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
+    pub digest: DigestType,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::char_flag"))]
+    pub bank_acc_type: u8,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
+    pub device_id: DeviceIdType,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::char_flag"))]
+    pub bank_secu_acc_type: u8,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
+    pub broker_id_by_bank: BankCodingForFutureType,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
+    pub bank_secu_acc: BankAccountType,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::char_flag"))]
+    pub bank_pwd_flag: u8,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::char_flag"))]
+    pub secu_pwd_flag: u8,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
+    pub oper_no: OperNoType,
+    pub request_id: RequestIdType,
+    pub tid: i32,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::char_flag"))]
+    pub transfer_status: u8,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
+    pub long_customer_name: LongIndividualNameType,
 }
 
-impl Default for InputBatchOrderActionField {
+impl Default for ReqTransferField {
     fn default() -> Self {
         unsafe { std::mem::zeroed() }
     }
 }
 
-impl InputBatchOrderActionField {
+impl ReqTransferField {
+    /// 创建银行资金转期货请求
+    pub fn bank_to_future(
+        broker_id: &str,
+        account_id: &str,
+        bank_id: &str,
+        amount: f64,
+    ) -> CtpResult<Self> {
+        Self::new(
+            TransferDirection::BankToFuture,
+            broker_id,
+            account_id,
+            bank_id,
+            amount,
+        )
+    }
+
+    /// 创建期货资金转银行请求
+    pub fn future_to_bank(
+        broker_id: &str,
+        account_id: &str,
+        bank_id: &str,
+        amount: f64,
+    ) -> CtpResult<Self> {
+        Self::new(
+            TransferDirection::FutureToBank,
+            broker_id,
+            account_id,
+            bank_id,
+            amount,
+        )
+    }
+
+    /// 创建转帐请求
+    ///
+    /// 银行分支机构代码默认为"0000"，币种默认为人民币
     pub fn new(
+        direction: TransferDirection,
         broker_id: &str,
-        investor_id: &str,
-        order_action_ref: &str,
-        user_id: &str,
-        exchange_id: &str,
+        account_id: &str,
+        bank_id: &str,
+        amount: f64,
     ) -> CtpResult<Self> {
-        let mut req = Self::default();
-        req.broker_id = BrokerIdType::from_utf8_string(broker_id)?;
-        req.investor_id = InvestorIdType::from_utf8_string(investor_id)?;
-        req.order_action_ref = OrderRefType::from_utf8_string(order_action_ref)?;
-        req.user_id = UserIdType::from_utf8_string(user_id)?;
-        req.exchange_id = ExchangeIdType::from_utf8_string(exchange_id)?;
-        Ok(req)
+        if !amount.is_finite() || amount <= 0.0 {
+            return Err(CtpError::InvalidParameterError(format!(
+                "转帐金额必须为正数: {}",
+                amount
+            )));
+        }
+
+        Ok(Self {
+            trade_code: TradeCodeType::from_utf8_string(direction.trade_code())?,
+            broker_id: BrokerIdType::from_utf8_string(broker_id)?,
+            account_id: AccountIdType::from_utf8_string(account_id)?,
+            bank_id: BankIdType::from_utf8_string(bank_id)?,
+            bank_branch_id: BankBrchIdType::from_utf8_string("0000")?,
+            currency_id: CurrencyIdType::from_utf8_string("CNY")?,
+            trade_amount: amount,
+            ..Default::default()
+        })
     }
-}
 
-// 删除预埋单字段
-#[repr(C)]
-#[derive(Debug, Clone)]
-pub struct RemoveParkedOrderField {
-    pub broker_id: BrokerIdType,
-    pub investor_id: InvestorIdType,
-    pub parked_order_id: ParkedOrderIdType,
-    pub invest_unit_id: InvestUnitIdType,
-}
+    /// 设置银行分支机构代码
+    pub fn with_bank_branch_id(mut self, bank_branch_id: &str) -> CtpResult<Self> {
+        self.bank_branch_id = BankBrchIdType::from_utf8_string(bank_branch_id)?;
+        Ok(self)
+    }
 
-impl Default for RemoveParkedOrderField {
-    fn default() -> Self {
-        unsafe { std::mem::zeroed() }
+    /// 设置银行帐号
+    pub fn with_bank_account(mut self, bank_account: &str) -> CtpResult<Self> {
+        self.bank_account = BankAccountType::from_utf8_string(bank_account)?;
+        Ok(self)
+    }
+
+    /// 设置银行密码，并要求明文核对
+    pub fn with_bank_password(mut self, bank_password: &str) -> CtpResult<Self> {
+        self.bank_password = PasswordType::from_utf8_string(bank_password)?;
+        self.bank_pwd_flag = PWD_FLAG_BLANK_CHECK;
+        Ok(self)
+    }
+
+    /// 设置期货资金密码，并要求明文核对
+    pub fn with_password(mut self, password: &str) -> CtpResult<Self> {
+        self.password = PasswordType::from_utf8_string(password)?;
+        self.secu_pwd_flag = PWD_FLAG_BLANK_CHECK;
+        Ok(self)
+    }
+
+    /// 设置币种
+    pub fn with_currency(mut self, currency_id: &str) -> CtpResult<Self> {
+        self.currency_id = CurrencyIdType::from_utf8_string(currency_id)?;
+        Ok(self)
     }
-}
 
-impl RemoveParkedOrderField {
-    pub fn new(broker_id: &str, investor_id: &str, parked_order_id: &str) -> CtpResult<Self> {
-        let mut req = Self::default();
-        req.broker_id = BrokerIdType::from_utf8_string(broker_id)?;
-        req.investor_id = InvestorIdType::from_utf8_string(investor_id)?;
-        req.parked_order_id = ParkedOrderIdType::from_utf8_string(parked_order_id)?;
-        Ok(req)
+    /// 设置用户代码
+    pub fn with_user_id(mut self, user_id: &str) -> CtpResult<Self> {
+        self.user_id = UserIdType::from_utf8_string(user_id)?;
+        Ok(self)
+    }
+
+    /// 转帐方向，业务功能码无法识别时返回`None`
+    pub fn direction(&self) -> Option<TransferDirection> {
+        TransferDirection::from_trade_code(self.trade_code)
     }
 }
 
-// 删除预埋撤单字段
+// 转帐响应字段
 #[repr(C)]
 #[derive(Debug, Clone)]
-pub struct RemoveParkedOrderActionField {
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(default)
+)]
+pub struct RspTransferField {
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
+    pub trade_code: TradeCodeType,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
+    pub bank_id: BankIdType,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
+    pub bank_branch_id: BankBrchIdType,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
     pub broker_id: BrokerIdType,
-    pub investor_id: InvestorIdType,
-    pub parked_order_action_id: ParkedOrderIdType,
-    pub invest_unit_id: InvestUnitIdType,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
+    pub broker_branch_id: FutureBranchIdType,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
+    pub trade_date: DateType,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
+    pub trade_time: TimeType,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
+    pub bank_serial: BankSerialType,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
+    pub trading_day: DateType,
+    pub plate_serial: i32,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::char_flag"))]
+    pub last_fragment: u8,
+    pub session_id: SessionIdType,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
+    pub customer_name: IndividualNameType,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::char_flag"))]
+    pub id_card_type: u8,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
+    pub identified_card_no: IdentifiedCardNoType,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::char_flag"))]
+    pub cust_type: u8,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
+    pub bank_account: BankAccountType,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
+    pub bank_password: PasswordType,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
+    pub account_id: AccountIdType,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
+    pub password: PasswordType,
+    pub install_id: InstallIdType,
+    pub future_serial: i32,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
+    pub user_id: UserIdType,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::char_flag"))]
+    pub verify_cert_no_flag: u8,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
+    pub currency_id: CurrencyIdType,
+    pub trade_amount: f64,
+    pub future_fetch_amount: f64,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::char_flag"))]
+    pub fee_pay_flag: u8,
+    pub cust_fee: f64,
+    pub broker_fee: f64,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
+    pub message: AddInfoType,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
+    pub digest: DigestType,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::char_flag"))]
+    pub bank_acc_type: u8,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
+    pub device_id: DeviceIdType,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::char_flag"))]
+    pub bank_secu_acc_type: u8,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
+    pub broker_id_by_bank: BankCodingForFutureType,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
+    pub bank_secu_acc: BankAccountType,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::char_flag"))]
+    pub bank_pwd_flag: u8,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::char_flag"))]
+    pub secu_pwd_flag: u8,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
+    pub oper_no: OperNoType,
+    pub request_id: RequestIdType,
+    pub tid: i32,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::char_flag"))]
+    pub transfer_status: u8,
+    pub error_id: i32,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
+    pub error_msg: ErrorMsgType,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
+    pub long_customer_name: LongIndividualNameType,
 }
 
-impl Default for RemoveParkedOrderActionField {
+impl Default for RspTransferField {
     fn default() -> Self {
         unsafe { std::mem::zeroed() }
     }
 }
 
-impl RemoveParkedOrderActionField {
-    pub fn new(
-        broker_id: &str,
-        investor_id: &str,
-        parked_order_action_id: &str,
-    ) -> CtpResult<Self> {
-        let mut req = Self::default();
-        req.broker_id = BrokerIdType::from_utf8_string(broker_id)?;
-        req.investor_id = InvestorIdType::from_utf8_string(investor_id)?;
-        req.parked_order_action_id = ParkedOrderIdType::from_utf8_string(parked_order_action_id)?;
-        Ok(req)
+impl RspTransferField {
+    /// 转帐方向，业务功能码无法识别时返回`None`
+    pub fn direction(&self) -> Option<TransferDirection> {
+        TransferDirection::from_trade_code(self.trade_code)
+    }
+
+    /// 是否已被冲正
+    pub fn is_repealed(&self) -> bool {
+        self.transfer_status == TRANSFER_STATUS_REPEALED
     }
 }
 
-// 查询最大报单数量字段
+// 查询银行余额请求字段
 #[repr(C)]
 #[derive(Debug, Clone)]
-pub struct QryMaxOrderVolumeField {
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(default)
+)]
+pub struct ReqQueryAccountField {
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
+    pub trade_code: TradeCodeType,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
+    pub bank_id: BankIdType,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
+    pub bank_branch_id: BankBrchIdType,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
     pub broker_id: BrokerIdType,
-    pub investor_id: InvestorIdType,
-    pub instrument_id: InstrumentIdType,
-    pub direction: u8,
-    pub offset_flag: u8,
-    pub hedge_flag: u8,
-    pub max_volume: i32,
-    pub exchange_id: ExchangeIdType,
-    pub invest_unit_id: InvestUnitIdType,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
+    pub broker_branch_id: FutureBranchIdType,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
+    pub trade_date: DateType,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
+    pub trade_time: TimeType,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
+    pub bank_serial: BankSerialType,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
+    pub trading_day: DateType,
+    pub plate_serial: i32,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::char_flag"))]
+    pub last_fragment: u8,
+    pub session_id: SessionIdType,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
+    pub customer_name: IndividualNameType,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::char_flag"))]
+    pub id_card_type: u8,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
+    pub identified_card_no: IdentifiedCardNoType,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::char_flag"))]
+    pub cust_type: u8,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
+    pub bank_account: BankAccountType,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
+    pub bank_password: PasswordType,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
+    pub account_id: AccountIdType,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
+    pub password: PasswordType,
+    pub future_serial: i32,
+    pub install_id: InstallIdType,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
+    pub user_id: UserIdType,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::char_flag"))]
+    pub verify_cert_no_flag: u8,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
+    pub currency_id: CurrencyIdType,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
+    pub digest: DigestType,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::char_flag"))]
+    pub bank_acc_type: u8,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
+    pub device_id: DeviceIdType,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::char_flag"))]
+    pub bank_secu_acc_type: u8,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
+    pub broker_id_by_bank: BankCodingForFutureType,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
+    pub bank_secu_acc: BankAccountType,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::char_flag"))]
+    pub bank_pwd_flag: u8,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::char_flag"))]
+    pub secu_pwd_flag: u8,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
+    pub oper_no: OperNoType,
+    pub request_id: RequestIdType,
+    pub tid: i32,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
+    pub long_customer_name: LongIndividualNameType,
 }
 
-impl Default for QryMaxOrderVolumeField {
+impl Default for ReqQueryAccountField {
     fn default() -> Self {
         unsafe { std::mem::zeroed() }
     }
 }
 
-impl QryMaxOrderVolumeField {
-    pub fn new(
-        broker_id: &str,
-        investor_id: &str,
-        instrument_id: &str,
-        direction: u8,
-    ) -> CtpResult<Self> {
-        let mut req = Self::default();
-        req.broker_id = BrokerIdType::from_utf8_string(broker_id)?;
-        req.investor_id = InvestorIdType::from_utf8_string(investor_id)?;
-        req.instrument_id = InstrumentIdType::from_utf8_string(instrument_id)?;
-        req.direction = direction;
-        Ok(req)
+impl ReqQueryAccountField {
+    /// 创建查询银行余额请求
+    ///
+    /// 银行分支机构代码默认为"0000"，币种默认为人民币
+    pub fn new(broker_id: &str, account_id: &str, bank_id: &str) -> CtpResult<Self> {
+        Ok(Self {
+            trade_code: TradeCodeType::from_utf8_string(TRADE_CODE_QUERY_BANK_BALANCE)?,
+            broker_id: BrokerIdType::from_utf8_string(broker_id)?,
+            account_id: AccountIdType::from_utf8_string(account_id)?,
+            bank_id: BankIdType::from_utf8_string(bank_id)?,
+            bank_branch_id: BankBrchIdType::from_utf8_string("0000")?,
+            currency_id: CurrencyIdType::from_utf8_string("CNY")?,
+            ..Default::default()
+        })
     }
-}
 
-// 查询行情字段
-#[repr(C)]
-#[derive(Debug, Clone)]
-pub struct QryDepthMarketDataField {
-    pub instrument_id: InstrumentIdType,
-    pub exchange_id: ExchangeIdType,
-}
+    /// 设置银行分支机构代码
+    pub fn with_bank_branch_id(mut self, bank_branch_id: &str) -> CtpResult<Self> {
+        self.bank_branch_id = BankBrchIdType::from_utf8_string(bank_branch_id)?;
+        Ok(self)
+    }
 
-impl Default for QryDepthMarketDataField {
-    fn default() -> Self {
-        unsafe { std::mem::zeroed() }
+    /// 设置银行帐号
+    pub fn with_bank_account(mut self, bank_account: &str) -> CtpResult<Self> {
+        self.bank_account = BankAccountType::from_utf8_string(bank_account)?;
+        Ok(self)
     }
-}
 
-impl QryDepthMarketDataField {
-    pub fn new() -> Self {
-        Self::default()
+    /// 设置银行密码，并要求明文核对
+    pub fn with_bank_password(mut self, bank_password: &str) -> CtpResult<Self> {
+        self.bank_password = PasswordType::from_utf8_string(bank_password)?;
+        self.bank_pwd_flag = PWD_FLAG_BLANK_CHECK;
+        Ok(self)
     }
 
-    pub fn with_instrument_id(mut self, instrument_id: &str) -> CtpResult<Self> {
-        self.instrument_id = InstrumentIdType::from_utf8_string(instrument_id)?;
+    /// 设置期货资金密码，并要求明文核对
+    pub fn with_password(mut self, password: &str) -> CtpResult<Self> {
+        self.password = PasswordType::from_utf8_string(password)?;
+        self.secu_pwd_flag = PWD_FLAG_BLANK_CHECK;
+        Ok(self)
+    }
+
+    /// 设置币种
+    pub fn with_currency(mut self, currency_id: &str) -> CtpResult<Self> {
+        self.currency_id = CurrencyIdType::from_utf8_string(currency_id)?;
         Ok(self)
     }
 }
 
-// 查询投资者结算结果字段
+// 查询银行余额通知字段
 #[repr(C)]
 #[derive(Debug, Clone)]
-pub struct QrySettlementInfoField {
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(default)
+)]
+pub struct NotifyQueryAccountField {
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
+    pub trade_code: TradeCodeType,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
+    pub bank_id: BankIdType,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
+    pub bank_branch_id: BankBrchIdType,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
     pub broker_id: BrokerIdType,
-    pub investor_id: InvestorIdType,
-    pub trading_day: TradingDayType,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
+    pub broker_branch_id: FutureBranchIdType,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
+    pub trade_date: DateType,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
+    pub trade_time: TimeType,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
+    pub bank_serial: BankSerialType,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
+    pub trading_day: DateType,
+    pub plate_serial: i32,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::char_flag"))]
+    pub last_fragment: u8,
+    pub session_id: SessionIdType,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
+    pub customer_name: IndividualNameType,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::char_flag"))]
+    pub id_card_type: u8,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
+    pub identified_card_no: IdentifiedCardNoType,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::char_flag"))]
+    pub cust_type: u8,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
+    pub bank_account: BankAccountType,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
+    pub bank_password: PasswordType,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
     pub account_id: AccountIdType,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
+    pub password: PasswordType,
+    pub future_serial: i32,
+    pub install_id: InstallIdType,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
+    pub user_id: UserIdType,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::char_flag"))]
+    pub verify_cert_no_flag: u8,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
     pub currency_id: CurrencyIdType,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
+    pub digest: DigestType,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::char_flag"))]
+    pub bank_acc_type: u8,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
+    pub device_id: DeviceIdType,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::char_flag"))]
+    pub bank_secu_acc_type: u8,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
+    pub broker_id_by_bank: BankCodingForFutureType,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
+    pub bank_secu_acc: BankAccountType,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::char_flag"))]
+    pub bank_pwd_flag: u8,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::char_flag"))]
+    pub secu_pwd_flag: u8,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
+    pub oper_no: OperNoType,
+    pub request_id: RequestIdType,
+    pub tid: i32,
+    pub bank_use_amount: f64,
+    pub bank_fetch_amount: f64,
+    pub error_id: i32,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
+    pub error_msg: ErrorMsgType,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
+    pub long_customer_name: LongIndividualNameType,
 }
 
-impl Default for QrySettlementInfoField {
+impl Default for NotifyQueryAccountField {
     fn default() -> Self {
         unsafe { std::mem::zeroed() }
     }
 }
 
-impl QrySettlementInfoField {
-    pub fn new(broker_id: &str, investor_id: &str) -> CtpResult<Self> {
-        let mut req = Self::default();
-        req.broker_id = BrokerIdType::from_utf8_string(broker_id)?;
-        req.investor_id = InvestorIdType::from_utf8_string(investor_id)?;
-        Ok(req)
-    }
-}
-
-// 查询转帐银行字段
+// 冲正响应字段
 #[repr(C)]
 #[derive(Debug, Clone)]
-pub struct QryTransferBankField {
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(default)
+)]
+pub struct RspRepealField {
+    pub repeal_time_interval: i32,
+    pub repealed_times: i32,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::char_flag"))]
+    pub bank_repeal_flag: u8,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::char_flag"))]
+    pub broker_repeal_flag: u8,
+    pub plate_repeal_serial: i32,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
+    pub bank_repeal_serial: BankSerialType,
+    pub future_repeal_serial: i32,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
+    pub trade_code: TradeCodeType,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
     pub bank_id: BankIdType,
-    pub bank_brch_id: BankBrchIdType,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
+    pub bank_branch_id: BankBrchIdType,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
+    pub broker_id: BrokerIdType,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
+    pub broker_branch_id: FutureBranchIdType,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
+    pub trade_date: DateType,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
+    pub trade_time: TimeType,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
+    pub bank_serial: BankSerialType,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
+    pub trading_day: DateType,
+    pub plate_serial: i32,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::char_flag"))]
+    pub last_fragment: u8,
+    pub session_id: SessionIdType,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
+    pub customer_name: IndividualNameType,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::char_flag"))]
+    pub id_card_type: u8,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
+    pub identified_card_no: IdentifiedCardNoType,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::char_flag"))]
+    pub cust_type: u8,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
+    pub bank_account: BankAccountType,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
+    pub bank_password: PasswordType,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
+    pub account_id: AccountIdType,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
+    pub password: PasswordType,
+    pub install_id: InstallIdType,
+    pub future_serial: i32,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
+    pub user_id: UserIdType,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::char_flag"))]
+    pub verify_cert_no_flag: u8,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
+    pub currency_id: CurrencyIdType,
+    pub trade_amount: f64,
+    pub future_fetch_amount: f64,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::char_flag"))]
+    pub fee_pay_flag: u8,
+    pub cust_fee: f64,
+    pub broker_fee: f64,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
+    pub message: AddInfoType,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
+    pub digest: DigestType,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::char_flag"))]
+    pub bank_acc_type: u8,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
+    pub device_id: DeviceIdType,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::char_flag"))]
+    pub bank_secu_acc_type: u8,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
+    pub broker_id_by_bank: BankCodingForFutureType,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
+    pub bank_secu_acc: BankAccountType,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::char_flag"))]
+    pub bank_pwd_flag: u8,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::char_flag"))]
+    pub secu_pwd_flag: u8,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
+    pub oper_no: OperNoType,
+    pub request_id: RequestIdType,
+    pub tid: i32,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::char_flag"))]
+    pub transfer_status: u8,
+    pub error_id: i32,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
+    pub error_msg: ErrorMsgType,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
+    pub long_customer_name: LongIndividualNameType,
 }
 
-impl Default for QryTransferBankField {
+impl Default for RspRepealField {
     fn default() -> Self {
         unsafe { std::mem::zeroed() }
     }
 }
 
-impl QryTransferBankField {
-    pub fn new() -> Self {
-        Self::default()
-    }
-}
-
-// 查询投资者持仓明细字段
+// 查询转帐流水字段
 #[repr(C)]
 #[derive(Debug, Clone)]
-pub struct QryInvestorPositionDetailField {
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(default)
+)]
+pub struct QryTransferSerialField {
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
     pub broker_id: BrokerIdType,
-    pub investor_id: InvestorIdType,
-    pub instrument_id: InstrumentIdType,
-    pub exchange_id: ExchangeIdType,
-    pub invest_unit_id: InvestUnitIdType,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
+    pub account_id: AccountIdType,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
+    pub bank_id: BankIdType,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
+    pub currency_id: CurrencyIdType,
 }
 
-impl Default for QryInvestorPositionDetailField {
+impl Default for QryTransferSerialField {
     fn default() -> Self {
         unsafe { std::mem::zeroed() }
     }
 }
 
-impl QryInvestorPositionDetailField {
-    pub fn new(broker_id: &str, investor_id: &str) -> CtpResult<Self> {
-        let mut req = Self::default();
-        req.broker_id = BrokerIdType::from_utf8_string(broker_id)?;
-        req.investor_id = InvestorIdType::from_utf8_string(investor_id)?;
-        Ok(req)
+impl QryTransferSerialField {
+    pub fn new(broker_id: &str, account_id: &str) -> CtpResult<Self> {
+        Ok(Self {
+            broker_id: BrokerIdType::from_utf8_string(broker_id)?,
+            account_id: AccountIdType::from_utf8_string(account_id)?,
+            ..Default::default()
+        })
+    }
+
+    pub fn with_bank_id(mut self, bank_id: &str) -> CtpResult<Self> {
+        self.bank_id = BankIdType::from_utf8_string(bank_id)?;
+        Ok(self)
+    }
+
+    pub fn with_currency(mut self, currency_id: &str) -> CtpResult<Self> {
+        self.currency_id = CurrencyIdType::from_utf8_string(currency_id)?;
+        Ok(self)
     }
 }
 
-// 查询客户通知字段
+// 转帐流水字段
 #[repr(C)]
 #[derive(Debug, Clone)]
-pub struct QryNoticeField {
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(default)
+)]
+pub struct TransferSerialField {
+    pub plate_serial: i32,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
+    pub trade_date: DateType,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
+    pub trading_day: DateType,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
+    pub trade_time: TimeType,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
+    pub trade_code: TradeCodeType,
+    pub session_id: SessionIdType,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
+    pub bank_id: BankIdType,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
+    pub bank_branch_id: BankBrchIdType,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::char_flag"))]
+    pub bank_acc_type: u8,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
+    pub bank_account: BankAccountType,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
+    pub bank_serial: BankSerialType,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
     pub broker_id: BrokerIdType,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
+    pub broker_branch_id: FutureBranchIdType,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::char_flag"))]
+    pub future_acc_type: u8,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
+    pub account_id: AccountIdType,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
+    pub investor_id: InvestorIdType,
+    pub future_serial: i32,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::char_flag"))]
+    pub id_card_type: u8,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
+    pub identified_card_no: IdentifiedCardNoType,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
+    pub currency_id: CurrencyIdType,
+    pub trade_amount: f64,
+    pub cust_fee: f64,
+    pub broker_fee: f64,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::char_flag"))]
+    pub availability_flag: u8,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
+    pub operator_code: OperatorCodeType,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
+    pub bank_new_account: BankAccountType,
+    pub error_id: i32,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
+    pub error_msg: ErrorMsgType,
 }
 
-impl Default for QryNoticeField {
+impl Default for TransferSerialField {
     fn default() -> Self {
         unsafe { std::mem::zeroed() }
     }
 }
 
-impl QryNoticeField {
-    pub fn new(broker_id: &str) -> CtpResult<Self> {
-        let mut req = Self::default();
-        req.broker_id = BrokerIdType::from_utf8_string(broker_id)?;
-        Ok(req)
-    }
-}
-
-// 第三阶段响应字段定义
-
-// 投资者结算结果字段
+// 查询签约银行字段
 #[repr(C)]
 #[derive(Debug, Clone)]
-pub struct SettlementInfoField {
-    pub trading_day: TradingDayType,
-    pub settlement_id: i32,
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(default)
+)]
+pub struct QryContractBankField {
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
     pub broker_id: BrokerIdType,
-    pub investor_id: InvestorIdType,
-    pub sequence_no: i32,
-    pub content: [u8; 1001],
-    pub account_id: AccountIdType,
-    pub currency_id: CurrencyIdType,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
+    pub bank_id: BankIdType,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
+    pub bank_brch_id: BankBrchIdType,
 }
 
-impl Default for SettlementInfoField {
+impl Default for QryContractBankField {
     fn default() -> Self {
         unsafe { std::mem::zeroed() }
     }
 }
 
-// 转帐银行字段
+impl QryContractBankField {
+    pub fn new(broker_id: &str) -> CtpResult<Self> {
+        Ok(Self {
+            broker_id: BrokerIdType::from_utf8_string(broker_id)?,
+            ..Default::default()
+        })
+    }
+
+    pub fn with_bank_id(mut self, bank_id: &str) -> CtpResult<Self> {
+        self.bank_id = BankIdType::from_utf8_string(bank_id)?;
+        Ok(self)
+    }
+}
+
+// 签约银行字段
 #[repr(C)]
 #[derive(Debug, Clone)]
-pub struct TransferBankField {
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(default)
+)]
+pub struct ContractBankField {
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
+    pub broker_id: BrokerIdType,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
     pub bank_id: BankIdType,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
     pub bank_brch_id: BankBrchIdType,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
     pub bank_name: BankNameType,
-    pub is_active: i32,
 }
 
-impl Default for TransferBankField {
+impl Default for ContractBankField {
     fn default() -> Self {
         unsafe { std::mem::zeroed() }
     }
 }
 
-// 投资者持仓明细字段
+// 查询银期签约关系字段
 #[repr(C)]
 #[derive(Debug, Clone)]
-pub struct InvestorPositionDetailField {
-    pub instrument_id: InstrumentIdType,
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(default)
+)]
+pub struct QryAccountregisterField {
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
     pub broker_id: BrokerIdType,
-    pub investor_id: InvestorIdType,
-    pub hedge_flag: u8,
-    pub direction: u8,
-    pub open_date: TradingDayType,
-    pub trade_id: TradeIdType,
-    pub volume: i32,
-    pub open_price: f64,
-    pub trading_day: TradingDayType,
-    pub settlement_id: i32,
-    pub trade_type: u8,
-    pub comb_instrument_id: InstrumentIdType,
-    pub exchange_id: ExchangeIdType,
-    pub close_profit_by_date: f64,
-    pub close_profit_by_trade: f64,
-    pub position_profit_by_date: f64,
-    pub position_profit_by_trade: f64,
-    pub margin: f64,
-    pub exch_margin: f64,
-    pub margin_rate_by_money: f64,
-    pub margin_rate_by_volume: f64,
-    pub last_settlement_price: f64,
-    pub settlement_price: f64,
-    pub close_volume: i32,
-    pub close_amount: f64,
-    pub time_first_volume: i32,
-    pub invest_unit_id: InvestUnitIdType,
-    pub spec_posidir: u8,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
+    pub account_id: AccountIdType,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
+    pub bank_id: BankIdType,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
+    pub bank_branch_id: BankBrchIdType,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
+    pub currency_id: CurrencyIdType,
 }
 
-impl Default for InvestorPositionDetailField {
+impl Default for QryAccountregisterField {
     fn default() -> Self {
         unsafe { std::mem::zeroed() }
     }
 }
 
-// 客户通知字段
+impl QryAccountregisterField {
+    pub fn new(broker_id: &str, account_id: &str) -> CtpResult<Self> {
+        Ok(Self {
+            broker_id: BrokerIdType::from_utf8_string(broker_id)?,
+            account_id: AccountIdType::from_utf8_string(account_id)?,
+            ..Default::default()
+        })
+    }
+
+    pub fn with_bank_id(mut self, bank_id: &str) -> CtpResult<Self> {
+        self.bank_id = BankIdType::from_utf8_string(bank_id)?;
+        Ok(self)
+    }
+
+    pub fn with_currency(mut self, currency_id: &str) -> CtpResult<Self> {
+        self.currency_id = CurrencyIdType::from_utf8_string(currency_id)?;
+        Ok(self)
+    }
+}
+
+// 银期签约关系字段
 #[repr(C)]
 #[derive(Debug, Clone)]
-pub struct NoticeField {
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(default)
+)]
+pub struct AccountregisterField {
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
+    pub trade_day: DateType,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
+    pub bank_id: BankIdType,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
+    pub bank_branch_id: BankBrchIdType,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
+    pub bank_account: BankAccountType,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
     pub broker_id: BrokerIdType,
-    pub content: [u8; 501],
-    pub url_link: [u8; 201],
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
+    pub broker_branch_id: FutureBranchIdType,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
+    pub account_id: AccountIdType,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::char_flag"))]
+    pub id_card_type: u8,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
+    pub identified_card_no: IdentifiedCardNoType,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
+    pub customer_name: IndividualNameType,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
+    pub currency_id: CurrencyIdType,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::char_flag"))]
+    pub open_or_destroy: u8,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
+    pub reg_date: DateType,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
+    pub out_date: DateType,
+    pub tid: i32,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::char_flag"))]
+    pub cust_type: u8,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::char_flag"))]
+    pub bank_acc_type: u8,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::fixed_str"))]
+    pub long_customer_name: LongIndividualNameType,
 }
 
-impl Default for NoticeField {
+impl Default for AccountregisterField {
     fn default() -> Self {
         unsafe { std::mem::zeroed() }
     }
@@ -158,19 +158,18 @@ fn test_fixed_bytes_conversion() {
         "固定长度往返转换失败"
     );
 
-    // 测试超长字符串截断
+    // 超长字符串返回错误而不是截断
     let long_str = "这是一个很长的测试字符串，用来测试截断功能";
-    let truncated: [u8; 10] = GbkConverter::utf8_to_fixed_bytes(long_str).expect("截断转换失败");
-
-    let truncated_back = GbkConverter::fixed_bytes_to_utf8(&truncated).expect("截断回转失败");
-
-    // 截断后的字符串应该不等于原字符串
-    assert_ne!(
-        long_str,
-        truncated_back.trim_end_matches('\0'),
-        "截断功能验证失败"
+    assert!(
+        GbkConverter::utf8_to_fixed_bytes::<10>(long_str).is_err(),
+        "超长字符串应返回错误"
     );
 
+    // 编码后恰好N字节时没有空间留给结尾的空字节
+    assert!(GbkConverter::utf8_to_fixed_bytes::<4>("测试").is_err());
+    let fixed: [u8; 5] = GbkConverter::utf8_to_fixed_bytes("测试").expect("固定长度转换失败");
+    assert_eq!(fixed[4], 0);
+
     println!("固定长度字节数组转换测试通过");
 }
 